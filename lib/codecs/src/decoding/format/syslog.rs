@@ -3,7 +3,7 @@ use chrono::{DateTime, Datelike, Utc};
 use lookup::event_path;
 use serde::{Deserialize, Serialize};
 use smallvec::{smallvec, SmallVec};
-use std::collections::BTreeMap;
+use std::collections::{btree_map::Entry, BTreeMap};
 use syslog_loose::{IncompleteDate, Message, ProcId, Protocol};
 use value::{kind::Collection, Kind};
 use vector_core::config::LogNamespace;
@@ -22,7 +22,7 @@ pub struct SyslogDeserializerConfig;
 impl SyslogDeserializerConfig {
     /// Build the `SyslogDeserializer` from this configuration.
     pub const fn build(&self) -> SyslogDeserializer {
-        SyslogDeserializer
+        SyslogDeserializer::new(None, None)
     }
 
     /// Return the type of event build by this deserializer.
@@ -51,8 +51,11 @@ impl SyslogDeserializerConfig {
                     .optional_field("appname", Kind::bytes(), None)
                     .optional_field("msgid", Kind::bytes(), None)
                     .optional_field("procid", Kind::integer().or_bytes(), None)
-                    // "structured data" is placed at the root. It will always be a map of strings
-                    .unknown_fields(Kind::object(Collection::from_unknown(Kind::bytes())))
+                    // "structured data" is placed at the root. It will always be a map of strings, or of
+                    // arrays of strings for the repeated parameters.
+                    .unknown_fields(Kind::object(Collection::from_unknown(
+                        Kind::bytes().or_array(Collection::from_unknown(Kind::bytes())),
+                    )))
             }
             LogNamespace::Vector => {
                 schema::Definition::new_with_default_metadata(
@@ -68,8 +71,11 @@ impl SyslogDeserializerConfig {
                 .optional_field("appname", Kind::bytes(), None)
                 .optional_field("msgid", Kind::bytes(), None)
                 .optional_field("procid", Kind::integer().or_bytes(), None)
-                // "structured data" is placed at the root. It will always be a map strings
-                .unknown_fields(Kind::object(Collection::from_unknown(Kind::bytes())))
+                // "structured data" is placed at the root. It will always be a map strings, or of
+                // arrays of strings for the repeated parameters.
+                .unknown_fields(Kind::object(Collection::from_unknown(
+                    Kind::bytes().or_array(Collection::from_unknown(Kind::bytes())),
+                )))
             }
         }
     }
@@ -77,8 +83,25 @@ impl SyslogDeserializerConfig {
 
 /// Deserializer that builds an `Event` from a byte frame containing a syslog
 /// message.
-#[derive(Debug, Clone)]
-pub struct SyslogDeserializer;
+#[derive(Debug, Clone, Default)]
+pub struct SyslogDeserializer {
+    structured_data_key: Option<String>,
+    raw_message_key: Option<String>,
+}
+
+impl SyslogDeserializer {
+    /// Creates a new `SyslogDeserializer`.
+    ///
+    /// The elements of the structured data are nested under `structured_data_key` by SD-ID, or
+    /// inserted at the root of the event if it isn't set. The raw frame is kept in
+    /// `raw_message_key`, if set.
+    pub const fn new(structured_data_key: Option<String>, raw_message_key: Option<String>) -> Self {
+        Self {
+            structured_data_key,
+            raw_message_key,
+        }
+    }
+}
 
 impl Deserializer for SyslogDeserializer {
     fn parse(
@@ -91,7 +114,15 @@ impl Deserializer for SyslogDeserializer {
         let parsed = syslog_loose::parse_message_with_year_exact(line, resolve_year)?;
 
         let mut log = LogEvent::from(Value::Object(BTreeMap::new()));
-        insert_fields_from_syslog(&mut log, parsed, log_namespace);
+        insert_fields_from_syslog(
+            &mut log,
+            parsed,
+            log_namespace,
+            self.structured_data_key.as_deref(),
+        );
+        if let Some(raw_message_key) = &self.raw_message_key {
+            log.insert(raw_message_key.as_str(), bytes.clone());
+        }
 
         Ok(smallvec![Event::from(log)])
     }
@@ -117,6 +148,7 @@ fn insert_fields_from_syslog(
     log: &mut LogEvent,
     parsed: Message<&str>,
     log_namespace: LogNamespace,
+    structured_data_key: Option<&str>,
 ) {
     match log_namespace {
         LogNamespace::Legacy => {
@@ -164,12 +196,42 @@ fn insert_fields_from_syslog(
         log.insert(event_path!("procid"), value);
     }
 
+    let mut structured_data: BTreeMap<String, BTreeMap<String, Value>> = BTreeMap::new();
     for element in parsed.structured_data.into_iter() {
-        let mut sdata: BTreeMap<String, Value> = BTreeMap::new();
+        // Relays may repeat an SD-ID, in which case the parameters of all its elements are merged.
+        let sdata = structured_data.entry(element.id.to_string()).or_default();
         for (name, value) in element.params() {
-            sdata.insert(name.to_string(), value.into());
+            insert_param(sdata, name.to_string(), value.into());
+        }
+    }
+
+    match structured_data_key {
+        Some(key) => {
+            let structured_data = structured_data
+                .into_iter()
+                .map(|(id, sdata)| (id, Value::from(sdata)))
+                .collect::<BTreeMap<_, _>>();
+            log.insert(key, structured_data);
+        }
+        None => {
+            for (id, sdata) in structured_data {
+                log.insert(event_path!(id.as_str()), sdata);
+            }
+        }
+    }
+}
+
+/// A parameter may be repeated within an element, in which case all of its values are kept in an
+/// array, in order.
+fn insert_param(sdata: &mut BTreeMap<String, Value>, name: String, value: Value) {
+    match sdata.entry(name) {
+        Entry::Vacant(entry) => {
+            entry.insert(value);
         }
-        log.insert(event_path!(element.id), sdata);
+        Entry::Occupied(mut entry) => match entry.get_mut() {
+            Value::Array(values) => values.push(value),
+            first => *first = Value::Array(vec![std::mem::replace(first, Value::Null), value]),
+        },
     }
 }
 
@@ -194,7 +256,7 @@ mod tests {
 
         let input =
             Bytes::from("<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 - MSG");
-        let deserializer = SyslogDeserializer;
+        let deserializer = SyslogDeserializer::default();
 
         let events = deserializer.parse(input, LogNamespace::Legacy).unwrap();
         assert_eq!(events.len(), 1);
@@ -217,11 +279,51 @@ mod tests {
 
         let input =
             Bytes::from("<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 - MSG");
-        let deserializer = SyslogDeserializer;
+        let deserializer = SyslogDeserializer::default();
 
         let events = deserializer.parse(input, LogNamespace::Vector).unwrap();
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].as_log()["message"], "MSG".into());
         assert!(events[0].as_log()["timestamp"].is_timestamp());
     }
+
+    #[test]
+    fn merges_repeated_structured_data() {
+        let input = Bytes::from(
+            r#"<34>1 2003-10-11T22:14:15.003Z host su - ID47 [origin ip="10.0.0.1" ip="10.0.0.2"][meta a="1"][meta b="2"] MSG"#,
+        );
+        let deserializer = SyslogDeserializer::default();
+
+        let events = deserializer.parse(input, LogNamespace::Vector).unwrap();
+        let log = events[0].as_log();
+        assert_eq!(
+            log.get(event_path!("origin", "ip")),
+            Some(&Value::from(vec![
+                Value::from("10.0.0.1"),
+                Value::from("10.0.0.2")
+            ]))
+        );
+        assert_eq!(log.get(event_path!("meta", "a")), Some(&Value::from("1")));
+        assert_eq!(log.get(event_path!("meta", "b")), Some(&Value::from("2")));
+    }
+
+    #[test]
+    fn nests_structured_data_and_keeps_raw_frame() {
+        let frame =
+            r#"<34>1 2003-10-11T22:14:15.003Z host su - ID47 [exampleSDID@32473 iut="3"] MSG"#;
+        let deserializer =
+            SyslogDeserializer::new(Some("structured_data".to_owned()), Some("raw".to_owned()));
+
+        let events = deserializer
+            .parse(Bytes::from(frame), LogNamespace::Vector)
+            .unwrap();
+        let log = events[0].as_log();
+        assert_eq!(
+            log.get(event_path!("structured_data", "exampleSDID@32473", "iut")),
+            Some(&Value::from("3"))
+        );
+        assert_eq!(log.get(event_path!("exampleSDID@32473")), None);
+        assert_eq!(log.get(event_path!("raw")), Some(&Value::from(frame)));
+        assert_eq!(log.get(event_path!("message")), Some(&Value::from("MSG")));
+    }
 }
@@ -10,7 +10,7 @@ pub struct GrokPattern {
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct Destination {
     pub path: LookupBuf,
-    pub filter_fn: Option<Function>,
+    pub filter_fns: Vec<Function>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
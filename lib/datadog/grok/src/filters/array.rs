@@ -17,8 +17,33 @@ use crate::{
     parse_grok_rules::Error as GrokStaticError,
 };
 
+/// Defines what to do with array elements the value filter fails on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidElements {
+    /// fail the whole filter(default)
+    Fail,
+    /// drop invalid elements - `skipInvalid`
+    Skip,
+    /// keep invalid elements as they are - `keepInvalid`
+    Keep,
+}
+
 pub fn filter_from_function(f: &Function) -> Result<GrokFilter, GrokStaticError> {
-    let args = f.args.as_ref();
+    let mut args = f.args.as_deref();
+
+    // the lenient mode can only be specified as the last argument, e.g. array(",", integer, skipInvalid)
+    let mut invalid_elements = InvalidElements::Fail;
+    if let Some([rest @ .., FunctionArgument::Function(Function { name, args: None })]) = args {
+        let mode = match name.as_str() {
+            "skipInvalid" => Some(InvalidElements::Skip),
+            "keepInvalid" => Some(InvalidElements::Keep),
+            _ => None,
+        };
+        if let Some(mode) = mode {
+            invalid_elements = mode;
+            args = Some(rest);
+        }
+    }
     let args_len = args.map_or(0, |args| args.len());
 
     let mut delimiter = None;
@@ -80,10 +105,16 @@ pub fn filter_from_function(f: &Function) -> Result<GrokFilter, GrokStaticError>
         }
     };
 
+    if invalid_elements != InvalidElements::Fail && value_filter.is_none() {
+        // there is nothing to fail without a value filter
+        return Err(GrokStaticError::InvalidFunctionArguments(f.name.clone()));
+    }
+
     Ok(GrokFilter::Array(
         brackets.map(|(start, end)| (start.to_string(), end.to_string())),
         delimiter,
         Box::new(value_filter),
+        invalid_elements,
     ))
 }
 
@@ -120,7 +151,7 @@ fn parse_array<'a>(
             preceded(
                 tag(brackets.0),
                 terminated(
-                    separated_list0(tag(delimiter), parse_value(delimiter, brackets.1)),
+                    separated_list0(tag(delimiter), parse_value(delimiter, brackets)),
                     tag(brackets.1),
                 ),
             )(input)
@@ -137,15 +168,44 @@ fn parse_value_no_brackets<'a>(delimiter: &'a str) -> impl Fn(&'a str) -> SResul
     }
 }
 
+/// Takes an array element until the delimiter or the closing bracket, skipping over nested arrays
+/// enclosed in the same brackets, e.g. `[1,2]` in `[[1,2],[3,4]]`.
 fn parse_value<'a>(
     delimiter: &'a str,
-    close_bracket: &'a str,
+    brackets: (&'a str, &'a str),
 ) -> impl Fn(&'a str) -> SResult<Value> {
     move |input| {
-        map(
-            alt((take_until(delimiter), take_until(close_bracket))),
-            |value: &str| Value::Bytes(Bytes::copy_from_slice(value.as_bytes())),
-        )(input)
+        map(take_element(delimiter, brackets), |value: &str| {
+            Value::Bytes(Bytes::copy_from_slice(value.as_bytes()))
+        })(input)
+    }
+}
+
+fn take_element<'a>(
+    delimiter: &'a str,
+    (open_bracket, close_bracket): (&'a str, &'a str),
+) -> impl Fn(&'a str) -> SResult<&'a str> {
+    // nesting can't be detected if both brackets are the same
+    let nested = open_bracket != close_bracket;
+    move |input| {
+        let mut depth = 0;
+        let mut i = 0;
+        while i < input.len() {
+            let rest = &input[i..];
+            if nested && rest.starts_with(open_bracket) {
+                depth += 1;
+                i += open_bracket.len();
+            } else if depth > 0 && rest.starts_with(close_bracket) {
+                depth -= 1;
+                i += close_bracket.len();
+            } else if depth == 0 && (rest.starts_with(delimiter) || rest.starts_with(close_bracket))
+            {
+                return Ok((rest, &input[..i]));
+            } else {
+                i += rest.chars().next().map_or(1, char::len_utf8);
+            }
+        }
+        Err(nom::Err::Error((input, nom::error::ErrorKind::TakeUntil)))
     }
 }
 
@@ -171,6 +231,15 @@ mod tests {
         assert_eq!(result, vec!["\"1".into(), "2\"".into()]);
     }
 
+    #[test]
+    fn parses_nested_arrays() {
+        let result = parse("[[1,2],[3,[4,5]],6]", None, None).unwrap();
+        assert_eq!(result, vec!["[1,2]".into(), "[3,[4,5]]".into(), "6".into()]);
+
+        let result = parse("{{a|b}|{c}}", Some(("{", "}")), Some("|")).unwrap();
+        assert_eq!(result, vec!["{a|b}".into(), "{c}".into()]);
+    }
+
     #[test]
     fn parses_escaped_special_characters() {
         let result = parse("[1\r2]", None, Some("\r")).unwrap();
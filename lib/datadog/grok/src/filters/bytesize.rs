@@ -0,0 +1,117 @@
+use value::Value;
+
+use crate::{
+    ast::{Function, FunctionArgument},
+    grok_filter::GrokFilter,
+    parse_grok::Error as GrokRuntimeError,
+    parse_grok_rules::Error as GrokStaticError,
+};
+
+/// How decimal unit prefixes(`k`, `MB`, ...) are interpreted.
+/// Binary prefixes(`KiB`, `Mi`, ...) are always powers of 1024.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteUnits {
+    // 1kB = 1000 bytes
+    Si,
+    // 1kB = 1024 bytes
+    Binary,
+}
+
+pub fn filter_from_function(f: &Function) -> Result<GrokFilter, GrokStaticError> {
+    match f.args.as_deref() {
+        None => Ok(GrokFilter::ByteSize(ByteUnits::Si)),
+        Some([FunctionArgument::Arg(Value::Bytes(units))]) => match units.as_ref() {
+            b"si" => Ok(GrokFilter::ByteSize(ByteUnits::Si)),
+            b"binary" => Ok(GrokFilter::ByteSize(ByteUnits::Binary)),
+            _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+        },
+        _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+    }
+}
+
+/// Converts a human-readable size, e.g. `10MB`, `1.5GiB` or `512k`, to a number of bytes.
+pub fn apply_filter(value: &Value, units: ByteUnits) -> Result<Value, GrokRuntimeError> {
+    let error = || {
+        GrokRuntimeError::FailedToApplyFilter(
+            GrokFilter::ByteSize(units).to_string(),
+            value.to_string(),
+        )
+    };
+
+    match value {
+        Value::Integer(_) => Ok(value.clone()),
+        Value::Bytes(bytes) => {
+            let input = String::from_utf8_lossy(bytes);
+            let input = input.trim();
+            let split_at = input
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .unwrap_or(input.len());
+            let (number, unit) = input.split_at(split_at);
+            let number = number.parse::<f64>().map_err(|_e| error())?;
+            let multiplier = multiplier(unit.trim_start(), units).ok_or_else(error)?;
+
+            let size = (number * multiplier).round();
+            if size.is_finite() && size <= i64::MAX as f64 {
+                Ok(Value::Integer(size as i64))
+            } else {
+                Err(error())
+            }
+        }
+        _ => Err(error()),
+    }
+}
+
+fn multiplier(unit: &str, units: ByteUnits) -> Option<f64> {
+    let unit = unit.to_ascii_lowercase();
+    let unit = unit.strip_suffix('b').unwrap_or(&unit);
+    let (prefix, binary) = match unit.strip_suffix('i') {
+        Some(prefix) if !prefix.is_empty() => (prefix, true),
+        _ => (unit, units == ByteUnits::Binary),
+    };
+    let exponent = match prefix {
+        "" => 0,
+        "k" => 1,
+        "m" => 2,
+        "g" => 3,
+        "t" => 4,
+        "p" => 5,
+        "e" => 6,
+        _ => return None,
+    };
+    let base: f64 = if binary { 1024.0 } else { 1000.0 };
+    Some(base.powi(exponent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sizes() {
+        for (input, units, expected) in [
+            ("512", ByteUnits::Si, 512_i64),
+            ("10B", ByteUnits::Si, 10),
+            ("10MB", ByteUnits::Si, 10_000_000),
+            ("10MB", ByteUnits::Binary, 10_485_760),
+            ("1.5GiB", ByteUnits::Si, 1_610_612_736),
+            ("512k", ByteUnits::Si, 512_000),
+            ("512k", ByteUnits::Binary, 524_288),
+            ("2 Ki", ByteUnits::Si, 2048),
+            ("1tb", ByteUnits::Si, 1_000_000_000_000),
+        ] {
+            assert_eq!(
+                apply_filter(&Value::from(input), units),
+                Ok(Value::from(expected)),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn fails_on_invalid_sizes() {
+        for input in ["", "MB", "10XB", "-1kB", "1.2.3k"] {
+            assert!(apply_filter(&Value::from(input), ByteUnits::Si).is_err());
+        }
+    }
+}
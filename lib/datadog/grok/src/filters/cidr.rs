@@ -0,0 +1,126 @@
+use std::net::IpAddr;
+
+use cidr_utils::cidr::IpCidr;
+use value::Value;
+
+use crate::{
+    ast::{Function, FunctionArgument},
+    grok_filter::GrokFilter,
+    parse_grok::Error as GrokRuntimeError,
+    parse_grok_rules::Error as GrokStaticError,
+};
+
+#[derive(Debug, Clone)]
+pub struct CidrFilter {
+    pub networks: Vec<IpCidr>,
+    /// emit whether the address is in any of the networks instead of the matching network - `boolean`
+    pub as_boolean: bool,
+}
+
+/// Parses arguments of the cidr filter, e.g. `cidr("10.0.0.0/8", "192.168.0.0/16")`
+/// or `cidr("10.0.0.0/8", boolean)`.
+pub fn filter_from_function(f: &Function) -> Result<GrokFilter, GrokStaticError> {
+    let mut args = f.args.as_deref().unwrap_or_default();
+
+    let mut as_boolean = false;
+    if let [rest @ .., FunctionArgument::Function(Function { name, args: None })] = args {
+        if name == "boolean" {
+            as_boolean = true;
+            args = rest;
+        }
+    }
+    if args.is_empty() {
+        return Err(GrokStaticError::InvalidFunctionArguments(f.name.clone()));
+    }
+
+    let networks = args
+        .iter()
+        .map(|arg| match arg {
+            FunctionArgument::Arg(Value::Bytes(network)) => {
+                IpCidr::from_str(String::from_utf8_lossy(network))
+                    .map_err(|_e| GrokStaticError::InvalidFunctionArguments(f.name.clone()))
+            }
+            _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(GrokFilter::Cidr(CidrFilter {
+        networks,
+        as_boolean,
+    }))
+}
+
+/// Returns the first network containing the IP address(or whether there is any).
+pub fn apply_filter(value: &Value, filter: &CidrFilter) -> Result<Value, GrokRuntimeError> {
+    let error = || {
+        GrokRuntimeError::FailedToApplyFilter(
+            GrokFilter::Cidr(filter.clone()).to_string(),
+            value.to_string(),
+        )
+    };
+
+    let address = match value {
+        Value::Bytes(bytes) => String::from_utf8_lossy(bytes)
+            .parse::<IpAddr>()
+            .map_err(|_e| error())?,
+        _ => return Err(error()),
+    };
+
+    let network = filter
+        .networks
+        .iter()
+        .find(|network| network.contains(address));
+    match network {
+        _ if filter.as_boolean => Ok(network.is_some().into()),
+        Some(network) => Ok(format_network(network).into()),
+        None => Err(error()),
+    }
+}
+
+/// Formats the network in its canonical notation, e.g. `fd00::/8`, unlike `IpCidr`'s `Display`
+/// which doesn't compress IPv6 addresses.
+fn format_network(network: &IpCidr) -> String {
+    let bits = match network {
+        IpCidr::V4(cidr) => cidr.get_bits(),
+        IpCidr::V6(cidr) => cidr.get_bits(),
+    };
+    format!("{}/{}", network.first_as_ip_addr(), bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(networks: &[&str], as_boolean: bool) -> CidrFilter {
+        CidrFilter {
+            networks: networks
+                .iter()
+                .map(|network| IpCidr::from_str(network).unwrap())
+                .collect(),
+            as_boolean,
+        }
+    }
+
+    #[test]
+    fn finds_matching_networks() {
+        let networks = &["10.0.0.0/8", "192.168.0.0/16", "fd00::/8"];
+        assert_eq!(
+            apply_filter(&Value::from("192.168.1.1"), &filter(networks, false)),
+            Ok(Value::from("192.168.0.0/16"))
+        );
+        assert_eq!(
+            apply_filter(&Value::from("fd12::1"), &filter(networks, false)),
+            Ok(Value::from("fd00::/8"))
+        );
+        assert!(apply_filter(&Value::from("8.8.8.8"), &filter(networks, false)).is_err());
+        assert_eq!(
+            apply_filter(&Value::from("8.8.8.8"), &filter(networks, true)),
+            Ok(Value::from(false))
+        );
+        assert_eq!(
+            apply_filter(&Value::from("10.1.2.3"), &filter(networks, true)),
+            Ok(Value::from(true))
+        );
+        assert!(apply_filter(&Value::from("not an ip"), &filter(networks, true)).is_err());
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+use std::fmt::Formatter;
+
+use value::Value;
+
+use crate::{
+    ast::{Function, FunctionArgument},
+    grok_filter::GrokFilter,
+    parse_grok::Error as GrokRuntimeError,
+    parse_grok_rules::Error as GrokStaticError,
+};
+
+pub fn filter_from_function(f: &Function) -> Result<GrokFilter, GrokStaticError> {
+    let args = f.args.as_ref();
+    let args_len = args.map_or(0, |args| args.len());
+
+    if args_len == 0 || args_len > 3 {
+        return Err(GrokStaticError::InvalidFunctionArguments(f.name.clone()));
+    }
+    let args = args.unwrap();
+
+    let columns = match args[0] {
+        FunctionArgument::Arg(Value::Bytes(ref bytes)) => String::from_utf8_lossy(bytes)
+            .split(',')
+            .map(|column| column.trim().to_string())
+            .collect::<Vec<String>>(),
+        _ => return Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+    };
+    if columns.iter().any(|column| column.is_empty()) {
+        return Err(GrokStaticError::InvalidFunctionArguments(f.name.clone()));
+    }
+
+    let single_byte_arg = |arg: &FunctionArgument| match arg {
+        FunctionArgument::Arg(Value::Bytes(bytes)) if bytes.len() == 1 => Ok(bytes[0]),
+        _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+    };
+    let delimiter = if args_len > 1 {
+        single_byte_arg(&args[1])?
+    } else {
+        // default delimiter
+        b','
+    };
+    let quote = if args_len > 2 {
+        single_byte_arg(&args[2])?
+    } else {
+        // default quoting character
+        b'"'
+    };
+
+    Ok(GrokFilter::Csv(CsvFilter {
+        columns,
+        delimiter,
+        quote,
+    }))
+}
+
+#[derive(Debug, Clone)]
+pub struct CsvFilter {
+    pub columns: Vec<String>,
+    pub delimiter: u8,
+    pub quote: u8,
+}
+
+impl std::fmt::Display for CsvFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "csv(\"{}\", \"{}\", \"{}\")",
+            self.columns.join(","),
+            self.delimiter as char,
+            self.quote as char
+        )
+    }
+}
+
+/// Splits a delimited value into fields named after the filter columns.
+/// Extra values are ignored, missing and empty values are skipped, numbers are converted to integers or floats.
+pub fn apply_filter(value: &Value, filter: &CsvFilter) -> Result<Value, GrokRuntimeError> {
+    match value {
+        Value::Bytes(bytes) => {
+            let mut reader = csv::ReaderBuilder::new()
+                .has_headers(false)
+                .flexible(true)
+                .delimiter(filter.delimiter)
+                .quote(filter.quote)
+                .from_reader(bytes.as_ref());
+
+            let record = match reader.byte_records().next() {
+                Some(Ok(record)) => record,
+                Some(Err(_)) | None => {
+                    return Err(GrokRuntimeError::FailedToApplyFilter(
+                        filter.to_string(),
+                        value.to_string(),
+                    ))
+                }
+            };
+
+            let result = filter
+                .columns
+                .iter()
+                .zip(record.iter())
+                .filter(|(_, field)| !field.is_empty())
+                .map(|(column, field)| (column.clone(), parse_value(field)))
+                .collect::<BTreeMap<String, Value>>();
+            Ok(result.into())
+        }
+        _ => Err(GrokRuntimeError::FailedToApplyFilter(
+            filter.to_string(),
+            value.to_string(),
+        )),
+    }
+}
+
+fn parse_value(field: &[u8]) -> Value {
+    let s = String::from_utf8_lossy(field);
+    if let Ok(v) = s.parse::<i64>() {
+        Value::Integer(v)
+    } else {
+        match s.parse::<f64>() {
+            Ok(v) if v.is_finite() => Value::from_f64_or_zero(v),
+            _ => s.as_ref().into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::btreemap;
+
+    use super::*;
+
+    fn filter(columns: &str, delimiter: u8, quote: u8) -> CsvFilter {
+        CsvFilter {
+            columns: columns.split(',').map(|s| s.to_string()).collect(),
+            delimiter,
+            quote,
+        }
+    }
+
+    #[test]
+    fn parses_csv() {
+        let result = apply_filter(
+            &Value::from("John,Doe,120,Jefferson St.,Riverside"),
+            &filter("first_name,name,st_nb,st_name,city", b',', b'"'),
+        )
+        .unwrap();
+
+        assert_eq!(
+            result,
+            Value::from(btreemap! {
+                "first_name" => "John",
+                "name" => "Doe",
+                "st_nb" => 120,
+                "st_name" => "Jefferson St.",
+                "city" => "Riverside",
+            })
+        );
+    }
+
+    #[test]
+    fn handles_quotes_and_escapes() {
+        let result = apply_filter(
+            &Value::from(r#"'a;b';'it''s';1.5"#),
+            &filter("first,second,third", b';', b'\''),
+        )
+        .unwrap();
+
+        assert_eq!(
+            result,
+            Value::from(btreemap! {
+                "first" => "a;b",
+                "second" => "it's",
+                "third" => 1.5,
+            })
+        );
+    }
+
+    #[test]
+    fn skips_missing_and_empty_values() {
+        let result = apply_filter(
+            &Value::from("John,,Doe,extra"),
+            &filter("first_name,middle_name,name", b',', b'"'),
+        )
+        .unwrap();
+
+        assert_eq!(
+            result,
+            Value::from(btreemap! {
+                "first_name" => "John",
+                "name" => "Doe",
+            })
+        );
+    }
+}
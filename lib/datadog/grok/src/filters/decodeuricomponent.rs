@@ -0,0 +1,65 @@
+use std::borrow::Cow;
+
+use percent_encoding::percent_decode_str;
+use value::Value;
+
+use crate::{
+    ast::{Function, FunctionArgument},
+    grok_filter::GrokFilter,
+    parse_grok::Error as GrokRuntimeError,
+    parse_grok_rules::Error as GrokStaticError,
+};
+
+pub fn filter_from_function(f: &Function) -> Result<GrokFilter, GrokStaticError> {
+    match f.args.as_ref() {
+        None => Ok(GrokFilter::DecodeUriComponent(false)),
+        Some(args) if args.len() == 1 => match args[0] {
+            FunctionArgument::Arg(Value::Boolean(plus_as_space)) => {
+                Ok(GrokFilter::DecodeUriComponent(plus_as_space))
+            }
+            _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+        },
+        _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+    }
+}
+
+/// Percent-decodes a URI component, e.g. `%2Fservice%2Ftest` becomes `/service/test`.
+/// If `plus_as_space` is set, `+` is decoded as a space, as in `application/x-www-form-urlencoded` values.
+pub fn apply_filter(value: &Value, plus_as_space: bool) -> Result<Value, GrokRuntimeError> {
+    match value {
+        Value::Bytes(bytes) => {
+            Ok(decode(String::from_utf8_lossy(bytes).as_ref(), plus_as_space).into())
+        }
+        _ => Err(GrokRuntimeError::FailedToApplyFilter(
+            GrokFilter::DecodeUriComponent(plus_as_space).to_string(),
+            value.to_string(),
+        )),
+    }
+}
+
+pub fn decode(input: &str, plus_as_space: bool) -> Cow<'_, str> {
+    if plus_as_space && input.contains('+') {
+        let input = input.replace('+', " ");
+        Cow::Owned(percent_decode_str(&input).decode_utf8_lossy().into_owned())
+    } else {
+        percent_decode_str(input).decode_utf8_lossy()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_uri_component() {
+        assert_eq!(decode("%2Fservice%2Ftest", false), "/service/test");
+        assert_eq!(decode("J%C3%B6rg", false), "Jörg");
+        assert_eq!(decode("no%escape", false), "no%escape");
+    }
+
+    #[test]
+    fn decodes_plus_as_space() {
+        assert_eq!(decode("a+b%2Bc", false), "a+b+c");
+        assert_eq!(decode("a+b%2Bc", true), "a b+c");
+    }
+}
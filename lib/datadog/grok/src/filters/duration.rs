@@ -0,0 +1,111 @@
+use value::Value;
+
+use crate::{
+    ast::{Function, FunctionArgument},
+    grok_filter::GrokFilter,
+    parse_grok::Error as GrokRuntimeError,
+    parse_grok_rules::Error as GrokStaticError,
+};
+
+pub fn filter_from_function(f: &Function) -> Result<GrokFilter, GrokStaticError> {
+    match f.args.as_deref() {
+        None => Ok(GrokFilter::Duration(unit_in_nanos("ms").unwrap())),
+        Some([FunctionArgument::Arg(Value::Bytes(unit))]) => {
+            unit_in_nanos(&String::from_utf8_lossy(unit))
+                .map(GrokFilter::Duration)
+                .ok_or_else(|| GrokStaticError::InvalidFunctionArguments(f.name.clone()))
+        }
+        _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+    }
+}
+
+/// Returns the number of nanoseconds in a given unit.
+fn unit_in_nanos(unit: &str) -> Option<f64> {
+    match unit {
+        "ns" => Some(1.0),
+        "us" | "µs" | "μs" => Some(1e3),
+        "ms" => Some(1e6),
+        "s" => Some(1e9),
+        "m" => Some(60e9),
+        "h" => Some(3600e9),
+        "d" => Some(86400e9),
+        _ => None,
+    }
+}
+
+/// Converts a human-readable duration, e.g. `1h30m`, `250ms` or `3.5s`, to a number of
+/// target units(given in nanoseconds).
+pub fn apply_filter(value: &Value, target_unit: f64) -> Result<Value, GrokRuntimeError> {
+    let error = || {
+        GrokRuntimeError::FailedToApplyFilter(
+            GrokFilter::Duration(target_unit).to_string(),
+            value.to_string(),
+        )
+    };
+
+    match value {
+        Value::Bytes(bytes) => {
+            let input = String::from_utf8_lossy(bytes);
+            let mut rest = input.trim();
+            if rest.is_empty() {
+                return Err(error());
+            }
+
+            let mut nanos = 0.0;
+            while !rest.is_empty() {
+                let number_end = rest
+                    .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                    .ok_or_else(error)?;
+                let (number, tail) = rest.split_at(number_end);
+                let unit_end = tail
+                    .find(|c: char| c.is_ascii_digit() || c == '.')
+                    .unwrap_or(tail.len());
+                let (unit, tail) = tail.split_at(unit_end);
+
+                let number = number.parse::<f64>().map_err(|_e| error())?;
+                nanos += number * unit_in_nanos(unit.trim()).ok_or_else(error)?;
+                rest = tail.trim_start();
+            }
+
+            let v = nanos / target_unit;
+            if (v as i64) as f64 == v {
+                Ok(Value::Integer(v as i64))
+            } else {
+                Ok(Value::from_f64_or_zero(v))
+            }
+        }
+        _ => Err(error()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        for (input, unit, expected) in [
+            ("1h30m", "ms", Value::from(5_400_000)),
+            ("250ms", "ms", Value::from(250)),
+            ("3.5s", "ms", Value::from(3500)),
+            ("3.5s", "s", Value::from(3.5)),
+            ("1m 30s", "s", Value::from(90)),
+            ("12µs", "ns", Value::from(12_000)),
+            ("1d", "h", Value::from(24)),
+        ] {
+            assert_eq!(
+                apply_filter(&Value::from(input), unit_in_nanos(unit).unwrap()),
+                Ok(expected),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn fails_on_invalid_durations() {
+        for input in ["", "10", "ms", "10x", "1h-30m", "1..5s"] {
+            assert!(apply_filter(&Value::from(input), 1e6).is_err(), "{}", input);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use value::Value;
+
+use crate::{
+    ast::{Function, FunctionArgument},
+    grok_filter::GrokFilter,
+    parse_grok::Error as GrokRuntimeError,
+    parse_grok_rules::Error as GrokStaticError,
+};
+
+pub fn filter_from_function(f: &Function) -> Result<GrokFilter, GrokStaticError> {
+    match f.args.as_ref() {
+        Some(args) if args.len() == 1 => match args[0] {
+            FunctionArgument::Arg(Value::Bytes(ref bytes)) => {
+                Regex::new(&String::from_utf8_lossy(bytes))
+                    .map(GrokFilter::Extract)
+                    .map_err(|_e| GrokStaticError::InvalidFunctionArguments(f.name.clone()))
+            }
+            _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+        },
+        _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+    }
+}
+
+/// Applies a regex to the value and returns:
+/// - an object with all matched named groups, if the regex has named groups
+/// - the first capture group, if the regex has unnamed groups only
+/// - the whole match otherwise
+pub fn apply_filter(value: &Value, re: &Regex) -> Result<Value, GrokRuntimeError> {
+    let error = || {
+        GrokRuntimeError::FailedToApplyFilter(
+            GrokFilter::Extract(re.clone()).to_string(),
+            value.to_string(),
+        )
+    };
+
+    match value {
+        Value::Bytes(bytes) => {
+            let input = String::from_utf8_lossy(bytes);
+            let captures = re.captures(&input).ok_or_else(error)?;
+
+            if re.capture_names().flatten().next().is_some() {
+                let result = re
+                    .capture_names()
+                    .flatten()
+                    .filter_map(|name| {
+                        captures
+                            .name(name)
+                            .map(|m| (name.to_string(), Value::from(m.as_str())))
+                    })
+                    .collect::<BTreeMap<String, Value>>();
+                Ok(result.into())
+            } else {
+                captures
+                    .get(if re.captures_len() > 1 { 1 } else { 0 })
+                    .map(|m| m.as_str().into())
+                    .ok_or_else(error)
+            }
+        }
+        _ => Err(error()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::btreemap;
+
+    use super::*;
+
+    #[test]
+    fn extracts_first_group() {
+        let re = Regex::new(r"id=(\d+)").unwrap();
+        assert_eq!(
+            apply_filter(&Value::from("user id=42 name=bob"), &re).unwrap(),
+            Value::from("42")
+        );
+    }
+
+    #[test]
+    fn extracts_whole_match_without_groups() {
+        let re = Regex::new(r"\d+").unwrap();
+        assert_eq!(
+            apply_filter(&Value::from("abc 123 def"), &re).unwrap(),
+            Value::from("123")
+        );
+    }
+
+    #[test]
+    fn extracts_named_groups() {
+        let re = Regex::new(r"(?P<user>\w+)@(?P<domain>[\w.]+)(?P<port>:\d+)?").unwrap();
+        assert_eq!(
+            apply_filter(&Value::from("mail from bob@example.com"), &re).unwrap(),
+            Value::from(btreemap! {
+                "user" => "bob",
+                "domain" => "example.com",
+            })
+        );
+    }
+
+    #[test]
+    fn fails_on_no_match() {
+        let re = Regex::new(r"id=(\d+)").unwrap();
+        assert!(apply_filter(&Value::from("no id here"), &re).is_err());
+    }
+}
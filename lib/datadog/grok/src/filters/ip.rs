@@ -0,0 +1,126 @@
+use std::net::Ipv6Addr;
+
+use value::Value;
+
+use crate::{
+    ast::{Function, FunctionArgument},
+    grok_filter::GrokFilter,
+    parse_grok::Error as GrokRuntimeError,
+    parse_grok_rules::Error as GrokStaticError,
+};
+
+/// How IPv6 addresses are normalized, IPv4 addresses and hostnames are normalized the same way in both cases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpFormat {
+    // e.g. 2001:db8::1
+    Compressed,
+    // e.g. 2001:0db8:0000:0000:0000:0000:0000:0001
+    Expanded,
+}
+
+/// Parses an optional normalization argument of the ip matchers, e.g. `%{ipv6("compressed"):ip}`.
+pub fn filter_from_function(f: &Function) -> Result<Option<GrokFilter>, GrokStaticError> {
+    match f.args.as_deref() {
+        None => Ok(None),
+        Some([FunctionArgument::Arg(Value::Bytes(format))]) => match format.as_ref() {
+            b"compressed" => Ok(Some(GrokFilter::Ip(IpFormat::Compressed))),
+            b"expanded" => Ok(Some(GrokFilter::Ip(IpFormat::Expanded))),
+            _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+        },
+        _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+    }
+}
+
+/// Normalizes an IP address or a hostname:
+/// - IPv4 addresses lose leading zeros, e.g. `010.001.0.1` -> `10.1.0.1`
+/// - IPv6 addresses are lowercased and compressed or expanded, zone indices are kept as is
+/// - hostnames are lowercased and lose the trailing dot
+pub fn apply_filter(value: &Value, format: IpFormat) -> Result<Value, GrokRuntimeError> {
+    let error = || {
+        GrokRuntimeError::FailedToApplyFilter(GrokFilter::Ip(format).to_string(), value.to_string())
+    };
+
+    match value {
+        Value::Bytes(bytes) => {
+            let input = String::from_utf8_lossy(bytes);
+            let normalized = if input.contains(':') {
+                let (address, zone) = match input.split_once('%') {
+                    Some((address, zone)) => (address, Some(zone)),
+                    None => (input.as_ref(), None),
+                };
+                let address = address.parse::<Ipv6Addr>().map_err(|_e| error())?;
+                let mut normalized = match format {
+                    IpFormat::Compressed => address.to_string(),
+                    IpFormat::Expanded => address
+                        .segments()
+                        .iter()
+                        .map(|segment| format!("{:04x}", segment))
+                        .collect::<Vec<_>>()
+                        .join(":"),
+                };
+                if let Some(zone) = zone {
+                    normalized.push('%');
+                    normalized.push_str(zone);
+                }
+                normalized
+            } else if let Some(address) = parse_ipv4(&input) {
+                address
+            } else {
+                input.trim_end_matches('.').to_lowercase()
+            };
+            Ok(normalized.into())
+        }
+        _ => Err(error()),
+    }
+}
+
+/// Parses an IPv4 address, allowing leading zeros in octets.
+fn parse_ipv4(input: &str) -> Option<String> {
+    let octets = input
+        .split('.')
+        .map(|octet| {
+            if octet.is_empty() || !octet.bytes().all(|b| b.is_ascii_digit()) {
+                None
+            } else {
+                octet.parse::<u8>().ok()
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+    (octets.len() == 4).then(|| {
+        octets
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(".")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_addresses() {
+        for (input, format, expected) in [
+            ("010.001.0.1", IpFormat::Compressed, "10.1.0.1"),
+            ("2001:DB8:0:0:0:0:0:1", IpFormat::Compressed, "2001:db8::1"),
+            (
+                "2001:db8::1",
+                IpFormat::Expanded,
+                "2001:0db8:0000:0000:0000:0000:0000:0001",
+            ),
+            ("fe80::1%eth0", IpFormat::Compressed, "fe80::1%eth0"),
+            ("Example.COM.", IpFormat::Compressed, "example.com"),
+        ] {
+            assert_eq!(
+                apply_filter(&Value::from(input), format),
+                Ok(Value::from(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn fails_on_invalid_ipv6() {
+        assert!(apply_filter(&Value::from("2001:db8:::1"), IpFormat::Compressed).is_err());
+    }
+}
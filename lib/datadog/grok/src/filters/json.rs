@@ -0,0 +1,190 @@
+use std::fmt::Formatter;
+
+use serde_json::{Map, Value as JsonValue};
+use value::Value;
+
+use crate::{
+    ast::{Function, FunctionArgument},
+    grok_filter::GrokFilter,
+    parse_grok::Error as GrokRuntimeError,
+    parse_grok_rules::Error as GrokStaticError,
+};
+
+/// Parses options of the json filter, e.g. `json(maxDepth(2), flatten("_"))`.
+pub fn filter_from_function(f: &Function) -> Result<GrokFilter, GrokStaticError> {
+    let mut filter = JsonFilter::default();
+
+    for arg in f.args.iter().flatten() {
+        match arg {
+            FunctionArgument::Function(option) => {
+                match (option.name.as_str(), option.args.as_deref()) {
+                    ("maxDepth", Some([FunctionArgument::Arg(Value::Integer(depth))]))
+                        if *depth > 0 =>
+                    {
+                        filter.max_depth = Some(*depth as usize);
+                    }
+                    ("flatten", None) => {
+                        // default separator
+                        filter.flatten_separator = Some(".".to_string());
+                    }
+                    ("flatten", Some([FunctionArgument::Arg(Value::Bytes(separator))])) => {
+                        filter.flatten_separator =
+                            Some(String::from_utf8_lossy(separator).to_string());
+                    }
+                    _ => return Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+                }
+            }
+            _ => return Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+        }
+    }
+
+    Ok(GrokFilter::Json(filter))
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct JsonFilter {
+    /// objects and arrays nested deeper than this are kept as JSON strings
+    pub max_depth: Option<usize>,
+    /// if set, nested object keys are joined with this separator
+    pub flatten_separator: Option<String>,
+}
+
+impl std::fmt::Display for JsonFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "json(maxDepth({:?}), flatten({:?}))",
+            self.max_depth, self.flatten_separator
+        )
+    }
+}
+
+pub fn apply_filter(value: &Value, filter: &JsonFilter) -> Result<Value, GrokRuntimeError> {
+    match value {
+        Value::Bytes(bytes) => serde_json::from_slice::<'_, JsonValue>(bytes.as_ref())
+            .map_err(|_e| {
+                GrokRuntimeError::FailedToApplyFilter(
+                    GrokFilter::Json(filter.clone()).to_string(),
+                    value.to_string(),
+                )
+            })
+            .map(|mut v| {
+                if let Some(max_depth) = filter.max_depth {
+                    v = limit_depth(v, 1, max_depth);
+                }
+                v = match (&filter.flatten_separator, v) {
+                    (Some(separator), JsonValue::Object(map)) => {
+                        let mut flattened = Map::new();
+                        flatten(None, map, separator, &mut flattened);
+                        JsonValue::Object(flattened)
+                    }
+                    (_, v) => v,
+                };
+                v.into()
+            }),
+        _ => Err(GrokRuntimeError::FailedToApplyFilter(
+            GrokFilter::Json(filter.clone()).to_string(),
+            value.to_string(),
+        )),
+    }
+}
+
+/// Serializes objects and arrays nested deeper than `max_depth` back to strings.
+fn limit_depth(value: JsonValue, depth: usize, max_depth: usize) -> JsonValue {
+    match value {
+        v @ (JsonValue::Object(_) | JsonValue::Array(_)) if depth > max_depth => {
+            JsonValue::String(v.to_string())
+        }
+        JsonValue::Object(map) => JsonValue::Object(
+            map.into_iter()
+                .map(|(k, v)| (k, limit_depth(v, depth + 1, max_depth)))
+                .collect(),
+        ),
+        JsonValue::Array(values) => JsonValue::Array(
+            values
+                .into_iter()
+                .map(|v| limit_depth(v, depth + 1, max_depth))
+                .collect(),
+        ),
+        v => v,
+    }
+}
+
+fn flatten(
+    prefix: Option<&str>,
+    map: Map<String, JsonValue>,
+    separator: &str,
+    result: &mut Map<String, JsonValue>,
+) {
+    for (k, v) in map {
+        let key = match prefix {
+            Some(prefix) => format!("{}{}{}", prefix, separator, k),
+            None => k,
+        };
+        match v {
+            JsonValue::Object(map) if !map.is_empty() => {
+                flatten(Some(&key), map, separator, result)
+            }
+            v => {
+                result.insert(key, v);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::btreemap;
+
+    use super::*;
+
+    const NESTED: &str = r#"{"a":{"b":{"c":1,"d":[1,{"e":2}]}},"f":"g"}"#;
+
+    #[test]
+    fn limits_depth() {
+        let filter = JsonFilter {
+            max_depth: Some(2),
+            flatten_separator: None,
+        };
+        assert_eq!(
+            apply_filter(&Value::from(NESTED), &filter).unwrap(),
+            Value::from(btreemap! {
+                "a" => btreemap! {
+                    "b" => r#"{"c":1,"d":[1,{"e":2}]}"#,
+                },
+                "f" => "g",
+            })
+        );
+    }
+
+    #[test]
+    fn flattens_keys() {
+        let filter = JsonFilter {
+            max_depth: None,
+            flatten_separator: Some("_".to_string()),
+        };
+        assert_eq!(
+            apply_filter(&Value::from(NESTED), &filter).unwrap(),
+            Value::from(btreemap! {
+                "a_b_c" => 1,
+                "a_b_d" => Value::Array(vec![1.into(), Value::from(btreemap! { "e" => 2 })]),
+                "f" => "g",
+            })
+        );
+    }
+
+    #[test]
+    fn limits_depth_before_flattening() {
+        let filter = JsonFilter {
+            max_depth: Some(1),
+            flatten_separator: Some(".".to_string()),
+        };
+        assert_eq!(
+            apply_filter(&Value::from(NESTED), &filter).unwrap(),
+            Value::from(btreemap! {
+                "a" => r#"{"b":{"c":1,"d":[1,{"e":2}]}}"#,
+                "f" => "g",
+            })
+        );
+    }
+}
@@ -63,16 +63,15 @@ pub fn filter_from_function(f: &Function) -> Result<GrokFilter, GrokStaticError>
         let quotes = if args_len > 2 {
             match f.args.as_ref().unwrap()[2] {
                 FunctionArgument::Arg(Value::Bytes(ref bytes)) => {
-                    let quotes = String::from_utf8_lossy(bytes);
-                    if quotes.len() == 2 {
-                        let mut chars = quotes.chars();
-                        vec![(
-                            chars.next().expect("open quote"),
-                            chars.next().expect("closing quote"),
-                        )]
-                    } else if quotes.is_empty() {
+                    let quotes = String::from_utf8_lossy(bytes)
+                        .chars()
+                        .collect::<Vec<char>>();
+                    if quotes.is_empty() {
                         // default
                         vec![('"', '"'), ('\'', '\''), ('<', '>')]
+                    } else if quotes.len() % 2 == 0 {
+                        // each consecutive pair of characters defines opening and closing quotes, e.g. "<>{}"
+                        quotes.chunks(2).map(|pair| (pair[0], pair[1])).collect()
                     } else {
                         return Err(GrokStaticError::InvalidFunctionArguments(f.name.clone()));
                     }
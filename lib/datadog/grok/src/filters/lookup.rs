@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+
+use value::Value;
+
+use crate::{
+    ast::{Function, FunctionArgument},
+    grok_filter::GrokFilter,
+    parse_grok::Error as GrokRuntimeError,
+    parse_grok_rules::Error as GrokStaticError,
+};
+
+/// Parses arguments of the lookup filter, e.g. `lookup({"0": "emergency", "1": "alert"}, "unknown")`.
+pub fn filter_from_function(f: &Function) -> Result<GrokFilter, GrokStaticError> {
+    match f.args.as_deref() {
+        Some([FunctionArgument::Arg(Value::Object(table))]) => {
+            Ok(GrokFilter::Lookup(table.clone(), None))
+        }
+        Some([FunctionArgument::Arg(Value::Object(table)), FunctionArgument::Arg(default)]) => {
+            Ok(GrokFilter::Lookup(table.clone(), Some(default.clone())))
+        }
+        _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+    }
+}
+
+/// Translates a value using a mapping table, falling back to the default value, if any.
+pub fn apply_filter(
+    value: &Value,
+    table: &BTreeMap<String, Value>,
+    default: &Option<Value>,
+) -> Result<Value, GrokRuntimeError> {
+    let key = match value {
+        Value::Bytes(bytes) => Some(String::from_utf8_lossy(bytes).to_string()),
+        Value::Integer(v) => Some(v.to_string()),
+        Value::Float(v) => Some(v.to_string()),
+        Value::Boolean(v) => Some(v.to_string()),
+        _ => None,
+    };
+
+    key.and_then(|key| table.get(&key))
+        .or(default.as_ref())
+        .cloned()
+        .ok_or_else(|| {
+            GrokRuntimeError::FailedToApplyFilter(
+                GrokFilter::Lookup(table.clone(), default.clone()).to_string(),
+                value.to_string(),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::btreemap;
+
+    use super::*;
+
+    #[test]
+    fn looks_up_values() {
+        let table = btreemap! {
+            "0" => "emergency",
+            "1" => "alert",
+        };
+        assert_eq!(
+            apply_filter(&Value::from("1"), &table, &None),
+            Ok(Value::from("alert"))
+        );
+        assert_eq!(
+            apply_filter(&Value::from(0), &table, &None),
+            Ok(Value::from("emergency"))
+        );
+        assert_eq!(
+            apply_filter(&Value::from("7"), &table, &Some(Value::from("unknown"))),
+            Ok(Value::from("unknown"))
+        );
+        assert!(apply_filter(&Value::from("7"), &table, &None).is_err());
+    }
+}
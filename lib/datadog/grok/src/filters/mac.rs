@@ -0,0 +1,47 @@
+use value::Value;
+
+use crate::{grok_filter::GrokFilter, parse_grok::Error as GrokRuntimeError};
+
+/// Normalizes a MAC address, e.g. `0123.4567.89AB` or `01-23-45-67-89-AB`, to its
+/// colon-separated lowercase form: `01:23:45:67:89:ab`.
+pub fn apply_filter(value: &Value) -> Result<Value, GrokRuntimeError> {
+    let error =
+        || GrokRuntimeError::FailedToApplyFilter(GrokFilter::Mac.to_string(), value.to_string());
+
+    match value {
+        Value::Bytes(bytes) => {
+            let digits = String::from_utf8_lossy(bytes)
+                .chars()
+                .filter(|c| !matches!(c, ':' | '-' | '.'))
+                .map(|c| c.to_ascii_lowercase())
+                .collect::<Vec<_>>();
+            if digits.len() != 12 || !digits.iter().all(|c| c.is_ascii_hexdigit()) {
+                return Err(error());
+            }
+
+            Ok(digits
+                .chunks(2)
+                .map(|octet| octet.iter().collect::<String>())
+                .collect::<Vec<_>>()
+                .join(":")
+                .into())
+        }
+        _ => Err(error()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_macs() {
+        for input in ["01:23:45:67:89:ab", "01-23-45-67-89-AB", "0123.4567.89ab"] {
+            assert_eq!(
+                apply_filter(&Value::from(input)),
+                Ok(Value::from("01:23:45:67:89:ab"))
+            );
+        }
+        assert!(apply_filter(&Value::from("01:23:45")).is_err());
+    }
+}
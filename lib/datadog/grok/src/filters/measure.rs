@@ -0,0 +1,72 @@
+use std::collections::BTreeMap;
+
+use value::Value;
+
+use crate::{grok_filter::GrokFilter, parse_grok::Error as GrokRuntimeError};
+
+/// Splits a measure, e.g. `25ms`, `3.4 GB` or `99%`, into an object with its numeric value
+/// and unit: `{ "value": 25, "unit": "ms" }`.
+pub fn apply_filter(value: &Value) -> Result<Value, GrokRuntimeError> {
+    let error = || {
+        GrokRuntimeError::FailedToApplyFilter(GrokFilter::Measure.to_string(), value.to_string())
+    };
+
+    match value {
+        Value::Bytes(bytes) => {
+            let input = String::from_utf8_lossy(bytes);
+            let input = input.trim();
+            let split_at = input
+                .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '+' | '-')))
+                .ok_or_else(error)?;
+            let (number, unit) = input.split_at(split_at);
+            let unit = unit.trim_start();
+            if unit.is_empty() {
+                return Err(error());
+            }
+
+            let number = number.parse::<f64>().map_err(|_e| error())?;
+            let number = if (number as i64) as f64 == number {
+                Value::Integer(number as i64)
+            } else {
+                Value::from_f64_or_zero(number)
+            };
+
+            let mut measure = BTreeMap::new();
+            measure.insert("value".to_string(), number);
+            measure.insert("unit".to_string(), unit.into());
+            Ok(measure.into())
+        }
+        _ => Err(error()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::btreemap;
+
+    use super::*;
+
+    #[test]
+    fn splits_measures() {
+        for (input, expected_value, expected_unit) in [
+            ("25ms", Value::from(25), "ms"),
+            ("3.4 GB", Value::from(3.4), "GB"),
+            ("99%", Value::from(99), "%"),
+            ("-1.5°C", Value::from(-1.5), "°C"),
+        ] {
+            assert_eq!(
+                apply_filter(&Value::from(input)),
+                Ok(Value::from(btreemap! {
+                    "value" => expected_value,
+                    "unit" => expected_unit,
+                }))
+            );
+        }
+    }
+
+    #[test]
+    fn fails_without_unit() {
+        assert!(apply_filter(&Value::from("25")).is_err());
+        assert!(apply_filter(&Value::from("ms")).is_err());
+    }
+}
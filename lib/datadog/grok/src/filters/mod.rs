@@ -1,2 +1,22 @@
 pub mod array;
+pub mod bytesize;
+pub mod cidr;
+pub mod csv;
+pub mod decodeuricomponent;
+pub mod duration;
+pub mod extract;
+pub mod ip;
+pub mod json;
 pub mod keyvalue;
+pub mod lookup;
+pub mod mac;
+pub mod measure;
+pub mod number;
+pub mod querystring;
+pub mod redact;
+pub mod replace;
+pub mod rubyhash;
+pub mod url;
+pub mod useragent;
+pub mod uuid;
+pub mod xml;
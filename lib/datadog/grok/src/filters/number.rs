@@ -0,0 +1,160 @@
+use std::fmt::Formatter;
+
+use value::Value;
+
+use crate::{
+    ast::{Function, FunctionArgument},
+    grok_filter::GrokFilter,
+    parse_grok::Error as GrokRuntimeError,
+    parse_grok_rules::Error as GrokStaticError,
+};
+
+// spaces used to group digits by locales like fr_FR: a regular, a no-break and a narrow no-break space
+const SPACES: &[char] = &[' ', '\u{a0}', '\u{202f}'];
+
+/// Parses arguments of a locale-aware number filter:
+/// - `number("de_DE")` - a locale
+/// - `number(".", ",")` - thousands and decimal separators
+pub fn filter_from_function(f: &Function) -> Result<GrokFilter, GrokStaticError> {
+    let string_arg = |arg: &FunctionArgument| match arg {
+        FunctionArgument::Arg(Value::Bytes(bytes)) => {
+            Ok(String::from_utf8_lossy(bytes).to_string())
+        }
+        _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+    };
+
+    let format = match f.args.as_ref() {
+        Some(args) if args.len() == 1 => NumberFormat::from_locale(&string_arg(&args[0])?),
+        Some(args) if args.len() == 2 => {
+            let thousands_separator = string_arg(&args[0])?;
+            let decimal_separator = string_arg(&args[1])?;
+            let mut decimal_separator = decimal_separator.chars();
+            match (decimal_separator.next(), decimal_separator.next()) {
+                (Some(decimal_separator), None) => Some(NumberFormat {
+                    thousands_separators: thousands_separator.chars().collect(),
+                    decimal_separator,
+                }),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+
+    format
+        .filter(|format| {
+            !format
+                .thousands_separators
+                .contains(&format.decimal_separator)
+        })
+        .map(GrokFilter::LocaleNumber)
+        .ok_or_else(|| GrokStaticError::InvalidFunctionArguments(f.name.clone()))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub thousands_separators: Vec<char>,
+    pub decimal_separator: char,
+}
+
+impl NumberFormat {
+    /// Returns the number format of a given locale(e.g. `de_DE` or `de-DE`), if known.
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let (thousands_separators, decimal_separator) = match locale.replace('-', "_").as_str() {
+            "en" | "en_US" | "en_GB" | "en_AU" | "en_CA" | "en_IN" | "ja_JP" | "zh_CN"
+            | "ko_KR" | "he_IL" | "th_TH" => (vec![','], '.'),
+            "de" | "de_DE" | "de_AT" | "es" | "es_ES" | "it" | "it_IT" | "nl" | "nl_NL" | "pt"
+            | "pt_BR" | "pt_PT" | "id_ID" | "tr_TR" | "da_DK" | "el_GR" => (vec!['.'], ','),
+            "fr" | "fr_FR" | "fr_CA" | "ru" | "ru_RU" | "pl" | "pl_PL" | "cs" | "cs_CZ" | "sv"
+            | "sv_SE" | "fi" | "fi_FI" | "nb" | "nb_NO" | "uk_UA" | "hu_HU" | "sk_SK" => {
+                (SPACES.to_vec(), ',')
+            }
+            "de_CH" | "it_CH" | "fr_CH" => (vec!['\'', '’'], '.'),
+            _ => return None,
+        };
+        Some(Self {
+            thousands_separators,
+            decimal_separator,
+        })
+    }
+}
+
+impl std::fmt::Display for NumberFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "number(\"{}\", \"{}\")",
+            self.thousands_separators.iter().collect::<String>(),
+            self.decimal_separator
+        )
+    }
+}
+
+/// Parses a number formatted as `1 234,56` or `1,234.56`, depending on the format.
+pub fn apply_filter(value: &Value, format: &NumberFormat) -> Result<Value, GrokRuntimeError> {
+    let error = || {
+        GrokRuntimeError::FailedToApplyFilter(
+            GrokFilter::LocaleNumber(format.clone()).to_string(),
+            value.to_string(),
+        )
+    };
+
+    match value {
+        Value::Bytes(bytes) => {
+            let mut normalized = String::with_capacity(bytes.len());
+            let mut fraction = false;
+            for c in String::from_utf8_lossy(bytes).trim().chars() {
+                if format.thousands_separators.contains(&c) {
+                    // digits are only grouped before the decimal separator
+                    if fraction {
+                        return Err(error());
+                    }
+                } else if c == format.decimal_separator {
+                    fraction = true;
+                    normalized.push('.');
+                } else {
+                    normalized.push(c);
+                }
+            }
+            let v = normalized.parse::<f64>().map_err(|_e| error())?;
+            if !v.is_finite() {
+                return Err(error());
+            }
+            if (v as i64) as f64 == v {
+                Ok(Value::Integer(v as i64))
+            } else {
+                Ok(Value::from_f64_or_zero(v))
+            }
+        }
+        _ => Err(error()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str, locale: &str) -> Result<Value, GrokRuntimeError> {
+        apply_filter(&Value::from(s), &NumberFormat::from_locale(locale).unwrap())
+    }
+
+    #[test]
+    fn parses_locale_formatted_numbers() {
+        assert_eq!(parse("1,234.56", "en_US"), Ok(Value::from(1234.56)));
+        assert_eq!(parse("1.234,56", "de_DE"), Ok(Value::from(1234.56)));
+        assert_eq!(parse("1 234,56", "fr_FR"), Ok(Value::from(1234.56)));
+        assert_eq!(parse("1\u{202f}234,5", "fr-FR"), Ok(Value::from(1234.5)));
+        assert_eq!(parse("1'234'567", "de_CH"), Ok(Value::from(1234567)));
+        assert_eq!(parse("-0,5", "de_DE"), Ok(Value::from(-0.5)));
+    }
+
+    #[test]
+    fn fails_on_invalid_numbers() {
+        assert!(parse("1.234,56", "en_US").is_err());
+        assert!(parse("abc", "de_DE").is_err());
+    }
+
+    #[test]
+    fn unknown_locale() {
+        assert_eq!(NumberFormat::from_locale("xx_XX"), None);
+    }
+}
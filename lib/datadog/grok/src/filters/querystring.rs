@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+
+use url::form_urlencoded;
+use value::Value;
+
+use crate::{grok_filter::GrokFilter, parse_grok::Error as GrokRuntimeError};
+
+/// Extracts URL-decoded key/value pairs from a query string, e.g. `?productId=superproduct&promotionCode=superpromo`.
+/// Values of repeated keys are collected to an array.
+pub fn apply_filter(value: &Value) -> Result<Value, GrokRuntimeError> {
+    match value {
+        Value::Bytes(bytes) => Ok(parse(bytes.as_ref()).into()),
+        _ => Err(GrokRuntimeError::FailedToApplyFilter(
+            GrokFilter::QueryString.to_string(),
+            value.to_string(),
+        )),
+    }
+}
+
+pub fn parse(input: &[u8]) -> BTreeMap<String, Value> {
+    let query_string = input.strip_prefix(b"?").unwrap_or(input);
+    let mut result = BTreeMap::new();
+    for (k, v) in form_urlencoded::parse(query_string) {
+        let v = Value::from(v.as_ref());
+        result
+            .entry(k.into_owned())
+            .and_modify(|existing| match existing {
+                Value::Array(values) => values.push(v.clone()),
+                existing => *existing = Value::Array(vec![existing.clone(), v.clone()]),
+            })
+            .or_insert(v);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_query_string() {
+        let result = parse(b"?productId=superproduct&promotionCode=superpromo");
+        assert_eq!(result.get("productId"), Some(&"superproduct".into()));
+        assert_eq!(result.get("promotionCode"), Some(&"superpromo".into()));
+    }
+
+    #[test]
+    fn decodes_keys_and_values() {
+        let result = parse(b"first%20name=J%C3%B6rg+M&a%26b=1");
+        assert_eq!(result.get("first name"), Some(&"Jörg M".into()));
+        assert_eq!(result.get("a&b"), Some(&"1".into()));
+    }
+
+    #[test]
+    fn collects_repeated_keys_to_array() {
+        let result = parse(b"foo=1&foo=2&foo=3");
+        assert_eq!(
+            result.get("foo"),
+            Some(&Value::Array(vec!["1".into(), "2".into(), "3".into()]))
+        );
+    }
+}
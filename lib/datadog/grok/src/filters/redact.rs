@@ -0,0 +1,104 @@
+use sha_2::{Digest, Sha256};
+use value::Value;
+
+use crate::{
+    ast::{Function, FunctionArgument},
+    grok_filter::GrokFilter,
+    parse_grok::Error as GrokRuntimeError,
+    parse_grok_rules::Error as GrokStaticError,
+};
+
+const DEFAULT_MASK: &str = "****";
+const MASK_CHAR: char = '*';
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Redaction {
+    /// replaces the value with a fixed mask
+    Mask(String),
+    /// replaces the value with its hex-encoded SHA-256 digest
+    Sha256,
+    /// masks all but the last N characters
+    KeepLast(usize),
+}
+
+/// Parses arguments of the redact filter:
+/// - `redact` or `redact("xxx")` - a fixed mask
+/// - `redact(sha256)` - a SHA-256 hash
+/// - `redact(keepLast(4))` - keep the last 4 characters only
+pub fn filter_from_function(f: &Function) -> Result<GrokFilter, GrokStaticError> {
+    let redaction = match f.args.as_deref() {
+        None => Redaction::Mask(DEFAULT_MASK.to_string()),
+        Some([FunctionArgument::Arg(Value::Bytes(mask))]) => {
+            Redaction::Mask(String::from_utf8_lossy(mask).to_string())
+        }
+        Some([FunctionArgument::Function(option)]) => {
+            match (option.name.as_str(), option.args.as_deref()) {
+                ("sha256", None) => Redaction::Sha256,
+                ("keepLast", Some([FunctionArgument::Arg(Value::Integer(n))])) if *n >= 0 => {
+                    Redaction::KeepLast(*n as usize)
+                }
+                _ => return Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+            }
+        }
+        _ => return Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+    };
+
+    Ok(GrokFilter::Redact(redaction))
+}
+
+pub fn apply_filter(value: &Value, redaction: &Redaction) -> Result<Value, GrokRuntimeError> {
+    let input = match value {
+        Value::Bytes(bytes) => String::from_utf8_lossy(bytes).to_string(),
+        Value::Integer(_) | Value::Float(_) | Value::Boolean(_) => value.to_string(),
+        _ => {
+            return Err(GrokRuntimeError::FailedToApplyFilter(
+                GrokFilter::Redact(redaction.clone()).to_string(),
+                value.to_string(),
+            ))
+        }
+    };
+
+    let redacted = match redaction {
+        Redaction::Mask(mask) => mask.clone(),
+        Redaction::Sha256 => hex::encode(Sha256::digest(input.as_bytes())),
+        Redaction::KeepLast(n) => {
+            let len = input.chars().count();
+            input
+                .chars()
+                .enumerate()
+                .map(|(i, c)| if i + n < len { MASK_CHAR } else { c })
+                .collect()
+        }
+    };
+
+    Ok(redacted.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_values() {
+        for (input, redaction, expected) in [
+            ("secret", Redaction::Mask("xxx".to_string()), "xxx"),
+            (
+                "secret",
+                Redaction::Sha256,
+                "2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b",
+            ),
+            (
+                "4111111111111111",
+                Redaction::KeepLast(4),
+                "************1111",
+            ),
+            ("abc", Redaction::KeepLast(4), "abc"),
+            ("abc", Redaction::KeepLast(0), "***"),
+        ] {
+            assert_eq!(
+                apply_filter(&Value::from(input), &redaction),
+                Ok(Value::from(expected))
+            );
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use regex::Regex;
+use value::Value;
+
+use crate::{
+    ast::{Function, FunctionArgument},
+    grok_filter::GrokFilter,
+    parse_grok::Error as GrokRuntimeError,
+    parse_grok_rules::Error as GrokStaticError,
+};
+
+pub fn filter_from_function(f: &Function) -> Result<GrokFilter, GrokStaticError> {
+    match f.args.as_ref() {
+        Some(args) if args.len() == 2 => match (&args[0], &args[1]) {
+            (
+                FunctionArgument::Arg(Value::Bytes(pattern)),
+                FunctionArgument::Arg(Value::Bytes(replacement)),
+            ) => {
+                let re = Regex::new(&String::from_utf8_lossy(pattern))
+                    .map_err(|_e| GrokStaticError::InvalidFunctionArguments(f.name.clone()))?;
+                Ok(GrokFilter::Replace(
+                    re,
+                    String::from_utf8_lossy(replacement).to_string(),
+                ))
+            }
+            _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+        },
+        _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+    }
+}
+
+/// Replaces all matches of a regex in the value.
+/// The replacement can reference capture groups, e.g. `$1` or `${name}`.
+pub fn apply_filter(
+    value: &Value,
+    re: &Regex,
+    replacement: &str,
+) -> Result<Value, GrokRuntimeError> {
+    match value {
+        Value::Bytes(bytes) => Ok(re
+            .replace_all(&String::from_utf8_lossy(bytes), replacement)
+            .as_ref()
+            .into()),
+        _ => Err(GrokRuntimeError::FailedToApplyFilter(
+            GrokFilter::Replace(re.clone(), replacement.to_string()).to_string(),
+            value.to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_all_matches() {
+        let re = Regex::new(r"\s+").unwrap();
+        assert_eq!(
+            apply_filter(&Value::from("a  b \t c"), &re, " ").unwrap(),
+            Value::from("a b c")
+        );
+    }
+
+    #[test]
+    fn strips_ansi_codes() {
+        let re = Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        assert_eq!(
+            apply_filter(&Value::from("\x1b[31merror\x1b[0m"), &re, "").unwrap(),
+            Value::from("error")
+        );
+    }
+
+    #[test]
+    fn supports_group_references() {
+        let re = Regex::new(r"user-(?P<id>\d+)").unwrap();
+        assert_eq!(
+            apply_filter(&Value::from("user-42 user-7"), &re, "u${id}").unwrap(),
+            Value::from("u42 u7")
+        );
+    }
+}
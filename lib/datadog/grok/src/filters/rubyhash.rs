@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use nom::{
+    branch::alt,
+    bytes::complete::{escaped, tag, take_while, take_while1},
+    character::complete::{char, digit1, satisfy},
+    combinator::{cut, map, map_opt, opt, recognize, value},
+    multi::{many1, separated_list0},
+    number::complete::double,
+    sequence::{preceded, separated_pair, terminated, tuple},
+    IResult,
+};
+use ordered_float::NotNan;
+use value::Value;
+
+use crate::{grok_filter::GrokFilter, parse_grok::Error as GrokRuntimeError};
+
+/// Parses a Ruby hash literal, e.g. `{"key"=>"value", :sym=>1}`, as printed by Ruby's `Hash#inspect`.
+/// Symbol keys and values are converted to strings without the leading `:`.
+pub fn apply_filter(value: &Value) -> Result<Value, GrokRuntimeError> {
+    match value {
+        Value::Bytes(bytes) => parse(String::from_utf8_lossy(bytes).as_ref()).map_err(|_e| {
+            GrokRuntimeError::FailedToApplyFilter(
+                GrokFilter::RubyHash.to_string(),
+                value.to_string(),
+            )
+        }),
+        _ => Err(GrokRuntimeError::FailedToApplyFilter(
+            GrokFilter::RubyHash.to_string(),
+            value.to_string(),
+        )),
+    }
+}
+
+type SResult<'a, O> = IResult<&'a str, O, (&'a str, nom::error::ErrorKind)>;
+
+fn parse(input: &str) -> Result<Value, String> {
+    let (rest, result) = preceded(sp, parse_hash)(input)
+        .map_err(|_| format!("could not parse '{}' as 'rubyhash'", input))?;
+
+    if rest.trim().is_empty() {
+        Ok(result)
+    } else {
+        Err("could not parse whole line successfully".into())
+    }
+}
+
+fn sp(input: &str) -> SResult<&str> {
+    take_while(|c| " \t\r\n".contains(c))(input)
+}
+
+/// Parses the content of a string with a given delimiter, keeping escape sequences as is.
+fn parse_inner_str<'a>(delimiter: char) -> impl FnMut(&'a str) -> SResult<&'a str> {
+    move |input| {
+        map(
+            opt(escaped(
+                recognize(many1(tuple((
+                    take_while1(|c: char| c != '\\' && c != delimiter),
+                    // consume \something
+                    opt(tuple((
+                        satisfy(|c| c == '\\'),
+                        satisfy(|c| c != '\\' && c != delimiter),
+                    ))),
+                )))),
+                '\\',
+                satisfy(|c| c == '\\' || c == delimiter),
+            )),
+            |inner| inner.unwrap_or(""),
+        )(input)
+    }
+}
+
+fn parse_str<'a>(delimiter: char) -> impl FnMut(&'a str) -> SResult<&'a str> {
+    preceded(
+        char(delimiter),
+        cut(terminated(parse_inner_str(delimiter), char(delimiter))),
+    )
+}
+
+fn parse_quoted(input: &str) -> SResult<&str> {
+    alt((parse_str('"'), parse_str('\'')))(input)
+}
+
+fn parse_symbol(input: &str) -> SResult<&str> {
+    take_while1(|c: char| c.is_alphanumeric() || c == '_')(input)
+}
+
+/// Parses `:symbol` or `:"quoted symbol"`.
+fn parse_colon_symbol(input: &str) -> SResult<&str> {
+    preceded(char(':'), alt((parse_quoted, parse_symbol)))(input)
+}
+
+fn parse_key(input: &str) -> SResult<String> {
+    map(
+        alt((parse_quoted, parse_colon_symbol, parse_symbol, digit1)),
+        String::from,
+    )(input)
+}
+
+/// Parses both `key => value` and `key: value` pairs.
+fn parse_key_value(input: &str) -> SResult<(String, Value)> {
+    separated_pair(
+        preceded(sp, parse_key),
+        cut(preceded(sp, alt((tag("=>"), tag(":"))))),
+        parse_value,
+    )(input)
+}
+
+fn parse_hash(input: &str) -> SResult<Value> {
+    map(
+        preceded(
+            char('{'),
+            cut(terminated(
+                separated_list0(preceded(sp, char(',')), parse_key_value),
+                preceded(sp, char('}')),
+            )),
+        ),
+        |pairs| Value::Object(pairs.into_iter().collect::<BTreeMap<String, Value>>()),
+    )(input)
+}
+
+fn parse_array(input: &str) -> SResult<Value> {
+    map(
+        preceded(
+            char('['),
+            cut(terminated(
+                separated_list0(preceded(sp, char(',')), parse_value),
+                preceded(sp, char(']')),
+            )),
+        ),
+        Value::Array,
+    )(input)
+}
+
+/// Parses a number, rejecting `NaN` and the infinities which Ruby prints as bare constants.
+fn parse_number(input: &str) -> SResult<Value> {
+    map_opt(double, |v| {
+        if !v.is_finite() {
+            None
+        } else if ((v as i64) as f64 - v).abs() == 0.0 {
+            // can be safely converted to Integer without precision loss
+            Some(Value::Integer(v as i64))
+        } else {
+            NotNan::new(v).ok().map(Value::Float)
+        }
+    })(input)
+}
+
+fn parse_value(input: &str) -> SResult<Value> {
+    preceded(
+        sp,
+        alt((
+            value(Value::Null, tag("nil")),
+            value(Value::Boolean(true), tag("true")),
+            value(Value::Boolean(false), tag("false")),
+            parse_hash,
+            parse_array,
+            map(alt((parse_quoted, parse_colon_symbol)), |v| {
+                Value::Bytes(Bytes::copy_from_slice(v.as_bytes()))
+            }),
+            parse_number,
+        )),
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::btreemap;
+
+    use super::*;
+
+    #[test]
+    fn parses_rubyhash() {
+        assert_eq!(
+            parse(r#"{name => "John", "job" => {"company" => "Big Company", "title" => "CTO"}}"#),
+            Ok(Value::from(btreemap! {
+                "name" => "John",
+                "job" => btreemap! {
+                    "company" => "Big Company",
+                    "title" => "CTO",
+                },
+            }))
+        );
+    }
+
+    #[test]
+    fn parses_symbols_and_scalars() {
+        assert_eq!(
+            parse(r#"{:sym=>1, "float"=>1.5, :status=>:ok, "list"=>[true, nil], key: 'value'}"#),
+            Ok(Value::from(btreemap! {
+                "sym" => 1,
+                "float" => 1.5,
+                "status" => "ok",
+                "list" => Value::Array(vec![Value::Boolean(true), Value::Null]),
+                "key" => "value",
+            }))
+        );
+    }
+
+    #[test]
+    fn fails_on_invalid_hash() {
+        assert!(parse(r#"{"key" => }"#).is_err());
+        assert!(parse(r#"{"key" => 1} trailing"#).is_err());
+    }
+
+    #[test]
+    fn fails_on_non_finite_numbers() {
+        assert!(parse(r#"{"a"=>NaN}"#).is_err());
+        assert!(parse(r#"{"a"=>Infinity}"#).is_err());
+        assert!(parse(r#"{"a"=>-Infinity}"#).is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use once_cell::sync::Lazy;
+use uaparser::{Parser, UserAgentParser};
+use value::Value;
+
+use crate::{
+    ast::{Function, FunctionArgument},
+    filters::decodeuricomponent,
+    grok_filter::GrokFilter,
+    parse_grok::Error as GrokRuntimeError,
+    parse_grok_rules::Error as GrokStaticError,
+};
+
+// a copy of the regexes used by the `parse_user_agent` VRL function
+static UA_PARSER: Lazy<UserAgentParser> = Lazy::new(|| {
+    let regexes = include_bytes!("../../data/user_agent_regexes.yaml");
+    UserAgentParser::from_bytes(regexes).expect("Regex file is not valid.")
+});
+
+pub fn filter_from_function(f: &Function) -> Result<GrokFilter, GrokStaticError> {
+    match f.args.as_ref() {
+        None => Ok(GrokFilter::UserAgent(false)),
+        Some(args) if args.len() == 1 => match args[0] {
+            FunctionArgument::Arg(Value::Boolean(decode_uri_component)) => {
+                Ok(GrokFilter::UserAgent(decode_uri_component))
+            }
+            _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+        },
+        _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+    }
+}
+
+/// Parses a user agent string into an object with `os`, `browser` and `device` fields.
+/// If `decode_uri_component` is set, the value is percent-decoded first.
+pub fn apply_filter(value: &Value, decode_uri_component: bool) -> Result<Value, GrokRuntimeError> {
+    match value {
+        Value::Bytes(bytes) => {
+            let user_agent = String::from_utf8_lossy(bytes);
+            let user_agent = if decode_uri_component {
+                Cow::Owned(decodeuricomponent::decode(&user_agent, false).into_owned())
+            } else {
+                user_agent
+            };
+            Ok(parse(&user_agent))
+        }
+        _ => Err(GrokRuntimeError::FailedToApplyFilter(
+            GrokFilter::UserAgent(decode_uri_component).to_string(),
+            value.to_string(),
+        )),
+    }
+}
+
+fn parse(user_agent: &str) -> Value {
+    fn insert(map: &mut BTreeMap<String, Value>, key: &str, value: Option<Cow<'_, str>>) {
+        if let Some(value) = value {
+            map.insert(key.to_string(), value.as_ref().into());
+        }
+    }
+
+    let client = UA_PARSER.parse(user_agent);
+
+    let mut os = BTreeMap::new();
+    insert(&mut os, "family", Some(client.os.family));
+    insert(&mut os, "major", client.os.major);
+    insert(&mut os, "minor", client.os.minor);
+    insert(&mut os, "patch", client.os.patch);
+    insert(&mut os, "patch_minor", client.os.patch_minor);
+
+    let mut browser = BTreeMap::new();
+    insert(&mut browser, "family", Some(client.user_agent.family));
+    insert(&mut browser, "major", client.user_agent.major);
+    insert(&mut browser, "minor", client.user_agent.minor);
+    insert(&mut browser, "patch", client.user_agent.patch);
+
+    let mut device = BTreeMap::new();
+    insert(&mut device, "family", Some(client.device.family));
+    insert(&mut device, "brand", client.device.brand);
+    insert(&mut device, "model", client.device.model);
+
+    let mut result = BTreeMap::new();
+    result.insert("os".to_string(), os.into());
+    result.insert("browser".to_string(), browser.into());
+    result.insert("device".to_string(), device.into());
+    result.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHROME_ON_MAC: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_13_5) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/67.0.3396.87 Safari/537.36";
+
+    #[test]
+    fn parses_user_agent() {
+        let result = parse(CHROME_ON_MAC);
+        let result = result.as_object().unwrap();
+
+        assert_eq!(
+            result.get("os"),
+            Some(&Value::from(BTreeMap::from([
+                ("family".to_string(), Value::from("Mac OS X")),
+                ("major".to_string(), Value::from("10")),
+                ("minor".to_string(), Value::from("13")),
+                ("patch".to_string(), Value::from("5")),
+            ])))
+        );
+        assert_eq!(
+            result.get("browser"),
+            Some(&Value::from(BTreeMap::from([
+                ("family".to_string(), Value::from("Chrome")),
+                ("major".to_string(), Value::from("67")),
+                ("minor".to_string(), Value::from("0")),
+                ("patch".to_string(), Value::from("3396")),
+            ])))
+        );
+    }
+
+    #[test]
+    fn decodes_uri_component() {
+        let encoded = Value::from(CHROME_ON_MAC.replace(' ', "%20").replace(';', "%3B"));
+
+        assert_eq!(
+            apply_filter(&encoded, true).unwrap(),
+            apply_filter(&Value::from(CHROME_ON_MAC), false).unwrap()
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use value::Value;
+
+use crate::{grok_filter::GrokFilter, parse_grok::Error as GrokRuntimeError};
+
+/// Normalizes a UUID, e.g. `{123E4567-E89B-12D3-A456-426614174000}`, `urn:uuid:...` or
+/// `123e4567e89b12d3a456426614174000`, to its lowercase hyphenated form.
+pub fn apply_filter(value: &Value) -> Result<Value, GrokRuntimeError> {
+    let error =
+        || GrokRuntimeError::FailedToApplyFilter(GrokFilter::Uuid.to_string(), value.to_string());
+
+    match value {
+        Value::Bytes(bytes) => {
+            let input = String::from_utf8_lossy(bytes);
+            let input = input.strip_prefix("urn:uuid:").unwrap_or(&input);
+            let input = input
+                .strip_prefix('{')
+                .and_then(|input| input.strip_suffix('}'))
+                .unwrap_or(input);
+            let digits = input
+                .chars()
+                .filter(|c| *c != '-')
+                .map(|c| c.to_ascii_lowercase())
+                .collect::<String>();
+            if digits.len() != 32 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(error());
+            }
+
+            Ok(format!(
+                "{}-{}-{}-{}-{}",
+                &digits[..8],
+                &digits[8..12],
+                &digits[12..16],
+                &digits[16..20],
+                &digits[20..]
+            )
+            .into())
+        }
+        _ => Err(error()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_uuids() {
+        for input in [
+            "123e4567-e89b-12d3-a456-426614174000",
+            "123E4567-E89B-12D3-A456-426614174000",
+            "{123e4567-e89b-12d3-a456-426614174000}",
+            "urn:uuid:123e4567-e89b-12d3-a456-426614174000",
+            "123e4567e89b12d3a456426614174000",
+        ] {
+            assert_eq!(
+                apply_filter(&Value::from(input)),
+                Ok(Value::from("123e4567-e89b-12d3-a456-426614174000"))
+            );
+        }
+        assert!(apply_filter(&Value::from("123e4567")).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::{btree_map::Entry, BTreeMap};
+
+use roxmltree::{Document, Node};
+use value::Value;
+
+use crate::{grok_filter::GrokFilter, parse_grok::Error as GrokRuntimeError};
+
+/// The key used for the text content of elements that also have attributes or child elements.
+const TEXT_KEY: &str = "value";
+
+/// Parses an XML document into an object, following DD conventions:
+/// - attributes are added to the element's object as is
+/// - the text of elements with attributes or child elements is stored under the `value` key
+/// - repeated elements are collected to an array
+pub fn apply_filter(value: &Value) -> Result<Value, GrokRuntimeError> {
+    let error =
+        || GrokRuntimeError::FailedToApplyFilter(GrokFilter::Xml.to_string(), value.to_string());
+
+    match value {
+        Value::Bytes(bytes) => {
+            let input = String::from_utf8_lossy(bytes);
+            let doc = Document::parse(&input).map_err(|_e| error())?;
+            let root = doc.root_element();
+
+            let mut result = BTreeMap::new();
+            result.insert(root.tag_name().name().to_string(), process_element(root));
+            Ok(result.into())
+        }
+        _ => Err(error()),
+    }
+}
+
+fn process_element(node: Node) -> Value {
+    let mut map = BTreeMap::new();
+    let mut text = String::new();
+
+    for attr in node.attributes() {
+        map.insert(attr.name().to_string(), attr.value().into());
+    }
+
+    for child in node.children() {
+        if child.is_element() {
+            let value = process_element(child);
+            match map.entry(child.tag_name().name().to_string()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+                Entry::Occupied(mut entry) => match entry.get_mut() {
+                    Value::Array(values) => values.push(value),
+                    existing => *existing = Value::Array(vec![existing.clone(), value]),
+                },
+            }
+        } else if child.is_text() {
+            text.push_str(child.text().unwrap_or_default().trim());
+        }
+    }
+
+    if map.is_empty() {
+        text.into()
+    } else {
+        if !text.is_empty() {
+            map.insert(TEXT_KEY.to_string(), text.into());
+        }
+        map.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::btreemap;
+
+    use super::*;
+
+    #[test]
+    fn parses_xml() {
+        let result = apply_filter(&Value::from(
+            r#"<book category="CHILDREN">
+                 <title lang="en">Harry Potter</title>
+                 <author>J K. Rowling</author>
+                 <year>2005</year>
+               </book>"#,
+        ))
+        .unwrap();
+
+        assert_eq!(
+            result,
+            Value::from(btreemap! {
+                "book" => btreemap! {
+                    "category" => "CHILDREN",
+                    "title" => btreemap! {
+                        "lang" => "en",
+                        "value" => "Harry Potter",
+                    },
+                    "author" => "J K. Rowling",
+                    "year" => "2005",
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn collects_repeated_elements_to_array() {
+        let result =
+            apply_filter(&Value::from("<items><item>a</item><item>b</item></items>")).unwrap();
+
+        assert_eq!(
+            result,
+            Value::from(btreemap! {
+                "items" => btreemap! {
+                    "item" => Value::Array(vec!["a".into(), "b".into()]),
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn fails_on_invalid_xml() {
+        assert!(apply_filter(&Value::from("<book></author>")).is_err());
+        assert!(apply_filter(&Value::from("book")).is_err());
+    }
+}
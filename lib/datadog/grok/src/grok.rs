@@ -11,7 +11,7 @@ include!(concat!(env!("OUT_DIR"), "/patterns.rs"));
 use std::collections::{btree_map, BTreeMap};
 use std::sync::Arc;
 
-use onig::{Captures, Regex};
+use onig::{MatchParam, Regex, Region, SearchOptions};
 use thiserror::Error;
 
 const MAX_RECURSION: usize = 1024;
@@ -25,36 +25,48 @@ const DEFINITION_INDEX: usize = 4;
 /// The `Matches` represent matched results from a `Pattern` against text.
 #[derive(Debug)]
 pub struct Matches<'a> {
-    captures: Captures<'a>,
+    text: &'a str,
+    region: Region,
     names: &'a BTreeMap<String, usize>,
 }
 
 impl<'a> Matches<'a> {
     /// Instantiates the matches for a pattern after the match.
-    pub fn new(captures: Captures<'a>, names: &'a BTreeMap<String, usize>) -> Self {
-        Matches { captures, names }
+    pub fn new(text: &'a str, region: Region, names: &'a BTreeMap<String, usize>) -> Self {
+        Matches {
+            text,
+            region,
+            names,
+        }
     }
 
     /// Returns a tuple of key/value with all the matches found.
     ///
-    /// Note that if no match is found, the value is empty.
+    /// Note that if a group did not participate in the match, the value is `None`.
     pub fn iter(&'a self) -> MatchesIter<'a> {
         MatchesIter {
-            captures: &self.captures,
+            matches: self,
             names: self.names.iter(),
         }
     }
+
+    /// Returns the text captured by a group with a given index, if any.
+    fn at(&self, index: usize) -> Option<&'a str> {
+        self.region
+            .pos(index)
+            .map(|(start, end)| &self.text[start..end])
+    }
 }
 
 pub struct MatchesIter<'a> {
-    captures: &'a Captures<'a>,
+    matches: &'a Matches<'a>,
     names: btree_map::Iter<'a, String, usize>,
 }
 
 impl<'a> Iterator for MatchesIter<'a> {
-    type Item = (&'a str, &'a str);
+    type Item = (&'a str, Option<&'a str>);
 
-    // Returns the name of the match group, the value matched.
+    // Returns the name of the match group, the value matched, if the group participated in the match.
     fn next(&mut self) -> Option<Self::Item> {
         // Okay, here's the trick. We allow the user to pass in an 'alias'. An
         // alias is a different name for the capture group name and one capture
@@ -64,8 +76,7 @@ impl<'a> Iterator for MatchesIter<'a> {
         // that index, if anything.
         self.names.next().map(|(k, v)| {
             let key = k.as_str();
-            let value = self.captures.at(*v as usize).unwrap_or("");
-            (key, value)
+            (key, self.matches.at(*v as usize))
         })
     }
 }
@@ -79,6 +90,7 @@ pub struct Pattern {
     // Clone + Sync + Send.
     regex: Arc<Regex>,
     names: BTreeMap<String, usize>,
+    source: String,
 }
 
 impl Pattern {
@@ -99,18 +111,54 @@ impl Pattern {
                 Pattern {
                     regex: Arc::new(r),
                     names,
+                    source: regex.to_string(),
                 }
             }),
             Err(_) => Err(Error::RegexCompilationFailed(regex.into())),
         }
     }
 
+    /// Returns the regex this `Pattern` was compiled from.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
     /// Matches this compiled `Pattern` against the text and returns the matches.
     #[inline]
     pub fn match_against<'a>(&'a self, text: &'a str) -> Option<Matches<'a>> {
+        let mut region = Region::new();
         self.regex
-            .captures(text)
-            .map(|cap| Matches::new(cap, &self.names))
+            .search_with_options(
+                text,
+                0,
+                text.len(),
+                SearchOptions::SEARCH_OPTION_NONE,
+                Some(&mut region),
+            )
+            .map(|_| Matches::new(text, region, &self.names))
+    }
+
+    /// Matches this compiled `Pattern` against the text, just like `match_against`,
+    /// but gives up once the regex engine backtracks more than `retry_limit` times.
+    pub fn match_against_with_limit<'a>(
+        &'a self,
+        text: &'a str,
+        retry_limit: u32,
+    ) -> Result<Option<Matches<'a>>, Error> {
+        let mut region = Region::new();
+        let mut param = MatchParam::default();
+        param.set_retry_limit_in_match(retry_limit);
+        self.regex
+            .search_with_param(
+                text,
+                0,
+                text.len(),
+                SearchOptions::SEARCH_OPTION_NONE,
+                Some(&mut region),
+                param,
+            )
+            .map(|found| found.map(|_| Matches::new(text, region, &self.names)))
+            .map_err(|_| Error::RetryLimitExceeded(retry_limit))
     }
 }
 
@@ -132,6 +180,11 @@ impl Grok {
         grok
     }
 
+    /// Returns names of all known pattern definitions.
+    pub fn definition_names(&self) -> impl Iterator<Item = &str> {
+        self.definitions.keys().map(String::as_str)
+    }
+
     /// Inserts a custom pattern.
     pub fn insert_definition<S: Into<String>>(&mut self, name: S, pattern: S) {
         self.definitions.insert(name.into(), pattern.into());
@@ -253,4 +306,7 @@ pub enum Error {
     /// Something is messed up during the compilation phase.
     #[error("Something unexpected happened during the compilation phase: \"{0}\"")]
     GenericCompilationFailure(String),
+    /// Matching gave up after backtracking more times than allowed.
+    #[error("Matching exceeded the limit of {0} backtracking steps")]
+    RetryLimitExceeded(u32),
 }
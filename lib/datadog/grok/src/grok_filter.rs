@@ -1,36 +1,121 @@
-use std::{convert::TryFrom, fmt, string::ToString};
+use std::{collections::BTreeMap, convert::TryFrom, fmt, string::ToString};
 
 use ordered_float::NotNan;
 use value::Value;
 
 use crate::{
     ast::{Function, FunctionArgument},
-    filters::{array, keyvalue, keyvalue::KeyValueFilter},
-    matchers::date::{apply_date_filter, DateFilter},
+    filters::{
+        array, array::InvalidElements, bytesize, bytesize::ByteUnits, cidr, cidr::CidrFilter, csv,
+        csv::CsvFilter, decodeuricomponent, duration, extract, ip, ip::IpFormat, json,
+        json::JsonFilter, keyvalue, keyvalue::KeyValueFilter, lookup, mac, measure, number,
+        number::NumberFormat, querystring, redact, redact::Redaction, replace, rubyhash, url,
+        useragent, uuid, xml,
+    },
+    matchers::date::{apply_date_filters, DateFilter},
     parse_grok::Error as GrokRuntimeError,
     parse_grok_rules::Error as GrokStaticError,
 };
 
+/// Names of all supported filters, used to suggest a fix for unknown ones.
+pub const FILTER_NAMES: &[&str] = &[
+    "array",
+    "base64",
+    "base64url",
+    "boolean",
+    "byteSize",
+    "cidr",
+    "csv",
+    "decodeBase64",
+    "decodeuricomponent",
+    "duration",
+    "extract",
+    "integer",
+    "integerExt",
+    "json",
+    "keepEmpty",
+    "keyvalue",
+    "lookup",
+    "lowercase",
+    "nullIf",
+    "number",
+    "numberExt",
+    "querystring",
+    "redact",
+    "replace",
+    "rubyhash",
+    "scale",
+    "substring",
+    "trim",
+    "trimLeft",
+    "trimRight",
+    "uppercase",
+    "url",
+    "useragent",
+    "xml",
+];
+
 #[derive(Debug, Clone)]
 pub enum GrokFilter {
-    Date(DateFilter),
+    Date(Vec<DateFilter>),
+    // one or more formats, tried in order
     Integer,
+    IntegerRadix(u32),
+    // integer in a given radix, e.g. 16 for `0x1A2B`
     IntegerExt,
     // with scientific notation support, e.g. 1e10
     Number,
     NumberExt,
     // with scientific notation support, e.g. 1.52e10
+    LocaleNumber(NumberFormat),
+    // with locale-specific thousands and decimal separators, e.g. 1.234,56
     NullIf(String),
+    NullIfMatches(regex::Regex),
     Scale(f64),
     Lowercase,
     Uppercase,
-    Json,
+    Trim(Option<Vec<char>>),
+    TrimLeft(Option<Vec<char>>),
+    TrimRight(Option<Vec<char>>),
+    // characters to trim, whitespace if not set
+    Substring(usize, Option<usize>),
+    // character offset and optional length
+    Json(JsonFilter),
     Array(
         Option<(String, String)>,
         Option<String>,
         Box<Option<GrokFilter>>,
+        InvalidElements,
     ),
     KeyValue(KeyValueFilter),
+    QueryString,
+    Boolean(String, String),
+    // true and false values, compared case-insensitively
+    UserAgent(bool),
+    // whether to percent-decode the value before parsing
+    Xml,
+    Csv(CsvFilter),
+    RubyHash,
+    DecodeUriComponent(bool),
+    // whether `+` should be decoded as a space
+    Url,
+    DecodeBase64(base64::Config),
+    Extract(regex::Regex),
+    Replace(regex::Regex, String),
+    ByteSize(ByteUnits),
+    Duration(f64),
+    // the number of nanoseconds in the target unit
+    Redact(Redaction),
+    Lookup(BTreeMap<String, Value>, Option<Value>),
+    // a mapping table and an optional default value
+    Ip(IpFormat),
+    // normalizes addresses captured by the ip matchers
+    Cidr(CidrFilter),
+    Measure,
+    // splits a value with a unit, e.g. 25ms
+    Uuid,
+    Mac,
+    // normalize values captured by the uuid and mac matchers
 }
 
 impl fmt::Display for GrokFilter {
@@ -38,16 +123,43 @@ impl fmt::Display for GrokFilter {
         match self {
             GrokFilter::Date(..) => f.pad("Date(..)"),
             GrokFilter::Integer => f.pad("Integer"),
+            GrokFilter::IntegerRadix(..) => f.pad("IntegerRadix(..)"),
             GrokFilter::IntegerExt => f.pad("IntegerExt"),
             GrokFilter::Number => f.pad("Number"),
             GrokFilter::NumberExt => f.pad("NumberExt"),
+            GrokFilter::LocaleNumber(..) => f.pad("LocaleNumber(..)"),
             GrokFilter::NullIf(..) => f.pad("NullIf(..)"),
+            GrokFilter::NullIfMatches(..) => f.pad("NullIfMatches(..)"),
             GrokFilter::Scale(..) => f.pad("Scale(..)"),
             GrokFilter::Lowercase => f.pad("Lowercase"),
             GrokFilter::Uppercase => f.pad("Uppercase"),
-            GrokFilter::Json => f.pad("Json"),
+            GrokFilter::Trim(..) => f.pad("Trim(..)"),
+            GrokFilter::TrimLeft(..) => f.pad("TrimLeft(..)"),
+            GrokFilter::TrimRight(..) => f.pad("TrimRight(..)"),
+            GrokFilter::Substring(..) => f.pad("Substring(..)"),
+            GrokFilter::Json(..) => f.pad("Json(..)"),
             GrokFilter::Array(..) => f.pad("Array(..)"),
             GrokFilter::KeyValue(..) => f.pad("KeyValue(..)"),
+            GrokFilter::QueryString => f.pad("QueryString"),
+            GrokFilter::Boolean(..) => f.pad("Boolean(..)"),
+            GrokFilter::UserAgent(..) => f.pad("UserAgent(..)"),
+            GrokFilter::Xml => f.pad("Xml"),
+            GrokFilter::Csv(..) => f.pad("Csv(..)"),
+            GrokFilter::RubyHash => f.pad("RubyHash"),
+            GrokFilter::DecodeUriComponent(..) => f.pad("DecodeUriComponent(..)"),
+            GrokFilter::Url => f.pad("Url"),
+            GrokFilter::DecodeBase64(..) => f.pad("DecodeBase64(..)"),
+            GrokFilter::Extract(..) => f.pad("Extract(..)"),
+            GrokFilter::Replace(..) => f.pad("Replace(..)"),
+            GrokFilter::ByteSize(..) => f.pad("ByteSize(..)"),
+            GrokFilter::Duration(..) => f.pad("Duration(..)"),
+            GrokFilter::Redact(..) => f.pad("Redact(..)"),
+            GrokFilter::Lookup(..) => f.pad("Lookup(..)"),
+            GrokFilter::Ip(..) => f.pad("Ip(..)"),
+            GrokFilter::Cidr(..) => f.pad("Cidr(..)"),
+            GrokFilter::Measure => f.pad("Measure"),
+            GrokFilter::Uuid => f.pad("Uuid"),
+            GrokFilter::Mac => f.pad("Mac"),
         }
     }
 }
@@ -70,33 +182,138 @@ impl TryFrom<&Function> for GrokFilter {
                 }
                 _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
             },
-            "integer" => Ok(GrokFilter::Integer),
+            "integer" if f.args.is_none() => Ok(GrokFilter::Integer),
             "integerExt" => Ok(GrokFilter::IntegerExt),
-            "number" => Ok(GrokFilter::Number),
+            "number" if f.args.is_none() => Ok(GrokFilter::Number),
+            "number"
+                if matches!(
+                    f.args.as_deref(),
+                    Some([FunctionArgument::Arg(Value::Bytes(_)), ..])
+                ) =>
+            {
+                number::filter_from_function(f)
+            }
+            "integer" | "number" => match f.args.as_ref() {
+                Some(args) if args.len() == 1 => match args[0] {
+                    FunctionArgument::Arg(Value::Integer(radix)) if (2..=36).contains(&radix) => {
+                        Ok(GrokFilter::IntegerRadix(radix as u32))
+                    }
+                    _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+                },
+                _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+            },
             "numberExt" => Ok(GrokFilter::NumberExt),
             "lowercase" => Ok(GrokFilter::Lowercase),
             "uppercase" => Ok(GrokFilter::Uppercase),
-            "json" => Ok(GrokFilter::Json),
+            "trim" => trim_chars_from_function(f).map(GrokFilter::Trim),
+            "trimLeft" => trim_chars_from_function(f).map(GrokFilter::TrimLeft),
+            "trimRight" => trim_chars_from_function(f).map(GrokFilter::TrimRight),
+            "substring" => {
+                let non_negative_arg = |arg: &FunctionArgument| match arg {
+                    FunctionArgument::Arg(Value::Integer(v)) if *v >= 0 => Ok(*v as usize),
+                    _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+                };
+                match f.args.as_ref() {
+                    Some(args) if args.len() == 1 => {
+                        Ok(GrokFilter::Substring(non_negative_arg(&args[0])?, None))
+                    }
+                    Some(args) if args.len() == 2 => Ok(GrokFilter::Substring(
+                        non_negative_arg(&args[0])?,
+                        Some(non_negative_arg(&args[1])?),
+                    )),
+                    _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+                }
+            }
+            "json" => json::filter_from_function(f),
             "nullIf" => f
                 .args
                 .as_ref()
-                .and_then(|args| {
-                    if let FunctionArgument::Arg(Value::Bytes(null_value)) = &args[0] {
-                        Some(GrokFilter::NullIf(
-                            String::from_utf8_lossy(null_value).to_string(),
-                        ))
-                    } else {
-                        None
-                    }
+                .and_then(|args| match &args[0] {
+                    FunctionArgument::Arg(Value::Bytes(null_value)) => Some(GrokFilter::NullIf(
+                        String::from_utf8_lossy(null_value).to_string(),
+                    )),
+                    // nullIf(re("pattern"))
+                    FunctionArgument::Function(Function {
+                        name,
+                        args: Some(re_args),
+                    }) if name == "re" && re_args.len() == 1 => match &re_args[0] {
+                        FunctionArgument::Arg(Value::Bytes(pattern)) => {
+                            // the whole value must match
+                            regex::Regex::new(&format!(
+                                "^(?:{})$",
+                                String::from_utf8_lossy(pattern)
+                            ))
+                            .ok()
+                            .map(GrokFilter::NullIfMatches)
+                        }
+                        _ => None,
+                    },
+                    _ => None,
                 })
                 .ok_or_else(|| GrokStaticError::InvalidFunctionArguments(f.name.clone())),
             "array" => array::filter_from_function(f),
             "keyvalue" => keyvalue::filter_from_function(f),
+            "querystring" => Ok(GrokFilter::QueryString),
+            "boolean" => match f.args.as_ref() {
+                None => Ok(GrokFilter::Boolean("true".to_string(), "false".to_string())),
+                Some(args) if args.len() == 2 => match (&args[0], &args[1]) {
+                    (
+                        FunctionArgument::Arg(Value::Bytes(true_value)),
+                        FunctionArgument::Arg(Value::Bytes(false_value)),
+                    ) => Ok(GrokFilter::Boolean(
+                        String::from_utf8_lossy(true_value).to_lowercase(),
+                        String::from_utf8_lossy(false_value).to_lowercase(),
+                    )),
+                    _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+                },
+                _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+            },
+            "useragent" => useragent::filter_from_function(f),
+            "xml" => Ok(GrokFilter::Xml),
+            "csv" => csv::filter_from_function(f),
+            "rubyhash" => Ok(GrokFilter::RubyHash),
+            "decodeuricomponent" => decodeuricomponent::filter_from_function(f),
+            "url" => Ok(GrokFilter::Url),
+            // `decodeBase64` is kept as an alias of `base64`
+            "base64" | "decodeBase64" => match f.args.as_ref() {
+                None => Ok(GrokFilter::DecodeBase64(base64::STANDARD)),
+                Some(args) if args.len() == 1 => match &args[0] {
+                    FunctionArgument::Arg(Value::Bytes(charset)) => match charset.as_ref() {
+                        b"standard" => Ok(GrokFilter::DecodeBase64(base64::STANDARD)),
+                        b"url_safe" => Ok(GrokFilter::DecodeBase64(base64::URL_SAFE)),
+                        _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+                    },
+                    _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+                },
+                _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+            },
+            "base64url" if f.args.is_none() => Ok(GrokFilter::DecodeBase64(base64::URL_SAFE)),
+            "extract" => extract::filter_from_function(f),
+            "replace" => replace::filter_from_function(f),
+            "byteSize" => bytesize::filter_from_function(f),
+            "duration" => duration::filter_from_function(f),
+            "redact" => redact::filter_from_function(f),
+            "lookup" => lookup::filter_from_function(f),
+            "cidr" => cidr::filter_from_function(f),
             _ => Err(GrokStaticError::UnknownFilter(f.name.clone())),
         }
     }
 }
 
+/// Parses an optional set of characters to trim, e.g. `trim("-_ ")`.
+fn trim_chars_from_function(f: &Function) -> Result<Option<Vec<char>>, GrokStaticError> {
+    match f.args.as_ref() {
+        None => Ok(None),
+        Some(args) if args.len() == 1 => match &args[0] {
+            FunctionArgument::Arg(Value::Bytes(chars)) if !chars.is_empty() => {
+                Ok(Some(String::from_utf8_lossy(chars).chars().collect()))
+            }
+            _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+        },
+        _ => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+    }
+}
+
 /// Applies a given Grok filter to the value and returns the result or error.
 /// For detailed description and examples of specific filters check out https://docs.datadoghq.com/logs/log_configuration/parsing/?tab=filters
 pub fn apply_filter(value: &Value, filter: &GrokFilter) -> Result<Value, GrokRuntimeError> {
@@ -113,6 +330,17 @@ pub fn apply_filter(value: &Value, filter: &GrokFilter) -> Result<Value, GrokRun
                 value.to_string(),
             )),
         },
+        GrokFilter::IntegerRadix(radix) => match value {
+            Value::Bytes(v) => parse_integer_with_radix(&String::from_utf8_lossy(v), *radix)
+                .map(Value::Integer)
+                .ok_or_else(|| {
+                    GrokRuntimeError::FailedToApplyFilter(filter.to_string(), value.to_string())
+                }),
+            _ => Err(GrokRuntimeError::FailedToApplyFilter(
+                filter.to_string(),
+                value.to_string(),
+            )),
+        },
         GrokFilter::IntegerExt => match value {
             Value::Bytes(v) => Ok(String::from_utf8_lossy(v)
                 .parse::<f64>()
@@ -144,6 +372,7 @@ pub fn apply_filter(value: &Value, filter: &GrokFilter) -> Result<Value, GrokRun
                 value.to_string(),
             )),
         },
+        GrokFilter::LocaleNumber(format) => number::apply_filter(value, format),
         GrokFilter::Scale(scale_factor) => {
             let v = match value {
                 Value::Integer(v) => Ok(Value::Float(
@@ -184,17 +413,49 @@ pub fn apply_filter(value: &Value, filter: &GrokFilter) -> Result<Value, GrokRun
                 value.to_string(),
             )),
         },
-        GrokFilter::Json => match value {
-            Value::Bytes(bytes) => serde_json::from_slice::<'_, serde_json::Value>(bytes.as_ref())
-                .map_err(|_e| {
-                    GrokRuntimeError::FailedToApplyFilter(filter.to_string(), value.to_string())
-                })
-                .map(|v| v.into()),
+        GrokFilter::Trim(chars) | GrokFilter::TrimLeft(chars) | GrokFilter::TrimRight(chars) => {
+            match value {
+                Value::Bytes(bytes) => {
+                    let s = String::from_utf8_lossy(bytes);
+                    let is_trimmed = |c: char| match chars {
+                        Some(chars) => chars.contains(&c),
+                        None => c.is_whitespace(),
+                    };
+                    let trimmed = match filter {
+                        GrokFilter::TrimLeft(_) => s.trim_start_matches(is_trimmed),
+                        GrokFilter::TrimRight(_) => s.trim_end_matches(is_trimmed),
+                        _ => s.trim_matches(is_trimmed),
+                    };
+                    Ok(trimmed.into())
+                }
+                _ => Err(GrokRuntimeError::FailedToApplyFilter(
+                    filter.to_string(),
+                    value.to_string(),
+                )),
+            }
+        }
+        GrokFilter::Substring(start, len) => match value {
+            Value::Bytes(bytes) => {
+                let s = String::from_utf8_lossy(bytes);
+                if *start > s.chars().count() {
+                    return Err(GrokRuntimeError::FailedToApplyFilter(
+                        filter.to_string(),
+                        value.to_string(),
+                    ));
+                }
+                let substring = s.chars().skip(*start);
+                Ok(match len {
+                    Some(len) => substring.take(*len).collect::<String>(),
+                    None => substring.collect::<String>(),
+                }
+                .into())
+            }
             _ => Err(GrokRuntimeError::FailedToApplyFilter(
                 filter.to_string(),
                 value.to_string(),
             )),
         },
+        GrokFilter::Json(json_filter) => json::apply_filter(value, json_filter),
         GrokFilter::NullIf(null_value) => match value {
             Value::Bytes(bytes) => {
                 if String::from_utf8_lossy(bytes) == *null_value {
@@ -208,9 +469,74 @@ pub fn apply_filter(value: &Value, filter: &GrokFilter) -> Result<Value, GrokRun
                 value.to_string(),
             )),
         },
-        GrokFilter::Date(date_filter) => apply_date_filter(value, date_filter),
+        GrokFilter::NullIfMatches(re) => match value {
+            Value::Bytes(bytes) => {
+                if re.is_match(&String::from_utf8_lossy(bytes)) {
+                    Ok(Value::Null)
+                } else {
+                    Ok(value.to_owned())
+                }
+            }
+            _ => Err(GrokRuntimeError::FailedToApplyFilter(
+                filter.to_string(),
+                value.to_string(),
+            )),
+        },
+        GrokFilter::Date(date_filters) => apply_date_filters(value, date_filters),
         GrokFilter::KeyValue(keyvalue_filter) => keyvalue::apply_filter(value, keyvalue_filter),
-        GrokFilter::Array(brackets, delimiter, value_filter) => match value {
+        GrokFilter::QueryString => querystring::apply_filter(value),
+        GrokFilter::Boolean(true_value, false_value) => match value {
+            Value::Bytes(bytes) => {
+                let v = String::from_utf8_lossy(bytes).to_lowercase();
+                if v == *true_value {
+                    Ok(Value::Boolean(true))
+                } else if v == *false_value {
+                    Ok(Value::Boolean(false))
+                } else {
+                    Err(GrokRuntimeError::FailedToApplyFilter(
+                        filter.to_string(),
+                        value.to_string(),
+                    ))
+                }
+            }
+            _ => Err(GrokRuntimeError::FailedToApplyFilter(
+                filter.to_string(),
+                value.to_string(),
+            )),
+        },
+        GrokFilter::UserAgent(decode_uri_component) => {
+            useragent::apply_filter(value, *decode_uri_component)
+        }
+        GrokFilter::Xml => xml::apply_filter(value),
+        GrokFilter::Csv(csv_filter) => csv::apply_filter(value, csv_filter),
+        GrokFilter::RubyHash => rubyhash::apply_filter(value),
+        GrokFilter::DecodeUriComponent(plus_as_space) => {
+            decodeuricomponent::apply_filter(value, *plus_as_space)
+        }
+        GrokFilter::Url => url::apply_filter(value),
+        GrokFilter::Extract(re) => extract::apply_filter(value, re),
+        GrokFilter::Replace(re, replacement) => replace::apply_filter(value, re, replacement),
+        GrokFilter::ByteSize(units) => bytesize::apply_filter(value, *units),
+        GrokFilter::Duration(unit) => duration::apply_filter(value, *unit),
+        GrokFilter::Redact(redaction) => redact::apply_filter(value, redaction),
+        GrokFilter::Lookup(table, default) => lookup::apply_filter(value, table, default),
+        GrokFilter::Ip(format) => ip::apply_filter(value, *format),
+        GrokFilter::Cidr(cidr_filter) => cidr::apply_filter(value, cidr_filter),
+        GrokFilter::Measure => measure::apply_filter(value),
+        GrokFilter::Uuid => uuid::apply_filter(value),
+        GrokFilter::Mac => mac::apply_filter(value),
+        GrokFilter::DecodeBase64(config) => match value {
+            Value::Bytes(bytes) => base64::decode_config(bytes, *config)
+                .map(|decoded| Value::Bytes(decoded.into()))
+                .map_err(|_e| {
+                    GrokRuntimeError::FailedToApplyFilter(filter.to_string(), value.to_string())
+                }),
+            _ => Err(GrokRuntimeError::FailedToApplyFilter(
+                filter.to_string(),
+                value.to_string(),
+            )),
+        },
+        GrokFilter::Array(brackets, delimiter, value_filter, invalid_elements) => match value {
             Value::Bytes(bytes) => array::parse(
                 String::from_utf8_lossy(bytes).as_ref(),
                 brackets
@@ -224,8 +550,13 @@ pub fn apply_filter(value: &Value, filter: &GrokFilter) -> Result<Value, GrokRun
             .and_then(|values| {
                 if let Some(value_filter) = value_filter.as_ref() {
                     let result = values
-                        .iter()
-                        .map(|v| apply_filter(v, value_filter))
+                        .into_iter()
+                        .filter_map(|v| match apply_filter(&v, value_filter) {
+                            Ok(v) => Some(Ok(v)),
+                            Err(_) if *invalid_elements == InvalidElements::Skip => None,
+                            Err(_) if *invalid_elements == InvalidElements::Keep => Some(Ok(v)),
+                            Err(error) => Some(Err(error)),
+                        })
                         .collect::<Result<Vec<Value>, _>>()
                         .map(Value::from);
                     return result;
@@ -239,3 +570,26 @@ pub fn apply_filter(value: &Value, filter: &GrokFilter) -> Result<Value, GrokRun
         },
     }
 }
+
+/// Parses an integer in a given radix, allowing an optional sign and a radix prefix(`0x`, `0o`, `0b`).
+fn parse_integer_with_radix(s: &str, radix: u32) -> Option<i64> {
+    let s = s.trim();
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let prefix = match radix {
+        16 => Some(["0x", "0X"]),
+        8 => Some(["0o", "0O"]),
+        2 => Some(["0b", "0B"]),
+        _ => None,
+    };
+    let digits = prefix
+        .and_then(|prefix| prefix.iter().find_map(|p| digits.strip_prefix(p)))
+        .unwrap_or(digits);
+    if digits.starts_with(['-', '+']) {
+        return None;
+    }
+    let v = i64::from_str_radix(digits, radix).ok()?;
+    Some(if negative { -v } else { v })
+}
@@ -10,6 +10,7 @@ pub type Spanned<'input, Loc> = (Loc, Tok<'input>, Loc);
 pub enum Token<S> {
     LRule,
     RRule,
+    LBrace,
     LBracket,
     RBracket,
     Colon,
@@ -65,6 +66,7 @@ impl<'input> Iterator for Lexer<'input> {
                         self.bump();
                         Some(Ok(self.token(start, LRule)))
                     }
+                    '{' => Some(Ok(self.token(start, LBrace))),
                     '}' => Some(Ok(self.token(start, RRule))),
                     '[' => Some(Ok(self.token(start, LBracket))),
                     ']' => Some(Ok(self.token(start, RBracket))),
@@ -15,3 +15,6 @@ mod matchers;
 pub mod parse_grok;
 mod parse_grok_pattern;
 pub mod parse_grok_rules;
+mod prefilter;
+mod raw_bytes;
+mod stats;
@@ -1,6 +1,6 @@
 use std::fmt::Formatter;
 
-use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
 use chrono_tz::{Tz, UTC};
 use peeking_take_while::PeekableExt;
 use regex::Regex;
@@ -101,22 +101,47 @@ pub struct RegexResult {
     pub tz_captured: bool,
 }
 
-pub fn parse_timezone(tz: &str) -> Result<FixedOffset, String> {
+/// A timezone used to interpret naive dates: either a fixed offset or an IANA timezone,
+/// whose offset depends on the date(e.g. due to DST).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timezone {
+    Fixed(FixedOffset),
+    Named(Tz),
+}
+
+impl Timezone {
+    /// Converts a naive local date/time in this timezone to a UTC timestamp in milliseconds.
+    /// Ambiguous local times(e.g. when clocks go back) resolve to the earliest instant,
+    /// non-existent ones(e.g. when clocks go forward) return `None`.
+    pub fn local_to_timestamp_millis(&self, dt: &NaiveDateTime) -> Option<i64> {
+        match self {
+            Timezone::Fixed(offset) => offset
+                .from_local_datetime(dt)
+                .earliest()
+                .map(|dt| dt.timestamp_millis()),
+            Timezone::Named(tz) => tz
+                .from_local_datetime(dt)
+                .earliest()
+                .map(|dt| dt.timestamp_millis()),
+        }
+    }
+}
+
+pub fn parse_timezone(tz: &str) -> Result<Timezone, String> {
     let tz = match tz {
-        "GMT" | "UTC" | "UT" | "Z" => FixedOffset::east(0),
-        _ if tz.starts_with('+') || tz.starts_with('-') => parse_offset(tz)?,
-        _ if tz.contains('+') => parse_offset(&tz[tz.find('+').unwrap()..])?,
-        _ if tz.contains('-') => parse_offset(&tz[tz.find('-').unwrap()..])?,
-        tz => parse_tz_id_or_name(tz)?,
+        "GMT" | "UTC" | "UT" | "Z" => Timezone::Fixed(FixedOffset::east(0)),
+        _ if tz.starts_with('+') || tz.starts_with('-') => Timezone::Fixed(parse_offset(tz)?),
+        _ if tz.contains('+') && !tz.contains('/') => {
+            Timezone::Fixed(parse_offset(&tz[tz.find('+').unwrap()..])?)
+        }
+        _ if tz.contains('-') && !tz.contains('/') => {
+            Timezone::Fixed(parse_offset(&tz[tz.find('-').unwrap()..])?)
+        }
+        tz => Timezone::Named(tz.parse::<Tz>()?),
     };
     Ok(tz)
 }
 
-fn parse_tz_id_or_name(tz: &str) -> Result<FixedOffset, String> {
-    let tz = tz.parse::<Tz>()?;
-    Ok(Utc::now().with_timezone(&tz).offset().fix())
-}
-
 fn parse_offset(tz: &str) -> Result<FixedOffset, String> {
     if tz.len() <= 3 {
         // +5, -12
@@ -226,7 +251,80 @@ pub fn time_format_to_regex(
     })
 }
 
+/// Units of epoch timestamps, supported by `date("UNIX")`, `date("UNIX_MS")` and `date("UNIX_NS")`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EpochUnit {
+    Seconds,
+    Milliseconds,
+    Nanoseconds,
+}
+
+impl EpochUnit {
+    pub fn from_format(format: &str) -> Option<Self> {
+        match format {
+            "UNIX" => Some(EpochUnit::Seconds),
+            "UNIX_MS" => Some(EpochUnit::Milliseconds),
+            "UNIX_NS" => Some(EpochUnit::Nanoseconds),
+            _ => None,
+        }
+    }
+
+    /// A regex matching an epoch timestamp with an optional fraction
+    pub const REGEX: &'static str = r"-?\d+(?:\.\d+)?";
+
+    fn nanos(&self) -> i128 {
+        match self {
+            EpochUnit::Seconds => 1_000_000_000,
+            EpochUnit::Milliseconds => 1_000_000,
+            EpochUnit::Nanoseconds => 1,
+        }
+    }
+
+    /// Parses an epoch timestamp, e.g. `1466065743.123456`, preserving sub-second precision
+    /// down to nanoseconds.
+    pub fn parse(&self, value: &str) -> Option<DateTime<Utc>> {
+        let (negative, value) = match value.strip_prefix('-') {
+            Some(value) => (true, value),
+            None => (false, value),
+        };
+        let (integral, fraction) = value.split_once('.').unwrap_or((value, ""));
+        if integral.is_empty() || !integral.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        let mut nanos = integral.parse::<i128>().ok()?.checked_mul(self.nanos())?;
+        // the fraction is only meaningful up to the nanosecond
+        let mut scale = self.nanos();
+        for digit in fraction.bytes() {
+            scale /= 10;
+            if scale == 0 {
+                break;
+            }
+            nanos += i128::from(digit - b'0') * scale;
+        }
+        if negative {
+            nanos = -nanos;
+        }
+
+        let secs = i64::try_from(nanos.div_euclid(1_000_000_000)).ok()?;
+        let nsecs = nanos.rem_euclid(1_000_000_000) as u32;
+        Utc.timestamp_opt(secs, nsecs).single()
+    }
+}
+
 pub fn apply_date_filter(value: &Value, filter: &DateFilter) -> Result<Value, GrokRuntimeError> {
+    if let (Some(epoch), Value::Bytes(bytes)) = (&filter.epoch, value) {
+        return epoch
+            .parse(String::from_utf8_lossy(bytes).trim())
+            .map(Value::Timestamp)
+            .ok_or_else(|| {
+                GrokRuntimeError::FailedToApplyFilter(filter.to_string(), value.to_string())
+            });
+    }
+
     match value {
         Value::Bytes(bytes) => {
             let mut value = String::from_utf8_lossy(bytes).into_owned();
@@ -292,32 +390,14 @@ pub fn apply_date_filter(value: &Value, filter: &DateFilter) -> Result<Value, Gr
                         NaiveDateTime::parse_from_str(&value, &filter.strp_format)
                     {
                         // try parsing as a naive datetime
-                        if let Some(tz) = &filter.target_tz {
-                            let tzs = parse_timezone(tz).map_err(|error| {
-                                warn!(message = "Error parsing tz", tz = %tz, % error);
-                                GrokRuntimeError::FailedToApplyFilter(
-                                    filter.to_string(),
-                                    value.to_string(),
-                                )
-                            })?;
-                            let dt = tzs.from_local_datetime(&dt).single().ok_or_else(|| {
-                                GrokRuntimeError::FailedToApplyFilter(
-                                    filter.to_string(),
-                                    value.to_string(),
-                                )
-                            })?;
-                            Ok(Utc
-                                .from_utc_datetime(&dt.naive_utc())
-                                .timestamp_millis()
-                                .into())
-                        } else {
-                            Ok(dt.timestamp_millis().into())
-                        }
+                        local_to_timestamp_millis(&dt, filter, &value)
                     } else if let Ok(nt) = NaiveTime::parse_from_str(&value, &filter.strp_format) {
                         // try parsing as a naive time
-                        Ok(NaiveDateTime::new(NaiveDate::from_ymd(1970, 1, 1), nt)
-                            .timestamp_millis()
-                            .into())
+                        local_to_timestamp_millis(
+                            &NaiveDateTime::new(NaiveDate::from_ymd(1970, 1, 1), nt),
+                            filter,
+                            &value,
+                        )
                     } else {
                         // try parsing as a naive date
                         let nd = NaiveDate::parse_from_str(&value, &filter.strp_format).map_err(
@@ -329,20 +409,11 @@ pub fn apply_date_filter(value: &Value, filter: &DateFilter) -> Result<Value, Gr
                                 )
                             },
                         )?;
-                        Ok(UTC
-                            .from_local_datetime(&NaiveDateTime::new(
-                                nd,
-                                NaiveTime::from_hms(0, 0, 0),
-                            ))
-                            .single()
-                            .ok_or_else(|| {
-                                GrokRuntimeError::FailedToApplyFilter(
-                                    filter.to_string(),
-                                    value.to_string(),
-                                )
-                            })?
-                            .timestamp_millis()
-                            .into())
+                        local_to_timestamp_millis(
+                            &NaiveDateTime::new(nd, NaiveTime::from_hms(0, 0, 0)),
+                            filter,
+                            &value,
+                        )
                     }
                 }
             }
@@ -354,6 +425,50 @@ pub fn apply_date_filter(value: &Value, filter: &DateFilter) -> Result<Value, Gr
     }
 }
 
+/// Tries to parse a date with each of the given filters in order, returning the first match.
+pub fn apply_date_filters(
+    value: &Value,
+    filters: &[DateFilter],
+) -> Result<Value, GrokRuntimeError> {
+    match filters {
+        [filter] => apply_date_filter(value, filter),
+        _ => filters
+            .iter()
+            .find_map(|filter| apply_date_filter(value, filter).ok())
+            .ok_or_else(|| {
+                let formats = filters
+                    .iter()
+                    .map(|filter| format!("{:?}", filter.original_format))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                warn!(
+                    message = "Error parsing date with any of the formats",
+                    date = %value,
+                    formats = %formats
+                );
+                GrokRuntimeError::FailedToApplyFilter(
+                    format!("date([{}])", formats),
+                    value.to_string(),
+                )
+            }),
+    }
+}
+
+/// Converts a naive date/time to a timestamp in the target timezone of the filter, if any,
+/// or in UTC otherwise.
+fn local_to_timestamp_millis(
+    dt: &NaiveDateTime,
+    filter: &DateFilter,
+    value: &str,
+) -> Result<Value, GrokRuntimeError> {
+    filter
+        .target_tz
+        .unwrap_or(Timezone::Named(UTC))
+        .local_to_timestamp_millis(dt)
+        .map(Into::into)
+        .ok_or_else(|| GrokRuntimeError::FailedToApplyFilter(filter.to_string(), value.to_string()))
+}
+
 #[derive(Debug, Clone)]
 pub struct DateFilter {
     // an original date format used for debugging purposes
@@ -364,8 +479,10 @@ pub struct DateFilter {
     pub tz_aware: bool,
     // an optional regex, which is used only when we need to extract a TZ name(always contains "tz" capture)
     pub regex_with_tz: Option<Regex>,
-    // an optional target TZ name
-    pub target_tz: Option<String>,
+    // an optional target TZ, used to interpret naive dates
+    pub target_tz: Option<Timezone>,
+    // set if the date is an epoch timestamp rather than a formatted date
+    pub epoch: Option<EpochUnit>,
 }
 
 impl std::fmt::Display for DateFilter {
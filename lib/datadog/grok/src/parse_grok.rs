@@ -1,11 +1,14 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, time::Instant};
 
 use tracing::warn;
 use value::Value;
 
+pub use crate::prefilter::GrokPrefilter;
+pub use crate::stats::{GrokStats, RuleStats};
 use crate::{
     grok_filter::apply_filter,
-    parse_grok_rules::{GrokField, GrokRule},
+    parse_grok_rules::{GrokField, GrokRule, OnMatchAction},
+    raw_bytes,
 };
 
 #[derive(thiserror::Error, Debug, PartialEq, Eq)]
@@ -14,30 +17,111 @@ pub enum Error {
     FailedToApplyFilter(String, String),
     #[error("value does not match any rule")]
     NoMatch,
+    #[error("matching exceeded the budget of {} backtracking steps", .0)]
+    MatchBudgetExceeded(u32),
+}
+
+/// Options controlling how values are parsed with grok rules.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ParseOptions<'a> {
+    /// a pre-filter, built for the rules, to only try the rules it finds as candidates for a value
+    pub prefilter: Option<&'a GrokPrefilter>,
+    /// the maximum number of backtracking steps matching a value against a rule may take,
+    /// parsing is aborted with `MatchBudgetExceeded` once it is exceeded
+    pub match_budget: Option<u32>,
+    /// match counters to update, created for the same rules
+    pub stats: Option<&'a GrokStats>,
 }
 
 /// Parses a given source field value by applying the list of grok rules until the first match found.
 pub fn parse_grok(source_field: &str, grok_rules: &[GrokRule]) -> Result<Value, Error> {
-    for rule in grok_rules {
-        match apply_grok_rule(source_field, rule) {
+    parse_grok_with_options(source_field, grok_rules, ParseOptions::default())
+}
+
+/// Parses a given source field value just like `parse_grok`, with the given options.
+pub fn parse_grok_with_options(
+    source_field: &str,
+    grok_rules: &[GrokRule],
+    options: ParseOptions<'_>,
+) -> Result<Value, Error> {
+    let candidates = match options.prefilter {
+        Some(prefilter) => prefilter.candidates(source_field),
+        None => (0..grok_rules.len()).collect(),
+    };
+    for i in candidates {
+        let result = match options.stats {
+            Some(stats) => {
+                let start = Instant::now();
+                let result = apply_grok_rule(source_field, &grok_rules[i], options.match_budget);
+                stats.record_attempt(i, result.is_ok(), start.elapsed());
+                result
+            }
+            None => apply_grok_rule(source_field, &grok_rules[i], options.match_budget),
+        };
+        match result {
             Err(Error::NoMatch) => continue,
             other => return other,
         }
     }
+    if let Some(stats) = options.stats {
+        stats.record_no_match();
+    }
     Err(Error::NoMatch)
 }
 
+/// Parses raw bytes just like `parse_grok_with_options`, without decoding them lossily first.
+/// Bytes, which are not valid UTF-8, are preserved as is in the parsed values.
+pub fn parse_grok_bytes(
+    source_field: &[u8],
+    grok_rules: &[GrokRule],
+    options: ParseOptions<'_>,
+) -> Result<Value, Error> {
+    match std::str::from_utf8(source_field) {
+        Ok(source_field) => parse_grok_with_options(source_field, grok_rules, options),
+        Err(_) => {
+            let escaped = raw_bytes::escape(source_field);
+            parse_grok_with_options(&escaped, grok_rules, options).map(raw_bytes::unescape_value)
+        }
+    }
+}
+
 /// Tries to parse a given string with a given grok rule.
 /// Returns a result value or an error otherwise.
 /// Possible errors:
 /// - FailedToApplyFilter - matches the rule, but there was a runtime error while applying on of the filters
 /// - NoMatch - this rule does not match a given string
-fn apply_grok_rule(source: &str, grok_rule: &GrokRule) -> Result<Value, Error> {
+/// - MatchBudgetExceeded - matching this rule takes more backtracking steps than allowed
+fn apply_grok_rule(
+    source: &str,
+    grok_rule: &GrokRule,
+    match_budget: Option<u32>,
+) -> Result<Value, Error> {
     let mut parsed = Value::Object(BTreeMap::new());
 
-    if let Some(ref matches) = grok_rule.pattern.match_against(source) {
+    let matches = match match_budget {
+        Some(budget) => grok_rule
+            .pattern
+            .match_against_with_limit(source, budget)
+            .map_err(|_| Error::MatchBudgetExceeded(budget))?,
+        None => grok_rule.pattern.match_against(source),
+    };
+    if let Some(ref matches) = matches {
         for (name, match_str) in matches.iter() {
+            let match_str = match match_str {
+                Some(match_str) => match_str,
+                None => continue,
+            };
+            let grok_field = grok_rule.fields.get(name);
+
             if match_str.is_empty() {
+                let keep_empty = match grok_field {
+                    Some(grok_field) => grok_field.keep_empty.clone(),
+                    None => grok_rule.keep_empty.then(|| Value::from("")),
+                };
+                // empty captures are either dropped or kept as is, without applying filters
+                if let Some(value) = keep_empty {
+                    insert_field(&mut parsed, name, grok_field, value);
+                }
                 continue;
             }
 
@@ -46,7 +130,8 @@ fn apply_grok_rule(source: &str, grok_rule: &GrokRule) -> Result<Value, Error> {
             if let Some(GrokField {
                 lookup: field,
                 filters,
-            }) = grok_rule.fields.get(name)
+                ..
+            }) = grok_field
             {
                 filters.iter().for_each(|filter| {
                     if let Some(ref v) = value {
@@ -59,55 +144,97 @@ fn apply_grok_rule(source: &str, grok_rule: &GrokRule) -> Result<Value, Error> {
                         }
                     }
                 });
+            }
 
-                if let Some(value) = value {
-                    match value {
-                        // root-level maps must be merged
-                        Value::Object(map) if field.is_root() => {
-                            parsed.as_object_mut().expect("root is object").extend(map);
-                        }
-                        // anything else at the root leve must be ignored
-                        _ if field.is_root() => {}
-                        // otherwise just apply VRL lookup insert logic
-                        _ => match parsed.get_by_path(field).cloned() {
-                            Some(Value::Array(mut values)) => {
-                                values.push(value);
-                                parsed.insert_by_path(field, values.into());
-                            }
-                            Some(v) => {
-                                parsed.insert_by_path(field, Value::Array(vec![v, value]));
-                            }
-                            None => {
-                                parsed.insert_by_path(field, value);
-                            }
-                        },
-                    };
-                }
-            } else {
-                // this must be a regex named capturing group (?<name>group),
-                // where name can only be alphanumeric - thus we do not need to parse field names(no nested fields)
-                parsed
-                    .as_object_mut()
-                    .expect("parsed value is not an object")
-                    .insert(name.to_string(), value.into());
+            if let Some(value) = value {
+                insert_field(&mut parsed, name, grok_field, value);
             }
         }
 
+        apply_on_match(&mut parsed, &grok_rule.on_match);
+
         Ok(parsed)
     } else {
         Err(Error::NoMatch)
     }
 }
 
+/// Executes post-match actions of a rule on the parsed value.
+fn apply_on_match(parsed: &mut Value, actions: &[OnMatchAction]) {
+    for action in actions {
+        match action {
+            // the root can't be moved or removed, keeping the parsed value an object
+            OnMatchAction::Rename(from, to) if from.is_root() || to.is_root() => {}
+            OnMatchAction::Remove(path) if path.is_root() => {}
+            OnMatchAction::Rename(from, to) => {
+                if let Some(value) = parsed.remove_by_path(from, true) {
+                    parsed.insert_by_path(to, value);
+                }
+            }
+            OnMatchAction::Remove(path) => {
+                parsed.remove_by_path(path, true);
+            }
+            OnMatchAction::Retain(paths) => {
+                let mut retained = Value::Object(BTreeMap::new());
+                for path in paths {
+                    if let Some(value) = parsed.get_by_path(path) {
+                        retained.insert_by_path(path, value.clone());
+                    }
+                }
+                *parsed = retained;
+            }
+        }
+    }
+}
+
+/// Inserts a value captured by a group with a given name into the parsed value.
+fn insert_field(parsed: &mut Value, name: &str, grok_field: Option<&GrokField>, value: Value) {
+    match grok_field {
+        Some(GrokField { lookup: field, .. }) => match value {
+            // root-level maps must be merged
+            Value::Object(map) if field.is_root() => {
+                parsed.as_object_mut().expect("root is object").extend(map);
+            }
+            // anything else at the root leve must be ignored
+            _ if field.is_root() => {}
+            // otherwise just apply VRL lookup insert logic
+            _ => match parsed.get_by_path(field).cloned() {
+                Some(Value::Array(mut values)) => {
+                    values.push(value);
+                    parsed.insert_by_path(field, values.into());
+                }
+                Some(v) => {
+                    parsed.insert_by_path(field, Value::Array(vec![v, value]));
+                }
+                None => {
+                    parsed.insert_by_path(field, value);
+                }
+            },
+        },
+        None => {
+            // this must be a regex named capturing group (?<name>group),
+            // where name can only be alphanumeric - thus we do not need to parse field names(no nested fields)
+            parsed
+                .as_object_mut()
+                .expect("parsed value is not an object")
+                .insert(name.to_string(), value);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use chrono::{TimeZone, Utc};
+    use lookup::LookupBuf;
     use ordered_float::NotNan;
     use tracing_test::traced_test;
     use value::Value;
     use vector_common::btreemap;
 
     use super::*;
-    use crate::parse_grok_rules::parse_grok_rules;
+    use crate::parse_grok_rules::{
+        parse_grok_rules, parse_grok_rules_with_options, CompileOptions,
+    };
 
     #[test]
     fn parses_simple_grok() {
@@ -194,7 +321,198 @@ mod tests {
             ("%{integerExt:field}", "-2", Ok(Value::from(-2))),
             ("%{integerExt:field}", "-1e+2", Ok(Value::from(-100))),
             ("%{integerExt:field}", "1234.1e+5", Err(Error::NoMatch)),
+            // string matchers validate the shape, but keep the value as is
+            ("%{integerStr:field}", "007", Ok(Value::from("007"))),
+            (
+                "%{integerStr:field}",
+                "123456789012345678901234567890",
+                Ok(Value::from("123456789012345678901234567890")),
+            ),
+            ("%{integerStr:field}", "1.5", Err(Error::NoMatch)),
+            ("%{numberStr:field}", "-0.50", Ok(Value::from("-0.50"))),
+            ("%{numberStr:field}", "1e5", Err(Error::NoMatch)),
+            ("%{integerExtStr:field}", "1e5", Ok(Value::from("1e5"))),
+            (
+                "%{numberExtStr:field}",
+                "1.50E+5",
+                Ok(Value::from("1.50E+5")),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn supports_measure_matcher() {
+        test_grok_pattern(vec![
+            (
+                "%{measure:field}",
+                "25ms",
+                Ok(Value::from(btreemap! {
+                    "value" => 25,
+                    "unit" => "ms",
+                })),
+            ),
+            (
+                "%{measure:field}",
+                "3.4 GB",
+                Ok(Value::from(btreemap! {
+                    "value" => 3.4,
+                    "unit" => "GB",
+                })),
+            ),
+            (
+                "%{measure:field}",
+                "99%",
+                Ok(Value::from(btreemap! {
+                    "value" => 99,
+                    "unit" => "%",
+                })),
+            ),
+            ("%{measure:field}", "25", Err(Error::NoMatch)),
+        ]);
+    }
+
+    #[test]
+    fn supports_uuid_and_mac_matchers() {
+        test_grok_pattern(vec![
+            (
+                "%{uuid:field}",
+                "123E4567-E89B-12D3-A456-426614174000",
+                Ok(Value::from("123e4567-e89b-12d3-a456-426614174000")),
+            ),
+            (
+                "%{uuid:field}",
+                "{123e4567-e89b-12d3-a456-426614174000}",
+                Ok(Value::from("123e4567-e89b-12d3-a456-426614174000")),
+            ),
+            (
+                "%{uuid:field}",
+                "urn:uuid:123e4567-e89b-12d3-a456-426614174000",
+                Ok(Value::from("123e4567-e89b-12d3-a456-426614174000")),
+            ),
+            (
+                "%{uuid:field}",
+                "123e4567e89b12d3a456426614174000",
+                Ok(Value::from("123e4567-e89b-12d3-a456-426614174000")),
+            ),
+            ("%{uuid:field}", "123e4567-e89b", Err(Error::NoMatch)),
+            (
+                "%{mac:field}",
+                "01-23-45-67-89-AB",
+                Ok(Value::from("01:23:45:67:89:ab")),
+            ),
+            (
+                "%{mac:field}",
+                "0123.4567.89ab",
+                Ok(Value::from("01:23:45:67:89:ab")),
+            ),
+            (
+                "%{mac:field}",
+                "01:23:45:67:89:AB",
+                Ok(Value::from("01:23:45:67:89:ab")),
+            ),
+            ("%{mac:field}", "01:23:45", Err(Error::NoMatch)),
+        ]);
+    }
+
+    #[test]
+    fn supports_boolean_matcher() {
+        test_grok_pattern(vec![
+            ("%{boolean:field}", "true", Ok(Value::Boolean(true))),
+            ("%{boolean:field}", "FALSE", Ok(Value::Boolean(false))),
+            ("%{boolean:field}", "yes", Err(Error::NoMatch)),
+            (
+                r#"%{boolean("yes", "no"):field}"#,
+                "Yes",
+                Ok(Value::Boolean(true)),
+            ),
+        ]);
+
+        test_full_grok(vec![(
+            r#"enabled=%{boolean("on", "off"):enabled} %{word:rest}"#,
+            "enabled=off done",
+            Ok(Value::from(btreemap! {
+                "enabled" => false,
+                "rest" => "done",
+            })),
+        )]);
+    }
+
+    #[test]
+    fn supports_ip_matchers() {
+        test_grok_pattern(vec![
+            ("%{ipv4:field}", "10.0.0.1", Ok(Value::from("10.0.0.1"))),
+            ("%{ipv4:field}", "10.0.0.256", Err(Error::NoMatch)),
+            (
+                "%{ipv6:field}",
+                "::ffff:192.168.0.1",
+                Ok(Value::from("::ffff:192.168.0.1")),
+            ),
+            (
+                "%{ipv6:field}",
+                "fe80::1%eth0",
+                Ok(Value::from("fe80::1%eth0")),
+            ),
+            ("%{ipv6:field}", "2001:db8:::1", Err(Error::NoMatch)),
+            (
+                r#"%{ipv6("compressed"):field}"#,
+                "2001:DB8:0:0:0:0:0:1",
+                Ok(Value::from("2001:db8::1")),
+            ),
+            (
+                r#"%{ip("expanded"):field}"#,
+                "::1",
+                Ok(Value::from("0000:0000:0000:0000:0000:0000:0000:0001")),
+            ),
+            (
+                r#"%{ipOrHost("compressed"):field}"#,
+                "Example.COM",
+                Ok(Value::from("example.com")),
+            ),
         ]);
+
+        test_full_grok(vec![(
+            "%{ipv6:ip} %{word:rest}",
+            "2001:db8::1 done",
+            Ok(Value::from(btreemap! {
+                "ip" => "2001:db8::1",
+                "rest" => "done",
+            })),
+        )]);
+
+        test_grok_pattern(vec![
+            ("%{cidr:field}", "10.0.0.0/8", Ok(Value::from("10.0.0.0/8"))),
+            ("%{cidr:field}", "fd00::/8", Ok(Value::from("fd00::/8"))),
+            ("%{cidr:field}", "10.0.0.0/33", Err(Error::NoMatch)),
+            (
+                r#"%{ip:field:cidr("10.0.0.0/8", "192.168.0.0/16")}"#,
+                "192.168.1.10",
+                Ok(Value::from("192.168.0.0/16")),
+            ),
+            (
+                r#"%{ip:field:cidr("10.0.0.0/8", boolean)}"#,
+                "192.168.1.10",
+                Ok(Value::from(false)),
+            ),
+        ]);
+
+        assert_eq!(
+            parse_grok_rules(
+                &[r#"%{ip:field:cidr("10.0.0.0/88")}"#.to_string()],
+                BTreeMap::new()
+            )
+            .unwrap_err()
+            .to_string(),
+            r#"invalid arguments for the function 'cidr'"#
+        );
+        assert_eq!(
+            parse_grok_rules(
+                &[r#"%{ipv6("unknown"):field}"#.to_string()],
+                BTreeMap::new()
+            )
+            .unwrap_err()
+            .to_string(),
+            r#"invalid arguments for the function 'ipv6'"#
+        );
     }
 
     #[test]
@@ -214,26 +532,509 @@ mod tests {
             ),
             ("%{integer:field:scale(10)}", "1", Ok(Value::from(10))),
             ("%{number:field:scale(0.5)}", "10.0", Ok(Value::from(5))),
+            ("%{data:field:boolean}", "True", Ok(Value::Boolean(true))),
+            ("%{data:field:boolean}", "false", Ok(Value::Boolean(false))),
+            (
+                r#"%{data:field:boolean("yes", "no")}"#,
+                "YES",
+                Ok(Value::Boolean(true)),
+            ),
+            (
+                r#"%{data:field:boolean("yes", "no")}"#,
+                "no",
+                Ok(Value::Boolean(false)),
+            ),
+            ("%{data:field:integer(16)}", "0x1A2B", Ok(Value::from(6699))),
+            ("%{data:field:integer(16)}", "-ff", Ok(Value::from(-255))),
+            ("%{data:field:integer(8)}", "0755", Ok(Value::from(493))),
+            ("%{data:field:number(2)}", "0b101", Ok(Value::from(5))),
+            (
+                r#"%{data:field:number("de_DE")}"#,
+                "1.234,56",
+                Ok(Value::from(1234.56)),
+            ),
+            (
+                r#"%{data:field:number(" ", ",")}"#,
+                "1 234,5",
+                Ok(Value::from(1234.5)),
+            ),
+            (
+                r#"%{data:field:extract("id=(\\d+)")}"#,
+                "user id=42",
+                Ok(Value::Bytes("42".into())),
+            ),
+            (
+                r#"%{data:field:replace("\\d", "x")}"#,
+                "id-123",
+                Ok(Value::Bytes("id-xxx".into())),
+            ),
+            (
+                "%{data:field:byteSize}",
+                "1.5MB",
+                Ok(Value::from(1_500_000)),
+            ),
+            (
+                r#"%{data:field:byteSize("binary")}"#,
+                "512k",
+                Ok(Value::from(524_288)),
+            ),
+            (
+                "%{data:field:duration}",
+                "1h30m",
+                Ok(Value::from(5_400_000)),
+            ),
+            (
+                r#"%{data:field:duration("s")}"#,
+                "250ms",
+                Ok(Value::from(0.25)),
+            ),
+            ("%{data:field:redact}", "secret", Ok(Value::from("****"))),
+            (
+                "%{data:field:redact(keepLast(4))}",
+                "4111111111111111",
+                Ok(Value::from("************1111")),
+            ),
+            (
+                r#"%{data:field:lookup({"0": "emergency", "1": "alert"})}"#,
+                "1",
+                Ok(Value::from("alert")),
+            ),
+            (
+                r#"%{data:field:lookup({0: "emergency"}, "unknown")}"#,
+                "7",
+                Ok(Value::from("unknown")),
+            ),
+            ("%{data:field:trim}", "  abc \t", Ok(Value::from("abc"))),
+            (
+                "%{data:field:trimLeft}",
+                "  abc  ",
+                Ok(Value::from("abc  ")),
+            ),
+            (
+                "%{data:field:trimRight}",
+                "  abc  ",
+                Ok(Value::from("  abc")),
+            ),
+            (
+                r#"%{data:field:trim("0 ")}"#,
+                "000123 ",
+                Ok(Value::from("123")),
+            ),
+            (
+                "%{data:field:substring(3, 4)}",
+                "ABC2022XYZ",
+                Ok(Value::from("2022")),
+            ),
+            (
+                "%{data:field:substring(7)}",
+                "ABC2022XYZ",
+                Ok(Value::from("XYZ")),
+            ),
+            (
+                "%{data:field:substring(1, 2)}",
+                "äöüß",
+                Ok(Value::from("öü")),
+            ),
+            (
+                "%{data:field:base64}",
+                "aGVsbG8gd29ybGQ=",
+                Ok(Value::Bytes("hello world".into())),
+            ),
+            (
+                "%{data:field:decodeBase64}",
+                "aGVsbG8gd29ybGQ=",
+                Ok(Value::Bytes("hello world".into())),
+            ),
+            (
+                "%{data:field:base64url}",
+                "Pz8_Pw==",
+                Ok(Value::Bytes("????".into())),
+            ),
+            (
+                r#"%{data:field:base64("url_safe")}"#,
+                "Pz8_Pw==",
+                Ok(Value::Bytes("????".into())),
+            ),
+            // not valid UTF-8, so kept as raw bytes
+            (
+                "%{data:field:base64}",
+                "/wA=",
+                Ok(Value::Bytes(vec![0xff, 0x00].into())),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn fails_on_invalid_radix() {
+        assert_eq!(
+            parse_grok_rules(&["%{data:field:integer(37)}".to_string()], BTreeMap::new())
+                .unwrap_err()
+                .to_string(),
+            r#"invalid arguments for the function 'integer'"#
+        );
+    }
+
+    #[test]
+    fn fails_on_unknown_number_locale() {
+        assert_eq!(
+            parse_grok_rules(
+                &[r#"%{data:field:number("xx_XX")}"#.to_string()],
+                BTreeMap::new()
+            )
+            .unwrap_err()
+            .to_string(),
+            r#"invalid arguments for the function 'number'"#
+        );
+    }
+
+    #[test]
+    fn ignores_field_if_array_element_filter_fails() {
+        test_full_grok(vec![(
+            r#"%{data:field:array(integer)}"#,
+            "[1,x,3]",
+            Ok(Value::from(BTreeMap::new())),
+        )]);
+    }
+
+    #[test]
+    fn supports_json_filter_options() {
+        test_full_grok(vec![
+            (
+                r#"%{data::json(flatten)}"#,
+                r#"{"http": {"status": 200, "url": "/"}}"#,
+                Ok(Value::from(btreemap! {
+                    "http.status" => 200,
+                    "http.url" => "/",
+                })),
+            ),
+            (
+                r#"%{data:payload:json(maxDepth(1))}"#,
+                r#"{"http": {"status": 200}}"#,
+                Ok(Value::from(btreemap! {
+                    "payload" => btreemap! {
+                        "http" => r#"{"status":200}"#,
+                    },
+                })),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn fails_on_invalid_json_filter_options() {
+        assert_eq!(
+            parse_grok_rules(&["%{data::json(maxDepth(0))}".to_string()], BTreeMap::new())
+                .unwrap_err()
+                .to_string(),
+            r#"invalid arguments for the function 'json'"#
+        );
+    }
+
+    #[test]
+    fn supports_chained_filters() {
+        test_grok_pattern(vec![
+            (
+                "%{data:field:integer:scale(0.001)}",
+                "1500",
+                Ok(Value::from(1.5)),
+            ),
+            (
+                "%{data:field:trim:uppercase}",
+                " abc ",
+                Ok(Value::from("ABC")),
+            ),
+            // the implicit filter of the matcher is applied first
+            (
+                "%{integer:field:scale(2):scale(0.25)}",
+                "10",
+                Ok(Value::from(5)),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn supports_null_if_with_regex() {
+        let pattern = r#"%{data:field:nullIf(re("-|N/A|(?i)null|\\s*"))}"#;
+        test_grok_pattern(vec![
+            (pattern, "-", Ok(Value::Null)),
+            (pattern, "N/A", Ok(Value::Null)),
+            (pattern, "NULL", Ok(Value::Null)),
+            (pattern, "   ", Ok(Value::Null)),
+            (pattern, "-1", Ok(Value::from("-1"))),
+        ]);
+    }
+
+    #[test]
+    fn ignores_field_if_substring_is_out_of_range() {
+        test_full_grok(vec![(
+            "%{data:field:substring(10)}",
+            "abc",
+            Ok(Value::from(BTreeMap::new())),
+        )]);
+    }
+
+    #[test]
+    fn ignores_field_if_boolean_filter_does_not_match() {
+        test_full_grok(vec![(
+            r#"%{data:field:boolean("yes", "no")}"#,
+            "true",
+            Ok(Value::from(BTreeMap::new())),
+        )]);
+    }
+
+    fn test_grok_pattern(tests: Vec<(&str, &str, Result<Value, Error>)>) {
+        for (filter, k, v) in tests {
+            let rules = parse_grok_rules(&[filter.to_string()], BTreeMap::new())
+                .expect("couldn't parse rules");
+            let parsed = parse_grok(k, &rules);
+
+            if v.is_ok() {
+                assert_eq!(
+                    parsed.unwrap(),
+                    Value::from(btreemap! {
+                        "field" =>  v.unwrap(),
+                    })
+                );
+            } else {
+                assert_eq!(parsed, v);
+            }
+        }
+    }
+
+    #[test]
+    fn prefiltered_parsing_matches_sequential_parsing() {
+        let rules = parse_grok_rules(
+            &[
+                r#"%{word:method} %{integer:status}"#.to_string(),
+                r#"(?<first>\w+) %{data:message}"#.to_string(),
+                r#"%{ipv4:ip}(?>:%{port:port})?"#.to_string(),
+                r#"%{notSpace:a} %{notSpace:b}"#.to_string(),
+            ],
+            BTreeMap::new(),
+        )
+        .expect("couldn't parse rules");
+        let prefilter = GrokPrefilter::new(&rules);
+
+        for value in [
+            "GET 200",
+            "GET two hundred",
+            "127.0.0.1:8080",
+            "1.2.3.4",
+            "- -",
+            "nothing",
+        ] {
+            assert_eq!(
+                parse_grok_with_options(
+                    value,
+                    &rules,
+                    ParseOptions {
+                        prefilter: Some(&prefilter),
+                        ..Default::default()
+                    }
+                ),
+                parse_grok(value, &rules)
+            );
+        }
+    }
+
+    #[test]
+    fn supports_unanchored_rules() {
+        let rules = parse_grok_rules_with_options(
+            &[
+                "user=%{word:user}".to_string(),
+                "status=%{integer:status}".to_string(),
+            ],
+            BTreeMap::new(),
+            CompileOptions {
+                anchored: false,
+                anchored_overrides: BTreeMap::from([(1, true)]),
+                ..Default::default()
+            },
+        )
+        .expect("couldn't parse rules");
+
+        assert_eq!(
+            parse_grok("[info] user=alice logged in", &rules),
+            Ok(Value::from(btreemap! { "user" => "alice" }))
+        );
+        // the second rule still has to match the whole value
+        assert_eq!(parse_grok("[info] status=200", &rules), Err(Error::NoMatch));
+        assert_eq!(
+            parse_grok("status=200", &rules),
+            Ok(Value::from(btreemap! { "status" => 200 }))
+        );
+    }
+
+    #[test]
+    fn counts_matches_per_rule() {
+        let rules = parse_grok_rules(
+            &[
+                "%{integer:status}".to_string(),
+                "%{word:method}".to_string(),
+                "%{ipv4:ip}".to_string(),
+            ],
+            BTreeMap::new(),
+        )
+        .expect("couldn't parse rules");
+        let stats = GrokStats::new(rules.len());
+        let options = ParseOptions {
+            stats: Some(&stats),
+            ..Default::default()
+        };
+
+        for value in ["200", "GET", "POST", "-"] {
+            let _ = parse_grok_with_options(value, &rules, options);
+        }
+
+        let counts = |i| {
+            let rule = stats.rule(i).unwrap();
+            (rule.attempts, rule.matched)
+        };
+        assert_eq!(counts(0), (4, 1));
+        assert_eq!(counts(1), (3, 2));
+        // the last rule never matches
+        assert_eq!(counts(2), (1, 0));
+        assert_eq!(stats.no_match(), 1);
+        assert!(stats.rule(0).unwrap().average_match_time().is_some());
+        assert_eq!(stats.rule(3), None);
+    }
+
+    #[test]
+    fn applies_on_match_actions() {
+        let path = |path: &'static str| LookupBuf::from_str(path).unwrap();
+        let parse = |actions: Vec<OnMatchAction>| {
+            let rules = parse_grok_rules_with_options(
+                &["%{word:user} %{integer:tmp.status} %{data:message}".to_string()],
+                BTreeMap::new(),
+                CompileOptions {
+                    on_match: BTreeMap::from([(0, actions)]),
+                    ..Default::default()
+                },
+            )
+            .expect("couldn't parse rules");
+            parse_grok("alice 200 hello", &rules)
+        };
+
+        assert_eq!(
+            parse(vec![
+                OnMatchAction::Rename(path("tmp.status"), path("http.status_code")),
+                OnMatchAction::Remove(path("tmp")),
+            ]),
+            Ok(Value::from(btreemap! {
+                "user" => "alice",
+                "message" => "hello",
+                "http" => btreemap! { "status_code" => 200 },
+            }))
+        );
+        assert_eq!(
+            parse(vec![OnMatchAction::Retain(vec![
+                path("user"),
+                path("tmp.status"),
+                path("missing"),
+            ])]),
+            Ok(Value::from(btreemap! {
+                "user" => "alice",
+                "tmp" => btreemap! { "status" => 200 },
+            }))
+        );
+        // the root is left as is
+        assert_eq!(
+            parse(vec![OnMatchAction::Remove(LookupBuf::root())]),
+            parse(vec![])
+        );
+    }
+
+    #[test]
+    fn keeps_empty_captures() {
+        test_full_grok(vec![
+            (
+                r#"%{data:a}:%{data:b:keepEmpty}:%{data:c:keepEmpty(null)}:%{data:d:integer:keepEmpty}"#,
+                ":::",
+                Ok(Value::from(btreemap! {
+                    "b" => "",
+                    "c" => Value::Null,
+                    "d" => "",
+                })),
+            ),
+            (
+                r#"%{data:a:keepEmpty}(?:-%{data:b:keepEmpty})?"#,
+                "",
+                // only captures, which participated in the match, are kept
+                Ok(Value::from(btreemap! { "a" => "" })),
+            ),
         ]);
+
+        let rules = parse_grok_rules_with_options(
+            &[r#"%{data:a}:(?<b>\w*)"#.to_string()],
+            BTreeMap::new(),
+            CompileOptions {
+                keep_empty: true,
+                ..Default::default()
+            },
+        )
+        .expect("couldn't parse rules");
+        assert_eq!(
+            parse_grok(":", &rules),
+            Ok(Value::from(btreemap! { "a" => "", "b" => "" }))
+        );
+
+        assert_eq!(
+            parse_grok_rules(&["%{data:a:keepEmpty(1)}".to_string()], BTreeMap::new())
+                .unwrap_err()
+                .to_string(),
+            "invalid arguments for the function 'keepEmpty'"
+        );
     }
 
-    fn test_grok_pattern(tests: Vec<(&str, &str, Result<Value, Error>)>) {
-        for (filter, k, v) in tests {
-            let rules = parse_grok_rules(&[filter.to_string()], BTreeMap::new())
-                .expect("couldn't parse rules");
-            let parsed = parse_grok(k, &rules);
+    #[test]
+    fn aborts_when_match_budget_is_exceeded() {
+        let rules = parse_grok_rules(&[r#"(?<field>(?:a|aa)+)b"#.to_string()], BTreeMap::new())
+            .expect("couldn't parse rules");
+        let options = ParseOptions {
+            match_budget: Some(1000),
+            ..Default::default()
+        };
 
-            if v.is_ok() {
-                assert_eq!(
-                    parsed.unwrap(),
-                    Value::from(btreemap! {
-                        "field" =>  v.unwrap(),
-                    })
-                );
-            } else {
-                assert_eq!(parsed, v);
-            }
-        }
+        assert_eq!(
+            // the trailing "a" fails the match after trying every way to split the "a"s before it
+            parse_grok_with_options(&format!("{}ba", "a".repeat(40)), &rules, options),
+            Err(Error::MatchBudgetExceeded(1000))
+        );
+        // cheap matches aren't affected
+        assert_eq!(
+            parse_grok_with_options("aab", &rules, options),
+            Ok(Value::from(btreemap! { "field" => "aa" }))
+        );
+    }
+
+    #[test]
+    fn preserves_invalid_utf8_bytes() {
+        let rules = parse_grok_rules(
+            &["<%{integer:pri}>%{word:app}: %{data:message}".to_string()],
+            BTreeMap::new(),
+        )
+        .expect("couldn't parse rules");
+
+        assert_eq!(
+            parse_grok_bytes(
+                b"<13>app: binary \xff\x00\xc3 payload",
+                &rules,
+                ParseOptions::default()
+            ),
+            Ok(Value::from(btreemap! {
+                "pri" => 13,
+                "app" => "app",
+                "message" => Value::Bytes(bytes::Bytes::from_static(b"binary \xff\x00\xc3 payload")),
+            }))
+        );
+        // valid UTF-8 is parsed as usual
+        assert_eq!(
+            parse_grok_bytes(
+                "<13>app: ünicode".as_bytes(),
+                &rules,
+                ParseOptions::default()
+            ),
+            parse_grok("<13>app: ünicode", &rules)
+        );
     }
 
     fn test_full_grok(tests: Vec<(&str, &str, Result<Value, Error>)>) {
@@ -378,6 +1179,34 @@ mod tests {
         assert_eq!(error, Error::NoMatch);
     }
 
+    #[test]
+    fn inserts_into_nested_destinations() {
+        test_full_grok(vec![
+            (
+                "%{ip:network.client.ip} %{word:network.client.name}",
+                "127.0.0.1 localhost",
+                Ok(Value::from(btreemap! {
+                    "network" => btreemap! {
+                        "client" => btreemap! {
+                            "ip" => "127.0.0.1",
+                            "name" => "localhost",
+                        },
+                    },
+                })),
+            ),
+            (
+                r#"%{word:http."user.agent"} %{word:http["status.code"]:integer}"#,
+                "curl 200",
+                Ok(Value::from(btreemap! {
+                    "http" => btreemap! {
+                        "user.agent" => "curl",
+                        "status.code" => 200,
+                    },
+                })),
+            ),
+        ]);
+    }
+
     #[test]
     fn appends_to_the_same_field() {
         let rules = parse_grok_rules(
@@ -413,7 +1242,58 @@ mod tests {
         .unwrap_err();
         assert_eq!(
             err.to_string(),
-            "Circular dependency found in the alias 'pattern1'"
+            "Circular dependency found in the alias 'pattern1': pattern1 -> pattern2 -> pattern1"
+        );
+
+        // the reported chain only includes the aliases forming the cycle
+        let err = parse_grok_rules(
+            &[r#"%{pattern1}"#.to_string()],
+            btreemap! {
+            "pattern1" => r#"%{pattern2}"#.to_string(),
+            "pattern2" => r#"%{pattern3} %{pattern4}"#.to_string(),
+            "pattern3" => r#"%{word}"#.to_string(),
+            "pattern4" => r#"%{pattern2}"#.to_string()},
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.error,
+            crate::parse_grok_rules::Error::CircularDependencyInAliasDefinition(
+                "pattern2".to_string(),
+                vec![
+                    "pattern2".to_string(),
+                    "pattern4".to_string(),
+                    "pattern2".to_string()
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn resolves_deep_alias_chains() {
+        let rules = parse_grok_rules(
+            &[r#"%{access} %{access}"#.to_string()],
+            btreemap! {
+            "access" => r#"%{client} %{_status}"#.to_string(),
+            "client" => r#"%{_ip}"#.to_string(),
+            "_ip" => r#"%{ipv4:network.client.ip}"#.to_string(),
+            "_status" => r#"%{_code}"#.to_string(),
+            "_code" => r#"%{integer:http.status_code}"#.to_string()},
+        )
+        .expect("couldn't parse rules");
+        let parsed = parse_grok("127.0.0.1 200 127.0.0.2 404", &rules).unwrap();
+
+        assert_eq!(
+            parsed,
+            Value::from(btreemap! {
+                "network" => btreemap! {
+                    "client" => btreemap! {
+                        "ip" => Value::Array(vec!["127.0.0.1".into(), "127.0.0.2".into()]),
+                    },
+                },
+                "http" => btreemap! {
+                    "status_code" => Value::Array(vec![200.into(), 404.into()]),
+                },
+            })
         );
     }
 
@@ -437,6 +1317,65 @@ mod tests {
         )]);
     }
 
+    #[test]
+    fn supports_regex_matcher_flags() {
+        test_grok_pattern(vec![
+            (
+                r#"%{regex("[a-z]+", "i"):field}"#,
+                "ABC",
+                Ok(Value::from("ABC")),
+            ),
+            (r#"%{regex("[a-z]+"):field}"#, "ABC", Err(Error::NoMatch)),
+            (
+                r#"%{regex("a.b", "s"):field}"#,
+                "a\nb",
+                Ok(Value::from("a\nb")),
+            ),
+            (r#"%{regex("a.b"):field}"#, "a\nb", Err(Error::NoMatch)),
+            (
+                r#"%{regex("\\w+", "u"):field}"#,
+                "héllo",
+                Ok(Value::from("héllo")),
+            ),
+        ]);
+
+        assert_eq!(
+            parse_grok_rules(
+                &[r#"%{regex("[a-z]+", "q"):field}"#.to_string()],
+                BTreeMap::new()
+            )
+            .unwrap_err()
+            .to_string(),
+            r#"invalid arguments for the function 'regex'"#
+        );
+    }
+
+    #[test]
+    fn promotes_named_groups_of_regex_matcher() {
+        // unlike raw regex groups, names of groups inside the regex matcher are lookup paths
+        test_full_grok(vec![
+            (
+                r#"%{regex("(?<user.name>\\w+)@(?<user.domain>[\\w.]+)")} %{word:status}"#,
+                "john@example.com ok",
+                Ok(Value::from(btreemap! {
+                    "user" => btreemap! {
+                        "name" => "john",
+                        "domain" => "example.com",
+                    },
+                    "status" => "ok",
+                })),
+            ),
+            (
+                r#"%{regex("(?P<id>\\d+)(?<=\\d)"):field}"#,
+                "42",
+                Ok(Value::from(btreemap! {
+                    "id" => "42",
+                    "field" => "42",
+                })),
+            ),
+        ]);
+    }
+
     #[test]
     fn supports_date_matcher() {
         test_grok_pattern(vec![
@@ -495,6 +1434,21 @@ mod tests {
                 "Thu Jun 16 08:29:03 2016",
                 Ok(Value::Integer(1466054943000)),
             ),
+            (
+                r#"%{date("EEE MMM dd HH:mm:ss yyyy", "Europe/Paris"):field}"#,
+                "Thu Jun 16 08:29:03 2016",
+                Ok(Value::Integer(1466058543000)),
+            ),
+            (
+                r#"%{date("EEE MMM dd HH:mm:ss yyyy", "Europe/Paris"):field}"#,
+                "Thu Jan 14 08:29:03 2016",
+                Ok(Value::Integer(1452756543000)),
+            ),
+            (
+                r#"%{date("dd/MMM/yyyy", "+3"):field}"#,
+                "06/Mar/2013",
+                Ok(Value::Integer(1362517200000)),
+            ),
             (
                 r#"%{date("EEE MMM dd HH:mm:ss yyyy", "UTC+5"):field}"#,
                 "Thu Jun 16 08:29:03 2016",
@@ -535,8 +1489,59 @@ mod tests {
                 "171113 14:14:20",
                 Ok(Value::Integer(1510582460000)),
             ),
+            (
+                r#"%{date(["yyyy-MM-dd HH:mm:ss.SSS", "yyyy-MM-dd HH:mm:ss"]):field}"#,
+                "2019-11-25 11:21:32",
+                Ok(Value::Integer(1574680892000)),
+            ),
+            (
+                r#"%{date(["yyyy-MM-dd HH:mm:ss.SSS", "yyyy-MM-dd HH:mm:ss"]):field}"#,
+                "2019-11-25 11:21:32.628",
+                Ok(Value::Integer(1574680892628)),
+            ),
+            (
+                r#"%{date(["dd/MMM/yyyy", "dd/MM/yyyy"], "+3"):field}"#,
+                "06/03/2013",
+                Ok(Value::Integer(1362517200000)),
+            ),
+        ]);
+
+        // epoch timestamps
+        test_grok_pattern(vec![
+            (
+                r#"%{date("UNIX"):field}"#,
+                "1466065743",
+                Ok(Value::Timestamp(Utc.timestamp(1466065743, 0))),
+            ),
+            (
+                r#"%{date("UNIX"):field}"#,
+                "1466065743.123456",
+                Ok(Value::Timestamp(Utc.timestamp(1466065743, 123456000))),
+            ),
+            (
+                r#"%{date("UNIX_MS"):field}"#,
+                "1466065743123.5",
+                Ok(Value::Timestamp(Utc.timestamp(1466065743, 123500000))),
+            ),
+            (
+                r#"%{date("UNIX_NS"):field}"#,
+                "1466065743123456789",
+                Ok(Value::Timestamp(Utc.timestamp(1466065743, 123456789))),
+            ),
+            (
+                r#"%{date("UNIX"):field}"#,
+                "-1.5",
+                Ok(Value::Timestamp(Utc.timestamp(-2, 500000000))),
+            ),
         ]);
 
+        // none of the fallback formats can parse the date
+        test_full_grok(vec![(
+            r#"%{date(["dd/MMM/yyyy", "dd-MMM-yyyy"]):field}"#,
+            "06/Foo/2013",
+            Ok(Value::from(BTreeMap::new())),
+        )]);
+
         // check error handling
         assert_eq!(
             parse_grok_rules(
@@ -556,6 +1561,21 @@ mod tests {
             .to_string(),
             r#"invalid arguments for the function 'date'"#
         );
+        assert_eq!(
+            parse_grok_rules(
+                &[r#"%{date("EEE MMM dd HH:mm:ss yyyy", 3):field}"#.to_string()],
+                BTreeMap::new(),
+            )
+            .unwrap_err()
+            .to_string(),
+            r#"invalid arguments for the function 'date'"#
+        );
+        assert_eq!(
+            parse_grok_rules(&[r#"%{date([]):field}"#.to_string()], BTreeMap::new())
+                .unwrap_err()
+                .to_string(),
+            r#"invalid arguments for the function 'date'"#
+        );
     }
 
     #[test]
@@ -621,6 +1641,24 @@ mod tests {
                 "{1;2.1}",
                 Ok(Value::Array(vec![10.into(), 21.into()])),
             ),
+            (
+                r#"%{data:field:array("[]", ",", array(integer))}"#,
+                "[[1,2],[3,4]]",
+                Ok(Value::Array(vec![
+                    Value::Array(vec![1.into(), 2.into()]),
+                    Value::Array(vec![3.into(), 4.into()]),
+                ])),
+            ),
+            (
+                r#"%{data:field:array(",", integer, skipInvalid)}"#,
+                "[1,x,3]",
+                Ok(Value::Array(vec![1.into(), 3.into()])),
+            ),
+            (
+                r#"%{data:field:array(integer, keepInvalid)}"#,
+                "[1,x,3]",
+                Ok(Value::Array(vec![1.into(), "x".into(), 3.into()])),
+            ),
         ]);
 
         test_full_grok(vec![
@@ -705,6 +1743,14 @@ mod tests {
                     "key" => "valueStr"
                 })),
             ),
+            (
+                r#"%{data::keyvalue("=", "", "{}[]")}"#,
+                r#"key1={valueStr1} key2=[valueStr2]"#,
+                Ok(Value::from(btreemap! {
+                    "key1" => "valueStr1",
+                    "key2" => "valueStr2",
+                })),
+            ),
             (
                 r#"%{data::keyvalue("=", "", "", "|")}"#,
                 r#"key1=value1|key2=value2"#,
@@ -844,6 +1890,36 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn parses_querystring() {
+        test_full_grok(vec![
+            (
+                "%{data::querystring}",
+                "?productId=superproduct&promotionCode=superpromo",
+                Ok(Value::from(btreemap! {
+                    "productId" => "superproduct",
+                    "promotionCode" => "superpromo",
+                })),
+            ),
+            (
+                r#"%{regex("[^?]*"):http.url_details.path}\?%{data:http.url_details.queryString:querystring}"#,
+                "/search?q=vector%20grok&page=2&tag=a&tag=b",
+                Ok(Value::from(btreemap! {
+                    "http" => btreemap! {
+                        "url_details" => btreemap! {
+                            "path" => "/search",
+                            "queryString" => btreemap! {
+                                "q" => "vector grok",
+                                "page" => "2",
+                                "tag" => Value::Array(vec!["a".into(), "b".into()]),
+                            },
+                        },
+                    },
+                })),
+            ),
+        ]);
+    }
+
     #[test]
     fn alias_and_main_rule_extract_same_fields_to_array() {
         let rules = parse_grok_rules(
@@ -888,6 +1964,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn applies_filters_of_alias_definitions() {
+        let rules = parse_grok_rules_with_options(
+            &[
+                "%{_duration:duration} %{_duration} %{_user:user} %{_user:admin:nullIf(\"root\")}"
+                    .to_string(),
+            ],
+            btreemap! {
+                "_duration" => "%{number}".to_string(),
+                "_user" => "%{word}".to_string()
+            },
+            CompileOptions {
+                alias_filters: btreemap! {
+                    "_duration" => "scale(1000)".to_string(),
+                    "_user" => ":lowercase".to_string()
+                },
+                ..Default::default()
+            },
+        )
+        .expect("couldn't parse rules");
+
+        // filters of the alias are applied before the ones of the destination
+        assert_eq!(
+            parse_grok("1.5 3 ALICE ROOT", &rules),
+            Ok(Value::from(btreemap! {
+                "duration" => 1500,
+                "user" => "alice",
+                "admin" => Value::Null,
+            }))
+        );
+
+        let error = parse_grok_rules_with_options(
+            &["%{_duration:duration}".to_string()],
+            btreemap! {
+                "_duration" => "%{number}".to_string()
+            },
+            CompileOptions {
+                alias_filters: btreemap! {
+                    "_duration" => "unknown".to_string()
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert_eq!(error.to_string(), "unknown filter 'unknown'");
+    }
+
     #[test]
     #[traced_test]
     fn does_not_emit_error_log_on_alternatives_with_filters() {
@@ -969,6 +2092,91 @@ mod tests {
                     "field" => Value::Array(vec!["abc".into(), "d\ne".into()]),
                 })),
             ),
+            // group modifiers are converted as well
+            (
+                "(?s:%{data:field})",
+                "a\nb",
+                Ok(Value::from(btreemap! {
+                    "field" => "a\nb"
+                })),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn supports_data_with_stop_pattern() {
+        test_full_grok(vec![
+            (
+                r#"%{data("\\s+\\w+="):message}(?: user=%{word:user})? status=%{integer:status}"#,
+                "hello world user=alice status=200",
+                Ok(Value::from(btreemap! {
+                    "message" => "hello world",
+                    "user" => "alice",
+                    "status" => 200,
+                })),
+            ),
+            (
+                r#"%{data("\\s+\\w+="):message}(?: user=%{word:user})? status=%{integer:status}"#,
+                "hello world status=200",
+                Ok(Value::from(btreemap! {
+                    "message" => "hello world",
+                    "status" => 200,
+                })),
+            ),
+            // unlike the lazy data matcher, it doesn't stop at the first possible match
+            (
+                r#"%{data(" "):first}%{data:rest}"#,
+                "a=1 b=2",
+                Ok(Value::from(btreemap! {
+                    "first" => "a=1",
+                    "rest" => " b=2",
+                })),
+            ),
+            // the rest of the rule must still match after the data
+            (r#"%{data(";"):first};"#, "a=1 b=2", Err(Error::NoMatch)),
         ]);
+
+        assert_eq!(
+            parse_grok_rules(&["%{data(1):field}".to_string()], BTreeMap::new())
+                .unwrap_err()
+                .to_string(),
+            "invalid arguments for the function 'data'"
+        );
+    }
+
+    #[test]
+    fn parses_stack_traces() {
+        let trace = "2022-01-01 ERROR Unexpected failure\njava.lang.IllegalStateException: oops\n\tat com.example.App.main(App.java:10)\n";
+        let expected = Ok(Value::from(btreemap! {
+            "date" => "2022-01-01",
+            "level" => "ERROR",
+            "message" => "Unexpected failure",
+            "error" => btreemap! {
+                "stack" => "java.lang.IllegalStateException: oops\n\tat com.example.App.main(App.java:10)\n",
+            },
+        }));
+
+        // a dedicated pattern keeps new lines in the stack trace only
+        let rules = parse_grok_rules(
+            &[
+                "%{notSpace:date} %{word:level} %{data:message}\n%{multilineData:error.stack}"
+                    .to_string(),
+            ],
+            BTreeMap::new(),
+        )
+        .expect("couldn't parse rules");
+        assert_eq!(parse_grok(trace, &rules), expected);
+
+        // or the dot matches new lines in the whole rule, lazy captures still stop at the first new line
+        let rules = parse_grok_rules_with_options(
+            &["%{notSpace:date} %{word:level} %{data:message}\n%{data:error.stack}".to_string()],
+            BTreeMap::new(),
+            CompileOptions {
+                multiline: true,
+                ..Default::default()
+            },
+        )
+        .expect("couldn't parse rules");
+        assert_eq!(parse_grok(trace, &rules), expected);
     }
 }
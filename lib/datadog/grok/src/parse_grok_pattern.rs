@@ -1,6 +1,9 @@
 use lalrpop_util::{lalrpop_mod, ParseError};
 
-use crate::{ast::GrokPattern, lexer::Lexer};
+use crate::{
+    ast::{Function, GrokPattern},
+    lexer::Lexer,
+};
 
 lalrpop_mod!(
     #[allow(clippy::all)]
@@ -20,10 +23,27 @@ pub fn parse_grok_pattern(input: &str) -> Result<GrokPattern, String> {
         })
 }
 
+/// Parses a chain of filters as :FILTER1:FILTER2, the leading colon is optional
+pub fn parse_filter_fns(input: &str) -> Result<Vec<Function>, String> {
+    let input = if input.starts_with(':') {
+        input.to_string()
+    } else {
+        format!(":{}", input)
+    };
+    let lexer = Lexer::new(&input);
+    parser::FilterFnsParser::new()
+        .parse(&input, lexer)
+        .map_err(|e| match e {
+            ParseError::User { error } => error.to_string(),
+            _ => format!("invalid filters: {}", input),
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use lookup::{LookupBuf, SegmentBuf};
     use value::Value;
+    use vector_common::btreemap;
 
     use super::*;
     use crate::ast::{Destination, Function, FunctionArgument};
@@ -49,9 +69,9 @@ mod tests {
             destination.path,
             from_path_segments(vec!["e-http", "status", "abc", r#".""#])
         );
-        let filter = destination.filter_fn.unwrap();
+        let filter = destination.filter_fns.first().unwrap();
         assert_eq!(filter.name, "integer");
-        let args = filter.args.unwrap();
+        let args = filter.args.as_ref().unwrap();
         let expected_args = vec![
             "a. df".into(),
             0.123.into(),
@@ -68,6 +88,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn quoted_path_segments() {
+        let input = r#"%{data:http."user.agent".null["a.b"]}"#;
+        let parsed = parse_grok_pattern(input).unwrap_or_else(|error| {
+            panic!("Problem parsing grok: {:?}", error);
+        });
+        assert_eq!(
+            parsed.destination.unwrap().path,
+            from_path_segments(vec!["http", "user.agent", "null", "a.b"])
+        );
+    }
+
     #[test]
     fn empty_field() {
         let input = r#"%{data:}"#;
@@ -87,10 +119,10 @@ mod tests {
             parsed.destination,
             Some(Destination {
                 path: LookupBuf::from("field"),
-                filter_fn: Some(Function {
+                filter_fns: vec![Function {
                     name: "filter".to_string(),
                     args: Some(vec![FunctionArgument::Arg(r#"escaped "quotes""#.into())])
-                })
+                }]
             })
         );
     }
@@ -105,10 +137,58 @@ mod tests {
             parsed.destination,
             Some(Destination {
                 path: LookupBuf::root(),
-                filter_fn: Some(Function {
+                filter_fns: vec![Function {
                     name: "json".to_string(),
                     args: None,
-                })
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn chained_filters() {
+        let input = r#"%{data:field:integer:scale(0.001)}"#;
+        let parsed = parse_grok_pattern(input).unwrap_or_else(|error| {
+            panic!("Problem parsing grok: {:?}", error);
+        });
+        assert_eq!(
+            parsed.destination,
+            Some(Destination {
+                path: LookupBuf::from("field"),
+                filter_fns: vec![
+                    Function {
+                        name: "integer".to_string(),
+                        args: None,
+                    },
+                    Function {
+                        name: "scale".to_string(),
+                        args: Some(vec![FunctionArgument::Arg(0.001.into())]),
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn object_literal_args() {
+        let input = r#"%{data:field:lookup({"0": "emergency", 1: "alert"}, null)}"#;
+        let parsed = parse_grok_pattern(input).unwrap_or_else(|error| {
+            panic!("Problem parsing grok: {:?}", error);
+        });
+        assert_eq!(
+            parsed.destination,
+            Some(Destination {
+                path: LookupBuf::from("field"),
+                filter_fns: vec![Function {
+                    name: "lookup".to_string(),
+                    args: Some(vec![
+                        FunctionArgument::Arg(Value::from(btreemap! {
+                            "0" => "emergency",
+                            "1" => "alert",
+                        })),
+                        FunctionArgument::Arg(Value::Null),
+                    ]),
+                }]
             })
         );
     }
@@ -132,11 +212,34 @@ mod tests {
             parsed.destination,
             Some(Destination {
                 path: LookupBuf::root(),
-                filter_fn: Some(Function {
+                filter_fns: vec![Function {
                     name: "array".to_string(),
                     args: Some(vec![FunctionArgument::Arg("\n".into())]),
-                })
+                }]
             })
         );
     }
+
+    #[test]
+    fn parses_filter_chains() {
+        let expected = vec![
+            Function {
+                name: "scale".to_string(),
+                args: Some(vec![FunctionArgument::Arg(Value::Integer(1000000))]),
+            },
+            Function {
+                name: "integer".to_string(),
+                args: None,
+            },
+        ];
+        assert_eq!(
+            parse_filter_fns("scale(1000000):integer").unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_filter_fns(":scale(1000000):integer").unwrap(),
+            expected
+        );
+        assert!(parse_filter_fns("scale(").is_err());
+    }
 }
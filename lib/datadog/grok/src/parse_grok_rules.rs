@@ -1,23 +1,32 @@
 use std::{
     collections::{BTreeMap, HashMap},
     convert::TryFrom,
+    fs,
+    ops::Range,
+    path::Path,
 };
 
-use lookup::LookupBuf;
+use lookup::{LookupBuf, SegmentBuf};
 use once_cell::sync::Lazy;
 use tracing::error;
 use value::Value;
 
-use crate::grok::Grok;
+use crate::grok::{Error as GrokError, Grok};
 use crate::{
     ast::{self, Destination, GrokPattern},
-    grok_filter::GrokFilter,
+    filters::ip,
+    grok_filter::{GrokFilter, FILTER_NAMES},
     matchers::{date, date::DateFilter},
-    parse_grok_pattern::parse_grok_pattern,
+    parse_grok_pattern::{parse_filter_fns, parse_grok_pattern},
 };
 
-static GROK_PATTERN_RE: Lazy<onig::Regex> =
-    Lazy::new(|| onig::Regex::new(r#"%\{(?:[^"\}]|(?<!\\)"(?:\\"|[^"])*(?<!\\)")+\}"#).unwrap());
+// allows one level of nested braces for object literals in filter arguments
+static GROK_PATTERN_RE: Lazy<onig::Regex> = Lazy::new(|| {
+    onig::Regex::new(
+        r#"%\{(?:[^"\{\}]|(?<!\\)"(?:\\"|[^"])*(?<!\\)"|\{(?:[^"\}]|(?<!\\)"(?:\\"|[^"])*(?<!\\)")*\})+\}"#,
+    )
+    .unwrap()
+});
 
 /// The result of parsing a grok rule with a final regular expression and the
 /// related field information, needed at runtime.
@@ -27,6 +36,22 @@ pub struct GrokRule {
     pub pattern: crate::grok::Pattern,
     /// a map of capture names(grok0, grok1, ...) to field information.
     pub fields: HashMap<String, GrokField>,
+    /// whether to keep captures of regex named groups, which matched an empty string
+    pub keep_empty: bool,
+    /// actions to execute on the parsed value after a successful match
+    pub on_match: Vec<OnMatchAction>,
+}
+
+/// An action executed on the parsed value after a rule matched, e.g. to clean up temporary captures.
+/// Actions are executed in the order they are declared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OnMatchAction {
+    /// moves a field to another path
+    Rename(LookupBuf, LookupBuf),
+    /// removes a field
+    Remove(LookupBuf),
+    /// keeps only the given fields, removing all others
+    Retain(Vec<LookupBuf>),
 }
 
 /// A grok field, that should be extracted, with its lookup path and
@@ -35,6 +60,8 @@ pub struct GrokRule {
 pub struct GrokField {
     pub lookup: LookupBuf,
     pub filters: Vec<GrokFilter>,
+    /// the value to keep a capture, which matched an empty string, as - such captures are dropped otherwise
+    pub keep_empty: Option<Value>,
 }
 
 /// The context used to parse grok rules.
@@ -48,6 +75,21 @@ pub struct GrokRuleParseContext {
     pub aliases: BTreeMap<String, String>,
     /// used to detect cycles in alias definitions
     pub alias_stack: Vec<String>,
+    /// the location of the grok expression a parsing error was found in
+    error_location: Option<ErrorLocation>,
+    /// a suggested fix for a parsing error
+    suggestion: Option<String>,
+    /// whether to keep captures, which matched an empty string, unless a field says otherwise
+    keep_empty: bool,
+    /// filters applied to the values captured with given aliases
+    alias_filters: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+struct ErrorLocation {
+    alias: Option<String>,
+    span: Range<usize>,
+    token: String,
 }
 
 impl GrokRuleParseContext {
@@ -74,6 +116,39 @@ impl GrokRuleParseContext {
             fields: HashMap::new(),
             aliases,
             alias_stack: vec![],
+            error_location: None,
+            suggestion: None,
+            keep_empty: false,
+            alias_filters: BTreeMap::new(),
+        }
+    }
+
+    /// records the location of an offending grok expression,
+    /// unless a more specific one(e.g. inside of an alias definition) is already known
+    fn locate_error(&mut self, alias: Option<String>, span: Range<usize>, token: &str) {
+        if self.error_location.is_none() {
+            self.error_location = Some(ErrorLocation {
+                alias,
+                span,
+                token: token.to_string(),
+            });
+        }
+    }
+
+    /// builds a diagnostic for an error found while parsing the rule with a given index
+    fn diagnostic(&mut self, rule_index: usize, error: Error) -> Diagnostic {
+        let location = self.error_location.take();
+        let suggestion = match &error {
+            Error::UnknownFilter(name) => closest_match(name, FILTER_NAMES.iter().copied()),
+            _ => self.suggestion.take(),
+        };
+        Diagnostic {
+            error,
+            rule_index,
+            alias: location.as_ref().and_then(|l| l.alias.clone()),
+            span: location.as_ref().map(|l| l.span.clone()),
+            token: location.map(|l| l.token),
+            suggestion: suggestion.map(|s| format!("did you mean '{}'?", s)),
         }
     }
 
@@ -91,8 +166,27 @@ pub enum Error {
     InvalidFunctionArguments(String),
     #[error("unknown filter '{}'", .0)]
     UnknownFilter(String),
-    #[error("Circular dependency found in the alias '{}'", .0)]
-    CircularDependencyInAliasDefinition(String),
+    #[error("Circular dependency found in the alias '{}': {}", .0, .1.join(" -> "))]
+    CircularDependencyInAliasDefinition(String, Vec<String>),
+    #[error("failed to read pattern file '{}': {}", .0, .1)]
+    InvalidPatternFile(String, String),
+}
+
+/// A compile-time error along with the location of the offending grok expression.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+#[error("{error}")]
+pub struct Diagnostic {
+    pub error: Error,
+    /// the index of the rule in the list of parsing rules
+    pub rule_index: usize,
+    /// the alias the offending expression is defined in, if it is not in the rule itself
+    pub alias: Option<String>,
+    /// the byte span of the offending expression within the rule or the alias definition
+    pub span: Option<Range<usize>>,
+    /// the offending expression, e.g. `%{data:field:lowercas}`
+    pub token: Option<String>,
+    /// a suggested fix, e.g. `did you mean 'lowercase'?`
+    pub suggestion: Option<String>,
 }
 
 ///
@@ -108,28 +202,152 @@ pub enum Error {
 /// You can write grok patterns with the %{MATCHER:EXTRACT:FILTER} syntax:
 /// - Matcher: A rule (possibly a reference to another token rule) that describes what to expect (number, word, notSpace, etc.)
 /// - Extract (optional): An identifier representing the capture destination for the piece of text matched by the Matcher.
-/// - Filter (optional): A post-processor of the match to transform it.
+/// - Filter (optional): A post-processor of the match to transform it. Filters can be chained, e.g. %{data:field:integer:scale(0.001)}, and are applied from left to right.
 ///
-/// Rules can reference aliases as %{alias_name}, aliases can reference each other themselves, cross-references or circular dependencies are not allowed and result in an error.
+/// Rules can reference aliases as %{alias_name}, aliases can reference each other themselves and are resolved recursively; circular dependencies are not allowed and result in an error listing the offending chain.
 /// Only one can match any given log. The first one that matches, from top to bottom, is the one that does the parsing.
 /// For further documentation and the full list of available matcher and filters check out https://docs.datadoghq.com/logs/processing/parsing
 pub fn parse_grok_rules(
     patterns: &[String],
     aliases: BTreeMap<String, String>,
-) -> Result<Vec<GrokRule>, Error> {
+) -> Result<Vec<GrokRule>, Diagnostic> {
+    parse_grok_rules_with_options(patterns, aliases, CompileOptions::default())
+}
+
+/// Options for compiling grok rules.
+#[derive(Debug, Clone)]
+pub struct CompileOptions {
+    /// additional named pattern definitions, e.g. the ones loaded with `read_pattern_files`,
+    /// taking precedence over the built-in patterns with the same name
+    pub definitions: BTreeMap<String, String>,
+    /// whether rules must match the whole value(the default) or may match a substring anywhere in it
+    pub anchored: bool,
+    /// overrides `anchored` for the rules with given indices
+    pub anchored_overrides: BTreeMap<usize, bool>,
+    /// whether the dot matches new lines in all rules, e.g. to parse multiline stack traces,
+    /// rules can also enable it with the `(?s)`/`(?m)` modifiers
+    pub multiline: bool,
+    /// whether to keep captures, which matched an empty string, as empty strings,
+    /// fields can opt in individually with the `keepEmpty` filter
+    pub keep_empty: bool,
+    /// actions to execute after a successful match, for the rules with given indices
+    pub on_match: BTreeMap<usize, Vec<OnMatchAction>>,
+    /// filters of the aliases with given names, e.g. `_duration` => `scale(1000000)`, in the syntax
+    /// of destination filters - they are applied to the values captured with these aliases
+    /// before the filters of the destination
+    pub alias_filters: BTreeMap<String, String>,
+}
+
+impl Default for CompileOptions {
+    fn default() -> Self {
+        Self {
+            definitions: BTreeMap::new(),
+            anchored: true,
+            anchored_overrides: BTreeMap::new(),
+            multiline: false,
+            keep_empty: false,
+            on_match: BTreeMap::new(),
+            alias_filters: BTreeMap::new(),
+        }
+    }
+}
+
+/// Parses DD grok rules, just like `parse_grok_rules`, with the given options.
+pub fn parse_grok_rules_with_options(
+    patterns: &[String],
+    aliases: BTreeMap<String, String>,
+    options: CompileOptions,
+) -> Result<Vec<GrokRule>, Diagnostic> {
     let mut grok = Grok::with_patterns();
+    for (name, definition) in options.definitions {
+        grok.insert_definition(name, definition);
+    }
 
     patterns
         .iter()
-        .filter(|&r| !r.is_empty())
-        .map(|r| {
-            parse_pattern(
-                r,
-                &mut GrokRuleParseContext::new(aliases.clone()),
-                &mut grok,
-            )
+        .enumerate()
+        .filter(|(_, r)| !r.is_empty())
+        .map(|(rule_index, r)| {
+            let anchored = options
+                .anchored_overrides
+                .get(&rule_index)
+                .copied()
+                .unwrap_or(options.anchored);
+            let mut context = GrokRuleParseContext::new(aliases.clone());
+            context.keep_empty = options.keep_empty;
+            context.alias_filters = options.alias_filters.clone();
+            let mut rule = parse_pattern(r, anchored, options.multiline, &mut context, &mut grok)
+                .map_err(|error| context.diagnostic(rule_index, error))?;
+            rule.on_match = options
+                .on_match
+                .get(&rule_index)
+                .cloned()
+                .unwrap_or_default();
+            Ok(rule)
+        })
+        .collect::<Result<Vec<GrokRule>, Diagnostic>>()
+}
+
+/// Reads named pattern definitions from the given pattern files or directories,
+/// using the classic grok pattern file syntax: one `NAME definition` per line,
+/// lines starting with `#` are comments.
+/// Files in a directory are read in the alphabetical order, later definitions override earlier ones.
+pub fn read_pattern_files<P: AsRef<Path>>(paths: &[P]) -> Result<BTreeMap<String, String>, Error> {
+    let mut definitions = BTreeMap::new();
+
+    for path in paths {
+        let path = path.as_ref();
+        let error = |e: std::io::Error| {
+            Error::InvalidPatternFile(path.display().to_string(), e.to_string())
+        };
+
+        let mut files = if path.is_dir() {
+            fs::read_dir(path)
+                .map_err(error)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(error)?
+                .into_iter()
+                .filter(|path| path.is_file())
+                .collect::<Vec<_>>()
+        } else {
+            vec![path.to_path_buf()]
+        };
+        files.sort();
+
+        for file in files {
+            let content = fs::read_to_string(&file).map_err(|e| {
+                Error::InvalidPatternFile(file.display().to_string(), e.to_string())
+            })?;
+            definitions.extend(
+                parse_pattern_definitions(&content)
+                    .map_err(|e| Error::InvalidPatternFile(file.display().to_string(), e))?,
+            );
+        }
+    }
+
+    Ok(definitions)
+}
+
+/// Parses pattern definitions in the classic grok pattern file syntax.
+fn parse_pattern_definitions(content: &str) -> Result<BTreeMap<String, String>, String> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim_start().starts_with('#') && !line.trim().is_empty())
+        .map(|(i, line)| {
+            let line = line.trim();
+            line.split_once(char::is_whitespace)
+                .map(|(name, definition)| (name.to_string(), definition.trim_start().to_string()))
+                .ok_or_else(|| {
+                    format!(
+                        "line {}: expected the format 'NAME definition', got '{}'",
+                        i + 1,
+                        line
+                    )
+                })
         })
-        .collect::<Result<Vec<GrokRule>, Error>>()
+        .collect()
 }
 
 ///
@@ -145,10 +363,13 @@ fn parse_alias(
     definition: &str,
     context: &mut GrokRuleParseContext,
 ) -> Result<(), Error> {
-    // track circular dependencies
-    if context.alias_stack.iter().any(|a| a == name) {
+    // track circular dependencies, reporting the chain of aliases forming the cycle
+    if let Some(i) = context.alias_stack.iter().position(|a| a == name) {
+        let mut chain = context.alias_stack[i..].to_vec();
+        chain.push(name.to_string());
         return Err(Error::CircularDependencyInAliasDefinition(
-            context.alias_stack.first().unwrap().to_string(),
+            name.to_string(),
+            chain,
         ));
     } else {
         context.alias_stack.push(name.to_string());
@@ -166,32 +387,60 @@ fn parse_alias(
 ///
 /// # Arguments
 ///
-/// - `pattern` - the definition of the pattern
+/// - `rule` - the definition of the pattern
+/// - `anchored` - whether the pattern must match the whole string
+/// - `multiline` - whether the dot matches new lines
 /// - `context` - the context required to parse the current grok rule
 /// - `grok` - an instance of Grok parser
 fn parse_pattern(
-    pattern: &str,
+    rule: &str,
+    anchored: bool,
+    multiline: bool,
     context: &mut GrokRuleParseContext,
     grok: &mut Grok,
 ) -> Result<GrokRule, Error> {
-    parse_grok_rule(pattern, context)?;
+    parse_grok_rule(rule, context)?;
     let mut pattern = String::new();
-    // \A, \z - parses from the beginning to the end of string, not line(until \n)
-    pattern.push_str(r#"\A"#);
-    pattern.push_str(&context.regex);
-    pattern.push_str(r#"\z"#);
+    if multiline {
+        pattern.push_str("(?m)");
+    }
+    if anchored {
+        // \A, \z - parses from the beginning to the end of string, not line(until \n)
+        pattern.push_str(r#"\A"#);
+        pattern.push_str(&context.regex);
+        pattern.push_str(r#"\z"#);
+    } else {
+        pattern.push_str(&context.regex);
+    }
 
     // our regex engine(onig) uses (?m) mode modifier instead of (?s) to make the dot match all characters
-    pattern = pattern.replace("(?s)", "(?m)").replace("(?-s)", "(?-m)");
+    pattern = pattern
+        .replace("(?s)", "(?m)")
+        .replace("(?-s)", "(?-m)")
+        .replace("(?s:", "(?m:")
+        .replace("(?-s:", "(?-m:");
 
     // compile pattern
-    let pattern = grok
-        .compile(&pattern, true)
-        .map_err(|e| Error::InvalidGrokExpression(pattern, e.to_string()))?;
+    let pattern = match grok.compile(&pattern, true) {
+        Ok(pattern) => pattern,
+        Err(e) => {
+            if let GrokError::DefinitionNotFound(name) = &e {
+                locate_unknown_definition(name, rule, context);
+                context.suggestion = closest_match(
+                    name,
+                    grok.definition_names()
+                        .chain(context.aliases.keys().map(String::as_str)),
+                );
+            }
+            return Err(Error::InvalidGrokExpression(pattern, e.to_string()));
+        }
+    };
 
     Ok(GrokRule {
         pattern,
         fields: context.fields.clone(),
+        keep_empty: context.keep_empty,
+        on_match: vec![],
     })
 }
 
@@ -207,9 +456,14 @@ fn parse_grok_rule(rule: &str, context: &mut GrokRuleParseContext) -> Result<(),
     for (start, end) in GROK_PATTERN_RE.find_iter(rule) {
         context.append_regex(&rule[regex_i..start]);
         regex_i = end;
-        let pattern = parse_grok_pattern(&rule[start..end])
-            .map_err(|e| Error::InvalidGrokExpression(rule[start..end].to_string(), e))?;
-        resolve_grok_pattern(&pattern, context)?;
+        let token = &rule[start..end];
+        let result = parse_grok_pattern(token)
+            .map_err(|e| Error::InvalidGrokExpression(token.to_string(), e))
+            .and_then(|pattern| resolve_grok_pattern(&pattern, context));
+        if result.is_err() {
+            context.locate_error(context.alias_stack.last().cloned(), start..end, token);
+        }
+        result?;
     }
     context.append_regex(&rule[regex_i..]);
 
@@ -233,40 +487,38 @@ fn resolve_grok_pattern(
         .destination
         .as_ref()
         .map(|_| context.generate_grok_compliant_name());
-    match pattern {
-        GrokPattern {
-            destination:
-                Some(Destination {
-                    path,
-                    filter_fn: Some(filter),
-                }),
-            ..
-        } => {
-            context.register_grok_field(
-                grok_alias.as_ref().expect("grok alias is not defined"),
-                GrokField {
-                    lookup: path.clone(),
-                    filters: vec![GrokFilter::try_from(filter)?],
-                },
-            );
-        }
-        GrokPattern {
-            destination:
-                Some(Destination {
-                    path,
-                    filter_fn: None,
-                }),
-            ..
-        } => {
-            context.register_grok_field(
-                grok_alias.as_ref().expect("grok alias is not defined"),
-                GrokField {
-                    lookup: path.clone(),
-                    filters: vec![],
-                },
-            );
+    if let GrokPattern {
+        destination: Some(Destination { path, filter_fns }),
+        ..
+    } = pattern
+    {
+        // filters are applied in the order they are defined, the ones of an alias go first
+        let alias_filter_fns = match context
+            .alias_filters
+            .get(&pattern.match_fn.name)
+            .filter(|_| context.aliases.contains_key(&pattern.match_fn.name))
+        {
+            Some(alias_filters) => parse_filter_fns(alias_filters)
+                .map_err(|e| Error::InvalidGrokExpression(alias_filters.clone(), e))?,
+            None => vec![],
+        };
+        let mut filters = vec![];
+        let mut keep_empty = context.keep_empty.then(|| Value::from(""));
+        for filter_fn in alias_filter_fns.iter().chain(filter_fns) {
+            if filter_fn.name == "keepEmpty" {
+                keep_empty = Some(keep_empty_from_function(filter_fn)?);
+            } else {
+                filters.push(GrokFilter::try_from(filter_fn)?);
+            }
         }
-        _ => {}
+        context.register_grok_field(
+            grok_alias.as_ref().expect("grok alias is not defined"),
+            GrokField {
+                lookup: path.clone(),
+                filters,
+                keep_empty,
+            },
+        );
     }
 
     let match_name = &pattern.match_fn.name;
@@ -283,7 +535,11 @@ fn resolve_grok_pattern(
                 parse_alias(match_name, &alias_def, context)?;
             }
         },
-        None if match_name == "regex" || match_name == "date" || match_name == "boolean" => {
+        None if match_name == "regex"
+            || match_name == "date"
+            || match_name == "boolean"
+            || (match_name == "data" && pattern.match_fn.args.is_some()) =>
+        {
             // these patterns will be converted to named capture groups e.g. (?<http.status_code>[0-9]{3})
             match &grok_alias {
                 Some(grok_alias) => {
@@ -323,13 +579,30 @@ fn resolves_match_function(
 ) -> Result<(), Error> {
     let match_fn = &pattern.match_fn;
     match match_fn.name.as_ref() {
-        "regex" => match match_fn.args.as_ref() {
-            Some(args) if !args.is_empty() => {
-                if let ast::FunctionArgument::Arg(Value::Bytes(ref b)) = args[0] {
-                    context.append_regex(&String::from_utf8_lossy(b));
-                    return Ok(());
+        "regex" => match match_fn.args.as_deref() {
+            Some([ast::FunctionArgument::Arg(Value::Bytes(b)), flags @ ..]) if flags.len() <= 1 => {
+                let regex = promote_named_groups(&String::from_utf8_lossy(b), context);
+                match flags {
+                    [] => context.append_regex(&regex),
+                    [ast::FunctionArgument::Arg(Value::Bytes(flags))] => {
+                        let flags =
+                            regex_flags(&String::from_utf8_lossy(flags)).ok_or_else(|| {
+                                Error::InvalidFunctionArguments(match_fn.name.clone())
+                            })?;
+                        context.append_regex(&format!("(?{}:{})", flags, regex));
+                    }
+                    _ => return Err(Error::InvalidFunctionArguments(match_fn.name.clone())),
                 }
-                Err(Error::InvalidFunctionArguments(match_fn.name.clone()))
+                Ok(())
+            }
+            _ => Err(Error::InvalidFunctionArguments(match_fn.name.clone())),
+        },
+        "data" if match_fn.args.is_some() => match match_fn.args.as_deref() {
+            // data bounded by a stop pattern, e.g. %{data("\\s+\\w+="):message} - matches anything
+            // up to the first match of the stop pattern, which is left for the rest of the rule
+            Some([ast::FunctionArgument::Arg(Value::Bytes(stop))]) if !stop.is_empty() => {
+                context.append_regex(&format!("(?:(?!(?:{})).)*", String::from_utf8_lossy(stop)));
+                Ok(())
             }
             _ => Err(Error::InvalidFunctionArguments(match_fn.name.clone())),
         },
@@ -354,6 +627,27 @@ fn resolves_match_function(
             context.append_regex("numberStr");
             Ok(())
         }
+        "measure" => {
+            if let Some(grok_alias) = &grok_alias {
+                context.register_filter(grok_alias, GrokFilter::Measure);
+            }
+            context.append_regex("measureStr");
+            Ok(())
+        }
+        "uuid" => {
+            if let Some(grok_alias) = &grok_alias {
+                context.register_filter(grok_alias, GrokFilter::Uuid);
+            }
+            context.append_regex("uuid");
+            Ok(())
+        }
+        "mac" => {
+            if let Some(grok_alias) = &grok_alias {
+                context.register_filter(grok_alias, GrokFilter::Mac);
+            }
+            context.append_regex("mac");
+            Ok(())
+        }
         "numberExt" => {
             if let Some(grok_alias) = &grok_alias {
                 context.register_filter(grok_alias, GrokFilter::NumberExt);
@@ -361,57 +655,81 @@ fn resolves_match_function(
             context.append_regex("numberExtStr");
             Ok(())
         }
+        "boolean" => {
+            // matches and converts in one step, e.g. %{boolean("yes", "no"):enabled}
+            let filter = GrokFilter::try_from(match_fn)?;
+            let (true_value, false_value) = match &filter {
+                GrokFilter::Boolean(true_value, false_value) => (true_value, false_value),
+                _ => unreachable!("boolean function always produces a boolean filter"),
+            };
+            context.append_regex(&format!(
+                "(?i:{}|{})",
+                regex::escape(true_value),
+                regex::escape(false_value)
+            ));
+            if let Some(grok_alias) = &grok_alias {
+                context.register_filter(grok_alias, filter);
+            }
+            Ok(())
+        }
+        "ipv4" | "ipv6" | "ip" | "ipOrHost" => {
+            if let Some(filter) = ip::filter_from_function(match_fn)? {
+                if let Some(grok_alias) = &grok_alias {
+                    context.register_filter(grok_alias, filter);
+                }
+            }
+            context.append_regex(&match_fn.name);
+            Ok(())
+        }
         "date" => {
-            return match match_fn.args.as_ref() {
-                Some(args) if !args.is_empty() && args.len() <= 2 => {
-                    if let ast::FunctionArgument::Arg(Value::Bytes(b)) = &args[0] {
-                        let format = String::from_utf8_lossy(b);
-                        let result = date::time_format_to_regex(&format, true)
-                            .map_err(|_e| Error::InvalidFunctionArguments(match_fn.name.clone()))?;
-                        let mut regext_opt = None;
-                        if result.tz_captured {
-                            regext_opt = Some(regex::Regex::new(&result.regex).map_err(|error| {
-                                error!(message = "Error compiling regex", regex = %result.regex, %error);
-                                Error::InvalidFunctionArguments(match_fn.name.clone())
-                            })?);
-                        }
-                        let strp_format = date::convert_time_format(&format).map_err(|error| {
-                            error!(message = "Error compiling regex", regex = %result.regex, %error);
-                            Error::InvalidFunctionArguments(match_fn.name.clone())
-                        })?;
-                        let mut target_tz = None;
-                        if args.len() == 2 {
-                            if let ast::FunctionArgument::Arg(Value::Bytes(b)) = &args[1] {
-                                let tz = String::from_utf8_lossy(b);
-                                date::parse_timezone(&tz).map_err(|error| {
-                                    error!(message = "Invalid(unrecognized) timezone", %error);
-                                    Error::InvalidFunctionArguments(match_fn.name.clone())
-                                })?;
-                                target_tz = Some(tz.to_string());
-                            }
-                        }
-                        let filter = GrokFilter::Date(DateFilter {
-                            original_format: format.to_string(),
-                            strp_format,
-                            regex_with_tz: regext_opt,
-                            target_tz,
-                            tz_aware: result.with_tz,
-                        });
-                        let result =
-                            date::time_format_to_regex(&format, false).map_err(|error| {
-                                error!(message = "Invalid time format", format = %format, %error);
-                                Error::InvalidFunctionArguments(match_fn.name.clone())
-                            })?;
-                        if let Some(grok_alias) = &grok_alias {
-                            context.register_filter(grok_alias, filter);
-                        }
-                        context.append_regex(&result.regex);
-                        return Ok(());
-                    }
-                    Err(Error::InvalidFunctionArguments(match_fn.name.clone()))
+            let (formats, tz) = match match_fn.args.as_deref() {
+                Some([formats]) => (formats, None),
+                Some([formats, tz]) => (formats, Some(tz)),
+                _ => return Err(Error::InvalidFunctionArguments(match_fn.name.clone())),
+            };
+            let formats = match formats {
+                ast::FunctionArgument::Arg(Value::Bytes(b)) => {
+                    vec![String::from_utf8_lossy(b).to_string()]
                 }
-                _ => Err(Error::InvalidFunctionArguments(match_fn.name.clone())),
+                // fallback formats, tried in order, e.g. date(["yyyy-MM-dd HH:mm:ss.SSS", "yyyy-MM-dd HH:mm:ss"])
+                ast::FunctionArgument::Arg(Value::Array(formats)) if !formats.is_empty() => formats
+                    .iter()
+                    .map(|format| match format {
+                        Value::Bytes(b) => Ok(String::from_utf8_lossy(b).to_string()),
+                        _ => Err(Error::InvalidFunctionArguments(match_fn.name.clone())),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+                _ => return Err(Error::InvalidFunctionArguments(match_fn.name.clone())),
             };
+            let target_tz = match tz {
+                None => None,
+                Some(ast::FunctionArgument::Arg(Value::Bytes(b))) => {
+                    let tz = String::from_utf8_lossy(b);
+                    Some(date::parse_timezone(&tz).map_err(|error| {
+                        error!(message = "Invalid(unrecognized) timezone", %error);
+                        Error::InvalidFunctionArguments(match_fn.name.clone())
+                    })?)
+                }
+                Some(_) => return Err(Error::InvalidFunctionArguments(match_fn.name.clone())),
+            };
+
+            let mut filters = Vec::with_capacity(formats.len());
+            let mut regexes = Vec::with_capacity(formats.len());
+            for format in &formats {
+                let (filter, regex) = date_filter_from_format(format, target_tz)?;
+                filters.push(filter);
+                regexes.push(regex);
+            }
+
+            if let Some(grok_alias) = &grok_alias {
+                context.register_filter(grok_alias, GrokFilter::Date(filters));
+            }
+            if regexes.len() == 1 {
+                context.append_regex(&regexes[0]);
+            } else {
+                context.append_regex(&format!("(?:{})", regexes.join("|")));
+            }
+            Ok(())
         }
         // otherwise just add it as is, it should be a known grok pattern
         grok_pattern_name => {
@@ -421,11 +739,328 @@ fn resolves_match_function(
     }
 }
 
+/// Converts flags of the regex matcher, e.g. `regex("[a-z]+", "i")`, to inline Oniguruma(Ruby syntax) options:
+/// - `i` - case-insensitive
+/// - `s` - dot matches newline(`m` in Ruby syntax)
+/// - `x` - extended(whitespace and comments are ignored)
+/// - `m` and `u` are accepted for compatibility, `^`/`$` always match at line boundaries and matching is always unicode-aware
+fn regex_flags(flags: &str) -> Option<String> {
+    let mut options = String::new();
+    for flag in flags.chars() {
+        let option = match flag {
+            'i' => Some('i'),
+            's' => Some('m'),
+            'x' => Some('x'),
+            'm' | 'u' => None,
+            _ => return None,
+        };
+        if let Some(option) = option {
+            if !options.contains(option) {
+                options.push(option);
+            }
+        }
+    }
+    Some(options)
+}
+
+/// Replaces named groups of a regex, e.g. `(?<user.name>\w+)`, with grok-compliant names and registers
+/// them as fields, so that they are promoted to event fields(names can be nested paths).
+fn promote_named_groups(regex: &str, context: &mut GrokRuleParseContext) -> String {
+    let mut result = String::with_capacity(regex.len());
+    let mut chars = regex.chars().peekable();
+    let mut in_class = false;
+    while let Some(c) = chars.next() {
+        result.push(c);
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    result.push(escaped);
+                }
+            }
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '(' if !in_class && chars.peek() == Some(&'?') => {
+                let rest = chars.clone().collect::<String>();
+                let name_start = if rest.starts_with("?P<") {
+                    3
+                } else if rest.starts_with("?<")
+                    && !rest.starts_with("?<=")
+                    && !rest.starts_with("?<!")
+                {
+                    2
+                } else {
+                    continue;
+                };
+                let name_end = match rest.find('>') {
+                    Some(name_end) => name_end,
+                    None => continue,
+                };
+                let name = &rest[name_start..name_end];
+                let grok_name = context.generate_grok_compliant_name();
+                context.register_grok_field(
+                    &grok_name,
+                    GrokField {
+                        lookup: LookupBuf::from_segments(
+                            name.split('.')
+                                .map(|field| SegmentBuf::from(field.to_string()))
+                                .collect(),
+                        ),
+                        filters: vec![],
+                        keep_empty: context.keep_empty.then(|| Value::from("")),
+                    },
+                );
+                result.push_str("?<");
+                result.push_str(&grok_name);
+                result.push('>');
+                for _ in 0..rest[..=name_end].chars().count() {
+                    chars.next();
+                }
+            }
+            _ => {}
+        }
+    }
+    result
+}
+
+/// Returns the value to keep empty captures as for the `keepEmpty` pseudo-filter:
+/// `keepEmpty` keeps them as empty strings, `keepEmpty(null)` as nulls.
+fn keep_empty_from_function(f: &ast::Function) -> Result<Value, Error> {
+    match f.args.as_deref() {
+        None | Some([]) => Ok(Value::from("")),
+        Some([ast::FunctionArgument::Arg(value @ (Value::Bytes(_) | Value::Null))]) => {
+            Ok(value.clone())
+        }
+        _ => Err(Error::InvalidFunctionArguments(f.name.clone())),
+    }
+}
+
+/// Finds the expression referencing an unknown pattern definition,
+/// first in the rule itself and then in the alias definitions.
+fn locate_unknown_definition(name: &str, rule: &str, context: &mut GrokRuleParseContext) {
+    let find = |definition: &str| {
+        GROK_PATTERN_RE
+            .find_iter(definition)
+            .find(|&(start, end)| {
+                parse_grok_pattern(&definition[start..end])
+                    .map_or(false, |pattern| pattern.match_fn.name == name)
+            })
+            .map(|(start, end)| (start..end, definition[start..end].to_string()))
+    };
+
+    let location = find(rule)
+        .map(|(span, token)| (None, span, token))
+        .or_else(|| {
+            context.aliases.iter().find_map(|(alias, definition)| {
+                find(definition).map(|(span, token)| (Some(alias.clone()), span, token))
+            })
+        });
+    if let Some((alias, span, token)) = location {
+        context.locate_error(alias, span, &token);
+    }
+}
+
+/// Finds the closest candidate to a given misspelled name, if any is close enough.
+fn closest_match<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<String> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// The Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut distances = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = distances[0];
+        distances[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(ca != *cb);
+            previous = distances[j + 1];
+            distances[j + 1] = substitution.min(previous + 1).min(distances[j] + 1);
+        }
+    }
+    distances[b.len()]
+}
+
+/// Builds a date filter for a given format, along with a regular expression matching it.
+fn date_filter_from_format(
+    format: &str,
+    target_tz: Option<date::Timezone>,
+) -> Result<(DateFilter, String), Error> {
+    let invalid_arguments = || Error::InvalidFunctionArguments("date".to_string());
+
+    if let Some(epoch) = date::EpochUnit::from_format(format) {
+        let filter = DateFilter {
+            original_format: format.to_string(),
+            strp_format: String::new(),
+            tz_aware: true,
+            regex_with_tz: None,
+            target_tz,
+            epoch: Some(epoch),
+        };
+        return Ok((filter, date::EpochUnit::REGEX.to_string()));
+    }
+
+    let result = date::time_format_to_regex(format, true).map_err(|_e| invalid_arguments())?;
+    let mut regext_opt = None;
+    if result.tz_captured {
+        regext_opt = Some(regex::Regex::new(&result.regex).map_err(|error| {
+            error!(message = "Error compiling regex", regex = %result.regex, %error);
+            invalid_arguments()
+        })?);
+    }
+    let strp_format = date::convert_time_format(format).map_err(|error| {
+        error!(message = "Error compiling regex", regex = %result.regex, %error);
+        invalid_arguments()
+    })?;
+    let filter = DateFilter {
+        original_format: format.to_string(),
+        strp_format,
+        regex_with_tz: regext_opt,
+        target_tz,
+        tz_aware: result.with_tz,
+        epoch: None,
+    };
+    let result = date::time_format_to_regex(format, false).map_err(|error| {
+        error!(message = "Invalid time format", format = %format, %error);
+        invalid_arguments()
+    })?;
+    Ok((filter, result.regex))
+}
+
 // test some tricky cases here, more high-level tests are in parse_grok
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn reports_error_locations_with_suggestions() {
+        let diagnostic = parse_grok_rules(
+            &[
+                "%{word:first}".to_string(),
+                "%{word:first} %{data:field:lowercas}".to_string(),
+            ],
+            BTreeMap::new(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            diagnostic,
+            Diagnostic {
+                error: Error::UnknownFilter("lowercas".to_string()),
+                rule_index: 1,
+                alias: None,
+                span: Some(14..36),
+                token: Some("%{data:field:lowercas}".to_string()),
+                suggestion: Some("did you mean 'lowercase'?".to_string()),
+            }
+        );
+        // the message of the underlying error is kept as is
+        assert_eq!(diagnostic.to_string(), "unknown filter 'lowercas'");
+
+        // errors inside of alias definitions are located within the alias
+        let diagnostic = parse_grok_rules(
+            &["%{_status}".to_string()],
+            BTreeMap::from([(
+                "_status".to_string(),
+                "%{integer:status} %{regex(1):field}".to_string(),
+            )]),
+        )
+        .unwrap_err();
+        assert_eq!(diagnostic.alias.as_deref(), Some("_status"));
+        assert_eq!(diagnostic.span, Some(18..35));
+        assert_eq!(diagnostic.suggestion, None);
+
+        // unknown pattern definitions are only found when the whole rule is compiled
+        let diagnostic =
+            parse_grok_rules(&["%{word} %{notSpac:field}".to_string()], BTreeMap::new())
+                .unwrap_err();
+        assert_eq!(diagnostic.span, Some(8..24));
+        assert_eq!(diagnostic.token.as_deref(), Some("%{notSpac:field}"));
+        assert_eq!(
+            diagnostic.suggestion.as_deref(),
+            Some("did you mean 'notSpace'?")
+        );
+    }
+
+    #[test]
+    fn suggests_closest_names() {
+        let candidates = ["lowercase", "uppercase", "integer"];
+        assert_eq!(
+            closest_match("uppercas", candidates.into_iter()),
+            Some("uppercase".to_string())
+        );
+        assert_eq!(
+            closest_match("interger", candidates.into_iter()),
+            Some("integer".to_string())
+        );
+        assert_eq!(closest_match("json", candidates.into_iter()), None);
+    }
+
+    #[test]
+    fn parses_pattern_definitions() {
+        let definitions = parse_pattern_definitions(
+            "# a comment\n\nUSERNAME [a-zA-Z0-9._-]+\nUSER\t%{USERNAME}\n  HOSTPORT %{IPORHOST}:%{POSINT}  \n",
+        )
+        .unwrap();
+        assert_eq!(
+            definitions,
+            BTreeMap::from([
+                ("USERNAME".to_string(), "[a-zA-Z0-9._-]+".to_string()),
+                ("USER".to_string(), "%{USERNAME}".to_string()),
+                ("HOSTPORT".to_string(), "%{IPORHOST}:%{POSINT}".to_string()),
+            ])
+        );
+
+        assert_eq!(
+            parse_pattern_definitions("USERNAME [a-z]+\nINVALID"),
+            Err("line 2: expected the format 'NAME definition', got 'INVALID'".to_string())
+        );
+    }
+
+    #[test]
+    fn reads_pattern_files() {
+        let dir = std::env::temp_dir().join(format!("grok-patterns-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("a"),
+            "APP_ID app-[0-9]+\nAPP_LEVEL (?:INFO|WARN)\n",
+        )
+        .unwrap();
+        fs::write(dir.join("b"), "APP_LEVEL (?:INFO|WARN|ERROR)\n").unwrap();
+
+        let definitions = read_pattern_files(&[&dir]).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            definitions.get("APP_LEVEL").map(String::as_str),
+            Some("(?:INFO|WARN|ERROR)")
+        );
+
+        let rules = parse_grok_rules_with_options(
+            &["%{APP_ID:app} %{APP_LEVEL:level}".to_string()],
+            BTreeMap::new(),
+            CompileOptions {
+                definitions,
+                ..Default::default()
+            },
+        )
+        .expect("couldn't parse rules");
+        assert_eq!(
+            crate::parse_grok::parse_grok("app-12 ERROR", &rules),
+            Ok(Value::from(BTreeMap::from([
+                ("app".to_string(), Value::from("app-12")),
+                ("level".to_string(), Value::from("ERROR")),
+            ])))
+        );
+
+        assert!(matches!(
+            read_pattern_files(&[dir.join("missing")]),
+            Err(Error::InvalidPatternFile(..))
+        ));
+    }
+
     #[test]
     fn supports_escaped_quotes() {
         let rules = parse_grok_rules(
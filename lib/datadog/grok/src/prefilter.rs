@@ -0,0 +1,283 @@
+use regex::{Regex, RegexSet, RegexSetBuilder};
+
+use crate::parse_grok_rules::GrokRule;
+
+// a pre-filter is only worth an extra pass over the value if it can rule out several rules
+const MIN_PREFILTERED_RULES: usize = 2;
+const SIZE_LIMIT: usize = 64 * (1 << 20);
+
+/// A pre-filter, finding the rules that may match a given value in a single pass over it.
+///
+/// Rules are compiled past the grok syntax with onig, which supports backtracking constructs
+/// the `regex` crate does not. Each rule is translated to a regex matching a superset of what
+/// the original rule matches(e.g. atomic groups become non-capturing ones, possessive quantifiers
+/// become greedy ones), rules which can't be translated are always tried.
+#[derive(Debug, Clone)]
+pub struct GrokPrefilter {
+    set: Option<RegexSet>,
+    // indices of the rules in the set
+    prefiltered: Vec<usize>,
+    // indices of the rules the set can't tell anything about
+    unfiltered: Vec<usize>,
+}
+
+impl GrokPrefilter {
+    pub fn new(grok_rules: &[GrokRule]) -> Self {
+        let (mut prefiltered, mut regexes, mut unfiltered) = (vec![], vec![], vec![]);
+        for (i, rule) in grok_rules.iter().enumerate() {
+            match translate(rule.pattern.as_str()).filter(|regex| Regex::new(regex).is_ok()) {
+                Some(regex) => {
+                    prefiltered.push(i);
+                    regexes.push(regex);
+                }
+                None => unfiltered.push(i),
+            }
+        }
+
+        let set = if prefiltered.len() >= MIN_PREFILTERED_RULES {
+            RegexSetBuilder::new(regexes)
+                .size_limit(SIZE_LIMIT)
+                .build()
+                .ok()
+        } else {
+            None
+        };
+        if set.is_none() {
+            unfiltered = (0..grok_rules.len()).collect();
+            prefiltered.clear();
+        }
+
+        Self {
+            set,
+            prefiltered,
+            unfiltered,
+        }
+    }
+
+    /// Returns indices of the rules that may match a given value, in the order of the rules.
+    pub fn candidates(&self, value: &str) -> Vec<usize> {
+        let mut candidates = self.unfiltered.clone();
+        if let Some(set) = &self.set {
+            candidates.extend(set.matches(value).iter().map(|i| self.prefiltered[i]));
+            candidates.sort_unstable();
+        }
+        candidates
+    }
+}
+
+/// Translates an onig regex(in the Ruby syntax) to the syntax of the `regex` crate,
+/// matching a superset of the original matches: look-arounds are dropped and backreferences
+/// match anything. Returns `None` for unsupported constructs, e.g. `\G` or conditionals.
+fn translate(onig: &str) -> Option<String> {
+    let chars = onig.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(onig.len());
+    let mut class_depth = 0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' => {
+                let escaped = *chars.get(i + 1)?;
+                i += 1;
+                match escaped {
+                    'A' | 'z' | 'b' | 'B' if class_depth == 0 => {
+                        result.push('\\');
+                        result.push(escaped);
+                    }
+                    'Z' if class_depth == 0 => result.push_str(r"(?:\n?\z)"),
+                    'd' | 'D' | 'w' | 'W' | 's' | 'S' | 'n' | 'r' | 't' | 'f' | 'v' => {
+                        result.push('\\');
+                        result.push(escaped);
+                    }
+                    '1'..='9' if class_depth == 0 => {
+                        while chars.get(i + 1).map_or(false, char::is_ascii_digit) {
+                            i += 1;
+                        }
+                        result.push_str("(?s:.*)");
+                    }
+                    'k' if class_depth == 0 && chars.get(i + 1) == Some(&'<') => {
+                        while chars.get(i) != Some(&'>') {
+                            i += 1;
+                            chars.get(i)?;
+                        }
+                        result.push_str("(?s:.*)");
+                    }
+                    'h' => result.push_str("[0-9A-Fa-f]"),
+                    'H' => result.push_str("[^0-9A-Fa-f]"),
+                    'x' | 'p' | 'P' => {
+                        // \xHH, \x{HHHH}, \p{Class} are supported as is
+                        result.push('\\');
+                        result.push(escaped);
+                        if chars.get(i + 1) == Some(&'{') {
+                            while chars.get(i) != Some(&'}') {
+                                i += 1;
+                                result.push(*chars.get(i)?);
+                            }
+                        }
+                    }
+                    c if is_meta_character(c) => {
+                        result.push('\\');
+                        result.push(c);
+                    }
+                    c if c.is_ascii_alphanumeric() => return None,
+                    c => result.push(c),
+                }
+            }
+            '[' => {
+                if class_depth > 0 && chars.get(i + 1) == Some(&':') {
+                    // a POSIX class, e.g. [:alpha:]
+                    let end =
+                        (i..chars.len() - 1).find(|&j| chars[j] == ':' && chars[j + 1] == ']')?;
+                    result.extend(&chars[i..=end + 1]);
+                    i = end + 1;
+                } else {
+                    class_depth += 1;
+                    result.push('[');
+                }
+            }
+            ']' if class_depth > 0 => {
+                class_depth -= 1;
+                result.push(']');
+            }
+            _ if class_depth > 0 => result.push(c),
+            '(' if chars.get(i + 1) == Some(&'?') => {
+                let rest = chars[i + 2..].iter().collect::<String>();
+                if ["=", "!", "<=", "<!"].iter().any(|p| rest.starts_with(p)) {
+                    // look-arounds only reject matches
+                    i = group_end(&chars, i)?;
+                } else if rest.starts_with('<') {
+                    // named groups are only needed to extract values
+                    let end = rest.find('>')?;
+                    result.push_str("(?:");
+                    i += 2 + end;
+                } else if rest.starts_with('>') || rest.starts_with(':') {
+                    // atomic groups never match more than regular ones
+                    result.push_str("(?:");
+                    i += 2;
+                } else {
+                    // flags, e.g. (?i), (?m:...) - onig uses `m` to make the dot match new lines
+                    let end = rest.find([')', ':'])?;
+                    let flags = &rest[..end];
+                    if flags.is_empty()
+                        || !flags.chars().all(|c| matches!(c, 'i' | 'm' | 'x' | '-'))
+                    {
+                        return None;
+                    }
+                    result.push_str("(?");
+                    result.push_str(&flags.replace('m', "s"));
+                    result.push_str(&rest[end..=end]);
+                    i += 2 + end;
+                }
+            }
+            // onig anchors always match at line boundaries
+            '^' => result.push_str("(?m:^)"),
+            '$' => result.push_str("(?m:$)"),
+            '*' | '+' | '?' | '}' => {
+                result.push(c);
+                // possessive quantifiers never match more than greedy ones
+                if chars.get(i + 1) == Some(&'+') {
+                    i += 1;
+                }
+            }
+            _ => result.push(c),
+        }
+        i += 1;
+    }
+
+    Some(result)
+}
+
+/// Finds the closing parenthesis of a group starting at a given index.
+fn group_end(chars: &[char], start: usize) -> Option<usize> {
+    let (mut depth, mut class_depth, mut i) = (0, 0, start);
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '[' => class_depth += 1,
+            ']' if class_depth > 0 => class_depth -= 1,
+            '(' if class_depth == 0 => depth += 1,
+            ')' if class_depth == 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+fn is_meta_character(c: char) -> bool {
+    matches!(
+        c,
+        '\\' | '.'
+            | '+'
+            | '*'
+            | '?'
+            | '('
+            | ')'
+            | '|'
+            | '['
+            | ']'
+            | '{'
+            | '}'
+            | '^'
+            | '$'
+            | '#'
+            | '&'
+            | '-'
+            | '~'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::parse_grok_rules::parse_grok_rules;
+
+    #[test]
+    fn translates_onig_syntax() {
+        assert_eq!(
+            translate(r#"\A(?<name0>(?>\w+|-))\s*+(?m).*?[\h\]:]$\z"#).unwrap(),
+            r#"\A(?:(?:\w+|-))\s*(?s).*?[[0-9A-Fa-f]\]:](?m:$)\z"#
+        );
+        assert_eq!(
+            translate(r#"(?i:true)\/\x{41}"#).unwrap(),
+            r#"(?i:true)/\x{41}"#
+        );
+
+        // look-arounds are dropped, backreferences match anything
+        assert_eq!(translate(r#"(?<![0-9(])\d+(?=[)]|\))"#).unwrap(), r#"\d+"#);
+        assert_eq!(
+            translate(r#"(?<q>['"])\w+\k<q>"#).unwrap(),
+            r#"(?:['"])\w+(?s:.*)"#
+        );
+        assert_eq!(translate(r#"(\w)\12"#).unwrap(), r#"(\w)(?s:.*)"#);
+        assert_eq!(translate(r#"\Gabc"#), None);
+    }
+
+    #[test]
+    fn finds_candidate_rules() {
+        let rules = parse_grok_rules(
+            &[
+                "%{integer:status} %{word:method}".to_string(),
+                r#"%{regex("\\G\\d+"):number}"#.to_string(),
+                "%{word:method} %{integer:status}".to_string(),
+                "%{ipv4:ip}".to_string(),
+            ],
+            BTreeMap::new(),
+        )
+        .expect("couldn't parse rules");
+        let prefilter = GrokPrefilter::new(&rules);
+
+        // `\G` can't be translated, so that rule is always tried
+        assert_eq!(prefilter.candidates("GET 200"), vec![1, 2]);
+        assert_eq!(prefilter.candidates("200 GET"), vec![0, 1]);
+        assert_eq!(prefilter.candidates("127.0.0.1"), vec![1, 3]);
+    }
+}
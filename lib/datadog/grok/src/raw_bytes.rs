@@ -0,0 +1,107 @@
+//! Our regex engine(onig) only matches valid UTF-8 strings, so that input bytes, which are not valid UTF-8,
+//! are escaped to private-use characters(U+F780..=U+F7FF, one per byte) before matching and unescaped
+//! in parsed values afterwards, instead of being lossily replaced.
+//! Valid characters from the same range are escaped byte by byte too, so unescaping is unambiguous.
+
+use std::str;
+
+use bytes::Bytes;
+use value::Value;
+
+const ESCAPE_BASE: u32 = 0xF700;
+const ESCAPE_START: char = '\u{F780}';
+const ESCAPE_END: char = '\u{F7FF}';
+
+/// Escapes bytes, which are not valid UTF-8, returning a string safe to match.
+pub fn escape(mut input: &[u8]) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    loop {
+        match str::from_utf8(input) {
+            Ok(valid) => {
+                push_valid(&mut escaped, valid);
+                return escaped;
+            }
+            Err(error) => {
+                let (valid, rest) = input.split_at(error.valid_up_to());
+                push_valid(
+                    &mut escaped,
+                    str::from_utf8(valid).expect("valid up to this point"),
+                );
+                let invalid_len = error.error_len().unwrap_or(rest.len());
+                rest[..invalid_len]
+                    .iter()
+                    .for_each(|byte| escaped.push(escape_byte(*byte)));
+                input = &rest[invalid_len..];
+            }
+        }
+    }
+}
+
+fn push_valid(escaped: &mut String, valid: &str) {
+    for c in valid.chars() {
+        if (ESCAPE_START..=ESCAPE_END).contains(&c) {
+            c.encode_utf8(&mut [0; 4])
+                .bytes()
+                .for_each(|byte| escaped.push(escape_byte(byte)));
+        } else {
+            escaped.push(c);
+        }
+    }
+}
+
+fn escape_byte(byte: u8) -> char {
+    // only bytes >= 0x80 can be part of invalid UTF-8 sequences or of the escape range
+    char::from_u32(ESCAPE_BASE + byte as u32).expect("a valid private-use character")
+}
+
+/// Unescapes a string, escaped with `escape`, to the original bytes.
+pub fn unescape(escaped: &str) -> Bytes {
+    let mut bytes = Vec::with_capacity(escaped.len());
+    for c in escaped.chars() {
+        if (ESCAPE_START..=ESCAPE_END).contains(&c) {
+            bytes.push((c as u32 - ESCAPE_BASE) as u8);
+        } else {
+            bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+        }
+    }
+    bytes.into()
+}
+
+/// Unescapes all strings in a parsed value.
+pub fn unescape_value(value: Value) -> Value {
+    match value {
+        Value::Bytes(bytes) => match str::from_utf8(&bytes) {
+            Ok(escaped) => Value::Bytes(unescape(escaped)),
+            Err(_) => Value::Bytes(bytes),
+        },
+        Value::Array(values) => Value::Array(values.into_iter().map(unescape_value).collect()),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| {
+                    let k = String::from_utf8_lossy(&unescape(&k)).into_owned();
+                    (k, unescape_value(v))
+                })
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_any_bytes() {
+        for input in [
+            &b"plain ascii"[..],
+            "ünicode".as_bytes(),
+            b"invalid \xff\xfe bytes",
+            b"truncated \xe2\x82",
+            "escape range \u{F781}".as_bytes(),
+        ] {
+            assert_eq!(unescape(&escape(input)), Bytes::copy_from_slice(input));
+        }
+        assert_eq!(escape("ünicode".as_bytes()), "ünicode");
+    }
+}
@@ -0,0 +1,82 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// Match counters of a rule set, updated while parsing values with `ParseOptions::stats`,
+/// e.g. to find rules, which never match.
+/// Counters can be updated concurrently, so that the same stats can be shared by several threads.
+#[derive(Debug, Default)]
+pub struct GrokStats {
+    rules: Vec<RuleCounters>,
+    no_match: AtomicU64,
+}
+
+#[derive(Debug, Default)]
+struct RuleCounters {
+    attempts: AtomicU64,
+    matched: AtomicU64,
+    match_nanos: AtomicU64,
+}
+
+/// A snapshot of the counters of a single rule.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuleStats {
+    /// how many values the rule was tried against
+    pub attempts: u64,
+    /// how many values matched the rule
+    pub matched: u64,
+    /// the total time spent matching values against the rule, including applying filters
+    pub match_time: Duration,
+}
+
+impl RuleStats {
+    /// Returns the average time it takes to try the rule, if it was tried at all.
+    pub fn average_match_time(&self) -> Option<Duration> {
+        u32::try_from(self.attempts)
+            .ok()
+            .filter(|attempts| *attempts > 0)
+            .map(|attempts| self.match_time / attempts)
+    }
+}
+
+impl GrokStats {
+    /// Creates empty stats for a given number of rules.
+    pub fn new(rule_count: usize) -> Self {
+        Self {
+            rules: (0..rule_count).map(|_| RuleCounters::default()).collect(),
+            no_match: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the counters of the rule with a given index.
+    pub fn rule(&self, index: usize) -> Option<RuleStats> {
+        self.rules.get(index).map(|counters| RuleStats {
+            attempts: counters.attempts.load(Ordering::Relaxed),
+            matched: counters.matched.load(Ordering::Relaxed),
+            match_time: Duration::from_nanos(counters.match_nanos.load(Ordering::Relaxed)),
+        })
+    }
+
+    /// Returns how many values didn't match any rule.
+    pub fn no_match(&self) -> u64 {
+        self.no_match.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn record_attempt(&self, index: usize, matched: bool, elapsed: Duration) {
+        if let Some(counters) = self.rules.get(index) {
+            counters.attempts.fetch_add(1, Ordering::Relaxed);
+            if matched {
+                counters.matched.fetch_add(1, Ordering::Relaxed);
+            }
+            counters.match_nanos.fetch_add(
+                u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX),
+                Ordering::Relaxed,
+            );
+        }
+    }
+
+    pub(crate) fn record_no_match(&self) {
+        self.no_match.fetch_add(1, Ordering::Relaxed);
+    }
+}
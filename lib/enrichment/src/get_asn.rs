@@ -0,0 +1,143 @@
+use value::{kind::Collection, Value};
+use vrl::prelude::FunctionExpression;
+use vrl::state::TypeState;
+use vrl::{
+    function::{ArgumentList, Compiled, Example, FunctionCompileContext, Parameter},
+    prelude::{Resolved, TypeDef},
+    value::kind,
+    Context, Expression, Function,
+};
+
+use crate::{
+    vrl_util::{compile_ip_lookup, lookup_ip},
+    IndexHandle, TableSearch,
+};
+
+/// The fields of an ASN database record, ignoring any other fields of an ISP database record.
+const FIELDS: [&str; 2] = ["autonomous_system_number", "autonomous_system_organization"];
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetAsn;
+impl Function for GetAsn {
+    fn identifier(&self) -> &'static str {
+        "get_asn"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "table",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "look up an IP address",
+            source: r#"get_asn!("2600:7000::1")"#,
+            result: Ok(
+                r#"{"autonomous_system_number": 6939, "autonomous_system_organization": "Hurricane Electric, Inc."}"#,
+            ),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let (table, index, enrichment_tables) = compile_ip_lookup(ctx, &arguments, "asn")?;
+
+        Ok(GetAsnFn {
+            value,
+            table,
+            select: FIELDS.iter().map(|field| field.to_string()).collect(),
+            index,
+            enrichment_tables,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetAsnFn {
+    value: Box<dyn Expression>,
+    table: String,
+    select: Vec<String>,
+    index: Option<IndexHandle>,
+    enrichment_tables: TableSearch,
+}
+
+impl FunctionExpression for GetAsnFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        lookup_ip(
+            value,
+            &self.enrichment_tables,
+            &self.table,
+            Some(&self.select),
+            self.index,
+        )
+        .map(Value::Object)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::object(Collection::any()).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use value::Secrets;
+    use vector_common::TimeZone;
+    use vrl::{prelude::expression, TargetValue};
+
+    use super::*;
+    use crate::test_util::{get_table_registry_with_tables, DummyEnrichmentTable};
+
+    #[test]
+    fn looks_up_ip() {
+        let registry = get_table_registry_with_tables(vec![(
+            "asn".to_string(),
+            DummyEnrichmentTable::new_with_data(BTreeMap::from([(
+                "autonomous_system_number".to_string(),
+                Value::from(6939),
+            )])),
+        )]);
+        let func = GetAsnFn {
+            value: Box::new(expression::Literal::from("2600:7000::1")),
+            table: "asn".to_string(),
+            select: FIELDS.iter().map(|field| field.to_string()).collect(),
+            index: Some(IndexHandle(0)),
+            enrichment_tables: registry.as_readonly(),
+        };
+
+        let tz = TimeZone::default();
+        let mut target = TargetValue {
+            value: vrl::value!({}),
+            metadata: vrl::value!({}),
+            secrets: Secrets::new(),
+        };
+        let mut runtime_state = vrl::state::Runtime::default();
+        let mut ctx = Context::new(&mut target, &mut runtime_state, &tz);
+
+        registry.finish_load();
+
+        assert_eq!(
+            Ok(vrl::value!({ "autonomous_system_number": 6939 })),
+            func.resolve(&mut ctx)
+        );
+    }
+}
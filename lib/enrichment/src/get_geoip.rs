@@ -0,0 +1,135 @@
+use value::{kind::Collection, Value};
+use vrl::prelude::FunctionExpression;
+use vrl::state::TypeState;
+use vrl::{
+    function::{ArgumentList, Compiled, Example, FunctionCompileContext, Parameter},
+    prelude::{Resolved, TypeDef},
+    value::kind,
+    Context, Expression, Function,
+};
+
+use crate::{
+    vrl_util::{compile_ip_lookup, lookup_ip},
+    IndexHandle, TableSearch,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetGeoip;
+impl Function for GetGeoip {
+    fn identifier(&self) -> &'static str {
+        "get_geoip"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "table",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "look up an IP address",
+            source: r#"get_geoip!("2.125.160.216", table: "geoip")"#,
+            result: Ok(r#"{"city_name": "Boxford", "country_code": "GB"}"#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let (table, index, enrichment_tables) = compile_ip_lookup(ctx, &arguments, "geoip")?;
+
+        Ok(GetGeoipFn {
+            value,
+            table,
+            index,
+            enrichment_tables,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetGeoipFn {
+    value: Box<dyn Expression>,
+    table: String,
+    index: Option<IndexHandle>,
+    enrichment_tables: TableSearch,
+}
+
+impl FunctionExpression for GetGeoipFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        lookup_ip(
+            value,
+            &self.enrichment_tables,
+            &self.table,
+            None,
+            self.index,
+        )
+        .map(Value::Object)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::object(Collection::any()).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use value::Secrets;
+    use vector_common::TimeZone;
+    use vrl::{prelude::expression, TargetValue};
+
+    use super::*;
+    use crate::test_util::{get_table_registry_with_tables, DummyEnrichmentTable};
+
+    #[test]
+    fn looks_up_ip() {
+        let registry = get_table_registry_with_tables(vec![(
+            "geoip".to_string(),
+            DummyEnrichmentTable::new_with_data(BTreeMap::from([(
+                "city_name".to_string(),
+                Value::from("Boxford"),
+            )])),
+        )]);
+        let func = GetGeoipFn {
+            value: Box::new(expression::Literal::from("2.125.160.216")),
+            table: "geoip".to_string(),
+            index: Some(IndexHandle(0)),
+            enrichment_tables: registry.as_readonly(),
+        };
+
+        let tz = TimeZone::default();
+        let mut target = TargetValue {
+            value: vrl::value!({}),
+            metadata: vrl::value!({}),
+            secrets: Secrets::new(),
+        };
+        let mut runtime_state = vrl::state::Runtime::default();
+        let mut ctx = Context::new(&mut target, &mut runtime_state, &tz);
+
+        registry.finish_load();
+
+        assert_eq!(
+            Ok(vrl::value!({ "city_name": "Boxford" })),
+            func.resolve(&mut ctx)
+        );
+    }
+}
@@ -1,13 +1,15 @@
 #![deny(warnings)]
 
 pub mod find_enrichment_table_records;
+pub mod get_asn;
 pub mod get_enrichment_table_record;
+pub mod get_geoip;
 pub mod tables;
 
 #[cfg(test)]
 mod test_util;
 mod vrl_util;
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, net::IpAddr};
 
 use dyn_clone::DynClone;
 pub use tables::{TableRegistry, TableSearch};
@@ -26,6 +28,13 @@ pub enum Condition<'a> {
         from: chrono::DateTime<chrono::Utc>,
         to: chrono::DateTime<chrono::Utc>,
     },
+    /// The field value starts with the prefix.
+    StartsWith { field: &'a str, prefix: String },
+    /// The field value matches the wildcard pattern, where `*` matches any number of characters
+    /// and `?` matches a single character.
+    Wildcard { field: &'a str, pattern: String },
+    /// The field value is a CIDR block(or a single IP address) containing the IP address.
+    ContainsIp { field: &'a str, ip: IpAddr },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -81,5 +90,7 @@ pub fn vrl_functions() -> Vec<Box<dyn vrl::Function>> {
     vec![
         Box::new(get_enrichment_table_record::GetEnrichmentTableRecord) as _,
         Box::new(find_enrichment_table_records::FindEnrichmentTableRecords) as _,
+        Box::new(get_geoip::GetGeoip) as _,
+        Box::new(get_asn::GetAsn) as _,
     ]
 }
@@ -1,4 +1,4 @@
-//! Utilities shared between both VRL functions.
+//! Utilities shared between the VRL functions.
 use std::collections::BTreeMap;
 
 use ::value::Value;
@@ -7,17 +7,21 @@ use vrl::{
     prelude::*,
 };
 
-use crate::{Case, Condition, IndexHandle, TableRegistry};
+use crate::{Case, Condition, IndexHandle, TableRegistry, TableSearch};
 
 #[derive(Debug)]
 pub enum Error {
     TablesNotLoaded,
+    DefaultTableNotFound(&'static str),
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Error::TablesNotLoaded => write!(f, "enrichment tables not loaded"),
+            Error::DefaultTableNotFound(table) => {
+                write!(f, "enrichment table '{}' not found", table)
+            }
         }
     }
 }
@@ -37,6 +41,15 @@ impl DiagnosticMessage for Error {
                     Span::default(),
                 )]
             }
+            Error::DefaultTableNotFound(table) => {
+                vec![Label::primary(
+                    format!(
+                        "enrichment table error: no table named '{}', set the table to look up",
+                        table
+                    ),
+                    Span::default(),
+                )]
+            }
         }
     }
 }
@@ -59,10 +72,53 @@ pub(crate) fn evaluate_condition(key: &str, value: Value) -> Result<Condition> {
                     .ok_or("to in condition must be a timestamp")?,
             }
         }
+        Value::Object(map) if map.len() == 1 && map.contains_key("prefix") => {
+            Condition::StartsWith {
+                field: key,
+                prefix: map
+                    .get("prefix")
+                    .expect("should contain prefix")
+                    .as_str()
+                    .ok_or("prefix in condition must be a string")?
+                    .into_owned(),
+            }
+        }
+        Value::Object(map) if map.len() == 1 && map.contains_key("wildcard") => {
+            Condition::Wildcard {
+                field: key,
+                pattern: map
+                    .get("wildcard")
+                    .expect("should contain wildcard")
+                    .as_str()
+                    .ok_or("wildcard in condition must be a string")?
+                    .into_owned(),
+            }
+        }
+        Value::Object(map) if map.len() == 1 && map.contains_key("contains_ip") => {
+            Condition::ContainsIp {
+                field: key,
+                ip: map
+                    .get("contains_ip")
+                    .expect("should contain contains_ip")
+                    .as_str()
+                    .and_then(|ip| ip.parse().ok())
+                    .ok_or("contains_ip in condition must be an IP address")?,
+            }
+        }
         _ => Condition::Equals { field: key, value },
     })
 }
 
+/// Returns whether a condition object compares a field, rather than matching it exactly,
+/// so that the field can't be indexed.
+fn is_comparison(map: &BTreeMap<String, expression::Expr>) -> bool {
+    (map.contains_key("from") && map.contains_key("to"))
+        || (map.len() == 1
+            && ["prefix", "wildcard", "contains_ip"]
+                .iter()
+                .any(|key| map.contains_key(*key)))
+}
+
 /// Add an index for the given condition to the given enrichment table.
 pub(crate) fn add_index(
     registry: &mut TableRegistry,
@@ -75,7 +131,7 @@ pub(crate) fn add_index(
         .filter_map(|(field, value)| match value {
             expression::Expr::Container(expression::Container {
                 variant: expression::Variant::Object(map),
-            }) if map.contains_key("from") && map.contains_key("to") => None,
+            }) if is_comparison(map) => None,
             _ => Some(field.as_ref()),
         })
         .collect::<Vec<_>>();
@@ -84,6 +140,55 @@ pub(crate) fn add_index(
     Ok(index)
 }
 
+/// Compiles the `table` argument of the functions looking up IP addresses in GeoIP tables,
+/// defaulting to the table named `default_table`, and adds an index to look up IP addresses.
+pub(crate) fn compile_ip_lookup(
+    ctx: &mut FunctionCompileContext,
+    arguments: &ArgumentList,
+    default_table: &'static str,
+) -> std::result::Result<(String, Option<IndexHandle>, TableSearch), Box<dyn DiagnosticMessage>> {
+    let registry = ctx
+        .get_external_context_mut::<TableRegistry>()
+        .ok_or(Box::new(Error::TablesNotLoaded) as Box<dyn DiagnosticMessage>)?;
+
+    let tables = registry
+        .table_ids()
+        .into_iter()
+        .map(Value::from)
+        .collect::<Vec<_>>();
+
+    let table = match arguments.optional_enum("table", &tables)? {
+        Some(table) => table
+            .try_bytes_utf8_lossy()
+            .expect("table is not valid utf8")
+            .into_owned(),
+        None if tables.contains(&Value::from(default_table)) => default_table.to_owned(),
+        None => return Err(Box::new(Error::DefaultTableNotFound(default_table))),
+    };
+
+    let index = registry
+        .add_index(&table, Case::Sensitive, &["ip"])
+        .map_err(|err| Box::new(ExpressionError::from(err)) as Box<dyn DiagnosticMessage>)?;
+
+    Ok((table, Some(index), registry.as_readonly()))
+}
+
+/// Looks up an IP address in a GeoIP table.
+pub(crate) fn lookup_ip(
+    ip: Value,
+    enrichment_tables: &TableSearch,
+    table: &str,
+    select: Option<&[String]>,
+    index: Option<IndexHandle>,
+) -> std::result::Result<BTreeMap<String, Value>, ExpressionError> {
+    let condition = [Condition::Equals {
+        field: "ip",
+        value: ip,
+    }];
+
+    Ok(enrichment_tables.find_table_row(table, Case::Sensitive, &condition, select, index)?)
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::{Arc, Mutex};
@@ -105,6 +210,40 @@ mod tests {
         assert_eq!(IndexHandle(0), index);
     }
 
+    #[test]
+    fn evaluates_comparison_conditions() {
+        assert_eq!(
+            Ok(Condition::StartsWith {
+                field: "host",
+                prefix: "www.".to_string(),
+            }),
+            evaluate_condition("host", value!({ "prefix": "www." }))
+        );
+        assert_eq!(
+            Ok(Condition::Wildcard {
+                field: "host",
+                pattern: "*.com".to_string(),
+            }),
+            evaluate_condition("host", value!({ "wildcard": "*.com" }))
+        );
+        assert_eq!(
+            Ok(Condition::ContainsIp {
+                field: "subnet",
+                ip: "10.0.0.1".parse().unwrap(),
+            }),
+            evaluate_condition("subnet", value!({ "contains_ip": "10.0.0.1" }))
+        );
+        assert!(evaluate_condition("subnet", value!({ "contains_ip": "not an ip" })).is_err());
+        // objects with other keys are still matched exactly
+        assert_eq!(
+            Ok(Condition::Equals {
+                field: "host",
+                value: value!({ "prefix": "www.", "other": 1 }),
+            }),
+            evaluate_condition("host", value!({ "prefix": "www.", "other": 1 }))
+        );
+    }
+
     #[test]
     fn add_indexes_with_dates() {
         let indexes = Arc::new(Mutex::new(Vec::new()));
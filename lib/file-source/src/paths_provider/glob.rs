@@ -1,9 +1,9 @@
 //! [`Glob`] paths provider.
 
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 
-pub use glob::MatchOptions;
 use glob::Pattern;
+pub use glob::{MatchOptions, PatternError};
 
 use super::PathsProvider;
 use crate::FileSourceInternalEvents;
@@ -11,10 +11,13 @@ use crate::FileSourceInternalEvents;
 /// A glob-based path provider.
 ///
 /// Provides the paths to the files on the file system that match include
-/// patterns and don't match the exclude patterns.
+/// patterns and don't match the exclude patterns nor the ignore rules.
 pub struct Glob<E: FileSourceInternalEvents> {
     include_patterns: Vec<String>,
     exclude_patterns: Vec<Pattern>,
+    ignore_rules: Vec<IgnoreRule>,
+    symlink_policy: SymlinkPolicy,
+    max_depth: Option<usize>,
     glob_match_options: MatchOptions,
     emitter: E,
 }
@@ -42,10 +45,56 @@ impl<E: FileSourceInternalEvents> Glob<E> {
         Some(Self {
             include_patterns,
             exclude_patterns,
+            ignore_rules: Vec::new(),
+            symlink_policy: SymlinkPolicy::Follow,
+            max_depth: None,
             glob_match_options,
             emitter,
         })
     }
+
+    /// Sets the gitignore-style rules applied to the paths matched by the include patterns.
+    pub fn with_ignore_rules(mut self, ignore_rules: Vec<IgnoreRule>) -> Self {
+        self.ignore_rules = ignore_rules;
+        self
+    }
+
+    /// Sets which symbolic links are followed.
+    pub const fn with_symlink_policy(mut self, symlink_policy: SymlinkPolicy) -> Self {
+        self.symlink_policy = symlink_policy;
+        self
+    }
+
+    /// Sets how deep below the base directory of an include pattern files are provided, where
+    /// `1` only provides the files directly within the base directory.
+    pub const fn with_max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    fn is_excluded(&self, candidate_path: &Path) -> bool {
+        let candidate_path_str = candidate_path.to_str().unwrap();
+        self.exclude_patterns
+            .iter()
+            .any(|exclude_pattern| exclude_pattern.matches(candidate_path_str))
+    }
+
+    /// Whether the candidate path, below the base directory of the include pattern it matched,
+    /// passes the ignore rules, the symlink policy and the depth limit.
+    fn is_allowed(&self, base: &Path, candidate_path: &Path) -> bool {
+        let relative = match candidate_path.strip_prefix(base) {
+            Ok(relative) => relative,
+            Err(_) => return true,
+        };
+
+        if let Some(max_depth) = self.max_depth {
+            if relative.components().count() > max_depth {
+                return false;
+            }
+        }
+
+        !is_ignored(&self.ignore_rules, relative) && self.symlink_policy.allows(base, relative)
+    }
 }
 
 impl<E: FileSourceInternalEvents> PathsProvider for Glob<E> {
@@ -55,6 +104,7 @@ impl<E: FileSourceInternalEvents> PathsProvider for Glob<E> {
         self.include_patterns
             .iter()
             .flat_map(|include_pattern| {
+                let base = literal_base(include_pattern);
                 glob::glob_with(include_pattern.as_str(), self.glob_match_options)
                     .expect("failed to read glob pattern")
                     .filter_map(|val| {
@@ -64,13 +114,244 @@ impl<E: FileSourceInternalEvents> PathsProvider for Glob<E> {
                         })
                         .ok()
                     })
+                    .filter(move |candidate_path| self.is_allowed(&base, candidate_path))
             })
-            .filter(|candidate_path: &PathBuf| -> bool {
-                !self.exclude_patterns.iter().any(|exclude_pattern| {
-                    let candidate_path_str = candidate_path.to_str().unwrap();
-                    exclude_pattern.matches(candidate_path_str)
-                })
-            })
+            .filter(|candidate_path: &PathBuf| -> bool { !self.is_excluded(candidate_path) })
             .collect()
     }
 }
+
+/// Which symbolic links are followed when providing paths.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SymlinkPolicy {
+    /// Follow symbolic links to both files and directories.
+    Follow,
+
+    /// Follow symbolic links to files, but don't descend into symbolic links to directories.
+    FilesOnly,
+
+    /// Don't follow any symbolic link.
+    Never,
+}
+
+impl SymlinkPolicy {
+    /// Symbolic links within the base directory itself are always followed, since the base
+    /// directory was written out explicitly.
+    fn allows(self, base: &Path, relative: &Path) -> bool {
+        if self == Self::Follow {
+            return true;
+        }
+
+        let mut path = base.to_path_buf();
+        let mut components = relative.components().peekable();
+        while let Some(component) = components.next() {
+            path.push(component);
+            let is_file = components.peek().is_none();
+            if is_file && self == Self::FilesOnly {
+                break;
+            }
+            if is_symlink(&path) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+fn is_symlink(path: &Path) -> bool {
+    path.symlink_metadata()
+        .map(|metadata| metadata.file_type().is_symlink())
+        .unwrap_or(false)
+}
+
+/// A rule of a gitignore-style list.
+///
+/// Rules are matched against the path of a file relative to the base directory of the include
+/// pattern which matched it:
+///
+/// - A rule starting with `!` includes again the paths excluded by the preceding rules.
+/// - A rule ending with `/` only matches directories, and so excludes everything below them.
+/// - A rule containing a `/` elsewhere matches the whole relative path, and a rule without one
+///   matches the name of the file or of any of its parent directories.
+/// - `*` doesn't match `/`, but `**` matches any number of directories.
+#[derive(Clone, Debug)]
+pub struct IgnoreRule {
+    pattern: Pattern,
+    negated: bool,
+    directory_only: bool,
+    anchored: bool,
+}
+
+impl IgnoreRule {
+    /// Parses a rule.
+    pub fn new(rule: &str) -> Result<Self, PatternError> {
+        let (negated, rule) = match rule.strip_prefix('!') {
+            Some(rule) => (true, rule),
+            None => (false, rule),
+        };
+        let (directory_only, rule) = match rule.strip_suffix('/') {
+            Some(rule) => (true, rule),
+            None => (false, rule),
+        };
+        let anchored = rule.contains('/');
+        let rule = rule.strip_prefix('/').unwrap_or(rule);
+
+        Ok(Self {
+            pattern: Pattern::new(rule)?,
+            negated,
+            directory_only,
+            anchored,
+        })
+    }
+
+    fn matches(&self, path: &Path, is_directory: bool) -> bool {
+        if self.directory_only && !is_directory {
+            return false;
+        }
+
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::default()
+        };
+        if self.anchored {
+            self.pattern.matches_path_with(path, options)
+        } else {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map_or(false, |name| self.pattern.matches_with(name, options))
+        }
+    }
+}
+
+/// Like gitignore, the directories are checked first, so that a file within an ignored directory
+/// can't be included again.
+fn is_ignored(rules: &[IgnoreRule], relative: &Path) -> bool {
+    if rules.is_empty() {
+        return false;
+    }
+
+    let ignored = |path: &Path, is_directory: bool| {
+        rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(path, is_directory))
+            .map_or(false, |rule| !rule.negated)
+    };
+
+    let mut directory = PathBuf::new();
+    let mut components = relative.components().peekable();
+    while let Some(component) = components.next() {
+        if components.peek().is_none() {
+            break;
+        }
+        directory.push(component);
+        if ignored(&directory, true) {
+            return true;
+        }
+    }
+    ignored(relative, false)
+}
+
+/// The directory of a pattern before its first component with any wildcard, or the parent
+/// directory of a pattern without wildcards.
+fn literal_base(pattern: &str) -> PathBuf {
+    let path = Path::new(pattern);
+    let mut base = PathBuf::new();
+    for component in path.components() {
+        if let Component::Normal(name) = component {
+            if name
+                .to_str()
+                .map_or(false, |name| name.contains(['*', '?', '[']))
+            {
+                return base;
+            }
+        }
+        base.push(component);
+    }
+    base.pop();
+    base
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn parse(rules: &[&str]) -> Vec<IgnoreRule> {
+        rules
+            .iter()
+            .map(|rule| IgnoreRule::new(rule).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn finds_literal_bases() {
+        assert_eq!(literal_base("/var/log/**/*.log"), Path::new("/var/log"));
+        assert_eq!(
+            literal_base("/var/log/app-*/out.log"),
+            Path::new("/var/log")
+        );
+        assert_eq!(literal_base("/var/log/app.log"), Path::new("/var/log"));
+    }
+
+    #[test]
+    fn applies_ignore_rules_in_order() {
+        let rules = parse(&["*.gz", "!keep.gz", "archive/", "/tmp/*.log"]);
+
+        assert!(!is_ignored(&rules, Path::new("app.log")));
+        assert!(is_ignored(&rules, Path::new("app.log.gz")));
+        assert!(is_ignored(&rules, Path::new("nested/app.log.gz")));
+        assert!(!is_ignored(&rules, Path::new("nested/keep.gz")));
+        assert!(is_ignored(&rules, Path::new("archive/app.log")));
+        assert!(is_ignored(&rules, Path::new("nested/archive/app.log")));
+        assert!(!is_ignored(&rules, Path::new("archive")));
+        assert!(is_ignored(&rules, Path::new("tmp/app.log")));
+        assert!(!is_ignored(&rules, Path::new("tmp/nested/app.log")));
+        assert!(!is_ignored(&rules, Path::new("nested/tmp/app.log")));
+    }
+
+    #[test]
+    fn ignored_directories_are_not_included_again() {
+        let rules = parse(&["archive/", "!archive/keep.log"]);
+
+        assert!(is_ignored(&rules, Path::new("archive/keep.log")));
+    }
+
+    #[test]
+    fn matches_any_depth_with_double_asterisks() {
+        let rules = parse(&["debug/**/*.log"]);
+
+        assert!(is_ignored(&rules, Path::new("debug/app.log")));
+        assert!(is_ignored(&rules, Path::new("debug/a/b/app.log")));
+        assert!(!is_ignored(&rules, Path::new("a/debug/app.log")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn follows_symlinks_by_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        fs::write(target.path().join("app.log"), "").unwrap();
+        fs::create_dir(dir.path().join("real")).unwrap();
+        fs::write(dir.path().join("real/app.log"), "").unwrap();
+        std::os::unix::fs::symlink(target.path(), dir.path().join("linked")).unwrap();
+        std::os::unix::fs::symlink(
+            target.path().join("app.log"),
+            dir.path().join("real/linked.log"),
+        )
+        .unwrap();
+
+        let allowed =
+            |policy: SymlinkPolicy, relative: &str| policy.allows(dir.path(), Path::new(relative));
+        for relative in ["real/app.log", "real/linked.log", "linked/app.log"] {
+            assert!(allowed(SymlinkPolicy::Follow, relative));
+        }
+        assert!(allowed(SymlinkPolicy::FilesOnly, "real/app.log"));
+        assert!(allowed(SymlinkPolicy::FilesOnly, "real/linked.log"));
+        assert!(!allowed(SymlinkPolicy::FilesOnly, "linked/app.log"));
+        assert!(allowed(SymlinkPolicy::Never, "real/app.log"));
+        assert!(!allowed(SymlinkPolicy::Never, "real/linked.log"));
+        assert!(!allowed(SymlinkPolicy::Never, "linked/app.log"));
+    }
+}
@@ -1,19 +1,24 @@
 use super::proto::{
-    common::v1::{any_value::Value as PBValue, KeyValue},
+    common::v1::{any_value::Value as PBValue, InstrumentationScope, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, SeverityNumber},
     resource::v1::Resource,
+    trace::v1::{
+        span::{Event as SpanEvent, Link},
+        ResourceSpans, Span, Status,
+    },
 };
 use bytes::Bytes;
-use chrono::{TimeZone, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use ordered_float::NotNan;
 use std::collections::BTreeMap;
 use value::Value;
 use vector_core::{
     config::log_schema,
-    event::{Event, LogEvent},
+    event::{Event, LogEvent, TraceEvent},
 };
 
 const RESOURCE_KEY: &str = "resources";
+const SCOPE_KEY: &str = "scope";
 const ATTRIBUTES_KEY: &str = "attributes";
 const TRACE_ID_KEY: &str = "trace_id";
 const SPAN_ID_KEY: &str = "span_id";
@@ -22,6 +27,7 @@ const SEVERITY_NUMBER_KEY: &str = "severity_number";
 const OBSERVED_TIMESTAMP_KEY: &str = "observed_timestamp";
 const DROPPED_ATTRIBUTES_COUNT_KEY: &str = "dropped_attributes_count";
 const FLAGS_KEY: &str = "flags";
+const INGEST_TIMESTAMP_KEY: &str = "ingest_timestamp";
 
 impl IntoIterator for ResourceLogs {
     type Item = Event;
@@ -30,14 +36,43 @@ impl IntoIterator for ResourceLogs {
         let resource = self.resource;
         self.scope_logs
             .into_iter()
-            .flat_map(|scope_log| scope_log.log_records)
-            .map(|log_record| {
-                ResourceLog {
+            .flat_map(|scope_log| {
+                let resource = resource.clone();
+                let scope = scope_log.scope;
+                scope_log
+                    .log_records
+                    .into_iter()
+                    .map(move |log_record| ResourceLog {
+                        resource: resource.clone(),
+                        scope: scope.clone(),
+                        log_record,
+                    })
+            })
+            .map(Into::into)
+            .collect::<Vec<Self::Item>>()
+            .into_iter()
+    }
+}
+
+impl IntoIterator for ResourceSpans {
+    type Item = Event;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+    fn into_iter(self) -> Self::IntoIter {
+        let resource = self.resource;
+        // All spans of a request share the time they were received at.
+        let now = Utc::now();
+        self.scope_spans
+            .into_iter()
+            .flat_map(|scope_span| {
+                let resource = resource.clone();
+                let scope = scope_span.scope;
+                scope_span.spans.into_iter().map(move |span| ResourceSpan {
                     resource: resource.clone(),
-                    log_record,
-                }
-                .into()
+                    scope: scope.clone(),
+                    span,
+                })
             })
+            .map(|resource_span| resource_span.into_event(now))
             .collect::<Vec<Self::Item>>()
             .into_iter()
     }
@@ -64,9 +99,16 @@ impl From<PBValue> for Value {
 
 struct ResourceLog {
     resource: Option<Resource>,
+    scope: Option<InstrumentationScope>,
     log_record: LogRecord,
 }
 
+struct ResourceSpan {
+    resource: Option<Resource>,
+    scope: Option<InstrumentationScope>,
+    span: Span,
+}
+
 fn kv_list_into_value(arr: Vec<KeyValue>) -> Value {
     Value::Object(
         arr.into_iter()
@@ -78,6 +120,32 @@ fn kv_list_into_value(arr: Vec<KeyValue>) -> Value {
     )
 }
 
+/// The instrumentation scope, or `None` if it's unknown.
+fn scope_into_value(scope: InstrumentationScope) -> Option<Value> {
+    let mut fields = BTreeMap::new();
+    if !scope.name.is_empty() {
+        fields.insert("name".to_owned(), Value::from(scope.name));
+    }
+    if !scope.version.is_empty() {
+        fields.insert("version".to_owned(), Value::from(scope.version));
+    }
+    if !scope.attributes.is_empty() {
+        fields.insert(
+            ATTRIBUTES_KEY.to_owned(),
+            kv_list_into_value(scope.attributes),
+        );
+    }
+    (!fields.is_empty()).then(|| Value::Object(fields))
+}
+
+fn id_into_value(id: Vec<u8>) -> Value {
+    Value::Bytes(Bytes::from(hex::encode(id)))
+}
+
+fn timestamp_into_value(unix_nano: u64) -> Value {
+    Value::Timestamp(Utc.timestamp_nanos(unix_nano as i64))
+}
+
 impl From<ResourceLog> for Event {
     fn from(rl: ResourceLog) -> Self {
         let mut le = LogEvent::default();
@@ -88,6 +156,9 @@ impl From<ResourceLog> for Event {
                 le.insert(RESOURCE_KEY, kv_list_into_value(resource.attributes));
             }
         }
+        if let Some(scope) = rl.scope.and_then(scope_into_value) {
+            le.insert(SCOPE_KEY, scope);
+        }
         if !rl.log_record.attributes.is_empty() {
             le.insert(ATTRIBUTES_KEY, kv_list_into_value(rl.log_record.attributes));
         }
@@ -95,16 +166,10 @@ impl From<ResourceLog> for Event {
             le.insert(log_schema().message_key(), v);
         }
         if !rl.log_record.trace_id.is_empty() {
-            le.insert(
-                TRACE_ID_KEY,
-                Value::Bytes(Bytes::from(hex::encode(rl.log_record.trace_id))),
-            );
+            le.insert(TRACE_ID_KEY, id_into_value(rl.log_record.trace_id));
         }
         if !rl.log_record.span_id.is_empty() {
-            le.insert(
-                SPAN_ID_KEY,
-                Value::Bytes(Bytes::from(hex::encode(rl.log_record.span_id))),
-            );
+            le.insert(SPAN_ID_KEY, id_into_value(rl.log_record.span_id));
         }
         if !rl.log_record.severity_text.is_empty() {
             le.insert(SEVERITY_TEXT_KEY, rl.log_record.severity_text);
@@ -144,3 +209,112 @@ impl From<ResourceLog> for Event {
         le.into()
     }
 }
+
+impl ResourceSpan {
+    fn into_event(self, now: DateTime<Utc>) -> Event {
+        let span = self.span;
+        let mut trace = TraceEvent::default();
+        trace.insert(TRACE_ID_KEY, id_into_value(span.trace_id));
+        trace.insert(SPAN_ID_KEY, id_into_value(span.span_id));
+        trace.insert("trace_state", span.trace_state);
+        trace.insert("parent_span_id", id_into_value(span.parent_span_id));
+        trace.insert("name", span.name);
+        trace.insert("kind", span.kind);
+        trace.insert(
+            "start_time_unix_nano",
+            timestamp_into_value(span.start_time_unix_nano),
+        );
+        trace.insert(
+            "end_time_unix_nano",
+            timestamp_into_value(span.end_time_unix_nano),
+        );
+
+        // optional fields
+        if let Some(resource) = self.resource {
+            if !resource.attributes.is_empty() {
+                trace.insert(RESOURCE_KEY, kv_list_into_value(resource.attributes));
+            }
+        }
+        if let Some(scope) = self.scope.and_then(scope_into_value) {
+            trace.insert(SCOPE_KEY, scope);
+        }
+        if !span.attributes.is_empty() {
+            trace.insert(ATTRIBUTES_KEY, kv_list_into_value(span.attributes));
+        }
+        if !span.events.is_empty() {
+            trace.insert(
+                "events",
+                span.events
+                    .into_iter()
+                    .map(Into::into)
+                    .collect::<Vec<Value>>(),
+            );
+        }
+        if !span.links.is_empty() {
+            trace.insert(
+                "links",
+                span.links
+                    .into_iter()
+                    .map(Into::into)
+                    .collect::<Vec<Value>>(),
+            );
+        }
+        if let Some(status) = span.status {
+            trace.insert("status", status);
+        }
+
+        trace.insert(DROPPED_ATTRIBUTES_COUNT_KEY, span.dropped_attributes_count);
+        trace.insert("dropped_events_count", span.dropped_events_count);
+        trace.insert("dropped_links_count", span.dropped_links_count);
+        trace.insert(INGEST_TIMESTAMP_KEY, now);
+
+        trace.into()
+    }
+}
+
+impl From<SpanEvent> for Value {
+    fn from(event: SpanEvent) -> Self {
+        let mut fields = BTreeMap::new();
+        fields.insert("name".to_owned(), event.name.into());
+        fields.insert(
+            "time_unix_nano".to_owned(),
+            timestamp_into_value(event.time_unix_nano),
+        );
+        fields.insert(
+            ATTRIBUTES_KEY.to_owned(),
+            kv_list_into_value(event.attributes),
+        );
+        fields.insert(
+            DROPPED_ATTRIBUTES_COUNT_KEY.to_owned(),
+            event.dropped_attributes_count.into(),
+        );
+        Value::Object(fields)
+    }
+}
+
+impl From<Link> for Value {
+    fn from(link: Link) -> Self {
+        let mut fields = BTreeMap::new();
+        fields.insert(TRACE_ID_KEY.to_owned(), id_into_value(link.trace_id));
+        fields.insert(SPAN_ID_KEY.to_owned(), id_into_value(link.span_id));
+        fields.insert("trace_state".to_owned(), link.trace_state.into());
+        fields.insert(
+            ATTRIBUTES_KEY.to_owned(),
+            kv_list_into_value(link.attributes),
+        );
+        fields.insert(
+            DROPPED_ATTRIBUTES_COUNT_KEY.to_owned(),
+            link.dropped_attributes_count.into(),
+        );
+        Value::Object(fields)
+    }
+}
+
+impl From<Status> for Value {
+    fn from(status: Status) -> Self {
+        let mut fields = BTreeMap::new();
+        fields.insert("message".to_owned(), status.message.into());
+        fields.insert("code".to_owned(), status.code.into());
+        Value::Object(fields)
+    }
+}
@@ -19,16 +19,21 @@ criterion_group!(
               compact,
               contains,
               decode_base64,
+              decode_gzip,
               decode_percent,
+              decode_zstd,
               decrypt,
               // TODO: Cannot pass a Path to bench_function
               //del,
+              dns_lookup,
               downcase,
               encode_base64,
+              encode_gzip,
               encode_key_value,
               encode_json,
               encode_logfmt,
               encode_percent,
+              encode_zstd,
               encrypt,
               ends_with,
               // TODO: Cannot pass a Path to bench_function
@@ -43,6 +48,7 @@ criterion_group!(
               get,
               get_env_var,
               get_hostname,
+              hmac,
               includes,
               int,
               ip_aton,
@@ -81,6 +87,7 @@ criterion_group!(
               // TODO: value is dynamic so we cannot assert equality
               //now,
               object,
+              parse_apache_error_log,
               parse_apache_log,
               parse_aws_alb_log,
               parse_aws_cloudwatch_log_subscription_message,
@@ -93,9 +100,12 @@ criterion_group!(
               parse_groks,
               parse_key_value,
               parse_klog,
+              parse_leef,
               parse_int,
               parse_json,
+              parse_nginx_error_log,
               parse_nginx_log,
+              parse_proto,
               parse_query_string,
               parse_regex,
               parse_regex_all,
@@ -269,6 +279,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    decode_gzip => vrl_stdlib::DecodeGzip;
+
+    literal {
+        args: func_args![value: Bytes::from(&b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\x2b\xc8\x49\x4d\x2c\x4e\x55\x48\x49\x4d\xce\x4f\x49\x55\xc8\x4d\x05\x00\x9b\x19\xf3\xbd\x10\x00\x00\x00"[..])],
+        want: Ok("please decode me"),
+    }
+}
+
 bench_function! {
     decode_percent => vrl_stdlib::DecodePercent;
 
@@ -278,6 +297,24 @@ bench_function! {
     }
 }
 
+bench_function! {
+    decode_zstd => vrl_stdlib::DecodeZstd;
+
+    literal {
+        args: func_args![value: Bytes::from(zstd::encode_all(&b"please decode me"[..], 3).unwrap())],
+        want: Ok("please decode me"),
+    }
+}
+
+bench_function! {
+    dns_lookup => vrl_stdlib::DnsLookup;
+
+    ip_address {
+        args: func_args![value: value!("127.0.0.1")],
+        want: Ok(value!(["127.0.0.1"])),
+    }
+}
+
 bench_function! {
     downcase => vrl_stdlib::Downcase;
 
@@ -296,6 +333,21 @@ bench_function! {
     }
 }
 
+bench_function! {
+    encode_gzip => vrl_stdlib::EncodeGzip;
+
+    literal {
+        args: func_args![value: "please encode me"],
+        want: Ok({
+            use std::io::Write;
+
+            let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(b"please encode me").unwrap();
+            Bytes::from(encoder.finish().unwrap())
+        }),
+    }
+}
+
 bench_function! {
     encode_key_value => vrl_stdlib::EncodeKeyValue;
 
@@ -397,6 +449,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    encode_zstd => vrl_stdlib::EncodeZstd;
+
+    literal {
+        args: func_args![value: "please encode me"],
+        want: Ok(Bytes::from(zstd::encode_all(&b"please encode me"[..], 3).unwrap())),
+    }
+}
+
 bench_function! {
     ends_with => vrl_stdlib::EndsWith;
 
@@ -534,6 +595,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    hmac => vrl_stdlib::Hmac;
+
+    sha256 {
+        args: func_args![value: "foo", key: "secret"],
+        want: Ok("773ba44693c7553d6ee20f61ea5d2757a9a4f4a44d2841ae4e95b52e4cd62db4"),
+    }
+}
+
 bench_function! {
     includes => vrl_stdlib::Includes;
 
@@ -1411,6 +1481,25 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_apache_error_log => vrl_stdlib::ParseApacheErrorLog;
+
+    literal {
+        args: func_args![value: r#"[Mon Mar 01 12:00:19 2021] [proxy:error] [pid 23964:tid 3814] (113)No route to host: AH00957: HTTP: attempt to connect to 10.1.0.244:9000 (hostname.domain.com) failed"#],
+        want: Ok(value!({
+            "timestamp": (DateTime::parse_from_rfc3339("2021-03-01T12:00:19Z").unwrap().with_timezone(&Utc)),
+            "module": "proxy",
+            "severity": "error",
+            "pid": 23964,
+            "thread": "3814",
+            "errno": 113,
+            "error": "No route to host",
+            "code": "AH00957",
+            "message": "HTTP: attempt to connect to 10.1.0.244:9000 (hostname.domain.com) failed",
+        })),
+    }
+}
+
 bench_function! {
     parse_apache_log => vrl_stdlib::ParseApacheLog;
 
@@ -1660,6 +1749,46 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_leef => vrl_stdlib::ParseLeef;
+
+    simple {
+        args: func_args![
+            value: "LEEF:2.0|Lancope|StealthWatch|1.0|41|^|src=10.0.1.8^dst=10.0.0.5^sev=5"
+        ],
+        want: Ok(value!({
+            "leefVersion": "2.0",
+            "deviceVendor": "Lancope",
+            "deviceProduct": "StealthWatch",
+            "deviceVersion": "1.0",
+            "eventId": "41",
+            "src": "10.0.1.8",
+            "dst": "10.0.0.5",
+            "sev": "5"
+        }))
+    }
+}
+
+bench_function! {
+    parse_nginx_error_log => vrl_stdlib::ParseNginxErrorLog;
+
+    literal {
+        args: func_args![value: r#"2021/04/01 13:02:31 [error] 31#31: *1 open() "/usr/share/nginx/html/not-found" failed (2: No such file or directory), client: 172.17.0.1, server: localhost, request: "POST /not-found HTTP/1.1", host: "localhost:8081""#],
+        want: Ok(value!({
+            "timestamp": (DateTime::parse_from_rfc3339("2021-04-01T13:02:31Z").unwrap().with_timezone(&Utc)),
+            "severity": "error",
+            "pid": 31,
+            "tid": 31,
+            "cid": 1,
+            "message": "open() \"/usr/share/nginx/html/not-found\" failed (2: No such file or directory)",
+            "client": "172.17.0.1",
+            "server": "localhost",
+            "request": "POST /not-found HTTP/1.1",
+            "host": "localhost:8081",
+        })),
+    }
+}
+
 bench_function! {
     parse_nginx_log => vrl_stdlib::ParseNginxLog;
 
@@ -1703,6 +1832,22 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_proto => vrl_stdlib::ParseProto;
+
+    literal {
+        args: func_args![
+            value: Bytes::from_static(b"\x0a\x07someone\x10\xd2\x09"),
+            desc_file: concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/protobuf/test.desc"),
+            message_type: "test.Person",
+        ],
+        want: Ok(value!({
+            "name": "someone",
+            "id": 1234,
+        })),
+    }
+}
+
 bench_function! {
     parse_query_string => vrl_stdlib::ParseQueryString;
 
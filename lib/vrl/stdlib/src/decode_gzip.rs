@@ -0,0 +1,183 @@
+use std::io::Read;
+
+use ::value::Value;
+use flate2::read::MultiGzDecoder;
+use vrl::prelude::expression::FunctionExpression;
+use vrl::prelude::*;
+
+/// The default cap on the size of a decompressed value, guarding against decompression bombs.
+const DEFAULT_MAX_SIZE: i64 = 10 * 1024 * 1024;
+
+fn decode_gzip(value: Value, max_size: Option<Value>) -> Resolved {
+    let value = value.try_bytes()?;
+    let max_size = match max_size {
+        None => DEFAULT_MAX_SIZE,
+        Some(max_size) => max_size.try_integer()?,
+    };
+    let max_size = u64::try_from(max_size)
+        .ok()
+        .filter(|max_size| *max_size > 0)
+        .ok_or_else(|| format!("invalid max size: {}", max_size))?;
+
+    // read a single byte past the cap, to find values exceeding it without decompressing them
+    // entirely
+    let mut decoded = Vec::new();
+    MultiGzDecoder::new(value.as_ref())
+        .take(max_size + 1)
+        .read_to_end(&mut decoded)
+        .map_err(|_| "unable to decode value with gzip")?;
+    if decoded.len() as u64 > max_size {
+        return Err(format!(
+            "decompressed value exceeds the max size of {} bytes",
+            max_size
+        )
+        .into());
+    }
+
+    Ok(Value::from(Bytes::from(decoded)))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeGzip;
+
+impl Function for DecodeGzip {
+    fn identifier(&self) -> &'static str {
+        "decode_gzip"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "max_size",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let max_size = arguments.optional("max_size");
+
+        if let Some(literal) = max_size.as_ref().and_then(|max_size| max_size.as_value()) {
+            if !literal.as_integer().map_or(false, |max_size| max_size > 0) {
+                return Err(vrl::function::Error::InvalidArgument {
+                    keyword: "max_size",
+                    value: literal,
+                    error: r#""max_size" must be a positive number of bytes"#,
+                }
+                .into());
+            }
+        }
+
+        Ok(DecodeGzipFn { value, max_size }.as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "demo string",
+                source: r#"decode_gzip!(decode_base64!("H4sIAAAAAAACAyvISU0sTlVISU3OT0lVyE0FAJsZ870QAAAA"))"#,
+                result: Ok("please decode me"),
+            },
+            Example {
+                title: "with max size",
+                source: r#"decode_gzip(encode_gzip("please decode me"), max_size: 8) ?? "too large""#,
+                result: Ok("too large"),
+            },
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeGzipFn {
+    value: Box<dyn Expression>,
+    max_size: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for DecodeGzipFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let max_size = self
+            .max_size
+            .as_ref()
+            .map(|max_size| max_size.resolve(ctx))
+            .transpose()?;
+
+        decode_gzip(value, max_size)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        // Always fallible due to the possibility of decoding errors that VRL can't detect
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+
+    fn encode(value: &[u8]) -> Value {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(value).unwrap();
+        Value::from(Bytes::from(encoder.finish().unwrap()))
+    }
+
+    test_function![
+        decode_gzip => DecodeGzip;
+
+        with_defaults {
+            args: func_args![value: encode(b"please decode me")],
+            want: Ok(value!("please decode me")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        concatenated_members {
+            args: func_args![value: {
+                let mut value = encode(b"please ").as_bytes().unwrap().to_vec();
+                value.extend_from_slice(encode(b"decode me").as_bytes().unwrap());
+                Value::from(Bytes::from(value))
+            }],
+            want: Ok(value!("please decode me")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        within_max_size {
+            args: func_args![value: encode(b"please decode me"), max_size: 16],
+            want: Ok(value!("please decode me")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        exceeds_max_size {
+            args: func_args![value: encode(&[0; 1024 * 1024]), max_size: 1024],
+            want: Err("decompressed value exceeds the max size of 1024 bytes"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_literal_max_size {
+            args: func_args![value: encode(b"please decode me"), max_size: 0],
+            want: Err("invalid argument"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_input {
+            args: func_args![value: value!("not gzip")],
+            want: Err("unable to decode value with gzip"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
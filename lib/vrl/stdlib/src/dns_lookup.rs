@@ -0,0 +1,138 @@
+use std::net::IpAddr;
+
+use ::value::Value;
+use dns_lookup_lib::lookup_host;
+use once_cell::sync::Lazy;
+use vrl::prelude::*;
+
+use crate::dns_util::{DnsCache, LookupOptions};
+
+static CACHE: Lazy<DnsCache<String, Vec<IpAddr>>> = Lazy::new(DnsCache::new);
+
+fn dns_lookup(value: Value, options: LookupOptions) -> Resolved {
+    let host = value.try_bytes_utf8_lossy()?.into_owned();
+    let addresses = CACHE
+        .lookup(&host, options, lookup)
+        .map_err(|err| format!("unable to perform a lookup: {}", err))?;
+
+    Ok(addresses
+        .into_iter()
+        .map(|address| Value::from(address.to_string()))
+        .collect::<Vec<_>>()
+        .into())
+}
+
+#[allow(clippy::ptr_arg)] // the cache is keyed by owned strings
+fn lookup(host: &String) -> std::io::Result<Vec<IpAddr>> {
+    // the resolver returns an address once per socket type
+    let mut addresses = lookup_host(host)?;
+    let mut seen = std::collections::HashSet::new();
+    addresses.retain(|address| seen.insert(*address));
+    Ok(addresses)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DnsLookup;
+
+impl Function for DnsLookup {
+    fn identifier(&self) -> &'static str {
+        "dns_lookup"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "timeout_ms",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "cache_ttl_secs",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Example",
+            source: r#"dns_lookup!("127.0.0.1", timeout_ms: 500, cache_ttl_secs: 300)"#,
+            result: Ok(r#"["127.0.0.1"]"#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let options = LookupOptions::from_arguments(&arguments)?;
+
+        Ok(DnsLookupFn { value, options }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DnsLookupFn {
+    value: Box<dyn Expression>,
+    options: LookupOptions,
+}
+
+impl FunctionExpression for DnsLookupFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        dns_lookup(value, self.options)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // requires network access
+    fn google() {
+        let options = LookupOptions {
+            timeout: std::time::Duration::from_secs(5),
+            cache_ttl: std::time::Duration::ZERO,
+        };
+        let addresses = dns_lookup(value!("dns.google"), options).unwrap();
+
+        // the order of addresses, and whether IPv6 addresses are returned, depends on the system
+        assert!(addresses.as_array().unwrap().contains(&value!("8.8.8.8")));
+    }
+
+    test_function![
+        dns_lookup => DnsLookup;
+
+        ip_address {
+            args: func_args![value: value!("127.0.0.1")],
+            want: Ok(value!(["127.0.0.1"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible(),
+        }
+
+        invalid_timeout {
+            args: func_args![value: value!("dns.google"), timeout_ms: 0],
+            want: Err("invalid argument"),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible(),
+        }
+
+        invalid_type {
+            args: func_args![value: value!(1)],
+            want: Err("expected string, got integer"),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).fallible(),
+        }
+    ];
+}
@@ -0,0 +1,201 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    io,
+    sync::{mpsc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use ::value::Value;
+use vrl::prelude::ArgumentList;
+
+pub(crate) const DEFAULT_TIMEOUT_MS: u64 = 1000;
+pub(crate) const DEFAULT_CACHE_TTL_SECS: u64 = 60;
+
+/// Caches are cleared, once they hold this many entries, which haven't expired yet.
+const MAX_CACHE_ENTRIES: usize = 10_000;
+
+/// A process-wide cache of lookup results, shared by all programs, so that hosts and addresses
+/// aren't resolved again for every event. Failed lookups are cached too, timeouts are not.
+pub(crate) struct DnsCache<K, V> {
+    entries: Mutex<HashMap<K, CacheEntry<V>>>,
+}
+
+/// The time a cached result expires at, and the result.
+type CacheEntry<V> = (Instant, Result<V, String>);
+
+impl<K, V> DnsCache<K, V>
+where
+    K: Eq + Hash + Clone + Send + 'static,
+    V: Clone + Send + 'static,
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn lookup(
+        &self,
+        key: &K,
+        options: LookupOptions,
+        resolve: fn(&K) -> io::Result<V>,
+    ) -> Result<V, String> {
+        if let Some((expires_at, result)) = self.entries.lock().expect("poisoned lock").get(key) {
+            if *expires_at > Instant::now() {
+                return result.clone();
+            }
+        }
+
+        let result = resolve_with_timeout(key.clone(), options.timeout, resolve)?;
+        if !options.cache_ttl.is_zero() {
+            let mut entries = self.entries.lock().expect("poisoned lock");
+            if entries.len() >= MAX_CACHE_ENTRIES {
+                let now = Instant::now();
+                entries.retain(|_, (expires_at, _)| *expires_at > now);
+                if entries.len() >= MAX_CACHE_ENTRIES {
+                    entries.clear();
+                }
+            }
+            entries.insert(
+                key.clone(),
+                (Instant::now() + options.cache_ttl, result.clone()),
+            );
+        }
+        result
+    }
+}
+
+/// Returns the result of the lookup, or an error, if it timed out.
+fn resolve_with_timeout<K, V>(
+    key: K,
+    timeout: Duration,
+    resolve: fn(&K) -> io::Result<V>,
+) -> Result<Result<V, String>, String>
+where
+    K: Send + 'static,
+    V: Send + 'static,
+{
+    // the system resolver blocks and can't be cancelled, so the lookup runs on its own thread,
+    // which is left to finish in the background, once the lookup timed out
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        drop(sender.send(resolve(&key).map_err(|err| err.to_string())));
+    });
+    receiver
+        .recv_timeout(timeout)
+        .map_err(|_| format!("lookup timed out after {}ms", timeout.as_millis()))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct LookupOptions {
+    pub(crate) timeout: Duration,
+    pub(crate) cache_ttl: Duration,
+}
+
+impl LookupOptions {
+    /// Reads the `timeout_ms` and `cache_ttl_secs` arguments, which have to be literals.
+    pub(crate) fn from_arguments(arguments: &ArgumentList) -> Result<Self, vrl::function::Error> {
+        let timeout_ms = match arguments.optional_value("timeout_ms")? {
+            None => DEFAULT_TIMEOUT_MS,
+            Some(Value::Integer(timeout)) if timeout > 0 => timeout as u64,
+            Some(value) => {
+                return Err(vrl::function::Error::InvalidArgument {
+                    keyword: "timeout_ms",
+                    value,
+                    error: "must be a positive integer",
+                })
+            }
+        };
+        let cache_ttl_secs = match arguments.optional_value("cache_ttl_secs")? {
+            None => DEFAULT_CACHE_TTL_SECS,
+            Some(Value::Integer(ttl)) if ttl >= 0 => ttl as u64,
+            Some(value) => {
+                return Err(vrl::function::Error::InvalidArgument {
+                    keyword: "cache_ttl_secs",
+                    value,
+                    error: "must be a non-negative integer",
+                })
+            }
+        };
+
+        Ok(Self {
+            timeout: Duration::from_millis(timeout_ms),
+            cache_ttl: Duration::from_secs(cache_ttl_secs),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    fn counting_resolve(key: &u32) -> io::Result<u32> {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        match key {
+            0 => Err(io::Error::new(io::ErrorKind::Other, "not found")),
+            key => Ok(key * 2),
+        }
+    }
+
+    #[allow(clippy::unnecessary_wraps)] // match the signature of resolvers
+    fn slow_resolve(key: &u32) -> io::Result<u32> {
+        thread::sleep(Duration::from_millis(200));
+        Ok(*key)
+    }
+
+    #[test]
+    fn caches_results() {
+        let cache = DnsCache::new();
+        let options = LookupOptions {
+            timeout: Duration::from_secs(1),
+            cache_ttl: Duration::from_secs(60),
+        };
+
+        let calls = CALLS.load(Ordering::SeqCst);
+        assert_eq!(cache.lookup(&1, options, counting_resolve), Ok(2));
+        assert_eq!(cache.lookup(&1, options, counting_resolve), Ok(2));
+        assert_eq!(
+            cache.lookup(&0, options, counting_resolve),
+            Err("not found".to_owned())
+        );
+        assert_eq!(
+            cache.lookup(&0, options, counting_resolve),
+            Err("not found".to_owned())
+        );
+        assert_eq!(CALLS.load(Ordering::SeqCst) - calls, 2);
+
+        let uncached = LookupOptions {
+            cache_ttl: Duration::ZERO,
+            ..options
+        };
+        assert_eq!(cache.lookup(&3, uncached, counting_resolve), Ok(6));
+        assert_eq!(cache.lookup(&3, uncached, counting_resolve), Ok(6));
+        assert_eq!(CALLS.load(Ordering::SeqCst) - calls, 4);
+    }
+
+    #[test]
+    fn times_out() {
+        let cache = DnsCache::new();
+        let options = LookupOptions {
+            timeout: Duration::from_millis(10),
+            cache_ttl: Duration::from_secs(60),
+        };
+
+        assert_eq!(
+            cache.lookup(&1, options, slow_resolve),
+            Err("lookup timed out after 10ms".to_owned())
+        );
+        // timeouts aren't cached
+        let options = LookupOptions {
+            timeout: Duration::from_secs(1),
+            ..options
+        };
+        assert_eq!(cache.lookup(&1, options, slow_resolve), Ok(1));
+    }
+}
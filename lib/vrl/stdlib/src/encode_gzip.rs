@@ -0,0 +1,170 @@
+use std::io::Write;
+
+use ::value::Value;
+use flate2::{write::GzEncoder, Compression};
+use vrl::prelude::expression::FunctionExpression;
+use vrl::prelude::*;
+
+const MAX_COMPRESSION_LEVEL: i64 = 9;
+
+fn encode_gzip(value: Value, compression_level: Option<Value>) -> Resolved {
+    let value = value.try_bytes()?;
+    let level = match compression_level {
+        None => Compression::default(),
+        Some(level) => {
+            let level = level.try_integer()?;
+            u32::try_from(level)
+                .ok()
+                .filter(|level| i64::from(*level) <= MAX_COMPRESSION_LEVEL)
+                .map(Compression::new)
+                .ok_or_else(|| format!("invalid compression level: {}", level))?
+        }
+    };
+
+    let mut encoder = GzEncoder::new(Vec::new(), level);
+    encoder
+        .write_all(&value)
+        .and_then(|_| encoder.finish())
+        .map(|compressed| Value::from(Bytes::from(compressed)))
+        .map_err(|error| format!("unable to compress value with gzip: {}", error).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeGzip;
+
+impl Function for EncodeGzip {
+    fn identifier(&self) -> &'static str {
+        "encode_gzip"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "compression_level",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let compression_level = arguments.optional("compression_level");
+
+        if let Some(literal) = compression_level
+            .as_ref()
+            .and_then(|level| level.as_value())
+        {
+            let valid = literal
+                .as_integer()
+                .map_or(false, |level| (0..=MAX_COMPRESSION_LEVEL).contains(&level));
+            if !valid {
+                return Err(vrl::function::Error::InvalidArgument {
+                    keyword: "compression_level",
+                    value: literal,
+                    error: r#""compression_level" must be between 0 and 9"#,
+                }
+                .into());
+            }
+        }
+
+        Ok(EncodeGzipFn {
+            value,
+            compression_level,
+        }
+        .as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_gzip!(encode_gzip("please encode me", compression_level: 9))"#,
+            result: Ok("please encode me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeGzipFn {
+    value: Box<dyn Expression>,
+    compression_level: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for EncodeGzipFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let compression_level = self
+            .compression_level
+            .as_ref()
+            .map(|level| level.resolve(ctx))
+            .transpose()?;
+
+        encode_gzip(value, compression_level)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        // a compression level, only known at runtime, may be invalid
+        let not_literal = self
+            .compression_level
+            .as_ref()
+            .map_or(false, |level| level.as_value().is_none());
+
+        TypeDef::bytes().with_fallibility(not_literal)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+
+    use super::*;
+
+    fn decode(value: &[u8]) -> Value {
+        let mut decoded = Vec::new();
+        GzDecoder::new(value).read_to_end(&mut decoded).unwrap();
+        Value::from(Bytes::from(decoded))
+    }
+
+    #[test]
+    fn round_trips_with_compression_levels() {
+        for level in [None, Some(value!(0)), Some(value!(9))] {
+            let compressed = encode_gzip(value!("please encode me"), level).unwrap();
+            assert_eq!(
+                decode(compressed.as_bytes().unwrap()),
+                value!("please encode me")
+            );
+        }
+    }
+
+    #[test]
+    fn fails_on_invalid_compression_level() {
+        assert_eq!(
+            encode_gzip(value!("please encode me"), Some(value!(10)))
+                .unwrap_err()
+                .to_string(),
+            "invalid compression level: 10"
+        );
+    }
+
+    test_function![
+        encode_gzip => EncodeGzip;
+
+        invalid_literal_level {
+            args: func_args![value: value!("please encode me"), compression_level: value!(10)],
+            want: Err("invalid argument"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
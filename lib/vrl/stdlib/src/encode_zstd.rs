@@ -0,0 +1,164 @@
+use ::value::Value;
+use vrl::prelude::expression::FunctionExpression;
+use vrl::prelude::*;
+
+fn encode_zstd(value: Value, compression_level: Option<Value>) -> Resolved {
+    let value = value.try_bytes()?;
+    let level = match compression_level {
+        None => zstd::DEFAULT_COMPRESSION_LEVEL,
+        Some(level) => {
+            let level = level.try_integer()?;
+            i32::try_from(level)
+                .ok()
+                .filter(|level| zstd::compression_level_range().contains(level))
+                .ok_or_else(|| format!("invalid compression level: {}", level))?
+        }
+    };
+
+    zstd::encode_all(value.as_ref(), level)
+        .map(|compressed| Value::from(Bytes::from(compressed)))
+        .map_err(|error| format!("unable to compress value with zstd: {}", error).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeZstd;
+
+impl Function for EncodeZstd {
+    fn identifier(&self) -> &'static str {
+        "encode_zstd"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "compression_level",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let compression_level = arguments.optional("compression_level");
+
+        if let Some(literal) = compression_level
+            .as_ref()
+            .and_then(|level| level.as_value())
+        {
+            let valid = literal
+                .as_integer()
+                .and_then(|level| i32::try_from(level).ok())
+                .map_or(false, |level| {
+                    zstd::compression_level_range().contains(&level)
+                });
+            if !valid {
+                return Err(vrl::function::Error::InvalidArgument {
+                    keyword: "compression_level",
+                    value: literal,
+                    error: r#""compression_level" must be a valid zstd compression level"#,
+                }
+                .into());
+            }
+        }
+
+        Ok(EncodeZstdFn {
+            value,
+            compression_level,
+        }
+        .as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_zstd!(encode_zstd("please encode me", compression_level: 19))"#,
+            result: Ok("please encode me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeZstdFn {
+    value: Box<dyn Expression>,
+    compression_level: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for EncodeZstdFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let compression_level = self
+            .compression_level
+            .as_ref()
+            .map(|level| level.resolve(ctx))
+            .transpose()?;
+
+        encode_zstd(value, compression_level)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        // a compression level, only known at runtime, may be invalid
+        let not_literal = self
+            .compression_level
+            .as_ref()
+            .map_or(false, |level| level.as_value().is_none());
+
+        TypeDef::bytes().with_fallibility(not_literal)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn decode(value: &[u8]) -> Value {
+        Value::from(Bytes::from(zstd::decode_all(value).unwrap()))
+    }
+
+    #[test]
+    fn round_trips_with_compression_levels() {
+        for level in [None, Some(value!(1)), Some(value!(19))] {
+            let compressed = encode_zstd(value!("please encode me"), level).unwrap();
+            assert_eq!(
+                decode(compressed.as_bytes().unwrap()),
+                value!("please encode me")
+            );
+        }
+    }
+
+    #[test]
+    fn fails_on_invalid_compression_level() {
+        assert_eq!(
+            encode_zstd(value!("please encode me"), Some(value!(1000)))
+                .unwrap_err()
+                .to_string(),
+            "invalid compression level: 1000"
+        );
+    }
+
+    test_function![
+        encode_zstd => EncodeZstd;
+
+        with_defaults {
+            args: func_args![value: value!("please encode me")],
+            want: Ok(Bytes::from(zstd::encode_all(&b"please encode me"[..], 3).unwrap())),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        invalid_literal_level {
+            args: func_args![value: value!("please encode me"), compression_level: value!(1000)],
+            want: Err("invalid argument"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
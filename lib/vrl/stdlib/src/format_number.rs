@@ -7,8 +7,10 @@ fn format_number(
     scale: Option<Value>,
     grouping_separator: Option<Value>,
     decimal_separator: Option<Value>,
+    locale: Option<&Bytes>,
+    units: Option<&Bytes>,
 ) -> Resolved {
-    let value: Decimal = match value {
+    let mut value: Decimal = match value {
         Value::Integer(v) => v.into(),
         Value::Float(v) => Decimal::from_f64(*v).expect("not NaN"),
         value => {
@@ -19,18 +21,46 @@ fn format_number(
             .into())
         }
     };
-    let scale = match scale {
+    let mut scale = match scale {
         Some(expr) => Some(expr.try_integer()?),
         None => None,
     };
+    // Explicit separators take precedence over the ones of the locale.
+    let (locale_decimal_separator, locale_grouping_separator) = match locale {
+        Some(locale) => locale_separators(locale),
+        None => (".", None),
+    };
     let grouping_separator = match grouping_separator {
         Some(expr) => Some(expr.try_bytes()?),
-        None => None,
+        None => locale_grouping_separator.map(Bytes::from),
     };
     let decimal_separator = match decimal_separator {
         Some(expr) => expr.try_bytes()?,
-        None => ".".into(),
+        None => locale_decimal_separator.into(),
     };
+    // Scale the value to the largest unit, that keeps it at least 1.
+    let suffix = units.map(|units| {
+        let (base, suffixes) = match units.as_ref() {
+            b"binary" => (Decimal::from(1024), BINARY_UNITS),
+            b"decimal" => (Decimal::from(1000), DECIMAL_UNITS),
+            _ => unreachable!("enum invariant"),
+        };
+        let mut suffixes = suffixes.iter();
+        let mut suffix = suffixes.next().expect("at least one unit");
+        while value.abs() >= base {
+            match suffixes.next() {
+                Some(next) => {
+                    value /= base;
+                    suffix = next;
+                }
+                None => break,
+            }
+        }
+        if *suffix != "B" {
+            scale.get_or_insert(2);
+        }
+        *suffix
+    });
     // Split integral and fractional part of float.
     let mut parts = value
         .to_string()
@@ -61,24 +91,62 @@ fn format_number(
     // Manipulate integral part based on configuration.
     if let Some(sep) = grouping_separator.as_deref() {
         let sep = String::from_utf8_lossy(sep);
-        let start = parts[0].len() % 3;
-
-        let positions: Vec<usize> = parts[0]
-            .chars()
-            .skip(start)
-            .enumerate()
-            .map(|(i, _)| i)
-            .filter(|i| i % 3 == 0)
-            .collect();
+        let (sign, digits) = match parts[0].strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", parts[0].as_str()),
+        };
 
-        for (i, pos) in positions.iter().enumerate() {
-            parts[0].insert_str(pos + (i * sep.len()) + start, &sep);
+        let mut grouped = sign.to_owned();
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                grouped.push_str(&sep);
+            }
+            grouped.push(c);
         }
+        parts[0] = grouped;
     }
     // Join results, using configured decimal separator.
-    Ok(parts
-        .join(&String::from_utf8_lossy(&decimal_separator[..]))
-        .into())
+    let mut formatted = parts.join(&String::from_utf8_lossy(&decimal_separator[..]));
+    if let Some(suffix) = suffix {
+        formatted.push(' ');
+        formatted.push_str(suffix);
+    }
+    Ok(formatted.into())
+}
+
+const BINARY_UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+const DECIMAL_UNITS: &[&str] = &["B", "kB", "MB", "GB", "TB", "PB", "EB"];
+
+fn locales() -> Vec<Value> {
+    LOCALES
+        .iter()
+        .map(|(locale, _, _)| Value::from(*locale))
+        .collect()
+}
+
+/// The decimal and grouping separators of the supported locales.
+const LOCALES: &[(&str, &str, &str)] = &[
+    ("de", ",", "."),
+    ("de_CH", ".", "\u{2019}"),
+    ("en", ".", ","),
+    ("es", ",", "."),
+    ("fr", ",", "\u{202f}"),
+    ("it", ",", "."),
+    ("ja", ".", ","),
+    ("nl", ",", "."),
+    ("pl", ",", "\u{a0}"),
+    ("pt", ",", "."),
+    ("ru", ",", "\u{a0}"),
+    ("sv", ",", "\u{a0}"),
+    ("zh", ".", ","),
+];
+
+fn locale_separators(locale: &Bytes) -> (&'static str, Option<&'static str>) {
+    LOCALES
+        .iter()
+        .find(|(name, _, _)| name.as_bytes() == locale.as_ref())
+        .map(|(_, decimal, grouping)| (*decimal, Some(*grouping)))
+        .expect("enum invariant")
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -111,6 +179,16 @@ impl Function for FormatNumber {
                 kind: kind::BYTES,
                 required: false,
             },
+            Parameter {
+                keyword: "locale",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "units",
+                kind: kind::BYTES,
+                required: false,
+            },
         ]
     }
 
@@ -124,22 +202,42 @@ impl Function for FormatNumber {
         let scale = arguments.optional("scale");
         let decimal_separator = arguments.optional("decimal_separator");
         let grouping_separator = arguments.optional("grouping_separator");
+        let locale = arguments
+            .optional_enum("locale", &locales())?
+            .map(|locale| locale.try_bytes().expect("locale not bytes"));
+        let units = arguments
+            .optional_enum("units", &[value!("binary"), value!("decimal")])?
+            .map(|units| units.try_bytes().expect("units not bytes"));
 
         Ok(FormatNumberFn {
             value,
             scale,
             decimal_separator,
             grouping_separator,
+            locale,
+            units,
         }
         .as_expr())
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "format number",
-            source: r#"format_number(4672.4, decimal_separator: ",", grouping_separator: "_")"#,
-            result: Ok("4_672,4"),
-        }]
+        &[
+            Example {
+                title: "format number",
+                source: r#"format_number(4672.4, decimal_separator: ",", grouping_separator: "_")"#,
+                result: Ok("4_672,4"),
+            },
+            Example {
+                title: "format number for a locale",
+                source: r#"format_number(4672.4, scale: 2, locale: "de")"#,
+                result: Ok("4.672,40"),
+            },
+            Example {
+                title: "format bytes",
+                source: r#"format_number(1572864, units: "binary")"#,
+                result: Ok("1.50 MiB"),
+            },
+        ]
     }
 }
 
@@ -149,6 +247,8 @@ struct FormatNumberFn {
     scale: Option<Box<dyn Expression>>,
     decimal_separator: Option<Box<dyn Expression>>,
     grouping_separator: Option<Box<dyn Expression>>,
+    locale: Option<Bytes>,
+    units: Option<Bytes>,
 }
 
 impl FunctionExpression for FormatNumberFn {
@@ -170,7 +270,14 @@ impl FunctionExpression for FormatNumberFn {
             .map(|expr| expr.resolve(ctx))
             .transpose()?;
 
-        format_number(value, scale, grouping_separator, decimal_separator)
+        format_number(
+            value,
+            scale,
+            grouping_separator,
+            decimal_separator,
+            self.locale.as_ref(),
+            self.units.as_ref(),
+        )
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -251,5 +358,58 @@ mod tests {
             want: Ok(value!("12345.00")),
             tdef: TypeDef::bytes().infallible(),
         }
+
+        grouping_full_groups {
+            args: func_args![value: -123_456,
+                             grouping_separator: ","],
+            want: Ok(value!("-123,456")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        locale {
+            args: func_args![value: 1_234_567.891,
+                             scale: 2,
+                             locale: "de"],
+            want: Ok(value!("1.234.567,89")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        locale_with_separator {
+            args: func_args![value: 1_234_567.891,
+                             locale: "fr",
+                             grouping_separator: " "],
+            want: Ok(value!("1 234 567,891")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        invalid_locale {
+            args: func_args![value: 1234,
+                             locale: "xx"],
+            want: Err(r#"invalid enum variant""#),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        binary_units {
+            args: func_args![value: 1_572_864,
+                             units: "binary"],
+            want: Ok(value!("1.50 MiB")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        decimal_units {
+            args: func_args![value: -2_500_000_000_i64,
+                             scale: 1,
+                             units: "decimal",
+                             locale: "de"],
+            want: Ok(value!("-2,5 GB")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        bytes_units {
+            args: func_args![value: 512,
+                             units: "binary"],
+            want: Ok(value!("512 B")),
+            tdef: TypeDef::bytes().infallible(),
+        }
     ];
 }
@@ -5,12 +5,14 @@ use chrono::{
 };
 use vrl::prelude::*;
 
-fn format_timestamp(bytes: Value, ts: Value) -> Resolved {
+use crate::locale_util::{locale_names, locales};
+
+fn format_timestamp(bytes: Value, ts: Value, locale: Option<&Bytes>) -> Resolved {
     let bytes = bytes.try_bytes()?;
     let format = String::from_utf8_lossy(&bytes);
     let ts = ts.try_timestamp()?;
 
-    try_format(&ts, &format).map(Into::into)
+    try_format(&ts, &format, locale).map(Into::into)
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -33,6 +35,11 @@ impl Function for FormatTimestamp {
                 kind: kind::BYTES,
                 required: true,
             },
+            Parameter {
+                keyword: "locale",
+                kind: kind::BYTES,
+                required: false,
+            },
         ]
     }
 
@@ -44,16 +51,31 @@ impl Function for FormatTimestamp {
     ) -> Compiled {
         let value = arguments.required("value");
         let format = arguments.required("format");
-
-        Ok(FormatTimestampFn { value, format }.as_expr())
+        let locale = arguments
+            .optional_enum("locale", &locales())?
+            .map(|locale| locale.try_bytes().expect("locale not bytes"));
+
+        Ok(FormatTimestampFn {
+            value,
+            format,
+            locale,
+        }
+        .as_expr())
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "format timestamp",
-            source: r#"format_timestamp!(t'2021-02-10T23:32:00+00:00', "%d %B %Y %H:%M")"#,
-            result: Ok("10 February 2021 23:32"),
-        }]
+        &[
+            Example {
+                title: "format timestamp",
+                source: r#"format_timestamp!(t'2021-02-10T23:32:00+00:00', "%d %B %Y %H:%M")"#,
+                result: Ok("10 February 2021 23:32"),
+            },
+            Example {
+                title: "localized names",
+                source: r#"format_timestamp!(t'2021-02-10T23:32:00+00:00', "%A %d %B %Y %H:%M", locale: "fr")"#,
+                result: Ok("mercredi 10 février 2021 23:32"),
+            },
+        ]
     }
 }
 
@@ -61,6 +83,7 @@ impl Function for FormatTimestamp {
 struct FormatTimestampFn {
     value: Box<dyn Expression>,
     format: Box<dyn Expression>,
+    locale: Option<Bytes>,
 }
 
 impl FunctionExpression for FormatTimestampFn {
@@ -68,7 +91,7 @@ impl FunctionExpression for FormatTimestampFn {
         let bytes = self.format.resolve(ctx)?;
         let ts = self.value.resolve(ctx)?;
 
-        format_timestamp(bytes, ts)
+        format_timestamp(bytes, ts, self.locale.as_ref())
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -76,13 +99,17 @@ impl FunctionExpression for FormatTimestampFn {
     }
 }
 
-fn try_format(dt: &DateTime<Utc>, format: &str) -> Result<String> {
+fn try_format(dt: &DateTime<Utc>, format: &str, locale: Option<&Bytes>) -> Result<String> {
     let items = StrftimeItems::new(format)
         .map(|item| match item {
             Item::Error => Err("invalid format".into()),
             _ => Ok(item),
         })
         .collect::<Result<Vec<_>>>()?;
+    let items = match locale {
+        Some(locale) => locale_names(locale).localize(items, dt),
+        None => items,
+    };
 
     Ok(dt.format_with_items(items.into_iter()).to_string())
 }
@@ -116,5 +143,13 @@ mod tests {
             want: Ok(value!("1970-01-01T00:00:10+00:00")),
             tdef: TypeDef::bytes().fallible(),
         }
+
+        localized_names {
+            args: func_args![value: Utc.ymd(2021, 3, 16).and_hms(0, 0, 0),
+                             format: "%a %d %b %Y, %A %d %B",
+                             locale: "de"],
+            want: Ok(value!("Di 16 Mär 2021, Dienstag 16 März")),
+            tdef: TypeDef::bytes().fallible(),
+        }
     ];
 }
@@ -0,0 +1,182 @@
+use ::sha1::Sha1;
+use ::value::Value;
+use hmac_lib::{
+    digest::{core_api::BlockSizeUser, Digest},
+    Mac, SimpleHmac,
+};
+use sha_2::{Sha256, Sha512};
+use vrl::prelude::*;
+
+fn hmac(value: Value, key: Value, algorithm: &Bytes, encoding: &Bytes) -> Resolved {
+    let value = value.try_bytes()?;
+    let key = key.try_bytes()?;
+    let hash = match algorithm.as_ref() {
+        b"sha1" => sign::<Sha1>(&value, &key),
+        b"sha256" => sign::<Sha256>(&value, &key),
+        b"sha512" => sign::<Sha512>(&value, &key),
+        _ => unreachable!("enum invariant"),
+    };
+    let hash = match encoding.as_ref() {
+        b"hex" => Bytes::from(hex::encode(hash)),
+        b"raw" => Bytes::from(hash),
+        _ => unreachable!("enum invariant"),
+    };
+    Ok(hash.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Hmac;
+
+fn algorithms() -> Vec<Value> {
+    vec![value!("sha1"), value!("sha256"), value!("sha512")]
+}
+
+fn encodings() -> Vec<Value> {
+    vec![value!("hex"), value!("raw")]
+}
+
+impl Function for Hmac {
+    fn identifier(&self) -> &'static str {
+        "hmac"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "algorithm",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "encoding",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "default algorithm",
+                source: r#"hmac("foo", "secret")"#,
+                result: Ok("773ba44693c7553d6ee20f61ea5d2757a9a4f4a44d2841ae4e95b52e4cd62db4"),
+            },
+            Example {
+                title: "custom algorithm",
+                source: r#"hmac("foo", "secret", algorithm: "sha1")"#,
+                result: Ok("9baed91be7f58b57c824b60da7cb262b2ecafbd2"),
+            },
+            Example {
+                title: "raw bytes",
+                source: r#"encode_base64(hmac("foo", "secret", encoding: "raw"))"#,
+                result: Ok("dzukRpPHVT1u4g9h6l0nV6mk9KRNKEGuTpW1LkzWLbQ="),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let key = arguments.required("key");
+        let algorithm = arguments
+            .optional_enum("algorithm", &algorithms())?
+            .unwrap_or_else(|| value!("sha256"))
+            .try_bytes()
+            .expect("algorithm not bytes");
+        let encoding = arguments
+            .optional_enum("encoding", &encodings())?
+            .unwrap_or_else(|| value!("hex"))
+            .try_bytes()
+            .expect("encoding not bytes");
+
+        Ok(HmacFn {
+            value,
+            key,
+            algorithm,
+            encoding,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct HmacFn {
+    value: Box<dyn Expression>,
+    key: Box<dyn Expression>,
+    algorithm: Bytes,
+    encoding: Bytes,
+}
+
+impl FunctionExpression for HmacFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let key = self.key.resolve(ctx)?;
+
+        hmac(value, key, &self.algorithm, &self.encoding)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[inline]
+fn sign<T: Digest + BlockSizeUser>(value: &[u8], key: &[u8]) -> Vec<u8> {
+    let mut mac = SimpleHmac::<T>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(value);
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        hmac => Hmac;
+
+        hmac {
+            args: func_args![value: "foo", key: "secret"],
+            want: Ok("773ba44693c7553d6ee20f61ea5d2757a9a4f4a44d2841ae4e95b52e4cd62db4"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        hmac_sha1 {
+            args: func_args![value: "foo", key: "secret", algorithm: "sha1"],
+            want: Ok("9baed91be7f58b57c824b60da7cb262b2ecafbd2"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        hmac_sha512 {
+            args: func_args![value: "foo", key: "secret", algorithm: "sha512"],
+            want: Ok("82df7103de8d82de45e01c45fe642b5d13c6c2b47decafebc009431c665c6fa5f3d1af4e978ea1bde91426622073ebeac61a3461efd467e0971c788bc8ebdbbe"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        hmac_raw {
+            args: func_args![value: "foo", key: "secret", algorithm: "sha1", encoding: "raw"],
+            want: Ok(Bytes::from(hex::decode("9baed91be7f58b57c824b60da7cb262b2ecafbd2").unwrap())),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        invalid_algorithm {
+            args: func_args![value: "foo", key: "secret", algorithm: "md5"],
+            want: Err(r#"invalid enum variant""#),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
@@ -48,16 +48,26 @@ mod compact;
 mod contains;
 #[cfg(feature = "decode_base64")]
 mod decode_base64;
+#[cfg(feature = "decode_gzip")]
+mod decode_gzip;
 #[cfg(feature = "decode_percent")]
 mod decode_percent;
+#[cfg(feature = "decode_zstd")]
+mod decode_zstd;
 #[cfg(feature = "decrypt")]
 mod decrypt;
 #[cfg(feature = "del")]
 mod del;
+#[cfg(feature = "dns_lookup")]
+mod dns_lookup;
+#[cfg(any(feature = "dns_lookup", feature = "reverse_dns"))]
+mod dns_util;
 #[cfg(feature = "downcase")]
 mod downcase;
 #[cfg(feature = "encode_base64")]
 mod encode_base64;
+#[cfg(feature = "encode_gzip")]
+mod encode_gzip;
 #[cfg(feature = "encode_json")]
 mod encode_json;
 #[cfg(feature = "encode_key_value")]
@@ -66,6 +76,8 @@ mod encode_key_value;
 mod encode_logfmt;
 #[cfg(feature = "encode_percent")]
 mod encode_percent;
+#[cfg(feature = "encode_zstd")]
+mod encode_zstd;
 #[cfg(feature = "encrypt")]
 mod encrypt;
 #[cfg(feature = "ends_with")]
@@ -96,6 +108,8 @@ mod get;
 mod get_env_var;
 #[cfg(feature = "get_hostname")]
 mod get_hostname;
+#[cfg(feature = "hmac")]
+mod hmac;
 #[cfg(feature = "includes")]
 mod includes;
 #[cfg(feature = "integer")]
@@ -150,12 +164,16 @@ mod join;
 mod keys;
 #[cfg(feature = "length")]
 mod length;
+#[cfg(any(feature = "format_timestamp", feature = "parse_timestamp"))]
+mod locale_util;
 #[cfg(feature = "log")]
 mod log;
 #[cfg(any(
     feature = "parse_common_log",
     feature = "parse_apache_log",
-    feature = "parse_nginx_log"
+    feature = "parse_apache_error_log",
+    feature = "parse_nginx_log",
+    feature = "parse_nginx_error_log"
 ))]
 mod log_util;
 #[cfg(feature = "map_keys")]
@@ -182,6 +200,8 @@ mod now;
 mod object;
 #[cfg(feature = "only_fields")]
 mod only_fields;
+#[cfg(feature = "parse_apache_error_log")]
+mod parse_apache_error_log;
 #[cfg(feature = "parse_apache_log")]
 mod parse_apache_log;
 #[cfg(feature = "parse_aws_alb_log")]
@@ -212,12 +232,18 @@ mod parse_json;
 mod parse_key_value;
 #[cfg(feature = "parse_klog")]
 mod parse_klog;
+#[cfg(feature = "parse_leef")]
+mod parse_leef;
 #[cfg(feature = "parse_linux_authorization")]
 mod parse_linux_authorization;
 #[cfg(feature = "parse_logfmt")]
 mod parse_logfmt;
+#[cfg(feature = "parse_nginx_error_log")]
+mod parse_nginx_error_log;
 #[cfg(feature = "parse_nginx_log")]
 mod parse_nginx_log;
+#[cfg(feature = "parse_proto")]
+mod parse_proto;
 #[cfg(feature = "parse_query_string")]
 mod parse_query_string;
 #[cfg(feature = "parse_regex")]
@@ -306,6 +332,8 @@ mod to_unix_timestamp;
 mod truncate;
 #[cfg(feature = "type_def")]
 mod type_def;
+#[cfg(feature = "ulid")]
+mod ulid;
 #[cfg(feature = "unique")]
 mod unique;
 #[cfg(feature = "unnest")]
@@ -314,6 +342,8 @@ mod unnest;
 mod upcase;
 #[cfg(feature = "uuid_v4")]
 mod uuid_v4;
+#[cfg(feature = "uuid_v7")]
+mod uuid_v7;
 #[cfg(feature = "values")]
 mod values;
 
@@ -337,16 +367,24 @@ pub use compact::Compact;
 pub use contains::Contains;
 #[cfg(feature = "decode_base64")]
 pub use decode_base64::DecodeBase64;
+#[cfg(feature = "decode_gzip")]
+pub use decode_gzip::DecodeGzip;
 #[cfg(feature = "decode_percent")]
 pub use decode_percent::DecodePercent;
+#[cfg(feature = "decode_zstd")]
+pub use decode_zstd::DecodeZstd;
 #[cfg(feature = "decrypt")]
 pub use decrypt::Decrypt;
 #[cfg(feature = "del")]
 pub use del::Del;
+#[cfg(feature = "dns_lookup")]
+pub use dns_lookup::DnsLookup;
 #[cfg(feature = "downcase")]
 pub use downcase::Downcase;
 #[cfg(feature = "encode_base64")]
 pub use encode_base64::EncodeBase64;
+#[cfg(feature = "encode_gzip")]
+pub use encode_gzip::EncodeGzip;
 #[cfg(feature = "encode_json")]
 pub use encode_json::EncodeJson;
 #[cfg(feature = "encode_key_value")]
@@ -355,6 +393,8 @@ pub use encode_key_value::EncodeKeyValue;
 pub use encode_logfmt::EncodeLogfmt;
 #[cfg(feature = "encode_percent")]
 pub use encode_percent::EncodePercent;
+#[cfg(feature = "encode_zstd")]
+pub use encode_zstd::EncodeZstd;
 #[cfg(feature = "encrypt")]
 pub use encrypt::Encrypt;
 #[cfg(feature = "ends_with")]
@@ -385,6 +425,8 @@ pub use get::Get;
 pub use get_env_var::GetEnvVar;
 #[cfg(feature = "get_hostname")]
 pub use get_hostname::GetHostname;
+#[cfg(feature = "hmac")]
+pub use hmac::Hmac;
 #[cfg(feature = "includes")]
 pub use includes::Includes;
 #[cfg(feature = "integer")]
@@ -461,6 +503,8 @@ pub use now::Now;
 pub use object::Object;
 #[cfg(feature = "only_fields")]
 pub use only_fields::OnlyFields;
+#[cfg(feature = "parse_apache_error_log")]
+pub use parse_apache_error_log::ParseApacheErrorLog;
 #[cfg(feature = "parse_apache_log")]
 pub use parse_apache_log::ParseApacheLog;
 #[cfg(feature = "parse_aws_alb_log")]
@@ -491,12 +535,18 @@ pub use parse_json::ParseJson;
 pub use parse_key_value::ParseKeyValue;
 #[cfg(feature = "parse_klog")]
 pub use parse_klog::ParseKlog;
+#[cfg(feature = "parse_leef")]
+pub use parse_leef::ParseLeef;
 #[cfg(feature = "parse_linux_authorization")]
 pub use parse_linux_authorization::ParseLinuxAuthorization;
 #[cfg(feature = "parse_logfmt")]
 pub use parse_logfmt::ParseLogFmt;
+#[cfg(feature = "parse_nginx_error_log")]
+pub use parse_nginx_error_log::ParseNginxErrorLog;
 #[cfg(feature = "parse_nginx_log")]
 pub use parse_nginx_log::ParseNginxLog;
+#[cfg(feature = "parse_proto")]
+pub use parse_proto::ParseProto;
 #[cfg(feature = "parse_query_string")]
 pub use parse_query_string::ParseQueryString;
 #[cfg(feature = "parse_regex")]
@@ -585,6 +635,8 @@ pub use to_unix_timestamp::ToUnixTimestamp;
 pub use truncate::Truncate;
 #[cfg(feature = "type_def")]
 pub use type_def::TypeDef;
+#[cfg(feature = "ulid")]
+pub use ulid::Ulid;
 #[cfg(feature = "unique")]
 pub use unique::Unique;
 #[cfg(feature = "unnest")]
@@ -593,6 +645,8 @@ pub use unnest::Unnest;
 pub use upcase::Upcase;
 #[cfg(feature = "uuid_v4")]
 pub use uuid_v4::UuidV4;
+#[cfg(feature = "uuid_v7")]
+pub use uuid_v7::UuidV7;
 #[cfg(feature = "values")]
 pub use values::Values;
 
@@ -626,16 +680,24 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Contains),
         #[cfg(feature = "decode_base64")]
         Box::new(DecodeBase64),
+        #[cfg(feature = "decode_gzip")]
+        Box::new(DecodeGzip),
         #[cfg(feature = "decode_percent")]
         Box::new(DecodePercent),
+        #[cfg(feature = "decode_zstd")]
+        Box::new(DecodeZstd),
         #[cfg(feature = "decrypt")]
         Box::new(Decrypt),
         #[cfg(feature = "del")]
         Box::new(Del),
+        #[cfg(feature = "dns_lookup")]
+        Box::new(DnsLookup),
         #[cfg(feature = "downcase")]
         Box::new(Downcase),
         #[cfg(feature = "encode_base64")]
         Box::new(EncodeBase64),
+        #[cfg(feature = "encode_gzip")]
+        Box::new(EncodeGzip),
         #[cfg(feature = "encode_json")]
         Box::new(EncodeJson),
         #[cfg(feature = "encode_key_value")]
@@ -644,6 +706,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(EncodeLogfmt),
         #[cfg(feature = "encode_percent")]
         Box::new(EncodePercent),
+        #[cfg(feature = "encode_zstd")]
+        Box::new(EncodeZstd),
         #[cfg(feature = "encrypt")]
         Box::new(Encrypt),
         #[cfg(feature = "ends_with")]
@@ -674,6 +738,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(GetEnvVar),
         #[cfg(feature = "get_hostname")]
         Box::new(GetHostname),
+        #[cfg(feature = "hmac")]
+        Box::new(Hmac),
         #[cfg(feature = "includes")]
         Box::new(Includes),
         #[cfg(feature = "integer")]
@@ -756,6 +822,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         //Box::new(OnlyFields),
         #[cfg(feature = "object")]
         Box::new(Object),
+        #[cfg(feature = "parse_apache_error_log")]
+        Box::new(ParseApacheErrorLog),
         #[cfg(feature = "parse_apache_log")]
         Box::new(ParseApacheLog),
         #[cfg(feature = "parse_aws_alb_log")]
@@ -786,12 +854,18 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseKeyValue),
         #[cfg(feature = "parse_klog")]
         Box::new(ParseKlog),
+        #[cfg(feature = "parse_leef")]
+        Box::new(ParseLeef),
         #[cfg(feature = "parse_linux_authorization")]
         Box::new(ParseLinuxAuthorization),
         #[cfg(feature = "parse_logfmt")]
         Box::new(ParseLogFmt),
+        #[cfg(feature = "parse_nginx_error_log")]
+        Box::new(ParseNginxErrorLog),
         #[cfg(feature = "parse_nginx_log")]
         Box::new(ParseNginxLog),
+        #[cfg(feature = "parse_proto")]
+        Box::new(ParseProto),
         #[cfg(feature = "parse_query_string")]
         Box::new(ParseQueryString),
         #[cfg(feature = "parse_regex")]
@@ -880,6 +954,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Truncate),
         #[cfg(feature = "type_def")]
         Box::new(TypeDef),
+        #[cfg(feature = "ulid")]
+        Box::new(Ulid),
         #[cfg(feature = "unique")]
         Box::new(Unique),
         #[cfg(feature = "unnest")]
@@ -888,6 +964,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Upcase),
         #[cfg(feature = "uuid_v4")]
         Box::new(UuidV4),
+        #[cfg(feature = "uuid_v7")]
+        Box::new(UuidV7),
         #[cfg(feature = "values")]
         Box::new(Values),
     ]
@@ -0,0 +1,425 @@
+//! Month and weekday names of the locales supported when parsing and formatting timestamps, as
+//! chrono only knows the English names.
+
+use ::value::Value;
+use bytes::Bytes;
+use chrono::{
+    format::{Fixed, Item},
+    DateTime, Datelike, Utc,
+};
+
+/// The month and weekday names of a locale, each with the full name first, followed by its
+/// abbreviations.
+pub(crate) struct Names {
+    months: [&'static [&'static str]; 12],
+    weekdays: [&'static [&'static str]; 7],
+}
+
+const ENGLISH: Names = Names {
+    months: [
+        &["January", "Jan"],
+        &["February", "Feb"],
+        &["March", "Mar"],
+        &["April", "Apr"],
+        &["May"],
+        &["June", "Jun"],
+        &["July", "Jul"],
+        &["August", "Aug"],
+        &["September", "Sep"],
+        &["October", "Oct"],
+        &["November", "Nov"],
+        &["December", "Dec"],
+    ],
+    weekdays: [
+        &["Monday", "Mon"],
+        &["Tuesday", "Tue"],
+        &["Wednesday", "Wed"],
+        &["Thursday", "Thu"],
+        &["Friday", "Fri"],
+        &["Saturday", "Sat"],
+        &["Sunday", "Sun"],
+    ],
+};
+
+/// The names of the supported locales.
+const LOCALES: &[(&str, Names)] = &[
+    (
+        "de",
+        Names {
+            months: [
+                &["Januar", "Jan", "Jänner", "Jän"],
+                &["Februar", "Feb"],
+                &["März", "Mär", "Mrz"],
+                &["April", "Apr"],
+                &["Mai"],
+                &["Juni", "Jun"],
+                &["Juli", "Jul"],
+                &["August", "Aug"],
+                &["September", "Sep", "Sept"],
+                &["Oktober", "Okt"],
+                &["November", "Nov"],
+                &["Dezember", "Dez"],
+            ],
+            weekdays: [
+                &["Montag", "Mo"],
+                &["Dienstag", "Di"],
+                &["Mittwoch", "Mi"],
+                &["Donnerstag", "Do"],
+                &["Freitag", "Fr"],
+                &["Samstag", "Sa", "Sonnabend"],
+                &["Sonntag", "So"],
+            ],
+        },
+    ),
+    ("en", ENGLISH),
+    (
+        "es",
+        Names {
+            months: [
+                &["enero", "ene"],
+                &["febrero", "feb"],
+                &["marzo", "mar"],
+                &["abril", "abr"],
+                &["mayo", "may"],
+                &["junio", "jun"],
+                &["julio", "jul"],
+                &["agosto", "ago"],
+                &["septiembre", "sept", "sep", "setiembre"],
+                &["octubre", "oct"],
+                &["noviembre", "nov"],
+                &["diciembre", "dic"],
+            ],
+            weekdays: [
+                &["lunes", "lun"],
+                &["martes", "mar"],
+                &["miércoles", "mié", "mie"],
+                &["jueves", "jue"],
+                &["viernes", "vie"],
+                &["sábado", "sáb", "sab"],
+                &["domingo", "dom"],
+            ],
+        },
+    ),
+    (
+        "fr",
+        Names {
+            months: [
+                &["janvier", "janv"],
+                &["février", "févr", "fév"],
+                &["mars"],
+                &["avril", "avr"],
+                &["mai"],
+                &["juin"],
+                &["juillet", "juil"],
+                &["août"],
+                &["septembre", "sept"],
+                &["octobre", "oct"],
+                &["novembre", "nov"],
+                &["décembre", "déc"],
+            ],
+            weekdays: [
+                &["lundi", "lun"],
+                &["mardi", "mar"],
+                &["mercredi", "mer"],
+                &["jeudi", "jeu"],
+                &["vendredi", "ven"],
+                &["samedi", "sam"],
+                &["dimanche", "dim"],
+            ],
+        },
+    ),
+    (
+        "it",
+        Names {
+            months: [
+                &["gennaio", "gen"],
+                &["febbraio", "feb"],
+                &["marzo", "mar"],
+                &["aprile", "apr"],
+                &["maggio", "mag"],
+                &["giugno", "giu"],
+                &["luglio", "lug"],
+                &["agosto", "ago"],
+                &["settembre", "set"],
+                &["ottobre", "ott"],
+                &["novembre", "nov"],
+                &["dicembre", "dic"],
+            ],
+            weekdays: [
+                &["lunedì", "lun"],
+                &["martedì", "mar"],
+                &["mercoledì", "mer"],
+                &["giovedì", "gio"],
+                &["venerdì", "ven"],
+                &["sabato", "sab"],
+                &["domenica", "dom"],
+            ],
+        },
+    ),
+    (
+        "nl",
+        Names {
+            months: [
+                &["januari", "jan"],
+                &["februari", "feb"],
+                &["maart", "mrt"],
+                &["april", "apr"],
+                &["mei"],
+                &["juni", "jun"],
+                &["juli", "jul"],
+                &["augustus", "aug"],
+                &["september", "sep"],
+                &["oktober", "okt"],
+                &["november", "nov"],
+                &["december", "dec"],
+            ],
+            weekdays: [
+                &["maandag", "ma"],
+                &["dinsdag", "di"],
+                &["woensdag", "wo"],
+                &["donderdag", "do"],
+                &["vrijdag", "vr"],
+                &["zaterdag", "za"],
+                &["zondag", "zo"],
+            ],
+        },
+    ),
+    (
+        "pt",
+        Names {
+            months: [
+                &["janeiro", "jan"],
+                &["fevereiro", "fev"],
+                &["março", "mar"],
+                &["abril", "abr"],
+                &["maio", "mai"],
+                &["junho", "jun"],
+                &["julho", "jul"],
+                &["agosto", "ago"],
+                &["setembro", "set"],
+                &["outubro", "out"],
+                &["novembro", "nov"],
+                &["dezembro", "dez"],
+            ],
+            weekdays: [
+                &["segunda-feira", "seg", "segunda"],
+                &["terça-feira", "ter", "terça"],
+                &["quarta-feira", "qua", "quarta"],
+                &["quinta-feira", "qui", "quinta"],
+                &["sexta-feira", "sex", "sexta"],
+                &["sábado", "sáb"],
+                &["domingo", "dom"],
+            ],
+        },
+    ),
+    (
+        "sv",
+        Names {
+            months: [
+                &["januari", "jan"],
+                &["februari", "feb"],
+                &["mars", "mar"],
+                &["april", "apr"],
+                &["maj"],
+                &["juni", "jun"],
+                &["juli", "jul"],
+                &["augusti", "aug"],
+                &["september", "sep"],
+                &["oktober", "okt"],
+                &["november", "nov"],
+                &["december", "dec"],
+            ],
+            weekdays: [
+                &["måndag", "mån"],
+                &["tisdag", "tis"],
+                &["onsdag", "ons"],
+                &["torsdag", "tors", "tor"],
+                &["fredag", "fre"],
+                &["lördag", "lör"],
+                &["söndag", "sön"],
+            ],
+        },
+    ),
+];
+
+pub(crate) fn locales() -> Vec<Value> {
+    LOCALES
+        .iter()
+        .map(|(locale, _)| Value::from(*locale))
+        .collect()
+}
+
+pub(crate) fn locale_names(locale: &Bytes) -> &'static Names {
+    LOCALES
+        .iter()
+        .find(|(name, _)| name.as_bytes() == locale.as_ref())
+        .map(|(_, names)| names)
+        .expect("enum invariant")
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum NameKind {
+    Month,
+    Weekday,
+}
+
+fn position(names: &[&[&str]], word: &str) -> Option<usize> {
+    names
+        .iter()
+        .position(|names| names.iter().any(|name| name.to_lowercase() == word))
+}
+
+impl Names {
+    /// Translates the localized month and weekday names in the value to English, so that chrono
+    /// can parse them. Names, which are both a month and a weekday(e.g. `mar` in Spanish), are
+    /// resolved with the order of the names in the format.
+    /// A dot after an abbreviation(e.g. `déc.`) is dropped unless the format expects it.
+    /// Names are translated to the English abbreviation where the format expects one, as chrono
+    /// only parses abbreviated month names for `%b`.
+    pub(crate) fn translate(&self, value: &str, items: &[Item<'_>]) -> String {
+        let mut expected = items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                let (kind, long) = match item {
+                    Item::Fixed(Fixed::ShortMonthName) => (NameKind::Month, false),
+                    Item::Fixed(Fixed::LongMonthName) => (NameKind::Month, true),
+                    Item::Fixed(Fixed::ShortWeekdayName) => (NameKind::Weekday, false),
+                    Item::Fixed(Fixed::LongWeekdayName) => (NameKind::Weekday, true),
+                    _ => return None,
+                };
+                let dot_follows = matches!(
+                    items.get(index + 1),
+                    Some(Item::Literal(literal)) if literal.starts_with('.')
+                );
+                Some((kind, long, dot_follows))
+            })
+            .peekable();
+
+        let mut translated = String::with_capacity(value.len());
+        let mut rest = value;
+        while let Some(c) = rest.chars().next() {
+            if !c.is_alphabetic() {
+                translated.push(c);
+                rest = &rest[c.len_utf8()..];
+                continue;
+            }
+
+            // words may contain dashes, e.g. `segunda-feira`
+            let mut end = 0;
+            let mut chars = rest.char_indices().peekable();
+            while let Some((index, c)) = chars.next() {
+                let dash = c == '-' && chars.peek().map_or(false, |(_, c)| c.is_alphabetic());
+                if !c.is_alphabetic() && !dash {
+                    break;
+                }
+                end = index + c.len_utf8();
+            }
+            let (word, after) = rest.split_at(end);
+            rest = after;
+
+            let lowercase = word.to_lowercase();
+            let month = position(&self.months, &lowercase);
+            let weekday = position(&self.weekdays, &lowercase);
+            let next = expected.peek().map(|(kind, _, _)| *kind);
+            let (kind, index) = match (month, weekday) {
+                (Some(month), _) if next == Some(NameKind::Month) || weekday.is_none() => {
+                    (NameKind::Month, month)
+                }
+                (_, Some(weekday)) => (NameKind::Weekday, weekday),
+                _ => {
+                    translated.push_str(word);
+                    continue;
+                }
+            };
+            let (long, dot_follows) = expected
+                .next_if(|(expected, _, _)| *expected == kind)
+                .map_or((true, true), |(_, long, dot_follows)| (long, dot_follows));
+
+            let names = match kind {
+                NameKind::Month => ENGLISH.months[index],
+                NameKind::Weekday => ENGLISH.weekdays[index],
+            };
+            let name = if long {
+                names[0]
+            } else {
+                names[names.len() - 1]
+            };
+            translated.push_str(name);
+            if !dot_follows {
+                rest = rest.strip_prefix('.').unwrap_or(rest);
+            }
+        }
+
+        translated
+    }
+
+    /// Replaces the month and weekday names in the format with the localized names of the
+    /// timestamp.
+    pub(crate) fn localize<'a>(&self, items: Vec<Item<'a>>, dt: &DateTime<Utc>) -> Vec<Item<'a>> {
+        let month = self.months[dt.month0() as usize];
+        let weekday = self.weekdays[dt.weekday().num_days_from_monday() as usize];
+        let abbreviation = |names: &[&'static str]| names.get(1).copied().unwrap_or(names[0]);
+
+        items
+            .into_iter()
+            .map(|item| match item {
+                Item::Fixed(Fixed::LongMonthName) => Item::Literal(month[0]),
+                Item::Fixed(Fixed::ShortMonthName) => Item::Literal(abbreviation(month)),
+                Item::Fixed(Fixed::LongWeekdayName) => Item::Literal(weekday[0]),
+                Item::Fixed(Fixed::ShortWeekdayName) => Item::Literal(abbreviation(weekday)),
+                item => item,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{format::strftime::StrftimeItems, TimeZone};
+
+    use super::*;
+
+    fn translate(locale: &str, value: &str, format: &str) -> String {
+        locale_names(&Bytes::from(locale.to_owned()))
+            .translate(value, &StrftimeItems::new(format).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn translates_names() {
+        assert_eq!(
+            translate("fr", "lundi 13 déc. 2021", "%A %d %b %Y"),
+            "Monday 13 Dec 2021"
+        );
+        assert_eq!(translate("fr", "13 déc. 2021", "%d %b. %Y"), "13 Dec. 2021");
+        assert_eq!(
+            translate("de", "15. März 2021", "%d. %B %Y"),
+            "15. March 2021"
+        );
+        assert_eq!(
+            translate("pt", "segunda-feira, 15-mar-2021", "%A, %d-%b-%Y"),
+            "Monday, 15-Mar-2021"
+        );
+    }
+
+    #[test]
+    fn resolves_ambiguous_names_with_format() {
+        assert_eq!(
+            translate("es", "mar 16 mar 2021", "%a %d %b %Y"),
+            "Tue 16 Mar 2021"
+        );
+        assert_eq!(translate("es", "16 mar 2021", "%d %b %Y"), "16 Mar 2021");
+    }
+
+    #[test]
+    fn localizes_names() {
+        let dt = Utc.ymd(2021, 3, 16).and_hms(0, 0, 0);
+        let items = locale_names(&Bytes::from("de"))
+            .localize(StrftimeItems::new("%A, %d. %B (%a, %b)").collect(), &dt);
+
+        assert_eq!(
+            dt.format_with_items(items.into_iter()).to_string(),
+            "Dienstag, 16. März (Di, Mär)"
+        );
+    }
+}
@@ -68,7 +68,7 @@ pub(crate) static REGEX_APACHE_COMBINED_LOG: Lazy<Vec<Regex>> = Lazy::new(|| {
 });
 
 // It is possible to customise the format output by apache.
-#[cfg(feature = "parse_apache_log")]
+#[cfg(any(feature = "parse_apache_log", feature = "parse_apache_error_log"))]
 pub(crate) static REGEX_APACHE_ERROR_LOG: Lazy<Vec<Regex>> = Lazy::new(|| {
     vec![
         // Simple format
@@ -131,7 +131,7 @@ pub(crate) static REGEX_NGINX_COMBINED_LOG: Lazy<Regex> = Lazy::new(|| {
     .expect("failed compiling regex for Nginx combined log")
 });
 
-#[cfg(feature = "parse_nginx_log")]
+#[cfg(any(feature = "parse_nginx_log", feature = "parse_nginx_error_log"))]
 pub(crate) static REGEX_NGINX_ERROR_LOG: Lazy<Regex> = Lazy::new(|| {
     Regex::new(
         r#"(?x)                                                                  # Ignore whitespace and comments in the regex expression.
@@ -141,7 +141,7 @@ pub(crate) static REGEX_NGINX_ERROR_LOG: Lazy<Regex> = Lazy::new(|| {
         (?P<pid>\d+)\#                                                           # Match any number
         (?P<tid>\d+):                                                            # Match any number
         (\s+\*(?P<cid>\d+))?                                                     # Match any number
-        \s+(?P<message>[^,]*)                                                    # Match any character
+        \s+(?P<message>.*?)                                                      # Match any character until the optional fields
         (,\s+excess:\s+(?P<excess>[^\s]+)\sby\szone\s"(?P<zone>[^,]+)")?         # Match any character after ', excess: ' until ' by zone ' and the rest of characters
         (,\s+client:\s+(?P<client>[^,]+))?                                       # Match any character after ', client: '
         (,\s+server:\s+(?P<server>[^,]+))?                                       # Match any character after ', server: '
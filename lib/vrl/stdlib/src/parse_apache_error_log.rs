@@ -0,0 +1,280 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::log_util;
+
+/// The timestamp formats tried if none is given: the default format of Apache 2.4, with or
+/// without microseconds, followed by the format of the common log.
+const DEFAULT_TIMESTAMP_FORMATS: [&str; 2] = ["%a %b %d %H:%M:%S%.f %Y", "%d/%b/%Y:%T %z"];
+
+fn parse_apache_error_log(
+    bytes: Value,
+    timestamp_format: Option<Value>,
+    ctx: &Context,
+) -> Resolved {
+    let message = bytes.try_bytes_utf8_lossy()?;
+    let parse = |timestamp_format: &str| {
+        log_util::parse_message(
+            &*log_util::REGEX_APACHE_ERROR_LOG,
+            &message,
+            timestamp_format,
+            ctx.timezone(),
+            "apache error",
+        )
+    };
+    let value = match timestamp_format {
+        Some(timestamp_format) => parse(&timestamp_format.try_bytes_utf8_lossy()?),
+        None => DEFAULT_TIMESTAMP_FORMATS[1..].iter().fold(
+            parse(DEFAULT_TIMESTAMP_FORMATS[0]),
+            |result, timestamp_format| {
+                result.or_else(|error| parse(timestamp_format).map_err(|_| error))
+            },
+        ),
+    }?;
+
+    Ok(split_error(value))
+}
+
+/// Splits the OS error(e.g. `(113)No route to host`) and the error code(e.g. `AH00957`) of the
+/// threaded MPM format from the message.
+fn split_error(mut value: Value) -> Value {
+    if let Some(object) = value.as_object_mut() {
+        let message = object.remove("message2");
+        if let Some(prefix) = object.remove("message1") {
+            let prefix = prefix
+                .try_bytes_utf8_lossy()
+                .unwrap_or_default()
+                .into_owned();
+            let mut message_parts = Vec::new();
+            for part in prefix
+                .split(": ")
+                .map(str::trim)
+                .filter(|part| !part.is_empty())
+            {
+                if let Some((errno, error)) = parse_os_error(part) {
+                    object.insert("errno".into(), errno.into());
+                    object.insert("error".into(), error.into());
+                } else if is_error_code(part) {
+                    object.insert("code".into(), part.into());
+                } else {
+                    message_parts.push(part.trim_end_matches(':').to_owned());
+                }
+            }
+            if let Some(message) = message.as_ref().and_then(Value::as_bytes) {
+                message_parts.push(String::from_utf8_lossy(message).into_owned());
+            }
+            object.insert("message".into(), message_parts.join(": ").into());
+        } else if let Some(message) = message {
+            object.insert("message".into(), message);
+        }
+    }
+
+    value
+}
+
+fn parse_os_error(part: &str) -> Option<(i64, &str)> {
+    let (errno, error) = part.strip_prefix('(')?.split_once(')')?;
+    Some((errno.parse().ok()?, error))
+}
+
+fn is_error_code(part: &str) -> bool {
+    part.strip_prefix("AH").map_or(false, |code| {
+        !code.is_empty() && code.bytes().all(|b| b.is_ascii_digit())
+    })
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseApacheErrorLog;
+
+impl Function for ParseApacheErrorLog {
+    fn identifier(&self) -> &'static str {
+        "parse_apache_error_log"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "timestamp_format",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let timestamp_format = arguments.optional("timestamp_format");
+
+        Ok(ParseApacheErrorLogFn {
+            value,
+            timestamp_format,
+        }
+        .as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "parse apache error log",
+                source: r#"encode_json(parse_apache_error_log!(s'[Mon Mar 01 12:00:19 2021] [ab:alert] [pid 4803:tid 3814] [client 147.159.108.175:24259] I will bypass the haptic COM bandwidth, that should matrix the CSS driver!'))"#,
+                result: Ok(
+                    r#"s'{"client":"147.159.108.175","message":"I will bypass the haptic COM bandwidth, that should matrix the CSS driver!","module":"ab","pid":4803,"port":24259,"severity":"alert","thread":"3814","timestamp":"2021-03-01T12:00:19Z"}'"#,
+                ),
+            },
+            Example {
+                title: "parse apache error log with an error code",
+                source: r#"encode_json(parse_apache_error_log!(s'[Mon Mar 01 12:00:19 2021] [proxy:error] [pid 23964] (113)No route to host: AH00957: HTTP: attempt to connect to 10.1.0.244:9000 (hostname.domain.com) failed'))"#,
+                result: Ok(
+                    r#"s'{"code":"AH00957","errno":113,"error":"No route to host","message":"HTTP: attempt to connect to 10.1.0.244:9000 (hostname.domain.com) failed","module":"proxy","pid":23964,"severity":"error","timestamp":"2021-03-01T12:00:19Z"}'"#,
+                ),
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseApacheErrorLogFn {
+    value: Box<dyn Expression>,
+    timestamp_format: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for ParseApacheErrorLogFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let bytes = self.value.resolve(ctx)?;
+        let timestamp_format = self
+            .timestamp_format
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        parse_apache_error_log(bytes, timestamp_format, ctx)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        (Field::from("timestamp"), Kind::timestamp() | Kind::null()),
+        (Field::from("module"), Kind::bytes() | Kind::null()),
+        (Field::from("severity"), Kind::bytes() | Kind::null()),
+        (Field::from("pid"), Kind::integer() | Kind::null()),
+        (Field::from("thread"), Kind::bytes() | Kind::null()),
+        (Field::from("client"), Kind::bytes() | Kind::null()),
+        (Field::from("port"), Kind::integer() | Kind::null()),
+        (Field::from("errno"), Kind::integer() | Kind::null()),
+        (Field::from("error"), Kind::bytes() | Kind::null()),
+        (Field::from("code"), Kind::bytes() | Kind::null()),
+        (Field::from("message"), Kind::bytes() | Kind::null()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::prelude::*;
+    use vector_common::btreemap;
+
+    use super::*;
+
+    test_function![
+        parse_apache_error_log => ParseApacheErrorLog;
+
+        apache_24_timestamp {
+            args: func_args![value: r#"[Wed Oct 11 14:32:52.123456 2000] [core:error] [pid 35708:tid 4328636416] [client 72.15.99.187:55820] File does not exist: /usr/local/apache2/htdocs/favicon.ico"#],
+            want: Ok(btreemap! {
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2000-10-11T14:32:52.123456Z").unwrap().into()),
+                "module" => "core",
+                "severity" => "error",
+                "pid" => 35708,
+                "thread" => "4328636416",
+                "client" => "72.15.99.187",
+                "port" => 55820,
+                "message" => "File does not exist: /usr/local/apache2/htdocs/favicon.ico",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        common_log_timestamp {
+            args: func_args![value: r#"[01/Mar/2021:12:00:19 +0000] [ab:alert] [pid 4803:tid 3814] [client 147.159.108.175:24259] I'll bypass the haptic COM bandwidth"#],
+            want: Ok(btreemap! {
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2021-03-01T12:00:19Z").unwrap().into()),
+                "module" => "ab",
+                "severity" => "alert",
+                "pid" => 4803,
+                "thread" => "3814",
+                "client" => "147.159.108.175",
+                "port" => 24259,
+                "message" => "I'll bypass the haptic COM bandwidth",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        os_error_and_code {
+            args: func_args![value: r#"[Mon Mar 01 12:00:19 2021] [proxy:error] [pid 23964:tid 3814] (113)No route to host: AH00957: HTTP: attempt to connect to 10.1.0.244:9000 (hostname.domain.com) failed"#],
+            want: Ok(btreemap! {
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2021-03-01T12:00:19Z").unwrap().into()),
+                "module" => "proxy",
+                "severity" => "error",
+                "pid" => 23964,
+                "thread" => "3814",
+                "errno" => 113,
+                "error" => "No route to host",
+                "code" => "AH00957",
+                "message" => "HTTP: attempt to connect to 10.1.0.244:9000 (hostname.domain.com) failed",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        code_and_client {
+            args: func_args![value: r#"[Mon Mar 01 12:00:19 2021] [authz_core:error] [pid 6377] AH01630: [client 10.0.0.1:49742] client denied by server configuration: /var/www/html/private"#],
+            want: Ok(btreemap! {
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2021-03-01T12:00:19Z").unwrap().into()),
+                "module" => "authz_core",
+                "severity" => "error",
+                "pid" => 6377,
+                "code" => "AH01630",
+                "client" => "10.0.0.1",
+                "port" => 49742,
+                "message" => "client denied by server configuration: /var/www/html/private",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        custom_timestamp_format {
+            args: func_args![
+                value: r#"[2021-06-04 15:40:27] [php7:emerg] [pid 4803] [client 95.223.77.60:35106] PHP Parse error:  syntax error"#,
+                timestamp_format: "%Y-%m-%d %H:%M:%S",
+            ],
+            want: Ok(btreemap! {
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2021-06-04T15:40:27Z").unwrap().into()),
+                "module" => "php7",
+                "severity" => "emerg",
+                "pid" => 4803,
+                "client" => "95.223.77.60",
+                "port" => 35106,
+                "message" => "PHP Parse error:  syntax error",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_line {
+            args: func_args![value: r#"127.0.0.1 bob frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326"#],
+            want: Err("failed parsing apache error log line"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
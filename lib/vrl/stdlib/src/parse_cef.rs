@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use ::value::Value;
 use nom::{
     self,
@@ -21,11 +23,18 @@ impl Function for ParseCef {
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::BYTES,
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "translate_custom_fields",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
     }
 
     fn examples(&self) -> &'static [Example] {
@@ -65,6 +74,13 @@ impl Function for ParseCef {
                     r#"{"cefVersion":"0","deviceVendor":"security","deviceProduct":"threatmanager","deviceVersion":"1.0","deviceEventClassId":"100","name":"Detected a | in message. No action needed.","severity":"10","src":"10.0.0.1","msg":"Detected a threat.\n No action needed","act":"blocked a =", "dst":"1.1.1.1"}"#,
                 ),
             },
+            Example {
+                title: "translate custom fields",
+                source: r#"parse_cef!("CEF:0|Dev|firewall|2.2|1|Connection denied|5|c6a1=2345:0425:2CA1:0000:0000:0567:5673:23b5 c6a1Label=Device IPv6 Address", translate_custom_fields: true)"#,
+                result: Ok(
+                    r#"{"cefVersion":"0","deviceVendor":"Dev","deviceProduct":"firewall","deviceVersion":"2.2","deviceEventClassId":"1","name":"Connection denied","severity":"5","Device IPv6 Address":"2345:0425:2CA1:0000:0000:0567:5673:23b5"}"#,
+                ),
+            },
         ]
     }
 
@@ -75,22 +91,37 @@ impl Function for ParseCef {
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
+        let translate_custom_fields = arguments.optional("translate_custom_fields");
 
-        Ok(ParseCefFn { value }.as_expr())
+        Ok(ParseCefFn {
+            value,
+            translate_custom_fields,
+        }
+        .as_expr())
     }
 }
 
 #[derive(Clone, Debug)]
 pub(crate) struct ParseCefFn {
     pub(crate) value: Box<dyn Expression>,
+    pub(crate) translate_custom_fields: Option<Box<dyn Expression>>,
 }
 
 impl FunctionExpression for ParseCefFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let bytes = self.value.resolve(ctx)?;
         let bytes = bytes.try_bytes_utf8_lossy()?;
-
-        parse(&bytes).map(Iterator::collect)
+        let translate_custom_fields = match &self.translate_custom_fields {
+            Some(expr) => expr.resolve(ctx)?.try_boolean()?,
+            None => false,
+        };
+
+        let fields = parse(&bytes)?;
+        if translate_custom_fields {
+            Ok(translate_custom_fields_with_labels(fields.collect()))
+        } else {
+            Ok(fields.collect())
+        }
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -144,6 +175,58 @@ fn parse(input: &str) -> Result<impl Iterator<Item = (String, Value)> + '_> {
     }
 }
 
+/// Names of the custom fields, which are paired with labels, e.g. `cs1` and `cs1Label`,
+/// both the short and the full ones, without the trailing numbers.
+const CUSTOM_FIELDS: &[&str] = &[
+    "c6a",
+    "cfp",
+    "cn",
+    "cs",
+    "deviceCustomDate",
+    "deviceCustomFloatingPoint",
+    "deviceCustomIPv6Address",
+    "deviceCustomNumber",
+    "deviceCustomString",
+    "flexDate",
+    "flexNumber",
+    "flexString",
+];
+
+fn is_custom_field(key: &str) -> bool {
+    let name = key.trim_end_matches(|c: char| c.is_ascii_digit());
+    name.len() < key.len() && CUSTOM_FIELDS.contains(&name)
+}
+
+/// Replaces the keys of custom fields with their labels, dropping the labels,
+/// e.g. `cs1=alice cs1Label=user` becomes `user=alice`.
+/// Custom fields without labels, as well as labels without values, are kept as is.
+fn translate_custom_fields_with_labels(fields: Vec<(String, Value)>) -> Value {
+    let labels = fields
+        .iter()
+        .filter_map(|(key, label)| {
+            key.strip_suffix("Label")
+                .filter(|field| is_custom_field(field))
+                .map(|field| (field.to_owned(), label.clone()))
+        })
+        .collect::<BTreeMap<_, _>>();
+    let values = fields
+        .iter()
+        .map(|(key, _)| key.clone())
+        .collect::<HashSet<_>>();
+
+    fields
+        .into_iter()
+        .filter_map(|(key, value)| match labels.get(&key) {
+            Some(Value::Bytes(label)) => Some((String::from_utf8_lossy(label).into_owned(), value)),
+            _ => match key.strip_suffix("Label") {
+                Some(field) if labels.contains_key(field) && values.contains(field) => None,
+                _ => Some((key, value)),
+            },
+        })
+        .collect::<BTreeMap<_, _>>()
+        .into()
+}
+
 fn parse_header(input: &str) -> IResult<&str, Vec<String>, VerboseError<&str>> {
     preceded(
         pair(take_until("CEF:"), tag("CEF:")),
@@ -571,5 +654,45 @@ mod test {
             tdef: type_def(),
         }
 
+        translate_custom_fields {
+            args: func_args! [
+                value: r#"CEF:0|Dev|firewall|2.2|1|Connection denied|5|cs1=alice cs1Label=user deviceCustomNumber2=42 deviceCustomNumber2Label=attempts cn3=7 cs4Label=unused"#,
+                translate_custom_fields: true,
+            ],
+            want: Ok(value!({
+                "cefVersion":"0",
+                "deviceVendor":"Dev",
+                "deviceProduct":"firewall",
+                "deviceVersion":"2.2",
+                "deviceEventClassId":"1",
+                "name":"Connection denied",
+                "severity":"5",
+                "user":"alice",
+                "attempts":"42",
+                "cn3":"7",
+                "cs4Label":"unused"
+            })),
+            tdef: type_def(),
+        }
+
+        custom_fields_untranslated {
+            args: func_args! [
+                value: r#"CEF:0|Dev|firewall|2.2|1|Connection denied|5|cs1=alice cs1Label=user"#,
+                translate_custom_fields: false,
+            ],
+            want: Ok(value!({
+                "cefVersion":"0",
+                "deviceVendor":"Dev",
+                "deviceProduct":"firewall",
+                "deviceVersion":"2.2",
+                "deviceEventClassId":"1",
+                "name":"Connection denied",
+                "severity":"5",
+                "cs1":"alice",
+                "cs1Label":"user"
+            })),
+            tdef: type_def(),
+        }
+
     ];
 }
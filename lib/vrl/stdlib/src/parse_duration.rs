@@ -17,28 +17,90 @@ fn parse_duration(bytes: Value, unit: Value) -> Resolved {
             .get(string.as_ref())
             .ok_or(format!("unknown unit format: '{}'", string))?
     };
-    let captures = RE
-        .captures(&value)
-        .ok_or(format!("unable to parse duration: '{}'", value))?;
-    let value = Decimal::from_str(&captures["value"])
-        .map_err(|error| format!("unable to parse number: {}", error))?;
-    let unit = UNITS
-        .get(&captures["unit"])
-        .ok_or(format!("unknown duration unit: '{}'", &captures["unit"]))?;
-    let number = value * unit / conversion_factor;
+    let seconds = if value.starts_with(['P', 'p']) {
+        parse_iso8601(&value)?
+    } else {
+        parse_compound(&value)?
+    };
+    let number = seconds / conversion_factor;
     let number = number
         .to_f64()
         .ok_or(format!("unable to format duration: '{}'", number))?;
     Ok(Value::from_f64_or_zero(number))
 }
 
+/// Parses a duration of one or more values with units, e.g. `1.5s` or `1h 30m15s`, to seconds.
+fn parse_compound(value: &str) -> Result<Decimal> {
+    if !RE.is_match(value) {
+        return Err(format!("unable to parse duration: '{}'", value).into());
+    }
+
+    COMPONENT_RE
+        .captures_iter(value)
+        .try_fold(Decimal::ZERO, |seconds, captures| {
+            let number = Decimal::from_str(&captures["value"])
+                .map_err(|error| format!("unable to parse number: {}", error))?;
+            let unit = UNITS
+                .get(&captures["unit"])
+                .ok_or(format!("unknown duration unit: '{}'", &captures["unit"]))?;
+            Ok(seconds + number * unit)
+        })
+}
+
+/// Parses an ISO 8601 duration, e.g. `PT5M` or `P1DT12H`, to seconds. Years and months are
+/// rejected, as their length depends on the date the duration starts at.
+fn parse_iso8601(value: &str) -> Result<Decimal> {
+    let invalid = || format!("unable to parse duration: '{}'", value);
+    let captures = ISO8601_RE.captures(value).ok_or_else(invalid)?;
+    if value.ends_with(['T', 't']) {
+        return Err(invalid().into());
+    }
+
+    let mut seconds = None;
+    for (name, unit) in [("w", "w"), ("d", "d"), ("h", "h"), ("m", "m"), ("s", "s")] {
+        if let Some(number) = captures.name(name) {
+            let number = Decimal::from_str(&number.as_str().replace(',', "."))
+                .map_err(|error| format!("unable to parse number: {}", error))?;
+            *seconds.get_or_insert(Decimal::ZERO) += number * UNITS[unit];
+        }
+    }
+    seconds.ok_or_else(|| invalid().into())
+}
+
 static RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(
-        r"(?ix)                        # i: case-insensitive, x: ignore whitespace + comments
+        r"(?ix)                                          # i: case-insensitive, x: ignore whitespace + comments
             \A
+            [0-9]*\.?[0-9]+\s?[µa-z]{1,2}                 # a value and unit, e.g. `1h` or `1.5 s`
+            (?:\s*[0-9]*\.?[0-9]+\s?[µa-z]{1,2})*         # more values and units, e.g. `1h30m` or `1h 30m`
+            \z",
+    )
+    .unwrap()
+});
+
+static COMPONENT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?ix)                        # i: case-insensitive, x: ignore whitespace + comments
             (?P<value>[0-9]*\.?[0-9]+) # value: integer or float
             \s?                        # optional space between value and unit
             (?P<unit>[µa-z]{1,2})      # unit: one or two letters
+            ",
+    )
+    .unwrap()
+});
+
+static ISO8601_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?ix)                                          # i: case-insensitive, x: ignore whitespace + comments
+            \A
+            P
+            (?:(?P<w>[0-9]+(?:[.,][0-9]+)?)W)?               # weeks
+            (?:(?P<d>[0-9]+(?:[.,][0-9]+)?)D)?               # days
+            (?:T
+                (?:(?P<h>[0-9]+(?:[.,][0-9]+)?)H)?           # hours
+                (?:(?P<m>[0-9]+(?:[.,][0-9]+)?)M)?           # minutes
+                (?:(?P<s>[0-9]+(?:[.,][0-9]+)?)S)?           # seconds
+            )?
             \z",
     )
     .unwrap()
@@ -56,6 +118,7 @@ static UNITS: Lazy<HashMap<String, Decimal>> = Lazy::new(|| {
         ("m", Decimal::new(60, 0)),
         ("h", Decimal::new(3_600, 0)),
         ("d", Decimal::new(86_400, 0)),
+        ("w", Decimal::new(604_800, 0)),
     ]
     .into_iter()
     .map(|(k, v)| (k.to_owned(), v))
@@ -71,11 +134,23 @@ impl Function for ParseDuration {
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "milliseconds",
-            source: r#"parse_duration!("1005ms", unit: "s")"#,
-            result: Ok("1.005"),
-        }]
+        &[
+            Example {
+                title: "milliseconds",
+                source: r#"parse_duration!("1005ms", unit: "s")"#,
+                result: Ok("1.005"),
+            },
+            Example {
+                title: "compound",
+                source: r#"parse_duration!("1h30m", unit: "m")"#,
+                result: Ok("90.0"),
+            },
+            Example {
+                title: "ISO 8601",
+                source: r#"parse_duration!("PT1M30S", unit: "s")"#,
+                result: Ok("90.0"),
+            },
+        ]
     }
 
     fn compile(
@@ -196,16 +271,72 @@ mod tests {
         }
 
         error_unit {
-            args: func_args![value: "1w",
+            args: func_args![value: "1y",
                              unit: "ns"],
-            want: Err("unknown duration unit: 'w'"),
+            want: Err("unknown duration unit: 'y'"),
             tdef: TypeDef::float().fallible(),
         }
 
         error_format {
             args: func_args![value: "1s",
-                             unit: "w"],
-            want: Err("unknown unit format: 'w'"),
+                             unit: "y"],
+            want: Err("unknown unit format: 'y'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        w_d {
+            args: func_args![value: "2w",
+                             unit: "d"],
+            want: Ok(14.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        compound {
+            args: func_args![value: "1h30m15s",
+                             unit: "s"],
+            want: Ok(5415.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        compound_with_spaces {
+            args: func_args![value: "1d 12h 1.5ms",
+                             unit: "ms"],
+            want: Ok(129_600_001.5),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_compound_unit {
+            args: func_args![value: "1h30y",
+                             unit: "s"],
+            want: Err("unknown duration unit: 'y'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        iso8601 {
+            args: func_args![value: "PT5M",
+                             unit: "s"],
+            want: Ok(300.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        iso8601_full {
+            args: func_args![value: "P1W2DT3H4M5,5S",
+                             unit: "s"],
+            want: Ok(788_645.5),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_iso8601_months {
+            args: func_args![value: "P1M",
+                             unit: "s"],
+            want: Err("unable to parse duration: 'P1M'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_iso8601_empty {
+            args: func_args![value: "PT",
+                             unit: "s"],
+            want: Err("unable to parse duration: 'PT'"),
             tdef: TypeDef::float().fallible(),
         }
     ];
@@ -1,22 +1,187 @@
+use ::value::Value;
 use datadog_grok::{
     parse_grok,
-    parse_grok_rules::{self, GrokRule},
+    parse_grok_rules::{self, GrokRule, OnMatchAction},
+};
+use lookup_lib::LookupBuf;
+use once_cell::sync::Lazy;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
 };
-use std::{collections::BTreeMap, fmt};
 use vrl::{
-    diagnostic::{Label, Span},
+    diagnostic::{Label, Note, Span},
     prelude::*,
 };
 
+// the number of distinct rule sets to keep compiled
+const CACHE_CAPACITY: usize = 64;
+
+// how often the match stats of a rule set are emitted as internal metrics
+const STATS_INTERVAL: Duration = Duration::from_secs(1);
+
+// identifies compiled rule sets in the `rule_set` tag of internal metrics
+static NEXT_RULE_SET_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Everything a compiled rule set depends on, identifying it in the cache.
+#[derive(Debug, Clone, PartialEq)]
+struct GroksConfig {
+    patterns: Vec<String>,
+    // whether each of the patterns is anchored
+    anchored: Vec<bool>,
+    // post-match actions of each of the patterns
+    on_match: Vec<Vec<OnMatchAction>>,
+    aliases: BTreeMap<String, String>,
+    alias_filters: BTreeMap<String, String>,
+    // custom pattern definitions
+    definitions: BTreeMap<String, String>,
+    multiline: bool,
+    keep_empty: bool,
+}
+
+/// Compiled rule sets shared by identical `parse_groks` calls, e.g. across remap components
+/// and config reloads, with the most recently used ones at the front.
+static CACHE: Lazy<Mutex<VecDeque<CacheEntry>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(CACHE_CAPACITY)));
+
+type CacheEntry = (GroksConfig, Arc<CompiledGroks>);
+
+#[derive(Debug)]
+struct CompiledGroks {
+    id: usize,
+    grok_rules: Vec<GrokRule>,
+    prefilter: parse_grok::GrokPrefilter,
+    stats: parse_grok::GrokStats,
+    // when the stats were last emitted
+    emitted_at: Mutex<Option<Instant>>,
+}
+
+impl CompiledGroks {
+    /// Emits the match stats as internal metrics, unless they were emitted within the last
+    /// `STATS_INTERVAL`.
+    fn emit_stats(&self) {
+        let mut emitted_at = match self.emitted_at.try_lock() {
+            Ok(emitted_at) => emitted_at,
+            // another thread is emitting the same stats
+            Err(_) => return,
+        };
+        let now = Instant::now();
+        if emitted_at.map_or(false, |at| now.duration_since(at) < STATS_INTERVAL) {
+            return;
+        }
+        *emitted_at = Some(now);
+        drop(emitted_at);
+
+        let rule_set = self.id.to_string();
+        for i in 0..self.grok_rules.len() {
+            let rule = match self.stats.rule(i) {
+                Some(rule) => rule,
+                None => continue,
+            };
+            let labels = [("rule_set", rule_set.clone()), ("rule", i.to_string())];
+            metrics::absolute_counter!("grok_rule_attempts_total", rule.attempts, &labels);
+            metrics::absolute_counter!("grok_rule_matches_total", rule.matched, &labels);
+            if let Some(average) = rule.average_match_time() {
+                metrics::gauge!(
+                    "grok_rule_average_match_seconds",
+                    average.as_secs_f64(),
+                    &labels
+                );
+            }
+        }
+        metrics::absolute_counter!(
+            "grok_no_match_total",
+            self.stats.no_match(),
+            "rule_set" => rule_set
+        );
+    }
+}
+
+fn compile_groks(
+    config: GroksConfig,
+) -> std::result::Result<Arc<CompiledGroks>, parse_grok_rules::Diagnostic> {
+    let mut cache = CACHE.lock().expect("grok cache poisoned");
+    if let Some(i) = cache.iter().position(|(c, _)| *c == config) {
+        let entry = cache.remove(i).expect("cache entry exists");
+        let compiled = Arc::clone(&entry.1);
+        cache.push_front(entry);
+        return Ok(compiled);
+    }
+    drop(cache);
+
+    // we use a datadog library here because it is a superset of grok
+    let grok_rules = parse_grok_rules::parse_grok_rules_with_options(
+        &config.patterns,
+        config.aliases.clone(),
+        parse_grok_rules::CompileOptions {
+            definitions: config.definitions.clone(),
+            anchored: true,
+            anchored_overrides: config.anchored.iter().copied().enumerate().collect(),
+            multiline: config.multiline,
+            keep_empty: config.keep_empty,
+            on_match: config.on_match.iter().cloned().enumerate().collect(),
+            alias_filters: config.alias_filters.clone(),
+        },
+    )?;
+    let prefilter = parse_grok::GrokPrefilter::new(&grok_rules);
+    let stats = parse_grok::GrokStats::new(grok_rules.len());
+    let compiled = Arc::new(CompiledGroks {
+        id: NEXT_RULE_SET_ID.fetch_add(1, Ordering::Relaxed),
+        grok_rules,
+        prefilter,
+        stats,
+        emitted_at: Mutex::new(None),
+    });
+
+    let mut cache = CACHE.lock().expect("grok cache poisoned");
+    cache.truncate(CACHE_CAPACITY - 1);
+    cache.push_front((config, Arc::clone(&compiled)));
+    Ok(compiled)
+}
+
+/// Reads post-match actions of a pattern object in the order they are executed:
+/// renames, removals and then retained fields.
+fn on_match_actions(object: &BTreeMap<String, Value>) -> Option<Vec<OnMatchAction>> {
+    let path = |value: &Value| match value {
+        Value::Bytes(bytes) => String::from_utf8_lossy(bytes).parse::<LookupBuf>().ok(),
+        _ => None,
+    };
+    let paths = |value: &Value| match value {
+        Value::Array(values) => values.iter().map(path).collect::<Option<Vec<_>>>(),
+        _ => None,
+    };
+
+    let mut actions = vec![];
+    if let Some(renames) = object.get("rename") {
+        for (from, to) in renames.as_object()? {
+            actions.push(OnMatchAction::Rename(from.parse().ok()?, path(to)?));
+        }
+    }
+    if let Some(removed) = object.get("remove") {
+        actions.extend(paths(removed)?.into_iter().map(OnMatchAction::Remove));
+    }
+    if let Some(retained) = object.get("retain") {
+        actions.push(OnMatchAction::Retain(paths(retained)?));
+    }
+    Some(actions)
+}
+
 #[derive(Debug)]
 pub(crate) enum Error {
-    InvalidGrokPattern(datadog_grok::parse_grok_rules::Error),
+    InvalidGrokPattern(datadog_grok::parse_grok_rules::Diagnostic),
+    InvalidPatternFile(datadog_grok::parse_grok_rules::Error),
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Error::InvalidGrokPattern(err) => err.fmt(f),
+            Error::InvalidPatternFile(err) => err.fmt(f),
         }
     }
 }
@@ -36,6 +201,35 @@ impl DiagnosticMessage for Error {
                     Span::default(),
                 )]
             }
+            Error::InvalidPatternFile(err) => {
+                vec![Label::primary(
+                    format!("grok pattern file error: {}", err),
+                    Span::default(),
+                )]
+            }
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        match self {
+            Error::InvalidGrokPattern(err) => {
+                let mut notes = vec![];
+                if let (Some(span), Some(token)) = (&err.span, &err.token) {
+                    let location = match &err.alias {
+                        Some(alias) => format!("alias '{}'", alias),
+                        None => format!("pattern #{}", err.rule_index),
+                    };
+                    notes.push(Note::Hint(format!(
+                        "in {} at {}..{}: {}",
+                        location, span.start, span.end, token
+                    )));
+                }
+                if let Some(suggestion) = &err.suggestion {
+                    notes.push(Note::Hint(suggestion.clone()));
+                }
+                notes
+            }
+            Error::InvalidPatternFile(_) => vec![],
         }
     }
 }
@@ -65,6 +259,31 @@ impl Function for ParseGroks {
                 kind: kind::OBJECT,
                 required: false,
             },
+            Parameter {
+                keyword: "pattern_files",
+                kind: kind::ARRAY,
+                required: false,
+            },
+            Parameter {
+                keyword: "match_budget",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "anchored",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "multiline",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "keep_empty",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
         ]
     }
 
@@ -104,66 +323,212 @@ impl Function for ParseGroks {
     ) -> Compiled {
         let value = arguments.required("value");
 
-        let patterns = arguments
+        let anchored = match arguments.optional_value("anchored")? {
+            None => true,
+            Some(Value::Boolean(anchored)) => anchored,
+            Some(value) => {
+                return Err(vrl::function::Error::InvalidArgument {
+                    keyword: "anchored",
+                    value,
+                    error: "must be a boolean",
+                }
+                .into())
+            }
+        };
+
+        // a pattern is either a string or an object with the `pattern` field and optional
+        // `anchored`, `rename`, `remove` and `retain` fields
+        let mut on_match = vec![];
+        let (patterns, anchored): (Vec<String>, Vec<bool>) = arguments
             .required_array("patterns")?
             .into_iter()
             .map(|expr| {
-                let pattern = expr
+                let value = expr
                     .as_value()
                     .ok_or(vrl::function::Error::ExpectedStaticExpression {
                         keyword: "patterns",
                         expr,
-                    })?
-                    .try_bytes_utf8_lossy()
-                    .expect("grok pattern not bytes")
-                    .into_owned();
-                Ok(pattern)
+                    })?;
+                let invalid_pattern = |value| vrl::function::Error::InvalidArgument {
+                    keyword: "patterns",
+                    value,
+                    error: "must be a string or an object with the `pattern` and optional `anchored`, `rename`, `remove` and `retain` fields",
+                };
+                match value {
+                    Value::Bytes(bytes) => {
+                        on_match.push(vec![]);
+                        Ok((String::from_utf8_lossy(&bytes).into_owned(), anchored))
+                    }
+                    Value::Object(object) => {
+                        let pattern = match (object.get("pattern"), object.get("anchored")) {
+                            (Some(Value::Bytes(bytes)), None) => {
+                                (String::from_utf8_lossy(bytes).into_owned(), anchored)
+                            }
+                            (Some(Value::Bytes(bytes)), Some(Value::Boolean(anchored))) => {
+                                (String::from_utf8_lossy(bytes).into_owned(), *anchored)
+                            }
+                            _ => return Err(invalid_pattern(Value::Object(object))),
+                        };
+                        match on_match_actions(&object) {
+                            Some(actions) => on_match.push(actions),
+                            None => return Err(invalid_pattern(Value::Object(object))),
+                        }
+                        Ok(pattern)
+                    }
+                    value => Err(invalid_pattern(value)),
+                }
             })
-            .collect::<std::result::Result<Vec<String>, vrl::function::Error>>()?;
+            .collect::<std::result::Result<Vec<(String, bool)>, vrl::function::Error>>()?
+            .into_iter()
+            .unzip();
 
+        // an alias is either a string or an object with the `pattern` and optional `filters` fields
+        let mut alias_filters = BTreeMap::new();
         let aliases = arguments
             .optional_object("aliases")?
             .unwrap_or_default()
             .into_iter()
             .map(|(key, expr)| {
-                let alias = expr
+                let value = expr
                     .as_value()
                     .ok_or(vrl::function::Error::ExpectedStaticExpression {
                         keyword: "aliases",
                         expr,
-                    })
-                    .map(|e| {
-                        e.try_bytes_utf8_lossy()
-                            .expect("should be a string")
-                            .into_owned()
                     })?;
+                let invalid_alias = |value| vrl::function::Error::InvalidArgument {
+                    keyword: "aliases",
+                    value,
+                    error: "must be a string or an object with the `pattern` and optional `filters` fields",
+                };
+                let alias = match value {
+                    Value::Bytes(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+                    Value::Object(object) => match (object.get("pattern"), object.get("filters")) {
+                        (Some(Value::Bytes(bytes)), None) => {
+                            String::from_utf8_lossy(bytes).into_owned()
+                        }
+                        (Some(Value::Bytes(bytes)), Some(Value::Bytes(filters))) => {
+                            alias_filters
+                                .insert(key.clone(), String::from_utf8_lossy(filters).into_owned());
+                            String::from_utf8_lossy(bytes).into_owned()
+                        }
+                        _ => return Err(invalid_alias(Value::Object(object))),
+                    },
+                    value => return Err(invalid_alias(value)),
+                };
                 Ok((key, alias))
             })
             .collect::<std::result::Result<BTreeMap<String, String>, vrl::function::Error>>()?;
 
-        // we use a datadog library here because it is a superset of grok
-        let grok_rules = parse_grok_rules::parse_grok_rules(&patterns, aliases)
-            .map_err(|e| Box::new(Error::InvalidGrokPattern(e)) as Box<dyn DiagnosticMessage>)?;
+        let pattern_files = arguments
+            .optional_array("pattern_files")?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|expr| {
+                let value =
+                    expr.as_value()
+                        .ok_or(vrl::function::Error::ExpectedStaticExpression {
+                            keyword: "pattern_files",
+                            expr,
+                        })?;
+                match value {
+                    Value::Bytes(bytes) => Ok(String::from_utf8_lossy(&bytes).into_owned()),
+                    value => Err(vrl::function::Error::InvalidArgument {
+                        keyword: "pattern_files",
+                        value,
+                        error: "must be an array of strings",
+                    }),
+                }
+            })
+            .collect::<std::result::Result<Vec<String>, vrl::function::Error>>()?;
+
+        let definitions = parse_grok_rules::read_pattern_files(&pattern_files)
+            .map_err(|e| Box::new(Error::InvalidPatternFile(e)) as Box<dyn DiagnosticMessage>)?;
+
+        let match_budget = arguments
+            .optional_value("match_budget")?
+            .map(|value| match value {
+                Value::Integer(budget) if budget > 0 && budget <= i64::from(u32::MAX) => {
+                    Ok(budget as u32)
+                }
+                value => Err(vrl::function::Error::InvalidArgument {
+                    keyword: "match_budget",
+                    value,
+                    error: "must be a positive integer",
+                }),
+            })
+            .transpose()?;
+
+        let multiline = match arguments.optional_value("multiline")? {
+            None => false,
+            Some(Value::Boolean(multiline)) => multiline,
+            Some(value) => {
+                return Err(vrl::function::Error::InvalidArgument {
+                    keyword: "multiline",
+                    value,
+                    error: "must be a boolean",
+                }
+                .into())
+            }
+        };
+
+        let keep_empty = match arguments.optional_value("keep_empty")? {
+            None => false,
+            Some(Value::Boolean(keep_empty)) => keep_empty,
+            Some(value) => {
+                return Err(vrl::function::Error::InvalidArgument {
+                    keyword: "keep_empty",
+                    value,
+                    error: "must be a boolean",
+                }
+                .into())
+            }
+        };
+
+        let compiled = compile_groks(GroksConfig {
+            patterns,
+            anchored,
+            on_match,
+            aliases,
+            alias_filters,
+            definitions,
+            multiline,
+            keep_empty,
+        })
+        .map_err(|e| Box::new(Error::InvalidGrokPattern(e)) as Box<dyn DiagnosticMessage>)?;
 
-        Ok(ParseGroksFn { value, grok_rules }.as_expr())
+        Ok(ParseGroksFn {
+            value,
+            compiled,
+            match_budget,
+        }
+        .as_expr())
     }
 }
 
 #[derive(Clone, Debug)]
 struct ParseGroksFn {
     value: Box<dyn Expression>,
-    grok_rules: Vec<GrokRule>,
+    compiled: Arc<CompiledGroks>,
+    match_budget: Option<u32>,
 }
 
 impl FunctionExpression for ParseGroksFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
-        let bytes = value.try_bytes_utf8_lossy()?;
+        let bytes = value.try_bytes()?;
 
-        let v = parse_grok::parse_grok(bytes.as_ref(), &self.grok_rules)
-            .map_err(|err| format!("unable to parse grok: {}", err))?;
+        let v = parse_grok::parse_grok_bytes(
+            bytes.as_ref(),
+            &self.compiled.grok_rules,
+            parse_grok::ParseOptions {
+                prefilter: Some(&self.compiled.prefilter),
+                match_budget: self.match_budget,
+                stats: Some(&self.compiled.stats),
+            },
+        );
+        self.compiled.emit_stats();
 
-        Ok(v)
+        v.map_err(|err| format!("unable to parse grok: {}", err).into())
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -174,10 +539,70 @@ impl FunctionExpression for ParseGroksFn {
 #[cfg(test)]
 mod test {
     use ::value::Value;
-    use vector_common::btreemap;
+    use vector_common::{btreemap, TimeZone};
 
     use super::*;
 
+    #[test]
+    fn shares_compiled_rule_sets() {
+        let compile = |patterns: &[&str]| {
+            compile_groks(GroksConfig {
+                patterns: patterns.iter().map(ToString::to_string).collect(),
+                anchored: vec![true; patterns.len()],
+                on_match: vec![vec![]; patterns.len()],
+                aliases: BTreeMap::new(),
+                alias_filters: BTreeMap::new(),
+                definitions: BTreeMap::new(),
+                multiline: false,
+                keep_empty: false,
+            })
+            .unwrap()
+        };
+
+        let first = compile(&["%{word:first} %{integer:second}"]);
+        let second = compile(&["%{word:first} %{integer:second}"]);
+        let other = compile(&["%{integer:first} %{word:second}"]);
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(!Arc::ptr_eq(&first, &other));
+    }
+
+    #[test]
+    fn records_rule_stats() {
+        let compiled = compile_groks(GroksConfig {
+            patterns: vec!["%{integer:code}".to_owned(), "%{word:name}".to_owned()],
+            anchored: vec![true; 2],
+            on_match: vec![vec![]; 2],
+            aliases: BTreeMap::new(),
+            alias_filters: BTreeMap::new(),
+            definitions: BTreeMap::new(),
+            multiline: false,
+            keep_empty: false,
+        })
+        .unwrap();
+
+        let tz = TimeZone::default();
+        for (value, matched) in [("200", true), ("ok", true), ("not ok", false)] {
+            let func = ParseGroksFn {
+                value: expr!(value),
+                compiled: Arc::clone(&compiled),
+                match_budget: None,
+            };
+            let mut object = value!({});
+            let mut runtime_state = vrl::state::Runtime::default();
+            let mut ctx = Context::new(&mut object, &mut runtime_state, &tz);
+            assert_eq!(func.resolve(&mut ctx).is_ok(), matched);
+        }
+
+        let integer = compiled.stats.rule(0).unwrap();
+        // the prefilter skips the rules, which can't match a value
+        assert_eq!((integer.attempts, integer.matched), (1, 1));
+        let word = compiled.stats.rule(1).unwrap();
+        assert_eq!((word.attempts, word.matched), (1, 1));
+        assert_eq!(compiled.stats.no_match(), 1);
+        assert!(compiled.emitted_at.lock().unwrap().is_some());
+    }
+
     test_function![
         parse_grok => ParseGroks;
 
@@ -213,6 +638,115 @@ mod test {
             tdef: TypeDef::object(Collection::any()).fallible(),
         }
 
+        missing_pattern_file {
+            args: func_args![ value: "foo",
+                              patterns: vec!["%{NOG}"],
+                              pattern_files: vec!["/nonexistent/grok/patterns"]],
+            want: Err("failed to read pattern file '/nonexistent/grok/patterns': No such file or directory (os error 2)"),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        invalid_pattern_file {
+            args: func_args![ value: "foo",
+                              patterns: vec!["%{NOG}"],
+                              pattern_files: Value::Array(vec![Value::Integer(1)])],
+            want: Err("invalid argument"),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        unanchored {
+            args: func_args![
+                value: "[info] user=alice logged in",
+                patterns: Value::Array(vec![
+                    value!({"pattern": "%{word:level} user=%{word:user}", "anchored": true}),
+                    "user=%{word:user}".into(),
+                ]),
+                anchored: false
+            ],
+            want: Ok(Value::from(btreemap! {
+                "user" => "alice",
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        keep_empty {
+            args: func_args![
+                value: "alice::200",
+                patterns: vec!["%{word:user}:%{data:message}:%{integer:status}"],
+                keep_empty: true
+            ],
+            want: Ok(Value::from(btreemap! {
+                "user" => "alice",
+                "message" => "",
+                "status" => 200,
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        on_match {
+            args: func_args![
+                value: "alice 200 hello",
+                patterns: Value::Array(vec![value!({
+                    "pattern": "%{word:user} %{integer:tmp.status} %{data:message}",
+                    "rename": {"tmp.status": "http.status_code"},
+                    "remove": ["tmp"]
+                })])
+            ],
+            want: Ok(Value::from(btreemap! {
+                "user" => "alice",
+                "message" => "hello",
+                "http" => btreemap! { "status_code" => 200 },
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        on_match_retain {
+            args: func_args![
+                value: "alice 200 hello",
+                patterns: Value::Array(vec![value!({
+                    "pattern": "%{word:user} %{integer:status} %{data:message}",
+                    "retain": ["user", "status"]
+                })])
+            ],
+            want: Ok(Value::from(btreemap! {
+                "user" => "alice",
+                "status" => 200,
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        alias_filters {
+            args: func_args![
+                value: "1.5 ALICE",
+                patterns: vec!["%{_duration:duration} %{_user:user}"],
+                aliases: value!({
+                    "_duration": {"pattern": "%{number}", "filters": "scale(1000)"},
+                    "_user": "%{word}"
+                })
+            ],
+            want: Ok(Value::from(btreemap! {
+                "duration" => 1500,
+                "user" => "ALICE",
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        multiline {
+            args: func_args![
+                value: "ERROR oops\n\tat App.main(App.java:10)",
+                patterns: vec!["%{word:level} %{data:message}\n%{data:error.stack}"],
+                multiline: true
+            ],
+            want: Ok(Value::from(btreemap! {
+                "level" => "ERROR",
+                "message" => "oops",
+                "error" => btreemap! {
+                    "stack" => "\tat App.main(App.java:10)",
+                },
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
         parsed2 {
             args: func_args![ value: "2020-10-02T23:22:12.223222Z",
                               patterns: vec!["(%{TIMESTAMP_ISO8601:timestamp}|%{LOGLEVEL:level})"]],
@@ -315,5 +849,15 @@ mod test {
             })),
             tdef: TypeDef::object(Collection::any()).fallible(),
         }
+
+        invalid_utf8 {
+            args: func_args![ value: Value::Bytes(Bytes::from_static(b"app: \xff\xfe")),
+                              patterns: vec!["%{word:app}: %{data:message}"]],
+            want: Ok(Value::from(btreemap! {
+                "app" => "app",
+                "message" => Value::Bytes(Bytes::from_static(b"\xff\xfe")),
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
     ];
 }
@@ -0,0 +1,269 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseLeef;
+
+impl Function for ParseLeef {
+    fn identifier(&self) -> &'static str {
+        "parse_leef"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "LEEF 1.0",
+                source: r#"parse_leef!("LEEF:1.0|Microsoft|MSExchange|4.0 SP1|15345|src=10.50.1.1\tdst=2.10.20.20\tspt=1200")"#,
+                result: Ok(
+                    r#"{"leefVersion":"1.0","deviceVendor":"Microsoft","deviceProduct":"MSExchange","deviceVersion":"4.0 SP1","eventId":"15345","src":"10.50.1.1","dst":"2.10.20.20","spt":"1200"}"#,
+                ),
+            },
+            Example {
+                title: "LEEF 2.0 with a custom delimiter",
+                source: r#"parse_leef!("LEEF:2.0|Lancope|StealthWatch|1.0|41|^|src=10.0.1.8^dst=10.0.0.5^sev=5")"#,
+                result: Ok(
+                    r#"{"leefVersion":"2.0","deviceVendor":"Lancope","deviceProduct":"StealthWatch","deviceVersion":"1.0","eventId":"41","src":"10.0.1.8","dst":"10.0.0.5","sev":"5"}"#,
+                ),
+            },
+            Example {
+                title: "with syslog prefix",
+                source: r#"parse_leef!("Jan 18 11:07:53 host LEEF:2.0|Lancope|StealthWatch|1.0|41|x7C|src=10.0.1.8|dst=10.0.0.5")"#,
+                result: Ok(
+                    r#"{"leefVersion":"2.0","deviceVendor":"Lancope","deviceProduct":"StealthWatch","deviceVersion":"1.0","eventId":"41","src":"10.0.1.8","dst":"10.0.0.5"}"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParseLeefFn { value }.as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct ParseLeefFn {
+    pub(crate) value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseLeefFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let bytes = self.value.resolve(ctx)?;
+        let bytes = bytes.try_bytes_utf8_lossy()?;
+
+        parse(&bytes).map(|fields| fields.into_iter().collect())
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        type_def()
+    }
+}
+
+const HEADERS: [&str; 5] = [
+    "leefVersion",
+    "deviceVendor",
+    "deviceProduct",
+    "deviceVersion",
+    "eventId",
+];
+
+fn parse(input: &str) -> Result<Vec<(String, Value)>> {
+    // ignore everything up to the header, e.g. a syslog prefix
+    let start = input.find("LEEF:").ok_or("missing LEEF header")?;
+    let input = &input[start + "LEEF:".len()..];
+
+    let version = input.split('|').next().unwrap_or_default();
+    let (header, delimiter, attributes) = match version {
+        "1.0" | "1" => {
+            let mut parts = input.splitn(HEADERS.len() + 1, '|');
+            let header = parts.by_ref().take(HEADERS.len()).collect::<Vec<_>>();
+            // LEEF 1.0 attributes are always separated by tabs
+            (header, '\t', parts.next())
+        }
+        "2.0" | "2" => {
+            let mut parts = input.splitn(HEADERS.len() + 2, '|');
+            let header = parts.by_ref().take(HEADERS.len()).collect::<Vec<_>>();
+            let delimiter = parse_delimiter(parts.next().unwrap_or_default())?;
+            (header, delimiter, parts.next())
+        }
+        version => return Err(format!("unsupported LEEF version: {}", version).into()),
+    };
+    if header.len() < HEADERS.len() {
+        return Err("incomplete LEEF header".into());
+    }
+
+    let mut fields = HEADERS
+        .iter()
+        .zip(header)
+        .map(|(key, value)| (key.to_string(), Value::from(value)))
+        .collect::<Vec<_>>();
+    for attribute in attributes
+        .unwrap_or_default()
+        .trim_end_matches(['\r', '\n'])
+        .split(delimiter)
+        .filter(|attribute| !attribute.trim().is_empty())
+    {
+        // values may contain `=`, keys can't
+        let (key, value) = attribute
+            .split_once('=')
+            .ok_or_else(|| format!("invalid LEEF attribute: {}", attribute))?;
+        fields.push((key.trim().to_string(), Value::from(value)));
+    }
+
+    Ok(fields)
+}
+
+/// Parses a LEEF 2.0 delimiter, either a single character or a hex code(e.g. `x09` or `0x09`),
+/// defaulting to a tab.
+fn parse_delimiter(delimiter: &str) -> Result<char> {
+    let mut chars = delimiter.chars();
+    match (chars.next(), chars.next()) {
+        (None, _) => Ok('\t'),
+        (Some(c), None) => Ok(c),
+        _ => delimiter
+            .strip_prefix("0x")
+            .or_else(|| delimiter.strip_prefix('x'))
+            .or_else(|| delimiter.strip_prefix('X'))
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .and_then(char::from_u32)
+            .ok_or_else(|| format!("invalid LEEF delimiter: {}", delimiter).into()),
+    }
+}
+
+fn type_def() -> TypeDef {
+    TypeDef::object(Collection::from_parts(
+        HEADERS
+            .iter()
+            .map(|header| (Field::from(*header), Kind::bytes()))
+            .collect(),
+        Kind::bytes(),
+    ))
+    .fallible()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_delimiter() {
+        assert_eq!(parse_delimiter("").unwrap(), '\t');
+        assert_eq!(parse_delimiter("^").unwrap(), '^');
+        assert_eq!(parse_delimiter("x09").unwrap(), '\t');
+        assert_eq!(parse_delimiter("0x7C").unwrap(), '|');
+        assert!(parse_delimiter("tab").is_err());
+    }
+
+    test_function![
+        parse_leef => ParseLeef;
+
+        leef_1 {
+            args: func_args! [
+                value: "LEEF:1.0|Microsoft|MSExchange|4.0 SP1|15345|src=10.50.1.1\tdst=2.10.20.20\tspt=1200\t",
+            ],
+            want: Ok(value!({
+                "leefVersion": "1.0",
+                "deviceVendor": "Microsoft",
+                "deviceProduct": "MSExchange",
+                "deviceVersion": "4.0 SP1",
+                "eventId": "15345",
+                "src": "10.50.1.1",
+                "dst": "2.10.20.20",
+                "spt": "1200"
+            })),
+            tdef: type_def(),
+        }
+
+        leef_2_default_delimiter {
+            args: func_args! [
+                value: "LEEF:2.0|Lancope|StealthWatch|1.0|41||src=10.0.1.8\tmsg=a=b c",
+            ],
+            want: Ok(value!({
+                "leefVersion": "2.0",
+                "deviceVendor": "Lancope",
+                "deviceProduct": "StealthWatch",
+                "deviceVersion": "1.0",
+                "eventId": "41",
+                "src": "10.0.1.8",
+                "msg": "a=b c"
+            })),
+            tdef: type_def(),
+        }
+
+        leef_2_hex_delimiter {
+            args: func_args! [
+                value: "<13>Jan 18 11:07:53 host LEEF:2.0|Lancope|StealthWatch|1.0|41|x5E|src=10.0.1.8^dst=10.0.0.5",
+            ],
+            want: Ok(value!({
+                "leefVersion": "2.0",
+                "deviceVendor": "Lancope",
+                "deviceProduct": "StealthWatch",
+                "deviceVersion": "1.0",
+                "eventId": "41",
+                "src": "10.0.1.8",
+                "dst": "10.0.0.5"
+            })),
+            tdef: type_def(),
+        }
+
+        only_header {
+            args: func_args! [
+                value: "LEEF:1.0|Microsoft|MSExchange|4.0 SP1|15345|",
+            ],
+            want: Ok(value!({
+                "leefVersion": "1.0",
+                "deviceVendor": "Microsoft",
+                "deviceProduct": "MSExchange",
+                "deviceVersion": "4.0 SP1",
+                "eventId": "15345"
+            })),
+            tdef: type_def(),
+        }
+
+        missing_header {
+            args: func_args! [
+                value: "CEF:0|Microsoft|MSExchange",
+            ],
+            want: Err("missing LEEF header"),
+            tdef: type_def(),
+        }
+
+        incomplete_header {
+            args: func_args! [
+                value: "LEEF:1.0|Microsoft|MSExchange",
+            ],
+            want: Err("incomplete LEEF header"),
+            tdef: type_def(),
+        }
+
+        unsupported_version {
+            args: func_args! [
+                value: "LEEF:3.0|Microsoft|MSExchange|4.0 SP1|15345|",
+            ],
+            want: Err("unsupported LEEF version: 3.0"),
+            tdef: type_def(),
+        }
+
+        invalid_attribute {
+            args: func_args! [
+                value: "LEEF:1.0|Microsoft|MSExchange|4.0 SP1|15345|src",
+            ],
+            want: Err("invalid LEEF attribute: src"),
+            tdef: type_def(),
+        }
+    ];
+}
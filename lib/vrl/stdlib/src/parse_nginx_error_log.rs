@@ -0,0 +1,204 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::log_util;
+
+fn parse_nginx_error_log(bytes: Value, timestamp_format: Option<Value>, ctx: &Context) -> Resolved {
+    let message = bytes.try_bytes_utf8_lossy()?;
+    let timestamp_format = match timestamp_format {
+        None => "%Y/%m/%d %H:%M:%S".to_owned(),
+        Some(timestamp_format) => timestamp_format.try_bytes_utf8_lossy()?.to_string(),
+    };
+    let regex = &*log_util::REGEX_NGINX_ERROR_LOG;
+    let captures = regex
+        .captures(&message)
+        .ok_or("failed parsing nginx error log line")?;
+
+    log_util::log_fields(regex, &captures, &timestamp_format, ctx.timezone()).map_err(Into::into)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseNginxErrorLog;
+
+impl Function for ParseNginxErrorLog {
+    fn identifier(&self) -> &'static str {
+        "parse_nginx_error_log"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "timestamp_format",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let timestamp_format = arguments.optional("timestamp_format");
+
+        Ok(ParseNginxErrorLogFn {
+            value,
+            timestamp_format,
+        }
+        .as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "parse nginx error log",
+            source: r#"encode_json(parse_nginx_error_log!(s'2021/04/01 13:02:31 [error] 31#31: *1 open() "/usr/share/nginx/html/not-found" failed (2: No such file or directory), client: 172.17.0.1, server: localhost, request: "POST /not-found HTTP/1.1", host: "localhost:8081"'))"#,
+            result: Ok(
+                r#"s'{"cid":1,"client":"172.17.0.1","host":"localhost:8081","message":"open() \"/usr/share/nginx/html/not-found\" failed (2: No such file or directory)","pid":31,"request":"POST /not-found HTTP/1.1","server":"localhost","severity":"error","tid":31,"timestamp":"2021-04-01T13:02:31Z"}'"#,
+            ),
+        }]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseNginxErrorLogFn {
+    value: Box<dyn Expression>,
+    timestamp_format: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for ParseNginxErrorLogFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let bytes = self.value.resolve(ctx)?;
+        let timestamp_format = self
+            .timestamp_format
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        parse_nginx_error_log(bytes, timestamp_format, ctx)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("timestamp".into(), Kind::timestamp()),
+        ("severity".into(), Kind::bytes()),
+        ("pid".into(), Kind::integer()),
+        ("tid".into(), Kind::integer()),
+        ("cid".into(), Kind::integer().or_null()),
+        ("message".into(), Kind::bytes()),
+        ("excess".into(), Kind::float().or_null()),
+        ("zone".into(), Kind::bytes().or_null()),
+        ("client".into(), Kind::bytes().or_null()),
+        ("server".into(), Kind::bytes().or_null()),
+        ("request".into(), Kind::bytes().or_null()),
+        ("upstream".into(), Kind::bytes().or_null()),
+        ("host".into(), Kind::bytes().or_null()),
+        ("referer".into(), Kind::bytes().or_null()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::prelude::*;
+    use vector_common::btreemap;
+
+    use super::*;
+
+    test_function![
+        parse_nginx_error_log => ParseNginxErrorLog;
+
+        error_line_valid {
+            args: func_args![
+                value: r#"2021/04/01 13:02:31 [error] 31#31: *1 open() "/usr/share/nginx/html/not-found" failed (2: No such file or directory), client: 172.17.0.1, server: localhost, request: "POST /not-found HTTP/1.1", host: "localhost:8081", referrer: "http://localhost:8081/""#,
+            ],
+            want: Ok(btreemap! {
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2021-04-01T13:02:31Z").unwrap().into()),
+                "severity" => "error",
+                "pid" => 31,
+                "tid" => 31,
+                "cid" => 1,
+                "message" => "open() \"/usr/share/nginx/html/not-found\" failed (2: No such file or directory)",
+                "client" => "172.17.0.1",
+                "server" => "localhost",
+                "request" => "POST /not-found HTTP/1.1",
+                "host" => "localhost:8081",
+                "referer" => "http://localhost:8081/",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+            tz: vector_common::TimeZone::Named(chrono_tz::Tz::UTC),
+        }
+
+        message_with_commas {
+            args: func_args![
+                value: r#"2022/04/15 08:16:13 [error] 7164#7164: *20 FastCGI sent in stderr: "PHP message: PHP Warning:  Undefined variable $a, $b in /var/www/index.php on line 3" while reading response header from upstream, client: 10.244.0.0, server: test.local, request: "GET / HTTP/2.0", upstream: "fastcgi://unix:/run/php/php-fpm.sock:""#,
+            ],
+            want: Ok(btreemap! {
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2022-04-15T08:16:13Z").unwrap().into()),
+                "severity" => "error",
+                "pid" => 7164,
+                "tid" => 7164,
+                "cid" => 20,
+                "message" => r#"FastCGI sent in stderr: "PHP message: PHP Warning:  Undefined variable $a, $b in /var/www/index.php on line 3" while reading response header from upstream"#,
+                "client" => "10.244.0.0",
+                "server" => "test.local",
+                "request" => "GET / HTTP/2.0",
+                "upstream" => "fastcgi://unix:/run/php/php-fpm.sock:",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+            tz: vector_common::TimeZone::Named(chrono_tz::Tz::UTC),
+        }
+
+        without_connection {
+            args: func_args![
+                value: r#"2021/06/17 19:25:59 [notice] 133309#133309: signal process started"#,
+            ],
+            want: Ok(btreemap! {
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2021-06-17T19:25:59Z").unwrap().into()),
+                "severity" => "notice",
+                "pid" => 133_309,
+                "tid" => 133_309,
+                "message" => "signal process started",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+            tz: vector_common::TimeZone::Named(chrono_tz::Tz::UTC),
+        }
+
+        with_timezone {
+            args: func_args![
+                value: r#"2021/06/17 19:25:59 [notice] 133309#133309: signal process started"#,
+            ],
+            want: Ok(btreemap! {
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2021-06-17T17:25:59Z").unwrap().into()),
+                "severity" => "notice",
+                "pid" => 133_309,
+                "tid" => 133_309,
+                "message" => "signal process started",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+            tz: vector_common::TimeZone::Named(chrono_tz::Europe::Paris),
+        }
+
+        access_log_line {
+            args: func_args![
+                value: r#"172.17.0.1 - - [31/Mar/2021:12:04:07 +0000] "GET / HTTP/1.1" 200 612 "-" "curl/7.75.0" "-""#,
+            ],
+            want: Err("failed parsing nginx error log line"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+            tz: vector_common::TimeZone::Named(chrono_tz::Tz::UTC),
+        }
+    ];
+}
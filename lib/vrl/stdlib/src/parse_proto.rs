@@ -0,0 +1,273 @@
+use std::{collections::BTreeMap, fmt, fs};
+
+use ::value::Value;
+use prost_reflect::{
+    DescriptorPool, DynamicMessage, FieldDescriptor, MapKey, MessageDescriptor, ReflectMessage,
+};
+use vrl::{
+    diagnostic::{Label, Span},
+    prelude::*,
+};
+
+fn parse_proto(descriptor: &MessageDescriptor, value: Value) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let message = DynamicMessage::decode(descriptor.clone(), bytes)
+        .map_err(|error| format!("unable to parse protobuf message: {}", error))?;
+
+    message_to_value(&message)
+}
+
+/// Converts a decoded message to an object, omitting fields that aren't set, which in proto3
+/// includes fields set to their default value.
+fn message_to_value(message: &DynamicMessage) -> Resolved {
+    message
+        .descriptor()
+        .fields()
+        .filter(|field| message.has_field(field))
+        .map(|field| {
+            let value = message.get_field(&field);
+            Ok((field.name().to_owned(), proto_to_value(&value, &field)?))
+        })
+        .collect::<Result<BTreeMap<_, _>>>()
+        .map(Value::from)
+}
+
+fn proto_to_value(value: &prost_reflect::Value, field: &FieldDescriptor) -> Resolved {
+    Ok(match value {
+        prost_reflect::Value::Bool(v) => Value::from(*v),
+        prost_reflect::Value::I32(v) => Value::from(*v),
+        prost_reflect::Value::I64(v) => Value::from(*v),
+        prost_reflect::Value::U32(v) => Value::from(*v),
+        prost_reflect::Value::U64(v) => {
+            Value::from(i64::try_from(*v).map_err(|_| format!("integer out of range: {}", v))?)
+        }
+        prost_reflect::Value::F32(v) => Value::from_f64_or_zero(f64::from(*v)),
+        prost_reflect::Value::F64(v) => Value::from_f64_or_zero(*v),
+        prost_reflect::Value::String(v) => Value::from(v.as_str()),
+        prost_reflect::Value::Bytes(v) => Value::from(v.clone()),
+        // enums are converted to the name of their value, unless the number is unknown
+        prost_reflect::Value::EnumNumber(v) => field
+            .kind()
+            .as_enum()
+            .and_then(|descriptor| descriptor.get_value(*v))
+            .map_or_else(
+                || Value::from(*v),
+                |enum_value| Value::from(enum_value.name()),
+            ),
+        prost_reflect::Value::Message(v) => message_to_value(v)?,
+        prost_reflect::Value::List(v) => v
+            .iter()
+            .map(|value| proto_to_value(value, field))
+            .collect::<Result<Vec<_>>>()?
+            .into(),
+        prost_reflect::Value::Map(v) => {
+            let value_field = field
+                .kind()
+                .as_message()
+                .map(MessageDescriptor::map_entry_value_field)
+                .ok_or("map field without map entry type")?;
+            v.iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        MapKey::Bool(k) => k.to_string(),
+                        MapKey::I32(k) => k.to_string(),
+                        MapKey::I64(k) => k.to_string(),
+                        MapKey::U32(k) => k.to_string(),
+                        MapKey::U64(k) => k.to_string(),
+                        MapKey::String(k) => k.clone(),
+                    };
+                    Ok((key, proto_to_value(value, &value_field)?))
+                })
+                .collect::<Result<BTreeMap<_, _>>>()?
+                .into()
+        }
+    })
+}
+
+#[derive(Debug)]
+pub(crate) enum Error {
+    InvalidDescriptorFile(String, String),
+    UnknownMessageType(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidDescriptorFile(path, err) => {
+                write!(f, "failed to read descriptor file '{}': {}", path, err)
+            }
+            Error::UnknownMessageType(message_type) => {
+                write!(
+                    f,
+                    "message type '{}' not found in descriptor file",
+                    message_type
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        109
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        vec![Label::primary(self.to_string(), Span::default())]
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseProto;
+
+impl Function for ParseProto {
+    fn identifier(&self) -> &'static str {
+        "parse_proto"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "desc_file",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "message_type",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        // the descriptor file has to exist, when the example is compiled
+        &[]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        let desc_file = arguments
+            .required_literal("desc_file")?
+            .to_value()
+            .try_bytes_utf8_lossy()
+            .expect("descriptor file path not bytes")
+            .into_owned();
+        let message_type = arguments
+            .required_literal("message_type")?
+            .to_value()
+            .try_bytes_utf8_lossy()
+            .expect("message type not bytes")
+            .into_owned();
+
+        // the descriptor set is read once, when the program is compiled
+        let pool = fs::read(&desc_file)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| DescriptorPool::decode(bytes.as_slice()).map_err(|e| e.to_string()))
+            .map_err(|e| {
+                Box::new(Error::InvalidDescriptorFile(desc_file, e)) as Box<dyn DiagnosticMessage>
+            })?;
+        let descriptor = pool.get_message_by_name(&message_type).ok_or_else(|| {
+            Box::new(Error::UnknownMessageType(message_type)) as Box<dyn DiagnosticMessage>
+        })?;
+
+        Ok(ParseProtoFn { value, descriptor }.as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ParseProtoFn {
+    value: Box<dyn Expression>,
+    descriptor: MessageDescriptor,
+}
+
+impl FunctionExpression for ParseProtoFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_proto(&self.descriptor, value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(Collection::any()).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DESC_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/protobuf/test.desc");
+
+    fn person() -> Bytes {
+        // encoded from `tests/data/protobuf/test.proto`
+        Bytes::from_static(b"\x0a\x07someone\x10\xd2\x09\x1a\x13someone@example.com\x22\x0b\x0a\x07123-456\x10\x01\x22\x05\x0a\x03789*\x0c\x0a\x03key\x12\x05value1\x00\x00\x00\x00\x00\x00\xf8?")
+    }
+
+    test_function![
+        parse_proto => ParseProto;
+
+        parses_message {
+            args: func_args![
+                value: person(),
+                desc_file: DESC_FILE,
+                message_type: "test.Person",
+            ],
+            want: Ok(value!({
+                "name": "someone",
+                "id": 1234,
+                "email": "someone@example.com",
+                "phones": [
+                    {"number": "123-456", "type": "HOME"},
+                    {"number": "789"}
+                ],
+                "data": {"key": "value"},
+                "score": 1.5
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        invalid_message {
+            args: func_args![
+                value: "not protobuf",
+                desc_file: DESC_FILE,
+                message_type: "test.Person",
+            ],
+            want: Err("unable to parse protobuf message: failed to decode Protobuf message: invalid wire type value: 6"),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        unknown_message_type {
+            args: func_args![
+                value: person(),
+                desc_file: DESC_FILE,
+                message_type: "test.Unknown",
+            ],
+            want: Err("message type 'test.Unknown' not found in descriptor file"),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        missing_descriptor_file {
+            args: func_args![
+                value: person(),
+                desc_file: "/nonexistent/test.desc",
+                message_type: "test.Person",
+            ],
+            want: Err("failed to read descriptor file '/nonexistent/test.desc': No such file or directory (os error 2)"),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+    ];
+}
@@ -1,11 +1,24 @@
 use ::value::Value;
+use chrono::format::strftime::StrftimeItems;
 use vector_common::conversion::Conversion;
 use vrl::prelude::*;
 
-fn parse_timestamp(value: Value, format: Value, ctx: &Context) -> Resolved {
+use crate::locale_util::{locale_names, locales};
+
+fn parse_timestamp(value: Value, format: Value, locale: Option<&Bytes>, ctx: &Context) -> Resolved {
     match value {
         Value::Bytes(v) => {
             let format = format.try_bytes_utf8_lossy()?;
+            // chrono only parses English names, so that localized names are translated first
+            let v = match locale {
+                Some(locale) => {
+                    let items = StrftimeItems::new(&format).collect::<Vec<_>>();
+                    locale_names(locale)
+                        .translate(&String::from_utf8_lossy(&v), &items)
+                        .into()
+                }
+                None => v,
+            };
             Conversion::parse(format!("timestamp|{}", format), *ctx.timezone())
                 .map_err(|e| e.to_string())?
                 .convert(v)
@@ -25,11 +38,18 @@ impl Function for ParseTimestamp {
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "valid",
-            source: r#"parse_timestamp!("11-Feb-2021 16:00 +00:00", format: "%v %R %z")"#,
-            result: Ok("t'2021-02-11T16:00:00Z'"),
-        }]
+        &[
+            Example {
+                title: "valid",
+                source: r#"parse_timestamp!("11-Feb-2021 16:00 +00:00", format: "%v %R %z")"#,
+                result: Ok("t'2021-02-11T16:00:00Z'"),
+            },
+            Example {
+                title: "localized names",
+                source: r#"parse_timestamp!("lundi 13 déc. 2021 16:00 +00:00", format: "%A %d %b %Y %R %z", locale: "fr")"#,
+                result: Ok("t'2021-12-13T16:00:00Z'"),
+            },
+        ]
     }
 
     fn compile(
@@ -40,8 +60,16 @@ impl Function for ParseTimestamp {
     ) -> Compiled {
         let value = arguments.required("value");
         let format = arguments.required("format");
-
-        Ok(ParseTimestampFn { value, format }.as_expr())
+        let locale = arguments
+            .optional_enum("locale", &locales())?
+            .map(|locale| locale.try_bytes().expect("locale not bytes"));
+
+        Ok(ParseTimestampFn {
+            value,
+            format,
+            locale,
+        }
+        .as_expr())
     }
 
     fn parameters(&self) -> &'static [Parameter] {
@@ -56,6 +84,11 @@ impl Function for ParseTimestamp {
                 kind: kind::BYTES,
                 required: true,
             },
+            Parameter {
+                keyword: "locale",
+                kind: kind::BYTES,
+                required: false,
+            },
         ]
     }
 }
@@ -64,13 +97,14 @@ impl Function for ParseTimestamp {
 struct ParseTimestampFn {
     value: Box<dyn Expression>,
     format: Box<dyn Expression>,
+    locale: Option<Bytes>,
 }
 
 impl FunctionExpression for ParseTimestampFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
         let format = self.format.resolve(ctx)?;
-        parse_timestamp(value, format, ctx)
+        parse_timestamp(value, format, self.locale.as_ref(), ctx)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -130,5 +164,35 @@ mod tests {
             tdef: TypeDef::timestamp().fallible(),
             tz: vector_common::TimeZone::Named(chrono_tz::Europe::Paris),
         }
+
+        parse_localized_names {
+            args: func_args![
+                value: "Mittwoch, 16. Okt. 2019 12:00:00 +0000",
+                format: "%A, %d. %b %Y %H:%M:%S %z",
+                locale: "de"
+            ],
+            want: Ok(value!(
+                DateTime::parse_from_rfc2822("Wed, 16 Oct 2019 12:00:00 +0000")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: vector_common::TimeZone::default(),
+        }
+
+        parse_ambiguous_localized_names {
+            args: func_args![
+                value: "mar, 15 mar 2022 12:00:00 +0000",
+                format: "%a, %d %b %Y %H:%M:%S %z",
+                locale: "es"
+            ],
+            want: Ok(value!(
+                DateTime::parse_from_rfc2822("Tue, 15 Mar 2022 12:00:00 +0000")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: vector_common::TimeZone::default(),
+        }
     ];
 }
@@ -1,11 +1,12 @@
 use std::{
     borrow::Cow,
     convert::{TryFrom, TryInto},
-    str::FromStr,
 };
 
 use ::value::Value;
 use once_cell::sync::Lazy;
+use regex::Captures;
+use sha_2::{Digest, Sha256};
 use vrl::prelude::*;
 
 // https://www.oreilly.com/library/view/regular-expressions-cookbook/9781449327453/ch04s12.html
@@ -20,6 +21,24 @@ static US_SOCIAL_SECURITY_NUMBER: Lazy<regex::Regex> = Lazy::new(|| {
     "#).unwrap()
 });
 
+static EMAIL_ADDRESS: Lazy<regex::Regex> = Lazy::new(|| {
+    regex::Regex::new(r#"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}"#)
+        .unwrap()
+});
+
+// 13 to 19 digits, optionally grouped by spaces or dashes, which are validated with the Luhn
+// algorithm before they are redacted.
+static CREDIT_CARD_NUMBER: Lazy<regex::Regex> =
+    Lazy::new(|| regex::Regex::new(r#"\b(?:[0-9][ -]?){12,18}[0-9]\b"#).unwrap());
+
+static IPV4_ADDRESS: Lazy<regex::Regex> = Lazy::new(|| {
+    regex::Regex::new(
+    r#"(?x)                                                               # Ignore whitespace and comments in the regex expression.
+    \b(?:(?:25[0-5]|2[0-4][0-9]|1[0-9]{2}|[1-9]?[0-9])\.){3}              # First three octets: 0-255 followed by a dot
+    (?:25[0-5]|2[0-4][0-9]|1[0-9]{2}|[1-9]?[0-9])\b                       # Last octet: 0-255
+    "#).unwrap()
+});
+
 #[derive(Clone, Copy, Debug)]
 pub struct Redact;
 
@@ -40,6 +59,11 @@ impl Function for Redact {
                 kind: kind::ARRAY,
                 required: true,
             },
+            Parameter {
+                keyword: "redactor",
+                kind: kind::BYTES | kind::OBJECT,
+                required: false,
+            },
         ]
     }
 
@@ -55,6 +79,18 @@ impl Function for Redact {
                 source: r#"redact({ "name": "John Doe", "ssn": "123-12-1234"}, filters: ["us_social_security_number"])"#,
                 result: Ok(r#"{ "name": "John Doe", "ssn": "[REDACTED]" }"#),
             },
+            Example {
+                title: "hash",
+                source: r#"redact("my id is 123456", filters: [r'\d+'], redactor: "sha256")"#,
+                result: Ok(
+                    r#"my id is 8d969eef6ecad3c29a3a629280e686cf0c3f5d5a86aff3ca12020c923adc6c92"#,
+                ),
+            },
+            Example {
+                title: "partial",
+                source: r#"redact("card 4111 1111 1111 1111", filters: ["credit_card_number"], redactor: "partial")"#,
+                result: Ok(r#"card ***************1111"#),
+            },
         ]
     }
 
@@ -89,7 +125,20 @@ impl Function for Redact {
             })
             .collect::<std::result::Result<Vec<Filter>, _>>()?;
 
-        let redactor = Redactor::Full;
+        let redactor = arguments
+            .optional_value("redactor")?
+            .map(|value| {
+                value
+                    .clone()
+                    .try_into()
+                    .map_err(|error| vrl::function::Error::InvalidArgument {
+                        keyword: "redactor",
+                        value,
+                        error,
+                    })
+            })
+            .transpose()?
+            .unwrap_or_default();
 
         Ok(RedactFn {
             value,
@@ -157,6 +206,9 @@ impl FunctionExpression for RedactFn {
 enum Filter {
     Pattern(Vec<Pattern>),
     UsSocialSecurityNumber,
+    EmailAddress,
+    CreditCardNumber,
+    Ipv4Address,
 }
 
 #[derive(Debug, Clone)]
@@ -180,7 +232,6 @@ impl TryFrom<Value> for Filter {
                 }?;
 
                 match r#type.as_ref() {
-                    b"us_social_security_number" => Ok(Filter::UsSocialSecurityNumber),
                     b"pattern" => {
                         let patterns = match object
                             .get("patterns")
@@ -200,13 +251,12 @@ impl TryFrom<Value> for Filter {
                         }?;
                         Ok(Filter::Pattern(patterns))
                     }
-                    _ => Err("unknown filter name"),
+                    name => Filter::named(name),
                 }
             }
             Value::Bytes(bytes) => match bytes.as_ref() {
                 b"pattern" => Err("pattern cannot be used without arguments"),
-                b"us_social_security_number" => Ok(Filter::UsSocialSecurityNumber),
-                _ => Err("unknown filter name"),
+                name => Filter::named(name),
             },
             Value::Regex(regex) => Ok(Filter::Pattern(vec![Pattern::Regex((*regex).clone())])),
             _ => Err("unknown literal for filter, must be a regex, filter name, or object"),
@@ -215,40 +265,93 @@ impl TryFrom<Value> for Filter {
 }
 
 impl Filter {
+    /// Returns a filter without parameters.
+    fn named(name: &[u8]) -> std::result::Result<Self, &'static str> {
+        match name {
+            b"us_social_security_number" => Ok(Filter::UsSocialSecurityNumber),
+            b"email_address" => Ok(Filter::EmailAddress),
+            b"credit_card_number" => Ok(Filter::CreditCardNumber),
+            b"ipv4_address" => Ok(Filter::Ipv4Address),
+            _ => Err("unknown filter name"),
+        }
+    }
+
     fn redact<'t>(&self, input: &'t str, redactor: &Redactor) -> Cow<'t, str> {
+        let replace = |captures: &Captures<'_>| redactor.redact(&captures[0]);
+
         match &self {
             Filter::Pattern(patterns) => {
                 patterns
                     .iter()
                     .fold(Cow::Borrowed(input), |input, pattern| match pattern {
-                        Pattern::Regex(regex) => regex
-                            .replace_all(&input, redactor.pattern())
-                            .into_owned()
-                            .into(),
+                        Pattern::Regex(regex) => {
+                            regex.replace_all(&input, replace).into_owned().into()
+                        }
                         Pattern::String(pattern) => {
-                            input.replace(pattern, redactor.pattern()).into()
+                            input.replace(pattern, &redactor.redact(pattern)).into()
                         }
                     })
             }
-            Filter::UsSocialSecurityNumber => {
-                US_SOCIAL_SECURITY_NUMBER.replace_all(input, redactor.pattern())
+            Filter::UsSocialSecurityNumber => US_SOCIAL_SECURITY_NUMBER.replace_all(input, replace),
+            Filter::EmailAddress => EMAIL_ADDRESS.replace_all(input, replace),
+            Filter::CreditCardNumber => {
+                CREDIT_CARD_NUMBER.replace_all(input, |captures: &Captures<'_>| {
+                    if is_luhn_valid(&captures[0]) {
+                        redactor.redact(&captures[0])
+                    } else {
+                        captures[0].to_owned()
+                    }
+                })
             }
+            Filter::Ipv4Address => IPV4_ADDRESS.replace_all(input, replace),
         }
     }
 }
 
+fn is_luhn_valid(number: &str) -> bool {
+    let sum = number
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .rev()
+        .enumerate()
+        .map(|(i, digit)| match (i % 2, digit * 2) {
+            (0, _) => digit,
+            (_, doubled) if doubled > 9 => doubled - 9,
+            (_, doubled) => doubled,
+        })
+        .sum::<u32>();
+    sum % 10 == 0
+}
+
 /// The recipe for redacting the matched filters.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Redactor {
+    /// Replaces the matched text with `[REDACTED]`.
     Full,
+    /// Replaces the matched text with its hex encoded SHA-256 hash, a deterministic pseudonym.
+    Sha256,
+    /// Masks all but the last characters of the matched text.
+    Partial { keep_last: usize },
+    /// Removes the matched text.
+    Remove,
 }
 
 impl Redactor {
-    fn pattern(&self) -> &str {
-        use Redactor::Full;
-
+    fn redact(&self, matched: &str) -> String {
         match self {
-            Full => "[REDACTED]",
+            Redactor::Full => "[REDACTED]".to_owned(),
+            Redactor::Sha256 => hex::encode(Sha256::digest(matched.as_bytes())),
+            Redactor::Partial { keep_last } => {
+                let len = matched.chars().count();
+                // text, which is too short to mask anything, is masked completely
+                let keep_last = if *keep_last < len { *keep_last } else { 0 };
+                matched
+                    .chars()
+                    .enumerate()
+                    .map(|(i, c)| if i < len - keep_last { '*' } else { c })
+                    .collect()
+            }
+            Redactor::Remove => String::new(),
         }
     }
 }
@@ -259,15 +362,35 @@ impl Default for Redactor {
     }
 }
 
-impl FromStr for Redactor {
-    type Err = &'static str;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        use Redactor::Full;
+impl TryFrom<Value> for Redactor {
+    type Error = &'static str;
 
-        match s {
-            "full" => Ok(Full),
-            _ => Err("unknown redactor"),
+    fn try_from(value: Value) -> std::result::Result<Self, Self::Error> {
+        match value {
+            Value::Bytes(bytes) => match bytes.as_ref() {
+                b"full" => Ok(Redactor::Full),
+                b"sha256" => Ok(Redactor::Sha256),
+                b"partial" => Ok(Redactor::Partial { keep_last: 4 }),
+                b"remove" => Ok(Redactor::Remove),
+                _ => Err("unknown redactor"),
+            },
+            Value::Object(object) => match object.get("type") {
+                Some(Value::Bytes(bytes)) if bytes.as_ref() == b"partial" => {
+                    match object.get("keep_last") {
+                        None => Ok(Redactor::Partial { keep_last: 4 }),
+                        Some(Value::Integer(keep_last)) if *keep_last >= 0 => {
+                            Ok(Redactor::Partial {
+                                keep_last: *keep_last as usize,
+                            })
+                        }
+                        Some(_) => Err("`keep_last` must be a non-negative integer"),
+                    }
+                }
+                Some(Value::Bytes(bytes)) => Value::Bytes(bytes.clone()).try_into(),
+                Some(_) => Err("type key in redactor must be a string"),
+                None => Err("redactor specified as object must have type parameter"),
+            },
+            _ => Err("redactor must be a string or an object"),
         }
     }
 }
@@ -322,6 +445,93 @@ mod test {
              tdef: TypeDef::bytes().infallible(),
         }
 
+        email_address {
+             args: func_args![
+                 value: "contact someone.else+vrl@example.co.uk today",
+                 filters: vec!["email_address"],
+             ],
+             want: Ok("contact [REDACTED] today"),
+             tdef: TypeDef::bytes().infallible(),
+        }
+
+        credit_card_number {
+             args: func_args![
+                 value: "valid 4111-1111-1111-1111, invalid 4111-1111-1111-1112",
+                 filters: vec!["credit_card_number"],
+             ],
+             want: Ok("valid [REDACTED], invalid 4111-1111-1111-1112"),
+             tdef: TypeDef::bytes().infallible(),
+        }
+
+        ipv4_address {
+             args: func_args![
+                 value: "from 10.0.0.1 to 256.1.1.1",
+                 filters: vec!["ipv4_address"],
+             ],
+             want: Ok("from [REDACTED] to 256.1.1.1"),
+             tdef: TypeDef::bytes().infallible(),
+        }
+
+        sha256 {
+             args: func_args![
+                 value: "hello 123-12-1234 world",
+                 filters: vec!["us_social_security_number"],
+                 redactor: "sha256",
+             ],
+             want: Ok("hello 30e6897f76dc102e32ee1d781c43417d259e586eac15c963d75ab8b5187769da world"),
+             tdef: TypeDef::bytes().infallible(),
+        }
+
+        partial {
+             args: func_args![
+                 value: "hello 123-12-1234 world",
+                 filters: vec!["us_social_security_number"],
+                 redactor: "partial",
+             ],
+             want: Ok("hello *******1234 world"),
+             tdef: TypeDef::bytes().infallible(),
+        }
+
+        partial_keep_last {
+             args: func_args![
+                 value: "hello 123456 world",
+                 filters: vec![Regex::new(r"\d+").unwrap()],
+                 redactor: value!({"type": "partial", "keep_last": 2}),
+             ],
+             want: Ok("hello ****56 world"),
+             tdef: TypeDef::bytes().infallible(),
+        }
+
+        partial_short {
+             args: func_args![
+                 value: "pin 1234",
+                 filters: vec![Regex::new(r"\d+").unwrap()],
+                 redactor: "partial",
+             ],
+             want: Ok("pin ****"),
+             tdef: TypeDef::bytes().infallible(),
+        }
+
+        remove {
+             args: func_args![
+                 value: "hello 123456 world",
+                 filters: vec![Regex::new(r"\d+ ").unwrap()],
+                 redactor: "remove",
+             ],
+             want: Ok("hello world"),
+             tdef: TypeDef::bytes().infallible(),
+        }
+
+        invalid_redactor {
+             args: func_args![
+                 value: "hello 123456 world",
+                 filters: vec![Regex::new(r"\d+").unwrap()],
+                 redactor: "mask",
+             ],
+             want: Err("invalid argument"),
+             tdef: TypeDef::bytes().infallible(),
+        }
+
         missing_patterns {
              args: func_args![
                  value: "hello 123456 world",
@@ -1,15 +1,22 @@
 use std::net::IpAddr;
 
 use ::value::Value;
-use dns_lookup::lookup_addr;
+use dns_lookup_lib::lookup_addr;
+use once_cell::sync::Lazy;
 use vrl::prelude::*;
 
-fn reverse_dns(value: Value) -> Resolved {
+use crate::dns_util::{DnsCache, LookupOptions};
+
+static CACHE: Lazy<DnsCache<IpAddr, String>> = Lazy::new(DnsCache::new);
+
+fn reverse_dns(value: Value, options: LookupOptions) -> Resolved {
     let ip: IpAddr = value
         .try_bytes_utf8_lossy()?
         .parse()
         .map_err(|err| format!("unable to parse IP address: {}", err))?;
-    let host = lookup_addr(&ip).map_err(|err| format!("unable to perform a lookup : {}", err))?;
+    let host = CACHE
+        .lookup(&ip, options, lookup_addr)
+        .map_err(|err| format!("unable to perform a lookup : {}", err))?;
 
     Ok(host.into())
 }
@@ -23,11 +30,23 @@ impl Function for ReverseDns {
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::BYTES,
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "timeout_ms",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "cache_ttl_secs",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
     }
 
     fn examples(&self) -> &'static [Example] {
@@ -45,20 +64,22 @@ impl Function for ReverseDns {
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
+        let options = LookupOptions::from_arguments(&arguments)?;
 
-        Ok(ReverseDnsFn { value }.as_expr())
+        Ok(ReverseDnsFn { value, options }.as_expr())
     }
 }
 
 #[derive(Debug, Clone)]
 struct ReverseDnsFn {
     value: Box<dyn Expression>,
+    options: LookupOptions,
 }
 
 impl FunctionExpression for ReverseDnsFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
-        reverse_dns(value)
+        reverse_dns(value, self.options)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -91,6 +112,12 @@ mod tests {
             tdef: TypeDef::bytes().fallible(),
         }
 
+        invalid_cache_ttl {
+            args: func_args![value: value!("8.8.8.8"), cache_ttl_secs: -1],
+            want: Err("invalid argument"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
         invalid_type {
             args: func_args![value: value!(1)],
             want: Err("expected string, got integer"),
@@ -0,0 +1,98 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ::value::Value;
+use bytes::Bytes;
+use rand::Rng;
+use vrl::prelude::*;
+use vrl::state::TypeState;
+
+/// Crockford's base32 alphabet, which skips `I`, `L`, `O` and `U`.
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+fn ulid() -> Value {
+    // 48 bits of milliseconds since the Unix epoch followed by 80 random bits
+    let random = rand::thread_rng().gen::<u128>() & ((1 << 80) - 1);
+    Bytes::from(encode((u128::from(unix_millis()) << 80) | random)).into()
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+fn encode(mut ulid: u128) -> Vec<u8> {
+    let mut encoded = vec![0; 26];
+    for c in encoded.iter_mut().rev() {
+        *c = ALPHABET[(ulid & 0x1f) as usize];
+        ulid >>= 5;
+    }
+    encoded
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Ulid;
+
+impl Function for Ulid {
+    fn identifier(&self) -> &'static str {
+        "ulid"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "generate ULID",
+            source: r#"length(ulid())"#,
+            result: Ok("26"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        _: ArgumentList,
+    ) -> Compiled {
+        Ok(UlidFn.as_expr())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct UlidFn;
+
+impl FunctionExpression for UlidFn {
+    fn resolve(&self, _: &mut Context) -> Resolved {
+        Ok(ulid())
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_type_def![default {
+        expr: |_| { UlidFn },
+        want: TypeDef::bytes().infallible(),
+    }];
+
+    #[test]
+    fn encodes_crockford_base32() {
+        assert_eq!(encode(0), b"00000000000000000000000000");
+        assert_eq!(encode(u128::MAX), b"7ZZZZZZZZZZZZZZZZZZZZZZZZZ");
+        assert_eq!(
+            encode(0x0171_5686_9800 << 80),
+            b"01E5B8D6000000000000000000"
+        );
+    }
+
+    #[test]
+    fn sorts_by_time() {
+        let first = super::ulid();
+        assert_eq!(first.as_str().unwrap().len(), 26);
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        assert!(first.as_str().unwrap() < super::ulid().as_str().unwrap());
+    }
+}
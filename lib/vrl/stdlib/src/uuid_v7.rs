@@ -0,0 +1,97 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ::value::Value;
+use bytes::Bytes;
+use rand::RngCore;
+use vrl::prelude::*;
+use vrl::state::TypeState;
+
+fn uuid_v7() -> Value {
+    let mut bytes = [0; 16];
+    rand::thread_rng().fill_bytes(&mut bytes[6..]);
+    // the first 48 bits are the milliseconds since the Unix epoch, so that UUIDs sort by time
+    bytes[..6].copy_from_slice(&unix_millis().to_be_bytes()[2..]);
+    bytes[6] = (bytes[6] & 0x0f) | 0x70;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let mut buf = [0; 36];
+    let uuid = uuid::Uuid::from_bytes(bytes)
+        .hyphenated()
+        .encode_lower(&mut buf);
+    Bytes::copy_from_slice(uuid.as_bytes()).into()
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct UuidV7;
+
+impl Function for UuidV7 {
+    fn identifier(&self) -> &'static str {
+        "uuid_v7"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "generate UUID v7",
+            source: r#"uuid_v7() != """#,
+            result: Ok("true"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        _: ArgumentList,
+    ) -> Compiled {
+        Ok(UuidV7Fn.as_expr())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct UuidV7Fn;
+
+impl FunctionExpression for UuidV7Fn {
+    fn resolve(&self, _: &mut Context) -> Resolved {
+        Ok(uuid_v7())
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_type_def![default {
+        expr: |_| { UuidV7Fn },
+        want: TypeDef::bytes().infallible(),
+    }];
+
+    #[test]
+    fn uuid_v7() {
+        let before = unix_millis();
+        let value = super::uuid_v7();
+        let uuid = uuid::Uuid::parse_str(&value.as_str().unwrap()).expect("valid UUID V7");
+
+        assert_eq!(uuid.get_version_num(), 7);
+        assert_eq!(uuid.get_variant(), uuid::Variant::RFC4122);
+        let mut millis = [0; 8];
+        millis[2..].copy_from_slice(&uuid.as_bytes()[..6]);
+        assert!((before..=unix_millis()).contains(&u64::from_be_bytes(millis)));
+    }
+
+    #[test]
+    fn sorts_by_time() {
+        let first = super::uuid_v7();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        assert!(first.as_str().unwrap() < super::uuid_v7().as_str().unwrap());
+    }
+}
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, env, path::PathBuf};
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+};
 
 use bollard::{
     container::{Config, CreateContainerOptions},
@@ -14,6 +18,8 @@ use vector_config::configurable_component;
 
 // From bollard source.
 const DEFAULT_TIMEOUT: u64 = 120;
+const DEFAULT_DOCKER_SOCKET: &str = "/var/run/docker.sock";
+const ROOTFUL_PODMAN_SOCKET: &str = "/run/podman/podman.sock";
 
 #[derive(Debug, Snafu)]
 pub enum Error {
@@ -41,7 +47,9 @@ pub struct DockerTlsConfig {
 }
 
 pub fn docker(host: Option<String>, tls: Option<DockerTlsConfig>) -> crate::Result<Docker> {
-    let host = host.or_else(|| env::var("DOCKER_HOST").ok());
+    let host = host
+        .or_else(|| env::var("DOCKER_HOST").ok())
+        .or_else(podman_host);
 
     match host {
         None => Docker::connect_with_local_defaults().map_err(Into::into),
@@ -91,6 +99,23 @@ pub fn docker(host: Option<String>, tls: Option<DockerTlsConfig>) -> crate::Resu
     }
 }
 
+/// Podman serves a Docker compatible API, so its socket is used on hosts without the default
+/// Docker socket. The socket of rootless Podman, running as the current user, is tried first.
+fn podman_host() -> Option<String> {
+    if cfg!(windows) || Path::new(DEFAULT_DOCKER_SOCKET).exists() {
+        return None;
+    }
+
+    let host = env::var_os("XDG_RUNTIME_DIR")
+        .map(|dir| PathBuf::from(dir).join("podman/podman.sock"))
+        .into_iter()
+        .chain(Some(PathBuf::from(ROOTFUL_PODMAN_SOCKET)))
+        .find(|socket| socket.exists())
+        .map(|socket| format!("unix://{}", socket.display()))?;
+    info!(message = "Docker socket not found, connecting to Podman.", %host);
+    Some(host)
+}
+
 // From bollard source, unfortunately they don't export this function.
 fn default_certs() -> Option<DockerTlsConfig> {
     let from_env = env::var("DOCKER_CERT_PATH").or_else(|_| env::var("DOCKER_CONFIG"));
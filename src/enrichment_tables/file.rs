@@ -2,11 +2,14 @@ use std::{
     collections::{BTreeMap, HashMap},
     fs,
     hash::Hasher,
+    net::IpAddr,
     path::PathBuf,
+    str::FromStr,
     time::SystemTime,
 };
 
 use bytes::Bytes;
+use cidr_utils::cidr::IpCidr;
 use enrichment::{Case, Condition, IndexHandle, Table};
 use tracing::trace;
 use value::Value;
@@ -299,6 +302,35 @@ impl File {
                     _ => false,
                 },
             },
+            Condition::StartsWith { field, prefix } => {
+                self.column_str(field, row)
+                    .map_or(false, |value| match case {
+                        Case::Sensitive => value.starts_with(prefix.as_str()),
+                        Case::Insensitive => {
+                            value.to_lowercase().starts_with(&prefix.to_lowercase())
+                        }
+                    })
+            }
+            Condition::Wildcard { field, pattern } => {
+                self.column_str(field, row)
+                    .map_or(false, |value| match case {
+                        Case::Sensitive => wildcard_match(pattern, &value),
+                        Case::Insensitive => {
+                            wildcard_match(&pattern.to_lowercase(), &value.to_lowercase())
+                        }
+                    })
+            }
+            Condition::ContainsIp { field, ip } => self
+                .column_str(field, row)
+                .map_or(false, |value| cidr_contains(&value, *ip)),
+        })
+    }
+
+    /// Returns the value of the given column as a string, if it is one.
+    fn column_str<'a>(&self, col: &str, row: &'a [Value]) -> Option<std::borrow::Cow<'a, str>> {
+        self.column_index(col).and_then(|idx| match &row[idx] {
+            Value::Bytes(bytes) => Some(String::from_utf8_lossy(bytes)),
+            _ => None,
         })
     }
 
@@ -453,6 +485,48 @@ fn hash_value(hasher: &mut seahash::SeaHasher, case: Case, value: &Value) -> Res
     Ok(())
 }
 
+/// Matches the value against a pattern, where `*` matches any number of characters and `?` matches
+/// a single character.
+fn wildcard_match(pattern: &str, value: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let value = value.chars().collect::<Vec<_>>();
+    let (mut p, mut v) = (0, 0);
+    // the position of the last `*` in the pattern and of the value when it was reached, to
+    // backtrack to if the rest of the pattern doesn't match
+    let mut backtrack = None;
+
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, v));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == value[v] => {
+                p += 1;
+                v += 1;
+            }
+            _ => match backtrack {
+                Some((star, star_v)) => {
+                    p = star + 1;
+                    v = star_v + 1;
+                    backtrack = Some((star, star_v + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Returns whether the CIDR block, or the single IP address, contains the IP address.
+fn cidr_contains(cidr: &str, ip: IpAddr) -> bool {
+    match IpCidr::from_str(cidr) {
+        Ok(cidr) => cidr.contains(ip),
+        Err(_) => IpAddr::from_str(cidr).map_or(false, |addr| addr == ip),
+    }
+}
+
 /// Returns an error if the iterator doesn't yield exactly one result.
 fn single_or_err<I, T>(mut iter: T) -> Result<I, String>
 where
@@ -960,6 +1034,113 @@ mod tests {
         );
     }
 
+    #[test]
+    fn finds_rows_with_prefix_and_wildcard() {
+        let file = File::new(
+            Default::default(),
+            SystemTime::now(),
+            vec![
+                vec!["api.example.com".into(), "api".into()],
+                vec!["www.example.com".into(), "web".into()],
+                vec!["www.example.org".into(), "web".into()],
+            ],
+            vec!["host".to_string(), "service".to_string()],
+        );
+
+        assert_eq!(
+            Ok(vec![
+                BTreeMap::from([(String::from("service"), Value::from("web"))]),
+                BTreeMap::from([(String::from("service"), Value::from("web"))]),
+            ]),
+            file.find_table_rows(
+                Case::Insensitive,
+                &[Condition::StartsWith {
+                    field: "host",
+                    prefix: "WWW.".to_string(),
+                }],
+                Some(&["service".to_string()]),
+                None
+            )
+        );
+
+        assert_eq!(
+            Ok(BTreeMap::from([(
+                String::from("service"),
+                Value::from("web")
+            )])),
+            file.find_table_row(
+                Case::Sensitive,
+                &[Condition::Wildcard {
+                    field: "host",
+                    pattern: "w?w.*.org".to_string(),
+                }],
+                Some(&["service".to_string()]),
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn finds_row_containing_ip() {
+        let mut file = File::new(
+            Default::default(),
+            SystemTime::now(),
+            vec![
+                vec!["10.0.0.0/8".into(), "internal".into()],
+                vec!["192.168.1.1".into(), "gateway".into()],
+                vec!["2001:db8::/32".into(), "documentation".into()],
+            ],
+            vec!["subnet".to_string(), "zone".to_string()],
+        );
+
+        let handle = file.add_index(Case::Sensitive, &[]).unwrap();
+
+        for (ip, subnet, zone) in [
+            ("10.1.2.3", "10.0.0.0/8", "internal"),
+            ("192.168.1.1", "192.168.1.1", "gateway"),
+            ("2001:db8::1", "2001:db8::/32", "documentation"),
+        ] {
+            assert_eq!(
+                Ok(BTreeMap::from([
+                    (String::from("subnet"), Value::from(subnet)),
+                    (String::from("zone"), Value::from(zone)),
+                ])),
+                file.find_table_row(
+                    Case::Sensitive,
+                    &[Condition::ContainsIp {
+                        field: "subnet",
+                        ip: ip.parse().unwrap(),
+                    }],
+                    None,
+                    Some(handle)
+                )
+            );
+        }
+
+        assert_eq!(
+            Err("no rows found".to_string()),
+            file.find_table_row(
+                Case::Sensitive,
+                &[Condition::ContainsIp {
+                    field: "subnet",
+                    ip: "172.16.0.1".parse().unwrap(),
+                }],
+                None,
+                Some(handle)
+            )
+        );
+    }
+
+    #[test]
+    fn matches_wildcards() {
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match("a*c", "abbbc"));
+        assert!(wildcard_match("a*b*c", "aXbYbZc"));
+        assert!(wildcard_match("?b?", "abc"));
+        assert!(!wildcard_match("a*c", "abcd"));
+        assert!(!wildcard_match("?", ""));
+    }
+
     #[test]
     fn doesnt_find_row() {
         let file = File::new(
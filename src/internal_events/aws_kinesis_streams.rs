@@ -0,0 +1,52 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct AwsKinesisStreamsReceiveError<'a> {
+    pub error: crate::Error,
+    /// The shard being read, if the error isn't about the stream as a whole.
+    pub shard_id: Option<&'a str>,
+}
+
+impl<'a> InternalEvent for AwsKinesisStreamsReceiveError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read records from Kinesis stream.",
+            error = %self.error,
+            shard_id = self.shard_id,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsKinesisStreamsLeaseError<'a> {
+    pub error: crate::Error,
+    pub shard_id: &'a str,
+}
+
+impl<'a> InternalEvent for AwsKinesisStreamsLeaseError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to update lease of shard.",
+            error = %self.error,
+            shard_id = %self.shard_id,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use std::path::Path;
+
+use aws_sdk_s3::error::ListObjectsV2Error;
+use aws_smithy_client::SdkError;
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::sources::aws_s3::sqs::ProcessingError;
+
+#[derive(Debug)]
+pub struct AwsS3ListObjectsError<'a> {
+    pub bucket: &'a str,
+    pub error: SdkError<ListObjectsV2Error>,
+}
+
+impl<'a> InternalEvent for AwsS3ListObjectsError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to list objects.",
+            bucket = %self.bucket,
+            error = %self.error,
+            error_code = "failed_listing_s3_objects",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_listing_s3_objects",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsS3ObjectProcessingError<'a> {
+    pub bucket: &'a str,
+    pub key: &'a str,
+    pub error: &'a ProcessingError,
+}
+
+impl<'a> InternalEvent for AwsS3ObjectProcessingError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to process S3 object.",
+            bucket = %self.bucket,
+            key = %self.key,
+            error = %self.error,
+            error_code = "failed_processing_s3_object",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_processing_s3_object",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsS3CheckpointError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl<'a> InternalEvent for AwsS3CheckpointError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Could not write checkpoint.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
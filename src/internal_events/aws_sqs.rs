@@ -203,6 +203,32 @@ impl<'a, E: std::fmt::Display> InternalEvent for SqsMessageDeleteError<'a, E> {
     }
 }
 
+#[cfg(feature = "sources-aws_sqs")]
+#[derive(Debug)]
+pub struct SqsMessageVisibilityError<'a, E> {
+    pub error: &'a E,
+}
+
+#[cfg(feature = "sources-aws_sqs")]
+impl<'a, E: std::fmt::Display> InternalEvent for SqsMessageVisibilityError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to extend the visibility timeout of SQS messages.",
+            error = %self.error,
+            error_code = "failed_changing_sqs_message_visibility",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_changing_sqs_message_visibility",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
 // AWS s3 source
 
 #[derive(Debug)]
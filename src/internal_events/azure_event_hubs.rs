@@ -0,0 +1,77 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct AzureEventHubsReceiveError<'a> {
+    pub error: crate::Error,
+    /// The partition being read, if the error isn't about the event hub as a whole.
+    pub partition_id: Option<&'a str>,
+}
+
+impl<'a> InternalEvent for AzureEventHubsReceiveError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read events from event hub.",
+            error = %self.error,
+            partition_id = self.partition_id,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureEventHubsOwnershipError<'a> {
+    pub error: crate::Error,
+    /// The partition being claimed or renewed, if the error isn't about listing ownership.
+    pub partition_id: Option<&'a str>,
+}
+
+impl<'a> InternalEvent for AzureEventHubsOwnershipError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to update ownership of partitions.",
+            error = %self.error,
+            partition_id = self.partition_id,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureEventHubsCheckpointError<'a> {
+    pub error: crate::Error,
+    pub partition_id: &'a str,
+}
+
+impl<'a> InternalEvent for AzureEventHubsCheckpointError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read or store checkpoint of partition.",
+            error = %self.error,
+            partition_id = %self.partition_id,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, UNINTENTIONAL},
+    sinks::clickhouse::EncodingError,
+};
+
+#[derive(Debug)]
+pub struct ClickhouseRowBinaryEncodingError {
+    pub error: EncodingError,
+}
+
+impl InternalEvent for ClickhouseRowBinaryEncodingError {
+    fn emit(self) {
+        let reason = "Failed to encode event as a row.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_code = "row_binary_encoding_failed",
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "row_binary_encoding_failed",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -213,3 +213,25 @@ impl InternalEvent for DockerLogsReceivedOutOfOrderError<'_> {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct DockerLogsCriParseError<'a> {
+    pub file: &'a str,
+}
+
+impl InternalEvent for DockerLogsCriParseError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to parse line as CRI log line.",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            file = %self.file,
+            internal_log_rate_limit = true
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
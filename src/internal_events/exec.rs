@@ -228,6 +228,31 @@ impl InternalEvent for ExecFailedToSignalChildError<'_> {
     }
 }
 
+#[derive(Debug)]
+pub struct ExecRespawnsExhausted<'a> {
+    pub command: &'a str,
+    pub respawns: u32,
+}
+
+impl InternalEvent for ExecRespawnsExhausted<'_> {
+    fn emit(self) {
+        error!(
+            message = "Command kept exiting, no longer restarting it.",
+            command = %self.command,
+            respawns = %self.respawns,
+            error_type = error_type::COMMAND_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "command" => self.command.to_owned(),
+            "error_type" => error_type::COMMAND_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
 pub struct ExecChannelClosedError;
 
 impl InternalEvent for ExecChannelClosedError {
@@ -7,7 +7,11 @@ use crate::{
     internal_events::{ComponentEventsDropped, UNINTENTIONAL},
 };
 
-#[cfg(any(feature = "sources-file", feature = "sources-kubernetes_logs"))]
+#[cfg(any(
+    feature = "sources-docker_logs",
+    feature = "sources-file",
+    feature = "sources-kubernetes_logs"
+))]
 pub use self::source::*;
 use vector_common::internal_event::{error_stage, error_type};
 
@@ -80,7 +84,11 @@ impl<'a, P: std::fmt::Debug> InternalEvent for FileIoError<'a, P> {
     }
 }
 
-#[cfg(any(feature = "sources-file", feature = "sources-kubernetes_logs"))]
+#[cfg(any(
+    feature = "sources-docker_logs",
+    feature = "sources-file",
+    feature = "sources-kubernetes_logs"
+))]
 mod source {
     use std::{io::Error, path::Path, time::Duration};
 
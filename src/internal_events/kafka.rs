@@ -176,3 +176,27 @@ impl InternalEvent for KafkaHeaderExtractionError<'_> {
         counter!("kafka_header_extraction_failures_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct KafkaTransactionAbortError {
+    pub error: rdkafka::error::KafkaError,
+}
+
+impl InternalEvent for KafkaTransactionAbortError {
+    fn emit(self) {
+        error!(
+            message = "Failed to abort transaction.",
+            error = %self.error,
+            error_code = "kafka_transaction_abort",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "kafka_transaction_abort",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::io::Error;
 
+#[cfg(feature = "sinks-nats")]
 use crate::{
     emit,
     internal_events::{ComponentEventsDropped, UNINTENTIONAL},
@@ -10,11 +11,13 @@ use vector_core::internal_event::InternalEvent;
 
 use super::prelude::io_error_code;
 
+#[cfg(feature = "sinks-nats")]
 #[derive(Debug)]
 pub struct NatsEventSendError {
     pub error: Error,
 }
 
+#[cfg(feature = "sinks-nats")]
 impl InternalEvent for NatsEventSendError {
     fn emit(self) {
         let reason = "Failed to send message.";
@@ -38,3 +41,29 @@ impl InternalEvent for NatsEventSendError {
         counter!("send_errors_total", 1);
     }
 }
+
+#[cfg(feature = "sources-nats")]
+#[derive(Debug)]
+pub struct NatsAckError {
+    pub error: Error,
+}
+
+#[cfg(feature = "sources-nats")]
+impl InternalEvent for NatsAckError {
+    fn emit(self) {
+        error!(
+            message = "Failed to acknowledge message.",
+            error = %self.error,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            error_code = io_error_code(&self.error),
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "error_code" => io_error_code(&self.error),
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
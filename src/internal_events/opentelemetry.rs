@@ -0,0 +1,36 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use crate::event::metric::MetricValue;
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, UNINTENTIONAL},
+};
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct OpentelemetryUnsupportedMetricError<'a> {
+    pub value: &'a MetricValue,
+}
+
+impl<'a> InternalEvent for OpentelemetryUnsupportedMetricError<'a> {
+    fn emit(self) {
+        let reason = "Metric type not supported by OTLP.";
+        error!(
+            message = reason,
+            error_code = "unsupported_metric",
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            value = ?self.value,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "unsupported_metric",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { reason, count: 1 });
+    }
+}
@@ -0,0 +1,35 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, UNINTENTIONAL},
+};
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct PostgresqlRowRejected<'a> {
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for PostgresqlRowRejected<'a> {
+    fn emit(self) {
+        let reason = "Event does not match the columns of the table.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_code = "row_rejected",
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "row_rejected",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { reason, count: 1 });
+    }
+}
@@ -0,0 +1,79 @@
+use std::path::Path;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::sources::sftp::ClientError;
+
+#[derive(Debug)]
+pub struct SftpListError<'a> {
+    pub error: ClientError,
+    pub endpoint: &'a str,
+}
+
+impl<'a> InternalEvent for SftpListError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to list files.",
+            endpoint = %self.endpoint,
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SftpDownloadError<'a> {
+    pub error: ClientError,
+    pub path: &'a str,
+}
+
+impl<'a> InternalEvent for SftpDownloadError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to download file.",
+            path = %self.path,
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SftpStateError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl<'a> InternalEvent for SftpStateError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Could not write the processed files.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
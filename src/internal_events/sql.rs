@@ -0,0 +1,73 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct SqlQueryError {
+    pub error: sqlx::Error,
+}
+
+impl InternalEvent for SqlQueryError {
+    fn emit(self) {
+        error!(
+            message = "Failed to run query.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SqlTrackingColumnError<'a> {
+    pub column: &'a str,
+    pub kind: &'static str,
+}
+
+impl<'a> InternalEvent for SqlTrackingColumnError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Tracking column is missing, or not of its type.",
+            column = %self.column,
+            kind = %self.kind,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SqlCheckpointError {
+    pub error: std::io::Error,
+    pub path: String,
+}
+
+impl InternalEvent for SqlCheckpointError {
+    fn emit(self) {
+        error!(
+            message = "Could not write checkpoint.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
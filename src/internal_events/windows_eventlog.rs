@@ -0,0 +1,71 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct WindowsEventLogReadError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for WindowsEventLogReadError {
+    fn emit(self) {
+        error!(
+            message = "Failed to read events.",
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogParseError {
+    pub error: roxmltree::Error,
+}
+
+impl InternalEvent for WindowsEventLogParseError {
+    fn emit(self) {
+        error!(
+            message = "Failed to parse event.",
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogCheckpointError {
+    pub error: std::io::Error,
+    pub path: String,
+}
+
+impl InternalEvent for WindowsEventLogCheckpointError {
+    fn emit(self) {
+        error!(
+            message = "Could not write bookmark.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use std::path::{Path, PathBuf};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
 use rdkafka::{consumer::ConsumerContext, ClientConfig, ClientContext, Statistics};
 use snafu::Snafu;
@@ -70,6 +73,35 @@ pub struct KafkaSaslConfig {
 
     /// The SASL mechanism to use.
     pub(crate) mechanism: Option<String>,
+
+    #[configurable(derived)]
+    pub(crate) oauthbearer: Option<KafkaOauthbearerConfig>,
+}
+
+/// Configuration for SASL/OAUTHBEARER authentication with tokens from an OpenID Connect provider.
+///
+/// Tokens are requested with the client credentials grant, and refreshed by `librdkafka` before
+/// they expire. When set, the SASL mechanism defaults to `OAUTHBEARER`.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct KafkaOauthbearerConfig {
+    /// The URL of the token endpoint of the OpenID Connect provider.
+    pub(crate) token_endpoint_url: String,
+
+    /// The client ID used to request tokens.
+    pub(crate) client_id: String,
+
+    /// The client secret used to request tokens.
+    pub(crate) client_secret: SensitiveString,
+
+    /// The scope of the requested tokens.
+    pub(crate) scope: Option<String>,
+
+    /// Extensions sent to the broker along with the token.
+    ///
+    /// Confluent Cloud, for example, requires the `logicalCluster` and `identityPoolId` extensions.
+    #[serde(default)]
+    pub(crate) extensions: BTreeMap<String, String>,
 }
 
 impl KafkaAuthConfig {
@@ -96,6 +128,12 @@ impl KafkaAuthConfig {
             if let Some(mechanism) = &sasl.mechanism {
                 client.set("sasl.mechanism", mechanism);
             }
+            if let Some(oauthbearer) = &sasl.oauthbearer {
+                if sasl.mechanism.is_none() {
+                    client.set("sasl.mechanism", "OAUTHBEARER");
+                }
+                oauthbearer.apply(client);
+            }
         }
 
         if tls_enabled {
@@ -118,6 +156,31 @@ impl KafkaAuthConfig {
     }
 }
 
+impl KafkaOauthbearerConfig {
+    fn apply(&self, client: &mut ClientConfig) {
+        client
+            .set("sasl.oauthbearer.method", "oidc")
+            .set(
+                "sasl.oauthbearer.token.endpoint.url",
+                self.token_endpoint_url.as_str(),
+            )
+            .set("sasl.oauthbearer.client.id", self.client_id.as_str())
+            .set("sasl.oauthbearer.client.secret", self.client_secret.inner());
+        if let Some(scope) = &self.scope {
+            client.set("sasl.oauthbearer.scope", scope.as_str());
+        }
+        if !self.extensions.is_empty() {
+            let extensions = self
+                .extensions
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect::<Vec<_>>()
+                .join(",");
+            client.set("sasl.oauthbearer.extensions", extensions);
+        }
+    }
+}
+
 fn pathbuf_to_string(path: &Path) -> crate::Result<&str> {
     path.to_str()
         .ok_or_else(|| KafkaError::InvalidPath { path: path.into() }.into())
@@ -134,3 +197,42 @@ impl ClientContext for KafkaStatisticsContext {
 }
 
 impl ConsumerContext for KafkaStatisticsContext {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_oauthbearer_config() {
+        let auth = KafkaAuthConfig {
+            sasl: Some(KafkaSaslConfig {
+                enabled: Some(true),
+                oauthbearer: Some(KafkaOauthbearerConfig {
+                    token_endpoint_url: "https://auth.example.com/oauth2/token".to_owned(),
+                    client_id: "vector".to_owned(),
+                    client_secret: "secret".to_owned().into(),
+                    scope: None,
+                    extensions: [
+                        ("identityPoolId".to_owned(), "pool-abc".to_owned()),
+                        ("logicalCluster".to_owned(), "lkc-123".to_owned()),
+                    ]
+                    .into(),
+                }),
+                ..Default::default()
+            }),
+            tls: None,
+        };
+
+        let mut client = ClientConfig::new();
+        auth.apply(&mut client).unwrap();
+        assert_eq!(client.get("security.protocol"), Some("sasl_plaintext"));
+        assert_eq!(client.get("sasl.mechanism"), Some("OAUTHBEARER"));
+        assert_eq!(client.get("sasl.oauthbearer.method"), Some("oidc"));
+        assert_eq!(client.get("sasl.oauthbearer.client.secret"), Some("secret"));
+        assert_eq!(client.get("sasl.oauthbearer.scope"), None);
+        assert_eq!(
+            client.get("sasl.oauthbearer.extensions"),
+            Some("identityPoolId=pool-abc,logicalCluster=lkc-123")
+        );
+    }
+}
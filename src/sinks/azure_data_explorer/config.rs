@@ -0,0 +1,196 @@
+use std::sync::Arc;
+
+use azure_identity::{AutoRefreshingTokenCredential, DefaultAzureCredential};
+use codecs::{encoding::Framer, JsonSerializer, NewlineDelimitedEncoder};
+use futures::FutureExt;
+use http::Uri;
+use indoc::indoc;
+use snafu::{ResultExt, Snafu};
+use tower::ServiceBuilder;
+use vector_config::configurable_component;
+
+use super::{
+    request_builder::AzureDataExplorerRequestBuilder,
+    resources::IngestionClient,
+    service::{AzureDataExplorerRetryLogic, AzureDataExplorerService},
+    sink::AzureDataExplorerSink,
+};
+use crate::{
+    codecs::{Encoder, Transformer},
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    http::HttpClient,
+    sinks::{
+        util::{
+            BatchConfig, Compression, ServiceBuilderExt, SinkBatchSettings, TowerRequestConfig,
+        },
+        Healthcheck, UriParseSnafu, VectorSink,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AzureDataExplorerDefaultBatchSettings;
+
+impl SinkBatchSettings for AzureDataExplorerDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(10_000_000);
+    // The cluster batches the blobs again before ingesting them, so there is little to gain from
+    // waiting longer here.
+    const TIMEOUT_SECS: f64 = 30.0;
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Compression `zlib` is not supported, use `gzip` or `none`"))]
+    UnsupportedCompression,
+}
+
+/// Configuration for the `azure_data_explorer` sink.
+#[configurable_component(sink("azure_data_explorer"))]
+#[derive(Clone, Debug)]
+pub struct AzureDataExplorerConfig {
+    /// The ingestion endpoint of the cluster.
+    ///
+    /// This is the URI of the cluster prefixed with `ingest-`, such as
+    /// `https://ingest-mycluster.westeurope.kusto.windows.net`.
+    ///
+    /// Credentials are loaded from Azure Active Directory, with the environment variables, a
+    /// Managed Identity or the `az` CLI tool, in this order.
+    pub ingestion_endpoint: String,
+
+    /// The database of the table.
+    pub database: String,
+
+    /// The table to ingest the events into.
+    pub table: String,
+
+    /// The name of the JSON ingestion mapping of the table, mapping the fields of the events to
+    /// its columns.
+    ///
+    /// If not set, the fields are mapped to the columns of the same name.
+    #[serde(default)]
+    pub mapping_reference: Option<String>,
+
+    /// Whether the blobs are ingested as soon as they are received, bypassing the ingestion
+    /// batching policy of the table.
+    ///
+    /// This lowers the latency of the ingestion, at the expense of its efficiency, and should be
+    /// used with caution.
+    #[serde(default)]
+    pub flush_immediately: bool,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default = "Compression::gzip_default")]
+    pub compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<AzureDataExplorerDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for AzureDataExplorerConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            ingestion_endpoint = "https://ingest-mycluster.westeurope.kusto.windows.net"
+            database = "my-database"
+            table = "my-table"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for AzureDataExplorerConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if matches!(self.compression, Compression::Zlib(_)) {
+            return Err(BuildError::UnsupportedCompression.into());
+        }
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let endpoint: Uri = self.ingestion_endpoint.parse().context(UriParseSnafu)?;
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+        let credential = Arc::new(AutoRefreshingTokenCredential::new(Arc::new(
+            DefaultAzureCredential::default(),
+        )));
+        let ingestion = IngestionClient::new(client.clone(), endpoint, credential);
+
+        let healthcheck = healthcheck(ingestion.clone()).boxed();
+
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let service = ServiceBuilder::new()
+            .settings(request_settings, AzureDataExplorerRetryLogic)
+            .service(AzureDataExplorerService {
+                client,
+                ingestion,
+                database: self.database.clone(),
+                table: self.table.clone(),
+                mapping_reference: self.mapping_reference.clone(),
+                flush_immediately: self.flush_immediately,
+                compression: self.compression,
+            });
+
+        let encoder = Encoder::<Framer>::new(
+            NewlineDelimitedEncoder::new().into(),
+            JsonSerializer::new().into(),
+        );
+        let request_builder = AzureDataExplorerRequestBuilder {
+            encoder: (self.encoding.clone(), encoder),
+            compression: self.compression,
+        };
+
+        let sink = AzureDataExplorerSink {
+            service,
+            request_builder,
+            batch_settings,
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Checks that the ingestion resources of the cluster can be fetched.
+async fn healthcheck(ingestion: IngestionClient) -> crate::Result<()> {
+    ingestion.resources().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureDataExplorerConfig>();
+    }
+}
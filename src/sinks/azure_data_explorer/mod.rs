@@ -0,0 +1,45 @@
+//! The `azure_data_explorer` sink.
+//!
+//! Events are ingested with [queued ingestion][queued_ingestion]: each batch is uploaded as a blob
+//! to one of the temporary storage containers of the cluster, and a message pointing to it is
+//! posted to one of its ingestion queues. The cluster then ingests the blobs according to the
+//! ingestion batching policy of the table.
+//!
+//! [queued_ingestion]: https://learn.microsoft.com/en-us/azure/data-explorer/kusto/api/netfx/about-kusto-ingest#queued-ingestion
+
+use http::StatusCode;
+use snafu::Snafu;
+
+mod config;
+mod request_builder;
+mod resources;
+mod service;
+mod sink;
+
+pub use config::AzureDataExplorerConfig;
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum AzureDataExplorerError {
+    #[snafu(display("Failed to get an Azure Active Directory token: {}", source))]
+    Token { source: azure_core::Error },
+
+    #[snafu(display("Failed to build the request: {}", source))]
+    BuildRequest { source: http::Error },
+
+    #[snafu(display("HTTP request failed: {}", source))]
+    Http { source: crate::http::HttpError },
+
+    #[snafu(display("{} failed with status {}: {}", operation, status, body))]
+    ResponseStatus {
+        operation: &'static str,
+        status: StatusCode,
+        body: String,
+    },
+
+    #[snafu(display("Invalid response to a management command: {}", source))]
+    ParseResponse { source: serde_json::Error },
+
+    #[snafu(display("The cluster returned no {:?} ingestion resource", name))]
+    MissingResource { name: &'static str },
+}
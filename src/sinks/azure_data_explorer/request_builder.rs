@@ -0,0 +1,77 @@
+use bytes::Bytes;
+use codecs::encoding::Framer;
+use vector_core::ByteSizeOf;
+
+use crate::{
+    codecs::{Encoder, Transformer},
+    event::{Event, EventFinalizers, Finalizable},
+    sinks::util::{request_builder::EncodeResult, Compression, RequestBuilder},
+};
+
+/// A batch of events, encoded as JSON lines.
+#[derive(Clone, Debug)]
+pub struct AzureDataExplorerRequest {
+    pub blob_data: Bytes,
+    /// The size of the data before compression, which the cluster estimates the batch size with.
+    pub raw_data_size: usize,
+    pub metadata: AzureDataExplorerMetadata,
+}
+
+impl Finalizable for AzureDataExplorerRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.metadata.finalizers)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AzureDataExplorerMetadata {
+    pub count: usize,
+    pub byte_size: usize,
+    pub finalizers: EventFinalizers,
+}
+
+pub struct AzureDataExplorerRequestBuilder {
+    pub encoder: (Transformer, Encoder<Framer>),
+    pub compression: Compression,
+}
+
+impl RequestBuilder<Vec<Event>> for AzureDataExplorerRequestBuilder {
+    type Metadata = AzureDataExplorerMetadata;
+    type Events = Vec<Event>;
+    type Encoder = (Transformer, Encoder<Framer>);
+    type Payload = Bytes;
+    type Request = AzureDataExplorerRequest;
+    type Error = std::io::Error;
+
+    fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(&self, mut events: Vec<Event>) -> (Self::Metadata, Self::Events) {
+        let finalizers = events.take_finalizers();
+        let metadata = AzureDataExplorerMetadata {
+            count: events.len(),
+            byte_size: events.size_of(),
+            finalizers,
+        };
+
+        (metadata, events)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let raw_data_size = payload.uncompressed_byte_size;
+        AzureDataExplorerRequest {
+            blob_data: payload.into_payload(),
+            raw_data_size,
+            metadata,
+        }
+    }
+}
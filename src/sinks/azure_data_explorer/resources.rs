@@ -0,0 +1,274 @@
+//! The ingestion resources of the cluster: the storage containers the blobs are uploaded to, the
+//! queues they are announced on, and the token authorizing the cluster to read them.
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use azure_core::auth::TokenCredential;
+use http::{header, Request, Uri};
+use hyper::Body;
+use serde::Deserialize;
+use serde_json::json;
+use snafu::ResultExt;
+use tokio::{sync::Mutex, time::Instant};
+
+use super::{AzureDataExplorerError, BuildRequestSnafu, HttpSnafu, ParseResponseSnafu, TokenSnafu};
+use crate::http::{HttpClient, HttpError};
+
+/// The resources are refreshed as often as the Kusto SDKs do.
+const RESOURCES_REFRESH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+const TEMP_STORAGE: &str = "TempStorage";
+const INGESTION_QUEUE: &str = "SecuredReadyForAggregationQueue";
+
+/// The response to a management command, in the v1 REST API format.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ManagementResponse {
+    tables: Vec<ResponseTable>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ResponseTable {
+    columns: Vec<ResponseColumn>,
+    rows: Vec<Vec<serde_json::Value>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ResponseColumn {
+    column_name: String,
+}
+
+impl ManagementResponse {
+    /// The values of the given columns in the rows of the first table.
+    fn rows<const N: usize>(&self, names: [&str; N]) -> Vec<[&str; N]> {
+        let table = match self.tables.first() {
+            Some(table) => table,
+            None => return Vec::new(),
+        };
+        let indexes = names.map(|name| {
+            table
+                .columns
+                .iter()
+                .position(|column| column.column_name == name)
+        });
+        table
+            .rows
+            .iter()
+            .filter_map(|row| {
+                let mut values = [""; N];
+                for (value, index) in values.iter_mut().zip(indexes) {
+                    *value = row.get(index?)?.as_str()?;
+                }
+                Some(values)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct IngestionResources {
+    /// The SAS URIs of the blob containers.
+    pub containers: Vec<String>,
+    /// The SAS URIs of the queues.
+    pub queues: Vec<String>,
+    /// The token the cluster authorizes the ingestion with.
+    pub authorization_context: String,
+    /// Spreads the blobs and messages over the containers and queues.
+    next: AtomicUsize,
+}
+
+impl IngestionResources {
+    fn from_responses(
+        resources: &ManagementResponse,
+        identity: &ManagementResponse,
+    ) -> Result<Self, AzureDataExplorerError> {
+        let mut containers = Vec::new();
+        let mut queues = Vec::new();
+        for [name, root] in resources.rows(["ResourceTypeName", "StorageRoot"]) {
+            match name {
+                TEMP_STORAGE => containers.push(root.to_owned()),
+                INGESTION_QUEUE => queues.push(root.to_owned()),
+                _ => {}
+            }
+        }
+        if containers.is_empty() {
+            return Err(AzureDataExplorerError::MissingResource { name: TEMP_STORAGE });
+        }
+        if queues.is_empty() {
+            return Err(AzureDataExplorerError::MissingResource {
+                name: INGESTION_QUEUE,
+            });
+        }
+
+        let authorization_context = identity
+            .rows(["AuthorizationContext"])
+            .first()
+            .map(|[token]| (*token).to_owned())
+            .ok_or(AzureDataExplorerError::MissingResource {
+                name: "AuthorizationContext",
+            })?;
+
+        Ok(Self {
+            containers,
+            queues,
+            authorization_context,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// The container and queue to use for the next blob.
+    pub fn next(&self) -> (&str, &str) {
+        let next = self.next.fetch_add(1, Ordering::Relaxed);
+        (
+            self.containers[next % self.containers.len()].as_str(),
+            self.queues[next % self.queues.len()].as_str(),
+        )
+    }
+}
+
+/// Runs the management commands of the ingestion endpoint, and keeps the ingestion resources.
+#[derive(Clone)]
+pub struct IngestionClient {
+    client: HttpClient,
+    endpoint: Uri,
+    /// The Azure Active Directory resource the tokens are requested for.
+    resource: String,
+    credential: Arc<dyn TokenCredential>,
+    resources: Arc<Mutex<Option<(Instant, Arc<IngestionResources>)>>>,
+}
+
+impl IngestionClient {
+    pub fn new(client: HttpClient, endpoint: Uri, credential: Arc<dyn TokenCredential>) -> Self {
+        let resource = endpoint.to_string().trim_end_matches('/').to_owned();
+        Self {
+            client,
+            endpoint,
+            resource,
+            credential,
+            resources: Arc::default(),
+        }
+    }
+
+    /// The ingestion resources, fetched again once they are an hour old.
+    pub async fn resources(&self) -> Result<Arc<IngestionResources>, AzureDataExplorerError> {
+        let mut cached = self.resources.lock().await;
+        if let Some((fetched, resources)) = cached.as_ref() {
+            if fetched.elapsed() < RESOURCES_REFRESH_INTERVAL {
+                return Ok(Arc::clone(resources));
+            }
+        }
+
+        let resources = Arc::new(IngestionResources::from_responses(
+            &self.management_command(".get ingestion resources").await?,
+            &self.management_command(".get kusto identity token").await?,
+        )?);
+        *cached = Some((Instant::now(), Arc::clone(&resources)));
+        Ok(resources)
+    }
+
+    async fn management_command(
+        &self,
+        command: &str,
+    ) -> Result<ManagementResponse, AzureDataExplorerError> {
+        let token = self
+            .credential
+            .get_token(&self.resource)
+            .await
+            .context(TokenSnafu)?;
+        let body = json!({ "db": "NetDefaultDB", "csl": command });
+        let request = Request::post(format!("{}v1/rest/mgmt", self.endpoint))
+            .header(
+                header::AUTHORIZATION,
+                format!("Bearer {}", token.token.secret()),
+            )
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::ACCEPT, "application/json")
+            .body(Body::from(body.to_string()))
+            .context(BuildRequestSnafu)?;
+
+        let response = self.client.send(request).await.context(HttpSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .map_err(|source| AzureDataExplorerError::Http {
+                source: HttpError::CallRequest { source },
+            })?;
+        if !status.is_success() {
+            return Err(AzureDataExplorerError::ResponseStatus {
+                operation: "Management command",
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            });
+        }
+        serde_json::from_slice(&body).context(ParseResponseSnafu)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(json: serde_json::Value) -> ManagementResponse {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn parses_ingestion_resources() {
+        let resources = response(json!({
+            "Tables": [{
+                "TableName": "Table_0",
+                "Columns": [
+                    { "ColumnName": "ResourceTypeName", "DataType": "String" },
+                    { "ColumnName": "StorageRoot", "DataType": "String" },
+                ],
+                "Rows": [
+                    ["SecuredReadyForAggregationQueue", "https://account.queue.core.windows.net/readyforaggregation-secured?sig=1"],
+                    ["FailedIngestionsQueue", "https://account.queue.core.windows.net/failedingestions?sig=2"],
+                    ["TempStorage", "https://account.blob.core.windows.net/first?sig=3"],
+                    ["TempStorage", "https://account.blob.core.windows.net/second?sig=4"],
+                ],
+            }],
+        }));
+        let identity = response(json!({
+            "Tables": [{
+                "TableName": "Table_0",
+                "Columns": [{ "ColumnName": "AuthorizationContext", "DataType": "String" }],
+                "Rows": [["token"]],
+            }],
+        }));
+
+        let resources = IngestionResources::from_responses(&resources, &identity).unwrap();
+        assert_eq!(resources.authorization_context, "token");
+        assert_eq!(
+            resources.next(),
+            (
+                "https://account.blob.core.windows.net/first?sig=3",
+                "https://account.queue.core.windows.net/readyforaggregation-secured?sig=1"
+            )
+        );
+        assert_eq!(
+            resources.next(),
+            (
+                "https://account.blob.core.windows.net/second?sig=4",
+                "https://account.queue.core.windows.net/readyforaggregation-secured?sig=1"
+            )
+        );
+    }
+
+    #[test]
+    fn requires_the_resources() {
+        let empty = response(json!({ "Tables": [] }));
+        assert!(matches!(
+            IngestionResources::from_responses(&empty, &empty),
+            Err(AzureDataExplorerError::MissingResource { name: TEMP_STORAGE })
+        ));
+    }
+}
@@ -0,0 +1,284 @@
+use std::task::{Context, Poll};
+
+use chrono::{SecondsFormat, Utc};
+use futures::future::BoxFuture;
+use http::{header, Request, StatusCode};
+use hyper::Body;
+use serde::Serialize;
+use snafu::ResultExt;
+use tower::Service;
+use tracing::Instrument;
+use uuid::Uuid;
+use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
+
+use super::{
+    request_builder::AzureDataExplorerRequest, resources::IngestionClient, AzureDataExplorerError,
+    BuildRequestSnafu, HttpSnafu,
+};
+use crate::{
+    event::EventStatus,
+    http::HttpClient,
+    sinks::util::{retries::RetryLogic, Compression},
+};
+
+/// The version of the Azure Storage REST API the blobs and messages are sent with.
+const STORAGE_API_VERSION: &str = "2021-08-06";
+
+/// The message telling the cluster which blob to ingest, and how.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct IngestionMessage<'a> {
+    id: Uuid,
+    blob_path: &'a str,
+    raw_data_size: usize,
+    database_name: &'a str,
+    table_name: &'a str,
+    retain_blob_on_success: bool,
+    flush_immediately: bool,
+    report_level: u8,
+    report_method: u8,
+    source_message_creation_time: String,
+    additional_properties: AdditionalProperties<'a>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AdditionalProperties<'a> {
+    authorization_context: &'a str,
+    format: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ingestion_mapping_reference: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ingestion_mapping_type: Option<&'static str>,
+}
+
+/// Inserts the path into the SAS URI of a container or queue, before its query.
+fn resource_uri(root: &str, path: &str) -> String {
+    match root.split_once('?') {
+        Some((base, sas)) => format!("{}/{}?{}", base.trim_end_matches('/'), path, sas),
+        None => format!("{}/{}", root.trim_end_matches('/'), path),
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureDataExplorerResponse {
+    count: usize,
+    events_byte_size: usize,
+    byte_size: usize,
+}
+
+impl DriverResponse for AzureDataExplorerResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(self.count, self.events_byte_size)
+    }
+
+    fn bytes_sent(&self) -> Option<(usize, &str)> {
+        Some((self.byte_size, "https"))
+    }
+}
+
+#[derive(Clone)]
+pub struct AzureDataExplorerService {
+    pub client: HttpClient,
+    pub ingestion: IngestionClient,
+    pub database: String,
+    pub table: String,
+    pub mapping_reference: Option<String>,
+    pub flush_immediately: bool,
+    pub compression: Compression,
+}
+
+impl AzureDataExplorerService {
+    async fn ingest(
+        self,
+        request: AzureDataExplorerRequest,
+    ) -> Result<AzureDataExplorerResponse, AzureDataExplorerError> {
+        let resources = self.ingestion.resources().await?;
+        let (container, queue) = resources.next();
+
+        let id = Uuid::new_v4();
+        let blob_name = format!(
+            "{}__{}__{}.{}",
+            self.database,
+            self.table,
+            id.hyphenated(),
+            // The cluster decompresses the blobs by their extension.
+            match self.compression {
+                Compression::None => "json",
+                _ => "json.gz",
+            }
+        );
+        let blob_path = resource_uri(container, &blob_name);
+        let byte_size = request.blob_data.len();
+
+        let upload = Request::put(&blob_path)
+            .header("x-ms-blob-type", "BlockBlob")
+            .header("x-ms-version", STORAGE_API_VERSION)
+            .header(header::CONTENT_TYPE, "application/octet-stream")
+            .body(Body::from(request.blob_data))
+            .context(BuildRequestSnafu)?;
+        self.send("Blob upload", upload).await?;
+
+        let message = IngestionMessage {
+            id,
+            blob_path: &blob_path,
+            raw_data_size: request.raw_data_size,
+            database_name: &self.database,
+            table_name: &self.table,
+            retain_blob_on_success: false,
+            flush_immediately: self.flush_immediately,
+            report_level: 0,
+            report_method: 0,
+            source_message_creation_time: Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true),
+            additional_properties: AdditionalProperties {
+                authorization_context: &resources.authorization_context,
+                format: "multijson",
+                ingestion_mapping_reference: self.mapping_reference.as_deref(),
+                ingestion_mapping_type: self.mapping_reference.as_ref().map(|_| "json"),
+            },
+        };
+        let message = serde_json::to_vec(&message).expect("the message is serializable");
+        let enqueue = Request::post(resource_uri(queue, "messages"))
+            .header("x-ms-version", STORAGE_API_VERSION)
+            .header(header::CONTENT_TYPE, "application/xml")
+            .body(Body::from(format!(
+                "<QueueMessage><MessageText>{}</MessageText></QueueMessage>",
+                base64::encode(message)
+            )))
+            .context(BuildRequestSnafu)?;
+        self.send("Queue message", enqueue).await?;
+
+        Ok(AzureDataExplorerResponse {
+            count: request.metadata.count,
+            events_byte_size: request.metadata.byte_size,
+            byte_size,
+        })
+    }
+
+    async fn send(
+        &self,
+        operation: &'static str,
+        request: Request<Body>,
+    ) -> Result<(), AzureDataExplorerError> {
+        let response = self.client.send(request).await.context(HttpSnafu)?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .unwrap_or_default();
+        Err(AzureDataExplorerError::ResponseStatus {
+            operation,
+            status,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        })
+    }
+}
+
+impl Service<AzureDataExplorerRequest> for AzureDataExplorerService {
+    type Response = AzureDataExplorerResponse;
+    type Error = AzureDataExplorerError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, request: AzureDataExplorerRequest) -> Self::Future {
+        Box::pin(
+            self.clone()
+                .ingest(request)
+                .instrument(info_span!("request").or_current()),
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AzureDataExplorerRetryLogic;
+
+impl RetryLogic for AzureDataExplorerRetryLogic {
+    type Error = AzureDataExplorerError;
+    type Response = AzureDataExplorerResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            AzureDataExplorerError::Token { .. } => true,
+            AzureDataExplorerError::Http { source } => source.is_retriable(),
+            AzureDataExplorerError::ResponseStatus { status, .. } => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserts_the_path_before_the_sas() {
+        assert_eq!(
+            resource_uri(
+                "https://account.blob.core.windows.net/container?sv=1&sig=2",
+                "blob.json"
+            ),
+            "https://account.blob.core.windows.net/container/blob.json?sv=1&sig=2"
+        );
+        assert_eq!(
+            resource_uri("https://account.queue.core.windows.net/queue/", "messages"),
+            "https://account.queue.core.windows.net/queue/messages"
+        );
+    }
+
+    #[test]
+    fn serializes_the_ingestion_message() {
+        let message = IngestionMessage {
+            id: Uuid::nil(),
+            blob_path: "https://account.blob.core.windows.net/container/blob.json.gz?sig=1",
+            raw_data_size: 42,
+            database_name: "database",
+            table_name: "table",
+            retain_blob_on_success: false,
+            flush_immediately: false,
+            report_level: 0,
+            report_method: 0,
+            source_message_creation_time: "2022-10-14T00:00:00.000000Z".to_owned(),
+            additional_properties: AdditionalProperties {
+                authorization_context: "token",
+                format: "multijson",
+                ingestion_mapping_reference: Some("mapping"),
+                ingestion_mapping_type: Some("json"),
+            },
+        };
+
+        assert_eq!(
+            serde_json::to_value(&message).unwrap(),
+            serde_json::json!({
+                "Id": "00000000-0000-0000-0000-000000000000",
+                "BlobPath": "https://account.blob.core.windows.net/container/blob.json.gz?sig=1",
+                "RawDataSize": 42,
+                "DatabaseName": "database",
+                "TableName": "table",
+                "RetainBlobOnSuccess": false,
+                "FlushImmediately": false,
+                "ReportLevel": 0,
+                "ReportMethod": 0,
+                "SourceMessageCreationTime": "2022-10-14T00:00:00.000000Z",
+                "AdditionalProperties": {
+                    "authorizationContext": "token",
+                    "format": "multijson",
+                    "ingestionMappingReference": "mapping",
+                    "ingestionMappingType": "json",
+                },
+            })
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use std::{fmt, num::NonZeroUsize};
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::Service;
+use vector_core::stream::{BatcherSettings, DriverResponse};
+
+use super::request_builder::{AzureDataExplorerRequest, AzureDataExplorerRequestBuilder};
+use crate::{
+    event::Event,
+    internal_events::SinkRequestBuildError,
+    sinks::util::{SinkBuilderExt, StreamSink},
+};
+
+pub struct AzureDataExplorerSink<S> {
+    pub service: S,
+    pub request_builder: AzureDataExplorerRequestBuilder,
+    pub batch_settings: BatcherSettings,
+}
+
+impl<S> AzureDataExplorerSink<S>
+where
+    S: Service<AzureDataExplorerRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        input
+            .batched(self.batch_settings.into_byte_size_config())
+            .request_builder(NonZeroUsize::new(64), self.request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(request) => Some(request),
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for AzureDataExplorerSink<S>
+where
+    S: Service<AzureDataExplorerRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
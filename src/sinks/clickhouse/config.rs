@@ -1,3 +1,5 @@
+use derivative::Derivative;
+use snafu::Snafu;
 use vector_config::configurable_component;
 
 use crate::{
@@ -11,10 +13,14 @@ use crate::{
         },
         Healthcheck, VectorSink,
     },
+    template::Template,
     tls::TlsConfig,
 };
 
-use super::http_sink::build_http_sink;
+use super::{
+    http_sink::build_http_sink,
+    row_binary::{Column, ColumnType},
+};
 
 /// Configuration for the `clickhouse` sink.
 #[configurable_component(sink("clickhouse"))]
@@ -26,10 +32,30 @@ pub struct ClickhouseConfig {
     pub endpoint: UriSerde,
 
     /// The table that data will be inserted into.
-    pub table: String,
+    ///
+    /// This is a template, allowing the events to be routed to different tables.
+    pub table: Template,
 
     /// The database that contains the table that data will be inserted into.
-    pub database: Option<String>,
+    ///
+    /// This is a template, allowing the events to be routed to different databases.
+    pub database: Option<Template>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub format: Format,
+
+    /// The columns of the table, which the events are encoded as with the `row_binary` format.
+    ///
+    /// The columns must be listed in the order of the table, unless they are a subset of it.
+    #[serde(default)]
+    pub columns: Vec<ColumnConfig>,
+
+    /// Sets `async_insert`, letting Clickhouse buffer the inserted data before writing it to the table.
+    ///
+    /// `wait_for_async_insert` is set as well, so that events are only acknowledged once they are written.
+    #[serde(default)]
+    pub async_insert: bool,
 
     /// Sets `input_format_skip_unknown_fields`, allowing Clickhouse to discard fields not present in the table schema.
     #[serde(default)]
@@ -69,6 +95,77 @@ pub struct ClickhouseConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// The format the events are inserted in.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Format {
+    /// Events are encoded as JSON objects, which Clickhouse converts to the types of the columns.
+    #[derivative(Default)]
+    JsonEachRow,
+
+    /// Events are encoded in the binary representation of the types of the `columns`.
+    ///
+    /// This is faster to insert than JSON, and the values are checked against the types before
+    /// they are sent.
+    RowBinary,
+}
+
+/// A column of the table.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ColumnConfig {
+    /// The name of the column.
+    pub name: String,
+
+    /// The Clickhouse type of the column, such as `String`, `Nullable(Int64)`, or `DateTime64(3)`.
+    #[serde(rename = "type")]
+    pub column_type: String,
+
+    /// The field of the events the values of the column are taken from.
+    ///
+    /// Defaults to the name of the column.
+    pub field: Option<String>,
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("The `row_binary` format requires the `columns` of the table"))]
+    MissingColumns,
+    #[snafu(display("The `columns` are only used by the `row_binary` format"))]
+    UnusedColumns,
+    #[snafu(display("Invalid type of column {:?}: {}", name, reason))]
+    InvalidColumnType { name: String, reason: String },
+}
+
+impl ClickhouseConfig {
+    pub(super) fn build_columns(&self) -> crate::Result<Vec<Column>> {
+        match (self.format, self.columns.is_empty()) {
+            (Format::RowBinary, true) => return Err(BuildError::MissingColumns.into()),
+            (Format::JsonEachRow, false) => return Err(BuildError::UnusedColumns.into()),
+            _ => {}
+        }
+
+        self.columns
+            .iter()
+            .map(|column| {
+                let column_type = column.column_type.parse::<ColumnType>().map_err(|reason| {
+                    BuildError::InvalidColumnType {
+                        name: column.name.clone(),
+                        reason,
+                    }
+                })?;
+                Ok(Column {
+                    name: column.name.clone(),
+                    field: column.field.clone().unwrap_or_else(|| column.name.clone()),
+                    column_type,
+                })
+            })
+            .collect()
+    }
+}
+
 impl_generate_config_from_default!(ClickhouseConfig);
 
 #[async_trait::async_trait]
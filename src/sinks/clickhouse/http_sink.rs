@@ -4,20 +4,27 @@ use http::{Request, StatusCode, Uri};
 use hyper::Body;
 use snafu::ResultExt;
 
-use super::ClickhouseConfig;
+use super::{
+    config::Format,
+    row_binary::{encode_row, Column},
+    ClickhouseConfig,
+};
 use crate::{
     codecs::Transformer,
     config::SinkContext,
     event::Event,
-    http::{HttpClient, HttpError, MaybeAuth},
+    http::{Auth, HttpClient, HttpError, MaybeAuth},
+    internal_events::ClickhouseRowBinaryEncodingError,
     sinks::{
         util::{
-            http::{BatchedHttpSink, HttpEventEncoder, HttpRetryLogic, HttpSink},
+            http::{HttpEventEncoder, HttpRetryLogic, HttpSink, PartitionHttpSink},
             retries::{RetryAction, RetryLogic},
-            Buffer, TowerRequestConfig,
+            Buffer, Compression, PartitionBuffer, PartitionInnerBuffer, TowerRequestConfig,
+            UriSerde,
         },
         Healthcheck, HealthcheckError, UriParseSnafu, VectorSink,
     },
+    template::{Template, TemplateRenderingError},
     tls::TlsSettings,
 };
 
@@ -35,9 +42,22 @@ pub(crate) async fn build_http_sink(
         ..cfg.clone()
     };
 
-    let sink = BatchedHttpSink::with_logic(
-        config.clone(),
-        Buffer::new(batch.size, cfg.compression),
+    let sink = ClickhouseSink {
+        endpoint: config.endpoint.clone(),
+        database: config.database.clone(),
+        table: config.table.clone(),
+        format: config.format,
+        columns: config.build_columns()?,
+        skip_unknown_fields: config.skip_unknown_fields,
+        async_insert: config.async_insert,
+        compression: config.compression,
+        encoding: config.encoding.clone(),
+        auth: config.auth.clone(),
+    };
+
+    let sink = PartitionHttpSink::with_retry_logic(
+        sink,
+        PartitionBuffer::new(Buffer::new(batch.size, cfg.compression)),
         ClickhouseRetryLogic::default(),
         request,
         batch.timeout,
@@ -50,50 +70,129 @@ pub(crate) async fn build_http_sink(
     Ok((VectorSink::from_event_sink(sink), healthcheck))
 }
 
+/// The table that events are inserted into.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PartitionKey {
+    database: String,
+    table: String,
+}
+
+struct ClickhouseSink {
+    endpoint: UriSerde,
+    database: Option<Template>,
+    table: Template,
+    format: Format,
+    columns: Vec<Column>,
+    skip_unknown_fields: bool,
+    async_insert: bool,
+    compression: Compression,
+    encoding: Transformer,
+    auth: Option<Auth>,
+}
+
 pub struct ClickhouseEventEncoder {
     transformer: Transformer,
+    format: Format,
+    columns: Vec<Column>,
+    database: Option<Template>,
+    table: Template,
+}
+
+impl ClickhouseEventEncoder {
+    fn render_key(
+        &self,
+        event: &Event,
+    ) -> Result<PartitionKey, (&'static str, TemplateRenderingError)> {
+        let database = match &self.database {
+            Some(database) => database
+                .render_string(event)
+                .map_err(|error| ("database", error))?,
+            None => "default".to_owned(),
+        };
+        let table = self
+            .table
+            .render_string(event)
+            .map_err(|error| ("table", error))?;
+        Ok(PartitionKey { database, table })
+    }
 }
 
-impl HttpEventEncoder<BytesMut> for ClickhouseEventEncoder {
-    fn encode_event(&mut self, mut event: Event) -> Option<BytesMut> {
+impl HttpEventEncoder<PartitionInnerBuffer<BytesMut, PartitionKey>> for ClickhouseEventEncoder {
+    fn encode_event(
+        &mut self,
+        mut event: Event,
+    ) -> Option<PartitionInnerBuffer<BytesMut, PartitionKey>> {
+        let key = self
+            .render_key(&event)
+            .map_err(|(field, error)| {
+                emit!(crate::internal_events::TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+
         self.transformer.transform(&mut event);
         let log = event.into_log();
 
-        let mut body = crate::serde::json::to_bytes(&log).expect("Events should be valid json!");
-        body.put_u8(b'\n');
+        let body = match self.format {
+            Format::JsonEachRow => {
+                let mut body =
+                    crate::serde::json::to_bytes(&log).expect("Events should be valid json!");
+                body.put_u8(b'\n');
+                body
+            }
+            Format::RowBinary => {
+                let mut body = BytesMut::new();
+                if let Err(error) = encode_row(&self.columns, &log, &mut body) {
+                    emit!(ClickhouseRowBinaryEncodingError { error });
+                    return None;
+                }
+                body
+            }
+        };
 
-        Some(body)
+        Some(PartitionInnerBuffer::new(body, key))
     }
 }
 
 #[async_trait::async_trait]
-impl HttpSink for ClickhouseConfig {
-    type Input = BytesMut;
-    type Output = BytesMut;
+impl HttpSink for ClickhouseSink {
+    type Input = PartitionInnerBuffer<BytesMut, PartitionKey>;
+    type Output = PartitionInnerBuffer<BytesMut, PartitionKey>;
     type Encoder = ClickhouseEventEncoder;
 
     fn build_encoder(&self) -> Self::Encoder {
         ClickhouseEventEncoder {
             transformer: self.encoding.clone(),
+            format: self.format,
+            columns: self.columns.clone(),
+            database: self.database.clone(),
+            table: self.table.clone(),
         }
     }
 
-    async fn build_request(&self, events: Self::Output) -> crate::Result<http::Request<Bytes>> {
-        let database = if let Some(database) = &self.database {
-            database.as_str()
-        } else {
-            "default"
-        };
+    async fn build_request(&self, output: Self::Output) -> crate::Result<http::Request<Bytes>> {
+        let (events, key) = output.into_parts();
 
         let uri = set_uri_query(
             &self.endpoint.with_default_parts().uri,
-            database,
-            &self.table,
-            self.skip_unknown_fields,
-        )
-        .expect("Unable to encode uri");
-
-        let mut builder = Request::post(&uri).header("Content-Type", "application/x-ndjson");
+            &key.database,
+            &key.table,
+            &InsertSettings {
+                format: self.format,
+                columns: &self.columns,
+                skip_unknown_fields: self.skip_unknown_fields,
+                async_insert: self.async_insert,
+            },
+        )?;
+
+        let content_type = match self.format {
+            Format::JsonEachRow => "application/x-ndjson",
+            Format::RowBinary => "application/octet-stream",
+        };
+        let mut builder = Request::post(&uri).header("Content-Type", content_type);
 
         if let Some(ce) = self.compression.content_encoding() {
             builder = builder.header("Content-Encoding", ce);
@@ -126,26 +225,57 @@ async fn healthcheck(client: HttpClient, config: ClickhouseConfig) -> crate::Res
     }
 }
 
-fn set_uri_query(uri: &Uri, database: &str, table: &str, skip_unknown: bool) -> crate::Result<Uri> {
+/// How the events are inserted.
+struct InsertSettings<'a> {
+    format: Format,
+    columns: &'a [Column],
+    skip_unknown_fields: bool,
+    async_insert: bool,
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('\"', "\\\""))
+}
+
+fn set_uri_query(
+    uri: &Uri,
+    database: &str,
+    table: &str,
+    settings: &InsertSettings<'_>,
+) -> crate::Result<Uri> {
+    let mut insert = format!(
+        "INSERT INTO {}.{}",
+        quote_identifier(database),
+        quote_identifier(table)
+    );
+    match settings.format {
+        Format::JsonEachRow => insert.push_str(" FORMAT JSONEachRow"),
+        Format::RowBinary => {
+            let columns = settings
+                .columns
+                .iter()
+                .map(|column| quote_identifier(&column.name))
+                .collect::<Vec<_>>();
+            insert.push_str(&format!(" ({}) FORMAT RowBinary", columns.join(", ")));
+        }
+    }
     let query = url::form_urlencoded::Serializer::new(String::new())
-        .append_pair(
-            "query",
-            format!(
-                "INSERT INTO \"{}\".\"{}\" FORMAT JSONEachRow",
-                database,
-                table.replace('\"', "\\\"")
-            )
-            .as_str(),
-        )
+        .append_pair("query", insert.as_str())
         .finish();
 
     let mut uri = uri.to_string();
     if !uri.ends_with('/') {
         uri.push('/');
     }
-    uri.push_str("?input_format_import_nested_json=1&");
-    if skip_unknown {
-        uri.push_str("input_format_skip_unknown_fields=1&");
+    uri.push('?');
+    if settings.format == Format::JsonEachRow {
+        uri.push_str("input_format_import_nested_json=1&");
+        if settings.skip_unknown_fields {
+            uri.push_str("input_format_skip_unknown_fields=1&");
+        }
+    }
+    if settings.async_insert {
+        uri.push_str("async_insert=1&wait_for_async_insert=1&");
     }
     uri.push_str(query.as_str());
 
@@ -184,6 +314,8 @@ impl RetryLogic for ClickhouseRetryLogic {
                     RetryAction::DontRetry("incorrect data".into())
                 } else if body.starts_with(b"Code: 53") {
                     RetryAction::DontRetry("type mismatch".into())
+                } else if body.starts_with(b"Code: 33") {
+                    RetryAction::DontRetry("cannot read all data".into())
                 } else {
                     RetryAction::Retry(String::from_utf8_lossy(body).to_string().into())
                 }
@@ -196,6 +328,16 @@ impl RetryLogic for ClickhouseRetryLogic {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sinks::clickhouse::row_binary::ColumnType;
+
+    fn json_settings() -> InsertSettings<'static> {
+        InsertSettings {
+            format: Format::JsonEachRow,
+            columns: &[],
+            skip_unknown_fields: false,
+            async_insert: false,
+        }
+    }
 
     #[test]
     fn generate_config() {
@@ -208,7 +350,7 @@ mod tests {
             &"http://localhost:80".parse().unwrap(),
             "my_database",
             "my_table",
-            false,
+            &json_settings(),
         )
         .unwrap();
         assert_eq!(uri.to_string(), "http://localhost:80/?input_format_import_nested_json=1&query=INSERT+INTO+%22my_database%22.%22my_table%22+FORMAT+JSONEachRow");
@@ -217,19 +359,70 @@ mod tests {
             &"http://localhost:80".parse().unwrap(),
             "my_database",
             "my_\"table\"",
-            false,
+            &json_settings(),
         )
         .unwrap();
         assert_eq!(uri.to_string(), "http://localhost:80/?input_format_import_nested_json=1&query=INSERT+INTO+%22my_database%22.%22my_%5C%22table%5C%22%22+FORMAT+JSONEachRow");
     }
 
+    #[test]
+    fn encode_row_binary() {
+        let columns = [
+            Column {
+                name: "message".to_owned(),
+                field: "message".to_owned(),
+                column_type: ColumnType::String,
+            },
+            Column {
+                name: "count".to_owned(),
+                field: "count".to_owned(),
+                column_type: ColumnType::UInt64,
+            },
+        ];
+        let uri = set_uri_query(
+            &"http://localhost:80".parse().unwrap(),
+            "my_database",
+            "my_table",
+            &InsertSettings {
+                format: Format::RowBinary,
+                columns: &columns,
+                skip_unknown_fields: true,
+                async_insert: true,
+            },
+        )
+        .unwrap();
+        assert_eq!(uri.to_string(), "http://localhost:80/?async_insert=1&wait_for_async_insert=1&query=INSERT+INTO+%22my_database%22.%22my_table%22+%28%22message%22%2C+%22count%22%29+FORMAT+RowBinary");
+    }
+
+    #[test]
+    fn render_table_templates() {
+        let encoder = ClickhouseEventEncoder {
+            transformer: Default::default(),
+            format: Format::JsonEachRow,
+            columns: Vec::new(),
+            database: None,
+            table: Template::try_from("logs_{{ service }}").unwrap(),
+        };
+        let mut event = Event::from("hello");
+        event.as_mut_log().insert("service", "api");
+
+        assert_eq!(
+            encoder.render_key(&event).unwrap(),
+            PartitionKey {
+                database: "default".to_owned(),
+                table: "logs_api".to_owned(),
+            }
+        );
+        assert!(encoder.render_key(&Event::from("hello")).is_err());
+    }
+
     #[test]
     fn encode_invalid() {
         set_uri_query(
             &"localhost:80".parse().unwrap(),
             "my_database",
             "my_table",
-            false,
+            &json_settings(),
         )
         .unwrap_err();
     }
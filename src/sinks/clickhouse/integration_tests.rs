@@ -18,7 +18,10 @@ use tokio::time::{timeout, Duration};
 use vector_core::event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, LogEvent};
 use warp::Filter;
 
-use super::*;
+use super::{
+    config::{ColumnConfig, Format},
+    *,
+};
 use crate::{
     codecs::{TimestampFormat, Transformer},
     config::{log_schema, SinkConfig, SinkContext},
@@ -45,7 +48,7 @@ async fn insert_events() {
 
     let config = ClickhouseConfig {
         endpoint: host.parse().unwrap(),
-        table: table.clone(),
+        table: table.clone().try_into().unwrap(),
         compression: Compression::None,
         batch,
         request: TowerRequestConfig {
@@ -98,7 +101,7 @@ async fn skip_unknown_fields() {
 
     let config = ClickhouseConfig {
         endpoint: host.parse().unwrap(),
-        table: table.clone(),
+        table: table.clone().try_into().unwrap(),
         skip_unknown_fields: true,
         compression: Compression::None,
         batch,
@@ -148,7 +151,7 @@ async fn insert_events_unix_timestamps() {
 
     let config = ClickhouseConfig {
         endpoint: host.parse().unwrap(),
-        table: table.clone(),
+        table: table.clone().try_into().unwrap(),
         compression: Compression::None,
         encoding: Transformer::new(None, None, Some(TimestampFormat::Unix)).unwrap(),
         batch,
@@ -273,7 +276,7 @@ async fn no_retry_on_incorrect_data() {
 
     let config = ClickhouseConfig {
         endpoint: host.parse().unwrap(),
-        table: table.clone(),
+        table: table.clone().try_into().unwrap(),
         compression: Compression::None,
         batch,
         ..Default::default()
@@ -324,7 +327,7 @@ async fn no_retry_on_incorrect_data_warp() {
 
     let config = ClickhouseConfig {
         endpoint: host.parse().unwrap(),
-        table: gen_table(),
+        table: gen_table().try_into().unwrap(),
         batch,
         ..Default::default()
     };
@@ -342,6 +345,84 @@ async fn no_retry_on_incorrect_data_warp() {
     assert_eq!(receiver.try_recv(), Ok(BatchStatus::Errored));
 }
 
+#[tokio::test]
+async fn insert_events_row_binary() {
+    trace_init();
+
+    let table = gen_table();
+    let host = clickhouse_address();
+
+    let mut batch = BatchConfig::default();
+    batch.max_events = Some(1);
+
+    let column = |name: &str, column_type: &str| ColumnConfig {
+        name: name.to_owned(),
+        column_type: column_type.to_owned(),
+        field: None,
+    };
+    let config = ClickhouseConfig {
+        endpoint: host.parse().unwrap(),
+        table: table.clone().try_into().unwrap(),
+        format: Format::RowBinary,
+        columns: vec![
+            column("host", "LowCardinality(String)"),
+            column("timestamp", "DateTime64(3, 'UTC')"),
+            column("message", "String"),
+            column("count", "UInt32"),
+            column("missing", "Nullable(String)"),
+        ],
+        async_insert: true,
+        compression: Compression::None,
+        batch,
+        request: TowerRequestConfig {
+            retry_attempts: Some(1),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let client = ClickhouseClient::new(host);
+    client
+        .create_table(
+            &table,
+            "host LowCardinality(String), timestamp DateTime64(3, 'UTC'), message String, count UInt32, missing Nullable(String)",
+        )
+        .await;
+
+    let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let (mut input_event, mut receiver) = make_event();
+    input_event.as_mut_log().insert("count", 42);
+
+    run_and_assert_sink_compliance(
+        sink,
+        stream::once(ready(input_event.clone())),
+        &HTTP_SINK_TAGS,
+    )
+    .await;
+
+    let output = client.select_all(&table).await;
+    assert_eq!(1, output.rows);
+
+    let log = input_event.as_log();
+    let row = &output.data[0];
+    assert_eq!(row["host"], "example.com");
+    assert_eq!(row["message"], "raw log line");
+    assert_eq!(row["count"], 42);
+    assert_eq!(row["missing"], Value::Null);
+    assert_eq!(
+        row["timestamp"],
+        log.get(log_schema().timestamp_key())
+            .unwrap()
+            .as_timestamp()
+            .unwrap()
+            .format("%Y-%m-%d %H:%M:%S%.3f")
+            .to_string()
+    );
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+}
+
 fn make_event() -> (Event, BatchStatusReceiver) {
     let (batch, receiver) = BatchNotifier::new_with_receiver();
     let mut event = LogEvent::from("raw log line").with_batch_notifier(&batch);
@@ -2,4 +2,6 @@ mod config;
 mod http_sink;
 #[cfg(all(test, feature = "clickhouse-integration-tests"))]
 mod integration_tests;
+mod row_binary;
 pub use self::config::ClickhouseConfig;
+pub use self::row_binary::EncodingError;
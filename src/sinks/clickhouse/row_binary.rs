@@ -0,0 +1,498 @@
+//! Encoding of events in the [`RowBinary`][row_binary] format, according to the types of the
+//! columns of the table.
+//!
+//! [row_binary]: https://clickhouse.com/docs/en/interfaces/formats/#rowbinary
+
+use std::{
+    net::{Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use snafu::Snafu;
+use vector_core::event::{LogEvent, Value};
+
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub enum EncodingError {
+    #[snafu(display("Column {:?}: cannot encode {} as {:?}", column, value, column_type))]
+    InvalidValue {
+        column: String,
+        column_type: ColumnType,
+        value: String,
+    },
+}
+
+/// The ClickHouse types columns can be encoded as.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ColumnType {
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Float32,
+    Float64,
+    Bool,
+    String,
+    FixedString(usize),
+    Uuid,
+    Date,
+    Date32,
+    DateTime,
+    DateTime64(u32),
+    Ipv4,
+    Ipv6,
+    Nullable(Box<ColumnType>),
+    Array(Box<ColumnType>),
+    Map(Box<ColumnType>, Box<ColumnType>),
+}
+
+impl FromStr for ColumnType {
+    type Err = String;
+
+    fn from_str(column_type: &str) -> Result<Self, Self::Err> {
+        let column_type = column_type.trim();
+        let (name, arguments) = match column_type.split_once('(') {
+            Some((name, arguments)) => (
+                name.trim(),
+                Some(
+                    arguments
+                        .strip_suffix(')')
+                        .ok_or_else(|| format!("unbalanced parentheses in {:?}", column_type))?,
+                ),
+            ),
+            None => (column_type, None),
+        };
+
+        let simple = match name {
+            "Int8" => Some(Self::Int8),
+            "Int16" => Some(Self::Int16),
+            "Int32" => Some(Self::Int32),
+            "Int64" => Some(Self::Int64),
+            "UInt8" => Some(Self::UInt8),
+            "UInt16" => Some(Self::UInt16),
+            "UInt32" => Some(Self::UInt32),
+            "UInt64" => Some(Self::UInt64),
+            "Float32" => Some(Self::Float32),
+            "Float64" => Some(Self::Float64),
+            "Bool" | "Boolean" => Some(Self::Bool),
+            "String" => Some(Self::String),
+            "UUID" => Some(Self::Uuid),
+            "Date" => Some(Self::Date),
+            "Date32" => Some(Self::Date32),
+            "IPv4" => Some(Self::Ipv4),
+            "IPv6" => Some(Self::Ipv6),
+            _ => None,
+        };
+        if let Some(simple) = simple {
+            return match arguments {
+                None => Ok(simple),
+                Some(_) => Err(format!("unexpected arguments in {:?}", column_type)),
+            };
+        }
+
+        let arguments = arguments.map(split_arguments).unwrap_or_default();
+        let invalid = || format!("invalid type {:?}", column_type);
+        match (name, arguments.as_slice()) {
+            // The time zone only matters when reading the values.
+            ("DateTime", [] | [_]) => Ok(Self::DateTime),
+            ("DateTime64", [precision] | [precision, _]) => match precision.parse() {
+                Ok(precision) if precision <= 9 => Ok(Self::DateTime64(precision)),
+                _ => Err(invalid()),
+            },
+            ("FixedString", [length]) => {
+                length.parse().map(Self::FixedString).map_err(|_| invalid())
+            }
+            ("Nullable", [inner]) => Ok(Self::Nullable(Box::new(inner.parse()?))),
+            ("Array", [inner]) => Ok(Self::Array(Box::new(inner.parse()?))),
+            // Low cardinality columns are encoded like the type they wrap.
+            ("LowCardinality", [inner]) => inner.parse(),
+            ("Map", [key, value]) => {
+                Ok(Self::Map(Box::new(key.parse()?), Box::new(value.parse()?)))
+            }
+            _ => Err(format!("unsupported type {:?}", column_type)),
+        }
+    }
+}
+
+/// Splits the arguments of a type on the commas which are not nested in other types.
+fn split_arguments(arguments: &str) -> Vec<&str> {
+    let mut split = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, c) in arguments.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                split.push(arguments[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(arguments[start..].trim());
+    split
+}
+
+/// A column of the table, and the field of the events its values are taken from.
+#[derive(Clone, Debug)]
+pub struct Column {
+    pub name: String,
+    pub field: String,
+    pub column_type: ColumnType,
+}
+
+/// Encodes the fields of the event as a row of the given columns.
+///
+/// Missing fields are encoded as `NULL` in nullable columns, and as the default value of the type
+/// otherwise, like ClickHouse does for the fields missing from `JSONEachRow` rows.
+pub fn encode_row(
+    columns: &[Column],
+    log: &LogEvent,
+    buffer: &mut BytesMut,
+) -> Result<(), EncodingError> {
+    for column in columns {
+        let value = log.get(column.field.as_str()).unwrap_or(&Value::Null);
+        encode_value(&column.column_type, value, buffer).map_err(|()| {
+            EncodingError::InvalidValue {
+                column: column.name.clone(),
+                column_type: column.column_type.clone(),
+                value: value.to_string_lossy(),
+            }
+        })?;
+    }
+    Ok(())
+}
+
+fn encode_value(column_type: &ColumnType, value: &Value, buffer: &mut BytesMut) -> Result<(), ()> {
+    if let ColumnType::Nullable(inner) = column_type {
+        return match value {
+            Value::Null => {
+                buffer.put_u8(1);
+                Ok(())
+            }
+            value => {
+                buffer.put_u8(0);
+                encode_value(inner, value, buffer)
+            }
+        };
+    }
+    if let Value::Null = value {
+        encode_default(column_type, buffer);
+        return Ok(());
+    }
+
+    match column_type {
+        ColumnType::Int8 => buffer.put_i8(integer(value)?.try_into().map_err(drop)?),
+        ColumnType::Int16 => buffer.put_i16_le(integer(value)?.try_into().map_err(drop)?),
+        ColumnType::Int32 => buffer.put_i32_le(integer(value)?.try_into().map_err(drop)?),
+        ColumnType::Int64 => buffer.put_i64_le(integer(value)?.try_into().map_err(drop)?),
+        ColumnType::UInt8 => buffer.put_u8(integer(value)?.try_into().map_err(drop)?),
+        ColumnType::UInt16 => buffer.put_u16_le(integer(value)?.try_into().map_err(drop)?),
+        ColumnType::UInt32 => buffer.put_u32_le(integer(value)?.try_into().map_err(drop)?),
+        ColumnType::UInt64 => buffer.put_u64_le(integer(value)?.try_into().map_err(drop)?),
+        ColumnType::Float32 => buffer.put_f32_le(float(value)? as f32),
+        ColumnType::Float64 => buffer.put_f64_le(float(value)?),
+        ColumnType::Bool => buffer.put_u8(match value {
+            Value::Boolean(boolean) => u8::from(*boolean),
+            Value::Integer(integer @ (0 | 1)) => *integer as u8,
+            Value::Bytes(bytes) => match bytes.as_ref() {
+                b"true" => 1,
+                b"false" => 0,
+                _ => return Err(()),
+            },
+            _ => return Err(()),
+        }),
+        ColumnType::String => match value {
+            Value::Bytes(bytes) => put_string(buffer, bytes),
+            value => put_string(buffer, value.to_string_lossy().as_bytes()),
+        },
+        ColumnType::FixedString(length) => {
+            let string = match value {
+                Value::Bytes(bytes) => bytes.to_vec(),
+                value => value.to_string_lossy().into_bytes(),
+            };
+            if string.len() > *length {
+                return Err(());
+            }
+            buffer.put_slice(&string);
+            buffer.put_bytes(0, length - string.len());
+        }
+        ColumnType::Uuid => {
+            let uuid = uuid::Uuid::parse_str(&string(value)?).map_err(drop)?;
+            // The two halves are encoded as little-endian 64-bit integers.
+            let (high, low) = uuid.as_u64_pair();
+            buffer.put_u64_le(high);
+            buffer.put_u64_le(low);
+        }
+        ColumnType::Date => {
+            buffer.put_u16_le(days_since_epoch(value)?.try_into().map_err(drop)?);
+        }
+        ColumnType::Date32 => {
+            buffer.put_i32_le(days_since_epoch(value)?.try_into().map_err(drop)?);
+        }
+        ColumnType::DateTime => {
+            buffer.put_u32_le(timestamp(value)?.timestamp().try_into().map_err(drop)?);
+        }
+        ColumnType::DateTime64(precision) => {
+            let timestamp = timestamp(value)?;
+            let ticks = i128::from(timestamp.timestamp()) * 10_i128.pow(*precision)
+                + i128::from(timestamp.timestamp_subsec_nanos()) / 10_i128.pow(9 - precision);
+            buffer.put_i64_le(ticks.try_into().map_err(drop)?);
+        }
+        ColumnType::Ipv4 => {
+            let address = Ipv4Addr::from_str(&string(value)?).map_err(drop)?;
+            buffer.put_u32_le(address.into());
+        }
+        ColumnType::Ipv6 => {
+            let address = match Ipv6Addr::from_str(&string(value)?) {
+                Ok(address) => address,
+                Err(_) => Ipv4Addr::from_str(&string(value)?)
+                    .map_err(drop)?
+                    .to_ipv6_mapped(),
+            };
+            buffer.put_slice(&address.octets());
+        }
+        ColumnType::Array(inner) => match value {
+            Value::Array(values) => {
+                put_length(buffer, values.len());
+                for value in values {
+                    encode_value(inner, value, buffer)?;
+                }
+            }
+            _ => return Err(()),
+        },
+        ColumnType::Map(key_type, value_type) => match value {
+            Value::Object(map) => {
+                put_length(buffer, map.len());
+                for (key, value) in map {
+                    encode_value(key_type, &Value::from(key.as_str()), buffer)?;
+                    encode_value(value_type, value, buffer)?;
+                }
+            }
+            _ => return Err(()),
+        },
+        ColumnType::Nullable(_) => unreachable!("nullable values are encoded above"),
+    }
+    Ok(())
+}
+
+fn encode_default(column_type: &ColumnType, buffer: &mut BytesMut) {
+    match column_type {
+        ColumnType::Int8 | ColumnType::UInt8 | ColumnType::Bool => buffer.put_u8(0),
+        ColumnType::Int16 | ColumnType::UInt16 | ColumnType::Date => buffer.put_u16_le(0),
+        ColumnType::Int32
+        | ColumnType::UInt32
+        | ColumnType::Float32
+        | ColumnType::Date32
+        | ColumnType::DateTime
+        | ColumnType::Ipv4 => buffer.put_u32_le(0),
+        ColumnType::Int64
+        | ColumnType::UInt64
+        | ColumnType::Float64
+        | ColumnType::DateTime64(_) => buffer.put_u64_le(0),
+        ColumnType::Uuid | ColumnType::Ipv6 => buffer.put_bytes(0, 16),
+        ColumnType::FixedString(length) => buffer.put_bytes(0, *length),
+        // Empty strings, arrays, and maps
+        ColumnType::String | ColumnType::Array(_) | ColumnType::Map(..) => put_length(buffer, 0),
+        ColumnType::Nullable(_) => buffer.put_u8(1),
+    }
+}
+
+fn integer(value: &Value) -> Result<i128, ()> {
+    match value {
+        Value::Integer(integer) => Ok((*integer).into()),
+        Value::Float(float) if float.fract() == 0.0 => Ok(float.into_inner() as i128),
+        Value::Boolean(boolean) => Ok((*boolean).into()),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .map_err(drop)?
+            .trim()
+            .parse()
+            .map_err(drop),
+        _ => Err(()),
+    }
+}
+
+fn float(value: &Value) -> Result<f64, ()> {
+    match value {
+        Value::Integer(integer) => Ok(*integer as f64),
+        Value::Float(float) => Ok(float.into_inner()),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .map_err(drop)?
+            .trim()
+            .parse()
+            .map_err(drop),
+        _ => Err(()),
+    }
+}
+
+fn string(value: &Value) -> Result<String, ()> {
+    match value {
+        Value::Bytes(bytes) => String::from_utf8(bytes.to_vec()).map_err(drop),
+        _ => Err(()),
+    }
+}
+
+/// Timestamps may also be given as seconds since the Unix epoch, or as RFC 3339 strings.
+fn timestamp(value: &Value) -> Result<DateTime<Utc>, ()> {
+    match value {
+        Value::Timestamp(timestamp) => Ok(*timestamp),
+        Value::Integer(seconds) => Utc.timestamp_opt(*seconds, 0).single().ok_or(()),
+        Value::Bytes(_) => DateTime::parse_from_rfc3339(&string(value)?)
+            .map(|timestamp| timestamp.with_timezone(&Utc))
+            .map_err(drop),
+        _ => Err(()),
+    }
+}
+
+fn days_since_epoch(value: &Value) -> Result<i64, ()> {
+    let epoch = NaiveDate::from_ymd(1970, 1, 1);
+    let date = match value {
+        Value::Bytes(_) => match NaiveDate::from_str(&string(value)?) {
+            Ok(date) => date,
+            Err(_) => timestamp(value)?.naive_utc().date(),
+        },
+        value => timestamp(value)?.naive_utc().date(),
+    };
+    Ok((date - epoch).num_days())
+}
+
+fn put_length(buffer: &mut BytesMut, mut length: usize) {
+    // LEB128
+    loop {
+        let byte = (length & 0x7f) as u8;
+        length >>= 7;
+        if length == 0 {
+            buffer.put_u8(byte);
+            break;
+        }
+        buffer.put_u8(byte | 0x80);
+    }
+}
+
+fn put_string(buffer: &mut BytesMut, string: &[u8]) {
+    put_length(buffer, string.len());
+    buffer.put_slice(string);
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::Event;
+
+    use super::*;
+
+    fn column(name: &str, column_type: &str) -> Column {
+        Column {
+            name: name.to_owned(),
+            field: name.to_owned(),
+            column_type: column_type.parse().unwrap(),
+        }
+    }
+
+    fn encode(columns: &[Column], log: &LogEvent) -> Result<Vec<u8>, EncodingError> {
+        let mut buffer = BytesMut::new();
+        encode_row(columns, log, &mut buffer)?;
+        Ok(buffer.to_vec())
+    }
+
+    #[test]
+    fn parses_types() {
+        assert_eq!(
+            "Map(LowCardinality(String), Array(Nullable(Int64)))".parse(),
+            Ok(ColumnType::Map(
+                Box::new(ColumnType::String),
+                Box::new(ColumnType::Array(Box::new(ColumnType::Nullable(Box::new(
+                    ColumnType::Int64
+                )))))
+            ))
+        );
+        assert_eq!(
+            "DateTime64(3, 'Europe/Berlin')".parse(),
+            Ok(ColumnType::DateTime64(3))
+        );
+        assert_eq!("DateTime('UTC')".parse(), Ok(ColumnType::DateTime));
+        assert_eq!("FixedString(4)".parse(), Ok(ColumnType::FixedString(4)));
+        for invalid in [
+            "Int256",
+            "Array(String",
+            "Nullable()",
+            "DateTime64(10)",
+            "String(1)",
+        ] {
+            assert!(invalid.parse::<ColumnType>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn encodes_rows() {
+        let mut log = Event::from("hello").into_log();
+        log.insert("count", 300);
+        log.insert("ratio", "0.5");
+        log.insert("tags", vec!["a", "b"]);
+        log.insert(
+            "timestamp",
+            Utc.ymd(2022, 10, 14).and_hms_milli(12, 0, 0, 123),
+        );
+
+        let columns = [
+            column("message", "String"),
+            column("count", "UInt16"),
+            column("ratio", "Float32"),
+            column("tags", "Array(LowCardinality(String))"),
+            column("timestamp", "DateTime64(3)"),
+            column("missing", "Nullable(String)"),
+            column("default", "UInt8"),
+        ];
+
+        let mut expected = vec![5];
+        expected.extend(b"hello");
+        expected.extend(300_u16.to_le_bytes());
+        expected.extend(0.5_f32.to_le_bytes());
+        expected.extend([2, 1, b'a', 1, b'b']);
+        expected.extend(1_665_748_800_123_i64.to_le_bytes());
+        expected.extend([1, 0]);
+        assert_eq!(encode(&columns, &log), Ok(expected));
+    }
+
+    #[test]
+    fn encodes_special_types() {
+        let mut log = LogEvent::default();
+        log.insert("uuid", "61f0c404-5cb3-11e7-907b-a6006ad3dba0");
+        log.insert("ip", "192.168.0.1");
+        log.insert("date", "2022-10-14");
+        log.insert("code", "ab");
+
+        let columns = [
+            column("uuid", "UUID"),
+            column("ip", "IPv4"),
+            column("ip", "IPv6"),
+            column("date", "Date"),
+            column("code", "FixedString(3)"),
+        ];
+
+        let mut expected = Vec::new();
+        expected.extend(0x61f0c4045cb311e7_u64.to_le_bytes());
+        expected.extend(0x907ba6006ad3dba0_u64.to_le_bytes());
+        expected.extend([1, 0, 168, 192]);
+        expected.extend([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 192, 168, 0, 1]);
+        expected.extend(19_279_u16.to_le_bytes());
+        expected.extend([b'a', b'b', 0]);
+        assert_eq!(encode(&columns, &log), Ok(expected));
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        let mut log = LogEvent::default();
+        log.insert("count", 300);
+        log.insert("name", "vector");
+
+        assert!(encode(&[column("count", "UInt8")], &log).is_err());
+        assert!(encode(&[column("name", "Int64")], &log).is_err());
+        assert!(encode(&[column("name", "FixedString(3)")], &log).is_err());
+    }
+}
@@ -0,0 +1,361 @@
+use derivative::Derivative;
+use futures::FutureExt;
+use http::{uri::Scheme, Uri};
+use indoc::indoc;
+use snafu::{ResultExt, Snafu};
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity};
+use tower::ServiceBuilder;
+use vector_config::configurable_component;
+
+use super::{
+    schema::{Column, RowEncoder},
+    service::{BigqueryClient, BigqueryRetryLogic, BigqueryService, Destination},
+    sink::BigquerySink,
+};
+use crate::{
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    gcp::{GcpAuthConfig, Scope, BIGQUERY_STORAGE_URL},
+    sinks::{
+        util::{BatchConfig, ServiceBuilderExt, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck, UriParseSnafu, VectorSink,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+// 10MB maximum request size: https://cloud.google.com/bigquery/quotas#write-api-limits
+const MAX_BATCH_PAYLOAD_SIZE: usize = 10_000_000;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BigqueryDefaultBatchSettings;
+
+impl SinkBatchSettings for BigqueryDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(10_000);
+    // Batches are sized by the events rather than the encoded rows, which are smaller.
+    const MAX_BYTES: Option<usize> = Some(MAX_BATCH_PAYLOAD_SIZE);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("The `schema` of the table must have at least one column"))]
+    MissingSchema,
+    #[snafu(display("Could not create endpoint: {}", source))]
+    Endpoint { source: tonic::transport::Error },
+    #[snafu(display("Could not set up endpoint TLS settings: {}", source))]
+    EndpointTls { source: tonic::transport::Error },
+}
+
+/// Configuration for the `gcp_bigquery` sink.
+#[configurable_component(sink("gcp_bigquery"))]
+#[derive(Clone, Debug)]
+pub struct BigqueryConfig {
+    /// The project of the table.
+    pub project: String,
+
+    /// The dataset of the table.
+    pub dataset: String,
+
+    /// The table to write the events to.
+    pub table: String,
+
+    /// The columns of the table, and the fields of the events their values are taken from.
+    ///
+    /// Events whose fields don't match the types of the columns are rejected, or written to the
+    /// `dead_letter_table`.
+    pub schema: Vec<ColumnConfig>,
+
+    /// A table of the same dataset to write the rejected events to.
+    ///
+    /// The table must have an `event` column of type `JSON` or `STRING`, holding the event encoded
+    /// as JSON, and an `error` column of type `STRING`, holding the reason it was rejected.
+    ///
+    /// If not set, the rejected events are dropped.
+    #[serde(default)]
+    pub dead_letter_table: Option<String>,
+
+    /// The endpoint of the Storage Write API.
+    #[serde(default)]
+    pub endpoint: Option<String>,
+
+    #[serde(default, flatten)]
+    pub auth: GcpAuthConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<BigqueryDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+/// A column of the table.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ColumnConfig {
+    /// The name of the column.
+    pub name: String,
+
+    #[configurable(derived)]
+    #[serde(rename = "type")]
+    pub column_type: ColumnType,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: ColumnMode,
+
+    /// The field of the events the values of the column are taken from.
+    ///
+    /// Defaults to the name of the column.
+    pub field: Option<String>,
+}
+
+/// The BigQuery type of a column.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnType {
+    /// A `STRING` column.
+    ///
+    /// Numbers, booleans, and timestamps are written as their text.
+    String,
+
+    /// A `BYTES` column.
+    Bytes,
+
+    /// An `INT64` column.
+    Int64,
+
+    /// A `FLOAT64` column.
+    Float64,
+
+    /// A `BOOL` column.
+    Bool,
+
+    /// A `TIMESTAMP` column.
+    ///
+    /// Timestamps may also be given as seconds since the Unix epoch, or as RFC 3339 strings.
+    Timestamp,
+
+    /// A `DATE` column.
+    ///
+    /// Dates may also be given as timestamps, or as `YYYY-MM-DD` strings.
+    Date,
+
+    /// A `JSON` column.
+    ///
+    /// Values are written as JSON, so that objects and arrays may be stored.
+    Json,
+}
+
+/// The mode of a column.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnMode {
+    /// The column may be `NULL`, which missing fields are written as.
+    #[derivative(Default)]
+    Nullable,
+
+    /// Events missing the field are rejected.
+    Required,
+
+    /// The column is an array, written from array fields.
+    Repeated,
+}
+
+impl GenerateConfig for BigqueryConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            project = "my-project"
+            dataset = "my-dataset"
+            table = "my-table"
+            schema = [{ name = "message", type = "string" }]
+        "#})
+        .unwrap()
+    }
+}
+
+impl BigqueryConfig {
+    fn build_columns(&self) -> crate::Result<Vec<Column>> {
+        if self.schema.is_empty() {
+            return Err(BuildError::MissingSchema.into());
+        }
+
+        Ok(self
+            .schema
+            .iter()
+            .map(|column| Column {
+                name: column.name.clone(),
+                field: column.field.clone().unwrap_or_else(|| column.name.clone()),
+                column_type: column.column_type,
+                mode: column.mode,
+            })
+            .collect())
+    }
+
+    /// The default stream of the table, which is committed as rows are appended.
+    fn write_stream(&self, table: &str) -> String {
+        format!(
+            "projects/{}/datasets/{}/tables/{}/streams/_default",
+            self.project, self.dataset, table
+        )
+    }
+
+    fn build_endpoint(&self) -> crate::Result<Endpoint> {
+        let uri: Uri = self
+            .endpoint
+            .as_deref()
+            .unwrap_or(BIGQUERY_STORAGE_URL)
+            .parse()
+            .context(UriParseSnafu)?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let host = uri.host().unwrap_or("bigquerystorage.googleapis.com");
+        let mut tls_config = ClientTlsConfig::new().domain_name(host);
+        if let Some((cert, key)) = tls.identity_pem() {
+            tls_config = tls_config.identity(Identity::from_pem(cert, key));
+        }
+        for authority in tls.authorities_pem() {
+            tls_config = tls_config.ca_certificate(Certificate::from_pem(authority));
+        }
+
+        let mut endpoint: Endpoint = uri.to_string().parse().context(EndpointSnafu)?;
+        if uri.scheme() != Some(&Scheme::HTTP) {
+            endpoint = endpoint.tls_config(tls_config).context(EndpointTlsSnafu)?;
+        }
+        Ok(endpoint)
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for BigqueryConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let encoder = RowEncoder::new(self.build_columns()?);
+        let batch_settings = self
+            .batch
+            .validate()?
+            .limit_max_bytes(MAX_BATCH_PAYLOAD_SIZE)?
+            .into_batcher_settings()?;
+
+        let auth = self.auth.build(Scope::CloudPlatform).await?;
+        // The OAuth token is renewed in the background, as the requests take it from `auth`.
+        auth.spawn_regenerate_token();
+        let client = BigqueryClient::new(self.build_endpoint()?.connect_lazy(), auth);
+
+        let table = Destination {
+            write_stream: self.write_stream(&self.table),
+            descriptor: encoder.descriptor().clone(),
+        };
+        let dead_letter_table = self.dead_letter_table.as_ref().map(|table| Destination {
+            write_stream: self.write_stream(table),
+            descriptor: RowEncoder::dead_letter().descriptor().clone(),
+        });
+
+        let healthcheck = healthcheck(
+            client.clone(),
+            std::iter::once(&table)
+                .chain(&dead_letter_table)
+                .map(|destination| destination.write_stream.clone())
+                .collect(),
+        )
+        .boxed();
+
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let service = ServiceBuilder::new()
+            .settings(request_settings, BigqueryRetryLogic)
+            .service(BigqueryService::new(client, table, dead_letter_table));
+
+        let sink = BigquerySink {
+            batch_settings,
+            encoder,
+            dead_letters: self.dead_letter_table.is_some(),
+            service,
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Checks that the default streams of the tables can be written to.
+async fn healthcheck(mut client: BigqueryClient, write_streams: Vec<String>) -> crate::Result<()> {
+    for write_stream in write_streams {
+        client.get_write_stream(write_stream).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<BigqueryConfig>();
+    }
+
+    #[test]
+    fn builds_columns() {
+        let config: BigqueryConfig = toml::from_str(indoc! {r#"
+            project = "project"
+            dataset = "dataset"
+            table = "table"
+            schema = [
+                { name = "message", type = "string", mode = "required" },
+                { name = "host", type = "string", field = "metadata.host" },
+                { name = "tags", type = "string", mode = "repeated" },
+            ]
+        "#})
+        .unwrap();
+
+        let columns = config.build_columns().unwrap();
+        assert_eq!(
+            columns
+                .iter()
+                .map(|column| (column.name.as_str(), column.field.as_str(), column.mode))
+                .collect::<Vec<_>>(),
+            [
+                ("message", "message", ColumnMode::Required),
+                ("host", "metadata.host", ColumnMode::Nullable),
+                ("tags", "tags", ColumnMode::Repeated),
+            ]
+        );
+        assert_eq!(
+            config.write_stream("table"),
+            "projects/project/datasets/dataset/tables/table/streams/_default"
+        );
+    }
+
+    #[test]
+    fn requires_a_schema() {
+        let config: BigqueryConfig = toml::from_str(indoc! {r#"
+            project = "project"
+            dataset = "dataset"
+            table = "table"
+            schema = []
+        "#})
+        .unwrap();
+        assert!(config.build_columns().is_err());
+    }
+}
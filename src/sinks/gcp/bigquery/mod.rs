@@ -0,0 +1,56 @@
+//! The `gcp_bigquery` sink.
+//!
+//! Events are appended as rows to the default stream of the table with the [Storage Write
+//! API][write_api], which makes them available for queries as soon as they are written.
+//!
+//! [write_api]: https://cloud.google.com/bigquery/docs/write-api
+
+use snafu::Snafu;
+
+mod config;
+mod schema;
+mod service;
+mod sink;
+
+pub use config::BigqueryConfig;
+
+// prost emits some generated code that includes clones on `Arc`
+// objects, which causes a clippy ding on this block. We don't
+// directly control the generated code, so allow this lint here.
+#[allow(clippy::clone_on_ref_ptr)]
+#[allow(warnings)]
+mod proto {
+    pub mod google {
+        pub mod cloud {
+            pub mod bigquery {
+                pub mod storage {
+                    pub mod v1 {
+                        include!(concat!(
+                            env!("OUT_DIR"),
+                            "/google.cloud.bigquery.storage.v1.rs"
+                        ));
+                    }
+                }
+            }
+        }
+
+        pub mod rpc {
+            include!(concat!(env!("OUT_DIR"), "/google.rpc.rs"));
+        }
+    }
+
+    pub use google::cloud::bigquery::storage::v1::*;
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum BigquerySinkError {
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: tonic::Status },
+
+    #[snafu(display("No response to the append request"))]
+    NoResponse,
+
+    #[snafu(display("Rows were rejected without the index of any of them"))]
+    UnknownRowErrors,
+}
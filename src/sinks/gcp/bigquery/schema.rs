@@ -0,0 +1,414 @@
+//! Encoding of events as protobuf messages matching the columns of the table, as the Storage Write
+//! API expects the rows.
+//!
+//! The messages are described to BigQuery with a descriptor built from the columns, so the rows
+//! are encoded here without generated types.
+
+use std::str::FromStr;
+
+use bytes::Bytes;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use prost::encoding;
+use prost_types::{
+    field_descriptor_proto::{Label, Type},
+    DescriptorProto, FieldDescriptorProto,
+};
+use snafu::Snafu;
+use vector_core::event::{LogEvent, Value};
+
+use super::config::{ColumnMode, ColumnType};
+
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub enum EncodingError {
+    #[snafu(display("Column {:?}: cannot encode {} as {:?}", column, value, column_type))]
+    InvalidValue {
+        column: String,
+        column_type: ColumnType,
+        value: String,
+    },
+
+    #[snafu(display("Column {:?}: missing value for a required column", column))]
+    MissingValue { column: String },
+}
+
+/// A column of the table, and the field of the events its values are taken from.
+#[derive(Clone, Debug)]
+pub struct Column {
+    pub name: String,
+    pub field: String,
+    pub column_type: ColumnType,
+    pub mode: ColumnMode,
+}
+
+/// The values of the column types, as the protobuf types BigQuery maps them from.
+enum ProtoValue {
+    String(String),
+    Bytes(Bytes),
+    Int64(i64),
+    Int32(i32),
+    Double(f64),
+    Bool(bool),
+}
+
+impl ColumnType {
+    const fn proto_type(self) -> Type {
+        match self {
+            // JSON values are written as their text.
+            Self::String | Self::Json => Type::String,
+            Self::Bytes => Type::Bytes,
+            // Timestamps are microseconds since the Unix epoch.
+            Self::Int64 | Self::Timestamp => Type::Int64,
+            // Dates are days since the Unix epoch.
+            Self::Date => Type::Int32,
+            Self::Float64 => Type::Double,
+            Self::Bool => Type::Bool,
+        }
+    }
+}
+
+impl ColumnMode {
+    const fn label(self) -> Label {
+        match self {
+            Self::Nullable => Label::Optional,
+            Self::Required => Label::Required,
+            Self::Repeated => Label::Repeated,
+        }
+    }
+}
+
+/// Encodes the events as rows of the given columns.
+#[derive(Clone, Debug)]
+pub struct RowEncoder {
+    columns: Vec<Column>,
+    descriptor: DescriptorProto,
+}
+
+impl RowEncoder {
+    pub fn new(columns: Vec<Column>) -> Self {
+        // The rows follow the proto2 semantics, so that missing values are `NULL` rather than
+        // the default values of the types.
+        let descriptor = DescriptorProto {
+            name: Some("Row".to_owned()),
+            field: columns
+                .iter()
+                .zip(1..)
+                .map(|(column, number)| FieldDescriptorProto {
+                    name: Some(column.name.clone()),
+                    number: Some(number),
+                    label: Some(column.mode.label() as i32),
+                    r#type: Some(column.column_type.proto_type() as i32),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        Self {
+            columns,
+            descriptor,
+        }
+    }
+
+    /// The rows of the dead-letter table, with the rejected events encoded as JSON and the reason
+    /// they were rejected.
+    pub fn dead_letter() -> Self {
+        Self::new(vec![
+            Column {
+                name: "event".to_owned(),
+                field: "event".to_owned(),
+                column_type: ColumnType::Json,
+                mode: ColumnMode::Required,
+            },
+            Column {
+                name: "error".to_owned(),
+                field: "error".to_owned(),
+                column_type: ColumnType::String,
+                mode: ColumnMode::Required,
+            },
+        ])
+    }
+
+    /// The descriptor of the messages the rows are encoded as.
+    pub const fn descriptor(&self) -> &DescriptorProto {
+        &self.descriptor
+    }
+
+    /// Encodes the fields of the event as a row.
+    ///
+    /// Missing fields are left out of the row, making them `NULL` in nullable columns and empty in
+    /// repeated columns.
+    pub fn encode(&self, log: &LogEvent) -> Result<Vec<u8>, EncodingError> {
+        let mut buffer = Vec::new();
+        for (column, tag) in self.columns.iter().zip(1..) {
+            let invalid = |value: &Value| EncodingError::InvalidValue {
+                column: column.name.clone(),
+                column_type: column.column_type,
+                value: value.to_string_lossy(),
+            };
+            match (log.get(column.field.as_str()), column.mode) {
+                (None | Some(Value::Null), ColumnMode::Required) => {
+                    return Err(EncodingError::MissingValue {
+                        column: column.name.clone(),
+                    })
+                }
+                (None | Some(Value::Null), _) => {}
+                // Repeated values are not packed, so each of them is encoded as a field.
+                (Some(Value::Array(values)), ColumnMode::Repeated) => {
+                    for value in values {
+                        let value =
+                            proto_value(column.column_type, value).map_err(|()| invalid(value))?;
+                        encode_value(tag, &value, &mut buffer);
+                    }
+                }
+                (Some(value), ColumnMode::Repeated) => return Err(invalid(value)),
+                (Some(value), _) => {
+                    let proto =
+                        proto_value(column.column_type, value).map_err(|()| invalid(value))?;
+                    encode_value(tag, &proto, &mut buffer);
+                }
+            }
+        }
+        Ok(buffer)
+    }
+}
+
+fn encode_value(tag: u32, value: &ProtoValue, buffer: &mut Vec<u8>) {
+    match value {
+        ProtoValue::String(value) => encoding::string::encode(tag, value, buffer),
+        ProtoValue::Bytes(value) => encoding::bytes::encode(tag, value, buffer),
+        ProtoValue::Int64(value) => encoding::int64::encode(tag, value, buffer),
+        ProtoValue::Int32(value) => encoding::int32::encode(tag, value, buffer),
+        ProtoValue::Double(value) => encoding::double::encode(tag, value, buffer),
+        ProtoValue::Bool(value) => encoding::bool::encode(tag, value, buffer),
+    }
+}
+
+fn proto_value(column_type: ColumnType, value: &Value) -> Result<ProtoValue, ()> {
+    Ok(match column_type {
+        ColumnType::String => match value {
+            Value::Bytes(bytes) => ProtoValue::String(String::from_utf8_lossy(bytes).into_owned()),
+            // Objects and arrays go in `JSON` columns.
+            Value::Object(_) | Value::Array(_) | Value::Null => return Err(()),
+            value => ProtoValue::String(value.to_string_lossy()),
+        },
+        ColumnType::Bytes => match value {
+            Value::Bytes(bytes) => ProtoValue::Bytes(bytes.clone()),
+            _ => return Err(()),
+        },
+        ColumnType::Int64 => ProtoValue::Int64(integer(value)?),
+        ColumnType::Float64 => ProtoValue::Double(float(value)?),
+        ColumnType::Bool => ProtoValue::Bool(match value {
+            Value::Boolean(boolean) => *boolean,
+            Value::Bytes(bytes) => match bytes.as_ref() {
+                b"true" => true,
+                b"false" => false,
+                _ => return Err(()),
+            },
+            _ => return Err(()),
+        }),
+        ColumnType::Timestamp => {
+            let timestamp = timestamp(value)?;
+            let micros = timestamp
+                .timestamp()
+                .checked_mul(1_000_000)
+                .and_then(|micros| micros.checked_add(timestamp.timestamp_subsec_micros().into()))
+                .ok_or(())?;
+            ProtoValue::Int64(micros)
+        }
+        ColumnType::Date => ProtoValue::Int32(days_since_epoch(value)?.try_into().map_err(drop)?),
+        ColumnType::Json => ProtoValue::String(serde_json::to_string(value).map_err(drop)?),
+    })
+}
+
+fn integer(value: &Value) -> Result<i64, ()> {
+    match value {
+        Value::Integer(integer) => Ok(*integer),
+        Value::Float(float) if float.fract() == 0.0 => Ok(float.into_inner() as i64),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .map_err(drop)?
+            .trim()
+            .parse()
+            .map_err(drop),
+        _ => Err(()),
+    }
+}
+
+fn float(value: &Value) -> Result<f64, ()> {
+    match value {
+        Value::Integer(integer) => Ok(*integer as f64),
+        Value::Float(float) => Ok(float.into_inner()),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .map_err(drop)?
+            .trim()
+            .parse()
+            .map_err(drop),
+        _ => Err(()),
+    }
+}
+
+fn string(value: &Value) -> Result<&str, ()> {
+    match value {
+        Value::Bytes(bytes) => std::str::from_utf8(bytes).map_err(drop),
+        _ => Err(()),
+    }
+}
+
+/// Timestamps may also be given as seconds since the Unix epoch, or as RFC 3339 strings.
+fn timestamp(value: &Value) -> Result<DateTime<Utc>, ()> {
+    match value {
+        Value::Timestamp(timestamp) => Ok(*timestamp),
+        Value::Integer(seconds) => Utc.timestamp_opt(*seconds, 0).single().ok_or(()),
+        Value::Bytes(_) => DateTime::parse_from_rfc3339(string(value)?)
+            .map(|timestamp| timestamp.with_timezone(&Utc))
+            .map_err(drop),
+        _ => Err(()),
+    }
+}
+
+fn days_since_epoch(value: &Value) -> Result<i64, ()> {
+    let epoch = NaiveDate::from_ymd(1970, 1, 1);
+    let date = match value {
+        Value::Bytes(_) => match NaiveDate::from_str(string(value)?) {
+            Ok(date) => date,
+            Err(_) => timestamp(value)?.naive_utc().date(),
+        },
+        value => timestamp(value)?.naive_utc().date(),
+    };
+    Ok((date - epoch).num_days())
+}
+
+#[cfg(test)]
+mod tests {
+    use prost::Message;
+
+    use super::*;
+
+    fn column(name: &str, column_type: ColumnType, mode: ColumnMode) -> Column {
+        Column {
+            name: name.to_owned(),
+            field: name.to_owned(),
+            column_type,
+            mode,
+        }
+    }
+
+    /// The message the rows of the test columns decode as.
+    #[derive(Clone, PartialEq, Message)]
+    struct Row {
+        #[prost(string, optional, tag = "1")]
+        message: Option<String>,
+        #[prost(int64, optional, tag = "2")]
+        count: Option<i64>,
+        #[prost(int64, optional, tag = "3")]
+        timestamp: Option<i64>,
+        #[prost(int32, optional, tag = "4")]
+        date: Option<i32>,
+        #[prost(string, repeated, tag = "5")]
+        tags: Vec<String>,
+        #[prost(string, optional, tag = "6")]
+        attributes: Option<String>,
+    }
+
+    fn log(message: &str) -> LogEvent {
+        let mut log = LogEvent::default();
+        log.insert("message", message);
+        log
+    }
+
+    fn encoder() -> RowEncoder {
+        RowEncoder::new(vec![
+            column("message", ColumnType::String, ColumnMode::Required),
+            column("count", ColumnType::Int64, ColumnMode::Nullable),
+            column("timestamp", ColumnType::Timestamp, ColumnMode::Nullable),
+            column("date", ColumnType::Date, ColumnMode::Nullable),
+            column("tags", ColumnType::String, ColumnMode::Repeated),
+            column("attributes", ColumnType::Json, ColumnMode::Nullable),
+        ])
+    }
+
+    #[test]
+    fn builds_the_descriptor() {
+        let descriptor = encoder().descriptor().clone();
+        let fields = descriptor
+            .field
+            .iter()
+            .map(|field| (field.name(), field.number(), field.label(), field.r#type()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            [
+                ("message", 1, Label::Required, Type::String),
+                ("count", 2, Label::Optional, Type::Int64),
+                ("timestamp", 3, Label::Optional, Type::Int64),
+                ("date", 4, Label::Optional, Type::Int32),
+                ("tags", 5, Label::Repeated, Type::String),
+                ("attributes", 6, Label::Optional, Type::String),
+            ]
+        );
+    }
+
+    #[test]
+    fn encodes_rows() {
+        let mut log = log("hello");
+        log.insert("count", "42");
+        log.insert(
+            "timestamp",
+            Utc.timestamp_opt(1_666_000_000, 123_456_000).unwrap(),
+        );
+        log.insert("date", "2022-10-17");
+        log.insert("tags", vec!["a", "b"]);
+        log.insert("attributes.key", "value");
+
+        let row = Row::decode(&*encoder().encode(&log).unwrap()).unwrap();
+        assert_eq!(
+            row,
+            Row {
+                message: Some("hello".to_owned()),
+                count: Some(42),
+                timestamp: Some(1_666_000_000_123_456),
+                date: Some(19282),
+                tags: vec!["a".to_owned(), "b".to_owned()],
+                attributes: Some(r#"{"key":"value"}"#.to_owned()),
+            }
+        );
+    }
+
+    #[test]
+    fn leaves_out_missing_values() {
+        let log = log("hello");
+        let row = Row::decode(&*encoder().encode(&log).unwrap()).unwrap();
+        assert_eq!(
+            row,
+            Row {
+                message: Some("hello".to_owned()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_values() {
+        let mut log = log("hello");
+        log.insert("count", "many");
+        assert_eq!(
+            encoder().encode(&log),
+            Err(EncodingError::InvalidValue {
+                column: "count".to_owned(),
+                column_type: ColumnType::Int64,
+                value: "many".to_owned(),
+            })
+        );
+
+        let mut log = log("hello");
+        log.insert("tags", "a");
+        assert!(encoder().encode(&log).is_err());
+
+        let mut log = LogEvent::default();
+        log.insert("count", 1);
+        assert_eq!(
+            encoder().encode(&log),
+            Err(EncodingError::MissingValue {
+                column: "message".to_owned()
+            })
+        );
+    }
+}
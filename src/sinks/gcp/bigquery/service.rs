@@ -0,0 +1,355 @@
+use std::{
+    collections::HashMap,
+    task::{Context, Poll},
+};
+
+use futures::{future::BoxFuture, stream};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use prost_types::DescriptorProto;
+use snafu::ResultExt;
+use tonic::{
+    codegen::InterceptedService, metadata::MetadataValue, service::Interceptor, transport::Channel,
+    Code, Request, Status,
+};
+use tower::Service;
+use vector_core::{internal_event::CountByteSize, stream::DriverResponse, ByteSizeOf};
+
+use super::{
+    proto::{
+        append_rows_request::{ProtoData, Rows},
+        append_rows_response::Response,
+        big_query_write_client::BigQueryWriteClient,
+        AppendRowsRequest, GetWriteStreamRequest, ProtoRows, ProtoSchema, RowError, WriteStream,
+    },
+    schema::RowEncoder,
+    BigquerySinkError, RequestSnafu,
+};
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable, LogEvent},
+    gcp::GcpAuthenticator,
+    internal_events::GcpBigqueryRowRejected,
+    sinks::util::retries::RetryLogic,
+};
+
+/// Adds the OAuth token to the requests.
+#[derive(Clone)]
+struct AuthInterceptor {
+    auth: GcpAuthenticator,
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        if let Some(token) = self.auth.make_token() {
+            let authorization = MetadataValue::try_from(&token).map_err(|_| {
+                Status::new(
+                    Code::FailedPrecondition,
+                    "Invalid token text returned by GCP",
+                )
+            })?;
+            request
+                .metadata_mut()
+                .insert("authorization", authorization);
+        }
+        Ok(request)
+    }
+}
+
+/// The table the rows are appended to.
+#[derive(Clone, Debug)]
+pub struct Destination {
+    pub write_stream: String,
+    pub descriptor: DescriptorProto,
+}
+
+#[derive(Clone)]
+pub struct BigqueryClient {
+    inner: BigQueryWriteClient<InterceptedService<Channel, AuthInterceptor>>,
+}
+
+impl BigqueryClient {
+    pub fn new(channel: Channel, auth: GcpAuthenticator) -> Self {
+        Self {
+            inner: BigQueryWriteClient::with_interceptor(channel, AuthInterceptor { auth }),
+        }
+    }
+
+    pub async fn get_write_stream(
+        &mut self,
+        name: String,
+    ) -> Result<WriteStream, BigquerySinkError> {
+        let mut request = Request::new(GetWriteStreamRequest { name: name.clone() });
+        add_routing_header(&mut request, "name", &name)?;
+        Ok(self
+            .inner
+            .get_write_stream(request)
+            .await
+            .context(RequestSnafu)?
+            .into_inner())
+    }
+
+    /// Appends the rows to the table, returning the errors of the rows which were rejected.
+    ///
+    /// None of the rows are appended when any of them is rejected.
+    async fn append_rows(
+        &mut self,
+        destination: &Destination,
+        serialized_rows: Vec<Vec<u8>>,
+    ) -> Result<Vec<RowError>, BigquerySinkError> {
+        let append = AppendRowsRequest {
+            write_stream: destination.write_stream.clone(),
+            rows: Some(Rows::ProtoRows(ProtoData {
+                writer_schema: Some(ProtoSchema {
+                    proto_descriptor: Some(destination.descriptor.clone()),
+                }),
+                rows: Some(ProtoRows { serialized_rows }),
+            })),
+            trace_id: String::new(),
+        };
+        // Each request is sent on its own stream, which is closed once it is answered.
+        let mut request = Request::new(stream::iter([append]));
+        add_routing_header(&mut request, "write_stream", &destination.write_stream)?;
+
+        let response = self
+            .inner
+            .append_rows(request)
+            .await
+            .context(RequestSnafu)?
+            .into_inner()
+            .message()
+            .await
+            .context(RequestSnafu)?
+            .ok_or(BigquerySinkError::NoResponse)?;
+
+        if !response.row_errors.is_empty() {
+            return Ok(response.row_errors);
+        }
+        match response.response {
+            Some(Response::Error(status)) => Err(BigquerySinkError::Request {
+                source: Status::new(Code::from_i32(status.code), status.message),
+            }),
+            _ => Ok(Vec::new()),
+        }
+    }
+}
+
+/// The API routes the requests by the resource they name.
+fn add_routing_header<T>(
+    request: &mut Request<T>,
+    parameter: &str,
+    value: &str,
+) -> Result<(), BigquerySinkError> {
+    let params = format!(
+        "{}={}",
+        parameter,
+        utf8_percent_encode(value, NON_ALPHANUMERIC)
+    );
+    let params = MetadataValue::try_from(params).map_err(|_| BigquerySinkError::Request {
+        source: Status::new(Code::InvalidArgument, "Invalid table name"),
+    })?;
+    request
+        .metadata_mut()
+        .insert("x-goog-request-params", params);
+    Ok(())
+}
+
+/// An event written to the table, keeping its finalizers to reject it on its own.
+#[derive(Clone, Debug)]
+pub struct BigqueryRow {
+    pub event: LogEvent,
+    pub finalizers: EventFinalizers,
+}
+
+#[derive(Clone, Debug)]
+pub struct BigqueryRequest {
+    /// The events and their encoded rows.
+    pub rows: Vec<(BigqueryRow, Vec<u8>)>,
+    /// The events which don't match the columns of the table, and why.
+    pub rejected: Vec<(BigqueryRow, String)>,
+    pub finalizers: EventFinalizers,
+}
+
+impl Finalizable for BigqueryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BigqueryResponse {
+    events_count: usize,
+    events_byte_size: usize,
+}
+
+impl BigqueryResponse {
+    fn add<'a>(&mut self, rows: impl Iterator<Item = &'a BigqueryRow>) {
+        for row in rows {
+            self.events_count += 1;
+            self.events_byte_size += row.event.size_of();
+        }
+    }
+}
+
+impl DriverResponse for BigqueryResponse {
+    // The rejected rows are marked as such by the service, which takes precedence.
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(self.events_count, self.events_byte_size)
+    }
+}
+
+#[derive(Clone)]
+pub struct BigqueryService {
+    client: BigqueryClient,
+    table: Destination,
+    dead_letter_table: Option<Destination>,
+    dead_letter_encoder: RowEncoder,
+}
+
+impl BigqueryService {
+    pub fn new(
+        client: BigqueryClient,
+        table: Destination,
+        dead_letter_table: Option<Destination>,
+    ) -> Self {
+        Self {
+            client,
+            table,
+            dead_letter_table,
+            dead_letter_encoder: RowEncoder::dead_letter(),
+        }
+    }
+
+    async fn write(
+        mut self,
+        request: BigqueryRequest,
+    ) -> Result<BigqueryResponse, BigquerySinkError> {
+        let mut response = BigqueryResponse::default();
+        self.dead_letter(request.rejected, &mut response).await?;
+
+        let mut rows = request.rows;
+        while !rows.is_empty() {
+            let serialized_rows = rows.iter().map(|(_, encoded)| encoded.clone()).collect();
+            let row_errors = self
+                .client
+                .append_rows(&self.table, serialized_rows)
+                .await?;
+            if row_errors.is_empty() {
+                response.add(rows.iter().map(|(row, _)| row));
+                break;
+            }
+
+            // The rows which were not rejected are sent again.
+            let mut errors = row_errors
+                .into_iter()
+                .map(|error| (error.index, error.message))
+                .collect::<HashMap<_, _>>();
+            let mut rejected = Vec::new();
+            let mut remaining = Vec::new();
+            for ((row, encoded), index) in rows.into_iter().zip(0..) {
+                match errors.remove(&index) {
+                    Some(error) => rejected.push((row, error)),
+                    None => remaining.push((row, encoded)),
+                }
+            }
+            if rejected.is_empty() {
+                return Err(BigquerySinkError::UnknownRowErrors);
+            }
+            self.dead_letter(rejected, &mut response).await?;
+            rows = remaining;
+        }
+
+        Ok(response)
+    }
+
+    /// Writes the rejected events to the dead-letter table, or rejects them if there is none.
+    async fn dead_letter(
+        &mut self,
+        rejected: Vec<(BigqueryRow, String)>,
+        response: &mut BigqueryResponse,
+    ) -> Result<(), BigquerySinkError> {
+        let table = match &self.dead_letter_table {
+            Some(table) if !rejected.is_empty() => table,
+            _ => {
+                for (row, error) in rejected {
+                    reject(&row, &error);
+                }
+                return Ok(());
+            }
+        };
+
+        let mut rows = Vec::new();
+        for (row, error) in rejected {
+            let mut dead_letter = LogEvent::default();
+            dead_letter.insert("event", row.event.value().clone());
+            dead_letter.insert("error", error);
+            match self.dead_letter_encoder.encode(&dead_letter) {
+                Ok(encoded) => rows.push((row, encoded)),
+                Err(error) => reject(&row, &error.to_string()),
+            }
+        }
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        let serialized_rows = rows.iter().map(|(_, encoded)| encoded.clone()).collect();
+        let row_errors = self.client.append_rows(table, serialized_rows).await?;
+        match row_errors.first() {
+            None => response.add(rows.iter().map(|(row, _)| row)),
+            // The columns of the dead-letter table don't match, so none of the rows can be written.
+            Some(row_error) => {
+                let error = format!("Rejected by the dead-letter table: {}", row_error.message);
+                for (row, _) in rows {
+                    reject(&row, &error);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn reject(row: &BigqueryRow, error: &str) {
+    emit!(GcpBigqueryRowRejected { error });
+    row.finalizers.update_status(EventStatus::Rejected);
+}
+
+impl Service<BigqueryRequest> for BigqueryService {
+    type Response = BigqueryResponse;
+    type Error = BigquerySinkError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Readiness of the gRPC client is checked when appending the rows.
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, request: BigqueryRequest) -> Self::Future {
+        Box::pin(self.clone().write(request))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BigqueryRetryLogic;
+
+impl RetryLogic for BigqueryRetryLogic {
+    type Error = BigquerySinkError;
+    type Response = BigqueryResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        use tonic::Code::*;
+
+        match error {
+            // The schema mismatches are `InvalidArgument`, and are not retried.
+            BigquerySinkError::Request { source } => matches!(
+                source.code(),
+                Cancelled | DeadlineExceeded | ResourceExhausted | Aborted | Internal | Unavailable
+            ),
+            BigquerySinkError::NoResponse => true,
+            BigquerySinkError::UnknownRowErrors => false,
+        }
+    }
+}
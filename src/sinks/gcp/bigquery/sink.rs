@@ -0,0 +1,105 @@
+use std::fmt;
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::Service;
+use vector_core::{
+    stream::{BatcherSettings, DriverResponse},
+    ByteSizeOf,
+};
+
+use super::{
+    schema::RowEncoder,
+    service::{BigqueryRequest, BigqueryRow},
+};
+use crate::{
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    internal_events::GcpBigqueryRowRejected,
+    sinks::util::{SinkBuilderExt, StreamSink},
+};
+
+/// An event, and its row or why it doesn't match the columns of the table.
+struct EncodedEvent {
+    row: BigqueryRow,
+    encoded: Result<Vec<u8>, String>,
+}
+
+impl ByteSizeOf for EncodedEvent {
+    fn allocated_bytes(&self) -> usize {
+        self.row.event.allocated_bytes()
+    }
+}
+
+pub struct BigquerySink<S> {
+    pub batch_settings: BatcherSettings,
+    pub encoder: RowEncoder,
+    /// Whether the rejected events are written to the dead-letter table.
+    pub dead_letters: bool,
+    pub service: S,
+}
+
+impl<S> BigquerySink<S>
+where
+    S: Service<BigqueryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let encoder = self.encoder;
+        let dead_letters = self.dead_letters;
+        input
+            .filter_map(move |event| {
+                let mut event = event.into_log();
+                let finalizers = event.take_finalizers();
+                let encoded = encoder.encode(&event).map_err(|error| error.to_string());
+                let row = BigqueryRow { event, finalizers };
+                let encoded = match encoded {
+                    Err(error) if !dead_letters => {
+                        emit!(GcpBigqueryRowRejected { error: &error });
+                        row.finalizers.update_status(EventStatus::Rejected);
+                        None
+                    }
+                    encoded => Some(EncodedEvent { row, encoded }),
+                };
+                futures::future::ready(encoded)
+            })
+            .batched(self.batch_settings.into_byte_size_config())
+            .map(build_request)
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+fn build_request(events: Vec<EncodedEvent>) -> BigqueryRequest {
+    let mut finalizers = EventFinalizers::default();
+    let mut rows = Vec::new();
+    let mut rejected = Vec::new();
+    for EncodedEvent { row, encoded } in events {
+        // The rows keep their finalizers, to reject them on their own.
+        finalizers.merge(row.finalizers.clone());
+        match encoded {
+            Ok(encoded) => rows.push((row, encoded)),
+            Err(error) => rejected.push((row, error)),
+        }
+    }
+    BigqueryRequest {
+        rows,
+        rejected,
+        finalizers,
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for BigquerySink<S>
+where
+    S: Service<BigqueryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
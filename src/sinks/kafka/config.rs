@@ -12,7 +12,7 @@ use crate::{
     serde::json::to_string,
     sinks::{
         kafka::sink::{healthcheck, KafkaSink},
-        util::{BatchConfig, NoDefaultsBatchSettings},
+        util::{BatchConfig, NoDefaultsBatchSettings, RealtimeEventBasedDefaultBatchSettings},
         Healthcheck, VectorSink,
     },
 };
@@ -78,6 +78,16 @@ pub struct KafkaSinkConfig {
     #[serde(alias = "headers_field")] // accidentally released as `headers_field` in 0.18
     pub headers_key: Option<String>,
 
+    /// Whether or not to use an idempotent producer.
+    ///
+    /// An idempotent producer writes every message exactly once and in order, even when sending it
+    /// has to be retried. Transactions always use an idempotent producer.
+    #[serde(default)]
+    pub idempotence: bool,
+
+    #[configurable(derived)]
+    pub transactions: Option<KafkaTransactionsConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -87,6 +97,36 @@ pub struct KafkaSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// Transactions, for exactly-once delivery into Kafka.
+///
+/// Events are batched, and every batch is written in a transaction, so that consumers reading with
+/// the `read_committed` isolation level see either the whole batch or none of it. The events of a
+/// batch are only acknowledged once its transaction is committed.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaTransactionsConfig {
+    /// The transactional ID of the producer.
+    ///
+    /// It must be unique to this sink, and stay the same across restarts of Vector, so that the
+    /// transactions left open by a previous run are aborted.
+    pub transactional_id: String,
+
+    /// The maximum time, in milliseconds, a transaction may stay open before the broker aborts it.
+    ///
+    /// The `message_timeout_ms` option is lowered to this value when it's higher.
+    #[serde(default = "default_transaction_timeout_ms")]
+    pub timeout_ms: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeEventBasedDefaultBatchSettings>,
+}
+
+const fn default_transaction_timeout_ms() -> u64 {
+    60000 // default in librdkafka
+}
+
 const fn default_socket_timeout_ms() -> u64 {
     60000 // default in librdkafka
 }
@@ -116,9 +156,26 @@ impl KafkaSinkConfig {
         match kafka_role {
             // All batch options are producer only.
             KafkaRole::Producer => {
+                // A message can't be delivered after the transaction it's part of has timed out.
+                let message_timeout_ms = match &self.transactions {
+                    Some(transactions) => self.message_timeout_ms.min(transactions.timeout_ms),
+                    None => self.message_timeout_ms,
+                };
                 client_config
                     .set("compression.codec", &to_string(self.compression))
-                    .set("message.timeout.ms", &self.message_timeout_ms.to_string());
+                    .set("message.timeout.ms", &message_timeout_ms.to_string());
+
+                if self.idempotence || self.transactions.is_some() {
+                    client_config.set("enable.idempotence", "true");
+                }
+                if let Some(transactions) = &self.transactions {
+                    client_config
+                        .set("transactional.id", &transactions.transactional_id)
+                        .set(
+                            "transaction.timeout.ms",
+                            &transactions.timeout_ms.to_string(),
+                        );
+                }
 
                 if let Some(value) = self.batch.timeout_secs {
                     // Delay in milliseconds to wait for messages in the producer queue to accumulate before
@@ -209,6 +266,8 @@ impl GenerateConfig for KafkaSinkConfig {
             message_timeout_ms: default_message_timeout_ms(),
             librdkafka_options: Default::default(),
             headers_key: None,
+            idempotence: false,
+            transactions: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -240,4 +299,55 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    fn producer_option(config: &KafkaSinkConfig, key: &str) -> Option<String> {
+        config
+            .to_rdkafka(KafkaRole::Producer)
+            .unwrap()
+            .get(key)
+            .map(Into::into)
+    }
+
+    #[test]
+    fn sets_transactional_options() {
+        let mut config: KafkaSinkConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "topic"
+            encoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(producer_option(&config, "enable.idempotence"), None);
+
+        config.idempotence = true;
+        assert_eq!(
+            producer_option(&config, "enable.idempotence").as_deref(),
+            Some("true")
+        );
+        assert_eq!(producer_option(&config, "transactional.id"), None);
+
+        config.idempotence = false;
+        config.transactions = Some(KafkaTransactionsConfig {
+            transactional_id: "vector".to_owned(),
+            timeout_ms: 30000,
+            batch: Default::default(),
+        });
+        assert_eq!(
+            producer_option(&config, "enable.idempotence").as_deref(),
+            Some("true")
+        );
+        assert_eq!(
+            producer_option(&config, "transactional.id").as_deref(),
+            Some("vector")
+        );
+        assert_eq!(
+            producer_option(&config, "transaction.timeout.ms").as_deref(),
+            Some("30000")
+        );
+        assert_eq!(
+            producer_option(&config, "message.timeout.ms").as_deref(),
+            Some("30000")
+        );
+    }
 }
@@ -1,11 +1,14 @@
-use std::task::{Context, Poll};
+use std::{
+    task::{Context, Poll},
+    time::Duration,
+};
 
 use bytes::Bytes;
-use futures::future::BoxFuture;
+use futures::future::{self, BoxFuture};
 use rdkafka::{
-    error::KafkaError,
+    error::{KafkaError, KafkaResult},
     message::OwnedHeaders,
-    producer::{FutureProducer, FutureRecord},
+    producer::{FutureProducer, FutureRecord, Producer},
     util::Timeout,
 };
 use tower::Service;
@@ -18,6 +21,7 @@ use vector_core::{
 
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
+    internal_events::KafkaTransactionAbortError,
     kafka::KafkaStatisticsContext,
 };
 
@@ -36,6 +40,7 @@ pub struct KafkaRequestMetadata {
 }
 
 pub struct KafkaResponse {
+    event_count: usize,
     event_byte_size: usize,
 }
 
@@ -45,7 +50,7 @@ impl DriverResponse for KafkaResponse {
     }
 
     fn events_sent(&self) -> CountByteSize {
-        CountByteSize(1, self.event_byte_size)
+        CountByteSize(self.event_count, self.event_byte_size)
     }
 }
 
@@ -55,6 +60,26 @@ impl Finalizable for KafkaRequest {
     }
 }
 
+impl KafkaRequest {
+    fn record(&self) -> FutureRecord<'_, [u8], [u8]> {
+        let mut record = FutureRecord::to(&self.metadata.topic).payload(self.body.as_ref());
+        if let Some(key) = &self.metadata.key {
+            record = record.key(&key[..]);
+        }
+        if let Some(timestamp) = self.metadata.timestamp_millis {
+            record = record.timestamp(timestamp);
+        }
+        if let Some(headers) = &self.metadata.headers {
+            record = record.headers(headers.clone());
+        }
+        record
+    }
+
+    fn byte_size(&self) -> usize {
+        self.body.len() + self.metadata.key.as_ref().map(|x| x.len()).unwrap_or(0)
+    }
+}
+
 #[derive(Clone)]
 pub struct KafkaService {
     kafka_producer: FutureProducer<KafkaStatisticsContext>,
@@ -83,25 +108,16 @@ impl Service<KafkaRequest> for KafkaService {
         let this = self.clone();
 
         Box::pin(async move {
-            let mut record =
-                FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
-            if let Some(key) = &request.metadata.key {
-                record = record.key(&key[..]);
-            }
-            if let Some(timestamp) = request.metadata.timestamp_millis {
-                record = record.timestamp(timestamp);
-            }
-            if let Some(headers) = request.metadata.headers {
-                record = record.headers(headers);
-            }
-
             // rdkafka will internally retry forever if the queue is full
-            match this.kafka_producer.send(record, Timeout::Never).await {
+            match this
+                .kafka_producer
+                .send(request.record(), Timeout::Never)
+                .await
+            {
                 Ok((_partition, _offset)) => {
-                    this.bytes_sent.emit(ByteSize(
-                        request.body.len() + request.metadata.key.map(|x| x.len()).unwrap_or(0),
-                    ));
+                    this.bytes_sent.emit(ByteSize(request.byte_size()));
                     Ok(KafkaResponse {
+                        event_count: 1,
                         event_byte_size: request.event_byte_size,
                     })
                 }
@@ -110,3 +126,97 @@ impl Service<KafkaRequest> for KafkaService {
         })
     }
 }
+
+/// Writes batches of requests in transactions, so that a batch is written either as a whole or
+/// not at all.
+///
+/// A producer can only have one transaction open at a time, so the service must not be called
+/// concurrently.
+#[derive(Clone)]
+pub struct KafkaTransactionalService {
+    kafka_producer: FutureProducer<KafkaStatisticsContext>,
+    timeout: Duration,
+    bytes_sent: Registered<BytesSent>,
+}
+
+impl KafkaTransactionalService {
+    pub(crate) fn new(
+        kafka_producer: FutureProducer<KafkaStatisticsContext>,
+        timeout: Duration,
+    ) -> KafkaTransactionalService {
+        KafkaTransactionalService {
+            kafka_producer,
+            timeout,
+            bytes_sent: register!(BytesSent::from(Protocol("kafka".into()))),
+        }
+    }
+
+    /// Registers the transactional ID with the broker, aborting the transactions left open by any
+    /// previous producer with the same ID.
+    pub(crate) async fn init_transactions(&self) -> KafkaResult<()> {
+        let timeout = self.timeout;
+        self.blocking(move |producer| producer.init_transactions(timeout))
+            .await
+    }
+
+    /// Runs one of the blocking transaction calls of the producer.
+    async fn blocking<F>(&self, f: F) -> KafkaResult<()>
+    where
+        F: FnOnce(&FutureProducer<KafkaStatisticsContext>) -> KafkaResult<()> + Send + 'static,
+    {
+        let producer = self.kafka_producer.clone();
+        tokio::task::spawn_blocking(move || f(&producer))
+            .await
+            .expect("the transaction call should not panic")
+    }
+
+    async fn produce(&self, requests: &[KafkaRequest]) -> KafkaResult<()> {
+        self.kafka_producer.begin_transaction()?;
+        // The records are enqueued in order as the sends are polled in order.
+        let sends = requests
+            .iter()
+            .map(|request| self.kafka_producer.send(request.record(), Timeout::Never));
+        for result in future::join_all(sends).await {
+            result.map_err(|(error, _record)| error)?;
+        }
+        let timeout = self.timeout;
+        self.blocking(move |producer| producer.commit_transaction(timeout))
+            .await
+    }
+}
+
+impl Service<Vec<KafkaRequest>> for KafkaTransactionalService {
+    type Response = KafkaResponse;
+    type Error = KafkaError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, requests: Vec<KafkaRequest>) -> Self::Future {
+        let this = self.clone();
+
+        Box::pin(async move {
+            if let Err(error) = this.produce(&requests).await {
+                // Nothing of the batch is visible to `read_committed` consumers once aborted. It
+                // isn't sent again: its events are rejected once the error is returned.
+                let timeout = this.timeout;
+                if let Err(abort_error) = this
+                    .blocking(move |producer| producer.abort_transaction(timeout))
+                    .await
+                {
+                    emit!(KafkaTransactionAbortError { error: abort_error });
+                }
+                return Err(error);
+            }
+
+            this.bytes_sent
+                .emit(ByteSize(requests.iter().map(KafkaRequest::byte_size).sum()));
+            Ok(KafkaResponse {
+                event_count: requests.len(),
+                event_byte_size: requests.iter().map(|request| request.event_byte_size).sum(),
+            })
+        })
+    }
+}
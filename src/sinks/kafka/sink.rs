@@ -11,7 +11,7 @@ use rdkafka::{
 use snafu::{ResultExt, Snafu};
 use tokio::time::Duration;
 use tower::limit::ConcurrencyLimit;
-use vector_core::config::log_schema;
+use vector_core::{config::log_schema, stream::BatcherSettings};
 
 use super::config::{KafkaRole, KafkaSinkConfig};
 use crate::{
@@ -20,8 +20,9 @@ use crate::{
     kafka::KafkaStatisticsContext,
     sinks::{
         kafka::{
-            config::QUEUED_MIN_MESSAGES, request_builder::KafkaRequestBuilder,
-            service::KafkaService,
+            config::QUEUED_MIN_MESSAGES,
+            request_builder::KafkaRequestBuilder,
+            service::{KafkaRequest, KafkaService, KafkaTransactionalService},
         },
         util::{builder::SinkBuilderExt, StreamSink},
     },
@@ -39,12 +40,22 @@ pub(super) enum BuildError {
 pub struct KafkaSink {
     transformer: Transformer,
     encoder: Encoder<()>,
-    service: KafkaService,
+    service: KafkaSinkService,
     topic: Template,
     key_field: Option<String>,
     headers_key: Option<String>,
 }
 
+enum KafkaSinkService {
+    /// Every event is produced on its own.
+    Messages(KafkaService),
+    /// Events are batched, and every batch is produced in a transaction.
+    Transactions {
+        service: KafkaTransactionalService,
+        batch_settings: BatcherSettings,
+    },
+}
+
 pub(crate) fn create_producer(
     client_config: ClientConfig,
 ) -> crate::Result<FutureProducer<KafkaStatisticsContext>> {
@@ -61,20 +72,28 @@ impl KafkaSink {
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
+        let service = match &config.transactions {
+            Some(transactions) => KafkaSinkService::Transactions {
+                service: KafkaTransactionalService::new(
+                    producer,
+                    Duration::from_millis(transactions.timeout_ms),
+                ),
+                batch_settings: transactions.batch.into_batcher_settings()?,
+            },
+            None => KafkaSinkService::Messages(KafkaService::new(producer)),
+        };
 
         Ok(KafkaSink {
             headers_key: config.headers_key,
             transformer,
             encoder,
-            service: KafkaService::new(producer),
+            service,
             topic: Template::try_from(config.topic).context(TopicTemplateSnafu)?,
             key_field: config.key_field,
         })
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
-        // rdkafka will internally retry forever, so we need some limit to prevent this from overflowing
-        let service = ConcurrencyLimit::new(self.service, QUEUED_MIN_MESSAGES as usize);
         let mut request_builder = KafkaRequestBuilder {
             key_field: self.key_field,
             headers_key: self.headers_key,
@@ -84,13 +103,37 @@ impl KafkaSink {
             log_schema: log_schema(),
         };
 
-        let sink = input
-            .filter_map(|event|
+        let requests = input.filter_map(|event|
                 // request_builder is fallible but the places it can fail are emitting
                 // `Error` and `DroppedEvent` internal events appropriately so no need to here.
-                future::ready(request_builder.build_request(event)))
-            .into_driver(service);
-        sink.run().await
+                future::ready(request_builder.build_request(event)));
+
+        match self.service {
+            KafkaSinkService::Messages(service) => {
+                // rdkafka will internally retry forever, so we need some limit to prevent this from overflowing
+                let service = ConcurrencyLimit::new(service, QUEUED_MIN_MESSAGES as usize);
+                requests.into_driver(service).run().await
+            }
+            KafkaSinkService::Transactions {
+                service,
+                batch_settings,
+            } => {
+                if let Err(error) = service.init_transactions().await {
+                    error!(message = "Failed to initialize transactions.", %error);
+                    return Err(());
+                }
+                // only one transaction can be open at a time
+                let service = ConcurrencyLimit::new(service, 1);
+                requests
+                    .batched(
+                        batch_settings
+                            .into_item_size_config(|request: &KafkaRequest| request.body.len()),
+                    )
+                    .into_driver(service)
+                    .run()
+                    .await
+            }
+        }
     }
 }
 
@@ -24,8 +24,19 @@ pub mod aws_sqs;
 pub mod axiom;
 #[cfg(feature = "sinks-azure_blob")]
 pub mod azure_blob;
-#[cfg(any(feature = "sinks-azure_blob", feature = "sinks-datadog_archives"))]
+#[cfg(any(
+    feature = "sinks-azure_blob",
+    feature = "sinks-datadog_archives",
+    feature = "sources-azure_event_hubs"
+))]
+// the `azure_event_hubs` source only builds clients
+#[cfg_attr(
+    not(any(feature = "sinks-azure_blob", feature = "sinks-datadog_archives")),
+    allow(dead_code)
+)]
 pub mod azure_common;
+#[cfg(feature = "sinks-azure_data_explorer")]
+pub mod azure_data_explorer;
 #[cfg(feature = "sinks-azure_monitor_logs")]
 pub mod azure_monitor_logs;
 #[cfg(feature = "sinks-blackhole")]
@@ -69,8 +80,12 @@ pub mod loki;
 pub mod nats;
 #[cfg(feature = "sinks-new_relic")]
 pub mod new_relic;
+#[cfg(feature = "sinks-opentelemetry")]
+pub mod opentelemetry;
 #[cfg(feature = "sinks-papertrail")]
 pub mod papertrail;
+#[cfg(feature = "sinks-postgresql")]
+pub mod postgresql;
 #[cfg(feature = "sinks-prometheus")]
 pub mod prometheus;
 #[cfg(feature = "sinks-pulsar")]
@@ -174,6 +189,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-azure_blob")]
     AzureBlob(#[configurable(derived)] azure_blob::AzureBlobSinkConfig),
 
+    /// Azure Data Explorer.
+    #[cfg(feature = "sinks-azure_data_explorer")]
+    AzureDataExplorer(#[configurable(derived)] azure_data_explorer::AzureDataExplorerConfig),
+
     /// Azure Monitor Logs.
     #[cfg(feature = "sinks-azure_monitor_logs")]
     AzureMonitorLogs(#[configurable(derived)] azure_monitor_logs::AzureMonitorLogsConfig),
@@ -214,6 +233,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-file")]
     File(#[configurable(derived)] file::FileSinkConfig),
 
+    /// GCP BigQuery.
+    #[cfg(feature = "sinks-gcp")]
+    GcpBigquery(#[configurable(derived)] gcp::bigquery::BigqueryConfig),
+
     /// Google Chronicle (unstructured).
     #[cfg(feature = "sinks-gcp")]
     GcpChronicleUnstructured(
@@ -280,10 +303,18 @@ pub enum Sinks {
     #[cfg(feature = "sinks-new_relic")]
     NewRelic(#[configurable(derived)] new_relic::NewRelicConfig),
 
+    /// OpenTelemetry.
+    #[cfg(feature = "sinks-opentelemetry")]
+    Opentelemetry(#[configurable(derived)] opentelemetry::OpentelemetryConfig),
+
     /// Papertrail.
     #[cfg(feature = "sinks-papertrail")]
     Papertrail(#[configurable(derived)] papertrail::PapertrailConfig),
 
+    /// PostgreSQL.
+    #[cfg(feature = "sinks-postgresql")]
+    Postgresql(#[configurable(derived)] postgresql::PostgresqlConfig),
+
     /// Prometheus Exporter.
     #[cfg(feature = "sinks-prometheus")]
     PrometheusExporter(#[configurable(derived)] prometheus::exporter::PrometheusExporterConfig),
@@ -390,6 +421,8 @@ impl NamedComponent for Sinks {
             Self::Axiom(config) => config.get_component_name(),
             #[cfg(feature = "sinks-azure_blob")]
             Self::AzureBlob(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-azure_data_explorer")]
+            Self::AzureDataExplorer(config) => config.get_component_name(),
             #[cfg(feature = "sinks-azure_monitor_logs")]
             Self::AzureMonitorLogs(config) => config.get_component_name(),
             #[cfg(feature = "sinks-blackhole")]
@@ -411,6 +444,8 @@ impl NamedComponent for Sinks {
             #[cfg(feature = "sinks-file")]
             Self::File(config) => config.get_component_name(),
             #[cfg(feature = "sinks-gcp")]
+            Self::GcpBigquery(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-gcp")]
             Self::GcpChronicleUnstructured(config) => config.get_component_name(),
             #[cfg(feature = "sinks-gcp")]
             Self::GcpStackdriverLogs(config) => config.get_component_name(),
@@ -442,8 +477,12 @@ impl NamedComponent for Sinks {
             Self::Nats(config) => config.get_component_name(),
             #[cfg(feature = "sinks-new_relic")]
             Self::NewRelic(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-opentelemetry")]
+            Self::Opentelemetry(config) => config.get_component_name(),
             #[cfg(feature = "sinks-papertrail")]
             Self::Papertrail(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-postgresql")]
+            Self::Postgresql(config) => config.get_component_name(),
             #[cfg(feature = "sinks-prometheus")]
             Self::PrometheusExporter(config) => config.get_component_name(),
             #[cfg(feature = "sinks-prometheus")]
@@ -0,0 +1,238 @@
+use std::collections::BTreeMap;
+
+use derivative::Derivative;
+use futures::FutureExt;
+use http::{uri::Scheme, Uri};
+use hyper::client::HttpConnector;
+use hyper_openssl::HttpsConnector;
+use hyper_proxy::ProxyConnector;
+use opentelemetry_proto::proto::collector::logs::v1::ExportLogsServiceRequest;
+use tonic::body::BoxBody;
+use tower::ServiceBuilder;
+use vector_config::configurable_component;
+
+use super::{
+    encoder::OpentelemetryEncoder,
+    service::{
+        build_headers, is_retriable_status, OpentelemetryPayload, OpentelemetryResponse,
+        OpentelemetryService,
+    },
+    sink::OpentelemetrySink,
+    OpentelemetrySinkError,
+};
+use crate::{
+    config::{
+        AcknowledgementsConfig, DataType, GenerateConfig, Input, ProxyConfig, SinkConfig,
+        SinkContext,
+    },
+    http::HttpClient,
+    sinks::{
+        util::{
+            http::RequestConfig, retries::RetryLogic, BatchConfig,
+            RealtimeEventBasedDefaultBatchSettings, ServiceBuilderExt, TowerRequestConfig,
+            UriSerde,
+        },
+        Healthcheck, VectorSink,
+    },
+    tls::{tls_connector_builder, MaybeTlsSettings, TlsEnableableConfig},
+};
+
+/// The protocol to export with.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Protocol {
+    /// OTLP over gRPC.
+    #[derivative(Default)]
+    Grpc,
+
+    /// OTLP over HTTP, with binary protobuf payloads.
+    Http,
+}
+
+/// Configuration for the `opentelemetry` sink.
+#[configurable_component(sink("opentelemetry"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetryConfig {
+    /// The endpoint of the OTLP receiver.
+    ///
+    /// With the `http` protocol, logs and metrics are sent to the `/v1/logs` and `/v1/metrics`
+    /// paths of the endpoint.
+    endpoint: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    protocol: Protocol,
+
+    /// Whether or not to compress requests.
+    ///
+    /// If set to `true`, requests will be compressed with [`gzip`][gzip_docs].
+    ///
+    /// [gzip_docs]: https://en.wikipedia.org/wiki/Gzip
+    #[serde(default)]
+    compression: bool,
+
+    /// Resource attributes to set from the events, mapped to the log field or metric tag they are
+    /// taken from.
+    ///
+    /// The fields and tags are removed from the events, and events are grouped by their resource.
+    /// Log events may also carry their resource attributes in the `resources` field, as decoded by
+    /// the `opentelemetry` source.
+    #[serde(default)]
+    resource_attributes: BTreeMap<String, String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeEventBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: RequestConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for OpentelemetryConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "http://127.0.0.1:4317""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for OpentelemetryConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let headers = build_headers(&self.request.headers)?;
+
+        let service = match self.protocol {
+            Protocol::Grpc => {
+                let uri = with_default_scheme(&self.endpoint, tls.is_tls())?;
+                let client = new_grpc_client(&tls, cx.proxy())?;
+                OpentelemetryService::grpc(client, uri, headers, self.compression)
+            }
+            Protocol::Http => {
+                let uri = self.endpoint.parse::<UriSerde>()?.with_default_parts();
+                let client = HttpClient::new(tls, cx.proxy())?;
+                OpentelemetryService::http(client, &uri, headers, self.compression)?
+            }
+        };
+
+        let healthcheck = healthcheck(service.clone()).boxed();
+        let request_settings = self
+            .request
+            .tower
+            .unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let service = ServiceBuilder::new()
+            .settings(request_settings, OpentelemetryRetryLogic)
+            .service(service);
+
+        let sink = OpentelemetrySink {
+            batch_settings,
+            encoder: OpentelemetryEncoder::new(self.resource_attributes.clone()),
+            service,
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Log | DataType::Metric)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// OTLP has no health check, so an empty export request is sent instead.
+async fn healthcheck(service: OpentelemetryService) -> crate::Result<()> {
+    service
+        .export(OpentelemetryPayload::Logs(
+            ExportLogsServiceRequest::default(),
+        ))
+        .await?;
+    Ok(())
+}
+
+/// gRPC doesn't like an endpoint without a scheme, so it defaults to http or https.
+fn with_default_scheme(endpoint: &str, tls: bool) -> crate::Result<Uri> {
+    let uri: Uri = endpoint.parse()?;
+    if uri.scheme().is_some() {
+        return Ok(uri);
+    }
+
+    let mut parts = uri.into_parts();
+    parts.scheme = Some(if tls { Scheme::HTTPS } else { Scheme::HTTP });
+    if parts.path_and_query.is_none() {
+        parts.path_and_query = Some("/".parse()?);
+    }
+    Ok(Uri::from_parts(parts)?)
+}
+
+fn new_grpc_client(
+    tls_settings: &MaybeTlsSettings,
+    proxy_config: &ProxyConfig,
+) -> crate::Result<hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>> {
+    let mut http = HttpConnector::new();
+    http.enforce_http(false);
+
+    let tls = tls_connector_builder(tls_settings)?;
+    let mut https = HttpsConnector::with_connector(http, tls)?;
+
+    let settings = tls_settings.tls().cloned();
+    https.set_callback(move |c, _uri| {
+        if let Some(settings) = &settings {
+            settings.apply_connect_configuration(c);
+        }
+
+        Ok(())
+    });
+
+    let mut proxy = ProxyConnector::new(https).unwrap();
+    proxy_config.configure(&mut proxy)?;
+
+    Ok(hyper::Client::builder().http2_only(true).build(proxy))
+}
+
+#[derive(Debug, Clone)]
+struct OpentelemetryRetryLogic;
+
+impl RetryLogic for OpentelemetryRetryLogic {
+    type Error = OpentelemetrySinkError;
+    type Response = OpentelemetryResponse;
+
+    fn is_retriable_error(&self, err: &Self::Error) -> bool {
+        use tonic::Code::*;
+
+        match err {
+            // The codes the OTLP specification lists as retryable.
+            OpentelemetrySinkError::Request { source } => matches!(
+                source.code(),
+                Cancelled
+                    | DeadlineExceeded
+                    | ResourceExhausted
+                    | Aborted
+                    | OutOfRange
+                    | Unavailable
+                    | DataLoss
+            ),
+            OpentelemetrySinkError::ResponseStatus { status, .. } => is_retriable_status(*status),
+            OpentelemetrySinkError::Http { .. } => true,
+            OpentelemetrySinkError::Compression { .. } => false,
+        }
+    }
+}
@@ -0,0 +1,657 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+use opentelemetry_proto::proto::{
+    collector::{logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest},
+    common::v1::{
+        any_value::Value as PBValue, AnyValue, ArrayValue, InstrumentationScope, KeyValue,
+        KeyValueList,
+    },
+    logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+    metrics::v1::{
+        metric::Data, number_data_point, summary_data_point::ValueAtQuantile,
+        AggregationTemporality, Gauge, Histogram, HistogramDataPoint, Metric as PBMetric,
+        NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum, Summary, SummaryDataPoint,
+    },
+    resource::v1::Resource,
+};
+use prost::Message;
+use vector_core::config::log_schema;
+
+use crate::{
+    event::{
+        metric::{Metric, MetricKind, MetricValue},
+        LogEvent, Value,
+    },
+    internal_events::OpentelemetryUnsupportedMetricError,
+    sinks::util::encode_namespace,
+};
+
+const RESOURCE_KEY: &str = "resources";
+const SCOPE_KEY: &str = "scope";
+const ATTRIBUTES_KEY: &str = "attributes";
+const TRACE_ID_KEY: &str = "trace_id";
+const SPAN_ID_KEY: &str = "span_id";
+const SEVERITY_TEXT_KEY: &str = "severity_text";
+const SEVERITY_NUMBER_KEY: &str = "severity_number";
+const OBSERVED_TIMESTAMP_KEY: &str = "observed_timestamp";
+const DROPPED_ATTRIBUTES_COUNT_KEY: &str = "dropped_attributes_count";
+const FLAGS_KEY: &str = "flags";
+
+/// Encodes events into OTLP export requests.
+///
+/// Log events are expected in the shape the `opentelemetry` source decodes log records into, so
+/// that records pass through Vector unchanged: the `resources`, `scope` and `attributes` fields,
+/// the message as the body, and so on. Any other field is sent as an attribute of the record.
+#[derive(Clone, Debug, Default)]
+pub struct OpentelemetryEncoder {
+    /// Resource attributes, mapped to the log field or metric tag they are taken from.
+    resource_attributes: BTreeMap<String, String>,
+}
+
+impl OpentelemetryEncoder {
+    pub const fn new(resource_attributes: BTreeMap<String, String>) -> Self {
+        Self {
+            resource_attributes,
+        }
+    }
+
+    pub fn encode_logs(&self, logs: Vec<LogEvent>) -> ExportLogsServiceRequest {
+        let mut groups = Groups::default();
+        for log in logs {
+            let (resource, scope, record) = self.encode_log(log);
+            groups.insert(resource, scope, record);
+        }
+
+        ExportLogsServiceRequest {
+            resource_logs: groups
+                .into_iter()
+                .map(|(resource, scopes)| ResourceLogs {
+                    resource: Some(resource),
+                    scope_logs: scopes
+                        .into_iter()
+                        .map(|(scope, log_records)| ScopeLogs {
+                            scope,
+                            log_records,
+                            schema_url: String::new(),
+                        })
+                        .collect(),
+                    schema_url: String::new(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Encodes the metrics, dropping those which can't be represented in OTLP.
+    pub fn encode_metrics(&self, metrics: Vec<Metric>) -> ExportMetricsServiceRequest {
+        let mut groups = Groups::default();
+        for metric in metrics {
+            match self.encode_metric(metric) {
+                Ok((resource, metric)) => groups.insert(resource, None, metric),
+                Err(value) => emit!(OpentelemetryUnsupportedMetricError { value: &value }),
+            }
+        }
+
+        ExportMetricsServiceRequest {
+            resource_metrics: groups
+                .into_iter()
+                .map(|(resource, scopes)| ResourceMetrics {
+                    resource: Some(resource),
+                    scope_metrics: scopes
+                        .into_iter()
+                        .map(|(scope, metrics)| ScopeMetrics {
+                            scope,
+                            metrics,
+                            schema_url: String::new(),
+                        })
+                        .collect(),
+                    schema_url: String::new(),
+                })
+                .collect(),
+        }
+    }
+
+    fn encode_log(&self, mut log: LogEvent) -> (Resource, Option<InstrumentationScope>, LogRecord) {
+        let mut resource = match log.remove(RESOURCE_KEY) {
+            Some(Value::Object(attributes)) => kv_list(attributes),
+            _ => Vec::new(),
+        };
+        for (attribute, field) in &self.resource_attributes {
+            if let Some(value) = log.remove(field.as_str()) {
+                upsert(&mut resource, attribute.clone(), value);
+            }
+        }
+
+        let scope = match log.remove(SCOPE_KEY) {
+            Some(Value::Object(mut scope)) => Some(InstrumentationScope {
+                name: scope
+                    .remove("name")
+                    .map(|name| name.to_string_lossy())
+                    .unwrap_or_default(),
+                version: scope
+                    .remove("version")
+                    .map(|version| version.to_string_lossy())
+                    .unwrap_or_default(),
+                attributes: match scope.remove(ATTRIBUTES_KEY) {
+                    Some(Value::Object(attributes)) => kv_list(attributes),
+                    _ => Vec::new(),
+                },
+                dropped_attributes_count: 0,
+            }),
+            _ => None,
+        };
+
+        let mut record = LogRecord {
+            body: log.remove(log_schema().message_key()).map(any_value),
+            ..Default::default()
+        };
+        if let Some(Value::Timestamp(timestamp)) = log.remove(log_schema().timestamp_key()) {
+            record.time_unix_nano = unix_nano(timestamp);
+        }
+        if let Some(Value::Timestamp(timestamp)) = log.remove(OBSERVED_TIMESTAMP_KEY) {
+            record.observed_time_unix_nano = unix_nano(timestamp);
+        }
+        if let Some(id) = take_id(&mut log, TRACE_ID_KEY) {
+            record.trace_id = id;
+        }
+        if let Some(id) = take_id(&mut log, SPAN_ID_KEY) {
+            record.span_id = id;
+        }
+        if let Some(Value::Bytes(text)) = log.remove(SEVERITY_TEXT_KEY) {
+            record.severity_text = String::from_utf8_lossy(&text).into_owned();
+        }
+        if let Some(number) = take_integer(&mut log, SEVERITY_NUMBER_KEY) {
+            record.severity_number = number as i32;
+        }
+        if let Some(flags) = take_integer(&mut log, FLAGS_KEY) {
+            record.flags = flags as u32;
+        }
+        if let Some(count) = take_integer(&mut log, DROPPED_ATTRIBUTES_COUNT_KEY) {
+            record.dropped_attributes_count = count as u32;
+        }
+        if let Some(Value::Object(attributes)) = log.remove(ATTRIBUTES_KEY) {
+            record.attributes = kv_list(attributes);
+        }
+        log.remove(log_schema().source_type_key());
+
+        let (value, _metadata) = log.into_parts();
+        if let Value::Object(fields) = value {
+            for (key, value) in fields {
+                upsert(&mut record.attributes, key, value);
+            }
+        }
+
+        (
+            Resource {
+                attributes: resource,
+                dropped_attributes_count: 0,
+            },
+            scope,
+            record,
+        )
+    }
+
+    /// Encodes the metric, or returns its value if it can't be represented.
+    fn encode_metric(&self, mut metric: Metric) -> Result<(Resource, PBMetric), MetricValue> {
+        let mut resource = Vec::new();
+        for (attribute, tag) in &self.resource_attributes {
+            if let Some(value) = metric.remove_tag(tag) {
+                resource.push(key_value(attribute.clone(), Value::from(value)));
+            }
+        }
+
+        let attributes = metric
+            .tags()
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| key_value(key.clone(), Value::from(value.clone())))
+            .collect::<Vec<_>>();
+        let time = metric.timestamp().unwrap_or_else(Utc::now);
+        let time_unix_nano = unix_nano(time);
+        // The start of the interval is only known for incremental metrics.
+        let start_time_unix_nano = match (metric.kind(), metric.interval_ms()) {
+            (MetricKind::Incremental, Some(interval)) => {
+                unix_nano(time - chrono::Duration::milliseconds(i64::from(u32::from(interval))))
+            }
+            _ => 0,
+        };
+        let temporality = match metric.kind() {
+            MetricKind::Incremental => AggregationTemporality::Delta,
+            MetricKind::Absolute => AggregationTemporality::Cumulative,
+        } as i32;
+        let number = |value: f64| NumberDataPoint {
+            attributes: attributes.clone(),
+            start_time_unix_nano,
+            time_unix_nano,
+            value: Some(number_data_point::Value::AsDouble(value)),
+            ..Default::default()
+        };
+
+        let data = match metric.value() {
+            MetricValue::Counter { value } => Data::Sum(Sum {
+                data_points: vec![number(*value)],
+                aggregation_temporality: temporality,
+                is_monotonic: true,
+            }),
+            MetricValue::Gauge { value } => Data::Gauge(Gauge {
+                data_points: vec![number(*value)],
+            }),
+            MetricValue::Set { values } => Data::Gauge(Gauge {
+                data_points: vec![number(values.len() as f64)],
+            }),
+            // Without buckets, only the count and the sum of the samples are sent.
+            MetricValue::Distribution { samples, .. } => {
+                let values = || samples.iter().map(|sample| sample.value);
+                Data::Histogram(Histogram {
+                    data_points: vec![HistogramDataPoint {
+                        attributes: attributes.clone(),
+                        start_time_unix_nano,
+                        time_unix_nano,
+                        count: samples.iter().map(|sample| u64::from(sample.rate)).sum(),
+                        sum: Some(
+                            samples
+                                .iter()
+                                .map(|sample| sample.value * f64::from(sample.rate))
+                                .sum(),
+                        ),
+                        min: values().reduce(f64::min),
+                        max: values().reduce(f64::max),
+                        ..Default::default()
+                    }],
+                    aggregation_temporality: temporality,
+                })
+            }
+            MetricValue::AggregatedHistogram {
+                buckets,
+                count,
+                sum,
+            } => {
+                let explicit_bounds = buckets
+                    .iter()
+                    .map(|bucket| bucket.upper_limit)
+                    .filter(|bound| bound.is_finite())
+                    .collect::<Vec<_>>();
+                let mut bucket_counts = buckets
+                    .iter()
+                    .take(explicit_bounds.len())
+                    .map(|bucket| bucket.count)
+                    .collect::<Vec<_>>();
+                // OTLP has a bucket for the values above the last bound, which Vector leaves out.
+                bucket_counts.push(count.saturating_sub(bucket_counts.iter().sum()));
+                Data::Histogram(Histogram {
+                    data_points: vec![HistogramDataPoint {
+                        attributes: attributes.clone(),
+                        start_time_unix_nano,
+                        time_unix_nano,
+                        count: *count,
+                        sum: Some(*sum),
+                        bucket_counts,
+                        explicit_bounds,
+                        ..Default::default()
+                    }],
+                    aggregation_temporality: temporality,
+                })
+            }
+            MetricValue::AggregatedSummary {
+                quantiles,
+                count,
+                sum,
+            } => Data::Summary(Summary {
+                data_points: vec![SummaryDataPoint {
+                    attributes: attributes.clone(),
+                    start_time_unix_nano,
+                    time_unix_nano,
+                    count: *count,
+                    sum: *sum,
+                    quantile_values: quantiles
+                        .iter()
+                        .map(|quantile| ValueAtQuantile {
+                            quantile: quantile.quantile,
+                            value: quantile.value,
+                        })
+                        .collect(),
+                    flags: 0,
+                }],
+            }),
+            MetricValue::Sketch { .. } => {
+                let (_series, data, _metadata) = metric.into_parts();
+                return Err(data.value);
+            }
+        };
+
+        Ok((
+            Resource {
+                attributes: resource,
+                dropped_attributes_count: 0,
+            },
+            PBMetric {
+                name: encode_namespace(metric.namespace(), '.', metric.name()),
+                description: String::new(),
+                unit: String::new(),
+                data: Some(data),
+            },
+        ))
+    }
+}
+
+/// Items grouped by their resource and their scope, in the order they were first seen in.
+struct Groups<T> {
+    resources: Vec<(Resource, Vec<(Option<InstrumentationScope>, Vec<T>)>)>,
+    // protobuf messages can't be hashed, so they're grouped by their encoding
+    index: HashMap<(Vec<u8>, Option<Vec<u8>>), (usize, usize)>,
+}
+
+impl<T> Default for Groups<T> {
+    fn default() -> Self {
+        Self {
+            resources: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+}
+
+impl<T> Groups<T> {
+    fn insert(&mut self, resource: Resource, scope: Option<InstrumentationScope>, item: T) {
+        let key = (
+            resource.encode_to_vec(),
+            scope.as_ref().map(Message::encode_to_vec),
+        );
+        if let Some((resource, scope)) = self.index.get(&key) {
+            self.resources[*resource].1[*scope].1.push(item);
+            return;
+        }
+
+        let resource_index = self
+            .index
+            .iter()
+            .find(|((resource, _), _)| *resource == key.0)
+            .map(|(_, (resource, _))| *resource);
+        let resource_index = match resource_index {
+            Some(index) => index,
+            None => {
+                self.resources.push((resource, Vec::new()));
+                self.resources.len() - 1
+            }
+        };
+        let scopes = &mut self.resources[resource_index].1;
+        scopes.push((scope, vec![item]));
+        self.index.insert(key, (resource_index, scopes.len() - 1));
+    }
+
+    fn into_iter(
+        self,
+    ) -> impl Iterator<Item = (Resource, Vec<(Option<InstrumentationScope>, Vec<T>)>)> {
+        self.resources.into_iter()
+    }
+}
+
+fn unix_nano(timestamp: DateTime<Utc>) -> u64 {
+    timestamp.timestamp_nanos().max(0) as u64
+}
+
+fn take_integer(log: &mut LogEvent, key: &str) -> Option<i64> {
+    match log.get(key) {
+        Some(Value::Integer(value)) => {
+            let value = *value;
+            log.remove(key);
+            Some(value)
+        }
+        _ => None,
+    }
+}
+
+/// Takes the hex encoded trace or span ID, or leaves the field as an attribute if it's not one.
+fn take_id(log: &mut LogEvent, key: &str) -> Option<Vec<u8>> {
+    let id = match log.get(key) {
+        Some(Value::Bytes(id)) => hex::decode(id).ok()?,
+        _ => return None,
+    };
+    log.remove(key);
+    Some(id)
+}
+
+/// Sets the attribute, replacing any attribute with the same key.
+fn upsert(attributes: &mut Vec<KeyValue>, key: String, value: Value) {
+    let attribute = key_value(key, value);
+    match attributes.iter_mut().find(|kv| kv.key == attribute.key) {
+        Some(existing) => *existing = attribute,
+        None => attributes.push(attribute),
+    }
+}
+
+fn kv_list(fields: BTreeMap<String, Value>) -> Vec<KeyValue> {
+    fields
+        .into_iter()
+        .map(|(key, value)| key_value(key, value))
+        .collect()
+}
+
+fn key_value(key: String, value: Value) -> KeyValue {
+    KeyValue {
+        key,
+        value: Some(any_value(value)),
+    }
+}
+
+fn any_value(value: Value) -> AnyValue {
+    let value = match value {
+        Value::Bytes(bytes) => Some(PBValue::StringValue(
+            String::from_utf8_lossy(&bytes).into_owned(),
+        )),
+        Value::Regex(regex) => Some(PBValue::StringValue(regex.as_str().to_owned())),
+        Value::Integer(value) => Some(PBValue::IntValue(value)),
+        Value::Float(value) => Some(PBValue::DoubleValue(value.into_inner())),
+        Value::Boolean(value) => Some(PBValue::BoolValue(value)),
+        Value::Timestamp(timestamp) => Some(PBValue::StringValue(
+            timestamp.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+        )),
+        Value::Object(fields) => Some(PBValue::KvlistValue(KeyValueList {
+            values: kv_list(fields),
+        })),
+        Value::Array(values) => Some(PBValue::ArrayValue(ArrayValue {
+            values: values.into_iter().map(any_value).collect(),
+        })),
+        Value::Null => None,
+    };
+    AnyValue { value }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use opentelemetry_proto::proto::metrics::v1::metric::Data;
+    use vector_core::{event::metric::Bucket, metrics::AgentDDSketch};
+
+    use super::*;
+    use crate::event::Event;
+
+    fn string(value: &str) -> Option<AnyValue> {
+        Some(AnyValue {
+            value: Some(PBValue::StringValue(value.to_owned())),
+        })
+    }
+
+    fn source_log() -> LogEvent {
+        let mut log = LogEvent::from("hello");
+        log.insert(
+            RESOURCE_KEY,
+            BTreeMap::from([("service.name".to_owned(), Value::from("checkout"))]),
+        );
+        log.insert("scope.name", "lib");
+        log.insert(
+            ATTRIBUTES_KEY,
+            BTreeMap::from([("http.method".to_owned(), Value::from("GET"))]),
+        );
+        log.insert("trace_id", "0123456789abcdef0123456789abcdef");
+        log.insert("span_id", "not hex");
+        log.insert("severity_text", "INFO");
+        log.insert("severity_number", 9);
+        log.insert("host", "example.com");
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.timestamp_opt(1, 500).single().unwrap(),
+        );
+        log.insert(log_schema().source_type_key(), "opentelemetry");
+        log
+    }
+
+    #[test]
+    fn encodes_logs() {
+        let encoder = OpentelemetryEncoder::new(BTreeMap::from([(
+            "host.name".to_owned(),
+            "host".to_owned(),
+        )]));
+
+        let request = encoder.encode_logs(vec![source_log(), source_log()]);
+
+        assert_eq!(request.resource_logs.len(), 1);
+        let resource_logs = &request.resource_logs[0];
+        let resource = resource_logs.resource.as_ref().unwrap();
+        assert_eq!(
+            resource
+                .attributes
+                .iter()
+                .map(|kv| (kv.key.as_str(), kv.value.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("service.name", string("checkout")),
+                ("host.name", string("example.com")),
+            ]
+        );
+
+        assert_eq!(resource_logs.scope_logs.len(), 1);
+        let scope_logs = &resource_logs.scope_logs[0];
+        assert_eq!(scope_logs.scope.as_ref().unwrap().name, "lib");
+        assert_eq!(scope_logs.log_records.len(), 2);
+
+        let record = &scope_logs.log_records[0];
+        assert_eq!(record.body, string("hello"));
+        assert_eq!(record.time_unix_nano, 1_000_000_500);
+        assert_eq!(record.severity_text, "INFO");
+        assert_eq!(record.severity_number, 9);
+        assert_eq!(
+            hex::encode(&record.trace_id),
+            "0123456789abcdef0123456789abcdef"
+        );
+        assert!(record.span_id.is_empty());
+
+        let attributes = record
+            .attributes
+            .iter()
+            .map(|kv| kv.key.as_str())
+            .collect::<Vec<_>>();
+        // the invalid span ID is kept, but the source type is not
+        assert_eq!(attributes, vec!["http.method", "span_id"]);
+    }
+
+    #[test]
+    fn groups_logs_by_resource() {
+        let encoder = OpentelemetryEncoder::default();
+        let mut other = source_log();
+        other.insert(
+            RESOURCE_KEY,
+            BTreeMap::from([("service.name".to_owned(), Value::from("cart"))]),
+        );
+
+        let request = encoder.encode_logs(vec![source_log(), other, source_log()]);
+
+        assert_eq!(request.resource_logs.len(), 2);
+        assert_eq!(request.resource_logs[0].scope_logs[0].log_records.len(), 2);
+        assert_eq!(request.resource_logs[1].scope_logs[0].log_records.len(), 1);
+    }
+
+    #[test]
+    fn encodes_metrics() {
+        let encoder = OpentelemetryEncoder::new(BTreeMap::from([(
+            "service.name".to_owned(),
+            "service".to_owned(),
+        )]));
+        let counter = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 2.0 },
+        )
+        .with_namespace(Some("http"))
+        .with_tags(Some(BTreeMap::from([
+            ("service".to_owned(), "checkout".to_owned()),
+            ("code".to_owned(), "200".to_owned()),
+        ])));
+        let histogram = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 3,
+                    },
+                    Bucket {
+                        upper_limit: 2.0,
+                        count: 4,
+                    },
+                ],
+                count: 10,
+                sum: 15.0,
+            },
+        );
+
+        let request = encoder.encode_metrics(vec![counter, histogram]);
+
+        assert_eq!(request.resource_metrics.len(), 2);
+        let counter = &request.resource_metrics[0];
+        assert_eq!(
+            counter.resource.as_ref().unwrap().attributes,
+            vec![KeyValue {
+                key: "service.name".to_owned(),
+                value: string("checkout"),
+            }]
+        );
+        let metric = &counter.scope_metrics[0].metrics[0];
+        assert_eq!(metric.name, "http.requests");
+        match metric.data.as_ref().unwrap() {
+            Data::Sum(sum) => {
+                assert_eq!(
+                    sum.aggregation_temporality,
+                    AggregationTemporality::Delta as i32
+                );
+                assert!(sum.is_monotonic);
+                assert_eq!(sum.data_points[0].attributes.len(), 1);
+                assert_eq!(
+                    sum.data_points[0].value,
+                    Some(number_data_point::Value::AsDouble(2.0))
+                );
+            }
+            data => panic!("unexpected data {:?}", data),
+        }
+
+        match request.resource_metrics[1].scope_metrics[0].metrics[0]
+            .data
+            .as_ref()
+            .unwrap()
+        {
+            Data::Histogram(histogram) => {
+                assert_eq!(
+                    histogram.aggregation_temporality,
+                    AggregationTemporality::Cumulative as i32
+                );
+                let point = &histogram.data_points[0];
+                assert_eq!(point.explicit_bounds, vec![1.0, 2.0]);
+                assert_eq!(point.bucket_counts, vec![3, 4, 3]);
+            }
+            data => panic!("unexpected data {:?}", data),
+        }
+    }
+
+    #[test]
+    fn drops_sketches() {
+        let sketch = Event::from(Metric::new(
+            "sketch",
+            MetricKind::Incremental,
+            MetricValue::from(AgentDDSketch::with_agent_defaults()),
+        ));
+
+        let request = OpentelemetryEncoder::default().encode_metrics(vec![sketch.into_metric()]);
+
+        assert!(request.resource_metrics.is_empty());
+    }
+}
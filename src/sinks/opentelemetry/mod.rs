@@ -0,0 +1,164 @@
+use http::StatusCode;
+use snafu::Snafu;
+
+mod config;
+mod encoder;
+mod service;
+mod sink;
+
+pub use config::OpentelemetryConfig;
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum OpentelemetrySinkError {
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: tonic::Status },
+
+    #[snafu(display("HTTP request failed: {}", source))]
+    Http { source: crate::http::HttpError },
+
+    #[snafu(display("Export failed with status {}: {}", status, body))]
+    ResponseStatus { status: StatusCode, body: String },
+
+    #[snafu(display("Failed to compress the request: {}", source))]
+    Compression { source: std::io::Error },
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{Buf, Bytes};
+    use futures::{channel::mpsc, StreamExt};
+    use http::request::Parts;
+    use opentelemetry_proto::proto::{
+        collector::logs::v1::ExportLogsServiceRequest, common::v1::any_value::Value as PBValue,
+    };
+    use prost::Message;
+    use vector_core::event::{BatchNotifier, BatchStatus};
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig as _, SinkContext},
+        sinks::util::test::build_test_server_generic,
+        test_util::{
+            components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+            next_addr, random_lines_with_stream,
+        },
+    };
+
+    // one byte for the compression flag plus four bytes for the length
+    const GRPC_HEADER_SIZE: usize = 5;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpentelemetryConfig>();
+    }
+
+    async fn deliver_logs(
+        protocol: &str,
+        status: StatusCode,
+        response: Bytes,
+    ) -> (Vec<String>, Vec<(Parts, Bytes)>, BatchStatus) {
+        let addr = next_addr();
+        let config = format!(
+            r#"
+                endpoint = "http://{}"
+                protocol = "{}"
+                request.headers.authorization = "Bearer token"
+            "#,
+            addr, protocol
+        );
+        let config: OpentelemetryConfig = toml::from_str(&config).unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let grpc = protocol == "grpc";
+        let (rx, trigger, server) = build_test_server_generic(addr, move || {
+            let mut builder = hyper::Response::builder().status(status);
+            if grpc {
+                builder = builder
+                    .header("grpc-status", "0") // OK
+                    .header("content-type", "application/grpc");
+            }
+            builder.body(hyper::Body::from(response.clone())).unwrap()
+        });
+        tokio::spawn(server);
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (lines, events) = random_lines_with_stream(8, 10, Some(batch));
+        if status.is_success() {
+            run_and_assert_sink_compliance(sink, events, &HTTP_SINK_TAGS).await;
+        } else {
+            sink.run(events).await.expect("Running sink failed");
+        }
+        drop(trigger);
+
+        let requests = rx.collect::<Vec<_>>().await;
+        (lines, requests, receiver.try_recv().unwrap())
+    }
+
+    fn received_lines(mut body: Bytes) -> Vec<String> {
+        let request = ExportLogsServiceRequest::decode(&mut body).unwrap();
+        request
+            .resource_logs
+            .into_iter()
+            .flat_map(|resource_logs| resource_logs.scope_logs)
+            .flat_map(|scope_logs| scope_logs.log_records)
+            .map(|record| match record.body.unwrap().value.unwrap() {
+                PBValue::StringValue(line) => line,
+                value => panic!("unexpected body {:?}", value),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn delivers_logs_over_grpc() {
+        // the response message is empty, so only its header is sent
+        let response = Bytes::from_static(&[0; GRPC_HEADER_SIZE]);
+        let (lines, requests, status) = deliver_logs("grpc", StatusCode::OK, response).await;
+
+        assert_eq!(status, BatchStatus::Delivered);
+        assert_eq!(requests.len(), 1);
+        let (parts, mut body) = requests.into_iter().next().unwrap();
+        assert_eq!(
+            parts.uri.path(),
+            "/opentelemetry.proto.collector.logs.v1.LogsService/Export"
+        );
+        assert_eq!(parts.headers["authorization"], "Bearer token");
+        body.advance(GRPC_HEADER_SIZE);
+        assert_eq!(received_lines(body), lines);
+    }
+
+    #[tokio::test]
+    async fn delivers_logs_over_http() {
+        let (lines, requests, status) = deliver_logs("http", StatusCode::OK, Bytes::new()).await;
+
+        assert_eq!(status, BatchStatus::Delivered);
+        assert_eq!(requests.len(), 1);
+        let (parts, body) = requests.into_iter().next().unwrap();
+        assert_eq!(parts.uri.path(), "/v1/logs");
+        assert_eq!(parts.headers["content-type"], "application/x-protobuf");
+        assert_eq!(parts.headers["authorization"], "Bearer token");
+        assert_eq!(received_lines(body), lines);
+    }
+
+    #[tokio::test]
+    async fn rejects_logs_on_client_errors() {
+        let (_, requests, status) =
+            deliver_logs("http", StatusCode::BAD_REQUEST, Bytes::new()).await;
+
+        assert_eq!(status, BatchStatus::Rejected);
+        // client errors are not retried
+        assert_eq!(requests.len(), 1);
+    }
+
+    #[test]
+    fn retries_retryable_statuses() {
+        assert!(service::is_retriable_status(
+            StatusCode::SERVICE_UNAVAILABLE
+        ));
+        assert!(service::is_retriable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!service::is_retriable_status(StatusCode::BAD_REQUEST));
+        assert!(!service::is_retriable_status(
+            StatusCode::INTERNAL_SERVER_ERROR
+        ));
+    }
+}
@@ -0,0 +1,332 @@
+use std::{
+    io::Write,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures::{future::BoxFuture, TryFutureExt};
+use http::{header::HeaderName, HeaderMap, HeaderValue, Request, StatusCode, Uri};
+use hyper::{client::HttpConnector, Body};
+use hyper_openssl::HttpsConnector;
+use hyper_proxy::ProxyConnector;
+use opentelemetry_proto::proto::collector::{
+    logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+    metrics::v1::{metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest},
+};
+use prost::Message;
+use tonic::{body::BoxBody, codec::CompressionEncoding, IntoRequest};
+use tower::Service;
+use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
+
+use super::OpentelemetrySinkError;
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    http::HttpClient,
+    internal_events::EndpointBytesSent,
+    sinks::util::{uri, Compression, Compressor, UriSerde},
+    Error,
+};
+
+/// An export request for either of the signals.
+#[derive(Clone, Debug)]
+pub enum OpentelemetryPayload {
+    Logs(ExportLogsServiceRequest),
+    Metrics(ExportMetricsServiceRequest),
+}
+
+impl OpentelemetryPayload {
+    fn encode_to_vec(&self) -> Vec<u8> {
+        match self {
+            Self::Logs(request) => request.encode_to_vec(),
+            Self::Metrics(request) => request.encode_to_vec(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct OpentelemetryRequest {
+    pub payload: OpentelemetryPayload,
+    pub finalizers: EventFinalizers,
+    pub events_count: usize,
+    pub events_byte_size: usize,
+}
+
+impl Finalizable for OpentelemetryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+pub struct OpentelemetryResponse {
+    events_count: usize,
+    events_byte_size: usize,
+}
+
+impl DriverResponse for OpentelemetryResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(self.events_count, self.events_byte_size)
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Transport {
+    Grpc {
+        logs: LogsServiceClient<HyperSvc>,
+        metrics: MetricsServiceClient<HyperSvc>,
+    },
+    Http {
+        client: HttpClient,
+        logs_uri: Uri,
+        metrics_uri: Uri,
+        headers: HeaderMap,
+        compression: bool,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct OpentelemetryService {
+    transport: Transport,
+    protocol: String,
+    endpoint: String,
+}
+
+impl OpentelemetryService {
+    pub fn grpc(
+        hyper_client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+        uri: Uri,
+        headers: HeaderMap,
+        compression: bool,
+    ) -> Self {
+        let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
+        let svc = HyperSvc {
+            uri,
+            headers,
+            client: hyper_client,
+        };
+        let mut logs = LogsServiceClient::new(svc.clone());
+        let mut metrics = MetricsServiceClient::new(svc);
+        if compression {
+            logs = logs.send_compressed(CompressionEncoding::Gzip);
+            metrics = metrics.send_compressed(CompressionEncoding::Gzip);
+        }
+
+        Self {
+            transport: Transport::Grpc { logs, metrics },
+            protocol,
+            endpoint,
+        }
+    }
+
+    /// Exports to the `/v1/logs` and `/v1/metrics` paths of the given endpoint.
+    pub fn http(
+        client: HttpClient,
+        uri: &UriSerde,
+        headers: HeaderMap,
+        compression: bool,
+    ) -> crate::Result<Self> {
+        let (protocol, endpoint) = uri::protocol_endpoint(uri.uri.clone());
+        Ok(Self {
+            transport: Transport::Http {
+                client,
+                logs_uri: uri.append_path("v1/logs")?.uri,
+                metrics_uri: uri.append_path("v1/metrics")?.uri,
+                headers,
+                compression,
+            },
+            protocol,
+            endpoint,
+        })
+    }
+}
+
+impl Service<OpentelemetryRequest> for OpentelemetryService {
+    type Response = OpentelemetryResponse;
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Readiness of the gRPC clients is checked when exporting, as in the `vector` sink.
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, request: OpentelemetryRequest) -> Self::Future {
+        let service = self.clone();
+        let events_count = request.events_count;
+        let events_byte_size = request.events_byte_size;
+
+        let future = async move {
+            let (protocol, endpoint) = (service.protocol.clone(), service.endpoint.clone());
+            service
+                .export(request.payload)
+                .map_ok(|byte_size| {
+                    emit!(EndpointBytesSent {
+                        byte_size,
+                        protocol: &protocol,
+                        endpoint: &endpoint,
+                    });
+                    OpentelemetryResponse {
+                        events_count,
+                        events_byte_size,
+                    }
+                })
+                .map_err(Into::into)
+                .await
+        };
+
+        Box::pin(future)
+    }
+}
+
+impl OpentelemetryService {
+    /// Exports the payload, returning the number of bytes sent.
+    pub async fn export(
+        self,
+        payload: OpentelemetryPayload,
+    ) -> Result<usize, OpentelemetrySinkError> {
+        match self.transport {
+            Transport::Grpc {
+                mut logs,
+                mut metrics,
+            } => {
+                let byte_size = match payload {
+                    OpentelemetryPayload::Logs(payload) => {
+                        let byte_size = payload.encoded_len();
+                        logs.export(payload.into_request()).await.map(|_| byte_size)
+                    }
+                    OpentelemetryPayload::Metrics(payload) => {
+                        let byte_size = payload.encoded_len();
+                        metrics
+                            .export(payload.into_request())
+                            .await
+                            .map(|_| byte_size)
+                    }
+                };
+                byte_size.map_err(|source| OpentelemetrySinkError::Request { source })
+            }
+            Transport::Http {
+                client,
+                logs_uri,
+                metrics_uri,
+                headers,
+                compression,
+            } => {
+                let uri = match payload {
+                    OpentelemetryPayload::Logs(_) => logs_uri,
+                    OpentelemetryPayload::Metrics(_) => metrics_uri,
+                };
+                send_http(&client, uri, headers, compression, &payload).await
+            }
+        }
+    }
+}
+
+/// Posts the protobuf encoded payload.
+async fn send_http(
+    client: &HttpClient,
+    uri: Uri,
+    headers: HeaderMap,
+    compression: bool,
+    payload: &OpentelemetryPayload,
+) -> Result<usize, OpentelemetrySinkError> {
+    let mut body = payload.encode_to_vec();
+    if compression {
+        let mut compressor = Compressor::from(Compression::gzip_default());
+        let compressed = compressor
+            .write_all(&body)
+            .and_then(|()| compressor.finish())
+            .map_err(|source| OpentelemetrySinkError::Compression { source })?;
+        body = compressed.to_vec();
+    }
+    let byte_size = body.len();
+
+    let mut builder = Request::post(uri).header("Content-Type", "application/x-protobuf");
+    if compression {
+        builder = builder.header("Content-Encoding", "gzip");
+    }
+    let mut request = builder
+        .body(Body::from(body))
+        .expect("the request should be valid");
+    request.headers_mut().extend(headers);
+
+    let response = client
+        .send(request)
+        .await
+        .map_err(|source| OpentelemetrySinkError::Http { source })?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(byte_size);
+    }
+    let body = hyper::body::to_bytes(response.into_body())
+        .await
+        .unwrap_or_else(|_| Bytes::new());
+    Err(OpentelemetrySinkError::ResponseStatus {
+        status,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+/// Builds the map of the configured headers, checking that they are valid.
+pub fn build_headers<'a>(
+    headers: impl IntoIterator<Item = (&'a String, &'a String)>,
+) -> crate::Result<HeaderMap> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        map.insert(
+            HeaderName::from_bytes(name.as_bytes())?,
+            HeaderValue::from_str(value)?,
+        );
+    }
+    Ok(map)
+}
+
+/// Whether the failed export may succeed when retried.
+///
+/// These are the codes and statuses the OTLP specification lists as retryable.
+pub const fn is_retriable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+#[derive(Clone, Debug)]
+pub struct HyperSvc {
+    uri: Uri,
+    headers: HeaderMap,
+    client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+}
+
+impl Service<hyper::Request<BoxBody>> for HyperSvc {
+    type Response = hyper::Response<hyper::Body>;
+    type Error = hyper::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, mut req: hyper::Request<BoxBody>) -> Self::Future {
+        let uri = Uri::builder()
+            .scheme(self.uri.scheme().unwrap().clone())
+            .authority(self.uri.authority().unwrap().clone())
+            .path_and_query(req.uri().path_and_query().unwrap().clone())
+            .build()
+            .unwrap();
+
+        *req.uri_mut() = uri;
+        req.headers_mut().extend(self.headers.clone());
+
+        Box::pin(self.client.request(req))
+    }
+}
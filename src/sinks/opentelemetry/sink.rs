@@ -0,0 +1,103 @@
+use std::fmt;
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::Service;
+use vector_core::{
+    partition::Partitioner,
+    stream::{BatcherSettings, DriverResponse},
+    ByteSizeOf,
+};
+
+use super::{
+    encoder::OpentelemetryEncoder,
+    service::{OpentelemetryPayload, OpentelemetryRequest},
+};
+use crate::{
+    event::{Event, EventFinalizers, Finalizable},
+    sinks::util::{SinkBuilderExt, StreamSink},
+};
+
+/// The signals are exported separately, so they're batched separately.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Signal {
+    Logs,
+    Metrics,
+}
+
+struct SignalPartitioner;
+
+impl Partitioner for SignalPartitioner {
+    type Item = Event;
+    type Key = Signal;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        match item {
+            Event::Metric(_) => Signal::Metrics,
+            // the sink doesn't accept traces
+            Event::Log(_) | Event::Trace(_) => Signal::Logs,
+        }
+    }
+}
+
+pub struct OpentelemetrySink<S> {
+    pub batch_settings: BatcherSettings,
+    pub encoder: OpentelemetryEncoder,
+    pub service: S,
+}
+
+impl<S> OpentelemetrySink<S>
+where
+    S: Service<OpentelemetryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let encoder = self.encoder;
+        input
+            .batched_partitioned(SignalPartitioner, self.batch_settings)
+            .map(move |(signal, events)| build_request(&encoder, signal, events))
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+fn build_request(
+    encoder: &OpentelemetryEncoder,
+    signal: Signal,
+    mut events: Vec<Event>,
+) -> OpentelemetryRequest {
+    let finalizers = events.take_finalizers();
+    let events_count = events.len();
+    let events_byte_size = events.iter().map(ByteSizeOf::size_of).sum();
+    let payload = match signal {
+        Signal::Logs => OpentelemetryPayload::Logs(
+            encoder.encode_logs(events.into_iter().map(Event::into_log).collect()),
+        ),
+        Signal::Metrics => OpentelemetryPayload::Metrics(
+            encoder.encode_metrics(events.into_iter().map(Event::into_metric).collect()),
+        ),
+    };
+
+    OpentelemetryRequest {
+        payload,
+        finalizers,
+        events_count,
+        events_byte_size,
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for OpentelemetrySink<S>
+where
+    S: Service<OpentelemetryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,385 @@
+//! The connections to the database, and the statements prepared on them.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use futures::pin_mut;
+use postgres_openssl::MakeTlsConnector;
+use snafu::ResultExt;
+use tokio_postgres::{
+    binary_copy::BinaryCopyInWriter,
+    types::{ToSql, Type},
+    Client, Config, NoTls, Statement,
+};
+
+use super::{
+    value::{Column, ColumnType, SqlValue},
+    ConnectSnafu, PostgresqlSinkError, PrepareSnafu, WriteSnafu,
+};
+
+/// Quotes an identifier, so that it is not folded to lower case.
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Quotes each part of a table name which may be qualified by its schema.
+fn quote_table(name: &str) -> String {
+    name.split('.')
+        .map(quote_identifier)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// The SQL statements writing to the table.
+#[derive(Debug)]
+pub struct Statements {
+    table: String,
+    /// The name of each column, and the field of the events its values are taken from.
+    columns: Vec<(String, String)>,
+    conflict_keys: Vec<String>,
+}
+
+impl Statements {
+    pub fn new(table: String, columns: Vec<(String, String)>, conflict_keys: Vec<String>) -> Self {
+        Self {
+            table,
+            columns,
+            conflict_keys,
+        }
+    }
+
+    fn column_list(&self) -> String {
+        self.columns
+            .iter()
+            .map(|(name, _)| quote_identifier(name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Describes the columns, for their types to be known once it is prepared.
+    fn select(&self) -> String {
+        format!(
+            "SELECT {} FROM {} LIMIT 0",
+            self.column_list(),
+            quote_table(&self.table)
+        )
+    }
+
+    fn copy(&self) -> String {
+        format!(
+            "COPY {} ({}) FROM STDIN (FORMAT binary)",
+            quote_table(&self.table),
+            self.column_list()
+        )
+    }
+
+    /// Inserts the rows from an array of values for each column, updating the rows which conflict
+    /// with them.
+    ///
+    /// The types of the arrays are given when the statement is prepared.
+    fn upsert(&self) -> String {
+        let arrays = (1..=self.columns.len())
+            .map(|index| format!("${}", index))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let updates = self
+            .columns
+            .iter()
+            .filter(|(name, _)| !self.conflict_keys.contains(name))
+            .map(|(name, _)| format!("{0} = EXCLUDED.{0}", quote_identifier(name)))
+            .collect::<Vec<_>>();
+        let action = if updates.is_empty() {
+            "NOTHING".to_owned()
+        } else {
+            format!("UPDATE SET {}", updates.join(", "))
+        };
+        format!(
+            "INSERT INTO {} ({}) SELECT * FROM UNNEST({}) ON CONFLICT ({}) DO {}",
+            quote_table(&self.table),
+            self.column_list(),
+            arrays,
+            self.conflict_keys
+                .iter()
+                .map(|key| quote_identifier(key))
+                .collect::<Vec<_>>()
+                .join(", "),
+            action
+        )
+    }
+}
+
+/// A connection, and its statement writing the rows.
+pub struct Connection {
+    client: Client,
+    columns: Vec<Column>,
+    types: Vec<Type>,
+    statement: Statement,
+    /// The indexes of the conflict keys among the columns, when the rows are upserted.
+    conflict_keys: Vec<usize>,
+}
+
+impl Connection {
+    async fn connect(
+        config: &Config,
+        tls: Option<&MakeTlsConnector>,
+        statements: &Statements,
+    ) -> Result<Self, PostgresqlSinkError> {
+        let client = match tls {
+            Some(tls) => {
+                let (client, connection) =
+                    config.connect(tls.clone()).await.context(ConnectSnafu)?;
+                tokio::spawn(connection);
+                client
+            }
+            None => {
+                let (client, connection) = config.connect(NoTls).await.context(ConnectSnafu)?;
+                tokio::spawn(connection);
+                client
+            }
+        };
+
+        let described = client
+            .prepare(&statements.select())
+            .await
+            .context(PrepareSnafu)?;
+        let types = described
+            .columns()
+            .iter()
+            .map(|column| column.type_().clone())
+            .collect::<Vec<_>>();
+        let columns = statements
+            .columns
+            .iter()
+            .zip(&types)
+            .map(|((name, field), ty)| {
+                Ok(Column {
+                    name: name.clone(),
+                    field: field.clone(),
+                    column_type: ColumnType::from_type(ty).ok_or_else(|| {
+                        PostgresqlSinkError::UnsupportedType {
+                            column: name.clone(),
+                            type_name: ty.name().to_owned(),
+                        }
+                    })?,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let conflict_keys = statements
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(_, (name, _))| statements.conflict_keys.contains(name))
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        let statement = if !conflict_keys.is_empty() {
+            let array_types = types
+                .iter()
+                .map(|ty| array_type(ty).expect("the column types have array types"))
+                .collect::<Vec<_>>();
+            client
+                .prepare_typed(&statements.upsert(), &array_types)
+                .await
+        } else {
+            client.prepare(&statements.copy()).await
+        }
+        .context(PrepareSnafu)?;
+
+        Ok(Self {
+            client,
+            columns,
+            types,
+            statement,
+            conflict_keys,
+        })
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.client.is_closed()
+    }
+
+    pub async fn write(&self, rows: Vec<Vec<SqlValue>>) -> Result<(), PostgresqlSinkError> {
+        if !self.conflict_keys.is_empty() {
+            self.upsert(rows).await
+        } else {
+            self.copy(rows).await
+        }
+    }
+
+    async fn copy(&self, rows: Vec<Vec<SqlValue>>) -> Result<(), PostgresqlSinkError> {
+        let sink = self
+            .client
+            .copy_in(&self.statement)
+            .await
+            .context(WriteSnafu)?;
+        let writer = BinaryCopyInWriter::new(sink, &self.types);
+        pin_mut!(writer);
+        for row in &rows {
+            let values = row
+                .iter()
+                .map(|value| value as &(dyn ToSql + Sync))
+                .collect::<Vec<_>>();
+            writer.as_mut().write(&values).await.context(WriteSnafu)?;
+        }
+        writer.finish().await.context(WriteSnafu)?;
+        Ok(())
+    }
+
+    async fn upsert(&self, rows: Vec<Vec<SqlValue>>) -> Result<(), PostgresqlSinkError> {
+        // A statement can't update the same row twice, so only the last of the rows with the same
+        // conflict keys is written. The values are compared by their text, as floats can't be
+        // hashed.
+        let mut last_rows = HashMap::new();
+        for (index, row) in rows.iter().enumerate() {
+            let key = self
+                .conflict_keys
+                .iter()
+                .map(|key| format!("{:?}", row[*key]))
+                .collect::<Vec<_>>();
+            last_rows.insert(key, index);
+        }
+        let mut written = last_rows.into_values().collect::<Vec<_>>();
+        written.sort_unstable();
+
+        let mut arrays = vec![Vec::with_capacity(written.len()); self.columns.len()];
+        for index in written {
+            for (array, value) in arrays.iter_mut().zip(&rows[index]) {
+                array.push(value.clone());
+            }
+        }
+        let params = arrays
+            .iter()
+            .map(|array| array as &(dyn ToSql + Sync))
+            .collect::<Vec<_>>();
+        self.client
+            .execute(&self.statement, &params)
+            .await
+            .context(WriteSnafu)?;
+        Ok(())
+    }
+}
+
+/// The type of the arrays of the given type.
+fn array_type(ty: &Type) -> Option<Type> {
+    Some(match *ty {
+        Type::BOOL => Type::BOOL_ARRAY,
+        Type::INT2 => Type::INT2_ARRAY,
+        Type::INT4 => Type::INT4_ARRAY,
+        Type::INT8 => Type::INT8_ARRAY,
+        Type::FLOAT4 => Type::FLOAT4_ARRAY,
+        Type::FLOAT8 => Type::FLOAT8_ARRAY,
+        Type::TEXT => Type::TEXT_ARRAY,
+        Type::VARCHAR => Type::VARCHAR_ARRAY,
+        Type::BPCHAR => Type::BPCHAR_ARRAY,
+        Type::NAME => Type::NAME_ARRAY,
+        Type::BYTEA => Type::BYTEA_ARRAY,
+        Type::JSON => Type::JSON_ARRAY,
+        Type::JSONB => Type::JSONB_ARRAY,
+        Type::TIMESTAMP => Type::TIMESTAMP_ARRAY,
+        Type::TIMESTAMPTZ => Type::TIMESTAMPTZ_ARRAY,
+        Type::DATE => Type::DATE_ARRAY,
+        Type::UUID => Type::UUID_ARRAY,
+        _ => return None,
+    })
+}
+
+/// Keeps the idle connections, opening another one when a request finds none of them.
+#[derive(Clone)]
+pub struct ConnectionPool {
+    config: Arc<Config>,
+    tls: Option<MakeTlsConnector>,
+    statements: Arc<Statements>,
+    idle: Arc<Mutex<Vec<Connection>>>,
+}
+
+impl ConnectionPool {
+    pub fn new(config: Config, tls: Option<MakeTlsConnector>, statements: Statements) -> Self {
+        Self {
+            config: Arc::new(config),
+            tls,
+            statements: Arc::new(statements),
+            idle: Arc::default(),
+        }
+    }
+
+    pub async fn take(&self) -> Result<Connection, PostgresqlSinkError> {
+        loop {
+            let idle = self.idle.lock().expect("mutex poisoned").pop();
+            match idle {
+                Some(connection) if connection.is_closed() => continue,
+                Some(connection) => return Ok(connection),
+                None => break,
+            }
+        }
+        Connection::connect(&self.config, self.tls.as_ref(), &self.statements).await
+    }
+
+    /// Keeps the connection for the next requests, unless it was closed.
+    pub fn put_back(&self, connection: Connection) {
+        if !connection.is_closed() {
+            self.idle.lock().expect("mutex poisoned").push(connection);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statements(conflict_keys: &[&str]) -> Statements {
+        Statements::new(
+            "public.Events".to_owned(),
+            vec![
+                ("id".to_owned(), "id".to_owned()),
+                ("host".to_owned(), "host".to_owned()),
+                ("message".to_owned(), "message".to_owned()),
+            ],
+            conflict_keys.iter().map(|key| (*key).to_owned()).collect(),
+        )
+    }
+
+    #[test]
+    fn quotes_identifiers() {
+        assert_eq!(quote_identifier(r#"my "column""#), r#""my ""column""""#);
+        assert_eq!(quote_table("public.Events"), r#""public"."Events""#);
+    }
+
+    #[test]
+    fn builds_copy_statements() {
+        let statements = statements(&[]);
+        assert_eq!(
+            statements.select(),
+            r#"SELECT "id", "host", "message" FROM "public"."Events" LIMIT 0"#
+        );
+        assert_eq!(
+            statements.copy(),
+            r#"COPY "public"."Events" ("id", "host", "message") FROM STDIN (FORMAT binary)"#
+        );
+    }
+
+    #[test]
+    fn builds_upsert_statements() {
+        assert_eq!(
+            statements(&["id"]).upsert(),
+            concat!(
+                r#"INSERT INTO "public"."Events" ("id", "host", "message") "#,
+                r#"SELECT * FROM UNNEST($1, $2, $3) ON CONFLICT ("id") "#,
+                r#"DO UPDATE SET "host" = EXCLUDED."host", "message" = EXCLUDED."message""#
+            )
+        );
+        assert_eq!(
+            statements(&["id", "host", "message"]).upsert(),
+            concat!(
+                r#"INSERT INTO "public"."Events" ("id", "host", "message") "#,
+                r#"SELECT * FROM UNNEST($1, $2, $3) ON CONFLICT ("id", "host", "message") "#,
+                r#"DO NOTHING"#
+            )
+        );
+    }
+}
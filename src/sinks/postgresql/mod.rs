@@ -0,0 +1,54 @@
+//! The `postgresql` sink.
+//!
+//! Events are written as rows of a table, whose columns are taken from the fields of the events.
+//! Batches are written with a binary `COPY`, or, when the table has conflict keys, with an
+//! `INSERT ... ON CONFLICT` statement taking the values of each column as an array. The statements
+//! are prepared once for each connection, and reused for the batches it writes.
+
+use snafu::Snafu;
+use tokio_postgres::Error as PgError;
+
+mod config;
+mod connection;
+mod service;
+mod sink;
+mod value;
+
+pub use config::PostgresqlConfig;
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum PostgresqlSinkError {
+    #[snafu(display("Failed to connect: {}", source))]
+    Connect { source: PgError },
+
+    #[snafu(display("Failed to prepare the statements: {}", source))]
+    Prepare { source: PgError },
+
+    #[snafu(display("Column {:?} has the unsupported type {:?}", column, type_name))]
+    UnsupportedType { column: String, type_name: String },
+
+    #[snafu(display("Failed to write the rows: {}", source))]
+    Write { source: PgError },
+}
+
+impl PostgresqlSinkError {
+    pub fn is_retriable(&self) -> bool {
+        let source = match self {
+            Self::Connect { source } | Self::Prepare { source } | Self::Write { source } => source,
+            Self::UnsupportedType { .. } => return false,
+        };
+        match source.code() {
+            // Errors without a code are I/O errors, or the connection being closed.
+            None => true,
+            Some(code) => {
+                let code = code.code();
+                // Connection exceptions, transaction rollbacks such as deadlocks, insufficient
+                // resources, and the server shutting down.
+                ["08", "40", "53", "57P"]
+                    .iter()
+                    .any(|class| code.starts_with(class))
+            }
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use tower::Service;
+use tracing::Instrument;
+use vector_core::{internal_event::CountByteSize, stream::DriverResponse, ByteSizeOf};
+
+use super::{connection::ConnectionPool, value::encode_row, PostgresqlSinkError};
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable, LogEvent},
+    internal_events::PostgresqlRowRejected,
+    sinks::util::retries::RetryLogic,
+};
+
+/// An event written to the table, keeping its finalizers to reject it on its own.
+#[derive(Clone, Debug)]
+pub struct PostgresqlRow {
+    pub event: LogEvent,
+    pub finalizers: EventFinalizers,
+}
+
+#[derive(Clone, Debug)]
+pub struct PostgresqlRequest {
+    pub rows: Vec<PostgresqlRow>,
+    pub finalizers: EventFinalizers,
+}
+
+impl Finalizable for PostgresqlRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+#[derive(Debug)]
+pub struct PostgresqlResponse {
+    events_count: usize,
+    events_byte_size: usize,
+}
+
+impl DriverResponse for PostgresqlResponse {
+    // The rejected rows are marked as such by the service, which takes precedence.
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(self.events_count, self.events_byte_size)
+    }
+}
+
+#[derive(Clone)]
+pub struct PostgresqlService {
+    pool: ConnectionPool,
+}
+
+impl PostgresqlService {
+    pub const fn new(pool: ConnectionPool) -> Self {
+        Self { pool }
+    }
+
+    async fn write(
+        self,
+        request: PostgresqlRequest,
+    ) -> Result<PostgresqlResponse, PostgresqlSinkError> {
+        let connection = self.pool.take().await?;
+
+        let mut events_count = 0;
+        let mut events_byte_size = 0;
+        let mut rows = Vec::with_capacity(request.rows.len());
+        for row in &request.rows {
+            match encode_row(connection.columns(), &row.event) {
+                Ok(values) => {
+                    events_count += 1;
+                    events_byte_size += row.event.size_of();
+                    rows.push(values);
+                }
+                Err(error) => {
+                    emit!(PostgresqlRowRejected {
+                        error: &error.to_string()
+                    });
+                    row.finalizers.update_status(EventStatus::Rejected);
+                }
+            }
+        }
+
+        if !rows.is_empty() {
+            connection.write(rows).await?;
+        }
+        self.pool.put_back(connection);
+
+        Ok(PostgresqlResponse {
+            events_count,
+            events_byte_size,
+        })
+    }
+}
+
+impl Service<PostgresqlRequest> for PostgresqlService {
+    type Response = PostgresqlResponse;
+    type Error = PostgresqlSinkError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Connections are opened by the requests which find none of them idle.
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, request: PostgresqlRequest) -> Self::Future {
+        Box::pin(
+            self.clone()
+                .write(request)
+                .instrument(info_span!("request").or_current()),
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PostgresqlRetryLogic;
+
+impl RetryLogic for PostgresqlRetryLogic {
+    type Error = PostgresqlSinkError;
+    type Response = PostgresqlResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_retriable()
+    }
+}
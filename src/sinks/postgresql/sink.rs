@@ -0,0 +1,70 @@
+use std::fmt;
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::Service;
+use vector_core::{
+    stream::{BatcherSettings, DriverResponse},
+    ByteSizeOf,
+};
+
+use super::service::{PostgresqlRequest, PostgresqlRow};
+use crate::{
+    event::{Event, EventFinalizers, Finalizable},
+    sinks::util::{SinkBuilderExt, StreamSink},
+};
+
+impl ByteSizeOf for PostgresqlRow {
+    fn allocated_bytes(&self) -> usize {
+        self.event.allocated_bytes()
+    }
+}
+
+pub struct PostgresqlSink<S> {
+    pub batch_settings: BatcherSettings,
+    pub service: S,
+}
+
+impl<S> PostgresqlSink<S>
+where
+    S: Service<PostgresqlRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        input
+            .map(|event| {
+                let mut event = event.into_log();
+                let finalizers = event.take_finalizers();
+                PostgresqlRow { event, finalizers }
+            })
+            .batched(self.batch_settings.into_byte_size_config())
+            .map(build_request)
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+fn build_request(rows: Vec<PostgresqlRow>) -> PostgresqlRequest {
+    // The rows keep their finalizers, to reject them on their own.
+    let mut finalizers = EventFinalizers::default();
+    for row in &rows {
+        finalizers.merge(row.finalizers.clone());
+    }
+    PostgresqlRequest { rows, finalizers }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for PostgresqlSink<S>
+where
+    S: Service<PostgresqlRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
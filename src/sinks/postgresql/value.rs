@@ -0,0 +1,327 @@
+//! Conversion of the fields of the events to the types of the columns of the table, which are
+//! written in the binary format of PostgreSQL.
+
+use std::{error::Error, str::FromStr};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use snafu::Snafu;
+use tokio_postgres::types::{to_sql_checked, IsNull, ToSql, Type};
+use uuid::Uuid;
+use vector_core::event::{LogEvent, Value};
+
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub enum ConversionError {
+    #[snafu(display("Column {:?}: cannot convert {} to {:?}", column, value, column_type))]
+    InvalidValue {
+        column: String,
+        column_type: ColumnType,
+        value: String,
+    },
+}
+
+/// The types of the columns which values can be written to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnType {
+    Bool,
+    Int2,
+    Int4,
+    Int8,
+    Float4,
+    Float8,
+    Text,
+    Bytea,
+    Json,
+    Jsonb,
+    Timestamp,
+    Timestamptz,
+    Date,
+    Uuid,
+}
+
+impl ColumnType {
+    pub fn from_type(ty: &Type) -> Option<Self> {
+        Some(match *ty {
+            Type::BOOL => Self::Bool,
+            Type::INT2 => Self::Int2,
+            Type::INT4 => Self::Int4,
+            Type::INT8 => Self::Int8,
+            Type::FLOAT4 => Self::Float4,
+            Type::FLOAT8 => Self::Float8,
+            Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME => Self::Text,
+            Type::BYTEA => Self::Bytea,
+            Type::JSON => Self::Json,
+            Type::JSONB => Self::Jsonb,
+            Type::TIMESTAMP => Self::Timestamp,
+            Type::TIMESTAMPTZ => Self::Timestamptz,
+            Type::DATE => Self::Date,
+            Type::UUID => Self::Uuid,
+            _ => return None,
+        })
+    }
+}
+
+/// A column of the table, and the field of the events its values are taken from.
+#[derive(Clone, Debug)]
+pub struct Column {
+    pub name: String,
+    pub field: String,
+    pub column_type: ColumnType,
+}
+
+/// A value converted for the type of its column.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SqlValue {
+    Null,
+    Bool(bool),
+    Int2(i16),
+    Int4(i32),
+    Int8(i64),
+    Float4(f32),
+    Float8(f64),
+    Text(String),
+    Bytea(Bytes),
+    /// The text of a `json` or `jsonb` value.
+    Json(String),
+    Timestamp(NaiveDateTime),
+    Timestamptz(DateTime<Utc>),
+    Date(NaiveDate),
+    Uuid(Uuid),
+}
+
+impl ToSql for SqlValue {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        match self {
+            Self::Null => Ok(IsNull::Yes),
+            Self::Bool(value) => value.to_sql(ty, out),
+            Self::Int2(value) => value.to_sql(ty, out),
+            Self::Int4(value) => value.to_sql(ty, out),
+            Self::Int8(value) => value.to_sql(ty, out),
+            Self::Float4(value) => value.to_sql(ty, out),
+            Self::Float8(value) => value.to_sql(ty, out),
+            Self::Text(value) => value.to_sql(ty, out),
+            Self::Bytea(value) => {
+                let value: &[u8] = value;
+                value.to_sql(ty, out)
+            }
+            Self::Json(value) => {
+                // The binary format of `jsonb` is its text, after a version number.
+                if *ty == Type::JSONB {
+                    out.put_u8(1);
+                }
+                out.put_slice(value.as_bytes());
+                Ok(IsNull::No)
+            }
+            Self::Timestamp(value) => value.to_sql(ty, out),
+            Self::Timestamptz(value) => value.to_sql(ty, out),
+            Self::Date(value) => value.to_sql(ty, out),
+            Self::Uuid(value) => {
+                out.put_slice(value.as_bytes());
+                Ok(IsNull::No)
+            }
+        }
+    }
+
+    // The values are only converted for the types of their columns.
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+
+    to_sql_checked!();
+}
+
+/// Converts the fields of the event to the values of the columns.
+///
+/// Missing fields are written as `NULL`.
+pub fn encode_row(columns: &[Column], log: &LogEvent) -> Result<Vec<SqlValue>, ConversionError> {
+    columns
+        .iter()
+        .map(|column| match log.get(column.field.as_str()) {
+            None | Some(Value::Null) => Ok(SqlValue::Null),
+            Some(value) => {
+                sql_value(column.column_type, value).map_err(|()| ConversionError::InvalidValue {
+                    column: column.name.clone(),
+                    column_type: column.column_type,
+                    value: value.to_string_lossy(),
+                })
+            }
+        })
+        .collect()
+}
+
+fn sql_value(column_type: ColumnType, value: &Value) -> Result<SqlValue, ()> {
+    Ok(match column_type {
+        ColumnType::Bool => SqlValue::Bool(match value {
+            Value::Boolean(boolean) => *boolean,
+            Value::Bytes(bytes) => match bytes.as_ref() {
+                b"true" => true,
+                b"false" => false,
+                _ => return Err(()),
+            },
+            _ => return Err(()),
+        }),
+        ColumnType::Int2 => SqlValue::Int2(integer(value)?.try_into().map_err(drop)?),
+        ColumnType::Int4 => SqlValue::Int4(integer(value)?.try_into().map_err(drop)?),
+        ColumnType::Int8 => SqlValue::Int8(integer(value)?),
+        ColumnType::Float4 => SqlValue::Float4(float(value)? as f32),
+        ColumnType::Float8 => SqlValue::Float8(float(value)?),
+        ColumnType::Text => match value {
+            Value::Bytes(bytes) => SqlValue::Text(String::from_utf8_lossy(bytes).into_owned()),
+            value => SqlValue::Text(value.to_string_lossy()),
+        },
+        ColumnType::Bytea => match value {
+            Value::Bytes(bytes) => SqlValue::Bytea(bytes.clone()),
+            _ => return Err(()),
+        },
+        ColumnType::Json | ColumnType::Jsonb => {
+            SqlValue::Json(serde_json::to_string(value).map_err(drop)?)
+        }
+        ColumnType::Timestamp => SqlValue::Timestamp(timestamp(value)?.naive_utc()),
+        ColumnType::Timestamptz => SqlValue::Timestamptz(timestamp(value)?),
+        ColumnType::Date => SqlValue::Date(match value {
+            Value::Bytes(_) => match NaiveDate::from_str(string(value)?) {
+                Ok(date) => date,
+                Err(_) => timestamp(value)?.naive_utc().date(),
+            },
+            value => timestamp(value)?.naive_utc().date(),
+        }),
+        ColumnType::Uuid => SqlValue::Uuid(Uuid::parse_str(string(value)?).map_err(drop)?),
+    })
+}
+
+fn integer(value: &Value) -> Result<i64, ()> {
+    match value {
+        Value::Integer(integer) => Ok(*integer),
+        Value::Float(float) if float.fract() == 0.0 => Ok(float.into_inner() as i64),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .map_err(drop)?
+            .trim()
+            .parse()
+            .map_err(drop),
+        _ => Err(()),
+    }
+}
+
+fn float(value: &Value) -> Result<f64, ()> {
+    match value {
+        Value::Integer(integer) => Ok(*integer as f64),
+        Value::Float(float) => Ok(float.into_inner()),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .map_err(drop)?
+            .trim()
+            .parse()
+            .map_err(drop),
+        _ => Err(()),
+    }
+}
+
+fn string(value: &Value) -> Result<&str, ()> {
+    match value {
+        Value::Bytes(bytes) => std::str::from_utf8(bytes).map_err(drop),
+        _ => Err(()),
+    }
+}
+
+/// Timestamps may also be given as seconds since the Unix epoch, or as RFC 3339 strings.
+fn timestamp(value: &Value) -> Result<DateTime<Utc>, ()> {
+    match value {
+        Value::Timestamp(timestamp) => Ok(*timestamp),
+        Value::Integer(seconds) => Utc.timestamp_opt(*seconds, 0).single().ok_or(()),
+        Value::Bytes(_) => DateTime::parse_from_rfc3339(string(value)?)
+            .map(|timestamp| timestamp.with_timezone(&Utc))
+            .map_err(drop),
+        _ => Err(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, column_type: ColumnType) -> Column {
+        Column {
+            name: name.to_owned(),
+            field: name.to_owned(),
+            column_type,
+        }
+    }
+
+    fn columns() -> Vec<Column> {
+        vec![
+            column("message", ColumnType::Text),
+            column("count", ColumnType::Int4),
+            column("timestamp", ColumnType::Timestamptz),
+            column("day", ColumnType::Date),
+            column("attributes", ColumnType::Jsonb),
+        ]
+    }
+
+    #[test]
+    fn encodes_rows() {
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+        log.insert("count", "42");
+        log.insert("timestamp", 1_600_000_000);
+        log.insert("day", "2022-10-14");
+        log.insert("attributes.level", "info");
+
+        assert_eq!(
+            encode_row(&columns(), &log).unwrap(),
+            [
+                SqlValue::Text("hello".to_owned()),
+                SqlValue::Int4(42),
+                SqlValue::Timestamptz(Utc.timestamp_opt(1_600_000_000, 0).single().unwrap()),
+                SqlValue::Date(NaiveDate::from_ymd(2022, 10, 14)),
+                SqlValue::Json(r#"{"level":"info"}"#.to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn writes_missing_values_as_null() {
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+
+        assert_eq!(
+            encode_row(&columns(), &log).unwrap(),
+            [
+                SqlValue::Text("hello".to_owned()),
+                SqlValue::Null,
+                SqlValue::Null,
+                SqlValue::Null,
+                SqlValue::Null,
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_values() {
+        let mut log = LogEvent::default();
+        log.insert("count", 1_i64 << 40);
+
+        assert_eq!(
+            encode_row(&columns(), &log),
+            Err(ConversionError::InvalidValue {
+                column: "count".to_owned(),
+                column_type: ColumnType::Int4,
+                value: "1099511627776".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn writes_jsonb_with_its_version() {
+        let value = SqlValue::Json("{}".to_owned());
+        let mut out = BytesMut::new();
+        value.to_sql(&Type::JSONB, &mut out).unwrap();
+        assert_eq!(&out[..], b"\x01{}");
+
+        let mut out = BytesMut::new();
+        value.to_sql(&Type::JSON, &mut out).unwrap();
+        assert_eq!(&out[..], b"{}");
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
     amqp::AmqpConfig,
     codecs::{Decoder, DecodingConfig},
     config::{log_schema, Output, SourceConfig, SourceContext},
-    event::{BatchNotifier, BatchStatus},
+    event::{BatchNotifier, BatchStatus, Value},
     internal_events::{
         source::{AmqpAckError, AmqpBytesReceived, AmqpEventError, AmqpRejectError},
         StreamClosedError,
@@ -19,9 +19,15 @@ use chrono::{TimeZone, Utc};
 use codecs::decoding::{DeserializerConfig, FramingConfig};
 use futures::{FutureExt, StreamExt};
 use futures_util::Stream;
-use lapin::{acker::Acker, message::Delivery, Channel};
+use lapin::{
+    acker::Acker,
+    message::Delivery,
+    types::{AMQPValue, FieldTable},
+    Channel,
+};
+use ordered_float::NotNan;
 use snafu::Snafu;
-use std::{io::Cursor, pin::Pin};
+use std::{collections::BTreeMap, io::Cursor, pin::Pin};
 use tokio_util::codec::FramedRead;
 use vector_common::{finalizer::UnorderedFinalizer, internal_event::EventsReceived};
 use vector_config::configurable_component;
@@ -39,6 +45,8 @@ enum BuildError {
     },
     #[snafu(display("Could not subscribe to AMQP queue: {}", source))]
     AmqpSubscribeError { source: lapin::Error },
+    #[snafu(display("Could not set AMQP prefetch count: {}", source))]
+    AmqpQosError { source: lapin::Error },
 }
 
 /// Configuration for the `amqp` source.
@@ -72,6 +80,18 @@ pub struct AmqpSourceConfig {
     #[serde(default = "default_offset_key")]
     pub(crate) offset_key: String,
 
+    /// The `AMQP` headers key.
+    ///
+    /// The headers of the message are added as an object under this key.
+    #[serde(default = "default_headers_key")]
+    pub(crate) headers_key: String,
+
+    /// The maximum number of unacknowledged messages delivered to the consumer at once.
+    ///
+    /// With acknowledgements enabled, messages are only acknowledged once they are processed, so
+    /// that this bounds the number of messages in flight. Unlimited if not set.
+    pub(crate) prefetch_count: Option<u16>,
+
     /// The namespace to use. This overrides the global setting.
     #[serde(default)]
     pub log_namespace: Option<bool>,
@@ -111,6 +131,10 @@ fn default_offset_key() -> String {
     "offset".into()
 }
 
+fn default_headers_key() -> String {
+    "headers".into()
+}
+
 impl_generate_config_from_default!(AmqpSourceConfig);
 
 impl AmqpSourceConfig {
@@ -170,6 +194,13 @@ pub(crate) async fn amqp_source(
         .await
         .map_err(|source| BuildError::AmqpCreateError { source })?;
 
+    if let Some(prefetch_count) = config.prefetch_count {
+        channel
+            .basic_qos(prefetch_count, lapin::options::BasicQosOptions::default())
+            .await
+            .map_err(|source| BuildError::AmqpQosError { source })?;
+    }
+
     Ok(Box::pin(run_amqp_source(
         config,
         shutdown,
@@ -187,6 +218,54 @@ struct Keys<'a> {
     exchange: &'a str,
     offset_key: &'a str,
     delivery_tag: i64,
+    headers_key: &'a str,
+    headers: Option<Value>,
+}
+
+/// Converts the headers of a message, which are typed by the `AMQP` field table encoding.
+fn headers_to_value(headers: &FieldTable) -> Value {
+    Value::Object(
+        headers
+            .inner()
+            .iter()
+            .map(|(key, value)| (key.as_str().to_owned(), amqp_to_value(value)))
+            .collect::<BTreeMap<_, _>>(),
+    )
+}
+
+fn amqp_to_value(value: &AMQPValue) -> Value {
+    match value {
+        AMQPValue::Boolean(value) => Value::Boolean(*value),
+        AMQPValue::ShortShortInt(value) => Value::Integer(*value as i64),
+        AMQPValue::ShortShortUInt(value) => Value::Integer(*value as i64),
+        AMQPValue::ShortInt(value) => Value::Integer(*value as i64),
+        AMQPValue::ShortUInt(value) => Value::Integer(*value as i64),
+        AMQPValue::LongInt(value) => Value::Integer(*value as i64),
+        AMQPValue::LongUInt(value) => Value::Integer(*value as i64),
+        AMQPValue::LongLongInt(value) => Value::Integer(*value),
+        AMQPValue::Float(value) => float_to_value(*value as f64),
+        AMQPValue::Double(value) => float_to_value(*value),
+        AMQPValue::DecimalValue(decimal) => {
+            float_to_value(decimal.value as f64 / 10f64.powi(decimal.scale as i32))
+        }
+        AMQPValue::ShortString(value) => Value::from(value.as_str()),
+        AMQPValue::LongString(value) => Value::from(Bytes::copy_from_slice(value.as_bytes())),
+        AMQPValue::ByteArray(value) => Value::from(Bytes::copy_from_slice(value.as_slice())),
+        AMQPValue::FieldArray(values) => {
+            Value::Array(values.as_slice().iter().map(amqp_to_value).collect())
+        }
+        AMQPValue::FieldTable(table) => headers_to_value(table),
+        AMQPValue::Timestamp(secs) => Utc
+            .timestamp_opt(*secs as i64, 0)
+            .single()
+            .map(Value::Timestamp)
+            .unwrap_or(Value::Null),
+        AMQPValue::Void => Value::Null,
+    }
+}
+
+fn float_to_value(value: f64) -> Value {
+    NotNan::new(value).map(Value::Float).unwrap_or(Value::Null)
 }
 
 /// Populates the decoded event with extra metadata.
@@ -229,6 +308,16 @@ fn populate_event(
     );
 
     log_namespace.insert_source_metadata("amqp", log, keys.offset_key, "offset", keys.delivery_tag);
+
+    if let Some(headers) = &keys.headers {
+        log_namespace.insert_source_metadata(
+            "amqp",
+            log,
+            keys.headers_key,
+            "headers",
+            headers.clone(),
+        );
+    }
 }
 
 /// Receives an event from `AMQP` and pushes it along the pipeline.
@@ -258,6 +347,8 @@ async fn receive_event(
         routing: &routing,
         exchange: &exchange,
         delivery_tag: msg.delivery_tag as i64,
+        headers_key: config.headers_key.as_str(),
+        headers: msg.properties.headers().as_ref().map(headers_to_value),
     };
 
     let stream = stream! {
@@ -400,9 +491,13 @@ async fn handle_ack(status: BatchStatus, entry: FinalizerEntry) {
                 emit!(AmqpAckError { error });
             }
         }
+        // The message is delivered again, as processing its events may succeed later on.
         BatchStatus::Errored => {
-            let ack_options = lapin::options::BasicRejectOptions::default();
-            if let Err(error) = entry.acker.reject(ack_options).await {
+            let ack_options = lapin::options::BasicNackOptions {
+                requeue: true,
+                ..Default::default()
+            };
+            if let Err(error) = entry.acker.nack(ack_options).await {
                 emit!(AmqpRejectError { error });
             }
         }
@@ -424,6 +519,21 @@ pub mod test {
         crate::test_util::test_generate_config::<AmqpSourceConfig>();
     }
 
+    #[test]
+    fn converts_headers() {
+        let mut nested = FieldTable::default();
+        nested.insert("flag".into(), AMQPValue::Boolean(true));
+        let mut headers = FieldTable::default();
+        headers.insert("name".into(), AMQPValue::LongString("value".into()));
+        headers.insert("count".into(), AMQPValue::LongInt(3));
+        headers.insert("nested".into(), AMQPValue::FieldTable(nested));
+
+        let value = headers_to_value(&headers);
+        assert_eq!(value.get("name"), Some(&Value::from("value")));
+        assert_eq!(value.get("count"), Some(&Value::Integer(3)));
+        assert_eq!(value.get("nested.flag"), Some(&Value::Boolean(true)));
+    }
+
     pub fn make_config() -> AmqpSourceConfig {
         let mut config = AmqpSourceConfig {
             queue: "it".to_string(),
@@ -482,13 +592,15 @@ mod integration_test {
         let payload_len = payload.len();
         trace!("Sending message of length {} to {}.", payload_len, exchange,);
 
+        let mut headers = FieldTable::default();
+        headers.insert("header".into(), AMQPValue::LongString("value".into()));
         channel
             .basic_publish(
                 exchange,
                 routing_key,
                 BasicPublishOptions::default(),
                 payload.as_ref(),
-                BasicProperties::default(),
+                BasicProperties::default().with_headers(headers),
             )
             .await
             .unwrap()
@@ -573,5 +685,6 @@ mod integration_test {
         let log_ts = log[log_schema().timestamp_key()].as_timestamp().unwrap();
         assert!(log_ts.signed_duration_since(now) < chrono::Duration::seconds(1));
         assert_eq!(log["exchange"], exchange.into());
+        assert_eq!(log["headers.header"], "value".into());
     }
 }
@@ -0,0 +1,223 @@
+use std::time::Duration;
+
+use codecs::decoding::{DeserializerConfig, FramingConfig};
+use snafu::Snafu;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use super::{lease::Leases, source::KinesisSource};
+use crate::{
+    aws::{auth::AwsAuthentication, create_client, region::RegionOrEndpoint},
+    codecs::DecodingConfig,
+    common::{dynamodb::DynamoDbClientBuilder, kinesis::KinesisClientBuilder},
+    config::{AcknowledgementsConfig, Output, SourceConfig, SourceContext},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    tls::TlsConfig,
+};
+
+/// The maximum number of records `GetRecords` returns at once.
+const MAX_BATCH_SIZE: i32 = 10_000;
+
+#[derive(Debug, Snafu)]
+enum ConfigError {
+    #[snafu(display("`batch_size` must be between 1 and {}", MAX_BATCH_SIZE))]
+    InvalidBatchSize,
+    #[snafu(display("`lease_duration_secs` must be at least 3"))]
+    InvalidLeaseDuration,
+}
+
+/// Where to start reading shards without a checkpoint from.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InitialPosition {
+    /// Start with the records added after the shard is first read.
+    #[derivative(Default)]
+    Latest,
+
+    /// Start with the oldest records of the shard, which are still retained.
+    TrimHorizon,
+}
+
+/// Configuration for the `aws_kinesis_streams` source.
+#[configurable_component(source("aws_kinesis_streams"))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct AwsKinesisStreamsConfig {
+    #[serde(flatten)]
+    pub region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub auth: AwsAuthentication,
+
+    /// The name of the stream to read records from.
+    pub stream_name: String,
+
+    /// The name of the consuming application.
+    ///
+    /// Instances of Vector with the same application name share the shards of the stream, and the
+    /// checkpoints of the shards.
+    pub application_name: String,
+
+    /// The name of the DynamoDB table to store leases and checkpoints of the shards in.
+    ///
+    /// The table must have a partition key named `lease_key`, of the string type. It can be shared
+    /// by several applications and streams.
+    pub checkpoint_table: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub initial_position: InitialPosition,
+
+    /// The maximum number of records to read from a shard at once.
+    #[serde(default = "default_batch_size")]
+    #[derivative(Default(value = "default_batch_size()"))]
+    pub batch_size: i32,
+
+    /// How long to wait between polling a shard for records, in seconds.
+    ///
+    /// Shards can be read from five times per second, by all consumers of the stream together.
+    #[serde(default = "default_poll_secs")]
+    #[derivative(Default(value = "default_poll_secs()"))]
+    pub poll_secs: f64,
+
+    /// How often to list the shards of the stream, in seconds.
+    ///
+    /// This discovers the shards created by resharding, and the shards whose leases expired.
+    #[serde(default = "default_shard_sync_secs")]
+    #[derivative(Default(value = "default_shard_sync_secs()"))]
+    pub shard_sync_secs: u64,
+
+    /// How long a lease of a shard is valid without being renewed, in seconds.
+    ///
+    /// Other instances of the application take over the shards of an instance, once their leases
+    /// expire.
+    #[serde(default = "default_lease_duration_secs")]
+    #[derivative(Default(value = "default_lease_duration_secs()"))]
+    pub lease_duration_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: AcknowledgementsConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+const fn default_batch_size() -> i32 {
+    1_000
+}
+
+const fn default_poll_secs() -> f64 {
+    1.0
+}
+
+const fn default_shard_sync_secs() -> u64 {
+    60
+}
+
+const fn default_lease_duration_secs() -> u64 {
+    30
+}
+
+impl_generate_config_from_default!(AwsKinesisStreamsConfig);
+
+#[async_trait::async_trait]
+impl SourceConfig for AwsKinesisStreamsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<crate::sources::Source> {
+        if !(1..=MAX_BATCH_SIZE).contains(&self.batch_size) {
+            return Err(ConfigError::InvalidBatchSize.into());
+        }
+        if self.lease_duration_secs < 3 {
+            return Err(ConfigError::InvalidLeaseDuration.into());
+        }
+
+        let kinesis = self.build_kinesis_client(&cx).await?;
+        let dynamodb = create_client::<DynamoDbClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            &cx.proxy,
+            &self.tls,
+            false,
+        )
+        .await?;
+        let leases = Leases::new(
+            dynamodb,
+            self.checkpoint_table.clone(),
+            format!("{}:{}", self.application_name, self.stream_name),
+            Duration::from_secs(self.lease_duration_secs),
+        );
+
+        let decoder = DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .build();
+
+        Ok(Box::pin(
+            KinesisSource {
+                client: kinesis,
+                leases,
+                stream_name: self.stream_name.clone(),
+                application_name: self.application_name.clone(),
+                initial_position: self.initial_position,
+                batch_size: self.batch_size,
+                poll_interval: Duration::from_secs_f64(self.poll_secs),
+                shard_sync_interval: Duration::from_secs(self.shard_sync_secs),
+                decoder,
+                acknowledgements: cx.do_acknowledgements(&self.acknowledgements),
+            }
+            .run(cx.out, cx.shutdown),
+        ))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(self.decoding.output_type())]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl AwsKinesisStreamsConfig {
+    async fn build_kinesis_client(
+        &self,
+        cx: &SourceContext,
+    ) -> crate::Result<aws_sdk_kinesis::Client> {
+        create_client::<KinesisClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            &cx.proxy,
+            &self.tls,
+            false,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AwsKinesisStreamsConfig>();
+    }
+}
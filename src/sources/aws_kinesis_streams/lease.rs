@@ -0,0 +1,206 @@
+//! Leases and checkpoints of shards, stored in DynamoDB.
+//!
+//! Every shard has an item, keyed by the application, stream and shard, holding the checkpoint of
+//! the shard and the lease of the instance currently reading it. Leases are taken with conditional
+//! writes, so that a shard is only read by a single instance at a time, and expire unless they are
+//! renewed, so that the shards of a stopped instance are taken over by the others.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aws_sdk_dynamodb::{
+    model::{AttributeValue, ReturnValue},
+    types::SdkError,
+    Client as DynamoDbClient,
+};
+
+/// The checkpoint of shards, which were read to their end.
+pub(super) const SHARD_END: &str = "SHARD_END";
+
+const LEASE_KEY: &str = "lease_key";
+const CHECKPOINT: &str = "shard_checkpoint";
+const LEASE_OWNER: &str = "lease_owner";
+const LEASE_EXPIRY: &str = "lease_expiry";
+
+#[derive(Clone)]
+pub(super) struct Leases {
+    client: DynamoDbClient,
+    table: String,
+    prefix: String,
+    owner: String,
+    duration: Duration,
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+impl Leases {
+    pub(super) fn new(
+        client: DynamoDbClient,
+        table: String,
+        prefix: String,
+        duration: Duration,
+    ) -> Self {
+        // The owner identifies this instance, so that leases of earlier runs are taken over once
+        // they expire.
+        let owner = format!(
+            "{}-{}",
+            crate::get_hostname().unwrap_or_else(|_| "vector".to_owned()),
+            uuid::Uuid::new_v4()
+        );
+        Self {
+            client,
+            table,
+            prefix,
+            owner,
+            duration,
+        }
+    }
+
+    /// How long a lease is valid for.
+    pub(super) const fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    fn key(&self, shard_id: &str) -> AttributeValue {
+        AttributeValue::S(format!("{}:{}", self.prefix, shard_id))
+    }
+
+    fn expiry(&self) -> AttributeValue {
+        AttributeValue::N((now_millis() + self.duration.as_millis()).to_string())
+    }
+
+    /// Gets the checkpoint of a shard.
+    pub(super) async fn checkpoint_of(&self, shard_id: &str) -> crate::Result<Option<String>> {
+        let output = self
+            .client
+            .get_item()
+            .table_name(&self.table)
+            .key(LEASE_KEY, self.key(shard_id))
+            .consistent_read(true)
+            .send()
+            .await?;
+        Ok(output
+            .item()
+            .and_then(|item| item.get(CHECKPOINT))
+            .and_then(|checkpoint| checkpoint.as_s().ok())
+            .cloned())
+    }
+
+    /// Takes the lease of a shard, if it's free or expired, returning the checkpoint of the shard.
+    pub(super) async fn acquire(&self, shard_id: &str) -> crate::Result<Option<Option<String>>> {
+        let result = self
+            .client
+            .update_item()
+            .table_name(&self.table)
+            .key(LEASE_KEY, self.key(shard_id))
+            .update_expression(format!(
+                "SET {} = :owner, {} = :expiry",
+                LEASE_OWNER, LEASE_EXPIRY
+            ))
+            .condition_expression(format!(
+                "attribute_not_exists({owner}) OR {owner} = :owner OR {expiry} < :now",
+                owner = LEASE_OWNER,
+                expiry = LEASE_EXPIRY
+            ))
+            .expression_attribute_values(":owner", AttributeValue::S(self.owner.clone()))
+            .expression_attribute_values(":expiry", self.expiry())
+            .expression_attribute_values(":now", AttributeValue::N(now_millis().to_string()))
+            .return_values(ReturnValue::AllNew)
+            .send()
+            .await;
+
+        match result {
+            Ok(output) => Ok(Some(
+                output
+                    .attributes()
+                    .and_then(|item| item.get(CHECKPOINT))
+                    .and_then(|checkpoint| checkpoint.as_s().ok())
+                    .cloned(),
+            )),
+            Err(SdkError::ServiceError { err, .. })
+                if err.is_conditional_check_failed_exception() =>
+            {
+                Ok(None)
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Renews the lease of a shard, returning whether it's still held.
+    pub(super) async fn renew(&self, shard_id: &str) -> crate::Result<bool> {
+        self.update_owned(shard_id, format!("SET {} = :expiry", LEASE_EXPIRY), None)
+            .await
+    }
+
+    /// Stores the checkpoint of a shard, and renews its lease, returning whether it's still held.
+    pub(super) async fn checkpoint(&self, shard_id: &str, checkpoint: &str) -> crate::Result<bool> {
+        self.update_owned(
+            shard_id,
+            format!(
+                "SET {} = :checkpoint, {} = :expiry",
+                CHECKPOINT, LEASE_EXPIRY
+            ),
+            Some(checkpoint),
+        )
+        .await
+    }
+
+    /// Releases the lease of a shard, so that other instances can take it over right away.
+    pub(super) async fn release(&self, shard_id: &str) -> crate::Result<()> {
+        let result = self
+            .client
+            .update_item()
+            .table_name(&self.table)
+            .key(LEASE_KEY, self.key(shard_id))
+            .update_expression(format!("REMOVE {}, {}", LEASE_OWNER, LEASE_EXPIRY))
+            .condition_expression(format!("{} = :owner", LEASE_OWNER))
+            .expression_attribute_values(":owner", AttributeValue::S(self.owner.clone()))
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(SdkError::ServiceError { err, .. })
+                if err.is_conditional_check_failed_exception() =>
+            {
+                Ok(())
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    async fn update_owned(
+        &self,
+        shard_id: &str,
+        update_expression: String,
+        checkpoint: Option<&str>,
+    ) -> crate::Result<bool> {
+        let mut request = self
+            .client
+            .update_item()
+            .table_name(&self.table)
+            .key(LEASE_KEY, self.key(shard_id))
+            .update_expression(update_expression)
+            .condition_expression(format!("{} = :owner", LEASE_OWNER))
+            .expression_attribute_values(":owner", AttributeValue::S(self.owner.clone()))
+            .expression_attribute_values(":expiry", self.expiry());
+        if let Some(checkpoint) = checkpoint {
+            request = request
+                .expression_attribute_values(":checkpoint", AttributeValue::S(checkpoint.into()));
+        }
+
+        match request.send().await {
+            Ok(_) => Ok(true),
+            Err(SdkError::ServiceError { err, .. })
+                if err.is_conditional_check_failed_exception() =>
+            {
+                Ok(false)
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+}
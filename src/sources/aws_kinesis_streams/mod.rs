@@ -0,0 +1,5 @@
+mod config;
+mod lease;
+mod source;
+
+pub use config::AwsKinesisStreamsConfig;
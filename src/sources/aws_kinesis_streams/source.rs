@@ -0,0 +1,506 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use aws_sdk_kinesis::{
+    model::{Record, Shard, ShardIteratorType},
+    Client as KinesisClient,
+};
+use chrono::{TimeZone, Utc};
+use futures::{FutureExt, StreamExt};
+use tokio::{
+    sync::mpsc,
+    task::JoinHandle,
+    time::{sleep_until, Instant},
+};
+use vector_common::{
+    finalizer::OrderedFinalizer,
+    internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol, Registered},
+};
+
+use super::{
+    config::InitialPosition,
+    lease::{Leases, SHARD_END},
+};
+use crate::{
+    codecs::Decoder,
+    event::{BatchNotifier, BatchStatus, MaybeAsLogMut},
+    internal_events::{
+        AwsKinesisStreamsLeaseError, AwsKinesisStreamsReceiveError, StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    sources::util,
+    SourceSender,
+};
+
+/// How long to wait after an error, before reading a shard again.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+pub struct KinesisSource {
+    pub client: KinesisClient,
+    pub(super) leases: Leases,
+    pub stream_name: String,
+    pub application_name: String,
+    pub(super) initial_position: InitialPosition,
+    pub batch_size: i32,
+    pub poll_interval: Duration,
+    pub shard_sync_interval: Duration,
+    pub decoder: Decoder,
+    pub(super) acknowledgements: bool,
+}
+
+impl KinesisSource {
+    pub async fn run(self, out: SourceSender, mut shutdown: ShutdownSignal) -> Result<(), ()> {
+        let source = Arc::new(self);
+        let mut running = HashMap::new();
+        let (finished_sender, mut finished) = mpsc::unbounded_channel();
+        let mut sync = tokio::time::interval(source.shard_sync_interval);
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = sync.tick() => {}
+                // Shards which were read to their end have children to start reading right away.
+                Some(shard_id) = finished.recv() => {
+                    running.remove(&shard_id);
+                }
+            }
+
+            let context = ShardContext {
+                out: out.clone(),
+                shutdown: shutdown.clone(),
+                finished: finished_sender.clone(),
+            };
+            if let Err(error) = source.sync_shards(&mut running, &context).await {
+                emit!(AwsKinesisStreamsReceiveError {
+                    error,
+                    shard_id: None
+                });
+            }
+        }
+
+        // The shards release their leases on shutdown.
+        for (_, task) in running {
+            let _ = task.await;
+        }
+        Ok(())
+    }
+
+    async fn list_shards(&self) -> crate::Result<Vec<Shard>> {
+        let mut shards = Vec::new();
+        let mut next_token = None;
+        loop {
+            // The stream name can't be combined with a token.
+            let request = match next_token.take() {
+                None => self.client.list_shards().stream_name(&self.stream_name),
+                Some(token) => self.client.list_shards().next_token(token),
+            };
+            let output = request.send().await?;
+            shards.extend(output.shards().unwrap_or_default().iter().cloned());
+            match output.next_token() {
+                Some(token) => next_token = Some(token.to_owned()),
+                None => return Ok(shards),
+            }
+        }
+    }
+
+    /// Starts reading the shards, which aren't read by any instance yet.
+    async fn sync_shards(
+        self: &Arc<Self>,
+        running: &mut HashMap<String, JoinHandle<()>>,
+        context: &ShardContext,
+    ) -> crate::Result<()> {
+        running.retain(|_, task| !task.is_finished());
+
+        let shards = self.list_shards().await?;
+        let listed = shards
+            .iter()
+            .filter_map(|shard| shard.shard_id())
+            .collect::<HashSet<_>>();
+        for shard in &shards {
+            let shard_id = match shard.shard_id() {
+                Some(shard_id) if !running.contains_key(shard_id) => shard_id,
+                _ => continue,
+            };
+
+            // Children are only read once their parents were read to their end, so that the
+            // records of a partition key are read in order. Parents which aren't listed anymore
+            // are past the retention period of the stream.
+            let parents = [shard.parent_shard_id(), shard.adjacent_parent_shard_id()]
+                .into_iter()
+                .flatten()
+                .filter(|parent| listed.contains(parent))
+                .collect::<Vec<_>>();
+            let mut parents_read = true;
+            for parent in &parents {
+                if self.leases.checkpoint_of(parent).await?.as_deref() != Some(SHARD_END) {
+                    parents_read = false;
+                    break;
+                }
+            }
+            if !parents_read {
+                continue;
+            }
+
+            let checkpoint = match self.leases.acquire(shard_id).await? {
+                Some(checkpoint) => checkpoint,
+                // Leased by another instance.
+                None => continue,
+            };
+            let position = match checkpoint {
+                Some(checkpoint) if checkpoint == SHARD_END => {
+                    self.leases.release(shard_id).await?;
+                    continue;
+                }
+                Some(sequence_number) => Position::After(sequence_number),
+                // The records of children are read from the start, so that none are missed.
+                None if !parents.is_empty() => Position::TrimHorizon,
+                None => match self.initial_position {
+                    InitialPosition::Latest => Position::Latest,
+                    InitialPosition::TrimHorizon => Position::TrimHorizon,
+                },
+            };
+
+            info!(message = "Reading shard.", shard_id = %shard_id);
+            let consumer = ShardConsumer {
+                source: Arc::clone(self),
+                shard_id: shard_id.to_owned(),
+                checkpoint: position,
+                context: context.clone(),
+                bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+            };
+            running.insert(shard_id.to_owned(), tokio::spawn(consumer.run()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+struct ShardContext {
+    out: SourceSender,
+    shutdown: ShutdownSignal,
+    finished: mpsc::UnboundedSender<String>,
+}
+
+/// A position within a shard.
+#[derive(Clone, Debug)]
+enum Position {
+    After(String),
+    TrimHorizon,
+    Latest,
+}
+
+impl Position {
+    fn iterator_type(&self) -> (ShardIteratorType, Option<&str>) {
+        match self {
+            Self::After(sequence_number) => (
+                ShardIteratorType::AfterSequenceNumber,
+                Some(sequence_number.as_str()),
+            ),
+            Self::TrimHorizon => (ShardIteratorType::TrimHorizon, None),
+            Self::Latest => (ShardIteratorType::Latest, None),
+        }
+    }
+}
+
+/// What to checkpoint, once the events of a batch are processed.
+#[derive(Debug)]
+enum Checkpoint {
+    Sequence(String),
+    ShardEnd,
+}
+
+/// Why reading a shard stopped.
+enum Exit {
+    Shutdown,
+    /// The lease was taken over by another instance.
+    LeaseLost,
+    ShardEnd,
+    /// Reading the shard failed, and is retried from the last checkpoint.
+    Retry,
+}
+
+/// Records read from a shard.
+struct Fetched {
+    records: Vec<Record>,
+    /// Whether the shard was closed by resharding, and is read to its end.
+    ended: bool,
+}
+
+/// Polls a shard for records.
+struct Reader {
+    /// The iterator to continue with, which is requested again after errors, as it expires.
+    iterator: Option<String>,
+    next_poll: Instant,
+}
+
+struct ShardConsumer {
+    source: Arc<KinesisSource>,
+    shard_id: String,
+    checkpoint: Position,
+    context: ShardContext,
+    bytes_received: Registered<BytesReceived>,
+}
+
+impl ShardConsumer {
+    async fn run(mut self) {
+        loop {
+            match self.consume().await {
+                Exit::Shutdown => break,
+                Exit::LeaseLost => {
+                    info!(message = "Lost lease of shard.", shard_id = %self.shard_id);
+                    break;
+                }
+                Exit::ShardEnd => {
+                    info!(message = "Finished reading shard.", shard_id = %self.shard_id);
+                    break;
+                }
+                Exit::Retry => {
+                    tokio::select! {
+                        _ = &mut self.context.shutdown => break,
+                        _ = tokio::time::sleep(RETRY_DELAY) => {}
+                    }
+                }
+            }
+        }
+
+        if let Err(error) = self.source.leases.release(&self.shard_id).await {
+            emit!(AwsKinesisStreamsLeaseError {
+                error,
+                shard_id: &self.shard_id
+            });
+        }
+        let _ = self.context.finished.send(self.shard_id);
+    }
+
+    /// Reads the shard from the last checkpoint, until it ends or fails.
+    async fn consume(&mut self) -> Exit {
+        let mut shutdown = self.context.shutdown.clone();
+        let (finalizer, mut ack_stream) = OrderedFinalizer::<Checkpoint>::maybe_new(
+            self.source.acknowledgements,
+            shutdown.clone(),
+        );
+        let mut reader = Reader {
+            iterator: None,
+            next_poll: Instant::now(),
+        };
+        let mut position = self.checkpoint.clone();
+        let mut ended = false;
+        // Leases are renewed well before they expire, so that the renewal arrives in time.
+        let renewal_interval = self.source.leases.duration() / 3;
+        let mut next_renewal = Instant::now() + renewal_interval;
+
+        loop {
+            while let Some(Some((status, checkpoint))) = ack_stream.next().now_or_never() {
+                if let Some(exit) = self.handle_ack(status, checkpoint).await {
+                    return exit;
+                }
+            }
+            if Instant::now() >= next_renewal {
+                next_renewal = Instant::now() + renewal_interval;
+                match self.source.leases.renew(&self.shard_id).await {
+                    Ok(true) => {}
+                    Ok(false) => return Exit::LeaseLost,
+                    Err(error) => emit!(AwsKinesisStreamsLeaseError {
+                        error,
+                        shard_id: &self.shard_id
+                    }),
+                }
+            }
+
+            // With the shard read to its end, only the checkpoint of its end is left to store.
+            if ended {
+                tokio::select! {
+                    _ = &mut shutdown => return Exit::Shutdown,
+                    entry = ack_stream.next() => if let Some((status, checkpoint)) = entry {
+                        if let Some(exit) = self.handle_ack(status, checkpoint).await {
+                            return exit;
+                        }
+                    },
+                    _ = sleep_until(next_renewal) => {}
+                }
+                continue;
+            }
+
+            let fetched = tokio::select! {
+                _ = &mut shutdown => return Exit::Shutdown,
+                fetched = self.fetch(&mut reader, &position) => fetched,
+            };
+            let fetched = match fetched {
+                Ok(fetched) => fetched,
+                Err(error) => {
+                    emit!(AwsKinesisStreamsReceiveError {
+                        error,
+                        shard_id: Some(&self.shard_id)
+                    });
+                    continue;
+                }
+            };
+
+            if let Some(sequence_number) = fetched
+                .records
+                .last()
+                .and_then(|record| record.sequence_number())
+            {
+                position = Position::After(sequence_number.to_owned());
+            }
+            ended = fetched.ended;
+            let checkpoint = match self.send_records(fetched, finalizer.as_ref()).await {
+                Ok(checkpoint) => checkpoint,
+                Err(()) => return Exit::Shutdown,
+            };
+            // Without acknowledgements, the records are checkpointed once they are sent.
+            if let Some(checkpoint) = checkpoint {
+                if let Some(exit) = self.store(checkpoint).await {
+                    return exit;
+                }
+            }
+        }
+    }
+
+    async fn fetch(&self, reader: &mut Reader, position: &Position) -> crate::Result<Fetched> {
+        let client = &self.source.client;
+        sleep_until(reader.next_poll).await;
+        reader.next_poll = Instant::now() + self.source.poll_interval;
+
+        let current = match reader.iterator.take() {
+            Some(current) => current,
+            None => {
+                let (iterator_type, sequence_number) = position.iterator_type();
+                client
+                    .get_shard_iterator()
+                    .stream_name(&self.source.stream_name)
+                    .shard_id(&self.shard_id)
+                    .shard_iterator_type(iterator_type)
+                    .set_starting_sequence_number(sequence_number.map(Into::into))
+                    .send()
+                    .await?
+                    .shard_iterator()
+                    .ok_or("Shard iterator is missing")?
+                    .to_owned()
+            }
+        };
+        let output = client
+            .get_records()
+            .shard_iterator(current)
+            .limit(self.source.batch_size)
+            .send()
+            .await?;
+        reader.iterator = output.next_shard_iterator().map(Into::into);
+        Ok(Fetched {
+            records: output.records().unwrap_or_default().to_vec(),
+            ended: reader.iterator.is_none(),
+        })
+    }
+
+    /// Sends the events of the records, returning the checkpoint to store right away, if
+    /// acknowledgements are disabled.
+    async fn send_records(
+        &mut self,
+        fetched: Fetched,
+        finalizer: Option<&OrderedFinalizer<Checkpoint>>,
+    ) -> Result<Option<Checkpoint>, ()> {
+        let checkpoint = if fetched.ended {
+            Some(Checkpoint::ShardEnd)
+        } else {
+            fetched
+                .records
+                .last()
+                .and_then(|record| record.sequence_number())
+                .map(|sequence_number| Checkpoint::Sequence(sequence_number.to_owned()))
+        };
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(finalizer.is_some());
+        let mut events = Vec::with_capacity(fetched.records.len());
+        for record in &fetched.records {
+            let data = record.data().map(|data| data.as_ref()).unwrap_or_default();
+            self.bytes_received.emit(ByteSize(data.len()));
+            let timestamp = record
+                .approximate_arrival_timestamp()
+                .and_then(|timestamp| {
+                    Utc.timestamp_opt(timestamp.secs(), timestamp.subsec_nanos())
+                        .single()
+                });
+
+            let stream_name = &self.source.stream_name;
+            let shard_id = &self.shard_id;
+            let decoded = util::decode_message(
+                self.source.decoder.clone(),
+                "aws_kinesis_streams",
+                data,
+                timestamp,
+                &batch,
+            )
+            .map(|mut event| {
+                if let Some(log) = event.maybe_as_log_mut() {
+                    log.insert("stream", stream_name.clone());
+                    log.insert("shard_id", shard_id.clone());
+                    if let Some(partition_key) = record.partition_key() {
+                        log.insert("partition_key", partition_key.to_owned());
+                    }
+                    if let Some(sequence_number) = record.sequence_number() {
+                        log.insert("sequence_number", sequence_number.to_owned());
+                    }
+                }
+                event
+            });
+            events.extend(decoded);
+        }
+        drop(batch);
+
+        let count = events.len();
+        if count > 0 {
+            if let Err(error) = self.context.out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                return Err(());
+            }
+        }
+
+        match (checkpoint, receiver, finalizer) {
+            (Some(checkpoint), Some(receiver), Some(finalizer)) => {
+                finalizer.add(checkpoint, receiver);
+                Ok(None)
+            }
+            (checkpoint, _, _) => Ok(checkpoint),
+        }
+    }
+
+    async fn handle_ack(&mut self, status: BatchStatus, checkpoint: Checkpoint) -> Option<Exit> {
+        match status {
+            // Rejected events would be rejected again, so that they are checkpointed as well.
+            BatchStatus::Delivered | BatchStatus::Rejected => self.store(checkpoint).await,
+            // Later records aren't checkpointed either, but read again along with the failed ones.
+            BatchStatus::Errored => Some(Exit::Retry),
+        }
+    }
+
+    async fn store(&mut self, checkpoint: Checkpoint) -> Option<Exit> {
+        let (value, position) = match &checkpoint {
+            Checkpoint::Sequence(sequence_number) => (
+                sequence_number.as_str(),
+                Position::After(sequence_number.clone()),
+            ),
+            Checkpoint::ShardEnd => (SHARD_END, self.checkpoint.clone()),
+        };
+
+        match self.source.leases.checkpoint(&self.shard_id, value).await {
+            Ok(true) => {
+                self.checkpoint = position;
+                match checkpoint {
+                    Checkpoint::ShardEnd => Some(Exit::ShardEnd),
+                    Checkpoint::Sequence(_) => None,
+                }
+            }
+            Ok(false) => Some(Exit::LeaseLost),
+            // The next checkpoint covers these records as well.
+            Err(error) => {
+                emit!(AwsKinesisStreamsLeaseError {
+                    error,
+                    shard_id: &self.shard_id
+                });
+                None
+            }
+        }
+    }
+}
@@ -23,6 +23,7 @@ use crate::{
     serde::bool_or_struct,
 };
 
+mod poll;
 pub mod sqs;
 
 /// Compression scheme for objects retrieved from S3.
@@ -58,6 +59,12 @@ enum Strategy {
     /// [aws_sqs]: https://aws.amazon.com/sqs/
     #[derivative(Default)]
     Sqs,
+
+    /// Consumes objects by listing the objects of a bucket on an interval.
+    ///
+    /// This is meant for buckets whose notifications can't be configured, such as buckets owned
+    /// by other accounts to which logs are delivered.
+    Poll,
 }
 
 /// Configuration for the `aws_s3` source.
@@ -84,6 +91,11 @@ pub struct AwsS3Config {
     /// Only relevant when `strategy = "sqs"`.
     sqs: Option<sqs::Config>,
 
+    /// Configuration options for polling.
+    ///
+    /// Only relevant when `strategy = "poll"`.
+    poll: Option<poll::Config>,
+
     /// The ARN of an [IAM role][iam_role] to assume at startup.
     ///
     /// [iam_role]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles.html
@@ -124,6 +136,11 @@ impl SourceConfig for AwsS3Config {
                     .await?
                     .run(cx, self.acknowledgements),
             )),
+            Strategy::Poll => Ok(Box::pin(
+                self.create_poll_ingestor(multiline_config, &cx)
+                    .await?
+                    .run(cx, self.acknowledgements),
+            )),
         }
     }
 
@@ -177,10 +194,12 @@ impl AwsS3Config {
                 let ingestor = sqs::Ingestor::new(
                     region,
                     sqs_client,
-                    s3_client,
+                    sqs::ObjectReader {
+                        s3_client,
+                        compression: self.compression,
+                        multiline,
+                    },
                     sqs.clone(),
-                    self.compression,
-                    multiline,
                 )
                 .await?;
 
@@ -189,6 +208,57 @@ impl AwsS3Config {
             None => Err(CreateSqsIngestorError::ConfigMissing {}.into()),
         }
     }
+
+    async fn create_poll_ingestor(
+        &self,
+        multiline: Option<line_agg::Config>,
+        cx: &SourceContext,
+    ) -> crate::Result<poll::Ingestor> {
+        let config = self
+            .poll
+            .as_ref()
+            .ok_or(CreatePollIngestorError::ConfigMissing)?;
+
+        let region = self
+            .region
+            .region()
+            .ok_or(CreatePollIngestorError::RegionMissing)?;
+
+        let endpoint = self
+            .region
+            .endpoint()
+            .map_err(|_| CreatePollIngestorError::InvalidEndpoint)?;
+
+        let s3_client = create_client::<S3ClientBuilder>(
+            &self.auth,
+            Some(region.clone()),
+            endpoint,
+            &cx.proxy,
+            &self.tls_options,
+            false,
+        )
+        .await?;
+
+        let mut checkpoint_path = cx
+            .globals
+            // source are only global, name can be used for subdir
+            .resolve_and_make_data_subdir(config.data_dir.as_ref(), cx.key.id())?;
+        checkpoint_path.push(poll::CHECKPOINT_FILENAME);
+
+        let ingestor = poll::Ingestor::new(
+            region,
+            sqs::ObjectReader {
+                s3_client,
+                compression: self.compression,
+                multiline,
+            },
+            config.clone(),
+            checkpoint_path,
+        )
+        .await?;
+
+        Ok(ingestor)
+    }
 }
 
 #[derive(Debug, Snafu)]
@@ -207,6 +277,16 @@ enum CreateSqsIngestorError {
     InvalidEndpoint,
 }
 
+#[derive(Debug, Snafu)]
+enum CreatePollIngestorError {
+    #[snafu(display("Configuration for `poll` required when strategy=poll"))]
+    ConfigMissing,
+    #[snafu(display("Region is required"))]
+    RegionMissing,
+    #[snafu(display("Endpoint is invalid"))]
+    InvalidEndpoint,
+}
+
 /// None if body is empty
 async fn s3_object_decoder(
     compression: Compression,
@@ -518,9 +598,10 @@ mod integration_tests {
             None,
             None,
             Some(MultilineConfig {
-                start_pattern: "abc".to_owned(),
-                mode: line_agg::Mode::HaltWith,
-                condition_pattern: "geh".to_owned(),
+                preset: None,
+                start_pattern: Some("abc".to_owned()),
+                mode: Some(line_agg::Mode::HaltWith),
+                condition_pattern: Some("geh".to_owned()),
                 timeout_ms: 1000,
             }),
             logs.join("\n").into_bytes(),
@@ -0,0 +1,415 @@
+use std::{
+    collections::BTreeSet,
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use aws_sdk_s3::error::ListObjectsV2Error;
+use aws_smithy_client::SdkError;
+use aws_types::region::Region;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio::{pin, select, time};
+use vector_common::internal_event::{BytesReceived, Protocol, Registered};
+use vector_config::configurable_component;
+
+use super::sqs::{ObjectReader, ProcessingError};
+use crate::{
+    config::{AcknowledgementsConfig, SourceContext},
+    internal_events::{AwsS3CheckpointError, AwsS3ListObjectsError, AwsS3ObjectProcessingError},
+    SourceSender,
+};
+
+pub(super) const CHECKPOINT_FILENAME: &str = "checkpoint.json";
+
+/// Polling configuration options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Config {
+    /// The name of the bucket to list objects of.
+    pub(super) bucket: String,
+
+    /// The prefix of the keys of the objects to read.
+    #[serde(default)]
+    pub(super) prefix: String,
+
+    /// The key to start listing objects after, until objects are read.
+    ///
+    /// Objects with this key, or keys before it, aren't read.
+    pub(super) start_after: Option<String>,
+
+    /// How long to wait between listings of the bucket, in seconds.
+    #[serde(default = "default_poll_secs")]
+    #[derivative(Default(value = "default_poll_secs()"))]
+    pub(super) poll_secs: u64,
+
+    /// How long objects may be added after the objects with keys after theirs, in seconds.
+    ///
+    /// The bucket is listed after a watermark, which only moves past the objects read once they
+    /// were modified this long ago, so that objects added late are read too. The objects read
+    /// after the watermark are kept in a ledger, so that they aren't read again.
+    #[serde(default = "default_watermark_delay_secs")]
+    #[derivative(Default(value = "default_watermark_delay_secs()"))]
+    pub(super) watermark_delay_secs: u64,
+
+    /// The directory used to persist the watermark and the ledger.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    pub(super) data_dir: Option<PathBuf>,
+}
+
+const fn default_poll_secs() -> u64 {
+    60
+}
+
+const fn default_watermark_delay_secs() -> u64 {
+    3600
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum IngestorNewError {
+    #[snafu(display("Could not read checkpoint from {:?}: {}", path, source))]
+    ReadCheckpoint { path: PathBuf, source: io::Error },
+}
+
+/// The objects read so far.
+#[derive(Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+struct Checkpoint {
+    /// The key after which the bucket is listed.
+    watermark: Option<String>,
+    /// The keys of the objects read after the watermark.
+    ledger: BTreeSet<String>,
+}
+
+#[derive(Debug)]
+struct ListedObject {
+    key: String,
+    last_modified: Option<DateTime<Utc>>,
+}
+
+impl Checkpoint {
+    async fn load(path: &Path) -> io::Result<Self> {
+        match tokio::fs::read(path).await {
+            Ok(data) => serde_json::from_slice(&data)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Writes to a temporary file first, so that the checkpoint is replaced as a whole.
+    async fn save(&self, path: &Path) -> io::Result<()> {
+        let temporary = path.with_extension("tmp");
+        tokio::fs::write(&temporary, serde_json::to_vec(self)?).await?;
+        tokio::fs::rename(&temporary, path).await
+    }
+
+    /// Moves the watermark past the objects listed in order which were read, and modified before
+    /// `settled`. Returns whether the checkpoint changed.
+    fn advance(&mut self, listed: &[ListedObject], settled: DateTime<Utc>) -> bool {
+        let mut changed = false;
+        for object in listed {
+            let is_settled = object
+                .last_modified
+                .map_or(true, |last_modified| last_modified <= settled);
+            if !is_settled || !self.ledger.remove(&object.key) {
+                break;
+            }
+            self.watermark = Some(object.key.clone());
+            changed = true;
+        }
+        changed
+    }
+
+    /// Forgets the objects which were deleted from the bucket. Returns whether any was forgotten.
+    fn retain(&mut self, listed: &[ListedObject]) -> bool {
+        let count = self.ledger.len();
+        let keys = listed
+            .iter()
+            .map(|object| object.key.as_str())
+            .collect::<BTreeSet<_>>();
+        self.ledger.retain(|key| keys.contains(key.as_str()));
+        self.ledger.len() != count
+    }
+}
+
+pub(super) struct Ingestor {
+    region: Region,
+    object_reader: ObjectReader,
+    bucket: String,
+    prefix: String,
+    start_after: Option<String>,
+    poll: Duration,
+    watermark_delay: chrono::Duration,
+    checkpoint: Checkpoint,
+    checkpoint_path: PathBuf,
+}
+
+impl Ingestor {
+    pub(super) async fn new(
+        region: Region,
+        object_reader: ObjectReader,
+        config: Config,
+        checkpoint_path: PathBuf,
+    ) -> Result<Ingestor, IngestorNewError> {
+        let checkpoint = Checkpoint::load(&checkpoint_path)
+            .await
+            .context(ReadCheckpointSnafu {
+                path: &checkpoint_path,
+            })?;
+
+        Ok(Ingestor {
+            region,
+            object_reader,
+            bucket: config.bucket,
+            prefix: config.prefix,
+            start_after: config.start_after,
+            poll: Duration::from_secs(config.poll_secs),
+            watermark_delay: chrono::Duration::seconds(config.watermark_delay_secs as i64),
+            checkpoint,
+            checkpoint_path,
+        })
+    }
+
+    pub(super) async fn run(
+        mut self,
+        cx: SourceContext,
+        acknowledgements: AcknowledgementsConfig,
+    ) -> Result<(), ()> {
+        let acknowledgements = cx.do_acknowledgements(&acknowledgements);
+        let mut out = cx.out;
+        let bytes_received = register!(BytesReceived::from(Protocol::HTTP));
+        let shutdown = cx.shutdown.fuse();
+        pin!(shutdown);
+
+        let mut interval = time::interval(self.poll);
+        loop {
+            select! {
+                _ = &mut shutdown => break,
+                result = async {
+                    interval.tick().await;
+                    self.run_once(&mut out, &bytes_received, acknowledgements).await
+                } => result?,
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn run_once(
+        &mut self,
+        out: &mut SourceSender,
+        bytes_received: &Registered<BytesReceived>,
+        acknowledgements: bool,
+    ) -> Result<(), ()> {
+        let listed = match self.list_objects().await {
+            Ok(listed) => listed,
+            Err(error) => {
+                emit!(AwsS3ListObjectsError {
+                    bucket: &self.bucket,
+                    error,
+                });
+                return Ok(());
+            }
+        };
+
+        if self.checkpoint.retain(&listed) {
+            self.save().await;
+        }
+
+        for object in &listed {
+            if self.checkpoint.ledger.contains(&object.key) {
+                continue;
+            }
+            match self
+                .object_reader
+                .read(
+                    &self.bucket,
+                    &object.key,
+                    self.region.as_ref(),
+                    out,
+                    bytes_received,
+                    acknowledgements,
+                )
+                .await
+            {
+                Ok(()) => {
+                    self.checkpoint.ledger.insert(object.key.clone());
+                    self.save().await;
+                }
+                // The events couldn't be sent, since Vector is shutting down.
+                Err(ProcessingError::PipelineSend { .. }) => return Err(()),
+                Err(error) => emit!(AwsS3ObjectProcessingError {
+                    bucket: &self.bucket,
+                    key: &object.key,
+                    error: &error,
+                }),
+            }
+        }
+
+        if self
+            .checkpoint
+            .advance(&listed, Utc::now() - self.watermark_delay)
+        {
+            self.save().await;
+        }
+
+        Ok(())
+    }
+
+    /// Lists the objects after the watermark, in the order of their keys.
+    async fn list_objects(&self) -> Result<Vec<ListedObject>, SdkError<ListObjectsV2Error>> {
+        let start_after = self
+            .checkpoint
+            .watermark
+            .clone()
+            .or_else(|| self.start_after.clone());
+        let mut listed = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let output = self
+                .object_reader
+                .s3_client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix(&self.prefix)
+                .set_start_after(start_after.clone())
+                .set_continuation_token(continuation_token)
+                .send()
+                .await?;
+
+            listed.extend(
+                output
+                    .contents
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|object| {
+                        Some(ListedObject {
+                            key: object.key?,
+                            last_modified: object
+                                .last_modified
+                                .map(|ts| Utc.timestamp(ts.secs(), ts.subsec_nanos())),
+                        })
+                    }),
+            );
+
+            continuation_token = output.next_continuation_token;
+            if !output.is_truncated || continuation_token.is_none() {
+                break;
+            }
+        }
+        Ok(listed)
+    }
+
+    async fn save(&self) {
+        if let Err(error) = self.checkpoint.save(&self.checkpoint_path).await {
+            emit!(AwsS3CheckpointError {
+                error,
+                path: &self.checkpoint_path,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(key: &str, minutes_ago: i64) -> ListedObject {
+        ListedObject {
+            key: key.to_owned(),
+            last_modified: Some(Utc::now() - chrono::Duration::minutes(minutes_ago)),
+        }
+    }
+
+    fn checkpoint(ledger: &[&str]) -> Checkpoint {
+        Checkpoint {
+            watermark: None,
+            ledger: checkpoint_keys(ledger),
+        }
+    }
+
+    fn checkpoint_keys(keys: &[&str]) -> BTreeSet<String> {
+        keys.iter().map(|key| key.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_poll_strategy() {
+        let config: super::super::AwsS3Config = toml::from_str(
+            r#"
+            region = "us-east-1"
+            strategy = "poll"
+            poll.bucket = "logs"
+            poll.prefix = "AWSLogs/"
+            "#,
+        )
+        .unwrap();
+        let config = config.poll.unwrap();
+
+        assert_eq!(config.bucket, "logs");
+        assert_eq!(config.prefix, "AWSLogs/");
+        assert_eq!(config.start_after, None);
+        assert_eq!(config.poll_secs, 60);
+        assert_eq!(config.watermark_delay_secs, 3600);
+    }
+
+    #[test]
+    fn advances_past_settled_objects_read_in_order() {
+        let settled = Utc::now() - chrono::Duration::minutes(60);
+        let listed = [
+            object("a", 120),
+            object("b", 90),
+            object("c", 120),
+            object("d", 120),
+        ];
+
+        // `c` wasn't read yet, so the watermark stops before it.
+        let mut checkpoint = checkpoint(&["a", "b", "d"]);
+        assert!(checkpoint.advance(&listed, settled));
+        assert_eq!(checkpoint.watermark.as_deref(), Some("b"));
+        assert_eq!(checkpoint.ledger, checkpoint_keys(&["d"]));
+
+        assert!(!checkpoint.advance(&listed[2..], settled));
+    }
+
+    #[test]
+    fn stays_before_recent_objects() {
+        let settled = Utc::now() - chrono::Duration::minutes(60);
+        let listed = [object("a", 120), object("b", 10), object("c", 120)];
+
+        let mut checkpoint = checkpoint(&["a", "b", "c"]);
+        assert!(checkpoint.advance(&listed, settled));
+        assert_eq!(checkpoint.watermark.as_deref(), Some("a"));
+        assert_eq!(checkpoint.ledger, checkpoint_keys(&["b", "c"]));
+    }
+
+    #[test]
+    fn forgets_deleted_objects() {
+        let mut checkpoint = checkpoint(&["a", "b"]);
+        assert!(checkpoint.retain(&[object("b", 0)]));
+        assert_eq!(checkpoint.ledger, checkpoint_keys(&["b"]));
+        assert!(!checkpoint.retain(&[object("b", 0)]));
+    }
+
+    #[tokio::test]
+    async fn saves_and_loads_checkpoints() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join(CHECKPOINT_FILENAME);
+        assert_eq!(
+            Checkpoint::load(&path).await.unwrap(),
+            Checkpoint::default()
+        );
+
+        let checkpoint = Checkpoint {
+            watermark: Some("AWSLogs/2022/10/20/a.log.gz".to_owned()),
+            ledger: checkpoint_keys(&["AWSLogs/2022/10/20/c.log.gz"]),
+        };
+        checkpoint.save(&path).await.unwrap();
+        assert_eq!(Checkpoint::load(&path).await.unwrap(), checkpoint);
+    }
+}
@@ -50,6 +50,23 @@ pub struct AwsSqsConfig {
     #[derivative(Default(value = "default_visibility_timeout_secs()"))]
     pub(super) visibility_timeout_secs: u32,
 
+    /// Whether to keep extending the visibility timeout of messages while their events are in flight.
+    ///
+    /// When enabled, the visibility timeout of the messages is extended by `visibility_timeout_secs` every half of it,
+    /// until they are deleted, so that they are not made available again while their events are slow to be delivered. When
+    /// the delivery fails, the timeout stops being extended and the messages are received again.
+    ///
+    /// Extending the visibility timeout requires the `sqs:ChangeMessageVisibility` permission on the queue.
+    #[serde(default)]
+    pub(super) extend_visibility_timeout: bool,
+
+    /// The maximum time, in seconds, messages are kept unavailable for by extending their visibility timeout.
+    ///
+    /// SQS does not allow keeping messages unavailable for more than 12 hours after they are received.
+    #[serde(default = "default_max_visibility_extension_secs")]
+    #[derivative(Default(value = "default_max_visibility_extension_secs()"))]
+    pub(super) max_visibility_extension_secs: u32,
+
     /// Whether to delete the message once Vector processes it.
     ///
     /// It can be useful to set this to `false` to debug or during initial Vector setup.
@@ -108,6 +125,8 @@ impl SourceConfig for AwsSqsConfig {
                 concurrency: self.client_concurrency,
                 visibility_timeout_secs: self.visibility_timeout_secs,
                 delete_message: self.delete_message,
+                extend_visibility_timeout: self.extend_visibility_timeout,
+                max_visibility_extension_secs: self.max_visibility_extension_secs,
                 acknowledgements,
             }
             .run(cx.out, cx.shutdown),
@@ -149,6 +168,10 @@ const fn default_visibility_timeout_secs() -> u32 {
     300
 }
 
+const fn default_max_visibility_extension_secs() -> u32 {
+    12 * 60 * 60
+}
+
 const fn default_true() -> bool {
     true
 }
@@ -1,12 +1,19 @@
-use std::{collections::HashMap, panic, str::FromStr, sync::Arc};
+use std::{collections::HashMap, panic, str::FromStr, sync::Arc, time::Duration};
 
 use aws_sdk_sqs::{
-    model::{DeleteMessageBatchRequestEntry, MessageSystemAttributeName, QueueAttributeName},
+    model::{
+        ChangeMessageVisibilityBatchRequestEntry, DeleteMessageBatchRequestEntry,
+        MessageSystemAttributeName, QueueAttributeName,
+    },
     Client as SqsClient,
 };
 use chrono::{DateTime, TimeZone, Utc};
 use futures::{FutureExt, StreamExt};
-use tokio::{pin, select};
+use tokio::{
+    pin, select,
+    task::JoinHandle,
+    time::{sleep, Instant},
+};
 use tracing_futures::Instrument;
 use vector_common::finalizer::UnorderedFinalizer;
 
@@ -14,7 +21,8 @@ use crate::{
     codecs::Decoder,
     event::{BatchNotifier, BatchStatus},
     internal_events::{
-        EndpointBytesReceived, SqsMessageDeleteError, SqsMessageReceiveError, StreamClosedError,
+        EndpointBytesReceived, SqsMessageDeleteError, SqsMessageReceiveError,
+        SqsMessageVisibilityError, StreamClosedError,
     },
     shutdown::ShutdownSignal,
     sources::util,
@@ -24,7 +32,24 @@ use crate::{
 // This is the maximum SQS supports in a single batch request
 const MAX_BATCH_SIZE: i32 = 10;
 
-type Finalizer = UnorderedFinalizer<Vec<String>>;
+type Finalizer = UnorderedFinalizer<InFlight>;
+
+/// Messages waiting for their events to be acknowledged.
+#[derive(Debug)]
+struct InFlight {
+    receipts: Vec<String>,
+    _extension: Option<VisibilityExtension>,
+}
+
+/// Keeps extending the visibility timeout of messages, until dropped.
+#[derive(Debug)]
+struct VisibilityExtension(JoinHandle<()>);
+
+impl Drop for VisibilityExtension {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
 
 #[derive(Clone)]
 pub struct SqsSource {
@@ -34,6 +59,8 @@ pub struct SqsSource {
     pub poll_secs: u32,
     pub visibility_timeout_secs: u32,
     pub delete_message: bool,
+    pub extend_visibility_timeout: bool,
+    pub max_visibility_extension_secs: u32,
     pub concurrency: u32,
     pub(super) acknowledgements: bool,
 }
@@ -47,9 +74,12 @@ impl SqsSource {
             let queue_url = self.queue_url.clone();
             tokio::spawn(
                 async move {
-                    while let Some((status, receipts)) = ack_stream.next().await {
+                    // The visibility timeout stops being extended once the messages are done with,
+                    // so that the ones which failed to be delivered are received again.
+                    while let Some((status, in_flight)) = ack_stream.next().await {
                         if status == BatchStatus::Delivered {
-                            delete_messages(client.clone(), receipts, queue_url.clone()).await;
+                            delete_messages(client.clone(), in_flight.receipts, queue_url.clone())
+                                .await;
                         }
                     }
                 }
@@ -149,6 +179,10 @@ impl SqsSource {
             }
             drop(batch); // Drop last reference to batch acknowledgement finalizer
             let count = events.len();
+            let extension = self
+                .delete_message
+                .then(|| self.extend_visibility(receipts_to_ack.clone()))
+                .flatten();
 
             match out.send_batch(events).await {
                 Ok(()) => {
@@ -156,7 +190,13 @@ impl SqsSource {
                         match batch_receiver {
                             Some(receiver) => finalizer
                                 .expect("Finalizer must exist for the batch receiver to be created")
-                                .add(receipts_to_ack, receiver),
+                                .add(
+                                    InFlight {
+                                        receipts: receipts_to_ack,
+                                        _extension: extension,
+                                    },
+                                    receiver,
+                                ),
                             None => {
                                 delete_messages(
                                     self.client.clone(),
@@ -172,6 +212,42 @@ impl SqsSource {
             }
         }
     }
+
+    /// Keeps the messages invisible to other consumers while their events are in flight, by
+    /// extending their visibility timeout every half of it, up to `max_visibility_extension_secs`
+    /// since they were received.
+    fn extend_visibility(&self, receipts: Vec<String>) -> Option<VisibilityExtension> {
+        if !self.extend_visibility_timeout || receipts.is_empty() {
+            return None;
+        }
+
+        let client = self.client.clone();
+        let queue_url = self.queue_url.clone();
+        let timeout = u64::from(self.visibility_timeout_secs);
+        let deadline =
+            Instant::now() + Duration::from_secs(self.max_visibility_extension_secs.into());
+        let period = Duration::from_secs((timeout / 2).max(1));
+
+        Some(VisibilityExtension(tokio::spawn(
+            async move {
+                loop {
+                    sleep(period).await;
+                    let remaining = deadline.saturating_duration_since(Instant::now()).as_secs();
+                    if remaining == 0 {
+                        break;
+                    }
+                    change_visibility(
+                        &client,
+                        &queue_url,
+                        &receipts,
+                        remaining.min(timeout) as i32,
+                    )
+                    .await;
+                }
+            }
+            .in_current_span(),
+        )))
+    }
 }
 
 fn get_timestamp(
@@ -201,6 +277,30 @@ async fn delete_messages(client: SqsClient, receipts: Vec<String>, queue_url: St
     }
 }
 
+async fn change_visibility(
+    client: &SqsClient,
+    queue_url: &str,
+    receipts: &[String],
+    visibility_timeout: i32,
+) {
+    let mut batch = client
+        .change_message_visibility_batch()
+        .queue_url(queue_url);
+
+    for (id, receipt) in receipts.iter().enumerate() {
+        batch = batch.entries(
+            ChangeMessageVisibilityBatchRequestEntry::builder()
+                .id(id.to_string())
+                .receipt_handle(receipt)
+                .visibility_timeout(visibility_timeout)
+                .build(),
+        );
+    }
+    if let Err(err) = batch.send().await {
+        emit!(SqsMessageVisibilityError { error: &err });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::SecondsFormat;
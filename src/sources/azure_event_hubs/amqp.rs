@@ -0,0 +1,443 @@
+//! Reading partitions of an event hub, over AMQP 1.0.
+
+use std::collections::HashMap;
+
+use azure_core::auth::TokenCredential;
+use azure_identity::DefaultAzureCredential;
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use fe2o3_amqp::{
+    connection::ConnectionHandle,
+    sasl_profile::SaslProfile,
+    session::SessionHandle,
+    types::{
+        messaging::{ApplicationProperties, Body, Message, MessageAnnotations, Properties, Source},
+        primitives::{SimpleValue, Symbol, Timestamp, Value},
+    },
+    Connection, Delivery, Receiver, Sender, Session,
+};
+use fe2o3_amqp_types::{
+    definitions::{ReceiverSettleMode, SenderSettleMode},
+    messaging::annotations::OwnedKey,
+};
+use serde_amqp::{described::Described, descriptor::Descriptor};
+use snafu::Snafu;
+
+use super::config::StartPosition;
+
+/// The scope of Azure Active Directory tokens, for Event Hubs.
+const AZURE_AD_RESOURCE: &str = "https://eventhubs.azure.net/";
+const SELECTOR_FILTER: &str = "apache.org:selector-filter:string";
+const MANAGEMENT_ADDRESS: &str = "$management";
+/// The address of the claims-based security node, which tokens are put to.
+const CBS_ADDRESS: &str = "$cbs";
+
+const OFFSET_ANNOTATION: &str = "x-opt-offset";
+const SEQUENCE_NUMBER_ANNOTATION: &str = "x-opt-sequence-number";
+const ENQUEUED_TIME_ANNOTATION: &str = "x-opt-enqueued-time";
+const PARTITION_KEY_ANNOTATION: &str = "x-opt-partition-key";
+
+#[derive(Debug, Snafu)]
+pub(super) enum ConnectionStringError {
+    #[snafu(display("Connection string is missing `{}`", key))]
+    MissingKey { key: &'static str },
+    #[snafu(display("Invalid endpoint in connection string: {:?}", endpoint))]
+    InvalidEndpoint { endpoint: String },
+}
+
+/// The parts of an Event Hubs connection string, with a shared access key.
+#[derive(Debug)]
+pub(super) struct ConnectionString {
+    pub namespace: String,
+    pub key_name: String,
+    pub key: String,
+    pub entity_path: Option<String>,
+}
+
+/// Parses a connection string like
+/// `Endpoint=sb://<namespace>/;SharedAccessKeyName=<name>;SharedAccessKey=<key>;EntityPath=<hub>`.
+pub(super) fn parse_connection_string(
+    connection_string: &str,
+) -> Result<ConnectionString, ConnectionStringError> {
+    let mut parts = connection_string
+        .split(';')
+        .filter_map(|part| part.split_once('='))
+        .map(|(key, value)| (key.trim().to_ascii_lowercase(), value.trim().to_owned()))
+        .collect::<HashMap<_, _>>();
+    let mut take = |key: &'static str| {
+        parts
+            .remove(&key.to_ascii_lowercase())
+            .filter(|value| !value.is_empty())
+            .ok_or(ConnectionStringError::MissingKey { key })
+    };
+
+    let endpoint = take("Endpoint")?;
+    let namespace = endpoint
+        .strip_prefix("sb://")
+        .map(|namespace| namespace.trim_end_matches('/'))
+        .filter(|namespace| !namespace.is_empty() && !namespace.contains('/'))
+        .ok_or_else(|| ConnectionStringError::InvalidEndpoint {
+            endpoint: endpoint.clone(),
+        })?
+        .to_owned();
+    Ok(ConnectionString {
+        namespace,
+        key_name: take("SharedAccessKeyName")?,
+        key: take("SharedAccessKey")?,
+        entity_path: take("EntityPath").ok(),
+    })
+}
+
+#[derive(Clone, Debug)]
+pub(super) enum Credentials {
+    /// Authenticated with SASL PLAIN, with the name and the value of the key.
+    SharedAccessKey { name: String, key: String },
+    /// Authenticated with a token put to the claims-based security node.
+    AzureAd,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct EventHub {
+    pub namespace: String,
+    pub name: String,
+    pub credentials: Credentials,
+}
+
+/// A connection to the namespace of an event hub, with a session to open links on.
+pub(super) struct Client {
+    connection: ConnectionHandle<()>,
+    session: SessionHandle<()>,
+}
+
+impl Client {
+    pub(super) async fn connect(event_hub: &EventHub) -> crate::Result<Self> {
+        // Event Hubs expects TLS to be established right away, instead of being negotiated.
+        let builder = Connection::builder()
+            .container_id(format!("vector-{}", uuid::Uuid::new_v4()))
+            .hostname(event_hub.namespace.as_str())
+            .alt_tls_establishment(true);
+        let builder = match &event_hub.credentials {
+            Credentials::SharedAccessKey { name, key } => {
+                builder.sasl_profile(SaslProfile::Plain {
+                    username: name.clone(),
+                    password: key.clone(),
+                })
+            }
+            Credentials::AzureAd => builder.sasl_profile(SaslProfile::Anonymous),
+        };
+        let mut connection = builder
+            .open(format!("amqps://{}:5671", event_hub.namespace).as_str())
+            .await?;
+        let mut session = Session::begin(&mut connection).await?;
+
+        if let Credentials::AzureAd = event_hub.credentials {
+            let token = DefaultAzureCredential::default()
+                .get_token(AZURE_AD_RESOURCE)
+                .await?;
+            let expires_at = Timestamp::from_milliseconds(token.expires_on.unix_timestamp() * 1000);
+            let response = request(
+                &mut session,
+                CBS_ADDRESS,
+                ApplicationProperties::builder()
+                    .insert("operation", "put-token")
+                    .insert("type", "jwt")
+                    .insert(
+                        "name",
+                        format!("amqp://{}/{}", event_hub.namespace, event_hub.name),
+                    )
+                    .insert("expiration", expires_at)
+                    .build(),
+                Value::String(token.token.secret().to_owned()),
+            )
+            .await?;
+            let status = response
+                .application_properties
+                .as_ref()
+                .and_then(|properties| properties.0.get("status-code"));
+            if !matches!(status, Some(SimpleValue::Int(200 | 202))) {
+                let description = response
+                    .application_properties
+                    .as_ref()
+                    .and_then(|properties| properties.0.get("status-description"))
+                    .and_then(simple_to_string)
+                    .unwrap_or_default();
+                return Err(format!("Putting the token was rejected: {}", description).into());
+            }
+        }
+
+        Ok(Self {
+            connection,
+            session,
+        })
+    }
+
+    /// Reads the identifiers of the partitions, from the management node of the event hub.
+    pub(super) async fn partition_ids(
+        &mut self,
+        event_hub: &EventHub,
+    ) -> crate::Result<Vec<String>> {
+        let response = request(
+            &mut self.session,
+            MANAGEMENT_ADDRESS,
+            ApplicationProperties::builder()
+                .insert("operation", "READ")
+                .insert("type", "com.microsoft:eventhub")
+                .insert("name", event_hub.name.as_str())
+                .build(),
+            Value::Null,
+        )
+        .await?;
+
+        let partition_ids = match &response.body {
+            Body::Value(value) => match &value.0 {
+                Value::Map(map) => map.get(&Value::from("partition_ids")).cloned(),
+                _ => None,
+            },
+            _ => None,
+        };
+        let partition_ids = match partition_ids {
+            Some(Value::Array(ids)) => ids.0,
+            Some(Value::List(ids)) => ids,
+            _ => return Err("Management response is missing the partition identifiers".into()),
+        };
+        Ok(partition_ids
+            .into_iter()
+            .filter_map(|id| match id {
+                Value::String(id) => Some(id),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Opens a receiver on a partition, starting after the offset, or at the start position.
+    pub(super) async fn receiver(
+        &mut self,
+        event_hub: &EventHub,
+        consumer_group: &str,
+        partition_id: &str,
+        start: Start<'_>,
+    ) -> crate::Result<Receiver> {
+        let source = Source::builder()
+            .address(format!(
+                "{}/ConsumerGroups/{}/Partitions/{}",
+                event_hub.name, consumer_group, partition_id
+            ))
+            .add_to_filter(
+                Symbol::from(SELECTOR_FILTER),
+                Some(Described {
+                    descriptor: Descriptor::Name(Symbol::from(SELECTOR_FILTER)),
+                    value: Value::String(start.filter()),
+                }),
+            )
+            .build();
+        // Events are settled once sent, as they can only be read again by their offset.
+        let receiver = Receiver::builder()
+            .name(format!("vector-{}-{}", partition_id, uuid::Uuid::new_v4()))
+            .source(source)
+            .sender_settle_mode(SenderSettleMode::Settled)
+            .receiver_settle_mode(ReceiverSettleMode::First)
+            .attach(&mut self.session)
+            .await?;
+        Ok(receiver)
+    }
+
+    pub(super) async fn close(mut self) {
+        let _ = self.session.end().await;
+        let _ = self.connection.close().await;
+    }
+}
+
+/// Sends a request to a node, like the management node, and waits for its response.
+async fn request(
+    session: &mut SessionHandle<()>,
+    address: &str,
+    application_properties: ApplicationProperties,
+    body: Value,
+) -> crate::Result<Message<Body<Value>>> {
+    let node = address.trim_start_matches('$');
+    let reply_to = format!("vector-{}-reply", node);
+    let mut sender = Sender::attach(session, format!("vector-{}-sender", node), address).await?;
+    let mut receiver = Receiver::builder()
+        .name(format!("vector-{}-receiver", node))
+        .source(address)
+        .target(reply_to.as_str())
+        .attach(session)
+        .await?;
+
+    let request = Message::builder()
+        .properties(
+            Properties::builder()
+                .message_id(uuid::Uuid::new_v4().to_string())
+                .reply_to(reply_to)
+                .build(),
+        )
+        .application_properties(application_properties)
+        .value(body)
+        .build();
+    sender.send(request).await?;
+    let response: Delivery<Body<Value>> = receiver.recv().await?;
+    receiver.accept(&response).await?;
+    let _ = sender.close().await;
+    let _ = receiver.close().await;
+    Ok(response.into_message())
+}
+
+/// Where a receiver starts reading a partition.
+#[derive(Clone, Copy, Debug)]
+pub(super) enum Start<'a> {
+    After(&'a str),
+    Position(StartPosition),
+}
+
+impl<'a> Start<'a> {
+    fn filter(self) -> String {
+        let offset = match self {
+            Self::After(offset) => offset,
+            Self::Position(StartPosition::Earliest) => "-1",
+            Self::Position(StartPosition::Latest) => "@latest",
+        };
+        format!("amqp.annotation.{} > '{}'", OFFSET_ANNOTATION, offset)
+    }
+}
+
+/// An event read from a partition.
+pub(super) struct EventData {
+    pub body: Vec<Bytes>,
+    pub offset: Option<String>,
+    pub sequence_number: Option<i64>,
+    pub enqueued_time: Option<DateTime<Utc>>,
+    pub partition_key: Option<String>,
+    pub properties: Vec<(String, String)>,
+}
+
+fn annotation<'a>(annotations: &'a Option<MessageAnnotations>, name: &str) -> Option<&'a Value> {
+    annotations
+        .as_ref()?
+        .0
+        .iter()
+        .find_map(|(key, value)| match key {
+            OwnedKey::Symbol(symbol) if symbol.0 == name => Some(value),
+            _ => None,
+        })
+}
+
+fn simple_to_string(value: &SimpleValue) -> Option<String> {
+    Some(match value {
+        SimpleValue::String(value) => value.clone(),
+        SimpleValue::Symbol(value) => value.0.clone(),
+        SimpleValue::Bool(value) => value.to_string(),
+        SimpleValue::Long(value) => value.to_string(),
+        SimpleValue::Int(value) => value.to_string(),
+        SimpleValue::ULong(value) => value.to_string(),
+        SimpleValue::UInt(value) => value.to_string(),
+        SimpleValue::Double(value) => value.to_string(),
+        SimpleValue::Uuid(value) => uuid::Uuid::from_bytes(*value.as_inner()).to_string(),
+        _ => return None,
+    })
+}
+
+impl From<Delivery<Body<Value>>> for EventData {
+    fn from(delivery: Delivery<Body<Value>>) -> Self {
+        let message = delivery.into_message();
+        let annotations = &message.message_annotations;
+        let offset = match annotation(annotations, OFFSET_ANNOTATION) {
+            Some(Value::String(offset)) => Some(offset.clone()),
+            Some(Value::Long(offset)) => Some(offset.to_string()),
+            _ => None,
+        };
+        let sequence_number = match annotation(annotations, SEQUENCE_NUMBER_ANNOTATION) {
+            Some(Value::Long(sequence_number)) => Some(*sequence_number),
+            _ => None,
+        };
+        let enqueued_time = match annotation(annotations, ENQUEUED_TIME_ANNOTATION) {
+            Some(Value::Timestamp(timestamp)) => {
+                Utc.timestamp_millis_opt(timestamp.milliseconds()).single()
+            }
+            _ => None,
+        };
+        let partition_key = match annotation(annotations, PARTITION_KEY_ANNOTATION) {
+            Some(Value::String(partition_key)) => Some(partition_key.clone()),
+            _ => None,
+        };
+        let properties = message
+            .application_properties
+            .as_ref()
+            .map(|properties| {
+                properties
+                    .0
+                    .iter()
+                    .filter_map(|(key, value)| Some((key.clone(), simple_to_string(value)?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Events are sent in data sections, but other clients may send them as values.
+        let body = match message.body {
+            Body::Data(sections) => sections
+                .into_iter()
+                .map(|data| Bytes::from(data.0.into_vec()))
+                .collect(),
+            Body::Value(value) => match value.0 {
+                Value::String(value) => vec![Bytes::from(value)],
+                Value::Binary(value) => vec![Bytes::from(value.into_vec())],
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+
+        Self {
+            body,
+            offset,
+            sequence_number,
+            enqueued_time,
+            partition_key,
+            properties,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_connection_string() {
+        let parsed = parse_connection_string(
+            "Endpoint=sb://example.servicebus.windows.net/;SharedAccessKeyName=listen;SharedAccessKey=c2VjcmV0=",
+        )
+        .unwrap();
+
+        assert_eq!(parsed.namespace, "example.servicebus.windows.net");
+        assert_eq!(parsed.key_name, "listen");
+        assert_eq!(parsed.key, "c2VjcmV0=");
+        assert_eq!(parsed.entity_path, None);
+    }
+
+    #[test]
+    fn rejects_connection_string_without_key() {
+        assert!(parse_connection_string(
+            "Endpoint=sb://example.servicebus.windows.net/;SharedAccessKeyName=listen"
+        )
+        .is_err());
+        assert!(parse_connection_string(
+            "Endpoint=https://example.servicebus.windows.net/;SharedAccessKeyName=listen;SharedAccessKey=c2VjcmV0"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn filters_start() {
+        assert_eq!(
+            Start::After("4294967296").filter(),
+            "amqp.annotation.x-opt-offset > '4294967296'"
+        );
+        assert_eq!(
+            Start::Position(StartPosition::Earliest).filter(),
+            "amqp.annotation.x-opt-offset > '-1'"
+        );
+        assert_eq!(
+            Start::Position(StartPosition::Latest).filter(),
+            "amqp.annotation.x-opt-offset > '@latest'"
+        );
+    }
+}
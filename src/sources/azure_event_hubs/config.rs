@@ -0,0 +1,279 @@
+use std::time::Duration;
+
+use codecs::decoding::{DeserializerConfig, FramingConfig};
+use snafu::Snafu;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use super::{
+    amqp::{Credentials, EventHub},
+    ownership::OwnershipStore,
+    source::EventHubsSource,
+};
+use crate::{
+    codecs::DecodingConfig,
+    config::{AcknowledgementsConfig, Output, SourceConfig, SourceContext},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    sinks::azure_common,
+};
+
+#[derive(Debug, Snafu)]
+enum ConfigError {
+    #[snafu(display("Either `connection_string` or `namespace` has to be provided"))]
+    MissingCredentials,
+    #[snafu(display("`connection_string` and `namespace` can't be provided at the same time"))]
+    ConflictingCredentials,
+    #[snafu(display(
+        "`event_hub_name` has to be provided, if the connection string has no `EntityPath`"
+    ))]
+    MissingEventHubName,
+    #[snafu(display("`ownership_expiration_secs` must be greater than `load_balancing_secs`"))]
+    InvalidOwnershipExpiration,
+}
+
+/// Where to start reading partitions without a checkpoint from.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StartPosition {
+    /// Start with the events enqueued after the partition is first read.
+    #[derivative(Default)]
+    Latest,
+
+    /// Start with the oldest events of the partition, which are still retained.
+    Earliest,
+}
+
+/// Storage of partition ownership and checkpoints, in an Azure Blob Storage container.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct CheckpointStoreConfig {
+    /// The Azure Blob Storage Account connection string.
+    ///
+    /// Either `storage_account`, or this field, must be specified.
+    pub connection_string: Option<SensitiveString>,
+
+    /// The Azure Blob Storage Account name, authenticated with Azure Active Directory.
+    ///
+    /// Either `connection_string`, or this field, must be specified.
+    pub storage_account: Option<String>,
+
+    /// The name of the container to store blobs in.
+    ///
+    /// The blobs are named like the ones of the Azure SDKs, so that processors built with them
+    /// can share the container.
+    pub container_name: String,
+}
+
+/// Configuration for the `azure_event_hubs` source.
+#[configurable_component(source("azure_event_hubs"))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct AzureEventHubsConfig {
+    /// The connection string of the Event Hubs namespace or event hub, with a shared access key.
+    ///
+    /// Either `namespace`, or this field, must be specified.
+    pub connection_string: Option<SensitiveString>,
+
+    /// The fully qualified Event Hubs namespace, such as `example.servicebus.windows.net`.
+    ///
+    /// Credentials are loaded from Azure Active Directory, with the environment variables, a
+    /// Managed Identity or the `az` CLI tool, in this order.
+    ///
+    /// Either `connection_string`, or this field, must be specified.
+    pub namespace: Option<String>,
+
+    /// The name of the event hub to read events from.
+    ///
+    /// Can be omitted if the connection string has an `EntityPath`.
+    pub event_hub_name: Option<String>,
+
+    /// The consumer group to read events with.
+    ///
+    /// Instances of Vector with the same consumer group and checkpoint store share the partitions
+    /// of the event hub, and the checkpoints of the partitions.
+    #[serde(default = "default_consumer_group")]
+    #[derivative(Default(value = "default_consumer_group()"))]
+    pub consumer_group: String,
+
+    #[configurable(derived)]
+    pub checkpoint_store: CheckpointStoreConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub start_position: StartPosition,
+
+    /// How often to balance the ownership of partitions between instances, in seconds.
+    ///
+    /// An instance claims at most one partition each time, so that the partitions are shared
+    /// evenly once instances start.
+    #[serde(default = "default_load_balancing_secs")]
+    #[derivative(Default(value = "default_load_balancing_secs()"))]
+    pub load_balancing_secs: u64,
+
+    /// How long the ownership of a partition is valid without being renewed, in seconds.
+    ///
+    /// Other instances take over the partitions of an instance, once their ownership expires.
+    #[serde(default = "default_ownership_expiration_secs")]
+    #[derivative(Default(value = "default_ownership_expiration_secs()"))]
+    pub ownership_expiration_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_consumer_group() -> String {
+    "$Default".to_owned()
+}
+
+const fn default_load_balancing_secs() -> u64 {
+    10
+}
+
+const fn default_ownership_expiration_secs() -> u64 {
+    60
+}
+
+impl_generate_config_from_default!(AzureEventHubsConfig);
+
+#[async_trait::async_trait]
+impl SourceConfig for AzureEventHubsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<crate::sources::Source> {
+        if self.ownership_expiration_secs <= self.load_balancing_secs {
+            return Err(ConfigError::InvalidOwnershipExpiration.into());
+        }
+
+        let event_hub = self.event_hub()?;
+        let client = azure_common::config::build_client(
+            self.checkpoint_store
+                .connection_string
+                .as_ref()
+                .map(|connection_string| connection_string.inner().to_owned()),
+            self.checkpoint_store.storage_account.clone(),
+            self.checkpoint_store.container_name.clone(),
+        )?;
+        let ownership = OwnershipStore::new(
+            client,
+            &event_hub,
+            &self.consumer_group,
+            Duration::from_secs(self.ownership_expiration_secs),
+        );
+
+        let decoder = DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .build();
+
+        Ok(Box::pin(
+            EventHubsSource {
+                event_hub,
+                consumer_group: self.consumer_group.clone(),
+                ownership,
+                start_position: self.start_position,
+                load_balancing_interval: Duration::from_secs(self.load_balancing_secs),
+                decoder,
+                acknowledgements: cx.do_acknowledgements(&self.acknowledgements),
+            }
+            .run(cx.out, cx.shutdown),
+        ))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(self.decoding.output_type())]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl AzureEventHubsConfig {
+    fn event_hub(&self) -> crate::Result<EventHub> {
+        let (namespace, credentials, entity_path) = match (&self.connection_string, &self.namespace)
+        {
+            (Some(connection_string), None) => {
+                let parsed = super::amqp::parse_connection_string(connection_string.inner())?;
+                (
+                    parsed.namespace,
+                    Credentials::SharedAccessKey {
+                        name: parsed.key_name,
+                        key: parsed.key,
+                    },
+                    parsed.entity_path,
+                )
+            }
+            (None, Some(namespace)) => (namespace.clone(), Credentials::AzureAd, None),
+            (None, None) => return Err(ConfigError::MissingCredentials.into()),
+            (Some(_), Some(_)) => return Err(ConfigError::ConflictingCredentials.into()),
+        };
+        let name = self
+            .event_hub_name
+            .clone()
+            .or(entity_path)
+            .ok_or(ConfigError::MissingEventHubName)?;
+        Ok(EventHub {
+            namespace,
+            name,
+            credentials,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureEventHubsConfig>();
+    }
+
+    #[test]
+    fn event_hub_from_connection_string() {
+        let config: AzureEventHubsConfig = toml::from_str(
+            r#"
+            connection_string = "Endpoint=sb://example.servicebus.windows.net/;SharedAccessKeyName=listen;SharedAccessKey=c2VjcmV0;EntityPath=logs"
+            checkpoint_store.storage_account = "example"
+            checkpoint_store.container_name = "checkpoints"
+            "#,
+        )
+        .unwrap();
+        let event_hub = config.event_hub().unwrap();
+
+        assert_eq!(event_hub.namespace, "example.servicebus.windows.net");
+        assert_eq!(event_hub.name, "logs");
+        assert_eq!(config.consumer_group, "$Default");
+        assert_eq!(config.start_position, StartPosition::Latest);
+    }
+
+    #[test]
+    fn event_hub_name_required_without_entity_path() {
+        let config: AzureEventHubsConfig = toml::from_str(
+            r#"
+            namespace = "example.servicebus.windows.net"
+            checkpoint_store.storage_account = "example"
+            checkpoint_store.container_name = "checkpoints"
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.event_hub().is_err());
+    }
+}
@@ -0,0 +1,6 @@
+mod amqp;
+mod config;
+mod ownership;
+mod source;
+
+pub use config::AzureEventHubsConfig;
@@ -0,0 +1,428 @@
+//! Ownership and checkpoints of partitions, stored in Azure Blob Storage.
+//!
+//! Every partition has an ownership blob, whose metadata holds the instance currently reading it,
+//! and a checkpoint blob, whose metadata holds the offset of the last event processed. Ownership
+//! is claimed with conditional writes on the ETag of the blob, so that a partition is only read by
+//! a single instance at a time, and expires unless it's renewed, so that the partitions of a
+//! stopped instance are taken over by the others.
+//!
+//! The blobs are laid out like the ones of the Azure SDKs.
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use azure_core::{
+    error::HttpError,
+    request_options::{IfMatchCondition, Metadata},
+};
+use azure_storage_blobs::prelude::*;
+use bytes::Bytes;
+use futures::StreamExt;
+use http::StatusCode;
+use rand::seq::SliceRandom;
+
+use super::amqp::EventHub;
+
+const OWNER_ID: &str = "ownerid";
+const OFFSET: &str = "offset";
+const SEQUENCE_NUMBER: &str = "sequencenumber";
+
+/// The ownership of a partition, as last read or written.
+#[derive(Clone, Debug)]
+pub(super) struct Ownership {
+    pub partition_id: String,
+    /// The owner, which is empty once the partition is released.
+    pub owner_id: String,
+    pub etag: String,
+    /// When the ownership was last claimed or renewed, in seconds since the Unix epoch.
+    pub last_modified: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct Checkpoint {
+    pub offset: String,
+    pub sequence_number: Option<i64>,
+}
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+/// Whether the write was conditional on an ETag, which changed since.
+fn is_conflict(error: &azure_core::Error) -> bool {
+    error
+        .downcast_ref::<HttpError>()
+        .and_then(|error| StatusCode::from_u16(error.status().into()).ok())
+        .map_or(false, |status| {
+            status == StatusCode::PRECONDITION_FAILED || status == StatusCode::CONFLICT
+        })
+}
+
+fn is_not_found(error: &azure_core::Error) -> bool {
+    error
+        .downcast_ref::<HttpError>()
+        .and_then(|error| StatusCode::from_u16(error.status().into()).ok())
+        == Some(StatusCode::NOT_FOUND)
+}
+
+#[derive(Clone)]
+pub(super) struct OwnershipStore {
+    client: Arc<ContainerClient>,
+    prefix: String,
+    owner_id: String,
+    expiration: Duration,
+}
+
+impl OwnershipStore {
+    pub(super) fn new(
+        client: Arc<ContainerClient>,
+        event_hub: &EventHub,
+        consumer_group: &str,
+        expiration: Duration,
+    ) -> Self {
+        // The owner identifies this instance, so that the ownership of earlier runs is taken over
+        // once it expires.
+        let owner_id = format!(
+            "{}-{}",
+            crate::get_hostname().unwrap_or_else(|_| "vector".to_owned()),
+            uuid::Uuid::new_v4()
+        );
+        Self {
+            client,
+            prefix: format!(
+                "{}/{}/{}",
+                event_hub.namespace, event_hub.name, consumer_group
+            )
+            .to_lowercase(),
+            owner_id,
+            expiration,
+        }
+    }
+
+    pub(super) fn owner_id(&self) -> &str {
+        &self.owner_id
+    }
+
+    fn ownership_blob(&self, partition_id: &str) -> String {
+        format!("{}/ownership/{}", self.prefix, partition_id)
+    }
+
+    fn checkpoint_blob(&self, partition_id: &str) -> String {
+        format!("{}/checkpoint/{}", self.prefix, partition_id)
+    }
+
+    /// Lists the ownership of all partitions, which were ever claimed.
+    pub(super) async fn list(&self) -> crate::Result<Vec<Ownership>> {
+        let prefix = format!("{}/ownership/", self.prefix);
+        let mut pages = self
+            .client
+            .list_blobs()
+            .prefix(prefix.clone())
+            .include_metadata(true)
+            .into_stream();
+        let mut ownerships = Vec::new();
+        while let Some(page) = pages.next().await {
+            for blob in page?.blobs.blobs() {
+                let partition_id = match blob.name.strip_prefix(&prefix) {
+                    Some(partition_id) => partition_id.to_owned(),
+                    None => continue,
+                };
+                ownerships.push(Ownership {
+                    partition_id,
+                    owner_id: blob
+                        .metadata
+                        .as_ref()
+                        .and_then(|metadata| metadata.get(OWNER_ID))
+                        .cloned()
+                        .unwrap_or_default(),
+                    etag: blob.properties.etag.to_string(),
+                    last_modified: blob.properties.last_modified.unix_timestamp(),
+                });
+            }
+        }
+        Ok(ownerships)
+    }
+
+    /// Claims a partition, or renews its ownership. The claim only succeeds if the ownership
+    /// wasn't changed since it was read, or doesn't exist yet without an ETag.
+    pub(super) async fn claim(
+        &self,
+        partition_id: &str,
+        etag: Option<&str>,
+    ) -> crate::Result<Option<Ownership>> {
+        self.write_ownership(partition_id, etag, &self.owner_id)
+            .await
+    }
+
+    /// Releases a partition, so that other instances claim it without waiting for it to expire.
+    pub(super) async fn release(&self, ownership: &Ownership) -> crate::Result<()> {
+        self.write_ownership(&ownership.partition_id, Some(&ownership.etag), "")
+            .await
+            .map(|_| ())
+    }
+
+    async fn write_ownership(
+        &self,
+        partition_id: &str,
+        etag: Option<&str>,
+        owner_id: &str,
+    ) -> crate::Result<Option<Ownership>> {
+        let mut metadata = Metadata::new();
+        metadata.insert(OWNER_ID, owner_id.to_owned());
+        let blob = self.client.blob_client(self.ownership_blob(partition_id));
+
+        // Blobs can't be created conditionally, so a missing ownership is created empty, and then
+        // claimed like an existing one, on the ETag of the created blob. Of racing claims, only
+        // the one on the blob created last succeeds.
+        let etag = match etag {
+            Some(etag) => etag.to_owned(),
+            None => blob.put_block_blob(Bytes::new()).into_future().await?.etag,
+        };
+        let result = blob
+            .set_metadata()
+            .metadata(metadata)
+            .if_match(IfMatchCondition::Match(etag))
+            .into_future()
+            .await;
+        match result {
+            Ok(response) => Ok(Some(Ownership {
+                partition_id: partition_id.to_owned(),
+                owner_id: owner_id.to_owned(),
+                etag: response.etag,
+                last_modified: now_secs(),
+            })),
+            Err(error) if is_conflict(&error) => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Gets the checkpoint of a partition.
+    pub(super) async fn checkpoint_of(
+        &self,
+        partition_id: &str,
+    ) -> crate::Result<Option<Checkpoint>> {
+        let result = self
+            .client
+            .blob_client(self.checkpoint_blob(partition_id))
+            .get_properties()
+            .into_future()
+            .await;
+        let metadata: HashMap<String, String> = match result {
+            Ok(response) => response.blob.metadata.unwrap_or_default(),
+            Err(error) if is_not_found(&error) => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+        Ok(metadata.get(OFFSET).map(|offset| Checkpoint {
+            offset: offset.clone(),
+            sequence_number: metadata
+                .get(SEQUENCE_NUMBER)
+                .and_then(|sequence_number| sequence_number.parse().ok()),
+        }))
+    }
+
+    /// Stores the checkpoint of a partition.
+    pub(super) async fn store_checkpoint(
+        &self,
+        partition_id: &str,
+        checkpoint: &Checkpoint,
+    ) -> crate::Result<()> {
+        let mut metadata = Metadata::new();
+        metadata.insert(OFFSET, checkpoint.offset.clone());
+        if let Some(sequence_number) = checkpoint.sequence_number {
+            metadata.insert(SEQUENCE_NUMBER, sequence_number.to_string());
+        }
+        self.client
+            .blob_client(self.checkpoint_blob(partition_id))
+            .put_block_blob(Bytes::new())
+            .metadata(metadata)
+            .into_future()
+            .await?;
+        Ok(())
+    }
+
+    /// Picks the partition to claim next, from the current ownership of all partitions.
+    pub(super) fn partition_to_claim(
+        &self,
+        partition_ids: &[String],
+        ownerships: &[Ownership],
+    ) -> Option<String> {
+        partition_to_claim(
+            partition_ids,
+            ownerships,
+            &self.owner_id,
+            now_secs(),
+            self.expiration.as_secs() as i64,
+        )
+    }
+}
+
+fn is_active(ownership: &Ownership, now: i64, expiration: i64) -> bool {
+    !ownership.owner_id.is_empty() && now - ownership.last_modified < expiration
+}
+
+/// Picks a partition to claim, if this instance owns less than its share of the partitions.
+///
+/// The partitions are shared evenly between the instances with an active ownership, with some
+/// owning one more if they don't divide evenly. Partitions without an active owner are claimed
+/// first, and otherwise one is taken over from an instance owning more than its share.
+fn partition_to_claim(
+    partition_ids: &[String],
+    ownerships: &[Ownership],
+    owner_id: &str,
+    now: i64,
+    expiration: i64,
+) -> Option<String> {
+    let active = ownerships
+        .iter()
+        .filter(|ownership| is_active(ownership, now, expiration))
+        .filter(|ownership| partition_ids.contains(&ownership.partition_id))
+        .collect::<Vec<_>>();
+    let mut owned = HashMap::<&str, Vec<&str>>::new();
+    owned.entry(owner_id).or_default();
+    for ownership in &active {
+        owned
+            .entry(&ownership.owner_id)
+            .or_default()
+            .push(&ownership.partition_id);
+    }
+
+    let minimum = partition_ids.len() / owned.len();
+    let extra = partition_ids.len() % owned.len();
+    let mine = owned[owner_id].len();
+    let above_minimum = owned
+        .values()
+        .filter(|partitions| partitions.len() > minimum)
+        .count();
+    // With partitions left over, some instances own one more than the minimum.
+    if mine > minimum || (mine == minimum && (extra == 0 || above_minimum >= extra)) {
+        return None;
+    }
+
+    let mut rng = rand::thread_rng();
+    let unowned = partition_ids
+        .iter()
+        .filter(|partition_id| {
+            !active
+                .iter()
+                .any(|ownership| &ownership.partition_id == *partition_id)
+        })
+        .collect::<Vec<_>>();
+    if let Some(partition_id) = unowned.choose(&mut rng) {
+        return Some((*partition_id).clone());
+    }
+
+    // Partitions are taken from instances owning more than the maximum, or otherwise the
+    // maximum, while this instance doesn't own the minimum yet.
+    let maximum = minimum + usize::from(extra > 0);
+    let mut candidates = owned
+        .iter()
+        .filter(|(owner, _)| **owner != owner_id)
+        .filter(|(_, partitions)| partitions.len() > maximum)
+        .flat_map(|(_, partitions)| partitions.iter())
+        .collect::<Vec<_>>();
+    if candidates.is_empty() && mine < minimum {
+        candidates = owned
+            .iter()
+            .filter(|(owner, _)| **owner != owner_id)
+            .filter(|(_, partitions)| partitions.len() == maximum && maximum > minimum)
+            .flat_map(|(_, partitions)| partitions.iter())
+            .collect();
+    }
+    candidates
+        .choose(&mut rng)
+        .map(|partition_id| (**partition_id).to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_000;
+    const EXPIRATION: i64 = 60;
+
+    fn partitions(count: usize) -> Vec<String> {
+        (0..count).map(|id| id.to_string()).collect()
+    }
+
+    fn owned(partition_id: usize, owner_id: &str, last_modified: i64) -> Ownership {
+        Ownership {
+            partition_id: partition_id.to_string(),
+            owner_id: owner_id.to_owned(),
+            etag: "etag".to_owned(),
+            last_modified,
+        }
+    }
+
+    #[test]
+    fn claims_unowned_partitions() {
+        let claimed = partition_to_claim(&partitions(4), &[], "a", NOW, EXPIRATION);
+
+        assert!(claimed.is_some());
+    }
+
+    #[test]
+    fn claims_expired_and_released_partitions() {
+        let ownerships = [
+            owned(0, "a", NOW),
+            owned(1, "b", NOW - EXPIRATION),
+            owned(2, "", NOW),
+        ];
+        let claimed = partition_to_claim(&partitions(3), &ownerships, "a", NOW, EXPIRATION);
+
+        assert!(matches!(claimed.as_deref(), Some("1") | Some("2")));
+    }
+
+    #[test]
+    fn stops_claiming_past_share() {
+        let ownerships = [
+            owned(0, "a", NOW),
+            owned(1, "a", NOW),
+            owned(2, "b", NOW),
+            owned(3, "b", NOW),
+        ];
+
+        assert_eq!(
+            partition_to_claim(&partitions(4), &ownerships, "a", NOW, EXPIRATION),
+            None
+        );
+        // One of the instances owns one more, once the partitions don't divide evenly.
+        assert_eq!(
+            partition_to_claim(&partitions(5), &ownerships, "a", NOW, EXPIRATION),
+            Some("4".to_owned())
+        );
+    }
+
+    #[test]
+    fn takes_over_from_instances_above_share() {
+        let ownerships = [
+            owned(0, "a", NOW),
+            owned(1, "a", NOW),
+            owned(2, "a", NOW),
+            owned(3, "a", NOW),
+        ];
+        let claimed = partition_to_claim(&partitions(4), &ownerships, "b", NOW, EXPIRATION);
+
+        assert!(claimed.is_some());
+        assert_eq!(
+            partition_to_claim(&partitions(4), &ownerships, "a", NOW, EXPIRATION),
+            None
+        );
+    }
+
+    #[test]
+    fn keeps_balanced_ownership() {
+        let ownerships = [owned(0, "a", NOW), owned(1, "a", NOW), owned(2, "b", NOW)];
+
+        // With three partitions between two instances, the one owning a single partition is done.
+        assert_eq!(
+            partition_to_claim(&partitions(3), &ownerships, "b", NOW, EXPIRATION),
+            None
+        );
+    }
+}
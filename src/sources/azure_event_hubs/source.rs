@@ -0,0 +1,448 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Duration,
+};
+
+use futures::StreamExt;
+use tokio::{sync::oneshot, task::JoinHandle, time::interval};
+use vector_common::{
+    finalizer::OrderedFinalizer,
+    internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol, Registered},
+};
+
+use super::{
+    amqp::{Client, EventData, EventHub, Start},
+    config::StartPosition,
+    ownership::{Checkpoint, Ownership, OwnershipStore},
+};
+use crate::{
+    codecs::Decoder,
+    event::{BatchNotifier, BatchStatus, MaybeAsLogMut, Value},
+    internal_events::{
+        AzureEventHubsCheckpointError, AzureEventHubsOwnershipError, AzureEventHubsReceiveError,
+        StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    sources::util,
+    SourceSender,
+};
+
+/// How long to wait after an error, before reading a partition again.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+/// How often to store the checkpoint of a partition, once events were processed.
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(5);
+
+pub struct EventHubsSource {
+    pub(super) event_hub: EventHub,
+    pub consumer_group: String,
+    pub(super) ownership: OwnershipStore,
+    pub(super) start_position: StartPosition,
+    pub load_balancing_interval: Duration,
+    pub decoder: Decoder,
+    pub(super) acknowledgements: bool,
+}
+
+/// A partition owned by this instance, and the consumer reading it.
+struct Owned {
+    ownership: Ownership,
+    consumer: Option<(oneshot::Sender<()>, JoinHandle<()>)>,
+}
+
+impl Owned {
+    fn is_running(&self) -> bool {
+        matches!(&self.consumer, Some((_, task)) if !task.is_finished())
+    }
+
+    /// Stops the consumer, which stores its checkpoint before it finishes.
+    async fn stop(self) {
+        if let Some((stop, task)) = self.consumer {
+            drop(stop);
+            let _ = task.await;
+        }
+    }
+}
+
+impl EventHubsSource {
+    pub async fn run(self, out: SourceSender, mut shutdown: ShutdownSignal) -> Result<(), ()> {
+        let source = Arc::new(self);
+        let mut partition_ids = Vec::new();
+        let mut owned = HashMap::new();
+        let mut balancing = interval(source.load_balancing_interval);
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = balancing.tick() => {}
+            }
+
+            // The partitions of an event hub are fixed, once it's created.
+            if partition_ids.is_empty() {
+                match source.partition_ids().await {
+                    Ok(ids) => partition_ids = ids,
+                    Err(error) => {
+                        emit!(AzureEventHubsReceiveError {
+                            error,
+                            partition_id: None
+                        });
+                        continue;
+                    }
+                }
+            }
+
+            if let Err(error) = source
+                .balance(&partition_ids, &mut owned, &out, &shutdown)
+                .await
+            {
+                emit!(AzureEventHubsOwnershipError {
+                    error,
+                    partition_id: None
+                });
+            }
+        }
+
+        // The partitions are released once their checkpoints are stored, so that other instances
+        // take them over right away.
+        for (partition_id, owned) in owned {
+            let ownership = owned.ownership.clone();
+            owned.stop().await;
+            if let Err(error) = source.ownership.release(&ownership).await {
+                emit!(AzureEventHubsOwnershipError {
+                    error,
+                    partition_id: Some(&partition_id)
+                });
+            }
+        }
+        Ok(())
+    }
+
+    async fn partition_ids(&self) -> crate::Result<Vec<String>> {
+        let mut client = Client::connect(&self.event_hub).await?;
+        let partition_ids = client.partition_ids(&self.event_hub).await;
+        client.close().await;
+        partition_ids
+    }
+
+    /// Renews the ownership of the partitions owned by this instance, and claims another one if
+    /// this instance owns less than its share.
+    async fn balance(
+        self: &Arc<Self>,
+        partition_ids: &[String],
+        owned: &mut HashMap<String, Owned>,
+        out: &SourceSender,
+        shutdown: &ShutdownSignal,
+    ) -> crate::Result<()> {
+        let mut ownerships = self.ownership.list().await?;
+
+        for ownership in &mut ownerships {
+            let current = match owned.get_mut(&ownership.partition_id) {
+                Some(current) => current,
+                None => continue,
+            };
+            // Taken over by another instance, or expired before it could be renewed.
+            if ownership.owner_id != self.ownership.owner_id()
+                || ownership.etag != current.ownership.etag
+            {
+                self.lose(owned, &ownership.partition_id).await;
+                continue;
+            }
+            match self
+                .ownership
+                .claim(&ownership.partition_id, Some(&ownership.etag))
+                .await
+            {
+                Ok(Some(renewed)) => {
+                    *ownership = renewed.clone();
+                    current.ownership = renewed;
+                }
+                Ok(None) => self.lose(owned, &ownership.partition_id).await,
+                Err(error) => emit!(AzureEventHubsOwnershipError {
+                    error,
+                    partition_id: Some(&ownership.partition_id)
+                }),
+            }
+        }
+        // Ownership which was deleted from the container isn't held anymore either.
+        for partition_id in owned.keys().cloned().collect::<Vec<_>>() {
+            if !ownerships
+                .iter()
+                .any(|ownership| ownership.partition_id == partition_id)
+            {
+                self.lose(owned, &partition_id).await;
+            }
+        }
+
+        if let Some(partition_id) = self
+            .ownership
+            .partition_to_claim(partition_ids, &ownerships)
+        {
+            let etag = ownerships
+                .iter()
+                .find(|ownership| ownership.partition_id == partition_id)
+                .map(|ownership| ownership.etag.as_str());
+            // Otherwise, the partition was claimed by another instance first.
+            if let Some(ownership) = self.ownership.claim(&partition_id, etag).await? {
+                info!(message = "Claimed partition.", partition_id = %partition_id);
+                owned.insert(
+                    partition_id,
+                    Owned {
+                        ownership,
+                        consumer: None,
+                    },
+                );
+            }
+        }
+
+        // Consumers are started for claimed partitions, and restarted if they failed.
+        for (partition_id, current) in owned.iter_mut() {
+            if current.is_running() {
+                continue;
+            }
+            let (stop, stopped) = oneshot::channel();
+            let consumer = PartitionConsumer {
+                source: Arc::clone(self),
+                partition_id: partition_id.clone(),
+                checkpoint: None,
+                out: out.clone(),
+                shutdown: shutdown.clone(),
+                stopped,
+                bytes_received: register!(BytesReceived::from(Protocol::from("amqp"))),
+            };
+            current.consumer = Some((stop, tokio::spawn(consumer.run())));
+        }
+        Ok(())
+    }
+
+    async fn lose(&self, owned: &mut HashMap<String, Owned>, partition_id: &str) {
+        if let Some(lost) = owned.remove(partition_id) {
+            info!(message = "Lost ownership of partition.", partition_id = %partition_id);
+            lost.stop().await;
+        }
+    }
+}
+
+/// Why reading a partition stopped.
+enum Exit {
+    Shutdown,
+    /// The partition isn't owned by this instance anymore.
+    Stopped,
+    /// Reading the partition failed, and is retried from the last checkpoint.
+    Retry,
+}
+
+struct PartitionConsumer {
+    source: Arc<EventHubsSource>,
+    partition_id: String,
+    checkpoint: Option<Checkpoint>,
+    out: SourceSender,
+    shutdown: ShutdownSignal,
+    stopped: oneshot::Receiver<()>,
+    bytes_received: Registered<BytesReceived>,
+}
+
+impl PartitionConsumer {
+    async fn run(mut self) {
+        match self
+            .source
+            .ownership
+            .checkpoint_of(&self.partition_id)
+            .await
+        {
+            Ok(checkpoint) => self.checkpoint = checkpoint,
+            Err(error) => {
+                emit!(AzureEventHubsCheckpointError {
+                    error,
+                    partition_id: &self.partition_id
+                });
+                return;
+            }
+        }
+
+        info!(message = "Reading partition.", partition_id = %self.partition_id);
+        loop {
+            match self.consume().await {
+                Exit::Shutdown | Exit::Stopped => break,
+                Exit::Retry => {
+                    tokio::select! {
+                        _ = &mut self.shutdown => break,
+                        _ = &mut self.stopped => break,
+                        _ = tokio::time::sleep(RETRY_DELAY) => {}
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reads the partition from the last checkpoint, until it's stopped or fails.
+    async fn consume(&mut self) -> Exit {
+        let mut pending = None;
+        let exit = self.receive(&mut pending).await;
+        self.store(pending).await;
+        exit
+    }
+
+    async fn receive(&mut self, pending: &mut Option<Checkpoint>) -> Exit {
+        let mut client = match Client::connect(&self.source.event_hub).await {
+            Ok(client) => client,
+            Err(error) => return self.receive_error(error),
+        };
+        let start = match &self.checkpoint {
+            Some(checkpoint) => Start::After(&checkpoint.offset),
+            None => Start::Position(self.source.start_position),
+        };
+        let mut receiver = match client
+            .receiver(
+                &self.source.event_hub,
+                &self.source.consumer_group,
+                &self.partition_id,
+                start,
+            )
+            .await
+        {
+            Ok(receiver) => receiver,
+            Err(error) => {
+                client.close().await;
+                return self.receive_error(error);
+            }
+        };
+
+        let mut shutdown = self.shutdown.clone();
+        let (finalizer, mut ack_stream) = OrderedFinalizer::<Checkpoint>::maybe_new(
+            self.source.acknowledgements,
+            shutdown.clone(),
+        );
+        let mut checkpoints = interval(CHECKPOINT_INTERVAL);
+        let exit = loop {
+            tokio::select! {
+                _ = &mut shutdown => break Exit::Shutdown,
+                _ = &mut self.stopped => break Exit::Stopped,
+                Some((status, checkpoint)) = ack_stream.next() => match status {
+                    // Rejected events would be rejected again, so that they are checkpointed as well.
+                    BatchStatus::Delivered | BatchStatus::Rejected => *pending = Some(checkpoint),
+                    // Later events aren't checkpointed either, but read again along with the
+                    // failed ones.
+                    BatchStatus::Errored => break Exit::Retry,
+                },
+                _ = checkpoints.tick() => self.store(pending.take()).await,
+                delivery = receiver.recv() => match delivery {
+                    Ok(delivery) => {
+                        let event = EventData::from(delivery);
+                        match self.send_event(event, finalizer.as_ref()).await {
+                            Ok(Some(checkpoint)) => *pending = Some(checkpoint),
+                            Ok(None) => {}
+                            Err(()) => break Exit::Shutdown,
+                        }
+                    }
+                    Err(error) => {
+                        emit!(AzureEventHubsReceiveError {
+                            error: error.into(),
+                            partition_id: Some(&self.partition_id)
+                        });
+                        break Exit::Retry;
+                    }
+                },
+            }
+        };
+
+        let _ = receiver.close().await;
+        client.close().await;
+        exit
+    }
+
+    fn receive_error(&self, error: crate::Error) -> Exit {
+        emit!(AzureEventHubsReceiveError {
+            error,
+            partition_id: Some(&self.partition_id)
+        });
+        Exit::Retry
+    }
+
+    /// Sends the events decoded from an event of the partition, returning the checkpoint to store
+    /// right away, if acknowledgements are disabled.
+    async fn send_event(
+        &mut self,
+        event: EventData,
+        finalizer: Option<&OrderedFinalizer<Checkpoint>>,
+    ) -> Result<Option<Checkpoint>, ()> {
+        let checkpoint = event.offset.as_ref().map(|offset| Checkpoint {
+            offset: offset.clone(),
+            sequence_number: event.sequence_number,
+        });
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(finalizer.is_some());
+        let mut events = Vec::new();
+        for body in &event.body {
+            self.bytes_received.emit(ByteSize(body.len()));
+            let decoded = util::decode_message(
+                self.source.decoder.clone(),
+                "azure_event_hubs",
+                body,
+                event.enqueued_time,
+                &batch,
+            )
+            .map(|mut decoded| {
+                if let Some(log) = decoded.maybe_as_log_mut() {
+                    log.insert("event_hub", self.source.event_hub.name.clone());
+                    log.insert("partition_id", self.partition_id.clone());
+                    if let Some(offset) = &event.offset {
+                        log.insert("offset", offset.clone());
+                    }
+                    if let Some(sequence_number) = event.sequence_number {
+                        log.insert("sequence_number", sequence_number);
+                    }
+                    if let Some(partition_key) = &event.partition_key {
+                        log.insert("partition_key", partition_key.clone());
+                    }
+                    if !event.properties.is_empty() {
+                        log.insert(
+                            "properties",
+                            event
+                                .properties
+                                .iter()
+                                .map(|(key, value)| (key.clone(), Value::from(value.clone())))
+                                .collect::<BTreeMap<_, _>>(),
+                        );
+                    }
+                }
+                decoded
+            });
+            events.extend(decoded);
+        }
+        drop(batch);
+
+        let count = events.len();
+        if count > 0 {
+            if let Err(error) = self.out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                return Err(());
+            }
+        }
+
+        match (checkpoint, receiver, finalizer) {
+            (Some(checkpoint), Some(receiver), Some(finalizer)) => {
+                finalizer.add(checkpoint, receiver);
+                Ok(None)
+            }
+            (checkpoint, _, _) => Ok(checkpoint),
+        }
+    }
+
+    async fn store(&mut self, checkpoint: Option<Checkpoint>) {
+        let checkpoint = match checkpoint {
+            Some(checkpoint) if Some(&checkpoint) != self.checkpoint.as_ref() => checkpoint,
+            _ => return,
+        };
+        match self
+            .source
+            .ownership
+            .store_checkpoint(&self.partition_id, &checkpoint)
+            .await
+        {
+            Ok(()) => self.checkpoint = Some(checkpoint),
+            // The next checkpoint covers these events as well.
+            Err(error) => emit!(AzureEventHubsCheckpointError {
+                error,
+                partition_id: &self.partition_id
+            }),
+        }
+    }
+}
@@ -0,0 +1,431 @@
+//! Reading of the log files written by containerd, CRI-O and the other runtimes implementing the
+//! Kubernetes Container Runtime Interface, on hosts without a Docker daemon.
+
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    future::ready,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use file_source::{
+    paths_provider::{
+        glob::{Glob, MatchOptions},
+        PathsProvider,
+    },
+    Checkpointer, FileServer, FingerprintStrategy, Fingerprinter, Line, ReadFrom,
+};
+use futures::{FutureExt, Stream, StreamExt, TryFutureExt};
+use tokio::task::spawn_blocking;
+use vector_core::ByteSizeOf;
+
+use super::{add_hostname, DockerLogsConfig, CONTAINER, NAME, STDERR, STDOUT, STREAM};
+use crate::{
+    config::log_schema,
+    event::{merge_state::LogEventMergeState, LogEvent},
+    internal_events::{
+        DockerLogsCriParseError, DockerLogsEventsReceived, DockerLogsTimestampParseError,
+        FileBytesReceived, FileSourceInternalEventsEmitter, StreamClosedError,
+    },
+    line_agg::{self, LineAgg},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+const FILE: &str = "file";
+
+/// A line of a CRI log file, `<timestamp> <stream> <tag> <message>`, where the tag is `P` for
+/// the parts of a line which the runtime split up and `F` for the last part.
+#[derive(Debug, Eq, PartialEq)]
+struct CriLine<'a> {
+    timestamp: &'a str,
+    stream: Bytes,
+    partial: bool,
+    message: Bytes,
+}
+
+fn parse_line(line: &Bytes) -> Option<CriLine<'_>> {
+    let mut fields = line.splitn(4, |&byte| byte == b' ');
+    let timestamp = std::str::from_utf8(fields.next()?).ok()?;
+    let stream = match fields.next()? {
+        b"stdout" => STDOUT.clone(),
+        b"stderr" => STDERR.clone(),
+        _ => return None,
+    };
+    // The tag may be followed by more flags separated with `:`.
+    let partial = match fields.next()?.split(|&byte| byte == b':').next()? {
+        b"P" => true,
+        b"F" => false,
+        _ => return None,
+    };
+    let message = fields.next().unwrap_or_default();
+
+    Some(CriLine {
+        timestamp,
+        stream,
+        partial,
+        message: line.slice(line.len() - message.len()..),
+    })
+}
+
+/// The container which wrote a log file, named like the files which the kubelet links in
+/// `/var/log/containers`, `<pod>_<namespace>_<container>-<id>.log`, or `<container>-<id>.log`.
+#[derive(Debug, Eq, PartialEq)]
+struct Container {
+    id: String,
+    name: String,
+}
+
+impl Container {
+    fn from_path(path: &Path) -> Option<Self> {
+        let stem = path.file_stem()?.to_str()?;
+        let (name, id) = stem.rsplit_once('-')?;
+        if id.len() != 64 || !id.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return None;
+        }
+        let name = name.rsplit('_').next().unwrap_or(name);
+
+        Some(Self {
+            id: id.to_owned(),
+            name: name.to_owned(),
+        })
+    }
+}
+
+/// Provides the files matched by `cri_include`, of the containers passing the container filters.
+struct CriPathsProvider {
+    glob: Glob<FileSourceInternalEventsEmitter>,
+    config: DockerLogsConfig,
+}
+
+impl PathsProvider for CriPathsProvider {
+    type IntoIter = Vec<PathBuf>;
+
+    fn paths(&self) -> Self::IntoIter {
+        self.glob
+            .paths()
+            .into_iter()
+            .filter(|path| {
+                Container::from_path(path).map_or(false, |container| {
+                    self.config
+                        .container_name_or_id_included(&container.id, [container.name.as_str()])
+                })
+            })
+            .collect()
+    }
+}
+
+/// Joins the parts of the lines which the runtime split up, separately for each file and stream.
+#[derive(Default)]
+struct PartialLines {
+    pending: HashMap<(String, Bytes), LogEventMergeState>,
+}
+
+impl PartialLines {
+    fn push(
+        &mut self,
+        file: &str,
+        stream: Bytes,
+        event: LogEvent,
+        partial: bool,
+    ) -> Option<LogEvent> {
+        let fields = [log_schema().message_key()];
+        let key = (file.to_owned(), stream);
+        match (self.pending.remove(&key), partial) {
+            (Some(mut state), true) => {
+                state.merge_in_next_event(event, &fields);
+                self.pending.insert(key, state);
+                None
+            }
+            (None, true) => {
+                self.pending.insert(key, LogEventMergeState::new(event));
+                None
+            }
+            (Some(state), false) => Some(state.merge_in_final_event(event, &fields)),
+            (None, false) => Some(event),
+        }
+    }
+}
+
+fn new_event(line: &Line, cri_line: CriLine<'_>, container: &Container) -> LogEvent {
+    let mut log_event = LogEvent::default();
+
+    log_event.insert(log_schema().source_type_key(), Bytes::from("docker"));
+    log_event.insert(log_schema().message_key(), cri_line.message);
+    log_event.insert(STREAM, cri_line.stream);
+
+    match DateTime::parse_from_rfc3339(cri_line.timestamp) {
+        Ok(timestamp) => {
+            log_event.insert(log_schema().timestamp_key(), timestamp.with_timezone(&Utc));
+        }
+        Err(error) => emit!(DockerLogsTimestampParseError {
+            error,
+            container_id: &container.id,
+        }),
+    }
+
+    log_event.insert(CONTAINER, container.id.clone());
+    log_event.insert(NAME, container.name.clone());
+    log_event.insert(FILE, line.filename.clone());
+    log_event
+}
+
+/// Merges the lines of the same container and stream, like the Docker runtime does with the
+/// lines of a container.
+fn line_agg_adapter(
+    inner: impl Stream<Item = LogEvent> + Unpin,
+    logic: line_agg::Logic<(Bytes, Bytes), LogEvent>,
+) -> impl Stream<Item = LogEvent> {
+    let line_agg_in = inner.map(|mut log_event| {
+        let message = log_event
+            .remove(log_schema().message_key())
+            .expect("message must exist in the event")
+            .coerce_to_bytes();
+        let container = log_event
+            .get(CONTAINER)
+            .expect("container ID must exist in the event")
+            .coerce_to_bytes();
+        let stream = log_event
+            .get(STREAM)
+            .expect("stream must exist in the event")
+            .coerce_to_bytes();
+        ((container, stream), message, log_event)
+    });
+    LineAgg::<_, (Bytes, Bytes), LogEvent>::new(line_agg_in, logic).map(
+        |(_, message, mut log_event)| {
+            log_event.insert(log_schema().message_key(), message);
+            log_event
+        },
+    )
+}
+
+pub(super) fn build(
+    config: DockerLogsConfig,
+    data_dir: PathBuf,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> crate::Result<crate::sources::Source> {
+    let line_agg_config = config
+        .multiline
+        .as_ref()
+        .map(line_agg::Config::try_from)
+        .transpose()?;
+
+    let glob = Glob::new(
+        &config.cri_include,
+        &[],
+        MatchOptions::default(),
+        FileSourceInternalEventsEmitter,
+    )
+    .ok_or("Invalid glob patterns in `cri_include`.")?;
+
+    let checkpointer = Checkpointer::new(&data_dir);
+    let file_server = FileServer {
+        paths_provider: CriPathsProvider {
+            glob,
+            config: config.clone(),
+        },
+        max_read_bytes: 2048,
+        ignore_checkpoints: false,
+        // Like with the Docker runtime, only the logs written from now on are collected.
+        read_from: ReadFrom::End,
+        ignore_before: None,
+        max_line_bytes: 32_768,
+        line_delimiter: Bytes::from("\n"),
+        data_dir,
+        glob_minimum_cooldown: Duration::from_secs(1),
+        // The runtime writes a timestamp at the start of each line, so that the files are told
+        // apart by their first line.
+        fingerprinter: Fingerprinter {
+            strategy: FingerprintStrategy::FirstLinesChecksum {
+                ignored_header_bytes: 0,
+                lines: 1,
+            },
+            max_line_length: 32_768,
+            ignore_not_found: true,
+        },
+        oldest_first: true,
+        // The runtime is responsible for rotating and removing the files.
+        remove_after: None,
+        emitter: FileSourceInternalEventsEmitter,
+        handle: tokio::runtime::Handle::current(),
+    };
+
+    let checkpoints = checkpointer.view();
+    let host_key = config.host_key.clone();
+    let hostname = crate::get_hostname().ok();
+    let partial_event_marker_field = config.partial_event_marker_field.clone();
+    let auto_partial_merge = config.auto_partial_merge;
+
+    Ok(Box::pin(async move {
+        info!(message = "Starting file server.", include = ?config.cri_include);
+
+        let (tx, rx) = futures::channel::mpsc::channel::<Vec<Line>>(2);
+        let mut partial_lines = PartialLines::default();
+        let events = rx
+            .map(futures::stream::iter)
+            .flatten()
+            .filter_map(move |line| {
+                emit!(FileBytesReceived {
+                    byte_size: line.text.len(),
+                    file: &line.filename,
+                });
+                checkpoints.update(line.file_id, line.end_offset);
+
+                let cri_line = parse_line(&line.text);
+                if cri_line.is_none() {
+                    emit!(DockerLogsCriParseError {
+                        file: &line.filename
+                    });
+                }
+                let container = Container::from_path(Path::new(&line.filename));
+                let event = container.zip(cri_line).and_then(|(container, cri_line)| {
+                    let stream = cri_line.stream.clone();
+                    let partial = cri_line.partial;
+                    let mut event = new_event(&line, cri_line, &container);
+                    if auto_partial_merge {
+                        event = partial_lines.push(&line.filename, stream, event, partial)?;
+                    } else if partial {
+                        if let Some(field) = &partial_event_marker_field {
+                            event.insert(field.as_str(), true);
+                        }
+                    }
+                    emit!(DockerLogsEventsReceived {
+                        byte_size: event.size_of(),
+                        container_id: &container.id,
+                        container_name: &container.name,
+                    });
+                    Some(event)
+                });
+                ready(event)
+            });
+
+        let events: Box<dyn Stream<Item = LogEvent> + Unpin + Send> =
+            if let Some(line_agg_config) = line_agg_config {
+                Box::new(line_agg_adapter(
+                    events,
+                    line_agg::Logic::new(line_agg_config),
+                ))
+            } else {
+                Box::new(events)
+            };
+
+        let mut events = events.map(move |event| add_hostname(event, &host_key, &hostname));
+        tokio::spawn(async move {
+            if let Err(error) = out.send_event_stream(&mut events).await {
+                let (count, _) = events.size_hint();
+                emit!(StreamClosedError { error, count });
+            }
+        });
+
+        let span = info_span!("file_server");
+        spawn_blocking(move || {
+            let _enter = span.enter();
+            let shutdown = shutdown.shared();
+            let result = file_server.run(tx, shutdown.clone(), shutdown, checkpointer);
+            // Panic on errors of the file server, which are passed on to the `JoinHandle`.
+            result.unwrap();
+        })
+        .map_err(|error| error!(message = "File server unexpectedly stopped.", %error))
+        .await
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "4f5c8a1b2e3d4c5b6a7980f1e2d3c4b5a69788f9e0d1c2b3a4958677f8e9d0c1";
+
+    fn event(message: &str) -> LogEvent {
+        LogEvent::from(message)
+    }
+
+    #[test]
+    fn parses_lines() {
+        let line = Bytes::from("2016-10-06T00:17:09.669794202Z stdout F The content of the log");
+        assert_eq!(
+            parse_line(&line),
+            Some(CriLine {
+                timestamp: "2016-10-06T00:17:09.669794202Z",
+                stream: STDOUT.clone(),
+                partial: false,
+                message: Bytes::from("The content of the log"),
+            })
+        );
+
+        let line = Bytes::from("2016-10-06T00:17:09.669794202Z stderr P:x a  b");
+        let parsed = parse_line(&line).unwrap();
+        assert_eq!(parsed.stream, *STDERR);
+        assert!(parsed.partial);
+        assert_eq!(parsed.message, Bytes::from("a  b"));
+
+        let line = Bytes::from("2016-10-06T00:17:09.669794202Z stdout F");
+        assert_eq!(parse_line(&line).unwrap().message, Bytes::new());
+
+        for line in [
+            "2016-10-06T00:17:09.669794202Z stdin F message",
+            "2016-10-06T00:17:09.669794202Z stdout X message",
+            "not a CRI line",
+        ] {
+            assert_eq!(parse_line(&Bytes::from(line)), None);
+        }
+    }
+
+    #[test]
+    fn parses_file_names() {
+        let path = PathBuf::from(format!(
+            "/var/log/containers/web-7d4b9c_default_nginx-{}.log",
+            ID
+        ));
+        assert_eq!(
+            Container::from_path(&path),
+            Some(Container {
+                id: ID.to_owned(),
+                name: "nginx".to_owned(),
+            })
+        );
+
+        let path = PathBuf::from(format!("/var/log/cri/my-app-{}.log", ID));
+        assert_eq!(Container::from_path(&path).unwrap().name, "my-app");
+
+        assert_eq!(Container::from_path(Path::new("/var/log/app-1.log")), None);
+    }
+
+    #[test]
+    fn joins_partial_lines() {
+        let mut partial_lines = PartialLines::default();
+        let message = |event: Option<LogEvent>| {
+            event.map(|event| event[log_schema().message_key()].to_string_lossy())
+        };
+
+        assert_eq!(
+            message(partial_lines.push("a.log", STDOUT.clone(), event("one "), true)),
+            None
+        );
+        // Parts of other files and streams are joined separately.
+        assert_eq!(
+            message(partial_lines.push("a.log", STDERR.clone(), event("error"), false)),
+            Some("error".to_owned())
+        );
+        assert_eq!(
+            message(partial_lines.push("b.log", STDOUT.clone(), event("other"), false)),
+            Some("other".to_owned())
+        );
+        assert_eq!(
+            message(partial_lines.push("a.log", STDOUT.clone(), event("two "), true)),
+            None
+        );
+        assert_eq!(
+            message(partial_lines.push("a.log", STDOUT.clone(), event("three"), false)),
+            Some("one two three".to_owned())
+        );
+        assert_eq!(
+            message(partial_lines.push("a.log", STDOUT.clone(), event("four"), false)),
+            Some("four".to_owned())
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
-    collections::HashMap, convert::TryFrom, future::ready, pin::Pin, sync::Arc, time::Duration,
+    collections::HashMap, convert::TryFrom, future::ready, path::PathBuf, pin::Pin, sync::Arc,
+    time::Duration,
 };
 
 use bollard::{
@@ -11,6 +12,7 @@ use bollard::{
 };
 use bytes::{Buf, Bytes};
 use chrono::{DateTime, FixedOffset, Local, ParseError, Utc};
+use derivative::Derivative;
 use futures::{Stream, StreamExt};
 use lookup::lookup_v2::{parse_value_path, OwnedSegment};
 use lookup::PathPrefix;
@@ -41,6 +43,8 @@ use crate::{
     SourceSender,
 };
 
+mod cri;
+
 const IMAGE: &str = "image";
 const CREATED_AT: &str = "container_created_at";
 const NAME: &str = "container_name";
@@ -73,8 +77,28 @@ pub struct DockerLogsConfig {
     /// Use an HTTPS URL to enable TLS encryption.
     ///
     /// If absent, Vector will try to use `DOCKER_HOST` environment variable. If `DOCKER_HOST` is also absent, Vector will use default Docker local socket (`/var/run/docker.sock` on Unix platforms, `//./pipe/docker_engine` on Windows).
+    ///
+    /// On Unix platforms, if the default Docker socket doesn't exist, Vector will use the Docker compatible socket of Podman instead, trying the rootless socket (`$XDG_RUNTIME_DIR/podman/podman.sock`) first and then the rootful one (`/run/podman/podman.sock`).
     docker_host: Option<String>,
 
+    #[configurable(derived)]
+    runtime: ContainerRuntime,
+
+    /// A list of glob patterns of the CRI log files to read, when `runtime` is `cri`.
+    ///
+    /// The ID and the name of the container are taken from the file name, which must end with the container ID, like the
+    /// files linked by the kubelet in `/var/log/containers`. Only the `include_containers` and `exclude_containers`
+    /// filters apply to these files.
+    cri_include: Vec<PathBuf>,
+
+    /// The directory used to persist the positions read in the CRI log files, when `runtime` is `cri`.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used. Make sure the running user has write
+    /// permissions to this directory.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    data_dir: Option<PathBuf>,
+
     /// A list of container IDs or names of containers to exclude from log collection.
     ///
     /// Matching is prefix first, so specifying a value of `foo` would match any container named `foo` as well as any
@@ -132,11 +156,31 @@ pub struct DockerLogsConfig {
     tls: Option<DockerTlsConfig>,
 }
 
+/// The container runtime to collect logs from.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerRuntime {
+    /// Docker, or Podman, through the API served at `docker_host`.
+    #[derivative(Default)]
+    Docker,
+
+    /// containerd, CRI-O or any other runtime implementing the Kubernetes Container Runtime Interface, by reading
+    /// the log files matched by `cri_include`.
+    ///
+    /// The parts of the lines which the runtime split up are joined when `auto_partial_merge` is enabled.
+    Cri,
+}
+
 impl Default for DockerLogsConfig {
     fn default() -> Self {
         Self {
             host_key: host_key(),
             docker_host: None,
+            runtime: ContainerRuntime::default(),
+            cri_include: vec![PathBuf::from("/var/log/containers/*.log")],
+            data_dir: None,
             tls: None,
             exclude_containers: None,
             include_containers: None,
@@ -195,6 +239,18 @@ impl_generate_config_from_default!(DockerLogsConfig);
 #[async_trait::async_trait]
 impl SourceConfig for DockerLogsConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.runtime == ContainerRuntime::Cri {
+            let data_dir = cx
+                .globals
+                .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+            return cri::build(
+                self.clone().with_empty_partial_event_marker_field_as_none(),
+                data_dir,
+                cx.shutdown,
+                cx.out,
+            );
+        }
+
         let source = DockerLogsSource::new(
             self.clone().with_empty_partial_event_marker_field_as_none(),
             cx.out,
@@ -1738,9 +1794,10 @@ mod integration_tests {
                 include_containers: Some(vec![name.to_owned()]),
                 include_images: Some(vec!["busybox".to_owned()]),
                 multiline: Some(MultilineConfig {
-                    start_pattern: "^[^\\s]".to_owned(),
-                    condition_pattern: "^[\\s]+at".to_owned(),
-                    mode: line_agg::Mode::ContinueThrough,
+                    preset: None,
+                    start_pattern: Some("^[^\\s]".to_owned()),
+                    condition_pattern: Some("^[\\s]+at".to_owned()),
+                    mode: Some(line_agg::Mode::ContinueThrough),
                     timeout_ms: 10,
                 }),
                 ..DockerLogsConfig::default()
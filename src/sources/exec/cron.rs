@@ -0,0 +1,280 @@
+//! Cron expressions the scheduled commands are run on.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Timelike, Utc};
+use vector_common::TimeZone;
+
+/// A cron expression with the five standard fields: minute, hour, day of month, month, and day of
+/// the week.
+///
+/// The fields accept `*`, values, ranges such as `1-5`, steps such as `*/15` or `0-30/10`, and lists
+/// of these separated by commas. Months and days of the week may also be written as their first
+/// three letters, and both `0` and `7` are Sunday.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// Like in cron, a day matches either day field when both are restricted, and both otherwise.
+    either_day: bool,
+}
+
+struct Field {
+    min: u32,
+    max: u32,
+    names: &'static [&'static str],
+}
+
+const MINUTE: Field = Field {
+    min: 0,
+    max: 59,
+    names: &[],
+};
+const HOUR: Field = Field {
+    min: 0,
+    max: 23,
+    names: &[],
+};
+const DAY_OF_MONTH: Field = Field {
+    min: 1,
+    max: 31,
+    names: &[],
+};
+const MONTH: Field = Field {
+    min: 1,
+    max: 12,
+    names: &[
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ],
+};
+const DAY_OF_WEEK: Field = Field {
+    min: 0,
+    max: 7,
+    names: &["sun", "mon", "tue", "wed", "thu", "fri", "sat"],
+};
+
+impl Field {
+    fn value(&self, value: &str) -> Result<u32, String> {
+        self.names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(value))
+            .map(|index| index as u32 + self.min)
+            .or_else(|| value.parse().ok())
+            .filter(|value| (self.min..=self.max).contains(value))
+            .ok_or_else(|| format!("invalid value {:?}", value))
+    }
+
+    fn parse(&self, field: &str) -> Result<u64, String> {
+        let mut values = 0;
+        for item in field.split(',') {
+            let (range, step) = match item.split_once('/') {
+                Some((range, step)) => {
+                    let step = step
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|step| *step > 0)
+                        .ok_or_else(|| format!("invalid step {:?}", step))?;
+                    (range, Some(step))
+                }
+                None => (item, None),
+            };
+            let (start, end) = if range == "*" {
+                (self.min, self.max)
+            } else if let Some((start, end)) = range.split_once('-') {
+                (self.value(start)?, self.value(end)?)
+            } else {
+                let start = self.value(range)?;
+                // `5/10` starts at 5 and goes on until the maximum
+                (start, if step.is_some() { self.max } else { start })
+            };
+            if start > end {
+                return Err(format!("invalid range {:?}", range));
+            }
+            for value in (start..=end).step_by(step.unwrap_or(1)) {
+                values |= 1 << value;
+            }
+        }
+        Ok(values)
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = String;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let expression = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            expression => expression,
+        };
+        let fields = expression.split_whitespace().collect::<Vec<_>>();
+        if fields.len() != 5 {
+            return Err(format!("expected 5 fields, found {}", fields.len()));
+        }
+
+        let mut days_of_week = DAY_OF_WEEK.parse(fields[4])?;
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            minutes: MINUTE.parse(fields[0])?,
+            hours: HOUR.parse(fields[1])?,
+            days_of_month: DAY_OF_MONTH.parse(fields[2])?,
+            months: MONTH.parse(fields[3])?,
+            days_of_week,
+            either_day: !fields[2].starts_with('*') && !fields[4].starts_with('*'),
+        })
+    }
+}
+
+const fn contains(values: u64, value: u32) -> bool {
+    values & (1 << value) != 0
+}
+
+impl CronSchedule {
+    /// The first time strictly after the given one which matches the expression.
+    ///
+    /// The search goes up to five years ahead, as expressions for leap days only match every four
+    /// years.
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let mut date = start.date();
+        for _ in 0..5 * 366 {
+            if self.matches_date(date) {
+                let (first_hour, first_minute) = if date == start.date() {
+                    (start.hour(), start.minute())
+                } else {
+                    (0, 0)
+                };
+                for hour in (first_hour..24).filter(|hour| contains(self.hours, *hour)) {
+                    let from = if hour == first_hour { first_minute } else { 0 };
+                    if let Some(minute) = (from..60).find(|minute| contains(self.minutes, *minute))
+                    {
+                        return Some(date.and_hms(hour, minute, 0));
+                    }
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+
+    /// The next time the expression matches in the given time zone.
+    pub fn next_run(&self, timezone: TimeZone, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match timezone {
+            TimeZone::Local => self.next_run_in(&chrono::Local, now),
+            TimeZone::Named(tz) => self.next_run_in(&tz, now),
+        }
+    }
+
+    fn next_run_in<Tz: chrono::TimeZone>(
+        &self,
+        tz: &Tz,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let mut after = now.with_timezone(tz).naive_local();
+        loop {
+            let next = self.next_after(after)?;
+            // the times skipped when daylight saving time starts never come
+            if let Some(next) = tz.from_local_datetime(&next).earliest() {
+                return Some(next.with_timezone(&Utc));
+            }
+            after = next;
+        }
+    }
+
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        let day_of_month = contains(self.days_of_month, date.day());
+        let day_of_week = contains(self.days_of_week, date.weekday().num_days_from_sunday());
+        contains(self.months, date.month())
+            && if self.either_day {
+                day_of_month || day_of_week
+            } else {
+                day_of_month && day_of_week
+            }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(expression: &str, after: &str) -> Option<NaiveDateTime> {
+        let after = NaiveDateTime::parse_from_str(after, "%Y-%m-%d %H:%M:%S").unwrap();
+        expression
+            .parse::<CronSchedule>()
+            .unwrap()
+            .next_after(after)
+    }
+
+    fn at(datetime: &str) -> Option<NaiveDateTime> {
+        Some(NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M:%S").unwrap())
+    }
+
+    #[test]
+    fn finds_next_runs() {
+        assert_eq!(
+            next("*/15 * * * *", "2022-10-14 10:07:30"),
+            at("2022-10-14 10:15:00")
+        );
+        assert_eq!(
+            next("*/15 * * * *", "2022-10-14 10:15:00"),
+            at("2022-10-14 10:30:00")
+        );
+        assert_eq!(
+            next("30 2 * * mon-fri", "2022-10-14 10:00:00"),
+            at("2022-10-17 02:30:00")
+        );
+        assert_eq!(
+            next("@monthly", "2022-12-14 10:00:00"),
+            at("2023-01-01 00:00:00")
+        );
+        assert_eq!(
+            next("0 0 29 feb *", "2022-10-14 10:00:00"),
+            at("2024-02-29 00:00:00")
+        );
+        assert_eq!(next("0 0 31 feb *", "2022-10-14 10:00:00"), None);
+    }
+
+    #[test]
+    fn matches_either_restricted_day() {
+        // the 13th, or any Friday
+        assert_eq!(
+            next("0 0 13 * 5", "2022-10-01 00:00:00"),
+            at("2022-10-07 00:00:00")
+        );
+        assert_eq!(
+            next("0 0 13 * 5", "2022-10-07 00:00:00"),
+            at("2022-10-13 00:00:00")
+        );
+        // any Sunday
+        assert_eq!(
+            next("0 0 * * 7", "2022-10-14 00:00:00"),
+            at("2022-10-16 00:00:00")
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        for expression in [
+            "* * * *",
+            "60 * * * *",
+            "* * 0 * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+        ] {
+            assert!(
+                expression.parse::<CronSchedule>().is_err(),
+                "{}",
+                expression
+            );
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     io::{Error, ErrorKind},
     path::PathBuf,
     process::ExitStatus,
@@ -10,6 +11,7 @@ use codecs::{
     decoding::{DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
+use derivative::Derivative;
 use futures::StreamExt;
 use smallvec::SmallVec;
 use snafu::Snafu;
@@ -17,11 +19,12 @@ use tokio::{
     io::{AsyncRead, BufReader},
     process::Command,
     sync::mpsc::{channel, Sender},
+    task::JoinHandle,
     time::{self, sleep, Duration, Instant},
 };
-use tokio_stream::wrappers::IntervalStream;
 use tokio_util::codec::FramedRead;
 use vector_common::internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol};
+use vector_common::TimeZone;
 use vector_config::{configurable_component, NamedComponent};
 use vector_core::ByteSizeOf;
 
@@ -31,7 +34,8 @@ use crate::{
     event::Event,
     internal_events::{
         ExecChannelClosedError, ExecCommandExecuted, ExecEventsReceived, ExecFailedError,
-        ExecFailedToSignalChild, ExecFailedToSignalChildError, ExecTimeoutError, StreamClosedError,
+        ExecFailedToSignalChild, ExecFailedToSignalChildError, ExecRespawnsExhausted,
+        ExecTimeoutError, StreamClosedError,
     },
     serde::default_decoding,
     shutdown::ShutdownSignal,
@@ -40,8 +44,11 @@ use crate::{
 use lookup::event_path;
 use vector_core::config::LogNamespace;
 
+mod cron;
 pub mod sized_bytes_codec;
 
+use self::cron::CronSchedule;
+
 /// Configuration for the `exec` source.
 #[configurable_component(source("exec"))]
 #[derive(Clone, Debug)]
@@ -62,6 +69,15 @@ pub struct ExecConfig {
     /// The directory in which to run the command.
     pub working_directory: Option<PathBuf>,
 
+    /// Custom environment variables to set or override when running the command.
+    ///
+    /// If a variable name already exists in the environment, its value is replaced.
+    pub environment: Option<HashMap<String, String>>,
+
+    /// Whether or not to clear the environment before setting the custom environment variables.
+    #[serde(default = "crate::serde::default_false")]
+    pub clear_environment: bool,
+
     /// Whether or not the output from stderr should be included when generating events.
     #[serde(default = "default_include_stderr")]
     pub include_stderr: bool,
@@ -97,9 +113,46 @@ pub enum Mode {
 pub struct ScheduledConfig {
     /// The interval, in seconds, between scheduled command runs.
     ///
-    /// If the command takes longer than `exec_interval_secs` to run, it will be killed.
+    /// If the command takes longer than `exec_interval_secs` to run, it will be killed, unless
+    /// another `overlap_policy` is set.
     #[serde(default = "default_exec_interval_secs")]
     exec_interval_secs: u64,
+
+    /// A cron expression the command is run on, instead of every `exec_interval_secs`.
+    ///
+    /// The five standard fields are supported, such as `*/5 * * * *`, as well as `@hourly`,
+    /// `@daily`, `@weekly`, `@monthly`, and `@yearly`.
+    cron: Option<String>,
+
+    /// The name of the time zone the cron expression is evaluated in.
+    ///
+    /// This overrides the [global `timezone`][global_timezone] option. The time zone name may be
+    /// any name in the [TZ database][tz_database], or `local` to indicate system local time.
+    ///
+    /// [global_timezone]: https://vector.dev/docs/reference/configuration//global-options#timezone
+    /// [tz_database]: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones
+    timezone: Option<TimeZone>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    overlap_policy: OverlapPolicy,
+}
+
+/// What happens when a scheduled run is due while the previous one is still running.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlapPolicy {
+    /// The command is killed when the next run is due.
+    #[derivative(Default)]
+    Replace,
+
+    /// The next run is skipped while the command is still running.
+    Skip,
+
+    /// The runs are allowed to overlap.
+    Allow,
 }
 
 /// Configuration options for streaming commands.
@@ -114,6 +167,20 @@ pub struct StreamingConfig {
     /// The amount of time, in seconds, that Vector will wait before rerunning a streaming command that exited.
     #[serde(default = "default_respawn_interval_secs")]
     respawn_interval_secs: u64,
+
+    /// The maximum amount of time, in seconds, that Vector will wait before rerunning a command that keeps exiting.
+    ///
+    /// When set, the wait doubles on every consecutive exit, starting from `respawn_interval_secs`, up to this maximum.
+    max_respawn_interval_secs: Option<u64>,
+
+    /// The maximum number of consecutive reruns, after which a command that keeps exiting is no longer rerun.
+    max_respawns: Option<u32>,
+
+    /// The amount of time, in seconds, a command must run for before it is considered healthy again.
+    ///
+    /// The exit of a healthy command resets the wait and the number of consecutive reruns.
+    #[serde(default = "default_respawn_reset_secs")]
+    respawn_reset_secs: u64,
 }
 
 #[derive(Debug, PartialEq, Eq, Snafu)]
@@ -122,6 +189,8 @@ pub enum ExecConfigError {
     CommandEmpty,
     #[snafu(display("The maximum buffer size must be greater than zero"))]
     ZeroBuffer,
+    #[snafu(display("Invalid cron expression {:?}: {}", expression, reason))]
+    InvalidCron { expression: String, reason: String },
 }
 
 impl Default for ExecConfig {
@@ -130,10 +199,15 @@ impl Default for ExecConfig {
             mode: Mode::Scheduled,
             scheduled: Some(ScheduledConfig {
                 exec_interval_secs: default_exec_interval_secs(),
+                cron: None,
+                timezone: None,
+                overlap_policy: OverlapPolicy::default(),
             }),
             streaming: None,
             command: vec!["echo".to_owned(), "Hello World!".to_owned()],
             working_directory: None,
+            environment: None,
+            clear_environment: false,
             include_stderr: default_include_stderr(),
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
             framing: None,
@@ -155,6 +229,10 @@ const fn default_respawn_interval_secs() -> u64 {
     5
 }
 
+const fn default_respawn_reset_secs() -> u64 {
+    60
+}
+
 const fn default_respawn_on_exit() -> bool {
     true
 }
@@ -182,7 +260,58 @@ impl ExecConfig {
         } else if self.maximum_buffer_size_bytes == 0 {
             Err(ExecConfigError::ZeroBuffer)
         } else {
-            Ok(())
+            self.cron_schedule().map(|_| ())
+        }
+    }
+
+    fn cron_schedule(&self) -> Result<Option<CronSchedule>, ExecConfigError> {
+        self.scheduled
+            .as_ref()
+            .and_then(|config| config.cron.as_ref())
+            .map(|expression| {
+                expression
+                    .parse()
+                    .map_err(|reason| ExecConfigError::InvalidCron {
+                        expression: expression.clone(),
+                        reason,
+                    })
+            })
+            .transpose()
+    }
+
+    fn schedule(&self, global_timezone: TimeZone) -> Result<Schedule, ExecConfigError> {
+        let timezone = self
+            .scheduled
+            .as_ref()
+            .and_then(|config| config.timezone)
+            .unwrap_or(global_timezone);
+        Ok(match self.cron_schedule()? {
+            Some(cron) => Schedule::Cron(Box::new(cron), timezone),
+            None => Schedule::Interval(Duration::from_secs(self.exec_interval_secs_or_default())),
+        })
+    }
+
+    fn overlap_policy_or_default(&self) -> OverlapPolicy {
+        self.scheduled
+            .as_ref()
+            .map(|config| config.overlap_policy)
+            .unwrap_or_default()
+    }
+
+    fn respawn_policy(&self) -> RespawnPolicy {
+        match &self.streaming {
+            None => RespawnPolicy {
+                interval: Duration::from_secs(default_respawn_interval_secs()),
+                max_interval: None,
+                max_respawns: None,
+                reset_after: Duration::from_secs(default_respawn_reset_secs()),
+            },
+            Some(config) => RespawnPolicy {
+                interval: Duration::from_secs(config.respawn_interval_secs),
+                max_interval: config.max_respawn_interval_secs.map(Duration::from_secs),
+                max_respawns: config.max_respawns,
+                reset_after: Duration::from_secs(config.respawn_reset_secs),
+            },
         }
     }
 
@@ -203,11 +332,55 @@ impl ExecConfig {
             Some(config) => config.respawn_on_exit,
         }
     }
+}
 
-    const fn respawn_interval_secs_or_default(&self) -> u64 {
-        match &self.streaming {
-            None => default_respawn_interval_secs(),
-            Some(config) => config.respawn_interval_secs,
+/// When scheduled commands are run.
+enum Schedule {
+    Interval(Duration),
+    Cron(Box<CronSchedule>, TimeZone),
+}
+
+impl Schedule {
+    fn first(&self) -> Option<Instant> {
+        match self {
+            Self::Interval(_) => Some(Instant::now()),
+            Self::Cron(..) => self.next(Instant::now()),
+        }
+    }
+
+    /// The run following the one due at the given instant.
+    fn next(&self, due: Instant) -> Option<Instant> {
+        match self {
+            Self::Interval(interval) => Some(due + *interval),
+            Self::Cron(schedule, timezone) => {
+                let now = Utc::now();
+                // Look past the current minute, as the clocks may disagree slightly on when it
+                // ends.
+                let next = schedule.next_run(*timezone, now + chrono::Duration::seconds(1))?;
+                Some(Instant::now() + (next - now).to_std().unwrap_or_default())
+            }
+        }
+    }
+}
+
+/// How streaming commands are rerun when they exit.
+struct RespawnPolicy {
+    interval: Duration,
+    max_interval: Option<Duration>,
+    max_respawns: Option<u32>,
+    reset_after: Duration,
+}
+
+impl RespawnPolicy {
+    /// The wait before the given consecutive rerun, starting from zero.
+    fn interval(&self, respawns: u32) -> Duration {
+        match self.max_interval {
+            Some(max_interval) => self
+                .interval
+                .checked_mul(2_u32.saturating_pow(respawns))
+                .unwrap_or(max_interval)
+                .min(max_interval),
+            None => self.interval,
         }
     }
 }
@@ -227,12 +400,14 @@ impl SourceConfig for ExecConfig {
 
         match &self.mode {
             Mode::Scheduled => {
-                let exec_interval_secs = self.exec_interval_secs_or_default();
+                let schedule = self.schedule(cx.globals.timezone)?;
+                let overlap_policy = self.overlap_policy_or_default();
 
                 Ok(Box::pin(run_scheduled(
                     self.clone(),
                     hostname,
-                    exec_interval_secs,
+                    schedule,
+                    overlap_policy,
                     decoder,
                     cx.shutdown,
                     cx.out,
@@ -240,13 +415,13 @@ impl SourceConfig for ExecConfig {
             }
             Mode::Streaming => {
                 let respawn_on_exit = self.respawn_on_exit_or_default();
-                let respawn_interval_secs = self.respawn_interval_secs_or_default();
+                let respawn_policy = self.respawn_policy();
 
                 Ok(Box::pin(run_streaming(
                     self.clone(),
                     hostname,
                     respawn_on_exit,
-                    respawn_interval_secs,
+                    respawn_policy,
                     decoder,
                     cx.shutdown,
                     cx.out,
@@ -267,47 +442,76 @@ impl SourceConfig for ExecConfig {
 async fn run_scheduled(
     config: ExecConfig,
     hostname: Option<String>,
-    exec_interval_secs: u64,
+    schedule: Schedule,
+    overlap_policy: OverlapPolicy,
     decoder: Decoder,
-    shutdown: ShutdownSignal,
+    mut shutdown: ShutdownSignal,
     out: SourceSender,
 ) -> Result<(), ()> {
     debug!("Starting scheduled exec runs.");
-    let schedule = Duration::from_secs(exec_interval_secs);
+    let mut runs: Vec<JoinHandle<()>> = Vec::new();
+    let mut next_run = schedule.first();
 
-    let mut interval = IntervalStream::new(time::interval(schedule)).take_until(shutdown.clone());
-
-    while interval.next().await.is_some() {
-        // Wait for our task to finish, wrapping it in a timeout
-        let timeout = tokio::time::timeout(
-            schedule,
-            run_command(
-                config.clone(),
-                hostname.clone(),
-                decoder.clone(),
-                shutdown.clone(),
-                out.clone(),
-            ),
-        )
-        .await;
+    while let Some(due) = next_run {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            _ = time::sleep_until(due) => {}
+        }
+        next_run = schedule.next(due);
+
+        runs.retain(|run| !run.is_finished());
+        if overlap_policy == OverlapPolicy::Skip && !runs.is_empty() {
+            warn!(
+                message = "Skipping scheduled run, the previous one is still running.",
+                command = %config.command_line(),
+                internal_log_rate_limit = true,
+            );
+            continue;
+        }
 
-        match timeout {
-            Ok(output) => {
-                if let Err(command_error) = output {
-                    emit!(ExecFailedError {
-                        command: config.command_line().as_str(),
-                        error: command_error,
-                    });
-                }
+        // The command is killed when the next run is due, unless runs may overlap
+        let timeout = match (overlap_policy, next_run) {
+            (OverlapPolicy::Replace, Some(next_run)) => {
+                Some(next_run.saturating_duration_since(Instant::now()))
             }
-            Err(error) => {
-                emit!(ExecTimeoutError {
-                    command: config.command_line().as_str(),
-                    elapsed_seconds: schedule.as_secs(),
-                    error,
+            _ => None,
+        };
+        let command = config.command_line();
+        let run = run_command(
+            config.clone(),
+            hostname.clone(),
+            decoder.clone(),
+            shutdown.clone(),
+            out.clone(),
+        );
+        runs.push(tokio::spawn(async move {
+            let output = match timeout {
+                Some(timeout) => match time::timeout(timeout, run).await {
+                    Ok(output) => output,
+                    Err(error) => {
+                        emit!(ExecTimeoutError {
+                            command: command.as_str(),
+                            elapsed_seconds: timeout.as_secs(),
+                            error,
+                        });
+                        return;
+                    }
+                },
+                None => run.await,
+            };
+
+            if let Err(command_error) = output {
+                emit!(ExecFailedError {
+                    command: command.as_str(),
+                    error: command_error,
                 });
             }
-        }
+        }));
+    }
+
+    // Let the running commands shut down
+    for run in runs {
+        let _ = run.await;
     }
 
     debug!("Finished scheduled exec runs.");
@@ -318,16 +522,17 @@ async fn run_streaming(
     config: ExecConfig,
     hostname: Option<String>,
     respawn_on_exit: bool,
-    respawn_interval_secs: u64,
+    respawn_policy: RespawnPolicy,
     decoder: Decoder,
     mut shutdown: ShutdownSignal,
     out: SourceSender,
 ) -> Result<(), ()> {
     if respawn_on_exit {
-        let duration = Duration::from_secs(respawn_interval_secs);
+        let mut respawns = 0;
 
         // Continue to loop while not shutdown
         loop {
+            let start = Instant::now();
             let output = run_command(
                 config.clone(),
                 hostname.clone(),
@@ -345,10 +550,25 @@ async fn run_streaming(
                 });
             }
 
+            if start.elapsed() >= respawn_policy.reset_after {
+                respawns = 0;
+            }
+            if respawn_policy
+                .max_respawns
+                .map_or(false, |max_respawns| respawns >= max_respawns)
+            {
+                emit!(ExecRespawnsExhausted {
+                    command: config.command_line().as_str(),
+                    respawns,
+                });
+                break;
+            }
+
             tokio::select! {
                 _ = &mut shutdown => break, // will break early if a shutdown is started
-                _ = sleep(duration) => debug!("Restarting streaming process."),
+                _ = sleep(respawn_policy.interval(respawns)) => debug!("Restarting streaming process."),
             }
+            respawns += 1;
         }
     } else {
         let output = run_command(config.clone(), hostname, decoder, shutdown, out).await;
@@ -546,6 +766,14 @@ fn build_command(config: &ExecConfig) -> Command {
         command.current_dir(current_dir);
     }
 
+    if config.clear_environment {
+        command.env_clear();
+    }
+
+    if let Some(envs) = &config.environment {
+        command.envs(envs);
+    }
+
     // Pipe our stdout to the process
     command.stdout(std::process::Stdio::piped());
 
@@ -696,9 +924,14 @@ mod tests {
             streaming: Some(StreamingConfig {
                 respawn_on_exit: default_respawn_on_exit(),
                 respawn_interval_secs: default_respawn_interval_secs(),
+                max_respawn_interval_secs: None,
+                max_respawns: None,
+                respawn_reset_secs: default_respawn_reset_secs(),
             }),
             command: vec!["./runner".to_owned(), "arg1".to_owned(), "arg2".to_owned()],
             working_directory: Some(PathBuf::from("/tmp")),
+            environment: None,
+            clear_environment: false,
             include_stderr: default_include_stderr(),
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
             framing: None,
@@ -719,6 +952,33 @@ mod tests {
         assert_eq!(expected_command_string, command_string);
     }
 
+    #[test]
+    fn test_invalid_cron() {
+        let mut config = standard_scheduled_test_config();
+        config.scheduled.as_mut().unwrap().cron = Some("*/0 * * * *".to_owned());
+
+        assert!(matches!(
+            config.validate(),
+            Err(ExecConfigError::InvalidCron { .. })
+        ));
+    }
+
+    #[test]
+    fn test_respawn_backoff() {
+        let mut config = standard_streaming_test_config();
+        config.streaming.as_mut().unwrap().max_respawn_interval_secs = Some(20);
+        let policy = config.respawn_policy();
+
+        let intervals = (0..4)
+            .map(|respawns| policy.interval(respawns).as_secs())
+            .collect::<Vec<_>>();
+        assert_eq!(intervals, vec![5, 10, 20, 20]);
+        assert_eq!(policy.interval(u32::MAX).as_secs(), 20);
+
+        let policy = standard_streaming_test_config().respawn_policy();
+        assert_eq!(policy.interval(3).as_secs(), 5);
+    }
+
     #[tokio::test]
     async fn test_spawn_reader_thread() {
         trace_init();
@@ -781,6 +1041,29 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_run_command_environment() {
+        let mut config = standard_scheduled_test_config();
+        config.command = vec![
+            "/bin/sh".to_owned(),
+            "-c".to_owned(),
+            "echo \"$GREETING $HOME\"".to_owned(),
+        ];
+        config.environment = Some(HashMap::from([("GREETING".to_owned(), "Hello".to_owned())]));
+        config.clear_environment = true;
+        let (tx, mut rx) = SourceSender::new_test();
+
+        let exit_status = run_command(config, None, Default::default(), ShutdownSignal::noop(), tx)
+            .await
+            .expect("command error");
+        assert_eq!(0_i32, exit_status.unwrap().code().unwrap());
+
+        let event = rx.next().await.expect("Expected to receive an event");
+        let log = event.as_log();
+        assert_eq!(log[log_schema().message_key()], "Hello ".into());
+    }
+
     #[tokio::test]
     #[cfg(unix)]
     async fn test_run_command_linux() {
@@ -883,9 +1166,14 @@ mod tests {
             streaming: Some(StreamingConfig {
                 respawn_on_exit: default_respawn_on_exit(),
                 respawn_interval_secs: default_respawn_interval_secs(),
+                max_respawn_interval_secs: None,
+                max_respawns: None,
+                respawn_reset_secs: default_respawn_reset_secs(),
             }),
             command: vec!["yes".to_owned()],
             working_directory: None,
+            environment: None,
+            clear_environment: false,
             include_stderr: default_include_stderr(),
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
             framing: None,
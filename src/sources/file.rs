@@ -3,7 +3,7 @@ use std::{convert::TryInto, path::PathBuf, time::Duration};
 use bytes::Bytes;
 use chrono::Utc;
 use file_source::{
-    paths_provider::glob::{Glob, MatchOptions},
+    paths_provider::glob::{Glob, IgnoreRule, MatchOptions, PatternError, SymlinkPolicy},
     Checkpointer, FileFingerprint, FileServer, FingerprintStrategy, Fingerprinter, Line, ReadFrom,
 };
 use futures::{FutureExt, Stream, StreamExt, TryFutureExt};
@@ -57,6 +57,8 @@ enum BuildError {
         indicator: String,
         source: regex::Error,
     },
+    #[snafu(display("ignore rule {:?} is not a valid pattern: {}", rule, source))]
+    InvalidIgnoreRule { rule: String, source: PatternError },
 }
 
 /// Configuration for the `file` source.
@@ -72,6 +74,24 @@ pub struct FileConfig {
     /// Takes precedence over the `include` option.
     pub exclude: Vec<PathBuf>,
 
+    /// Array of gitignore-style rules applied, in order, to the files matched by the `include` option.
+    ///
+    /// Rules are matched against the path of a file relative to the directory before the first
+    /// wildcard of the `include` pattern which matched it. A rule starting with `!` includes again
+    /// the files excluded by the preceding rules, a rule ending with `/` excludes everything within
+    /// the matching directories, and a rule without any other `/` matches the name of the file or of
+    /// any of its parent directories.
+    pub ignore_rules: Vec<String>,
+
+    #[configurable(derived)]
+    pub symlinks: SymlinksConfig,
+
+    /// The maximum depth of the files read below the directory before the first wildcard of the
+    /// `include` pattern which matched them.
+    ///
+    /// A depth of `1` only reads the files directly within that directory.
+    pub max_depth: Option<usize>,
+
     /// Overrides the name of the log field used to add the file path to each event.
     ///
     /// The value will be the full path to the file where the event was read message.
@@ -214,6 +234,36 @@ pub enum FingerprintConfig {
     DevInode,
 }
 
+/// Which symbolic links are followed when looking for files.
+#[configurable_component]
+#[derive(Copy, Clone, Debug, Derivative, PartialEq, Eq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SymlinksConfig {
+    /// Follow symbolic links to both files and directories.
+    #[derivative(Default)]
+    Follow,
+
+    /// Follow symbolic links to files, but don't look for files within symbolic links to directories.
+    FilesOnly,
+
+    /// Don't follow any symbolic link.
+    ///
+    /// Symbolic links within the directory before the first wildcard of the `include` pattern are
+    /// still followed.
+    Never,
+}
+
+impl From<SymlinksConfig> for SymlinkPolicy {
+    fn from(config: SymlinksConfig) -> Self {
+        match config {
+            SymlinksConfig::Follow => SymlinkPolicy::Follow,
+            SymlinksConfig::FilesOnly => SymlinkPolicy::FilesOnly,
+            SymlinksConfig::Never => SymlinkPolicy::Never,
+        }
+    }
+}
+
 /// File position to use when reading a new file.
 #[configurable_component]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -280,6 +330,9 @@ impl Default for FileConfig {
         Self {
             include: vec![],
             exclude: vec![],
+            ignore_rules: vec![],
+            symlinks: SymlinksConfig::default(),
+            max_depth: None,
             file_key: Some("file".to_string()),
             start_at_beginning: None,
             ignore_checkpoints: None,
@@ -334,6 +387,10 @@ impl SourceConfig for FileConfig {
                 Regex::new(indicator)
                     .with_context(|_| InvalidMessageStartIndicatorSnafu { indicator })?;
             }
+
+            for rule in &self.ignore_rules {
+                IgnoreRule::new(rule).with_context(|_| InvalidIgnoreRuleSnafu { rule })?;
+            }
         }
 
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
@@ -373,13 +430,21 @@ pub fn file_source(
         config.read_from,
     );
 
+    let ignore_rules = config
+        .ignore_rules
+        .iter()
+        .map(|rule| IgnoreRule::new(rule).unwrap()) // validated in build
+        .collect();
     let paths_provider = Glob::new(
         &config.include,
         &config.exclude,
         MatchOptions::default(),
         FileSourceInternalEventsEmitter,
     )
-    .expect("invalid glob patterns");
+    .expect("invalid glob patterns")
+    .with_ignore_rules(ignore_rules)
+    .with_symlink_policy(config.symlinks.into())
+    .with_max_depth(config.max_depth);
 
     let encoding_charset = config.encoding.clone().map(|e| e.charset);
 
@@ -1477,9 +1542,10 @@ mod tests {
         let config = file::FileConfig {
             include: vec![dir.path().join("*")],
             multiline: Some(MultilineConfig {
-                start_pattern: "INFO".to_owned(),
-                condition_pattern: "INFO".to_owned(),
-                mode: line_agg::Mode::HaltBefore,
+                preset: None,
+                start_pattern: Some("INFO".to_owned()),
+                condition_pattern: Some("INFO".to_owned()),
+                mode: Some(line_agg::Mode::HaltBefore),
                 timeout_ms: 25, // less than 50 in sleep()
             }),
             ..test_default_file_config(&dir)
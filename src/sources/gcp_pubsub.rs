@@ -1,7 +1,7 @@
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::{
-    error::Error as _, future::Future, pin::Pin, sync::Arc, task::Context, task::Poll,
-    time::Duration,
+    collections::HashMap, error::Error as _, future::Future, pin::Pin, sync::Arc, task::Context,
+    task::Poll, time::Duration,
 };
 
 use chrono::{DateTime, NaiveDateTime, Utc};
@@ -11,7 +11,10 @@ use futures::{stream, stream::FuturesUnordered, FutureExt, Stream, StreamExt, Tr
 use http::uri::{InvalidUri, Scheme, Uri};
 use once_cell::sync::Lazy;
 use snafu::{ResultExt, Snafu};
-use tokio::sync::{mpsc, watch};
+use tokio::{
+    sync::{mpsc, watch},
+    time::Instant,
+};
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{
     metadata::{errors::InvalidMetadataValue, MetadataValue},
@@ -52,7 +55,7 @@ const MAX_ACK_DEADLINE_SECS: i32 = 600;
 // processing.
 const ACK_QUEUE_SIZE: usize = 8;
 
-type Finalizer = UnorderedFinalizer<Vec<String>>;
+type Finalizer = UnorderedFinalizer<u64>;
 
 // prost emits some generated code that includes clones on `Arc`
 // objects, which causes a clippy ding on this block. We don't
@@ -158,6 +161,28 @@ pub struct PubsubConfig {
     /// Deprecated, old name of `ack_deadline_secs`.
     pub ack_deadline_seconds: Option<i32>,
 
+    /// The maximum amount of time, in seconds, to extend the acknowledgement deadline of messages
+    /// for, while their events are being delivered.
+    ///
+    /// With acknowledgements enabled, the deadline of messages is extended until their events are
+    /// delivered, so that slow sinks don't cause messages to be retransmitted. Set to `0` to disable.
+    #[serde(default = "default_max_ack_extension")]
+    #[derivative(Default(value = "default_max_ack_extension()"))]
+    pub max_ack_extension_secs: u64,
+
+    /// The maximum number of messages to receive on a stream, which haven't been acknowledged yet.
+    ///
+    /// The server stops sending messages on a stream once this many messages are outstanding. By
+    /// default, there is no limit.
+    pub max_outstanding_messages: Option<i64>,
+
+    /// The maximum size, in bytes, of the messages to receive on a stream, which haven't been
+    /// acknowledged yet.
+    ///
+    /// The server stops sending messages on a stream once this many bytes are outstanding. By
+    /// default, there is no limit.
+    pub max_outstanding_bytes: Option<i64>,
+
     /// The amount of time, in seconds, to wait between retry attempts after an error.
     pub retry_delay_secs: Option<f64>,
 
@@ -189,6 +214,10 @@ const fn default_ack_deadline() -> i32 {
     600
 }
 
+const fn default_max_ack_extension() -> u64 {
+    3600
+}
+
 const fn default_retry_delay() -> f64 {
     1.0
 }
@@ -281,6 +310,9 @@ impl SourceConfig for PubsubConfig {
             shutdown: cx.shutdown,
             out: cx.out,
             ack_deadline_secs,
+            max_ack_extension: Duration::from_secs(self.max_ack_extension_secs),
+            max_outstanding_messages: self.max_outstanding_messages.unwrap_or_default(),
+            max_outstanding_bytes: self.max_outstanding_bytes.unwrap_or_default(),
             retry_delay: Duration::from_secs_f64(retry_delay_secs),
             keepalive: Duration::from_secs_f64(self.keepalive_secs),
             concurrency: Default::default(),
@@ -315,6 +347,9 @@ struct PubsubSource {
     decoder: Decoder,
     acknowledgements: bool,
     ack_deadline_secs: i32,
+    max_ack_extension: Duration,
+    max_outstanding_messages: i64,
+    max_outstanding_bytes: i64,
     shutdown: ShutdownSignal,
     out: SourceSender,
     retry_delay: Duration,
@@ -326,6 +361,57 @@ struct PubsubSource {
     full_response_size: usize,
 }
 
+/// An update of acknowledgements, sent on the request stream.
+#[derive(Debug, Default)]
+struct AckUpdate {
+    /// The messages to acknowledge.
+    ack_ids: Vec<String>,
+    /// The messages to extend the acknowledgement deadline of.
+    extend_ids: Vec<String>,
+}
+
+impl AckUpdate {
+    const fn ack(ack_ids: Vec<String>) -> Self {
+        Self {
+            ack_ids,
+            extend_ids: Vec::new(),
+        }
+    }
+}
+
+/// The batches of a stream, which haven't been finalized yet.
+#[derive(Default)]
+struct PendingAcks {
+    next_id: u64,
+    batches: HashMap<u64, (Instant, Vec<String>)>,
+}
+
+impl PendingAcks {
+    fn insert(&mut self, ack_ids: Vec<String>) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.batches.insert(id, (Instant::now(), ack_ids));
+        id
+    }
+
+    fn remove(&mut self, id: u64) -> Option<Vec<String>> {
+        self.batches.remove(&id).map(|(_, ack_ids)| ack_ids)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.batches.is_empty()
+    }
+
+    /// The messages, whose deadline hasn't been extended for longer than the maximum yet.
+    fn extendable(&self, max_extension: Duration) -> Vec<String> {
+        self.batches
+            .values()
+            .filter(|(received, _)| received.elapsed() < max_extension)
+            .flat_map(|(_, ack_ids)| ack_ids.iter().cloned())
+            .collect()
+    }
+}
+
 enum State {
     RetryNow,
     RetryDelay,
@@ -441,7 +527,12 @@ impl PubsubSource {
 
         let (finalizer, mut ack_stream) =
             Finalizer::maybe_new(self.acknowledgements, self.shutdown.clone());
-        let mut pending_acks = 0;
+        let mut pending_acks = PendingAcks::default();
+
+        // Deadlines are extended well before they expire, so that the extension arrives in time.
+        let extend_period = Duration::from_secs(self.ack_deadline_secs as u64 / 2);
+        let mut extend_deadlines =
+            tokio::time::interval_at(Instant::now() + extend_period, extend_period);
 
         let protocol = self
             .uri
@@ -453,11 +544,23 @@ impl PubsubSource {
         loop {
             tokio::select! {
                 biased;
-                receipts = ack_stream.next() => if let Some((status, receipts)) = receipts {
-                    pending_acks -= 1;
+                receipts = ack_stream.next() => if let Some((status, batch)) = receipts {
+                    let receipts = pending_acks.remove(batch).unwrap_or_default();
                     if status == BatchStatus::Delivered {
                         ack_ids_sender
-                            .send(receipts)
+                            .send(AckUpdate::ack(receipts))
+                            .await
+                            .unwrap_or_else(|_| unreachable!("request stream never closes"));
+                    }
+                },
+                _ = extend_deadlines.tick(),
+                    if !pending_acks.is_empty() && !self.max_ack_extension.is_zero() =>
+                {
+                    let extend_ids = pending_acks.extendable(self.max_ack_extension);
+                    if !extend_ids.is_empty() {
+                        debug!(message = "Extending acknowledgement deadlines.", count = extend_ids.len());
+                        ack_ids_sender
+                            .send(AckUpdate { extend_ids, ..Default::default() })
                             .await
                             .unwrap_or_else(|_| unreachable!("request stream never closes"));
                     }
@@ -476,13 +579,13 @@ impl PubsubSource {
                     Some(Err(error)) => break translate_error(error),
                     None => break State::RetryNow,
                 },
-                _ = &mut self.shutdown, if pending_acks == 0 => return State::Shutdown,
+                _ = &mut self.shutdown, if pending_acks.is_empty() => return State::Shutdown,
                 _ = self.token_generator.changed() => {
                     debug!("New authentication token generated, restarting stream.");
                     break State::RetryNow;
                 },
                 _ = tokio::time::sleep(self.keepalive) => {
-                    if pending_acks == 0 {
+                    if pending_acks.is_empty() {
                         // No pending acks, and no new data, so drop
                         // this stream if we aren't the only active
                         // one.
@@ -501,7 +604,7 @@ impl PubsubSource {
                     // in a new request with empty fields, effectively
                     // a keepalive.
                     ack_ids_sender
-                        .send(Vec::new())
+                        .send(AckUpdate::default())
                         .await
                         .unwrap_or_else(|_| unreachable!("request stream never closes"));
                 }
@@ -511,11 +614,13 @@ impl PubsubSource {
 
     fn request_stream(
         &self,
-        ack_ids: mpsc::Receiver<Vec<String>>,
+        ack_ids: mpsc::Receiver<AckUpdate>,
     ) -> impl Stream<Item = proto::StreamingPullRequest> + 'static {
         let subscription = self.subscription.clone();
         let client_id = CLIENT_ID.clone();
         let stream_ack_deadline_seconds = self.ack_deadline_secs;
+        let max_outstanding_messages = self.max_outstanding_messages;
+        let max_outstanding_bytes = self.max_outstanding_bytes;
         let ack_ids = ReceiverStream::new(ack_ids).ready_chunks(ACK_QUEUE_SIZE);
 
         stream::once(async move {
@@ -525,28 +630,20 @@ impl PubsubSource {
                 subscription,
                 client_id,
                 stream_ack_deadline_seconds,
+                max_outstanding_messages,
+                max_outstanding_bytes,
                 ..Default::default()
             }
         })
-        .chain(ack_ids.map(|chunks| {
-            // These "requests" serve only to send updates about
-            // acknowledgements to the server. None of the above
-            // fields need to be repeated and, in fact, will cause
-            // an stream error and cancellation if they are
-            // present.
-            proto::StreamingPullRequest {
-                ack_ids: chunks.into_iter().flatten().collect(),
-                ..Default::default()
-            }
-        }))
+        .chain(ack_ids.map(move |chunks| ack_request(chunks, stream_ack_deadline_seconds)))
     }
 
     async fn handle_response(
         &mut self,
         response: proto::StreamingPullResponse,
         finalizer: &Option<Finalizer>,
-        ack_ids: &mpsc::Sender<Vec<String>>,
-        pending_acks: &mut usize,
+        ack_ids: &mpsc::Sender<AckUpdate>,
+        pending_acks: &mut PendingAcks,
         busy_flag: &Arc<AtomicBool>,
         bytes_received: &Registered<BytesReceived>,
     ) {
@@ -563,15 +660,14 @@ impl PubsubSource {
             Err(error) => emit!(StreamClosedError { error, count }),
             Ok(()) => match notifier {
                 None => ack_ids
-                    .send(ids)
+                    .send(AckUpdate::ack(ids))
                     .await
                     .unwrap_or_else(|_| unreachable!("request stream never closes")),
                 Some(notifier) => {
                     finalizer
                         .as_ref()
                         .expect("Finalizer must have been set up for acknowledgements")
-                        .add(ids, notifier);
-                    *pending_acks += 1;
+                        .add(pending_acks.insert(ids), notifier);
                 }
             },
         }
@@ -624,12 +720,31 @@ impl PubsubSource {
             if let Some(log) = event.maybe_as_log_mut() {
                 log.insert("message_id", message.message_id.clone());
                 log.insert("attributes", attributes.clone());
+                if !message.ordering_key.is_empty() {
+                    log.insert("ordering_key", message.ordering_key.clone());
+                }
             }
             event
         })
     }
 }
 
+/// Merges acknowledgement updates into a request.
+///
+/// These "requests" serve only to send updates about acknowledgements to the server. None of the
+/// fields of the initial request need to be repeated and, in fact, will cause a stream error and
+/// cancellation if they are present.
+fn ack_request(updates: Vec<AckUpdate>, ack_deadline_secs: i32) -> proto::StreamingPullRequest {
+    let mut request = proto::StreamingPullRequest::default();
+    for update in updates {
+        request.ack_ids.extend(update.ack_ids);
+        request.modify_deadline_ack_ids.extend(update.extend_ids);
+    }
+    request.modify_deadline_seconds =
+        vec![ack_deadline_secs; request.modify_deadline_ack_ids.len()];
+    request
+}
+
 fn translate_error(error: tonic::Status) -> State {
     // GCP occasionally issues a connection reset
     // in the middle of the streaming pull. This
@@ -677,6 +792,45 @@ mod tests {
     fn generate_config() {
         crate::test_util::test_generate_config::<PubsubConfig>();
     }
+
+    #[test]
+    fn merges_ack_updates() {
+        let request = ack_request(
+            vec![
+                AckUpdate::ack(vec!["a".into(), "b".into()]),
+                AckUpdate {
+                    ack_ids: vec!["c".into()],
+                    extend_ids: vec!["d".into(), "e".into()],
+                },
+                AckUpdate::default(),
+            ],
+            60,
+        );
+        assert_eq!(request.ack_ids, ["a", "b", "c"]);
+        assert_eq!(request.modify_deadline_ack_ids, ["d", "e"]);
+        assert_eq!(request.modify_deadline_seconds, [60, 60]);
+        assert!(request.subscription.is_empty());
+        assert_eq!(request.stream_ack_deadline_seconds, 0);
+    }
+
+    #[test]
+    fn extends_pending_acks() {
+        let mut pending = PendingAcks::default();
+        let first = pending.insert(vec!["a".into()]);
+        let second = pending.insert(vec!["b".into(), "c".into()]);
+        assert_ne!(first, second);
+
+        let mut extendable = pending.extendable(Duration::from_secs(60));
+        extendable.sort();
+        assert_eq!(extendable, ["a", "b", "c"]);
+        assert!(pending.extendable(Duration::ZERO).is_empty());
+
+        assert_eq!(pending.remove(first), Some(vec!["a".into()]));
+        assert_eq!(pending.remove(first), None);
+        assert!(!pending.is_empty());
+        pending.remove(second);
+        assert!(pending.is_empty());
+    }
 }
 
 #[cfg(all(test, feature = "gcp-integration-tests"))]
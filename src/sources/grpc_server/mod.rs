@@ -0,0 +1,321 @@
+use std::{convert::Infallible, net::SocketAddr, path::PathBuf};
+
+use futures::FutureExt;
+use hyper::{server::accept, service::make_service_fn, Server};
+use prost_reflect::{DescriptorPool, Kind};
+use snafu::{OptionExt, ResultExt, Snafu};
+use tower::Layer;
+use tracing::{Instrument, Span};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{
+        AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource, SourceConfig,
+        SourceContext,
+    },
+    serde::bool_or_struct,
+    sources::{util::grpc::DecompressionAndMetricsLayer, Source},
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+mod service;
+
+use self::service::DynamicService;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Could not read descriptor set {:?}: {}", path, source))]
+    ReadDescriptorSet {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Invalid descriptor set {:?}: {}", path, source))]
+    DecodeDescriptorSet {
+        path: PathBuf,
+        source: prost_reflect::DescriptorError,
+    },
+    #[snafu(display("Service {:?} not found in the descriptor set", service))]
+    UnknownService { service: String },
+    #[snafu(display(
+        "Field {:?} is not a repeated message field of the requests of {:?}",
+        field,
+        method
+    ))]
+    InvalidEventsField { field: String, method: String },
+}
+
+/// Configuration for the `grpc_server` source.
+#[configurable_component(source("grpc_server"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcServerConfig {
+    /// The address to listen for connections on.
+    ///
+    /// It _must_ include a port.
+    address: SocketAddr,
+
+    /// The path to the file descriptor set, with the service and the messages it's using.
+    ///
+    /// The file descriptor set can be generated from the `.proto` files with
+    /// `protoc --include_imports --descriptor_set_out=<path>`.
+    descriptor_set_path: PathBuf,
+
+    /// The fully qualified name of the service to expose, such as `acme.ingest.v1.IngestService`.
+    ///
+    /// Each unary method of the service is accepted, with its request message decoded into an
+    /// event. The methods respond with an empty response message, once the events are delivered.
+    service: String,
+
+    /// The name of a repeated message field of the requests, whose elements are each decoded into
+    /// an event.
+    ///
+    /// The other fields of the requests are added to each of the events. By default, each request
+    /// is decoded into a single event.
+    events_field: Option<String>,
+
+    /// The key to add the name of the method that was called to.
+    #[serde(default = "default_method_key")]
+    method_key: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_method_key() -> String {
+    "method".to_owned()
+}
+
+impl GenerateConfig for GrpcServerConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:50051".parse().unwrap(),
+            descriptor_set_path: "/etc/vector/ingest.desc".into(),
+            service: "acme.ingest.v1.IngestService".to_owned(),
+            events_field: None,
+            method_key: default_method_key(),
+            tls: None,
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for GrpcServerConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
+        let bytes =
+            tokio::fs::read(&self.descriptor_set_path)
+                .await
+                .context(ReadDescriptorSetSnafu {
+                    path: &self.descriptor_set_path,
+                })?;
+        let pool = DescriptorPool::decode(bytes.as_slice()).context(DecodeDescriptorSetSnafu {
+            path: &self.descriptor_set_path,
+        })?;
+        let service = pool
+            .get_service_by_name(&self.service)
+            .context(UnknownServiceSnafu {
+                service: &self.service,
+            })?;
+
+        if let Some(field) = &self.events_field {
+            for method in service.methods() {
+                let is_message_list =
+                    method
+                        .input()
+                        .get_field_by_name(field)
+                        .map_or(false, |descriptor| {
+                            descriptor.is_list() && matches!(descriptor.kind(), Kind::Message(_))
+                        });
+                if !is_message_list {
+                    return Err(BuildError::InvalidEventsField {
+                        field: field.clone(),
+                        method: method.full_name().to_owned(),
+                    }
+                    .into());
+                }
+            }
+        }
+
+        let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
+        let service = DecompressionAndMetricsLayer::default().layer(DynamicService::new(
+            service,
+            self.events_field.clone(),
+            self.method_key.clone(),
+            cx.out,
+            cx.do_acknowledgements(&self.acknowledgements),
+        ));
+        let address = self.address;
+        let shutdown = cx.shutdown;
+
+        Ok(Box::pin(async move {
+            let span = Span::current();
+            let listener = tls_settings.bind(&address).await.map_err(|error| {
+                error!(message = "Failed to bind gRPC server.", %error);
+            })?;
+
+            info!(message = "Building gRPC server.", address = %address);
+
+            Server::builder(accept::from_stream(listener.accept_stream()))
+                .http2_only(true)
+                .serve(make_service_fn(move |_| {
+                    let service = service.clone();
+                    async move { Ok::<_, Infallible>(service) }
+                }))
+                .with_graceful_shutdown(shutdown.map(|_| ()))
+                .instrument(span)
+                .await
+                .map_err(|error| {
+                    error!(message = "Source future failed.", %error);
+                })
+        }))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::uri::PathAndQuery;
+    use prost_reflect::{DynamicMessage, Value as ProtoValue};
+    use tonic::transport::Channel;
+
+    use super::{service::DynamicCodec, *};
+    use crate::{
+        config::log_schema,
+        test_util::{
+            self,
+            components::{assert_source_compliance, SOURCE_TAGS},
+        },
+        SourceSender,
+    };
+
+    fn descriptor_set_path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/protobuf/ingest.desc")
+    }
+
+    fn config(address: SocketAddr, events_field: Option<&str>) -> GrpcServerConfig {
+        GrpcServerConfig {
+            address,
+            descriptor_set_path: descriptor_set_path(),
+            service: "test.ingest.IngestService".to_owned(),
+            events_field: events_field.map(Into::into),
+            method_key: default_method_key(),
+            tls: None,
+            acknowledgements: Default::default(),
+        }
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GrpcServerConfig>();
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_service() {
+        let mut config = config(test_util::next_addr(), None);
+        config.service = "test.ingest.UnknownService".to_owned();
+        let (tx, _rx) = SourceSender::new_test();
+
+        let error = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("UnknownService"));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_events_field() {
+        let config = config(test_util::next_addr(), Some("host"));
+        let (tx, _rx) = SourceSender::new_test();
+
+        assert!(config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn receives_request_entries() {
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let address = test_util::next_addr();
+            let (tx, rx) = SourceSender::new_test();
+            let source = config(address, Some("entries"))
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            test_util::wait_for_tcp(address).await;
+
+            let pool =
+                DescriptorPool::decode(std::fs::read(descriptor_set_path()).unwrap().as_slice())
+                    .unwrap();
+            let request_descriptor = pool
+                .get_message_by_name("test.ingest.IngestRequest")
+                .unwrap();
+            let entry_descriptor = pool.get_message_by_name("test.ingest.LogEntry").unwrap();
+            let response_descriptor = pool
+                .get_message_by_name("test.ingest.IngestResponse")
+                .unwrap();
+
+            let entries = [("first", 0), ("second", 2)]
+                .into_iter()
+                .map(|(message, level)| {
+                    let mut entry = DynamicMessage::new(entry_descriptor.clone());
+                    entry.set_field_by_name("message", ProtoValue::String(message.to_owned()));
+                    entry.set_field_by_name("level", ProtoValue::EnumNumber(level));
+                    ProtoValue::Message(entry)
+                })
+                .collect();
+            let mut request = DynamicMessage::new(request_descriptor);
+            request.set_field_by_name("host", ProtoValue::String("web-1".to_owned()));
+            request.set_field_by_name("entries", ProtoValue::List(entries));
+
+            let channel = Channel::from_shared(format!("http://{}", address))
+                .unwrap()
+                .connect()
+                .await
+                .unwrap();
+            let mut client = tonic::client::Grpc::new(channel);
+            client.ready().await.unwrap();
+            client
+                .unary(
+                    tonic::Request::new(request),
+                    PathAndQuery::from_static("/test.ingest.IngestService/Ingest"),
+                    DynamicCodec(response_descriptor),
+                )
+                .await
+                .unwrap();
+
+            let events = test_util::collect_n(rx, 2).await;
+            let first = events[0].as_log();
+            assert_eq!(first["host"], "web-1".into());
+            assert_eq!(first["message"], "first".into());
+            // Enum values set to their default aren't set.
+            assert!(first.get("level").is_none());
+            assert_eq!(first["method"], "Ingest".into());
+            assert_eq!(first[log_schema().source_type_key()], "grpc_server".into());
+            let second = events[1].as_log();
+            assert_eq!(second["message"], "second".into());
+            assert_eq!(second["level"], "ERROR".into());
+        })
+        .await;
+    }
+}
@@ -0,0 +1,298 @@
+//! A gRPC service whose methods are described at runtime, by a descriptor set.
+
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use chrono::Utc;
+use futures::{future::BoxFuture, FutureExt};
+use http::{Request, Response};
+use hyper::Body;
+use prost::Message;
+use prost_reflect::{
+    DynamicMessage, FieldDescriptor, MapKey, MessageDescriptor, MethodDescriptor, ServiceDescriptor,
+};
+use tonic::{
+    body::BoxBody,
+    codec::{Codec, CompressionEncoding, DecodeBuf, Decoder, EncodeBuf, Encoder},
+    server::Grpc,
+    Status,
+};
+use vector_core::{
+    config::log_schema,
+    event::{BatchNotifier, BatchStatus, Event, LogEvent, Value},
+    ByteSizeOf,
+};
+
+use crate::{
+    internal_events::{EventsReceived, StreamClosedError},
+    SourceSender,
+};
+
+#[derive(Clone)]
+pub struct DynamicService {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    service: ServiceDescriptor,
+    events_field: Option<String>,
+    method_key: String,
+    out: SourceSender,
+    acknowledgements: bool,
+}
+
+impl DynamicService {
+    pub fn new(
+        service: ServiceDescriptor,
+        events_field: Option<String>,
+        method_key: String,
+        out: SourceSender,
+        acknowledgements: bool,
+    ) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                service,
+                events_field,
+                method_key,
+                out,
+                acknowledgements,
+            }),
+        }
+    }
+}
+
+impl tower::Service<Request<Body>> for DynamicService {
+    type Response = Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let inner = Arc::clone(&self.inner);
+        async move {
+            // Paths are made of the full name of the service, and the name of the method.
+            let method = request
+                .uri()
+                .path()
+                .strip_prefix('/')
+                .and_then(|path| path.split_once('/'))
+                .filter(|(service, _)| *service == inner.service.full_name())
+                .and_then(|(_, method)| {
+                    inner
+                        .service
+                        .methods()
+                        .find(|descriptor| descriptor.name() == method)
+                })
+                .filter(|method| !method.is_client_streaming() && !method.is_server_streaming());
+            let method = match method {
+                Some(method) => method,
+                None => {
+                    return Ok(Status::unimplemented(format!(
+                        "Unknown or streaming method {:?}",
+                        request.uri().path()
+                    ))
+                    .to_http())
+                }
+            };
+
+            let mut grpc = Grpc::new(DynamicCodec(method.input()))
+                .accept_compressed(CompressionEncoding::Gzip);
+            let handler = tower::service_fn(move |request: tonic::Request<DynamicMessage>| {
+                let inner = Arc::clone(&inner);
+                let method = method.clone();
+                async move { inner.handle(&method, request.into_inner()).await }
+            });
+            Ok(grpc.unary(handler, request).await)
+        }
+        .boxed()
+    }
+}
+
+impl Inner {
+    async fn handle(
+        &self,
+        method: &MethodDescriptor,
+        message: DynamicMessage,
+    ) -> Result<tonic::Response<DynamicMessage>, Status> {
+        let mut events = self
+            .decode_events(method, message)
+            .map_err(Status::invalid_argument)?;
+
+        let count = events.len();
+        emit!(EventsReceived {
+            count,
+            byte_size: events.size_of(),
+        });
+
+        let receiver = BatchNotifier::maybe_apply_to(self.acknowledgements, &mut events);
+        self.out.clone().send_batch(events).await.map_err(|error| {
+            let message = error.to_string();
+            emit!(StreamClosedError { error, count });
+            Status::unavailable(message)
+        })?;
+
+        let status = match receiver {
+            Some(receiver) => receiver.await,
+            None => BatchStatus::Delivered,
+        };
+        match status {
+            BatchStatus::Errored => Err(Status::internal("Delivery error")),
+            BatchStatus::Rejected => Err(Status::data_loss("Delivery failed")),
+            BatchStatus::Delivered => {
+                Ok(tonic::Response::new(DynamicMessage::new(method.output())))
+            }
+        }
+    }
+
+    fn decode_events(
+        &self,
+        method: &MethodDescriptor,
+        message: DynamicMessage,
+    ) -> Result<Vec<Event>, String> {
+        let mut fields = match message_to_value(&message)? {
+            Value::Object(fields) => fields,
+            _ => unreachable!("messages are converted to objects"),
+        };
+
+        let entries = match &self.events_field {
+            Some(field) => match fields.remove(field) {
+                Some(Value::Array(entries)) => entries,
+                // Repeated fields without elements aren't set.
+                _ => Vec::new(),
+            },
+            None => vec![Value::Object(BTreeMap::new())],
+        };
+
+        let now = Utc::now();
+        Ok(entries
+            .into_iter()
+            .map(|entry| {
+                let mut event_fields = fields.clone();
+                if let Value::Object(entry) = entry {
+                    event_fields.extend(entry);
+                }
+                let mut log = LogEvent::from(event_fields);
+                if !self.method_key.is_empty() {
+                    log.try_insert(self.method_key.as_str(), method.name());
+                }
+                log.try_insert(log_schema().source_type_key(), Bytes::from("grpc_server"));
+                log.try_insert(log_schema().timestamp_key(), now);
+                Event::from(log)
+            })
+            .collect())
+    }
+}
+
+/// Converts a decoded message to an object, omitting fields that aren't set, which in proto3
+/// includes fields set to their default value.
+fn message_to_value(message: &DynamicMessage) -> Result<Value, String> {
+    message
+        .fields()
+        .map(|(field, value)| Ok((field.name().to_owned(), proto_to_value(value, &field)?)))
+        .collect::<Result<BTreeMap<_, _>, String>>()
+        .map(Value::from)
+}
+
+fn proto_to_value(value: &prost_reflect::Value, field: &FieldDescriptor) -> Result<Value, String> {
+    Ok(match value {
+        prost_reflect::Value::Bool(v) => Value::from(*v),
+        prost_reflect::Value::I32(v) => Value::from(*v),
+        prost_reflect::Value::I64(v) => Value::from(*v),
+        prost_reflect::Value::U32(v) => Value::from(*v),
+        prost_reflect::Value::U64(v) => {
+            Value::from(i64::try_from(*v).map_err(|_| format!("Integer out of range: {}", v))?)
+        }
+        prost_reflect::Value::F32(v) => Value::from_f64_or_zero(f64::from(*v)),
+        prost_reflect::Value::F64(v) => Value::from_f64_or_zero(*v),
+        prost_reflect::Value::String(v) => Value::from(v.as_str()),
+        prost_reflect::Value::Bytes(v) => Value::from(v.clone()),
+        // enums are converted to the name of their value, unless the number is unknown
+        prost_reflect::Value::EnumNumber(v) => field
+            .kind()
+            .as_enum()
+            .and_then(|descriptor| descriptor.get_value(*v))
+            .map_or_else(
+                || Value::from(*v),
+                |enum_value| Value::from(enum_value.name()),
+            ),
+        prost_reflect::Value::Message(v) => message_to_value(v)?,
+        prost_reflect::Value::List(v) => v
+            .iter()
+            .map(|value| proto_to_value(value, field))
+            .collect::<Result<Vec<_>, String>>()?
+            .into(),
+        prost_reflect::Value::Map(v) => {
+            let value_field = field
+                .kind()
+                .as_message()
+                .map(MessageDescriptor::map_entry_value_field)
+                .ok_or("Map field without map entry type")?;
+            v.iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        MapKey::Bool(k) => k.to_string(),
+                        MapKey::I32(k) => k.to_string(),
+                        MapKey::I64(k) => k.to_string(),
+                        MapKey::U32(k) => k.to_string(),
+                        MapKey::U64(k) => k.to_string(),
+                        MapKey::String(k) => k.clone(),
+                    };
+                    Ok((key, proto_to_value(value, &value_field)?))
+                })
+                .collect::<Result<BTreeMap<_, _>, String>>()?
+                .into()
+        }
+    })
+}
+
+/// Encodes messages, and decodes them with the descriptor of the messages received.
+#[derive(Clone)]
+pub struct DynamicCodec(pub MessageDescriptor);
+
+impl Codec for DynamicCodec {
+    type Encode = DynamicMessage;
+    type Decode = DynamicMessage;
+    type Encoder = DynamicEncoder;
+    type Decoder = DynamicDecoder;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        DynamicEncoder
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        DynamicDecoder(self.0.clone())
+    }
+}
+
+pub struct DynamicEncoder;
+
+impl Encoder for DynamicEncoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        item.encode(dst)
+            .map_err(|error| Status::internal(error.to_string()))
+    }
+}
+
+pub struct DynamicDecoder(MessageDescriptor);
+
+impl Decoder for DynamicDecoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        DynamicMessage::decode(self.0.clone(), src)
+            .map(Some)
+            .map_err(|error| Status::invalid_argument(error.to_string()))
+    }
+}
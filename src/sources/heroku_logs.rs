@@ -331,7 +331,10 @@ mod tests {
         let len = body.lines().count();
         let mut req = reqwest::Client::new().post(&format!("http://{}/events?{}", address, query));
         if let Some(auth) = auth {
-            req = req.basic_auth(auth.username, Some(auth.password.inner()));
+            req = req.basic_auth(
+                auth.username.unwrap(),
+                auth.password.as_ref().map(|password| password.inner()),
+            );
         }
         req.header("Logplex-Msg-Count", len)
             .header("Logplex-Frame-Id", "frame-foo")
@@ -346,8 +349,9 @@ mod tests {
 
     fn make_auth() -> HttpSourceAuthConfig {
         HttpSourceAuthConfig {
-            username: random_string(16),
-            password: random_string(16).into(),
+            username: Some(random_string(16)),
+            password: Some(random_string(16).into()),
+            ..Default::default()
         }
     }
 
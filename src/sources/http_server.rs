@@ -926,6 +926,39 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn http_zstd() {
+        let mut events = assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
+            let body = zstd::stream::encode_all("test body".as_bytes(), 0).unwrap();
+
+            let mut headers = HeaderMap::new();
+            headers.insert("Content-Encoding", "zstd".parse().unwrap());
+
+            let (rx, addr) = source(
+                vec![],
+                vec![],
+                "http_path",
+                "/",
+                "POST",
+                true,
+                EventStatus::Delivered,
+                true,
+                None,
+                None,
+            )
+            .await;
+
+            spawn_ok_collect_n(send_bytes(addr, body, headers), rx, 1).await
+        })
+        .await;
+
+        let event = events.remove(0);
+        assert_eq!(
+            event.as_log()[log_schema().message_key()],
+            "test body".into()
+        );
+    }
+
     #[tokio::test]
     async fn http_path() {
         let mut events = assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
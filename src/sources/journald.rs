@@ -13,6 +13,7 @@ use bytes::Bytes;
 use chrono::TimeZone;
 use codecs::{decoding::BoxedFramingError, CharacterDelimitedDecoder};
 use futures::{poll, stream::BoxStream, task::Poll, StreamExt};
+use glob::Pattern;
 use nix::{
     sys::signal::{kill, Signal},
     unistd::Pid,
@@ -80,10 +81,25 @@ enum BuildError {
         value,
     ))]
     DuplicatedMatches { field: String, value: String },
+    #[snafu(display("The unit {:?} is not a valid glob: {}", unit, source))]
+    InvalidUnitGlob {
+        unit: String,
+        source: glob::PatternError,
+    },
+    #[snafu(display(
+        "The priority {:?} is not a syslog level nor a range of syslog levels",
+        priority
+    ))]
+    InvalidPriority { priority: String },
 }
 
 type Matches = HashMap<String, HashSet<String>>;
 
+/// The syslog levels, from the most to the least important.
+const PRIORITIES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
 /// Configuration for the `journald` source.
 #[configurable_component(source("journald"))]
 #[derive(Clone, Debug, Default)]
@@ -92,6 +108,14 @@ pub struct JournaldConfig {
     /// Only include entries that appended to the journal after Vector starts reading it.
     pub since_now: Option<bool>,
 
+    #[configurable(derived)]
+    pub start_from: Option<StartFrom>,
+
+    /// Only include entries of this syslog level or more important, or of a range of levels such as `warning..err`.
+    ///
+    /// Levels are either names, such as `err`, or numbers from `0` to `7`.
+    pub priority: Option<String>,
+
     /// Only include entries that occurred after the current boot of the system.
     pub current_boot_only: Option<bool>,
 
@@ -105,11 +129,15 @@ pub struct JournaldConfig {
     /// A list of unit names to monitor.
     ///
     /// If empty or not present, all units are accepted. Unit names lacking a "." will have ".service" appended to make them a valid service unit name.
+    ///
+    /// Unit names containing `*`, `?` or `[` are globs, such as `nginx-*`.
     pub include_units: Vec<String>,
 
     /// A list of unit names to exclude from monitoring.
     ///
     /// Unit names lacking a "." will have ".service" appended to make them a valid service unit name.
+    ///
+    /// Unit names containing `*`, `?` or `[` are globs, such as `nginx-*`.
     pub exclude_units: Vec<String>,
 
     /// A list of sets of field/value pairs to monitor.
@@ -152,32 +180,76 @@ pub struct JournaldConfig {
     remap_priority: bool,
 }
 
+/// Where to start reading the journal when no checkpoint was saved yet.
+///
+/// Overrides `since_now` when set.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "position", rename_all = "snake_case")]
+pub enum StartFrom {
+    /// Read from the oldest entry of the journal.
+    Head,
+
+    /// Only read the entries appended after Vector starts reading the journal.
+    Tail,
+
+    /// Read the entries after the entry with the given cursor.
+    Cursor {
+        /// The cursor of the entry, such as one saved by `journalctl --cursor-file`.
+        cursor: String,
+    },
+}
+
 impl JournaldConfig {
-    fn merged_include_matches(&self) -> crate::Result<Matches> {
-        let include_units = match (!self.units.is_empty(), !self.include_units.is_empty()) {
-            (true, true) => return Err(BuildError::BothUnitsAndIncludeUnits.into()),
-            (true, false) => {
-                warn!("The `units` setting is deprecated, use `include_units` instead.");
-                &self.units
-            }
-            (false, _) => &self.include_units,
-        };
+    fn include_units(&self) -> crate::Result<&[String]> {
+        match (!self.units.is_empty(), !self.include_units.is_empty()) {
+            (true, true) => Err(BuildError::BothUnitsAndIncludeUnits.into()),
+            (true, false) => Ok(&self.units),
+            (false, _) => Ok(&self.include_units),
+        }
+    }
 
-        Ok(Self::merge_units(&self.include_matches, include_units))
+    fn merged_include_matches(&self) -> crate::Result<Matches> {
+        Ok(Self::merge_units(
+            &self.include_matches,
+            self.include_units()?,
+        ))
     }
 
     fn merged_exclude_matches(&self) -> Matches {
         Self::merge_units(&self.exclude_matches, &self.exclude_units)
     }
 
+    /// Units which are globs are matched separately, see `unit_globs`.
     fn merge_units(matches: &Matches, units: &[String]) -> Matches {
         let mut matches = matches.clone();
-        for unit in units {
+        for unit in units.iter().filter(|unit| !is_glob(unit)) {
             let entry = matches.entry(String::from(SYSTEMD_UNIT));
             entry.or_default().insert(fixup_unit(unit));
         }
         matches
     }
+
+    fn unit_globs(units: &[String]) -> crate::Result<Vec<Pattern>> {
+        units
+            .iter()
+            .filter(|unit| is_glob(unit))
+            .map(|unit| {
+                Pattern::new(&fixup_unit(unit))
+                    .with_context(|_| InvalidUnitGlobSnafu { unit })
+                    .map_err(Into::into)
+            })
+            .collect()
+    }
+
+    fn start_from(&self) -> StartFrom {
+        self.start_from
+            .clone()
+            .unwrap_or(match self.since_now.unwrap_or(false) {
+                true => StartFrom::Tail,
+                false => StartFrom::Head,
+            })
+    }
 }
 
 impl_generate_config_from_default!(JournaldConfig);
@@ -190,6 +262,9 @@ impl SourceConfig for JournaldConfig {
         if self.remap_priority {
             warn!("Option `remap_priority` has been deprecated. Please use the `remap` transform and function `to_syslog_level` instead.");
         }
+        if !self.units.is_empty() {
+            warn!("The `units` setting is deprecated, use `include_units` instead.");
+        }
 
         let data_dir = cx
             .globals
@@ -212,6 +287,25 @@ impl SourceConfig for JournaldConfig {
             return Err(BuildError::DuplicatedMatches { field, value }.into());
         }
 
+        let includes = Filter {
+            matches: include_matches,
+            unit_globs: Self::unit_globs(self.include_units()?)?,
+        };
+        let excludes = Filter {
+            matches: exclude_matches,
+            unit_globs: Self::unit_globs(&self.exclude_units)?,
+        };
+
+        let priority = self
+            .priority
+            .as_deref()
+            .map(|priority| {
+                parse_priority(priority).ok_or_else(|| BuildError::InvalidPriority {
+                    priority: priority.to_owned(),
+                })
+            })
+            .transpose()?;
+
         let mut checkpoint_path = data_dir;
         checkpoint_path.push(CHECKPOINT_FILENAME);
 
@@ -224,7 +318,8 @@ impl SourceConfig for JournaldConfig {
             journalctl_path,
             self.journal_directory.clone(),
             self.current_boot_only.unwrap_or(true),
-            self.since_now.unwrap_or(false),
+            self.start_from(),
+            priority,
         );
 
         let batch_size = self.batch_size.unwrap_or(DEFAULT_BATCH_SIZE);
@@ -232,8 +327,8 @@ impl SourceConfig for JournaldConfig {
 
         Ok(Box::pin(
             JournaldSource {
-                include_matches,
-                exclude_matches,
+                includes,
+                excludes,
                 checkpoint_path,
                 batch_size,
                 remap_priority: self.remap_priority,
@@ -255,8 +350,8 @@ impl SourceConfig for JournaldConfig {
 }
 
 struct JournaldSource {
-    include_matches: Matches,
-    exclude_matches: Matches,
+    includes: Filter,
+    excludes: Filter,
     checkpoint_path: PathBuf,
     batch_size: usize,
     remap_priority: bool,
@@ -413,11 +508,7 @@ impl<'a> Batch<'a> {
                             self.cursor = Some(tmp);
                         }
 
-                        if !filter_matches(
-                            &record,
-                            &self.source.include_matches,
-                            &self.source.exclude_matches,
-                        ) {
+                        if !filter_matches(&record, &self.source.includes, &self.source.excludes) {
                             self.record_size += bytes.len();
                             let event = create_event(record, &self.batch);
                             self.events.push(event);
@@ -476,7 +567,8 @@ struct StartJournalctl {
     path: PathBuf,
     journal_dir: Option<PathBuf>,
     current_boot_only: bool,
-    since_now: bool,
+    start_from: StartFrom,
+    priority: Option<String>,
 }
 
 impl StartJournalctl {
@@ -484,13 +576,15 @@ impl StartJournalctl {
         path: PathBuf,
         journal_dir: Option<PathBuf>,
         current_boot_only: bool,
-        since_now: bool,
+        start_from: StartFrom,
+        priority: Option<String>,
     ) -> Self {
         Self {
             path,
             journal_dir,
             current_boot_only,
-            since_now,
+            start_from,
+            priority,
         }
     }
 
@@ -510,13 +604,21 @@ impl StartJournalctl {
             command.arg("--boot");
         }
 
-        if let Some(cursor) = checkpoint {
-            command.arg(format!("--after-cursor={}", cursor));
-        } else if self.since_now {
-            command.arg("--since=now");
-        } else {
-            // journalctl --follow only outputs a few lines without a starting point
-            command.arg("--since=2000-01-01");
+        if let Some(priority) = &self.priority {
+            command.arg(format!("--priority={}", priority));
+        }
+
+        match (checkpoint, &self.start_from) {
+            (Some(cursor), _) | (None, StartFrom::Cursor { cursor }) => {
+                command.arg(format!("--after-cursor={}", cursor));
+            }
+            (None, StartFrom::Tail) => {
+                command.arg("--since=now");
+            }
+            (None, StartFrom::Head) => {
+                // journalctl --follow only outputs a few lines without a starting point
+                command.arg("--since=2000-01-01");
+            }
         }
 
         command
@@ -579,6 +681,26 @@ fn create_event(record: Record, batch: &Option<BatchNotifier>) -> LogEvent {
     log
 }
 
+fn is_glob(unit: &str) -> bool {
+    unit.contains(['*', '?', '['])
+}
+
+/// Parses a syslog level, or a range of levels, into the numbers `journalctl` accepts.
+fn parse_priority(priority: &str) -> Option<String> {
+    let level = |level: &str| {
+        let level = level.trim().to_lowercase();
+        PRIORITIES
+            .iter()
+            .position(|name| *name == level)
+            .or_else(|| level.parse().ok().filter(|level| *level < PRIORITIES.len()))
+    };
+
+    match priority.split_once("..") {
+        Some((from, to)) => Some(format!("{}..{}", level(from)?, level(to)?)),
+        None => level(priority).map(|level| level.to_string()),
+    }
+}
+
 /// Map the given unit name into a valid systemd unit
 /// by appending ".service" if no extension is present.
 fn fixup_unit(unit: &str) -> String {
@@ -644,12 +766,41 @@ fn remap_priority(priority: &mut JsonValue) {
     }
 }
 
-fn filter_matches(record: &Record, includes: &Matches, excludes: &Matches) -> bool {
+/// The field/value pairs and the unit globs to include or exclude.
+#[derive(Debug, Default)]
+struct Filter {
+    matches: Matches,
+    unit_globs: Vec<Pattern>,
+}
+
+impl Filter {
+    fn is_empty(&self) -> bool {
+        self.matches.is_empty() && self.unit_globs.is_empty()
+    }
+
+    fn contains(&self, record: &Record) -> bool {
+        contains_match(record, &self.matches)
+            || record.get(SYSTEMD_UNIT).map_or(false, |unit| {
+                self.unit_globs.iter().any(|glob| glob.matches(unit))
+            })
+    }
+}
+
+impl From<Matches> for Filter {
+    fn from(matches: Matches) -> Self {
+        Self {
+            matches,
+            unit_globs: Vec::new(),
+        }
+    }
+}
+
+fn filter_matches(record: &Record, includes: &Filter, excludes: &Filter) -> bool {
     match (includes.is_empty(), excludes.is_empty()) {
         (true, true) => false,
-        (false, true) => !contains_match(record, includes),
-        (true, false) => contains_match(record, excludes),
-        (false, false) => !contains_match(record, includes) || contains_match(record, excludes),
+        (false, true) => !includes.contains(record),
+        (true, false) => excludes.contains(record),
+        (false, false) => !includes.contains(record) || excludes.contains(record),
     }
 }
 
@@ -1109,9 +1260,9 @@ mod tests {
 
     #[test]
     fn filter_matches_works_correctly() {
-        let empty: Matches = HashMap::new();
-        let includes = create_unit_matches(vec!["one", "two"]);
-        let excludes = create_unit_matches(vec!["foo", "bar"]);
+        let empty = Filter::default();
+        let includes: Filter = create_unit_matches(vec!["one", "two"]).into();
+        let excludes: Filter = create_unit_matches(vec!["foo", "bar"]).into();
 
         let zero = HashMap::new();
         assert!(!filter_matches(&zero, &empty, &empty));
@@ -1132,6 +1283,47 @@ mod tests {
         assert!(filter_matches(&two, &includes, &excludes));
     }
 
+    #[test]
+    fn filter_matches_unit_globs() {
+        let config = JournaldConfig {
+            include_units: vec!["app-*".into(), "sshd".into()],
+            exclude_units: vec!["app-debug*".into()],
+            ..Default::default()
+        };
+        let includes = Filter {
+            matches: config.merged_include_matches().unwrap(),
+            unit_globs: JournaldConfig::unit_globs(&config.include_units).unwrap(),
+        };
+        let excludes = Filter {
+            matches: config.merged_exclude_matches(),
+            unit_globs: JournaldConfig::unit_globs(&config.exclude_units).unwrap(),
+        };
+
+        let unit = |unit: &str| Record::from([(String::from(SYSTEMD_UNIT), String::from(unit))]);
+        assert!(!filter_matches(
+            &unit("app-web.service"),
+            &includes,
+            &excludes
+        ));
+        assert!(!filter_matches(&unit("sshd.service"), &includes, &excludes));
+        assert!(filter_matches(
+            &unit("app-debug.service"),
+            &includes,
+            &excludes
+        ));
+        assert!(filter_matches(&unit("cron.service"), &includes, &excludes));
+    }
+
+    #[test]
+    fn parses_priorities() {
+        assert_eq!(parse_priority("err").as_deref(), Some("3"));
+        assert_eq!(parse_priority("4").as_deref(), Some("4"));
+        assert_eq!(parse_priority("emerg..WARNING").as_deref(), Some("0..4"));
+        assert_eq!(parse_priority("loud"), None);
+        assert_eq!(parse_priority("8"), None);
+        assert_eq!(parse_priority("err.."), None);
+    }
+
     #[test]
     fn merges_units_and_matches_option() {
         let include_units = vec!["one", "two"].into_iter().map(String::from).collect();
@@ -1202,18 +1394,25 @@ mod tests {
         let journal_dir = None;
         let current_boot_only = false;
         let cursor = None;
-        let since_now = false;
+        let start_from = StartFrom::Head;
 
-        let command = create_command(&path, journal_dir, current_boot_only, since_now, cursor);
+        let command = create_command(&path, journal_dir, current_boot_only, start_from, cursor);
         let cmd_line = format!("{:?}", command);
         assert!(!cmd_line.contains("--directory="));
         assert!(!cmd_line.contains("--boot"));
+        assert!(!cmd_line.contains("--priority="));
         assert!(cmd_line.contains("--since=2000-01-01"));
 
-        let since_now = true;
+        let start_from = StartFrom::Tail;
         let journal_dir = None;
 
-        let command = create_command(&path, journal_dir, current_boot_only, since_now, cursor);
+        let command = create_command(
+            &path,
+            journal_dir,
+            current_boot_only,
+            start_from.clone(),
+            cursor,
+        );
         let cmd_line = format!("{:?}", command);
         assert!(cmd_line.contains("--since=now"));
 
@@ -1221,22 +1420,53 @@ mod tests {
         let current_boot_only = true;
         let cursor = Some("2021-01-01");
 
-        let command = create_command(&path, journal_dir, current_boot_only, since_now, cursor);
+        let command = create_command(&path, journal_dir, current_boot_only, start_from, cursor);
         let cmd_line = format!("{:?}", command);
         assert!(cmd_line.contains("--directory=/tmp/journal-dir"));
         assert!(cmd_line.contains("--boot"));
-        assert!(cmd_line.contains("--after-cursor="));
+        assert!(cmd_line.contains("--after-cursor=2021-01-01"));
+
+        let start_from = StartFrom::Cursor {
+            cursor: "s=abc".into(),
+        };
+
+        let command = create_command(&path, None, false, start_from.clone(), None);
+        let cmd_line = format!("{:?}", command);
+        assert!(cmd_line.contains("--after-cursor=s=abc"));
+        assert!(!cmd_line.contains("--since="));
+
+        let command = create_command(&path, None, false, start_from, Some("s=def"));
+        let cmd_line = format!("{:?}", command);
+        assert!(cmd_line.contains("--after-cursor=s=def"));
+        assert!(!cmd_line.contains("s=abc"));
+
+        let command = StartJournalctl::new(
+            path,
+            None,
+            false,
+            StartFrom::Head,
+            parse_priority("warning..err"),
+        )
+        .make_command(None);
+        let cmd_line = format!("{:?}", command);
+        assert!(cmd_line.contains("--priority=4..3"));
     }
 
     fn create_command(
         path: &Path,
         journal_dir: Option<PathBuf>,
         current_boot_only: bool,
-        since_now: bool,
+        start_from: StartFrom,
         cursor: Option<&str>,
     ) -> Command {
-        StartJournalctl::new(path.into(), journal_dir, current_boot_only, since_now)
-            .make_command(cursor)
+        StartJournalctl::new(
+            path.into(),
+            journal_dir,
+            current_boot_only,
+            start_from,
+            None,
+        )
+        .make_command(cursor)
     }
 
     fn message(event: &Event) -> Value {
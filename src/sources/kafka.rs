@@ -16,6 +16,7 @@ use rdkafka::{
     config::ClientConfig,
     consumer::{Consumer, StreamConsumer},
     message::{BorrowedMessage, Headers, Message},
+    Timestamp,
 };
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::FramedRead;
@@ -131,6 +132,14 @@ pub struct KafkaSourceConfig {
     #[serde(default = "default_headers_key")]
     headers_key: String,
 
+    /// The name of the log field used to add the timestamp type to each event.
+    ///
+    /// The value will be `create_time` when the timestamp was set by the producer, and
+    /// `log_append_time` when it was set by the broker.
+    ///
+    /// By default, the timestamp type isn't added.
+    timestamp_type_key: Option<String>,
+
     /// Advanced options set directly on the underlying `librdkafka` client.
     ///
     /// See the [librdkafka documentation](https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md) for details.
@@ -366,6 +375,7 @@ struct Keys<'a> {
     partition: &'a str,
     offset: &'a str,
     headers: &'a str,
+    timestamp_type: Option<&'a str>,
 }
 
 impl<'a> Keys<'a> {
@@ -378,12 +388,14 @@ impl<'a> Keys<'a> {
             partition: config.partition_key.as_str(),
             offset: config.offset_key.as_str(),
             headers: config.headers_key.as_str(),
+            timestamp_type: config.timestamp_type_key.as_deref(),
         }
     }
 }
 
 struct ReceivedMessage {
     timestamp: DateTime<Utc>,
+    timestamp_type: Option<&'static str>,
     key: Value,
     headers: BTreeMap<String, Value>,
     topic: String,
@@ -399,6 +411,7 @@ impl ReceivedMessage {
             .to_millis()
             .and_then(|millis| Utc.timestamp_millis_opt(millis).latest())
             .unwrap_or_else(Utc::now);
+        let timestamp_type = timestamp_type_name(msg.timestamp());
 
         let key = msg
             .key()
@@ -407,20 +420,17 @@ impl ReceivedMessage {
 
         let mut headers_map = BTreeMap::new();
         if let Some(headers) = msg.headers() {
-            // Using index-based for loop because rdkafka's `Headers` trait
-            // does not provide Iterator-based API
-            for i in 0..headers.count() {
-                if let Some(header) = headers.get(i) {
-                    headers_map.insert(
-                        header.0.to_string(),
-                        Bytes::from(header.1.to_owned()).into(),
-                    );
-                }
+            for header in headers.iter() {
+                // Headers without a value are kept as empty, like they were before `librdkafka`
+                // told them apart.
+                let value = header.value.unwrap_or_default();
+                headers_map.insert(header.key.to_string(), Bytes::from(value.to_owned()).into());
             }
         }
 
         Self {
             timestamp,
+            timestamp_type,
             key,
             headers: headers_map,
             topic: msg.topic().to_string(),
@@ -438,10 +448,21 @@ impl ReceivedMessage {
             log.insert(keys.partition, Value::from(self.partition));
             log.insert(keys.offset, Value::from(self.offset));
             log.insert(keys.headers, Value::from(self.headers.clone()));
+            if let (Some(key), Some(timestamp_type)) = (keys.timestamp_type, self.timestamp_type) {
+                log.insert(key, timestamp_type);
+            }
         }
     }
 }
 
+const fn timestamp_type_name(timestamp: Timestamp) -> Option<&'static str> {
+    match timestamp {
+        Timestamp::NotAvailable => None,
+        Timestamp::CreateTime(_) => Some("create_time"),
+        Timestamp::LogAppendTime(_) => Some("log_append_time"),
+    }
+}
+
 #[derive(Debug)]
 struct FinalizerEntry {
     topic: String,
@@ -533,6 +554,19 @@ mod test {
         }
     }
 
+    #[test]
+    fn names_timestamp_types() {
+        assert_eq!(timestamp_type_name(Timestamp::NotAvailable), None);
+        assert_eq!(
+            timestamp_type_name(Timestamp::CreateTime(1_666_000_000_000)),
+            Some("create_time")
+        );
+        assert_eq!(
+            timestamp_type_name(Timestamp::LogAppendTime(1_666_000_000_000)),
+            Some("log_append_time")
+        );
+    }
+
     #[tokio::test]
     async fn consumer_create_ok() {
         let config = make_config("topic", "group");
@@ -558,7 +592,7 @@ mod integration_test {
     use rdkafka::{
         config::{ClientConfig, FromClientConfig},
         consumer::BaseConsumer,
-        message::OwnedHeaders,
+        message::{Header, OwnedHeaders},
         producer::{FutureProducer, FutureRecord},
         util::Timeout,
         Offset, TopicPartitionList,
@@ -600,7 +634,10 @@ mod integration_test {
                 .payload(&text)
                 .key(key)
                 .timestamp(timestamp)
-                .headers(OwnedHeaders::new().add(header_key, header_value));
+                .headers(OwnedHeaders::new().insert(Header {
+                    key: header_key,
+                    value: Some(header_value),
+                }));
 
             if let Err(error) = producer.send(record, Timeout::Never).await {
                 panic!("Cannot send event to Kafka: {:?}", error);
@@ -16,6 +16,7 @@ use crate::kubernetes::pod_manager_logic::extract_static_pod_config_hashsum;
 pub struct K8sPathsProvider {
     pod_state: Store<Pod>,
     namespace_state: Store<Namespace>,
+    include_namespaces: Vec<String>,
     exclude_paths: Vec<glob::Pattern>,
 }
 
@@ -24,11 +25,13 @@ impl K8sPathsProvider {
     pub fn new(
         pod_state: Store<Pod>,
         namespace_state: Store<Namespace>,
+        include_namespaces: Vec<String>,
         exclude_paths: Vec<glob::Pattern>,
     ) -> Self {
         Self {
             pod_state,
             namespace_state,
+            include_namespaces,
             exclude_paths,
         }
     }
@@ -47,9 +50,11 @@ impl PathsProvider for K8sPathsProvider {
             .filter(|pod| {
                 trace!(message = "Verifying Namespace metadata for pod.", pod = ?pod.metadata.name);
                 if let Some(namespace) = pod.metadata.namespace.as_ref() {
-                    self.namespace_state
-                        .get(&ObjectRef::<Namespace>::new(namespace))
-                        .is_some()
+                    is_included(&self.include_namespaces, namespace)
+                        && self
+                            .namespace_state
+                            .get(&ObjectRef::<Namespace>::new(namespace))
+                            .is_some()
                 } else {
                     false
                 }
@@ -63,6 +68,11 @@ impl PathsProvider for K8sPathsProvider {
     }
 }
 
+/// Whether the `Pod`s of the namespace are read, which they all are without included namespaces.
+fn is_included(include_namespaces: &[String], namespace: &str) -> bool {
+    include_namespaces.is_empty() || include_namespaces.iter().any(|name| name == namespace)
+}
+
 /// This function takes a `Pod` resource and returns the path to where the logs
 /// for the said `Pod` are expected to be found.
 ///
@@ -93,6 +93,24 @@ pub struct Config {
     /// Specifies the field selector to filter `Pod`s with, to be used in addition to the built-in `Node` filter.
     extra_field_selector: String,
 
+    /// A list of namespaces to read the logs of the `Pod`s from.
+    ///
+    /// If empty, the `Pod`s of all namespaces are read, unless excluded by `exclude_namespaces`.
+    include_namespaces: Vec<String>,
+
+    /// A list of namespaces to not read the logs of the `Pod`s from.
+    exclude_namespaces: Vec<String>,
+
+    /// A list of the annotations of `Pod`s to add to each event.
+    ///
+    /// If not set, all the annotations are added.
+    include_pod_annotations: Option<Vec<String>>,
+
+    /// A list of the labels of the `Node` to add to each event.
+    ///
+    /// If not set, all the labels are added.
+    include_node_labels: Option<Vec<String>>,
+
     /// Whether or not to automatically merge partial events.
     auto_partial_merge: bool,
 
@@ -171,6 +189,10 @@ impl Default for Config {
             extra_namespace_label_selector: "".to_string(),
             self_node_name: default_self_node_name_env_template(),
             extra_field_selector: "".to_string(),
+            include_namespaces: Vec::new(),
+            exclude_namespaces: Vec::new(),
+            include_pod_annotations: None,
+            include_node_labels: None,
             auto_partial_merge: true,
             data_dir: None,
             pod_annotation_fields: pod_metadata_annotator::FieldsSpec::default(),
@@ -217,11 +239,14 @@ struct Source {
     pod_fields_spec: pod_metadata_annotator::FieldsSpec,
     namespace_fields_spec: namespace_metadata_annotator::FieldsSpec,
     node_field_spec: node_metadata_annotator::FieldsSpec,
+    include_pod_annotations: Option<Vec<String>>,
+    include_node_labels: Option<Vec<String>>,
     field_selector: String,
     label_selector: String,
     namespace_label_selector: String,
     node_selector: String,
     self_node_name: String,
+    include_namespaces: Vec<String>,
     exclude_paths: Vec<glob::Pattern>,
     max_read_bytes: usize,
     max_line_bytes: usize,
@@ -294,11 +319,14 @@ impl Source {
             pod_fields_spec: config.pod_annotation_fields.clone(),
             namespace_fields_spec: config.namespace_annotation_fields.clone(),
             node_field_spec: config.node_annotation_fields.clone(),
+            include_pod_annotations: config.include_pod_annotations.clone(),
+            include_node_labels: config.include_node_labels.clone(),
             field_selector,
             label_selector,
             namespace_label_selector,
             node_selector,
             self_node_name,
+            include_namespaces: config.include_namespaces.clone(),
             exclude_paths,
             max_read_bytes: config.max_read_bytes,
             max_line_bytes: config.max_line_bytes,
@@ -321,11 +349,14 @@ impl Source {
             pod_fields_spec,
             namespace_fields_spec,
             node_field_spec,
+            include_pod_annotations,
+            include_node_labels,
             field_selector,
             label_selector,
             namespace_label_selector,
             node_selector,
             self_node_name,
+            include_namespaces,
             exclude_paths,
             max_read_bytes,
             max_line_bytes,
@@ -400,11 +431,17 @@ impl Source {
             delay_deletion,
         )));
 
-        let paths_provider =
-            K8sPathsProvider::new(pod_state.clone(), ns_state.clone(), exclude_paths);
-        let annotator = PodMetadataAnnotator::new(pod_state, pod_fields_spec);
+        let paths_provider = K8sPathsProvider::new(
+            pod_state.clone(),
+            ns_state.clone(),
+            include_namespaces,
+            exclude_paths,
+        );
+        let annotator =
+            PodMetadataAnnotator::new(pod_state, pod_fields_spec, include_pod_annotations);
         let ns_annotator = NamespaceMetadataAnnotator::new(ns_state, namespace_fields_spec);
-        let node_annotator = NodeMetadataAnnotator::new(node_state, node_field_spec);
+        let node_annotator =
+            NodeMetadataAnnotator::new(node_state, node_field_spec, include_node_labels);
 
         // TODO: maybe more of the parameters have to be configurable.
 
@@ -665,7 +702,16 @@ fn prepare_field_selector(config: &Config, self_node_name: &str) -> crate::Resul
         ?self_node_name
     );
 
-    let field_selector = format!("spec.nodeName={}", self_node_name);
+    let mut field_selector = format!("spec.nodeName={}", self_node_name);
+
+    // Field selectors can't select several namespaces, so those are filtered by the paths
+    // provider instead.
+    if let [namespace] = config.include_namespaces.as_slice() {
+        field_selector = format!("{},metadata.namespace={}", field_selector, namespace);
+    }
+    for namespace in &config.exclude_namespaces {
+        field_selector = format!("{},metadata.namespace!={}", field_selector, namespace);
+    }
 
     if config.extra_field_selector.is_empty() {
         return Ok(field_selector);
@@ -773,6 +819,24 @@ mod tests {
                 },
                 "spec.nodeName=qwe,foo=bar",
             ),
+            (
+                Config {
+                    self_node_name: "qwe".to_owned(),
+                    include_namespaces: vec!["ns1".to_owned()],
+                    exclude_namespaces: vec!["ns2".to_owned(), "ns3".to_owned()],
+                    extra_field_selector: "foo=bar".to_owned(),
+                    ..Default::default()
+                },
+                "spec.nodeName=qwe,metadata.namespace=ns1,metadata.namespace!=ns2,metadata.namespace!=ns3,foo=bar",
+            ),
+            (
+                Config {
+                    self_node_name: "qwe".to_owned(),
+                    include_namespaces: vec!["ns1".to_owned(), "ns2".to_owned()],
+                    ..Default::default()
+                },
+                "spec.nodeName=qwe",
+            ),
         ];
 
         for (input, expected) in cases {
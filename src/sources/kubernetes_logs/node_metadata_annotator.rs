@@ -31,14 +31,20 @@ impl Default for FieldsSpec {
 pub struct NodeMetadataAnnotator {
     node_state_reader: Store<Node>,
     fields_spec: FieldsSpec,
+    include_labels: Option<Vec<String>>,
 }
 
 impl NodeMetadataAnnotator {
-    /// Create a new [`NodeMetadataAnnotator`].
-    pub const fn new(node_state_reader: Store<Node>, fields_spec: FieldsSpec) -> Self {
+    /// Create a new [`NodeMetadataAnnotator`], which only adds the given labels if any.
+    pub const fn new(
+        node_state_reader: Store<Node>,
+        fields_spec: FieldsSpec,
+        include_labels: Option<Vec<String>>,
+    ) -> Self {
         Self {
             node_state_reader,
             fields_spec,
+            include_labels,
         }
     }
 }
@@ -52,16 +58,27 @@ impl NodeMetadataAnnotator {
         let resource = self.node_state_reader.get(&obj)?;
         let node: &Node = resource.as_ref();
 
-        annotate_from_metadata(log, &self.fields_spec, &node.metadata);
+        annotate_from_metadata(
+            log,
+            &self.fields_spec,
+            &node.metadata,
+            self.include_labels.as_deref(),
+        );
         Some(())
     }
 }
 
-fn annotate_from_metadata(log: &mut LogEvent, fields_spec: &FieldsSpec, metadata: &ObjectMeta) {
+fn annotate_from_metadata(
+    log: &mut LogEvent,
+    fields_spec: &FieldsSpec,
+    metadata: &ObjectMeta,
+    include_labels: Option<&[String]>,
+) {
     // Calculate and cache the prefix path.
     let prefix_path = parse_value_path(&fields_spec.node_labels);
     if let Some(labels) = &metadata.labels {
-        for (key, val) in labels.iter() {
+        let included = |key: &String| include_labels.map_or(true, |keys| keys.contains(key));
+        for (key, val) in labels.iter().filter(|(key, _)| included(key)) {
             let mut path = prefix_path.clone().segments;
             path.push(OwnedSegment::Field(key.clone()));
             log.insert((PathPrefix::Event, &path), val.to_owned());
@@ -133,8 +150,42 @@ mod tests {
 
         for (fields_spec, metadata, expected) in cases.into_iter() {
             let mut log = LogEvent::default();
-            annotate_from_metadata(&mut log, &fields_spec, &metadata);
+            annotate_from_metadata(&mut log, &fields_spec, &metadata, None);
             assert_event_data_eq!(log, expected);
         }
     }
+
+    #[test]
+    fn test_annotate_from_metadata_with_included_labels() {
+        let metadata = ObjectMeta {
+            labels: Some(
+                vec![
+                    (
+                        "topology.kubernetes.io/zone".to_owned(),
+                        "zone-a".to_owned(),
+                    ),
+                    ("kubernetes.io/hostname".to_owned(), "node-1".to_owned()),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+            ..ObjectMeta::default()
+        };
+        let include_labels = vec!["topology.kubernetes.io/zone".to_owned()];
+
+        let mut log = LogEvent::default();
+        annotate_from_metadata(
+            &mut log,
+            &FieldsSpec::default(),
+            &metadata,
+            Some(include_labels.as_slice()),
+        );
+
+        let mut expected = LogEvent::default();
+        expected.insert(
+            "kubernetes.node_labels.\"topology.kubernetes.io/zone\"",
+            "zone-a",
+        );
+        assert_event_data_eq!(log, expected);
+    }
 }
@@ -79,14 +79,20 @@ impl Default for FieldsSpec {
 pub struct PodMetadataAnnotator {
     pods_state_reader: Store<Pod>,
     fields_spec: FieldsSpec,
+    include_annotations: Option<Vec<String>>,
 }
 
 impl PodMetadataAnnotator {
-    /// Create a new [`PodMetadataAnnotator`].
-    pub const fn new(pods_state_reader: Store<Pod>, fields_spec: FieldsSpec) -> Self {
+    /// Create a new [`PodMetadataAnnotator`], which only adds the given annotations if any.
+    pub const fn new(
+        pods_state_reader: Store<Pod>,
+        fields_spec: FieldsSpec,
+        include_annotations: Option<Vec<String>>,
+    ) -> Self {
         Self {
             pods_state_reader,
             fields_spec,
+            include_annotations,
         }
     }
 }
@@ -103,7 +109,12 @@ impl PodMetadataAnnotator {
         let pod: &Pod = resource.as_ref();
 
         annotate_from_file_info(log, &self.fields_spec, &file_info);
-        annotate_from_metadata(log, &self.fields_spec, &pod.metadata);
+        annotate_from_metadata(
+            log,
+            &self.fields_spec,
+            &pod.metadata,
+            self.include_annotations.as_deref(),
+        );
 
         let container;
         if let Some(ref pod_spec) = pod.spec {
@@ -144,7 +155,12 @@ fn annotate_from_file_info(
     );
 }
 
-fn annotate_from_metadata(log: &mut LogEvent, fields_spec: &FieldsSpec, metadata: &ObjectMeta) {
+fn annotate_from_metadata(
+    log: &mut LogEvent,
+    fields_spec: &FieldsSpec,
+    metadata: &ObjectMeta,
+    include_annotations: Option<&[String]>,
+) {
     for (key, val) in [
         (&fields_spec.pod_name, &metadata.name),
         (&fields_spec.pod_namespace, &metadata.namespace),
@@ -176,7 +192,8 @@ fn annotate_from_metadata(log: &mut LogEvent, fields_spec: &FieldsSpec, metadata
 
     if let Some(annotations) = &metadata.annotations {
         let prefix_path = parse_value_path(&fields_spec.pod_annotations);
-        for (key, val) in annotations.iter() {
+        let included = |key: &String| include_annotations.map_or(true, |keys| keys.contains(key));
+        for (key, val) in annotations.iter().filter(|(key, _)| included(key)) {
             let mut path = prefix_path.clone().segments;
             path.push(OwnedSegment::Field(key.clone()));
             log.insert((PathPrefix::Event, &path), val.to_owned());
@@ -364,7 +381,7 @@ mod tests {
 
         for (fields_spec, metadata, expected) in cases.into_iter() {
             let mut log = LogEvent::default();
-            annotate_from_metadata(&mut log, &fields_spec, &metadata);
+            annotate_from_metadata(&mut log, &fields_spec, &metadata, None);
             assert_event_data_eq!(log, expected);
         }
     }
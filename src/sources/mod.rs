@@ -9,10 +9,14 @@ pub mod apache_metrics;
 pub mod aws_ecs_metrics;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 pub mod aws_kinesis_firehose;
+#[cfg(feature = "sources-aws_kinesis_streams")]
+pub mod aws_kinesis_streams;
 #[cfg(feature = "sources-aws_s3")]
 pub mod aws_s3;
 #[cfg(feature = "sources-aws_sqs")]
 pub mod aws_sqs;
+#[cfg(feature = "sources-azure_event_hubs")]
+pub mod azure_event_hubs;
 #[cfg(any(feature = "sources-datadog_agent"))]
 pub mod datadog_agent;
 #[cfg(feature = "sources-demo_logs")]
@@ -36,6 +40,8 @@ pub mod file_descriptors;
 pub mod fluent;
 #[cfg(feature = "sources-gcp_pubsub")]
 pub mod gcp_pubsub;
+#[cfg(feature = "sources-grpc_server")]
+pub mod grpc_server;
 #[cfg(feature = "sources-heroku_logs")]
 pub mod heroku_logs;
 #[cfg(feature = "sources-host_metrics")]
@@ -58,6 +64,8 @@ pub mod kubernetes_logs;
 pub mod logstash;
 #[cfg(feature = "sources-mongodb_metrics")]
 pub mod mongodb_metrics;
+#[cfg(feature = "sources-mqtt")]
+pub mod mqtt;
 #[cfg(all(feature = "sources-nats"))]
 pub mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
@@ -70,16 +78,26 @@ pub mod postgresql_metrics;
 pub mod prometheus;
 #[cfg(feature = "sources-redis")]
 pub mod redis;
+#[cfg(feature = "sources-sftp")]
+pub mod sftp;
+#[cfg(feature = "sources-snmp_trap")]
+pub mod snmp_trap;
 #[cfg(feature = "sources-socket")]
 pub mod socket;
 #[cfg(feature = "sources-splunk_hec")]
 pub mod splunk_hec;
+#[cfg(feature = "sources-sql")]
+pub mod sql;
 #[cfg(feature = "sources-statsd")]
 pub mod statsd;
 #[cfg(feature = "sources-syslog")]
 pub mod syslog;
 #[cfg(feature = "sources-vector")]
 pub mod vector;
+#[cfg(feature = "sources-websocket")]
+pub mod websocket;
+#[cfg(all(windows, feature = "sources-windows_eventlog"))]
+pub mod windows_eventlog;
 
 pub(crate) mod util;
 
@@ -122,6 +140,10 @@ pub enum Sources {
     #[cfg(feature = "sources-aws_kinesis_firehose")]
     AwsKinesisFirehose(#[configurable(derived)] aws_kinesis_firehose::AwsKinesisFirehoseConfig),
 
+    /// AWS Kinesis Data Streams.
+    #[cfg(feature = "sources-aws_kinesis_streams")]
+    AwsKinesisStreams(#[configurable(derived)] aws_kinesis_streams::AwsKinesisStreamsConfig),
+
     /// AWS S3.
     #[cfg(feature = "sources-aws_s3")]
     AwsS3(#[configurable(derived)] aws_s3::AwsS3Config),
@@ -130,6 +152,10 @@ pub enum Sources {
     #[cfg(feature = "sources-aws_sqs")]
     AwsSqs(#[configurable(derived)] aws_sqs::AwsSqsConfig),
 
+    /// Azure Event Hubs.
+    #[cfg(feature = "sources-azure_event_hubs")]
+    AzureEventHubs(#[configurable(derived)] azure_event_hubs::AzureEventHubsConfig),
+
     /// Datadog Agent.
     #[cfg(feature = "sources-datadog_agent")]
     DatadogAgent(#[configurable(derived)] datadog_agent::DatadogAgentConfig),
@@ -172,6 +198,10 @@ pub enum Sources {
     #[cfg(feature = "sources-gcp_pubsub")]
     GcpPubsub(#[configurable(derived)] gcp_pubsub::PubsubConfig),
 
+    /// gRPC server.
+    #[cfg(feature = "sources-grpc_server")]
+    GrpcServer(#[configurable(derived)] grpc_server::GrpcServerConfig),
+
     /// Heroku Logs.
     #[cfg(feature = "sources-heroku_logs")]
     HerokuLogs(#[configurable(derived)] heroku_logs::LogplexConfig),
@@ -220,6 +250,10 @@ pub enum Sources {
     #[cfg(feature = "sources-mongodb_metrics")]
     MongodbMetrics(#[configurable(derived)] mongodb_metrics::MongoDbMetricsConfig),
 
+    /// MQTT.
+    #[cfg(feature = "sources-mqtt")]
+    Mqtt(#[configurable(derived)] mqtt::MqttSourceConfig),
+
     /// NATS.
     #[cfg(all(feature = "sources-nats"))]
     Nats(#[configurable(derived)] nats::NatsSourceConfig),
@@ -270,6 +304,14 @@ pub enum Sources {
     #[cfg(test)]
     TestTripwire(#[configurable(derived)] crate::test_util::mock::sources::TripwireSourceConfig),
 
+    /// SFTP.
+    #[cfg(feature = "sources-sftp")]
+    Sftp(#[configurable(derived)] sftp::SftpConfig),
+
+    /// SNMP trap.
+    #[cfg(feature = "sources-snmp_trap")]
+    SnmpTrap(#[configurable(derived)] snmp_trap::SnmpTrapConfig),
+
     /// Socket.
     #[cfg(feature = "sources-socket")]
     Socket(#[configurable(derived)] socket::SocketConfig),
@@ -278,6 +320,10 @@ pub enum Sources {
     #[cfg(feature = "sources-splunk_hec")]
     SplunkHec(#[configurable(derived)] splunk_hec::SplunkConfig),
 
+    /// SQL.
+    #[cfg(feature = "sources-sql")]
+    Sql(#[configurable(derived)] sql::SqlConfig),
+
     /// Statsd.
     #[cfg(feature = "sources-statsd")]
     Statsd(#[configurable(derived)] statsd::StatsdConfig),
@@ -299,6 +345,14 @@ pub enum Sources {
     /// Vector.
     #[cfg(feature = "sources-vector")]
     Vector(#[configurable(derived)] vector::VectorConfig),
+
+    /// WebSocket.
+    #[cfg(feature = "sources-websocket")]
+    Websocket(#[configurable(derived)] websocket::WebSocketConfig),
+
+    /// Windows Event Log.
+    #[cfg(all(windows, feature = "sources-windows_eventlog"))]
+    WindowsEventLog(#[configurable(derived)] windows_eventlog::WindowsEventLogConfig),
 }
 
 // We can't use `enum_dispatch` here because it doesn't support associated constants.
@@ -315,10 +369,14 @@ impl NamedComponent for Sources {
             Self::AwsEcsMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sources-aws_kinesis_firehose")]
             Self::AwsKinesisFirehose(config) => config.get_component_name(),
+            #[cfg(feature = "sources-aws_kinesis_streams")]
+            Self::AwsKinesisStreams(config) => config.get_component_name(),
             #[cfg(feature = "sources-aws_s3")]
             Self::AwsS3(config) => config.get_component_name(),
             #[cfg(feature = "sources-aws_sqs")]
             Self::AwsSqs(config) => config.get_component_name(),
+            #[cfg(feature = "sources-azure_event_hubs")]
+            Self::AzureEventHubs(config) => config.get_component_name(),
             #[cfg(feature = "sources-datadog_agent")]
             Self::DatadogAgent(config) => config.get_component_name(),
             #[cfg(feature = "sources-demo_logs")]
@@ -339,6 +397,8 @@ impl NamedComponent for Sources {
             Self::Fluent(config) => config.get_component_name(),
             #[cfg(feature = "sources-gcp_pubsub")]
             Self::GcpPubsub(config) => config.get_component_name(),
+            #[cfg(feature = "sources-grpc_server")]
+            Self::GrpcServer(config) => config.get_component_name(),
             #[cfg(feature = "sources-heroku_logs")]
             Self::HerokuLogs(config) => config.get_component_name(),
             #[cfg(feature = "sources-host_metrics")]
@@ -363,6 +423,8 @@ impl NamedComponent for Sources {
             Self::Logstash(config) => config.get_component_name(),
             #[cfg(feature = "sources-mongodb_metrics")]
             Self::MongodbMetrics(config) => config.get_component_name(),
+            #[cfg(feature = "sources-mqtt")]
+            Self::Mqtt(config) => config.get_component_name(),
             #[cfg(all(feature = "sources-nats"))]
             Self::Nats(config) => config.get_component_name(),
             #[cfg(feature = "sources-nginx_metrics")]
@@ -387,10 +449,16 @@ impl NamedComponent for Sources {
             Self::TestPanic(config) => config.get_component_name(),
             #[cfg(test)]
             Self::TestTripwire(config) => config.get_component_name(),
+            #[cfg(feature = "sources-sftp")]
+            Self::Sftp(config) => config.get_component_name(),
+            #[cfg(feature = "sources-snmp_trap")]
+            Self::SnmpTrap(config) => config.get_component_name(),
             #[cfg(feature = "sources-socket")]
             Self::Socket(config) => config.get_component_name(),
             #[cfg(feature = "sources-splunk_hec")]
             Self::SplunkHec(config) => config.get_component_name(),
+            #[cfg(feature = "sources-sql")]
+            Self::Sql(config) => config.get_component_name(),
             #[cfg(feature = "sources-statsd")]
             Self::Statsd(config) => config.get_component_name(),
             #[cfg(feature = "sources-stdin")]
@@ -401,6 +469,10 @@ impl NamedComponent for Sources {
             Self::UnitTestStream(config) => config.get_component_name(),
             #[cfg(feature = "sources-vector")]
             Self::Vector(config) => config.get_component_name(),
+            #[cfg(feature = "sources-websocket")]
+            Self::Websocket(config) => config.get_component_name(),
+            #[cfg(all(windows, feature = "sources-windows_eventlog"))]
+            Self::WindowsEventLog(config) => config.get_component_name(),
         }
     }
 }
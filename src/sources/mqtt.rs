@@ -0,0 +1,681 @@
+//! `MQTT` source.
+//! Subscribes to topic filters on an MQTT 3.1.1 or 5 broker, emitting an event per message.
+use std::{io::Cursor, time::Duration};
+
+use bytes::Bytes;
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
+use futures::{FutureExt, StreamExt};
+use rumqttc::{
+    v5::{self, mqttbytes::v5::Packet as V5Packet},
+    Packet, TlsConfiguration, Transport,
+};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::FramedRead;
+use value::Kind;
+use vector_common::{
+    finalizer::OrderedFinalizer,
+    internal_event::{ByteSize, BytesReceived, EventsReceived, InternalEventHandle as _, Protocol},
+    sensitive_string::SensitiveString,
+};
+use vector_config::{configurable_component, NamedComponent};
+use vector_core::{
+    config::{AcknowledgementsConfig, LogNamespace},
+    ByteSizeOf,
+};
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{log_schema, Output, SourceConfig, SourceContext},
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{MqttAckError, MqttConnectionError, StreamClosedError},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    tls::{MaybeTlsSettings, TlsEnableableConfig, TlsError},
+    SourceSender,
+};
+
+/// How long to wait before polling again after a connection error, which makes the client
+/// reconnect.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid topic filter {:?}: {}", filter, reason))]
+    InvalidTopicFilter {
+        filter: String,
+        reason: &'static str,
+    },
+    #[snafu(display("At least one topic filter is required"))]
+    NoTopicFilters,
+    #[snafu(display("Could not create TLS settings: {}", source))]
+    Tls { source: TlsError },
+}
+
+/// MQTT protocol version.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+pub enum MqttProtocolVersion {
+    /// MQTT 3.1.1.
+    #[derivative(Default)]
+    #[serde(rename = "3.1.1")]
+    V3_1_1,
+
+    /// MQTT 5.
+    #[serde(rename = "5")]
+    V5,
+}
+
+/// Quality of service to subscribe with.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MqttQos {
+    /// Messages are delivered at most once, and may be lost.
+    AtMostOnce,
+
+    /// Messages are delivered at least once, and may be duplicated.
+    ///
+    /// With acknowledgements enabled, messages are only acknowledged to the broker once their
+    /// events are delivered.
+    #[derivative(Default)]
+    AtLeastOnce,
+}
+
+/// Username and password authentication.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MqttAuthConfig {
+    /// Username.
+    user: String,
+
+    /// Password.
+    password: SensitiveString,
+}
+
+/// Configuration for the `mqtt` source.
+#[configurable_component(source("mqtt"))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSourceConfig {
+    /// The MQTT broker host to connect to.
+    #[serde(default = "default_host")]
+    #[derivative(Default(value = "default_host()"))]
+    host: String,
+
+    /// The MQTT broker port to connect to.
+    ///
+    /// Defaults to `8883` with TLS enabled, and to `1883` otherwise.
+    port: Option<u16>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    protocol_version: MqttProtocolVersion,
+
+    /// The client identifier of the connection.
+    #[serde(default = "default_client_id")]
+    #[derivative(Default(value = "default_client_id()"))]
+    client_id: String,
+
+    /// The topic filters to subscribe to.
+    ///
+    /// Filters may contain the single level wildcard `+` and the multi level wildcard `#`, e.g.
+    /// `sensors/+/temperature` or `sensors/#`.
+    topics: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    qos: MqttQos,
+
+    /// Whether to start a clean session, discarding the subscriptions and pending messages of
+    /// earlier connections with the same client identifier.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    clean_session: bool,
+
+    /// The interval, in seconds, in which the broker is pinged to keep the connection alive.
+    #[serde(default = "default_keep_alive_secs")]
+    #[derivative(Default(value = "default_keep_alive_secs()"))]
+    keep_alive_secs: u64,
+
+    #[configurable(derived)]
+    auth: Option<MqttAuthConfig>,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    /// The log field name to use for the topic of the message.
+    #[serde(default = "default_topic_key")]
+    #[derivative(Default(value = "default_topic_key()"))]
+    topic_key: String,
+
+    /// The log field name to use for the retained flag of the message.
+    #[serde(default = "default_retained_key")]
+    #[derivative(Default(value = "default_retained_key()"))]
+    retained_key: String,
+
+    /// The namespace to use. This overrides the global setting.
+    #[serde(default)]
+    log_namespace: Option<bool>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_host() -> String {
+    "localhost".into()
+}
+
+fn default_client_id() -> String {
+    "vector".into()
+}
+
+const fn default_keep_alive_secs() -> u64 {
+    60
+}
+
+fn default_topic_key() -> String {
+    "topic".into()
+}
+
+fn default_retained_key() -> String {
+    "retained".into()
+}
+
+impl crate::config::GenerateConfig for MqttSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            host = "localhost"
+            topics = ["vector/#"]"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for MqttSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.topics.is_empty() {
+            return Err(BuildError::NoTopicFilters.into());
+        }
+        for filter in &self.topics {
+            validate_topic_filter(filter)?;
+        }
+
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        // Acknowledging messages only makes sense with a quality of service, which redelivers.
+        let acknowledgements =
+            cx.do_acknowledgements(&self.acknowledgements) && self.qos == MqttQos::AtLeastOnce;
+        let transport = self.transport()?;
+        let (client, event_loop) = self.connect(transport, acknowledgements);
+
+        Ok(Box::pin(mqtt_source(
+            self.clone(),
+            client,
+            event_loop,
+            cx.shutdown,
+            cx.out,
+            log_namespace,
+            acknowledgements,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                self.get_component_name(),
+                Some(self.topic_key.as_str()),
+                "topic",
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                self.get_component_name(),
+                Some(self.retained_key.as_str()),
+                "retained",
+                Kind::boolean(),
+                None,
+            );
+
+        vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl MqttSourceConfig {
+    fn decoder(&self, log_namespace: LogNamespace) -> Decoder {
+        DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build()
+    }
+
+    fn transport(&self) -> Result<Transport, BuildError> {
+        let tls = match MaybeTlsSettings::from_config(&self.tls, false).context(TlsSnafu)? {
+            MaybeTlsSettings::Raw(()) => return Ok(Transport::Tcp),
+            MaybeTlsSettings::Tls(tls) => tls,
+        };
+
+        // Without explicit authorities the client doesn't trust any server, so that TLS is only
+        // usable with a `ca_file`.
+        let ca = tls.authorities_pem().flatten().collect();
+        // The private key is always PKCS#8 encoded, which the client reads as an `ECC` key.
+        let client_auth = tls
+            .identity_pem()
+            .map(|(cert, key)| (cert, rumqttc::Key::ECC(key)));
+
+        Ok(Transport::Tls(TlsConfiguration::Simple {
+            ca,
+            alpn: None,
+            client_auth,
+        }))
+    }
+
+    fn port(&self) -> u16 {
+        let tls = self
+            .tls
+            .as_ref()
+            .map_or(false, |tls| tls.enabled.unwrap_or(false));
+        self.port.unwrap_or(if tls { 8883 } else { 1883 })
+    }
+
+    fn connect(&self, transport: Transport, manual_acks: bool) -> (MqttClient, MqttEventLoop) {
+        let keep_alive = Duration::from_secs(self.keep_alive_secs);
+        // The capacity of the request channel between the client and the event loop.
+        let capacity = 100;
+
+        match self.protocol_version {
+            MqttProtocolVersion::V3_1_1 => {
+                let mut options =
+                    rumqttc::MqttOptions::new(&self.client_id, &self.host, self.port());
+                options
+                    .set_transport(transport)
+                    .set_keep_alive(keep_alive)
+                    .set_clean_session(self.clean_session)
+                    .set_manual_acks(manual_acks);
+                if let Some(auth) = &self.auth {
+                    options.set_credentials(&auth.user, auth.password.inner());
+                }
+                let (client, event_loop) = rumqttc::AsyncClient::new(options, capacity);
+                (MqttClient::V3(client), MqttEventLoop::V3(event_loop))
+            }
+            MqttProtocolVersion::V5 => {
+                let mut options = v5::MqttOptions::new(&self.client_id, &self.host, self.port());
+                options
+                    .set_transport(transport)
+                    .set_keep_alive(keep_alive)
+                    .set_clean_session(self.clean_session)
+                    .set_manual_acks(manual_acks);
+                if let Some(auth) = &self.auth {
+                    options.set_credentials(&auth.user, auth.password.inner());
+                }
+                let (client, event_loop) = v5::AsyncClient::new(options, capacity);
+                (MqttClient::V5(client), MqttEventLoop::V5(event_loop))
+            }
+        }
+    }
+}
+
+/// Validates a topic filter according to the MQTT specification, as the broker would otherwise
+/// only reject the subscription after connecting.
+fn validate_topic_filter(filter: &str) -> Result<(), BuildError> {
+    let invalid = |reason| BuildError::InvalidTopicFilter {
+        filter: filter.to_owned(),
+        reason,
+    };
+
+    if filter.is_empty() {
+        return Err(invalid("topic filters must not be empty"));
+    }
+    let mut levels = filter.split('/').peekable();
+    while let Some(level) = levels.next() {
+        if level == "#" {
+            if levels.peek().is_some() {
+                return Err(invalid("`#` must be the last level"));
+            }
+        } else if level != "+" && (level.contains('#') || level.contains('+')) {
+            return Err(invalid("wildcards must occupy an entire level"));
+        }
+    }
+    Ok(())
+}
+
+type MqttError = Box<dyn std::error::Error + Send + Sync>;
+
+enum MqttClient {
+    V3(rumqttc::AsyncClient),
+    V5(v5::AsyncClient),
+}
+
+enum MqttEventLoop {
+    V3(rumqttc::EventLoop),
+    V5(v5::EventLoop),
+}
+
+// A source only receives packets of a single version, so that boxing them wouldn't save anything.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+enum MqttPublish {
+    V3(rumqttc::Publish),
+    /// A publish packet, as rumqttc doesn't export the type of its contents for MQTT 5.
+    V5(V5Packet),
+}
+
+/// The incoming packets, that the source cares about.
+// Most packets are publish packets, so that boxing them wouldn't save anything.
+#[allow(clippy::large_enum_variant)]
+enum Incoming {
+    Connected,
+    Publish(MqttPublish),
+    Other,
+}
+
+impl MqttClient {
+    async fn subscribe(&self, topics: &[String], qos: MqttQos) -> Result<(), MqttError> {
+        for topic in topics {
+            match self {
+                Self::V3(client) => {
+                    let qos = match qos {
+                        MqttQos::AtMostOnce => rumqttc::QoS::AtMostOnce,
+                        MqttQos::AtLeastOnce => rumqttc::QoS::AtLeastOnce,
+                    };
+                    client.subscribe(topic, qos).await?;
+                }
+                Self::V5(client) => {
+                    let qos = match qos {
+                        MqttQos::AtMostOnce => v5::mqttbytes::QoS::AtMostOnce,
+                        MqttQos::AtLeastOnce => v5::mqttbytes::QoS::AtLeastOnce,
+                    };
+                    client.subscribe(topic, qos).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn ack(&self, publish: &MqttPublish) -> Result<(), MqttError> {
+        match (self, publish) {
+            (Self::V3(client), MqttPublish::V3(publish)) => client.ack(publish).await?,
+            (Self::V5(client), MqttPublish::V5(V5Packet::Publish(publish, _))) => {
+                client.ack(publish).await?
+            }
+            _ => unreachable!("client and publish are of the same protocol version"),
+        }
+        Ok(())
+    }
+}
+
+impl MqttEventLoop {
+    async fn poll(&mut self) -> Result<Incoming, MqttError> {
+        Ok(match self {
+            Self::V3(event_loop) => match event_loop.poll().await? {
+                rumqttc::Event::Incoming(Packet::ConnAck(_)) => Incoming::Connected,
+                rumqttc::Event::Incoming(Packet::Publish(publish)) => {
+                    Incoming::Publish(MqttPublish::V3(publish))
+                }
+                _ => Incoming::Other,
+            },
+            Self::V5(event_loop) => match event_loop.poll().await? {
+                v5::Event::Incoming(packet) => match *packet {
+                    V5Packet::ConnAck(_) => Incoming::Connected,
+                    packet @ V5Packet::Publish(..) => Incoming::Publish(MqttPublish::V5(packet)),
+                    _ => Incoming::Other,
+                },
+                _ => Incoming::Other,
+            },
+        })
+    }
+}
+
+impl MqttPublish {
+    fn topic(&self) -> String {
+        match self {
+            Self::V3(publish) => publish.topic.clone(),
+            Self::V5(V5Packet::Publish(publish, _)) => {
+                String::from_utf8_lossy(&publish.topic).into_owned()
+            }
+            Self::V5(_) => unreachable!("only publish packets are kept"),
+        }
+    }
+
+    fn payload(&self) -> Bytes {
+        match self {
+            Self::V3(publish) => publish.payload.clone(),
+            Self::V5(V5Packet::Publish(publish, _)) => publish.payload.clone(),
+            Self::V5(_) => unreachable!("only publish packets are kept"),
+        }
+    }
+
+    fn retained(&self) -> bool {
+        match self {
+            Self::V3(publish) => publish.retain,
+            Self::V5(V5Packet::Publish(publish, _)) => publish.retain,
+            Self::V5(_) => unreachable!("only publish packets are kept"),
+        }
+    }
+}
+
+async fn mqtt_source(
+    config: MqttSourceConfig,
+    client: MqttClient,
+    mut event_loop: MqttEventLoop,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+    log_namespace: LogNamespace,
+    acknowledgements: bool,
+) -> Result<(), ()> {
+    let (finalizer, mut ack_stream) =
+        OrderedFinalizer::<MqttPublish>::maybe_new(acknowledgements, shutdown.clone());
+    let decoder = config.decoder(log_namespace);
+    let bytes_received = register!(BytesReceived::from(Protocol::from("mqtt")));
+
+    debug!(
+        message = "Starting MQTT source.",
+        host = %config.host,
+        port = %config.port(),
+    );
+    let mut shutdown = shutdown.fuse();
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            entry = ack_stream.next() => {
+                if let Some((status, publish)) = entry {
+                    handle_ack(&client, status, publish).await;
+                }
+            },
+            incoming = event_loop.poll() => match incoming {
+                // Subscribe on every connection, as a clean session forgets earlier subscriptions.
+                Ok(Incoming::Connected) => {
+                    if let Err(error) = client.subscribe(&config.topics, config.qos).await {
+                        emit!(MqttConnectionError { error });
+                    }
+                }
+                Ok(Incoming::Publish(publish)) => {
+                    bytes_received.emit(ByteSize(publish.payload().len()));
+                    receive_message(
+                        &config,
+                        &decoder,
+                        &mut out,
+                        log_namespace,
+                        finalizer.as_ref(),
+                        publish,
+                    )
+                    .await?;
+                }
+                Ok(Incoming::Other) => {}
+                // The next poll reconnects.
+                Err(error) => {
+                    emit!(MqttConnectionError { error });
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                }
+            },
+        }
+    }
+
+    Ok(())
+}
+
+async fn receive_message(
+    config: &MqttSourceConfig,
+    decoder: &Decoder,
+    out: &mut SourceSender,
+    log_namespace: LogNamespace,
+    finalizer: Option<&OrderedFinalizer<MqttPublish>>,
+    publish: MqttPublish,
+) -> Result<(), ()> {
+    let topic = publish.topic();
+    let retained = publish.retained();
+    let now = Utc::now();
+    let (batch, receiver) = match finalizer {
+        Some(_) => {
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            (Some(batch), Some(receiver))
+        }
+        None => (None, None),
+    };
+
+    let mut stream = FramedRead::new(Cursor::new(publish.payload()), decoder.clone());
+    while let Some(next) = stream.next().await {
+        match next {
+            Ok((events, _byte_size)) => {
+                let count = events.len();
+                emit!(EventsReceived {
+                    count,
+                    byte_size: events.size_of(),
+                });
+
+                let events = events.into_iter().map(|mut event| {
+                    if let Event::Log(ref mut log) = event {
+                        log_namespace.insert_vector_metadata(
+                            log,
+                            log_schema().source_type_key(),
+                            "source_type",
+                            Bytes::from_static(MqttSourceConfig::NAME.as_bytes()),
+                        );
+                        log_namespace.insert_vector_metadata(
+                            log,
+                            log_schema().timestamp_key(),
+                            "ingest_timestamp",
+                            now,
+                        );
+                        log_namespace.insert_source_metadata(
+                            MqttSourceConfig::NAME,
+                            log,
+                            config.topic_key.as_str(),
+                            "topic",
+                            topic.clone(),
+                        );
+                        log_namespace.insert_source_metadata(
+                            MqttSourceConfig::NAME,
+                            log,
+                            config.retained_key.as_str(),
+                            "retained",
+                            retained,
+                        );
+                    }
+                    match &batch {
+                        Some(batch) => event.with_batch_notifier(batch),
+                        None => event,
+                    }
+                });
+
+                out.send_batch(events).await.map_err(|error| {
+                    emit!(StreamClosedError { error, count });
+                })?;
+            }
+            Err(error) => {
+                // Error is logged by `crate::codecs`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
+                }
+            }
+        }
+    }
+
+    // Without acknowledgements the client acknowledges messages itself.
+    if let (Some(finalizer), Some(receiver)) = (finalizer, receiver) {
+        finalizer.add(publish, receiver);
+    }
+
+    Ok(())
+}
+
+async fn handle_ack(client: &MqttClient, status: BatchStatus, publish: MqttPublish) {
+    match status {
+        // Rejected events would be rejected again, so that they are acknowledged as well.
+        BatchStatus::Delivered | BatchStatus::Rejected => {
+            if let Err(error) = client.ack(&publish).await {
+                emit!(MqttAckError { error });
+            }
+        }
+        // MQTT can't reject single messages, unacknowledged messages are redelivered once the
+        // client reconnects with a persistent session.
+        BatchStatus::Errored => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSourceConfig>();
+    }
+
+    #[test]
+    fn validates_topic_filters() {
+        for filter in [
+            "sensors",
+            "sensors/+/temperature",
+            "sensors/#",
+            "#",
+            "+",
+            "/",
+        ] {
+            assert!(validate_topic_filter(filter).is_ok(), "{}", filter);
+        }
+        for filter in ["", "sensors/#/temperature", "sensors/temp+", "sensors#"] {
+            assert!(validate_topic_filter(filter).is_err(), "{}", filter);
+        }
+    }
+
+    #[test]
+    fn defaults_port_to_tls() {
+        let config = MqttSourceConfig::default();
+        assert_eq!(config.port(), 1883);
+
+        let config = MqttSourceConfig {
+            tls: Some(TlsEnableableConfig::enabled()),
+            ..Default::default()
+        };
+        assert_eq!(config.port(), 8883);
+
+        let config = MqttSourceConfig {
+            port: Some(1884),
+            ..Default::default()
+        };
+        assert_eq!(config.port(), 1884);
+    }
+}
@@ -0,0 +1,235 @@
+//! Reading messages with JetStream pull consumers.
+//!
+//! JetStream is driven through its API subjects with the core NATS connection, as the client only
+//! supports JetStream on blocking connections.
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use serde_json::json;
+use snafu::{ResultExt, Snafu};
+use tokio::time::{timeout_at, Instant};
+use vector_common::{
+    finalizer::UnorderedFinalizer,
+    internal_event::{BytesReceived, Protocol},
+};
+use vector_config::configurable_component;
+
+use super::handle_message;
+use crate::{
+    codecs::Decoder,
+    event::{BatchNotifier, BatchStatus},
+    internal_events::NatsAckError,
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// How long a pull request waits for messages, before it expires.
+const PULL_EXPIRES: Duration = Duration::from_secs(5);
+
+/// Configuration for reading messages with a JetStream pull consumer.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct JetStreamConfig {
+    /// The name of the stream to read messages from.
+    pub stream: String,
+
+    /// The name of the durable consumer to read messages with.
+    ///
+    /// Instances of Vector with the same consumer share the messages of the stream, and continue
+    /// where the consumer left off after restarts.
+    pub durable_name: String,
+
+    /// Whether to create the consumer, if it doesn't exist yet.
+    ///
+    /// The consumer only receives the messages of the `subject`, with acknowledgements of each
+    /// message required.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    pub create_consumer: bool,
+
+    /// The maximum number of messages to pull at once.
+    #[serde(default = "default_batch_size")]
+    #[derivative(Default(value = "default_batch_size()"))]
+    pub batch_size: usize,
+
+    /// How long to wait for a message to be acknowledged before it's delivered again, in seconds.
+    ///
+    /// Only used when creating the consumer.
+    #[serde(default = "default_ack_wait_secs")]
+    #[derivative(Default(value = "default_ack_wait_secs()"))]
+    pub ack_wait_secs: u64,
+}
+
+const fn default_batch_size() -> usize {
+    100
+}
+
+const fn default_ack_wait_secs() -> u64 {
+    30
+}
+
+#[derive(Debug, Snafu)]
+pub enum ConsumerError {
+    #[snafu(display("Failed to request consumer: {}", source))]
+    Request { source: std::io::Error },
+    #[snafu(display("Invalid consumer response: {}", source))]
+    Response { source: serde_json::Error },
+    #[snafu(display("Failed to create consumer: {}", description))]
+    Create { description: String },
+}
+
+/// The replies to send for the outcome of a message.
+mod ack {
+    pub const ACK: &str = "+ACK";
+    /// Delivers the message again, after errors.
+    pub const NAK: &str = "-NAK";
+    /// Stops delivering the message, which would be rejected again.
+    pub const TERM: &str = "+TERM";
+}
+
+/// Creates the durable consumer, or updates it if it exists.
+pub async fn create_consumer(
+    connection: &nats::asynk::Connection,
+    config: &JetStreamConfig,
+    subject: &str,
+) -> Result<(), ConsumerError> {
+    let request = json!({
+        "stream_name": config.stream,
+        "config": {
+            "durable_name": config.durable_name,
+            "filter_subject": subject,
+            "deliver_policy": "all",
+            "ack_policy": "explicit",
+            "ack_wait": Duration::from_secs(config.ack_wait_secs).as_nanos() as u64,
+        },
+    });
+    let response = connection
+        .request(
+            &format!(
+                "$JS.API.CONSUMER.DURABLE.CREATE.{}.{}",
+                config.stream, config.durable_name
+            ),
+            request.to_string(),
+        )
+        .await
+        .context(RequestSnafu)?;
+
+    let response: serde_json::Value =
+        serde_json::from_slice(&response.data).context(ResponseSnafu)?;
+    match response.get("error") {
+        None => Ok(()),
+        Some(error) => Err(ConsumerError::Create {
+            description: error
+                .get("description")
+                .and_then(|description| description.as_str())
+                .unwrap_or("unknown error")
+                .to_owned(),
+        }),
+    }
+}
+
+pub async fn jetstream_source(
+    connection: nats::asynk::Connection,
+    config: JetStreamConfig,
+    decoder: Decoder,
+    acknowledgements: bool,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let next_subject = format!(
+        "$JS.API.CONSUMER.MSG.NEXT.{}.{}",
+        config.stream, config.durable_name
+    );
+    let inbox = connection.new_inbox();
+    let subscription = connection.subscribe(&inbox).await.map_err(|error| {
+        error!(message = "Failed to subscribe to inbox.", %error);
+    })?;
+    let request = json!({
+        "batch": config.batch_size,
+        "expires": PULL_EXPIRES.as_nanos() as u64,
+    })
+    .to_string();
+
+    let (finalizer, mut ack_stream) =
+        UnorderedFinalizer::<String>::maybe_new(acknowledgements, shutdown.clone());
+    let ack_connection = connection.clone();
+    tokio::spawn(async move {
+        while let Some((status, reply)) = ack_stream.next().await {
+            let kind = match status {
+                BatchStatus::Delivered => ack::ACK,
+                BatchStatus::Errored => ack::NAK,
+                BatchStatus::Rejected => ack::TERM,
+            };
+            acknowledge(&ack_connection, &reply, kind).await;
+        }
+    });
+
+    let bytes_received = register!(BytesReceived::from(Protocol::TCP));
+    loop {
+        if let Err(error) = connection
+            .publish_request(&next_subject, &inbox, &request)
+            .await
+        {
+            error!(message = "Failed to pull messages.", %error);
+            return Err(());
+        }
+
+        // Status messages carry no acknowledgement subject, and end the pull request once it
+        // expired, or the consumer has no messages left.
+        let deadline = Instant::now() + PULL_EXPIRES;
+        let mut received = 0;
+        while received < config.batch_size {
+            let msg = tokio::select! {
+                _ = &mut shutdown => return Ok(()),
+                msg = timeout_at(deadline, subscription.next()) => match msg {
+                    Ok(Some(msg)) => msg,
+                    Ok(None) => return Ok(()),
+                    Err(_) => break,
+                },
+            };
+            let reply = match msg.reply {
+                Some(ref reply) if reply.starts_with("$JS.ACK.") => reply.clone(),
+                _ => break,
+            };
+            received += 1;
+
+            let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(acknowledgements);
+            handle_message(&msg.data, &decoder, &bytes_received, &batch, &mut out).await?;
+            drop(batch);
+            match (&finalizer, receiver) {
+                (Some(finalizer), Some(receiver)) => finalizer.add(reply, receiver),
+                // Without acknowledgements, messages are acknowledged once they are sent.
+                _ => acknowledge(&connection, &reply, ack::ACK).await,
+            }
+        }
+    }
+}
+
+async fn acknowledge(connection: &nats::asynk::Connection, reply: &str, kind: &str) {
+    if let Err(error) = connection.publish(reply, kind).await {
+        emit!(NatsAckError { error });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_defaults() {
+        let config: JetStreamConfig = toml::from_str(
+            r#"
+            stream = "logs"
+            durable_name = "vector"
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.create_consumer);
+        assert_eq!(config.batch_size, 100);
+        assert_eq!(config.ack_wait_secs, 30);
+    }
+}
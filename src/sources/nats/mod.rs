@@ -5,23 +5,29 @@ use futures::{pin_mut, stream, Stream, StreamExt};
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::FramedRead;
 use vector_common::internal_event::{
-    ByteSize, BytesReceived, EventsReceived, InternalEventHandle as _, Protocol,
+    ByteSize, BytesReceived, EventsReceived, InternalEventHandle as _, Protocol, Registered,
 };
 use vector_config::configurable_component;
 use vector_core::{config::LogNamespace, ByteSizeOf};
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
-    config::{log_schema, GenerateConfig, Output, SourceConfig, SourceContext},
-    event::Event,
+    config::{
+        log_schema, AcknowledgementsConfig, GenerateConfig, Output, SourceConfig, SourceContext,
+    },
+    event::{BatchNotifier, Event},
     internal_events::StreamClosedError,
     nats::{from_tls_auth_config, NatsAuthConfig, NatsConfigError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
     tls::TlsEnableableConfig,
     SourceSender,
 };
 
+mod jetstream;
+
+pub use jetstream::JetStreamConfig;
+
 #[derive(Debug, Snafu)]
 enum BuildError {
     #[snafu(display("NATS Config Error: {}", source))]
@@ -30,6 +36,10 @@ enum BuildError {
     Connect { source: std::io::Error },
     #[snafu(display("NATS Subscribe Error: {}", source))]
     Subscribe { source: std::io::Error },
+    #[snafu(display("NATS JetStream Consumer Error: {}", source))]
+    Consumer { source: jetstream::ConsumerError },
+    #[snafu(display("`queue` can't be combined with `jetstream`"))]
+    QueueWithJetStream,
 }
 
 /// Configuration for the `nats` source.
@@ -52,8 +62,16 @@ pub struct NatsSourceConfig {
     subject: String,
 
     /// NATS Queue Group to join.
+    ///
+    /// Not supported with JetStream, whose durable consumers are shared instead.
     queue: Option<String>,
 
+    /// Reads messages with a JetStream pull consumer, instead of a core NATS subscription.
+    ///
+    /// Messages are then acknowledged once they are processed, and delivered again otherwise.
+    #[configurable(derived)]
+    jetstream: Option<JetStreamConfig>,
+
     #[configurable(derived)]
     tls: Option<TlsEnableableConfig>,
 
@@ -69,6 +87,10 @@ pub struct NatsSourceConfig {
     #[serde(default = "default_decoding")]
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
 }
 
 impl GenerateConfig for NatsSourceConfig {
@@ -86,7 +108,6 @@ impl GenerateConfig for NatsSourceConfig {
 #[async_trait::async_trait]
 impl SourceConfig for NatsSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
-        let (connection, subscription) = create_subscription(self).await?;
         let decoder = DecodingConfig::new(
             self.framing.clone(),
             self.decoding.clone(),
@@ -94,6 +115,19 @@ impl SourceConfig for NatsSourceConfig {
         )
         .build();
 
+        if let Some(jetstream) = &self.jetstream {
+            let connection = create_consumer(self, jetstream).await?;
+            return Ok(Box::pin(jetstream::jetstream_source(
+                connection,
+                jetstream.clone(),
+                decoder,
+                cx.do_acknowledgements(&self.acknowledgements),
+                cx.shutdown,
+                cx.out,
+            )));
+        }
+
+        let (connection, subscription) = create_subscription(self).await?;
         Ok(Box::pin(nats_source(
             connection,
             subscription,
@@ -108,7 +142,7 @@ impl SourceConfig for NatsSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        self.jetstream.is_some()
     }
 }
 
@@ -147,37 +181,48 @@ async fn nats_source(
     pin_mut!(stream);
     let bytes_received = register!(BytesReceived::from(Protocol::TCP));
     while let Some(msg) = stream.next().await {
-        bytes_received.emit(ByteSize(msg.data.len()));
-        let mut stream = FramedRead::new(msg.data.as_ref(), decoder.clone());
-        while let Some(next) = stream.next().await {
-            match next {
-                Ok((events, _byte_size)) => {
-                    let count = events.len();
-                    emit!(EventsReceived {
-                        count,
-                        byte_size: events.size_of()
-                    });
-
-                    let now = Utc::now();
-
-                    let events = events.into_iter().map(|mut event| {
-                        if let Event::Log(ref mut log) = event {
-                            log.try_insert(log_schema().source_type_key(), Bytes::from("nats"));
-                            log.try_insert(log_schema().timestamp_key(), now);
-                        }
-                        event
-                    });
-
-                    out.send_batch(events).await.map_err(|error| {
-                        emit!(StreamClosedError { error, count });
-                    })?;
-                }
-                Err(error) => {
-                    // Error is logged by `crate::codecs`, no further
-                    // handling is needed here.
-                    if !error.can_continue() {
-                        break;
+        handle_message(&msg.data, &decoder, &bytes_received, &None, &mut out).await?;
+    }
+    Ok(())
+}
+
+async fn handle_message(
+    data: &[u8],
+    decoder: &Decoder,
+    bytes_received: &Registered<BytesReceived>,
+    batch: &Option<BatchNotifier>,
+    out: &mut SourceSender,
+) -> Result<(), ()> {
+    bytes_received.emit(ByteSize(data.len()));
+    let mut stream = FramedRead::new(data, decoder.clone());
+    while let Some(next) = stream.next().await {
+        match next {
+            Ok((events, _byte_size)) => {
+                let count = events.len();
+                emit!(EventsReceived {
+                    count,
+                    byte_size: events.size_of()
+                });
+
+                let now = Utc::now();
+
+                let events = events.into_iter().map(|mut event| {
+                    if let Event::Log(ref mut log) = event {
+                        log.try_insert(log_schema().source_type_key(), Bytes::from("nats"));
+                        log.try_insert(log_schema().timestamp_key(), now);
                     }
+                    event.with_batch_notifier_option(batch)
+                });
+
+                out.send_batch(events).await.map_err(|error| {
+                    emit!(StreamClosedError { error, count });
+                })?;
+            }
+            Err(error) => {
+                // Error is logged by `crate::codecs`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
                 }
             }
         }
@@ -200,6 +245,23 @@ async fn create_subscription(
     Ok((nc, subscription))
 }
 
+async fn create_consumer(
+    config: &NatsSourceConfig,
+    jetstream: &JetStreamConfig,
+) -> Result<nats::asynk::Connection, BuildError> {
+    if config.queue.is_some() {
+        return Err(BuildError::QueueWithJetStream);
+    }
+
+    let nc = config.connect().await?;
+    if jetstream.create_consumer {
+        jetstream::create_consumer(&nc, jetstream, &config.subject)
+            .await
+            .context(ConsumerSnafu)?;
+    }
+    Ok(nc)
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::print_stdout)] //tests
@@ -218,6 +280,7 @@ mod integration_tests {
     #![allow(clippy::print_stdout)] //tests
 
     use super::*;
+    use crate::event::EventStatus;
     use crate::nats::{NatsAuthCredentialsFile, NatsAuthNKey, NatsAuthToken, NatsAuthUserPassword};
     use crate::test_util::{
         collect_n,
@@ -263,6 +326,8 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: None,
@@ -288,6 +353,8 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: None,
@@ -318,6 +385,8 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: None,
@@ -348,6 +417,8 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: None,
@@ -377,6 +448,8 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: None,
@@ -406,6 +479,8 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: None,
@@ -436,6 +511,8 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: None,
@@ -466,6 +543,8 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: Some(TlsEnableableConfig {
@@ -497,6 +576,8 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: None,
@@ -522,6 +603,8 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: Some(TlsEnableableConfig {
@@ -555,6 +638,8 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: Some(TlsEnableableConfig {
@@ -586,6 +671,8 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: Some(TlsEnableableConfig {
@@ -621,6 +708,8 @@ mod integration_tests {
             subject: subject.clone(),
             url,
             queue: None,
+            jetstream: None,
+            acknowledgements: Default::default(),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             tls: Some(TlsEnableableConfig {
@@ -644,4 +733,85 @@ mod integration_tests {
             r
         );
     }
+
+    #[tokio::test]
+    async fn nats_jetstream_acknowledges_messages() {
+        let subject = format!("test-{}", random_string(10));
+        let stream = format!("stream-{}", random_string(10));
+        let url =
+            std::env::var("NATS_ADDRESS").unwrap_or_else(|_| String::from("nats://localhost:4222"));
+
+        let conf = NatsSourceConfig {
+            connection_name: "".to_owned(),
+            subject: subject.clone(),
+            url,
+            queue: None,
+            jetstream: Some(JetStreamConfig {
+                stream: stream.clone(),
+                durable_name: "vector".to_owned(),
+                ..Default::default()
+            }),
+            acknowledgements: true.into(),
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            tls: None,
+            auth: None,
+        };
+
+        let nc = conf.connect().await.unwrap();
+        let request = serde_json::json!({ "name": stream, "subjects": [subject] });
+        nc.request(
+            &format!("$JS.API.STREAM.CREATE.{}", stream),
+            request.to_string(),
+        )
+        .await
+        .unwrap();
+        nc.publish(&subject, "my message").await.unwrap();
+
+        let jetstream = conf.jetstream.clone().unwrap();
+        let connection = create_consumer(&conf, &jetstream).await.unwrap();
+        let events = assert_source_compliance(&SOURCE_TAGS, async move {
+            let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+            let decoder = DecodingConfig::new(
+                conf.framing.clone(),
+                conf.decoding.clone(),
+                LogNamespace::Legacy,
+            )
+            .build();
+            tokio::spawn(jetstream::jetstream_source(
+                connection,
+                jetstream,
+                decoder,
+                true,
+                ShutdownSignal::noop(),
+                tx,
+            ));
+
+            collect_n(rx, 1).await
+        })
+        .await;
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "my message".into()
+        );
+
+        // The message is acknowledged, so that none are left pending.
+        let mut pending = 1;
+        for _ in 0..10 {
+            let info = nc
+                .request(
+                    &format!("$JS.API.CONSUMER.INFO.{}.vector", stream),
+                    Vec::<u8>::new(),
+                )
+                .await
+                .unwrap();
+            let info: serde_json::Value = serde_json::from_slice(&info.data).unwrap();
+            pending = info["num_ack_pending"].as_u64().unwrap();
+            if pending == 0 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+        assert_eq!(pending, 0);
+    }
 }
@@ -3,8 +3,9 @@ use std::net::SocketAddr;
 use bytes::Bytes;
 use futures_util::FutureExt;
 use http::StatusCode;
-use opentelemetry_proto::proto::collector::logs::v1::{
-    ExportLogsServiceRequest, ExportLogsServiceResponse,
+use opentelemetry_proto::proto::collector::{
+    logs::v1::{ExportLogsServiceRequest, ExportLogsServiceResponse},
+    trace::v1::{ExportTraceServiceRequest, ExportTraceServiceResponse},
 };
 use prost::Message;
 use snafu::Snafu;
@@ -63,32 +64,57 @@ pub(crate) fn build_warp_filter(
     out: SourceSender,
     bytes_received: Registered<BytesReceived>,
 ) -> BoxedFilter<(Response,)> {
-    warp::post()
-        .and(warp::path!("v1" / "logs"))
-        .and(warp::header::exact_ignore_case(
-            "content-type",
-            "application/x-protobuf",
-        ))
-        .and(warp::header::optional::<String>("content-encoding"))
-        .and(warp::body::bytes())
-        .and_then(move |encoding_header: Option<String>, body: Bytes| {
+    let logs_filter = {
+        let out = out.clone();
+        let bytes_received = bytes_received.clone();
+        warp::path!("v1" / "logs").and(protobuf_body()).and_then(
+            move |encoding_header: Option<String>, body: Bytes| {
+                let events = decode(&encoding_header, body).and_then(|body| {
+                    bytes_received.emit(ByteSize(body.len()));
+                    decode_logs_body(body)
+                });
+
+                handle_request(
+                    events,
+                    acknowledgements,
+                    out.clone(),
+                    super::LOGS,
+                    ExportLogsServiceResponse {},
+                )
+            },
+        )
+    };
+    let traces_filter = warp::path!("v1" / "traces").and(protobuf_body()).and_then(
+        move |encoding_header: Option<String>, body: Bytes| {
             let events = decode(&encoding_header, body).and_then(|body| {
                 bytes_received.emit(ByteSize(body.len()));
-                decode_body(body)
+                decode_traces_body(body)
             });
 
-            handle_request(events, acknowledgements, out.clone(), super::LOGS)
-        })
+            handle_request(
+                events,
+                acknowledgements,
+                out.clone(),
+                super::TRACES,
+                ExportTraceServiceResponse {},
+            )
+        },
+    );
+
+    warp::post()
+        .and(logs_filter.or(traces_filter).unify())
         .boxed()
 }
 
-fn decode_body(body: Bytes) -> Result<Vec<Event>, ErrorMessage> {
-    let request = ExportLogsServiceRequest::decode(body).map_err(|error| {
-        ErrorMessage::new(
-            StatusCode::BAD_REQUEST,
-            format!("Could not decode request: {}", error),
-        )
-    })?;
+/// The content encoding and body of protobuf requests.
+fn protobuf_body() -> impl Filter<Extract = (Option<String>, Bytes), Error = Rejection> + Clone {
+    warp::header::exact_ignore_case("content-type", "application/x-protobuf")
+        .and(warp::header::optional::<String>("content-encoding"))
+        .and(warp::body::bytes())
+}
+
+fn decode_logs_body(body: Bytes) -> Result<Vec<Event>, ErrorMessage> {
+    let request = ExportLogsServiceRequest::decode(body).map_err(decode_error)?;
 
     let events: Vec<Event> = request
         .resource_logs
@@ -104,11 +130,36 @@ fn decode_body(body: Bytes) -> Result<Vec<Event>, ErrorMessage> {
     Ok(events)
 }
 
-async fn handle_request(
+fn decode_traces_body(body: Bytes) -> Result<Vec<Event>, ErrorMessage> {
+    let request = ExportTraceServiceRequest::decode(body).map_err(decode_error)?;
+
+    let events: Vec<Event> = request
+        .resource_spans
+        .into_iter()
+        .flat_map(|v| v.into_iter())
+        .collect();
+
+    emit!(EventsReceived {
+        byte_size: events.size_of(),
+        count: events.len(),
+    });
+
+    Ok(events)
+}
+
+fn decode_error(error: prost::DecodeError) -> ErrorMessage {
+    ErrorMessage::new(
+        StatusCode::BAD_REQUEST,
+        format!("Could not decode request: {}", error),
+    )
+}
+
+async fn handle_request<R: Message>(
     events: Result<Vec<Event>, ErrorMessage>,
     acknowledgements: bool,
     mut out: SourceSender,
     output: &str,
+    response: R,
 ) -> Result<Response, Rejection> {
     match events {
         Ok(mut events) => {
@@ -123,11 +174,9 @@ async fn handle_request(
                 })?;
 
             match receiver {
-                None => Ok(protobuf(ExportLogsServiceResponse {}).into_response()),
+                None => Ok(protobuf(response).into_response()),
                 Some(receiver) => match receiver.await {
-                    BatchStatus::Delivered => {
-                        Ok(protobuf(ExportLogsServiceResponse {}).into_response())
-                    }
+                    BatchStatus::Delivered => Ok(protobuf(response).into_response()),
                     BatchStatus::Errored => Err(warp::reject::custom(Status {
                         code: 2, // UNKNOWN - OTLP doesn't require use of status.code, but we can't encode a None here
                         message: "Error delivering contents to sink".into(),
@@ -1,7 +1,7 @@
 use crate::{
     config::{SourceConfig, SourceContext},
     event::{into_event_stream, Event, EventStatus, LogEvent, Value},
-    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS},
+    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS, TRACES},
     test_util::{
         self,
         components::{assert_source_compliance, SOURCE_TAGS},
@@ -13,10 +13,14 @@ use chrono::{TimeZone, Utc};
 use futures::Stream;
 use futures_util::StreamExt;
 use opentelemetry_proto::proto::{
-    collector::logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
-    common::v1::{any_value, AnyValue, KeyValue},
+    collector::{
+        logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+        trace::v1::{trace_service_client::TraceServiceClient, ExportTraceServiceRequest},
+    },
+    common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
     resource::v1::Resource as OtelResource,
+    trace::v1::{span, ResourceSpans, ScopeSpans, Span, Status as SpanStatus},
 };
 use std::collections::BTreeMap;
 use tonic::Request;
@@ -43,7 +47,7 @@ async fn receive_grpc_logs() {
             },
             acknowledgements: Default::default(),
         };
-        let (sender, logs_output, _) = new_source(EventStatus::Delivered);
+        let (sender, logs_output, _, _) = new_source(EventStatus::Delivered);
         let server = source
             .build(SourceContext::new_test(sender, None))
             .await
@@ -124,18 +128,127 @@ async fn receive_grpc_logs() {
     .await;
 }
 
+#[tokio::test]
+async fn receive_grpc_traces() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let grpc_addr = next_addr();
+        let http_addr = next_addr();
+
+        let source = OpentelemetryConfig {
+            grpc: GrpcConfig {
+                address: grpc_addr,
+                tls: Default::default(),
+            },
+            http: HttpConfig {
+                address: http_addr,
+                tls: Default::default(),
+            },
+            acknowledgements: Default::default(),
+        };
+        let (sender, _, traces_output, _) = new_source(EventStatus::Delivered);
+        let server = source
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(grpc_addr).await;
+
+        let mut client = TraceServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+        let req = Request::new(ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                resource: Some(OtelResource {
+                    attributes: vec![string_attribute("res_key", "res_val")],
+                    dropped_attributes_count: 0,
+                }),
+                scope_spans: vec![ScopeSpans {
+                    scope: Some(InstrumentationScope {
+                        name: "checkout".into(),
+                        version: "1.0.0".into(),
+                        attributes: vec![],
+                        dropped_attributes_count: 0,
+                    }),
+                    spans: vec![Span {
+                        trace_id: str_into_hex_bytes("4ac52aadf321c2e531db005df08792f5"),
+                        span_id: str_into_hex_bytes("0b9e4bda2a55530d"),
+                        trace_state: "".into(),
+                        parent_span_id: str_into_hex_bytes("b7ad6b7169203331"),
+                        name: "GET /cart".into(),
+                        kind: span::SpanKind::Server as i32,
+                        start_time_unix_nano: 1,
+                        end_time_unix_nano: 2,
+                        attributes: vec![string_attribute("attr_key", "attr_val")],
+                        dropped_attributes_count: 0,
+                        events: vec![],
+                        dropped_events_count: 0,
+                        links: vec![],
+                        dropped_links_count: 0,
+                        status: Some(SpanStatus {
+                            message: "".into(),
+                            code: 1,
+                        }),
+                    }],
+                    schema_url: "v1".into(),
+                }],
+                schema_url: "v1".into(),
+            }],
+        });
+        let _ = client.export(req).await;
+        let mut output = test_util::collect_ready(traces_output).await;
+        assert_eq!(output.len(), 1);
+        let trace = output.pop().unwrap().into_trace();
+
+        assert_eq!(
+            trace.get("trace_id"),
+            Some(&"4ac52aadf321c2e531db005df08792f5".into())
+        );
+        assert_eq!(trace.get("span_id"), Some(&"0b9e4bda2a55530d".into()));
+        assert_eq!(
+            trace.get("parent_span_id"),
+            Some(&"b7ad6b7169203331".into())
+        );
+        assert_eq!(trace.get("name"), Some(&"GET /cart".into()));
+        assert_eq!(trace.get("kind"), Some(&2.into()));
+        assert_eq!(
+            trace.get("start_time_unix_nano"),
+            Some(&Utc.timestamp_nanos(1).into())
+        );
+        assert_eq!(trace.get("attributes.attr_key"), Some(&"attr_val".into()));
+        assert_eq!(trace.get("resources.res_key"), Some(&"res_val".into()));
+        assert_eq!(trace.get("scope.name"), Some(&"checkout".into()));
+        assert_eq!(trace.get("scope.version"), Some(&"1.0.0".into()));
+        assert_eq!(trace.get("status.code"), Some(&1.into()));
+        assert!(trace.get("ingest_timestamp").is_some());
+    })
+    .await;
+}
+
 fn new_source(
     status: EventStatus,
 ) -> (
     SourceSender,
     impl Stream<Item = Event>,
     impl Stream<Item = Event>,
+    impl Stream<Item = Event>,
 ) {
     let (mut sender, recv) = SourceSender::new_test_finalize(status);
     let logs_output = sender
         .add_outputs(status, LOGS.to_string())
         .flat_map(into_event_stream);
-    (sender, logs_output, recv)
+    let traces_output = sender
+        .add_outputs(status, TRACES.to_string())
+        .flat_map(into_event_stream);
+    (sender, logs_output, traces_output, recv)
+}
+
+fn string_attribute(key: &str, value: &str) -> KeyValue {
+    KeyValue {
+        key: key.into(),
+        value: Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.into())),
+        }),
+    }
 }
 
 fn str_into_hex_bytes(s: &str) -> Vec<u8> {
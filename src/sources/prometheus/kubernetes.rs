@@ -0,0 +1,612 @@
+//! Kubernetes service discovery of the scrape targets.
+//!
+//! The discovered objects are watched through reflectors, and their targets are listed at every
+//! scrape interval with the `__meta_kubernetes_*` labels of Prometheus' `kubernetes_sd_configs`.
+
+use std::{collections::HashSet, fmt::Debug, hash::Hash, path::PathBuf, sync::Arc, time::Duration};
+
+use derivative::Derivative;
+use k8s_openapi::{
+    api::core::v1::{Endpoints, Pod, Service},
+    apimachinery::pkg::apis::meta::v1::ObjectMeta,
+    NamespaceResourceScope,
+};
+use kube::{
+    api::{Api, ListParams},
+    config::{self, KubeConfigOptions},
+    runtime::{
+        reflector::{self, store::Store, ObjectRef},
+        watcher,
+    },
+    Client, Config as ClientConfig, Resource,
+};
+use serde::de::DeserializeOwned;
+use tokio::task::JoinHandle;
+use vector_config::configurable_component;
+
+use super::relabel::{Labels, ADDRESS_LABEL, METRICS_PATH_LABEL, SCHEME_LABEL};
+use crate::kubernetes::{custom_reflector, meta_cache::MetaCache};
+
+const LABEL_PREFIX: &str = "__meta_kubernetes_";
+
+/// Kubernetes service discovery configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct KubernetesSdConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    role: KubernetesRole,
+
+    /// The namespaces to discover targets in.
+    ///
+    /// By default, targets are discovered in all namespaces.
+    #[serde(default)]
+    namespaces: Vec<String>,
+
+    /// The label selector the discovered objects must match.
+    label_selector: Option<String>,
+
+    /// The field selector the discovered objects must match.
+    field_selector: Option<String>,
+
+    /// Whether the `prometheus.io/*` annotations select and configure the targets.
+    ///
+    /// When enabled, only the objects annotated with `prometheus.io/scrape: "true"` are scraped, and the
+    /// `prometheus.io/scheme`, `prometheus.io/path`, and `prometheus.io/port` annotations override the scheme, path,
+    /// and port of their targets. With the `endpoints` role, the annotations of the service are used.
+    #[serde(default = "crate::serde::default_true")]
+    annotations: bool,
+
+    /// Optional path to a kubeconfig file readable by Vector. If not set,
+    /// Vector will try to connect to Kubernetes using in-cluster configuration.
+    kube_config_file: Option<PathBuf>,
+}
+
+/// The kind of Kubernetes objects the targets are discovered from.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum KubernetesRole {
+    /// A target for every declared port of every container of the running pods.
+    #[derivative(Default)]
+    Pod,
+
+    /// A target for every port of every address of the endpoints of services.
+    Endpoints,
+
+    /// A target for every port of the services, addressed by their DNS name.
+    Service,
+}
+
+/// The scrape settings of the `prometheus.io/*` annotations of an object.
+#[derive(Debug, Default)]
+struct ScrapeAnnotations<'a> {
+    scrape: bool,
+    scheme: Option<&'a str>,
+    path: Option<&'a str>,
+    port: Option<&'a str>,
+}
+
+impl<'a> ScrapeAnnotations<'a> {
+    fn from_meta(meta: &'a ObjectMeta) -> Self {
+        let annotations = match &meta.annotations {
+            Some(annotations) => annotations,
+            None => return Self::default(),
+        };
+        let get = |name: &str| annotations.get(name).map(String::as_str);
+        Self {
+            scrape: get("prometheus.io/scrape") == Some("true"),
+            scheme: get("prometheus.io/scheme"),
+            path: get("prometheus.io/path"),
+            port: get("prometheus.io/port"),
+        }
+    }
+
+    /// Applies the annotated settings to the targets of the object, which share a single target
+    /// once their port is overridden.
+    fn apply(&self, targets: Vec<Labels>) -> Vec<Labels> {
+        let mut addresses = HashSet::new();
+        targets
+            .into_iter()
+            .filter_map(|mut labels| {
+                if let Some(scheme) = self.scheme {
+                    labels.insert(SCHEME_LABEL.to_owned(), scheme.to_owned());
+                }
+                if let Some(path) = self.path {
+                    labels.insert(METRICS_PATH_LABEL.to_owned(), path.to_owned());
+                }
+                if let Some(port) = self.port {
+                    let address = with_port(&labels[ADDRESS_LABEL], port);
+                    if !addresses.insert(address.clone()) {
+                        return None;
+                    }
+                    labels.insert(ADDRESS_LABEL.to_owned(), address);
+                }
+                Some(labels)
+            })
+            .collect()
+    }
+}
+
+/// The reflected Kubernetes objects the targets are discovered from.
+#[derive(Clone)]
+pub(super) struct KubernetesDiscovery {
+    role: KubernetesRole,
+    annotations: bool,
+    pods: Vec<Store<Pod>>,
+    endpoints: Vec<Store<Endpoints>>,
+    services: Vec<Store<Service>>,
+}
+
+impl KubernetesSdConfig {
+    /// Starts the reflectors of the discovered objects, which have to be aborted once the source
+    /// shuts down.
+    pub(super) async fn build(&self) -> crate::Result<(KubernetesDiscovery, Vec<JoinHandle<()>>)> {
+        // If the user passed a custom Kubeconfig use it, otherwise
+        // we attempt to load the local kubec-config, followed by the
+        // in-cluster environment variables
+        let client_config = match &self.kube_config_file {
+            Some(kc) => {
+                ClientConfig::from_custom_kubeconfig(
+                    config::Kubeconfig::read_from(kc)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        let client = Client::try_from(client_config)?;
+
+        let params = ListParams {
+            label_selector: self.label_selector.clone(),
+            field_selector: self.field_selector.clone(),
+            ..Default::default()
+        };
+        let mut reflectors = Vec::new();
+        let mut discovery = KubernetesDiscovery {
+            role: self.role,
+            annotations: self.annotations,
+            pods: Vec::new(),
+            endpoints: Vec::new(),
+            services: Vec::new(),
+        };
+        match self.role {
+            KubernetesRole::Pod => discovery.pods = self.watch(&client, &params, &mut reflectors),
+            KubernetesRole::Endpoints => {
+                discovery.endpoints = self.watch(&client, &params, &mut reflectors);
+                // the services of the endpoints carry their labels and annotations
+                discovery.services = self.watch(&client, &ListParams::default(), &mut reflectors);
+            }
+            KubernetesRole::Service => {
+                discovery.services = self.watch(&client, &params, &mut reflectors)
+            }
+        }
+
+        Ok((discovery, reflectors))
+    }
+
+    fn watch<K>(
+        &self,
+        client: &Client,
+        params: &ListParams,
+        reflectors: &mut Vec<JoinHandle<()>>,
+    ) -> Vec<Store<K>>
+    where
+        K: Resource<Scope = NamespaceResourceScope>
+            + Clone
+            + Debug
+            + DeserializeOwned
+            + Send
+            + Sync
+            + 'static,
+        K::DynamicType: Default + Eq + Hash + Clone + Send + Sync,
+    {
+        let apis = if self.namespaces.is_empty() {
+            vec![Api::<K>::all(client.clone())]
+        } else {
+            self.namespaces
+                .iter()
+                .map(|namespace| Api::<K>::namespaced(client.clone(), namespace))
+                .collect()
+        };
+
+        apis.into_iter()
+            .map(|api| {
+                let store_w = reflector::store::Writer::default();
+                let store = store_w.as_reader();
+                reflectors.push(tokio::spawn(custom_reflector(
+                    store_w,
+                    MetaCache::new(),
+                    watcher(api, params.clone()),
+                    Duration::ZERO,
+                )));
+                store
+            })
+            .collect()
+    }
+}
+
+impl KubernetesDiscovery {
+    /// Lists the targets of the objects currently known, as their labels before relabeling.
+    pub(super) fn targets(&self) -> Vec<Labels> {
+        match self.role {
+            KubernetesRole::Pod => objects(&self.pods)
+                .flat_map(|pod| pod_targets(&pod, self.annotations))
+                .collect(),
+            KubernetesRole::Endpoints => objects(&self.endpoints)
+                .flat_map(|endpoints| {
+                    let service = self.service(&endpoints.metadata);
+                    endpoints_targets(&endpoints, service.as_deref(), self.annotations)
+                })
+                .collect(),
+            KubernetesRole::Service => objects(&self.services)
+                .flat_map(|service| service_targets(&service, self.annotations))
+                .collect(),
+        }
+    }
+
+    fn service(&self, meta: &ObjectMeta) -> Option<Arc<Service>> {
+        let name = meta.name.as_deref()?;
+        let mut reference = ObjectRef::new(name);
+        if let Some(namespace) = &meta.namespace {
+            reference = reference.within(namespace);
+        }
+        self.services.iter().find_map(|store| store.get(&reference))
+    }
+}
+
+fn objects<K>(stores: &[Store<K>]) -> impl Iterator<Item = Arc<K>> + '_
+where
+    K: Resource + Clone + 'static,
+    K::DynamicType: Eq + Hash + Clone,
+{
+    stores.iter().flat_map(|store| store.state())
+}
+
+fn pod_targets(pod: &Pod, use_annotations: bool) -> Vec<Labels> {
+    let annotations = ScrapeAnnotations::from_meta(&pod.metadata);
+    if use_annotations && !annotations.scrape {
+        return Vec::new();
+    }
+    let (status, spec) = match (&pod.status, &pod.spec) {
+        (Some(status), Some(spec)) => (status, spec),
+        _ => return Vec::new(),
+    };
+    let ip = match &status.pod_ip {
+        Some(ip) => ip,
+        None => return Vec::new(),
+    };
+    // the pods which are done don't serve metrics anymore
+    if matches!(status.phase.as_deref(), Some("Succeeded") | Some("Failed")) {
+        return Vec::new();
+    }
+
+    let mut common = Labels::new();
+    object_labels("pod", &pod.metadata, &mut common);
+    insert(&mut common, "pod_ip", ip);
+    insert_some(&mut common, "pod_node_name", spec.node_name.as_ref());
+    insert_some(&mut common, "pod_phase", status.phase.as_ref());
+    insert_some(&mut common, "pod_uid", pod.metadata.uid.as_ref());
+    let ready = status
+        .conditions
+        .iter()
+        .flatten()
+        .any(|condition| condition.type_ == "Ready" && condition.status == "True");
+    insert(&mut common, "pod_ready", &ready.to_string());
+
+    let mut targets = Vec::new();
+    for container in &spec.containers {
+        let ports = container.ports.as_deref().unwrap_or_default();
+        if ports.is_empty() {
+            let mut labels = common.clone();
+            insert(&mut labels, "pod_container_name", &container.name);
+            labels.insert(ADDRESS_LABEL.to_owned(), ip.clone());
+            targets.push(labels);
+        }
+        for port in ports {
+            let mut labels = common.clone();
+            insert(&mut labels, "pod_container_name", &container.name);
+            insert_some(&mut labels, "pod_container_port_name", port.name.as_ref());
+            insert(
+                &mut labels,
+                "pod_container_port_number",
+                &port.container_port.to_string(),
+            );
+            insert_some(
+                &mut labels,
+                "pod_container_port_protocol",
+                port.protocol.as_ref(),
+            );
+            labels.insert(
+                ADDRESS_LABEL.to_owned(),
+                with_port(ip, &port.container_port.to_string()),
+            );
+            targets.push(labels);
+        }
+    }
+
+    if use_annotations {
+        annotations.apply(targets)
+    } else {
+        targets
+    }
+}
+
+fn endpoints_targets(
+    endpoints: &Endpoints,
+    service: Option<&Service>,
+    use_annotations: bool,
+) -> Vec<Labels> {
+    let annotations = service
+        .map(|service| ScrapeAnnotations::from_meta(&service.metadata))
+        .unwrap_or_default();
+    if use_annotations && !annotations.scrape {
+        return Vec::new();
+    }
+
+    let mut common = Labels::new();
+    object_labels("endpoints", &endpoints.metadata, &mut common);
+    if let Some(service) = service {
+        object_labels("service", &service.metadata, &mut common);
+    }
+
+    let mut targets = Vec::new();
+    for subset in endpoints.subsets.iter().flatten() {
+        let addresses = subset
+            .addresses
+            .iter()
+            .flatten()
+            .map(|address| (address, true))
+            .chain(
+                subset
+                    .not_ready_addresses
+                    .iter()
+                    .flatten()
+                    .map(|address| (address, false)),
+            );
+        for (address, ready) in addresses {
+            for port in subset.ports.iter().flatten() {
+                let mut labels = common.clone();
+                insert(&mut labels, "endpoint_ready", &ready.to_string());
+                insert_some(&mut labels, "endpoint_port_name", port.name.as_ref());
+                insert_some(
+                    &mut labels,
+                    "endpoint_port_protocol",
+                    port.protocol.as_ref(),
+                );
+                insert_some(&mut labels, "endpoint_hostname", address.hostname.as_ref());
+                insert_some(
+                    &mut labels,
+                    "endpoint_node_name",
+                    address.node_name.as_ref(),
+                );
+                if let Some(target) = &address.target_ref {
+                    insert_some(
+                        &mut labels,
+                        "endpoint_address_target_kind",
+                        target.kind.as_ref(),
+                    );
+                    insert_some(
+                        &mut labels,
+                        "endpoint_address_target_name",
+                        target.name.as_ref(),
+                    );
+                }
+                labels.insert(
+                    ADDRESS_LABEL.to_owned(),
+                    with_port(&address.ip, &port.port.to_string()),
+                );
+                targets.push(labels);
+            }
+        }
+    }
+
+    if use_annotations {
+        annotations.apply(targets)
+    } else {
+        targets
+    }
+}
+
+fn service_targets(service: &Service, use_annotations: bool) -> Vec<Labels> {
+    let annotations = ScrapeAnnotations::from_meta(&service.metadata);
+    if use_annotations && !annotations.scrape {
+        return Vec::new();
+    }
+    let (name, spec) = match (&service.metadata.name, &service.spec) {
+        (Some(name), Some(spec)) => (name, spec),
+        _ => return Vec::new(),
+    };
+    let host = match &service.metadata.namespace {
+        Some(namespace) => format!("{}.{}.svc", name, namespace),
+        None => name.clone(),
+    };
+
+    let mut common = Labels::new();
+    object_labels("service", &service.metadata, &mut common);
+    insert_some(&mut common, "service_type", spec.type_.as_ref());
+    insert_some(&mut common, "service_cluster_ip", spec.cluster_ip.as_ref());
+
+    let targets = spec
+        .ports
+        .iter()
+        .flatten()
+        .map(|port| {
+            let mut labels = common.clone();
+            insert_some(&mut labels, "service_port_name", port.name.as_ref());
+            insert(&mut labels, "service_port_number", &port.port.to_string());
+            insert_some(&mut labels, "service_port_protocol", port.protocol.as_ref());
+            labels.insert(
+                ADDRESS_LABEL.to_owned(),
+                with_port(&host, &port.port.to_string()),
+            );
+            labels
+        })
+        .collect();
+
+    if use_annotations {
+        annotations.apply(targets)
+    } else {
+        targets
+    }
+}
+
+/// Inserts the namespace, name, labels and annotations of an object of the given kind.
+fn object_labels(kind: &str, meta: &ObjectMeta, labels: &mut Labels) {
+    insert_some(labels, "namespace", meta.namespace.as_ref());
+    insert_some(labels, &format!("{}_name", kind), meta.name.as_ref());
+    for (name, value) in meta.labels.iter().flatten() {
+        let name = sanitize_label_name(name);
+        insert(labels, &format!("{}_label_{}", kind, name), value);
+        insert(labels, &format!("{}_labelpresent_{}", kind, name), "true");
+    }
+    for (name, value) in meta.annotations.iter().flatten() {
+        let name = sanitize_label_name(name);
+        insert(labels, &format!("{}_annotation_{}", kind, name), value);
+        insert(
+            labels,
+            &format!("{}_annotationpresent_{}", kind, name),
+            "true",
+        );
+    }
+}
+
+fn insert(labels: &mut Labels, name: &str, value: &str) {
+    labels.insert(format!("{}{}", LABEL_PREFIX, name), value.to_owned());
+}
+
+fn insert_some(labels: &mut Labels, name: &str, value: Option<&String>) {
+    if let Some(value) = value {
+        insert(labels, name, value);
+    }
+}
+
+/// Replaces the characters which aren't valid in label names by underscores, like Prometheus.
+fn sanitize_label_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Sets the port of an address, which may be an IPv6 address with or without a port.
+fn with_port(address: &str, port: &str) -> String {
+    let host = if let Some(rest) = address.strip_prefix('[') {
+        rest.split(']').next().unwrap_or(rest)
+    } else if address.matches(':').count() == 1 {
+        address.split(':').next().unwrap_or(address)
+    } else {
+        address
+    };
+    if host.contains(':') {
+        format!("[{}]:{}", host, port)
+    } else {
+        format!("{}:{}", host, port)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use k8s_openapi::api::core::v1::{Container, ContainerPort, PodCondition, PodSpec, PodStatus};
+
+    use super::*;
+
+    fn pod(annotations: &[(&str, &str)], ports: &[i32]) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some("web-0".to_owned()),
+                namespace: Some("default".to_owned()),
+                labels: Some(
+                    vec![("app.kubernetes.io/name".to_owned(), "web".to_owned())]
+                        .into_iter()
+                        .collect(),
+                ),
+                annotations: Some(
+                    annotations
+                        .iter()
+                        .map(|(name, value)| (name.to_string(), value.to_string()))
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+            spec: Some(PodSpec {
+                containers: vec![Container {
+                    name: "web".to_owned(),
+                    ports: Some(
+                        ports
+                            .iter()
+                            .map(|port| ContainerPort {
+                                container_port: *port,
+                                ..Default::default()
+                            })
+                            .collect(),
+                    ),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            status: Some(PodStatus {
+                pod_ip: Some("10.0.0.1".to_owned()),
+                phase: Some("Running".to_owned()),
+                conditions: Some(vec![PodCondition {
+                    type_: "Ready".to_owned(),
+                    status: "True".to_owned(),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn lists_pod_targets() {
+        let targets = pod_targets(&pod(&[], &[8080, 9090]), false);
+        assert_eq!(targets.len(), 2);
+        let labels = &targets[1];
+        assert_eq!(labels[ADDRESS_LABEL], "10.0.0.1:9090");
+        assert_eq!(labels["__meta_kubernetes_namespace"], "default");
+        assert_eq!(labels["__meta_kubernetes_pod_name"], "web-0");
+        assert_eq!(labels["__meta_kubernetes_pod_ready"], "true");
+        assert_eq!(labels["__meta_kubernetes_pod_container_name"], "web");
+        assert_eq!(
+            labels["__meta_kubernetes_pod_container_port_number"],
+            "9090"
+        );
+        assert_eq!(
+            labels["__meta_kubernetes_pod_label_app_kubernetes_io_name"],
+            "web"
+        );
+    }
+
+    #[test]
+    fn applies_pod_annotations() {
+        assert!(pod_targets(&pod(&[], &[8080]), true).is_empty());
+
+        let targets = pod_targets(
+            &pod(
+                &[
+                    ("prometheus.io/scrape", "true"),
+                    ("prometheus.io/port", "9102"),
+                    ("prometheus.io/path", "/stats"),
+                ],
+                &[8080, 9090],
+            ),
+            true,
+        );
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0][ADDRESS_LABEL], "10.0.0.1:9102");
+        assert_eq!(targets[0][METRICS_PATH_LABEL], "/stats");
+        assert_eq!(
+            targets[0]["__meta_kubernetes_pod_annotation_prometheus_io_port"],
+            "9102"
+        );
+    }
+
+    #[test]
+    fn sets_ports() {
+        assert_eq!(with_port("10.0.0.1", "80"), "10.0.0.1:80");
+        assert_eq!(with_port("10.0.0.1:8080", "80"), "10.0.0.1:80");
+        assert_eq!(with_port("fd00::1", "80"), "[fd00::1]:80");
+        assert_eq!(with_port("[fd00::1]:8080", "80"), "[fd00::1]:80");
+    }
+}
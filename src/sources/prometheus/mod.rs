@@ -1,4 +1,7 @@
+#[cfg(feature = "sources-prometheus-kubernetes")]
+mod kubernetes;
 pub(crate) mod parser;
+mod relabel;
 mod remote_write;
 mod scrape;
 
@@ -0,0 +1,466 @@
+//! Prometheus-style relabeling of the scrape targets.
+//!
+//! A target is described by its labels: `__scheme__`, `__address__` and `__metrics_path__` make up
+//! its URL, the `__param_<name>` labels its query parameters, and the `__meta_*` labels are set by
+//! the service discovery. The relabeling rules rewrite these labels in order, and may drop the
+//! target altogether. The labels left which don't start with `__` are added as tags to the metrics
+//! scraped from the target.
+
+use std::{collections::BTreeMap, convert::TryInto};
+
+use derivative::Derivative;
+use http::Uri;
+use md5::{Digest, Md5};
+use regex::Regex;
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+
+/// The labels of a target.
+pub(super) type Labels = BTreeMap<String, String>;
+
+pub(super) const ADDRESS_LABEL: &str = "__address__";
+pub(super) const SCHEME_LABEL: &str = "__scheme__";
+pub(super) const METRICS_PATH_LABEL: &str = "__metrics_path__";
+const PARAM_LABEL_PREFIX: &str = "__param_";
+
+#[derive(Debug, Snafu)]
+pub(super) enum RelabelError {
+    #[snafu(display("Invalid relabeling regex {:?}: {}", regex, source))]
+    InvalidRegex { regex: String, source: regex::Error },
+    #[snafu(display("The `{}` relabeling action requires `{}` to be set", action, option))]
+    MissingOption {
+        action: &'static str,
+        option: &'static str,
+    },
+}
+
+/// A relabeling rule, with the semantics of Prometheus' `relabel_configs`.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct RelabelConfig {
+    /// The labels whose values are concatenated and matched against `regex`.
+    ///
+    /// Missing labels have an empty value.
+    #[serde(default)]
+    source_labels: Vec<String>,
+
+    /// The separator placed between the concatenated values of the source labels.
+    #[serde(default = "default_separator")]
+    separator: String,
+
+    /// The label the result is written to, for the `replace` and `hashmod` actions.
+    target_label: Option<String>,
+
+    /// The regular expression matched against the concatenated values, or against the label names
+    /// for the `labelmap`, `labeldrop` and `labelkeep` actions.
+    ///
+    /// The regular expression is anchored at both ends.
+    #[serde(default = "default_regex")]
+    regex: String,
+
+    /// The modulus of the hash of the concatenated values, for the `hashmod` action.
+    modulus: Option<u64>,
+
+    /// The value written to the target label, for the `replace` and `labelmap` actions.
+    ///
+    /// Capture groups of `regex` are referred to as `$1`, `$2`, and so on.
+    #[serde(default = "default_replacement")]
+    replacement: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    action: RelabelAction,
+}
+
+fn default_separator() -> String {
+    ";".to_owned()
+}
+
+fn default_regex() -> String {
+    "(.*)".to_owned()
+}
+
+fn default_replacement() -> String {
+    "$1".to_owned()
+}
+
+/// The action of a relabeling rule.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RelabelAction {
+    /// Write the replacement to the target label if the values match the regular expression. The
+    /// target label is removed if the replacement is empty.
+    #[derivative(Default)]
+    Replace,
+
+    /// Drop the target if the values don't match the regular expression.
+    Keep,
+
+    /// Drop the target if the values match the regular expression.
+    Drop,
+
+    /// Write the hash of the values modulo `modulus` to the target label, to shard the targets.
+    Hashmod,
+
+    /// Copy the labels whose name matches the regular expression to the label named after the
+    /// replacement.
+    Labelmap,
+
+    /// Remove the labels whose name matches the regular expression.
+    Labeldrop,
+
+    /// Remove the labels whose name doesn't match the regular expression.
+    Labelkeep,
+}
+
+impl RelabelAction {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Replace => "replace",
+            Self::Keep => "keep",
+            Self::Drop => "drop",
+            Self::Hashmod => "hashmod",
+            Self::Labelmap => "labelmap",
+            Self::Labeldrop => "labeldrop",
+            Self::Labelkeep => "labelkeep",
+        }
+    }
+}
+
+/// The compiled relabeling rules.
+#[derive(Debug, Default)]
+pub(super) struct Relabeler {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug)]
+struct Rule {
+    source_labels: Vec<String>,
+    separator: String,
+    target_label: String,
+    regex: Regex,
+    modulus: u64,
+    replacement: String,
+    action: RelabelAction,
+}
+
+impl Relabeler {
+    pub(super) fn new(configs: &[RelabelConfig]) -> Result<Self, RelabelError> {
+        let rules = configs.iter().map(Rule::new).collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Applies the rules in order, returning `None` if the target is dropped.
+    pub(super) fn relabel(&self, mut labels: Labels) -> Option<Labels> {
+        self.rules
+            .iter()
+            .all(|rule| rule.apply(&mut labels))
+            .then_some(labels)
+    }
+}
+
+impl Rule {
+    fn new(config: &RelabelConfig) -> Result<Self, RelabelError> {
+        let action = config.action;
+        let missing = |option| RelabelError::MissingOption {
+            action: action.as_str(),
+            option,
+        };
+
+        let target_label = match action {
+            RelabelAction::Replace | RelabelAction::Hashmod => config
+                .target_label
+                .clone()
+                .ok_or_else(|| missing("target_label"))?,
+            _ => String::new(),
+        };
+        let modulus = match action {
+            RelabelAction::Hashmod => config
+                .modulus
+                .filter(|modulus| *modulus > 0)
+                .ok_or_else(|| missing("modulus"))?,
+            _ => 0,
+        };
+        let regex = Regex::new(&format!("^(?:{})$", config.regex)).context(InvalidRegexSnafu {
+            regex: config.regex.clone(),
+        })?;
+
+        Ok(Self {
+            source_labels: config.source_labels.clone(),
+            separator: config.separator.clone(),
+            target_label,
+            regex,
+            modulus,
+            replacement: config.replacement.clone(),
+            action,
+        })
+    }
+
+    /// Applies the rule, returning `false` if the target is dropped.
+    fn apply(&self, labels: &mut Labels) -> bool {
+        let value = self
+            .source_labels
+            .iter()
+            .map(|name| labels.get(name).map(String::as_str).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join(&self.separator);
+
+        match self.action {
+            RelabelAction::Keep => return self.regex.is_match(&value),
+            RelabelAction::Drop => return !self.regex.is_match(&value),
+            RelabelAction::Replace => {
+                if let Some(captures) = self.regex.captures(&value) {
+                    let mut target = String::new();
+                    captures.expand(&self.target_label, &mut target);
+                    let mut replacement = String::new();
+                    captures.expand(&self.replacement, &mut replacement);
+                    if replacement.is_empty() {
+                        labels.remove(&target);
+                    } else if !target.is_empty() {
+                        labels.insert(target, replacement);
+                    }
+                }
+            }
+            RelabelAction::Hashmod => {
+                // the same hash as Prometheus, so that targets are sharded alike
+                let hash = Md5::digest(value.as_bytes());
+                let sum = u64::from_be_bytes(hash[8..].try_into().expect("16 bytes long hash"));
+                labels.insert(self.target_label.clone(), (sum % self.modulus).to_string());
+            }
+            RelabelAction::Labelmap => {
+                let mapped = labels
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        self.regex.captures(name).map(|captures| {
+                            let mut name = String::new();
+                            captures.expand(&self.replacement, &mut name);
+                            (name, value.clone())
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                labels.extend(mapped);
+            }
+            RelabelAction::Labeldrop => labels.retain(|name, _| !self.regex.is_match(name)),
+            RelabelAction::Labelkeep => labels.retain(|name, _| self.regex.is_match(name)),
+        }
+        true
+    }
+}
+
+/// The labels of a configured endpoint, before relabeling.
+pub(super) fn url_labels(url: &Uri) -> Labels {
+    let mut labels = Labels::new();
+    labels.insert(
+        SCHEME_LABEL.to_owned(),
+        url.scheme_str().unwrap_or("http").to_owned(),
+    );
+    if let Some(authority) = url.authority() {
+        labels.insert(ADDRESS_LABEL.to_owned(), authority.to_string());
+    }
+    labels.insert(METRICS_PATH_LABEL.to_owned(), url.path().to_owned());
+    if let Some(query) = url.query() {
+        for (name, value) in url::form_urlencoded::parse(query.as_bytes()) {
+            labels.insert(
+                format!("{}{}", PARAM_LABEL_PREFIX, name),
+                value.into_owned(),
+            );
+        }
+    }
+    labels
+}
+
+/// Builds the URL of a target from its labels, returning `None` if it has no valid address.
+pub(super) fn target_url(labels: &Labels) -> Option<Uri> {
+    let address = labels
+        .get(ADDRESS_LABEL)
+        .filter(|address| !address.is_empty())?;
+    let scheme = labels.get(SCHEME_LABEL).map_or("http", String::as_str);
+    let path = labels
+        .get(METRICS_PATH_LABEL)
+        .map_or("/metrics", String::as_str);
+
+    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+    for (name, value) in labels {
+        if let Some(name) = name.strip_prefix(PARAM_LABEL_PREFIX) {
+            serializer.append_pair(name, value);
+        }
+    }
+    let query = serializer.finish();
+
+    let path = if path.starts_with('/') {
+        path.to_owned()
+    } else {
+        format!("/{}", path)
+    };
+    let url = if query.is_empty() {
+        format!("{}://{}{}", scheme, address, path)
+    } else {
+        format!("{}://{}{}?{}", scheme, address, path, query)
+    };
+    url.parse().ok()
+}
+
+/// The labels added as tags to the scraped metrics, which are all but the internal ones.
+pub(super) fn target_tags(labels: Labels) -> Labels {
+    labels
+        .into_iter()
+        .filter(|(name, _)| !name.starts_with("__"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relabeler(configs: &str) -> Relabeler {
+        #[derive(serde::Deserialize)]
+        struct Configs {
+            relabel_configs: Vec<RelabelConfig>,
+        }
+        let configs: Configs = toml::from_str(configs).unwrap();
+        Relabeler::new(&configs.relabel_configs).unwrap()
+    }
+
+    fn labels(pairs: &[(&str, &str)]) -> Labels {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn keeps_and_drops_targets() {
+        let relabeler = relabeler(
+            r#"
+            [[relabel_configs]]
+            source_labels = ["__meta_kubernetes_pod_annotation_prometheus_io_scrape"]
+            regex = "true"
+            action = "keep"
+
+            [[relabel_configs]]
+            source_labels = ["__meta_kubernetes_namespace"]
+            regex = "kube-.*"
+            action = "drop"
+            "#,
+        );
+
+        let kept = labels(&[
+            (
+                "__meta_kubernetes_pod_annotation_prometheus_io_scrape",
+                "true",
+            ),
+            ("__meta_kubernetes_namespace", "default"),
+        ]);
+        assert_eq!(relabeler.relabel(kept.clone()), Some(kept));
+        assert_eq!(
+            relabeler.relabel(labels(&[
+                (
+                    "__meta_kubernetes_pod_annotation_prometheus_io_scrape",
+                    "true"
+                ),
+                ("__meta_kubernetes_namespace", "kube-system"),
+            ])),
+            None
+        );
+        assert_eq!(
+            relabeler.relabel(labels(&[("__meta_kubernetes_namespace", "default")])),
+            None
+        );
+    }
+
+    #[test]
+    fn replaces_and_maps_labels() {
+        let relabeler = relabeler(
+            r#"
+            [[relabel_configs]]
+            source_labels = ["__address__", "__meta_kubernetes_pod_annotation_prometheus_io_port"]
+            regex = '([^:]+)(?::\d+)?;(\d+)'
+            replacement = "$1:$2"
+            target_label = "__address__"
+
+            [[relabel_configs]]
+            regex = "__meta_kubernetes_pod_label_(.+)"
+            action = "labelmap"
+
+            [[relabel_configs]]
+            regex = "tier"
+            action = "labeldrop"
+            "#,
+        );
+
+        let labels = relabeler
+            .relabel(labels(&[
+                ("__address__", "10.0.0.1:8080"),
+                (
+                    "__meta_kubernetes_pod_annotation_prometheus_io_port",
+                    "9102",
+                ),
+                ("__meta_kubernetes_pod_label_app", "web"),
+                ("__meta_kubernetes_pod_label_tier", "frontend"),
+            ]))
+            .unwrap();
+        assert_eq!(labels["__address__"], "10.0.0.1:9102");
+        assert_eq!(labels["app"], "web");
+        assert!(!labels.contains_key("tier"));
+        assert_eq!(target_tags(labels), self::labels(&[("app", "web")]),);
+    }
+
+    #[test]
+    fn shards_targets() {
+        let relabeler = relabeler(
+            r#"
+            [[relabel_configs]]
+            source_labels = ["__address__"]
+            modulus = 4
+            target_label = "__tmp_hash"
+            action = "hashmod"
+            "#,
+        );
+
+        let hash = |address| {
+            relabeler
+                .relabel(labels(&[("__address__", address)]))
+                .unwrap()["__tmp_hash"]
+                .parse::<u64>()
+                .unwrap()
+        };
+        assert!(hash("10.0.0.1:8080") < 4);
+        assert_eq!(hash("10.0.0.1:8080"), hash("10.0.0.1:8080"));
+    }
+
+    #[test]
+    fn rejects_incomplete_rules() {
+        for action in [RelabelAction::Replace, RelabelAction::Hashmod] {
+            let config = RelabelConfig {
+                source_labels: Vec::new(),
+                separator: default_separator(),
+                target_label: None,
+                regex: default_regex(),
+                modulus: None,
+                replacement: default_replacement(),
+                action,
+            };
+            assert!(Relabeler::new(&[config]).is_err());
+        }
+    }
+
+    #[test]
+    fn builds_target_urls() {
+        let url: Uri = "https://example.com:9090/federate?match%5B%5D=up"
+            .parse()
+            .unwrap();
+        assert_eq!(target_url(&url_labels(&url)), Some(url));
+
+        assert_eq!(
+            target_url(&labels(&[("__address__", "10.0.0.1:8080")])),
+            Some("http://10.0.0.1:8080/metrics".parse().unwrap())
+        );
+        assert_eq!(target_url(&labels(&[("__scheme__", "http")])), None);
+    }
+}
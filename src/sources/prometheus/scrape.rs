@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use bytes::Bytes;
 use futures_util::FutureExt;
@@ -6,9 +6,17 @@ use http::{response::Parts, Uri};
 
 use snafu::{ResultExt, Snafu};
 use vector_config::configurable_component;
-use vector_core::{config::LogNamespace, event::Event};
+use vector_core::{
+    config::LogNamespace,
+    event::{Event, Metric},
+};
 
-use super::parser;
+#[cfg(feature = "sources-prometheus-kubernetes")]
+use super::kubernetes::{KubernetesDiscovery, KubernetesSdConfig};
+use super::{
+    parser,
+    relabel::{self, Labels, RelabelConfig, Relabeler},
+};
 use crate::sources::util::http::HttpMethod;
 use crate::{
     config::{self, GenerateConfig, Output, SourceConfig, SourceContext},
@@ -37,6 +45,8 @@ static NOT_FOUND_NO_PATH: &str = "No path is set on the endpoint and we got a 40
 enum ConfigError {
     #[snafu(display("Cannot set both `endpoints` and `hosts`"))]
     BothEndpointsAndHosts,
+    #[snafu(display("Either `endpoints` or `kubernetes_sd` must be set"))]
+    NoEndpoints,
 }
 
 /// Configuration for the `prometheus_scrape` source.
@@ -44,9 +54,20 @@ enum ConfigError {
 #[derive(Clone, Debug)]
 pub struct PrometheusScrapeConfig {
     /// Endpoints to scrape metrics from.
-    #[serde(alias = "hosts")]
+    #[serde(alias = "hosts", default)]
     endpoints: Vec<String>,
 
+    #[cfg(feature = "sources-prometheus-kubernetes")]
+    #[configurable(derived)]
+    kubernetes_sd: Option<KubernetesSdConfig>,
+
+    /// Relabeling rules applied to the targets before they are scraped, like Prometheus' `relabel_configs`.
+    ///
+    /// The rules apply to the discovered targets as well as to the configured `endpoints`. The labels left which
+    /// don't start with `__` are added as tags to the scraped metrics.
+    #[serde(default)]
+    relabel_configs: Vec<RelabelConfig>,
+
     /// The interval between scrapes, in seconds.
     #[serde(default = "default_scrape_interval_secs")]
     scrape_interval_secs: u64,
@@ -98,6 +119,9 @@ impl GenerateConfig for PrometheusScrapeConfig {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
             query: HashMap::new(),
+            #[cfg(feature = "sources-prometheus-kubernetes")]
+            kubernetes_sd: None,
+            relabel_configs: Vec::new(),
             tls: None,
             auth: None,
         })
@@ -108,6 +132,13 @@ impl GenerateConfig for PrometheusScrapeConfig {
 #[async_trait::async_trait]
 impl SourceConfig for PrometheusScrapeConfig {
     async fn build(&self, cx: SourceContext) -> Result<sources::Source> {
+        #[cfg(feature = "sources-prometheus-kubernetes")]
+        let discovers_targets = self.kubernetes_sd.is_some();
+        #[cfg(not(feature = "sources-prometheus-kubernetes"))]
+        let discovers_targets = false;
+        if self.endpoints.is_empty() && !discovers_targets {
+            return Err(ConfigError::NoEndpoints.into());
+        }
         let urls = self
             .endpoints
             .iter()
@@ -115,11 +146,26 @@ impl SourceConfig for PrometheusScrapeConfig {
             .map(|r| r.map(|uri| build_url(&uri, &self.query)))
             .collect::<std::result::Result<Vec<Uri>, sources::BuildError>>()?;
         let tls = TlsSettings::from_options(&self.tls)?;
+        let relabeler = Arc::new(Relabeler::new(&self.relabel_configs)?);
+        #[cfg(feature = "sources-prometheus-kubernetes")]
+        let (discovery, reflectors) = match &self.kubernetes_sd {
+            Some(kubernetes_sd) => {
+                let (discovery, reflectors) = kubernetes_sd.build().await?;
+                (Some(discovery), reflectors)
+            }
+            None => (None, Vec::new()),
+        };
+        #[cfg(not(feature = "sources-prometheus-kubernetes"))]
+        let reflectors: Vec<tokio::task::JoinHandle<()>> = Vec::new();
 
         let builder = PrometheusScrapeBuilder {
             honor_labels: self.honor_labels,
             instance_tag: self.instance_tag.clone(),
             endpoint_tag: self.endpoint_tag.clone(),
+            query: self.query.clone(),
+            relabeler,
+            #[cfg(feature = "sources-prometheus-kubernetes")]
+            discovery,
         };
 
         let inputs = GenericHttpClientInputs {
@@ -133,7 +179,15 @@ impl SourceConfig for PrometheusScrapeConfig {
             shutdown: cx.shutdown,
         };
 
-        Ok(call(inputs, builder, cx.out, HttpMethod::Get).boxed())
+        Ok(async move {
+            let result = call(inputs, builder, cx.out, HttpMethod::Get).await;
+            // Stop Kubernetes object reflectors to avoid their leak on vector reload.
+            for reflector in reflectors {
+                reflector.abort();
+            }
+            result
+        }
+        .boxed())
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
@@ -171,11 +225,57 @@ struct PrometheusScrapeBuilder {
     honor_labels: bool,
     instance_tag: Option<String>,
     endpoint_tag: Option<String>,
+    query: HashMap<String, Vec<String>>,
+    relabeler: Arc<Relabeler>,
+    #[cfg(feature = "sources-prometheus-kubernetes")]
+    discovery: Option<KubernetesDiscovery>,
+}
+
+impl PrometheusScrapeBuilder {
+    /// Relabels a target, returning its URL and the context with the tags of its labels unless it
+    /// is dropped.
+    ///
+    /// The configured query parameters are appended to the URLs of the discovered targets, as the
+    /// configured endpoints already have them.
+    fn relabel(&self, labels: Labels, discovered: bool) -> Option<(Uri, PrometheusScrapeContext)> {
+        let labels = self.relabeler.relabel(labels)?;
+        let mut url = relabel::target_url(&labels)?;
+        if discovered {
+            url = build_url(&url, &self.query);
+        }
+        let mut context = self.build(&url);
+        context.target_tags = relabel::target_tags(labels);
+        Some((url, context))
+    }
 }
 
 impl HttpClientBuilder for PrometheusScrapeBuilder {
     type Context = PrometheusScrapeContext;
 
+    /// Relabels the configured endpoints and adds the discovered targets.
+    fn targets(&self, urls: &[Uri]) -> Vec<(Uri, Self::Context)> {
+        #[cfg_attr(not(feature = "sources-prometheus-kubernetes"), allow(unused_mut))]
+        let mut targets: Vec<_> = if self.relabeler.is_empty() {
+            urls.iter()
+                .map(|url| (url.clone(), self.build(url)))
+                .collect()
+        } else {
+            urls.iter()
+                .filter_map(|url| self.relabel(relabel::url_labels(url), false))
+                .collect()
+        };
+        #[cfg(feature = "sources-prometheus-kubernetes")]
+        if let Some(discovery) = &self.discovery {
+            targets.extend(
+                discovery
+                    .targets()
+                    .into_iter()
+                    .filter_map(|labels| self.relabel(labels, true)),
+            );
+        }
+        targets
+    }
+
     /// Expands the context with the instance info and endpoint info for the current request.
     fn build(&self, url: &Uri) -> Self::Context {
         let instance_info = self.instance_tag.as_ref().map(|tag| {
@@ -202,6 +302,8 @@ impl HttpClientBuilder for PrometheusScrapeBuilder {
         PrometheusScrapeContext {
             instance_info,
             endpoint_info,
+            target_tags: Labels::new(),
+            honor_labels: self.honor_labels,
         }
     }
 }
@@ -210,6 +312,9 @@ impl HttpClientBuilder for PrometheusScrapeBuilder {
 struct PrometheusScrapeContext {
     instance_info: Option<InstanceInfo>,
     endpoint_info: Option<EndpointInfo>,
+    /// The labels of the target left after relabeling.
+    target_tags: Labels,
+    honor_labels: bool,
 }
 
 impl HttpClientContext for PrometheusScrapeContext {
@@ -227,16 +332,7 @@ impl HttpClientContext for PrometheusScrapeContext {
                         honor_label,
                     }) = &self.instance_info
                     {
-                        match (honor_label, metric.tag_value(tag)) {
-                            (false, Some(old_instance)) => {
-                                metric.insert_tag(format!("exported_{}", tag), old_instance);
-                                metric.insert_tag(tag.clone(), instance.clone());
-                            }
-                            (true, Some(_)) => {}
-                            (_, None) => {
-                                metric.insert_tag(tag.clone(), instance.clone());
-                            }
-                        }
+                        insert_tag(metric, tag, instance, *honor_label);
                     }
                     if let Some(EndpointInfo {
                         tag,
@@ -244,16 +340,10 @@ impl HttpClientContext for PrometheusScrapeContext {
                         honor_label,
                     }) = &self.endpoint_info
                     {
-                        match (honor_label, metric.tag_value(tag)) {
-                            (false, Some(old_endpoint)) => {
-                                metric.insert_tag(format!("exported_{}", tag), old_endpoint);
-                                metric.insert_tag(tag.clone(), endpoint.clone());
-                            }
-                            (true, Some(_)) => {}
-                            (_, None) => {
-                                metric.insert_tag(tag.clone(), endpoint.clone());
-                            }
-                        }
+                        insert_tag(metric, tag, endpoint, *honor_label);
+                    }
+                    for (tag, value) in &self.target_tags {
+                        insert_tag(metric, tag, value, self.honor_labels);
                     }
                 }
                 Some(events)
@@ -287,6 +377,21 @@ impl HttpClientContext for PrometheusScrapeContext {
     }
 }
 
+/// Adds a tag to a scraped metric, renaming the scraped tag to `exported_<tag>` on conflicts unless
+/// labels are honored.
+fn insert_tag(metric: &mut Metric, tag: &str, value: &str, honor_label: bool) {
+    match (honor_label, metric.tag_value(tag)) {
+        (false, Some(old_value)) => {
+            metric.insert_tag(format!("exported_{}", tag), old_value);
+            metric.insert_tag(tag.to_owned(), value.to_owned());
+        }
+        (true, Some(_)) => {}
+        (_, None) => {
+            metric.insert_tag(tag.to_owned(), value.to_owned());
+        }
+    }
+}
+
 #[cfg(all(test, feature = "sinks-prometheus"))]
 mod test {
     use hyper::{
@@ -333,6 +438,10 @@ mod test {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: true,
             query: HashMap::new(),
+            #[cfg(feature = "sources-prometheus-kubernetes")]
+            #[cfg(feature = "sources-prometheus-kubernetes")]
+            kubernetes_sd: None,
+            relabel_configs: Vec::new(),
             auth: None,
             tls: None,
         };
@@ -366,6 +475,10 @@ mod test {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: true,
             query: HashMap::new(),
+            #[cfg(feature = "sources-prometheus-kubernetes")]
+            #[cfg(feature = "sources-prometheus-kubernetes")]
+            kubernetes_sd: None,
+            relabel_configs: Vec::new(),
             auth: None,
             tls: None,
         };
@@ -397,6 +510,58 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_prometheus_relabel_configs() {
+        let in_addr = next_addr();
+
+        let dummy_endpoint = warp::path!("metrics").map(|| {
+            r#"
+                    promhttp_metric_handler_requests_total{code="200"} 100 1612411516789
+                    "#
+        });
+
+        tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+        wait_for_tcp(in_addr).await;
+
+        let relabel_configs: Vec<RelabelConfig> = serde_json::from_value(serde_json::json!([
+            {"source_labels": ["__metrics_path__"], "regex": "/(.*)", "target_label": "job"},
+            {"source_labels": ["__address__"], "regex": "unreachable:.*", "action": "drop"},
+        ]))
+        .unwrap();
+        let config = PrometheusScrapeConfig {
+            endpoints: vec![
+                format!("http://{}/metrics", in_addr),
+                "http://unreachable:9090/metrics".to_string(),
+            ],
+            scrape_interval_secs: 1,
+            instance_tag: None,
+            endpoint_tag: None,
+            honor_labels: false,
+            query: HashMap::new(),
+            #[cfg(feature = "sources-prometheus-kubernetes")]
+            #[cfg(feature = "sources-prometheus-kubernetes")]
+            kubernetes_sd: None,
+            relabel_configs,
+            auth: None,
+            tls: None,
+        };
+
+        let events = run_and_assert_source_compliance(
+            config,
+            Duration::from_secs(3),
+            &HTTP_PULL_SOURCE_TAGS,
+        )
+        .await;
+        assert!(!events.is_empty());
+
+        for event in events {
+            assert_eq!(
+                event.into_metric().tag_value("job"),
+                Some(String::from("metrics"))
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_prometheus_do_not_honor_labels() {
         let in_addr = next_addr();
@@ -417,6 +582,10 @@ mod test {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
             query: HashMap::new(),
+            #[cfg(feature = "sources-prometheus-kubernetes")]
+            #[cfg(feature = "sources-prometheus-kubernetes")]
+            kubernetes_sd: None,
+            relabel_configs: Vec::new(),
             auth: None,
             tls: None,
         };
@@ -589,6 +758,9 @@ mod test {
                 endpoint_tag: None,
                 honor_labels: false,
                 query: HashMap::new(),
+                #[cfg(feature = "sources-prometheus-kubernetes")]
+                kubernetes_sd: None,
+                relabel_configs: Vec::new(),
                 scrape_interval_secs: 1,
                 tls: None,
                 auth: None,
@@ -679,6 +851,10 @@ mod integration_tests {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
             query: HashMap::new(),
+            #[cfg(feature = "sources-prometheus-kubernetes")]
+            #[cfg(feature = "sources-prometheus-kubernetes")]
+            kubernetes_sd: None,
+            relabel_configs: Vec::new(),
             auth: None,
             tls: None,
         };
@@ -11,20 +11,21 @@ use vector_common::internal_event::{
     ByteSize, BytesReceived, InternalEventHandle as _, Protocol, Registered,
 };
 use vector_config::configurable_component;
-use vector_core::config::LogNamespace;
+use vector_core::config::{AcknowledgementsConfig, LogNamespace};
 use vector_core::ByteSizeOf;
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{log_schema, GenerateConfig, Output, SourceConfig, SourceContext},
-    event::Event,
+    event::{BatchNotifier, Event},
     internal_events::{EventsReceived, StreamClosedError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
     SourceSender,
 };
 
 mod channel;
 mod list;
+mod stream;
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -46,6 +47,12 @@ pub enum DataTypeConfig {
     ///
     /// This is based on Redis' Pub/Sub capabilities.
     Channel,
+
+    /// The `stream` data type.
+    ///
+    /// Entries are read with a consumer group, and acknowledged once they are processed, so that
+    /// unacknowledged entries are read again after a restart.
+    Stream,
 }
 
 /// Options for the Redis `list` data type.
@@ -71,6 +78,73 @@ pub enum Method {
     Rpop,
 }
 
+/// Options for the Redis `stream` data type.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The consumer group to read entries with.
+    #[serde(default = "default_group")]
+    #[derivative(Default(value = "default_group()"))]
+    group: String,
+
+    /// The name of the consumer within the consumer group.
+    ///
+    /// Every instance of Vector reading from the same group needs a distinct name, as the pending
+    /// entries of a consumer are read again by the consumer of the same name after a restart.
+    #[serde(default = "default_consumer")]
+    #[derivative(Default(value = "default_consumer()"))]
+    consumer: String,
+
+    /// Whether to create the consumer group, and the stream, if they don't exist.
+    ///
+    /// Created groups start with the entries added after their creation.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    create_group: bool,
+
+    /// The minimum time, in seconds, for which entries of other consumers must have been pending
+    /// to be claimed on start.
+    ///
+    /// This takes over the entries of consumers which are gone. By default, no entries are
+    /// claimed. Requires Redis 6.2 or later.
+    claim_min_idle_secs: Option<u64>,
+
+    /// The maximum number of entries to read at once.
+    #[serde(default = "default_batch_size")]
+    #[derivative(Default(value = "default_batch_size()"))]
+    batch_size: usize,
+
+    /// The field of the entries holding the message to decode.
+    ///
+    /// Entries without the field are skipped.
+    #[serde(default = "default_stream_field")]
+    #[derivative(Default(value = "default_stream_field()"))]
+    field: String,
+
+    /// Sets the name of the log field to use to add the entry ID to each event.
+    ///
+    /// By default, this is not set and the field will not be automatically added.
+    id_key: Option<String>,
+}
+
+fn default_group() -> String {
+    "vector".to_owned()
+}
+
+fn default_consumer() -> String {
+    crate::get_hostname().unwrap_or_else(|_| "vector".to_owned())
+}
+
+const fn default_batch_size() -> usize {
+    100
+}
+
+fn default_stream_field() -> String {
+    "message".to_owned()
+}
+
 pub struct ConnectionInfo {
     protocol: &'static str,
     endpoint: String,
@@ -102,6 +176,9 @@ pub struct RedisSourceConfig {
     #[configurable(derived)]
     list: Option<ListOption>,
 
+    #[configurable(derived)]
+    stream: Option<StreamOption>,
+
     /// The Redis URL to connect to.
     ///
     /// The URL must take the form of `protocol://server:port/db` where the `protocol` can either be `redis` or `rediss` for connections secured via TLS.
@@ -126,6 +203,10 @@ pub struct RedisSourceConfig {
     #[serde(default = "default_decoding")]
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
 }
 
 impl GenerateConfig for RedisSourceConfig {
@@ -190,6 +271,19 @@ impl SourceConfig for RedisSourceConfig {
                 )
                 .await
             }
+            DataTypeConfig::Stream => {
+                stream::consume(
+                    client,
+                    bytes_received.clone(),
+                    self.key.clone(),
+                    self.redis_key.clone(),
+                    self.stream.clone().unwrap_or_default(),
+                    decoder,
+                    cx.do_acknowledgements(&self.acknowledgements),
+                    cx,
+                )
+                .await
+            }
         }
     }
 
@@ -198,7 +292,7 @@ impl SourceConfig for RedisSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        matches!(self.data_type, DataTypeConfig::Stream)
     }
 }
 
@@ -209,6 +303,31 @@ async fn handle_line(
     decoder: Decoder,
     bytes_received: &Registered<BytesReceived>,
     out: &mut SourceSender,
+) -> Result<(), ()> {
+    handle_message(
+        line,
+        key,
+        redis_key,
+        &[],
+        decoder,
+        bytes_received,
+        None,
+        out,
+    )
+    .await
+}
+
+/// Decodes a message into events, adding the given extra fields to them.
+#[allow(clippy::too_many_arguments)]
+async fn handle_message(
+    line: String,
+    key: &str,
+    redis_key: Option<&str>,
+    extra_fields: &[(&str, &str)],
+    decoder: Decoder,
+    bytes_received: &Registered<BytesReceived>,
+    batch: Option<&BatchNotifier>,
+    out: &mut SourceSender,
 ) -> Result<(), ()> {
     let now = Utc::now();
 
@@ -231,8 +350,14 @@ async fn handle_line(
                         if let Some(redis_key) = redis_key {
                             event.as_mut_log().insert(redis_key, key);
                         }
+                        for (field, value) in extra_fields {
+                            event.as_mut_log().insert(*field, *value);
+                        }
+                    }
+                    match batch {
+                        Some(batch) => event.with_batch_notifier(batch),
+                        None => event,
                     }
-                    event
                 });
 
                 if let Err(error) = out.send_batch(events).await {
@@ -268,6 +393,7 @@ mod integration_test {
 
     use super::*;
     use crate::config::log_schema;
+    use crate::event::EventStatus;
     use crate::test_util::components::{run_and_assert_source_compliance_n, SOURCE_TAGS};
     use crate::{
         test_util::{collect_n, random_string},
@@ -295,11 +421,13 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Rpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 3, &SOURCE_TAGS).await;
@@ -328,11 +456,13 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Lpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 3, &SOURCE_TAGS).await;
@@ -342,6 +472,59 @@ mod integration_test {
         assert_eq!(events[2].as_log()[log_schema().message_key()], "3".into());
     }
 
+    #[tokio::test]
+    async fn redis_source_stream_acknowledges_entries() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_tokio_connection_manager().await.unwrap();
+
+        let key = format!("test-stream-{}", random_string(10));
+        debug!("Test key name: {}.", key);
+
+        // Create the group before adding the entries, so that it reads all of them.
+        let _: () = conn
+            .xgroup_create_mkstream(&key, "vector", "0")
+            .await
+            .unwrap();
+        for message in ["1", "2", "3"] {
+            let _: String = conn.xadd(&key, "*", &[("message", message)]).await.unwrap();
+        }
+
+        let config = RedisSourceConfig {
+            data_type: DataTypeConfig::Stream,
+            list: None,
+            stream: Some(StreamOption {
+                id_key: Some("stream_id".to_owned()),
+                ..Default::default()
+            }),
+            url: REDIS_SERVER.to_owned(),
+            key: key.clone(),
+            redis_key: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            acknowledgements: true.into(),
+        };
+
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let context = SourceContext::new_test(tx, None);
+        let source = config
+            .build(context)
+            .await
+            .expect("source should not fail to build");
+        tokio::spawn(source);
+
+        let events = collect_n(rx, 3).await;
+        assert_eq!(events[0].as_log()[log_schema().message_key()], "1".into());
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "2".into());
+        assert_eq!(events[2].as_log()[log_schema().message_key()], "3".into());
+        assert!(events[0].as_log().contains("stream_id"));
+
+        // Give the source a moment to acknowledge the entries.
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        let pending: redis::streams::StreamPendingReply =
+            conn.xpending(&key, "vector").await.unwrap();
+        assert_eq!(pending.count(), 0);
+    }
+
     #[tokio::test]
     async fn redis_source_channel_consume_event() {
         let key = format!("test-channel-{}", random_string(10));
@@ -351,11 +534,13 @@ mod integration_test {
         let config = RedisSourceConfig {
             data_type: DataTypeConfig::Channel,
             list: None,
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
         };
 
         let (tx, rx) = SourceSender::new_test();
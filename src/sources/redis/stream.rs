@@ -0,0 +1,300 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use redis::{
+    aio::ConnectionManager,
+    streams::{StreamId, StreamPendingCountReply, StreamReadOptions, StreamReadReply},
+    AsyncCommands, RedisResult, Value,
+};
+use snafu::{ResultExt, Snafu};
+use vector_common::{
+    finalizer::OrderedFinalizer,
+    internal_event::{BytesReceived, Registered},
+};
+
+use super::{handle_message, StreamOption};
+use crate::{
+    codecs,
+    config::SourceContext,
+    event::{BatchNotifier, BatchStatus},
+    internal_events::RedisReceiveEventError,
+    sources::Source,
+    SourceSender,
+};
+
+/// How long to block for new entries, so that errors of the connection surface.
+const BLOCK_MILLIS: usize = 1_000;
+/// How long to wait after an error, before reading again.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Failed to create connection: {}", source))]
+    Connection { source: redis::RedisError },
+    #[snafu(display("Failed to create consumer group: {}", source))]
+    CreateGroup { source: redis::RedisError },
+}
+
+/// Where to read the entries of the consumer from.
+enum Position {
+    /// Entries which were read before, but not acknowledged, after the given ID.
+    Pending(String),
+    /// Entries which were never read by the group.
+    New,
+}
+
+impl Position {
+    fn id(&self) -> &str {
+        match self {
+            Self::Pending(id) => id,
+            Self::New => ">",
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn consume(
+    client: redis::Client,
+    bytes_received: Registered<BytesReceived>,
+    key: String,
+    redis_key: Option<String>,
+    options: StreamOption,
+    decoder: codecs::Decoder,
+    acknowledgements: bool,
+    cx: SourceContext,
+) -> crate::Result<Source> {
+    let mut conn = client
+        .get_tokio_connection_manager()
+        .await
+        .context(ConnectionSnafu {})?;
+    // Reads block their connection, so that entries are acknowledged with a connection of their own.
+    let mut ack_conn = conn.clone();
+    if acknowledgements {
+        ack_conn = client
+            .get_tokio_connection_manager()
+            .await
+            .context(ConnectionSnafu {})?;
+    }
+
+    if options.create_group {
+        create_group(&mut conn, &key, &options.group)
+            .await
+            .context(CreateGroupSnafu {})?;
+    }
+
+    Ok(Box::pin(async move {
+        let mut shutdown = cx.shutdown;
+        let mut out = cx.out;
+
+        let (finalizer, mut ack_stream) =
+            OrderedFinalizer::<Vec<String>>::maybe_new(acknowledgements, shutdown.clone());
+        let ack_key = key.clone();
+        let ack_group = options.group.clone();
+        tokio::spawn(async move {
+            while let Some((status, ids)) = ack_stream.next().await {
+                handle_ack(&mut ack_conn, &ack_key, &ack_group, status, ids).await;
+            }
+        });
+
+        if let Some(min_idle_secs) = options.claim_min_idle_secs {
+            if let Err(error) = claim_pending(&mut conn, &key, &options, min_idle_secs).await {
+                emit!(RedisReceiveEventError::from(error));
+            }
+        }
+
+        // Without acknowledgements entries aren't kept pending, so that there are none to read.
+        let mut position = if acknowledgements {
+            Position::Pending("0".to_owned())
+        } else {
+            Position::New
+        };
+        loop {
+            let mut read_options = StreamReadOptions::default()
+                .group(&options.group, &options.consumer)
+                .count(options.batch_size);
+            match position {
+                Position::Pending(_) => {}
+                Position::New if acknowledgements => {
+                    read_options = read_options.block(BLOCK_MILLIS)
+                }
+                Position::New => read_options = read_options.block(BLOCK_MILLIS).noack(),
+            }
+
+            let reply: RedisResult<Option<StreamReadReply>> = tokio::select! {
+                reply = conn.xread_options(&[&key], &[position.id()], &read_options) => reply,
+                _ = &mut shutdown => break,
+            };
+            let entries = match reply {
+                Ok(reply) => reply
+                    .map(|reply| reply.keys.into_iter().flat_map(|key| key.ids).collect())
+                    .unwrap_or_default(),
+                Err(error) => {
+                    emit!(RedisReceiveEventError::from(error));
+                    tokio::time::sleep(RETRY_DELAY).await;
+                    continue;
+                }
+            };
+
+            if let Position::Pending(_) = position {
+                position = match entries.last() {
+                    Some(entry) => Position::Pending(entry.id.clone()),
+                    None => Position::New,
+                };
+            }
+            if entries.is_empty() {
+                continue;
+            }
+
+            if handle_entries(
+                entries,
+                &key,
+                redis_key.as_deref(),
+                &options,
+                &decoder,
+                &bytes_received,
+                finalizer.as_ref(),
+                &mut out,
+            )
+            .await
+            .is_err()
+            {
+                break;
+            }
+        }
+        Ok(())
+    }))
+}
+
+async fn create_group(conn: &mut ConnectionManager, key: &str, group: &str) -> RedisResult<()> {
+    match conn
+        .xgroup_create_mkstream::<_, _, _, ()>(key, group, "$")
+        .await
+    {
+        // The group exists already.
+        Err(error) if error.code() == Some("BUSYGROUP") => Ok(()),
+        result => result,
+    }
+}
+
+/// Claims the entries of other consumers, which have been pending for longer than the minimum
+/// idle time, so that they are read as pending entries of this consumer.
+async fn claim_pending(
+    conn: &mut ConnectionManager,
+    key: &str,
+    options: &StreamOption,
+    min_idle_secs: u64,
+) -> RedisResult<()> {
+    let min_idle_millis = min_idle_secs.saturating_mul(1_000) as usize;
+    let mut start = "-".to_owned();
+    loop {
+        let reply: StreamPendingCountReply = conn
+            .xpending_count(key, &options.group, &start, "+", options.batch_size)
+            .await?;
+        let last = match reply.ids.last() {
+            Some(last) => last.id.clone(),
+            None => return Ok(()),
+        };
+
+        let ids = reply
+            .ids
+            .iter()
+            .filter(|pending| {
+                pending.consumer != options.consumer && pending.last_delivered_ms >= min_idle_millis
+            })
+            .map(|pending| pending.id.as_str())
+            .collect::<Vec<_>>();
+        if !ids.is_empty() {
+            debug!(message = "Claiming pending entries.", count = ids.len());
+            conn.xclaim::<_, _, _, _, _, Value>(
+                key,
+                &options.group,
+                &options.consumer,
+                min_idle_millis,
+                &ids,
+            )
+            .await?;
+        }
+
+        // Continue after the last entry, exclusive ranges were added in Redis 6.2.
+        start = format!("({}", last);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn handle_entries(
+    entries: Vec<StreamId>,
+    key: &str,
+    redis_key: Option<&str>,
+    options: &StreamOption,
+    decoder: &codecs::Decoder,
+    bytes_received: &Registered<BytesReceived>,
+    finalizer: Option<&OrderedFinalizer<Vec<String>>>,
+    out: &mut SourceSender,
+) -> Result<(), ()> {
+    let (batch, receiver) = match finalizer {
+        Some(_) => {
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            (Some(batch), Some(receiver))
+        }
+        None => (None, None),
+    };
+
+    let mut ids = Vec::with_capacity(entries.len());
+    for entry in entries {
+        match entry.get::<String>(&options.field) {
+            Some(line) => {
+                let extra_fields = match options.id_key.as_deref() {
+                    Some(id_key) => vec![(id_key, entry.id.as_str())],
+                    None => Vec::new(),
+                };
+                handle_message(
+                    line,
+                    key,
+                    redis_key,
+                    &extra_fields,
+                    decoder.clone(),
+                    bytes_received,
+                    batch.as_ref(),
+                    out,
+                )
+                .await?;
+            }
+            None => {
+                warn!(
+                    message = "Skipping stream entry without the message field.",
+                    id = %entry.id,
+                    field = %options.field,
+                    internal_log_rate_limit = true,
+                );
+            }
+        }
+        ids.push(entry.id);
+    }
+
+    // The receiver resolves once the events are processed, skipped entries are acknowledged
+    // along with them.
+    drop(batch);
+    if let (Some(finalizer), Some(receiver)) = (finalizer, receiver) {
+        finalizer.add(ids, receiver);
+    }
+    Ok(())
+}
+
+async fn handle_ack(
+    conn: &mut ConnectionManager,
+    key: &str,
+    group: &str,
+    status: BatchStatus,
+    ids: Vec<String>,
+) {
+    match status {
+        // Rejected events would be rejected again, so that they are acknowledged as well.
+        BatchStatus::Delivered | BatchStatus::Rejected => {
+            if let Err(error) = conn.xack::<_, _, _, usize>(key, group, &ids).await {
+                emit!(RedisReceiveEventError::from(error));
+            }
+        }
+        // The entries stay pending, and are read again after a restart.
+        BatchStatus::Errored => {}
+    }
+}
@@ -0,0 +1,298 @@
+//! Blocking clients of SFTP and FTP servers, which are used from `spawn_blocking`.
+
+use std::{
+    io::{self, Read},
+    net::{TcpStream, ToSocketAddrs},
+    path::Path,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use snafu::{ResultExt, Snafu};
+use ssh2::{HashType, Session, Sftp};
+use suppaftp::{list, native_tls, types::FileType, FtpError, FtpStream, TlsConnector};
+
+#[derive(Debug, Snafu)]
+pub enum ClientError {
+    #[snafu(display("Could not connect to {}: {}", address, source))]
+    Connect { address: String, source: io::Error },
+    #[snafu(display("SSH error: {}", source))]
+    Ssh { source: ssh2::Error },
+    #[snafu(display("Host key has the unexpected fingerprint {}", fingerprint))]
+    HostKeyMismatch { fingerprint: String },
+    #[snafu(display("FTP error: {}", source))]
+    Ftp { source: FtpError },
+    #[snafu(display("Could not set up TLS: {}", source))]
+    Tls { source: native_tls::Error },
+    #[snafu(display("Could not read {:?}: {}", path, source))]
+    Read { path: String, source: io::Error },
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum Protocol {
+    Sftp,
+    /// FTP with explicit TLS, which is set up after connecting with `AUTH TLS`.
+    Ftps,
+    Ftp,
+}
+
+impl Protocol {
+    pub(super) fn from_scheme(scheme: &str) -> Option<Self> {
+        match scheme {
+            "sftp" => Some(Self::Sftp),
+            "ftps" => Some(Self::Ftps),
+            "ftp" => Some(Self::Ftp),
+            _ => None,
+        }
+    }
+
+    pub(super) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Sftp => "sftp",
+            Self::Ftps => "ftps",
+            Self::Ftp => "ftp",
+        }
+    }
+
+    pub(super) const fn default_port(self) -> u16 {
+        match self {
+            Self::Sftp => 22,
+            Self::Ftps | Self::Ftp => 21,
+        }
+    }
+}
+
+/// The remote directory to poll.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) struct Endpoint {
+    pub(super) protocol: Protocol,
+    pub(super) host: String,
+    pub(super) port: u16,
+    pub(super) directory: String,
+}
+
+impl Endpoint {
+    pub(super) fn path_of(&self, name: &str) -> String {
+        format!("{}/{}", self.directory.trim_end_matches('/'), name)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct Auth {
+    pub(super) username: String,
+    pub(super) password: Option<String>,
+    pub(super) private_key_path: Option<std::path::PathBuf>,
+    pub(super) private_key_passphrase: Option<String>,
+    pub(super) host_key_fingerprint: Option<String>,
+}
+
+/// A regular file of the remote directory.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) struct RemoteFile {
+    pub(super) name: String,
+    pub(super) size: u64,
+    /// The Unix timestamp of the last modification, in seconds.
+    pub(super) modified: Option<i64>,
+}
+
+pub(super) enum Client {
+    Sftp { session: Session, sftp: Sftp },
+    Ftp(FtpStream),
+}
+
+impl Client {
+    pub(super) fn connect(
+        endpoint: &Endpoint,
+        auth: &Auth,
+        timeout: Duration,
+    ) -> Result<Self, ClientError> {
+        let address = format!("{}:{}", endpoint.host, endpoint.port);
+
+        match endpoint.protocol {
+            Protocol::Sftp => {
+                let stream = connect_tcp(&address, timeout).context(ConnectSnafu { address })?;
+                let mut session = Session::new().context(SshSnafu)?;
+                session.set_timeout(timeout.as_millis() as u32);
+                session.set_tcp_stream(stream);
+                session.handshake().context(SshSnafu)?;
+
+                if let Some(expected) = &auth.host_key_fingerprint {
+                    let fingerprint = session
+                        .host_key_hash(HashType::Sha256)
+                        .map(fingerprint_of)
+                        .unwrap_or_default();
+                    if !fingerprint_matches(expected, &fingerprint) {
+                        return Err(ClientError::HostKeyMismatch { fingerprint });
+                    }
+                }
+
+                match &auth.private_key_path {
+                    Some(path) => session.userauth_pubkey_file(
+                        &auth.username,
+                        None,
+                        path,
+                        auth.private_key_passphrase.as_deref(),
+                    ),
+                    None => session.userauth_password(
+                        &auth.username,
+                        auth.password.as_deref().unwrap_or_default(),
+                    ),
+                }
+                .context(SshSnafu)?;
+
+                let sftp = session.sftp().context(SshSnafu)?;
+                Ok(Self::Sftp { session, sftp })
+            }
+            Protocol::Ftps | Protocol::Ftp => {
+                // suppaftp 4 opens the connection itself, so the timeout only
+                // applies once it's established.
+                let mut ftp = FtpStream::connect(&address).context(FtpSnafu)?;
+                let stream = ftp.get_ref();
+                stream
+                    .set_read_timeout(Some(timeout))
+                    .and_then(|()| stream.set_write_timeout(Some(timeout)))
+                    .context(ConnectSnafu { address })?;
+                if endpoint.protocol == Protocol::Ftps {
+                    let connector = native_tls::TlsConnector::new().context(TlsSnafu)?;
+                    ftp = ftp
+                        .into_secure(TlsConnector::from(connector), &endpoint.host)
+                        .context(FtpSnafu)?;
+                }
+                ftp.login(
+                    auth.username.as_str(),
+                    auth.password.as_deref().unwrap_or_default(),
+                )
+                .context(FtpSnafu)?;
+                ftp.transfer_type(FileType::Binary).context(FtpSnafu)?;
+                Ok(Self::Ftp(ftp))
+            }
+        }
+    }
+
+    /// Lists the regular files of a directory, without its subdirectories.
+    pub(super) fn list(&mut self, directory: &str) -> Result<Vec<RemoteFile>, ClientError> {
+        match self {
+            Self::Sftp { sftp, .. } => Ok(sftp
+                .readdir(Path::new(directory))
+                .context(SshSnafu)?
+                .into_iter()
+                .filter(|(_, stat)| stat.is_file())
+                .filter_map(|(path, stat)| {
+                    Some(RemoteFile {
+                        name: path.file_name()?.to_str()?.to_owned(),
+                        size: stat.size.unwrap_or_default(),
+                        modified: stat.mtime.map(|mtime| mtime as i64),
+                    })
+                })
+                .collect()),
+            Self::Ftp(ftp) => Ok(ftp
+                .list(Some(directory))
+                .context(FtpSnafu)?
+                .iter()
+                .filter_map(|line| line.parse::<list::File>().ok())
+                .filter(|file| file.is_file())
+                .map(|file| RemoteFile {
+                    name: file.name().to_owned(),
+                    size: file.size() as u64,
+                    modified: file
+                        .modified()
+                        .duration_since(UNIX_EPOCH)
+                        .ok()
+                        .map(|modified| modified.as_secs() as i64),
+                })
+                .collect()),
+        }
+    }
+
+    pub(super) fn download(&mut self, path: &str) -> Result<Vec<u8>, ClientError> {
+        match self {
+            Self::Sftp { sftp, .. } => {
+                let mut file = sftp.open(Path::new(path)).context(SshSnafu)?;
+                let mut data = Vec::new();
+                file.read_to_end(&mut data).context(ReadSnafu {
+                    path: path.to_owned(),
+                })?;
+                Ok(data)
+            }
+            Self::Ftp(ftp) => Ok(ftp.retr_as_buffer(path).context(FtpSnafu)?.into_inner()),
+        }
+    }
+
+    /// Closes the connection, ignoring errors since it's not used anymore.
+    pub(super) fn close(self) {
+        match self {
+            Self::Sftp { session, sftp } => {
+                drop(sftp);
+                let _ = session.disconnect(None, "", None);
+            }
+            Self::Ftp(mut ftp) => {
+                let _ = ftp.quit();
+            }
+        }
+    }
+}
+
+fn connect_tcp(address: &str, timeout: Duration) -> io::Result<TcpStream> {
+    let mut last_error = None;
+    for address in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&address, timeout) {
+            Ok(stream) => {
+                stream.set_read_timeout(Some(timeout))?;
+                stream.set_write_timeout(Some(timeout))?;
+                return Ok(stream);
+            }
+            Err(error) => last_error = Some(error),
+        }
+    }
+    Err(last_error
+        .unwrap_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No address found for host")))
+}
+
+/// The fingerprint of a host key, formatted like OpenSSH does.
+fn fingerprint_of(hash: &[u8]) -> String {
+    format!(
+        "SHA256:{}",
+        base64::encode_config(hash, base64::STANDARD_NO_PAD)
+    )
+}
+
+fn fingerprint_matches(expected: &str, fingerprint: &str) -> bool {
+    let expected = expected.trim().trim_end_matches('=');
+    let expected = expected.strip_prefix("SHA256:").unwrap_or(expected);
+    fingerprint.strip_prefix("SHA256:") == Some(expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_fingerprints() {
+        let fingerprint = fingerprint_of(&[0xab; 32]);
+        assert_eq!(
+            fingerprint,
+            "SHA256:q6urq6urq6urq6urq6urq6urq6urq6urq6urq6urq6s"
+        );
+
+        assert!(fingerprint_matches(&fingerprint, &fingerprint));
+        assert!(fingerprint_matches(
+            "q6urq6urq6urq6urq6urq6urq6urq6urq6urq6urq6s=",
+            &fingerprint
+        ));
+        assert!(!fingerprint_matches(
+            "SHA256:AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+            &fingerprint
+        ));
+        assert!(!fingerprint_matches("", ""));
+    }
+
+    #[test]
+    fn joins_paths() {
+        let endpoint = Endpoint {
+            protocol: Protocol::Sftp,
+            host: "files.example.com".to_owned(),
+            port: 22,
+            directory: "/incoming/".to_owned(),
+        };
+        assert_eq!(endpoint.path_of("app.log"), "/incoming/app.log");
+    }
+}
@@ -0,0 +1,546 @@
+//! Polls a directory of an SFTP or FTP server, reading the files added to it.
+//!
+//! The clients are blocking, so they are used from `spawn_blocking`, and a connection is opened
+//! for each poll.
+
+use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
+
+use bytes::Bytes;
+use codecs::decoding::{DeserializerConfig, FramingConfig};
+use futures::StreamExt;
+use glob::Pattern;
+use snafu::{ResultExt, Snafu};
+use tokio::time;
+use tokio_stream::wrappers::IntervalStream;
+use url::Url;
+use vector_common::{
+    internal_event::{self, ByteSize, BytesReceived, InternalEventHandle as _},
+    sensitive_string::SensitiveString,
+};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{
+        log_schema, AcknowledgementsConfig, GenerateConfig, Output, SourceConfig, SourceContext,
+    },
+    event::{BatchNotifier, BatchStatus, MaybeAsLogMut},
+    internal_events::{SftpDownloadError, SftpListError, SftpStateError, StreamClosedError},
+    serde::{bool_or_struct, default_decoding},
+    shutdown::ShutdownSignal,
+    sources::util,
+    SourceSender,
+};
+
+mod client;
+mod state;
+
+pub use client::ClientError;
+use client::{Auth, Client, Endpoint, Protocol, RemoteFile};
+use state::{FileVersion, ProcessedFiles};
+
+const STATE_FILENAME: &str = "processed.json";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid endpoint {:?}: {}", endpoint, source))]
+    InvalidEndpoint {
+        endpoint: String,
+        source: url::ParseError,
+    },
+    #[snafu(display(
+        "Unsupported scheme {:?} of the endpoint, `sftp`, `ftps` and `ftp` are supported",
+        scheme
+    ))]
+    UnsupportedScheme { scheme: String },
+    #[snafu(display("The endpoint {:?} has no host", endpoint))]
+    MissingHost { endpoint: String },
+    #[snafu(display(
+        "`private_key_path` and `host_key_fingerprint` are only supported with SFTP"
+    ))]
+    SftpOnlyOptions,
+    #[snafu(display("Invalid pattern {:?}: {}", pattern, source))]
+    InvalidPattern {
+        pattern: String,
+        source: glob::PatternError,
+    },
+    #[snafu(display("Could not read the processed files from {:?}: {}", path, source))]
+    ReadState {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// Configuration for the `sftp` source.
+#[configurable_component(source("sftp"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SftpConfig {
+    /// The URL of the directory to poll, such as `sftp://files.example.com/incoming`.
+    ///
+    /// SFTP is used with an `sftp://` URL, FTP with explicit TLS with an `ftps://` URL, and FTP
+    /// without TLS with an `ftp://` URL.
+    pub endpoint: String,
+
+    /// The username to log in with.
+    pub username: String,
+
+    /// The password to log in with.
+    ///
+    /// With SFTP, it's not used if `private_key_path` is set.
+    pub password: Option<SensitiveString>,
+
+    /// The path of the private key to log in with, for SFTP.
+    pub private_key_path: Option<PathBuf>,
+
+    /// The passphrase of the private key.
+    pub private_key_passphrase: Option<SensitiveString>,
+
+    /// The SHA256 fingerprint the host key of the SFTP server is expected to have, formatted like
+    /// OpenSSH does, such as `SHA256:q6urq6urq6urq6urq6urq6urq6urq6urq6urq6urq6s`.
+    ///
+    /// If not set, the host key isn't verified.
+    pub host_key_fingerprint: Option<String>,
+
+    /// Glob patterns of the file names to read.
+    ///
+    /// The files of subdirectories aren't read.
+    #[serde(default = "default_include")]
+    pub include: Vec<String>,
+
+    /// Glob patterns of the file names not to read, even if they are included.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+
+    /// The interval between listings of the directory, in seconds.
+    ///
+    /// A file is only read once it has the same size and modification time in two listings, so
+    /// that files that are still uploaded aren't read.
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+
+    /// The timeout of connecting to the server, and of each request, in seconds.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// The directory used to persist the names of the files read.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    pub data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    pub framing: Option<FramingConfig>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_include() -> Vec<String> {
+    vec!["*".to_owned()]
+}
+
+const fn default_interval_secs() -> u64 {
+    60
+}
+
+const fn default_timeout_secs() -> u64 {
+    30
+}
+
+impl GenerateConfig for SftpConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            endpoint: "sftp://files.example.com/incoming".to_owned(),
+            username: "vector".to_owned(),
+            password: None,
+            private_key_path: Some("/etc/vector/id_ed25519".into()),
+            private_key_passphrase: None,
+            host_key_fingerprint: None,
+            include: vec!["*.log".to_owned()],
+            exclude: Vec::new(),
+            interval_secs: default_interval_secs(),
+            timeout_secs: default_timeout_secs(),
+            data_dir: None,
+            framing: None,
+            decoding: default_decoding(),
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for SftpConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let endpoint = self.parse_endpoint()?;
+        if endpoint.protocol != Protocol::Sftp
+            && (self.private_key_path.is_some() || self.host_key_fingerprint.is_some())
+        {
+            return Err(BuildError::SftpOnlyOptions.into());
+        }
+        let auth = Auth {
+            username: self.username.clone(),
+            password: self
+                .password
+                .as_ref()
+                .map(|password| password.inner().to_owned()),
+            private_key_path: self.private_key_path.clone(),
+            private_key_passphrase: self
+                .private_key_passphrase
+                .as_ref()
+                .map(|passphrase| passphrase.inner().to_owned()),
+            host_key_fingerprint: self.host_key_fingerprint.clone(),
+        };
+
+        let mut state_path = cx
+            .globals
+            // source are only global, name can be used for subdir
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        state_path.push(STATE_FILENAME);
+        let processed = ProcessedFiles::load(&state_path)
+            .await
+            .context(ReadStateSnafu { path: &state_path })?;
+
+        let framing = self
+            .framing
+            .clone()
+            .unwrap_or_else(|| self.decoding.default_stream_framing());
+        let decoder =
+            DecodingConfig::new(framing, self.decoding.clone(), LogNamespace::Legacy).build();
+
+        Ok(Box::pin(
+            SftpSource {
+                endpoint: Arc::new(endpoint),
+                auth: Arc::new(auth),
+                include: parse_patterns(&self.include)?,
+                exclude: parse_patterns(&self.exclude)?,
+                interval: Duration::from_secs(self.interval_secs),
+                timeout: Duration::from_secs(self.timeout_secs),
+                state_path,
+                decoder,
+                acknowledgements: cx.do_acknowledgements(&self.acknowledgements),
+            }
+            .run(processed, cx.out, cx.shutdown),
+        ))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(self.decoding.output_type())]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl SftpConfig {
+    fn parse_endpoint(&self) -> Result<Endpoint, BuildError> {
+        let url = Url::parse(&self.endpoint).context(InvalidEndpointSnafu {
+            endpoint: &self.endpoint,
+        })?;
+        let protocol =
+            Protocol::from_scheme(url.scheme()).ok_or_else(|| BuildError::UnsupportedScheme {
+                scheme: url.scheme().to_owned(),
+            })?;
+        let host = url
+            .host_str()
+            .ok_or_else(|| BuildError::MissingHost {
+                endpoint: self.endpoint.clone(),
+            })?
+            .to_owned();
+        let directory = match url.path() {
+            "" => "/".to_owned(),
+            path => path.to_owned(),
+        };
+        Ok(Endpoint {
+            protocol,
+            host,
+            port: url.port().unwrap_or_else(|| protocol.default_port()),
+            directory,
+        })
+    }
+}
+
+fn parse_patterns(patterns: &[String]) -> Result<Vec<Pattern>, BuildError> {
+    patterns
+        .iter()
+        .map(|pattern| Pattern::new(pattern).context(InvalidPatternSnafu { pattern }))
+        .collect()
+}
+
+struct SftpSource {
+    endpoint: Arc<Endpoint>,
+    auth: Arc<Auth>,
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    interval: Duration,
+    timeout: Duration,
+    state_path: PathBuf,
+    decoder: Decoder,
+    acknowledgements: bool,
+}
+
+impl SftpSource {
+    async fn run(
+        self,
+        mut processed: ProcessedFiles,
+        mut out: SourceSender,
+        shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        let bytes_received = register!(BytesReceived::from(internal_event::Protocol::from(
+            self.endpoint.protocol.as_str()
+        )));
+        // The versions of the files in the last listing, to tell whether they are still uploaded.
+        let mut listed = HashMap::new();
+
+        let mut interval = IntervalStream::new(time::interval(self.interval)).take_until(shutdown);
+        while interval.next().await.is_some() {
+            let (client, files) = match self.list().await {
+                Ok(listing) => listing,
+                Err(error) => {
+                    emit!(SftpListError {
+                        error,
+                        endpoint: &self.endpoint.host,
+                    });
+                    continue;
+                }
+            };
+            let mut client = Some(client);
+
+            if processed.retain(&files) {
+                self.save(&processed).await;
+            }
+            let previous = std::mem::replace(
+                &mut listed,
+                files
+                    .iter()
+                    .map(|file| (file.name.clone(), FileVersion::from(file)))
+                    .collect::<HashMap<_, _>>(),
+            );
+
+            // Files are read once they didn't change since the last listing.
+            let unread = files
+                .iter()
+                .filter(|file| previous.get(&file.name) == Some(&FileVersion::from(*file)))
+                .filter(|file| !processed.contains(file))
+                .collect::<Vec<_>>();
+            for file in unread {
+                let path = self.endpoint.path_of(&file.name);
+                let (returned, result) = self.download(client.take(), path.clone()).await;
+                client = returned;
+                let data = match result {
+                    Ok(data) => data,
+                    Err(error) => {
+                        emit!(SftpDownloadError { error, path: &path });
+                        continue;
+                    }
+                };
+                bytes_received.emit(ByteSize(data.len()));
+
+                if self.send(&data, &path, &mut out).await? {
+                    processed.insert(file);
+                    self.save(&processed).await;
+                }
+            }
+
+            if let Some(client) = client {
+                tokio::task::spawn_blocking(move || client.close());
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<(Client, Vec<RemoteFile>), ClientError> {
+        let endpoint = Arc::clone(&self.endpoint);
+        let auth = Arc::clone(&self.auth);
+        let timeout = self.timeout;
+        let (client, files) = tokio::task::spawn_blocking(move || {
+            let mut client = Client::connect(&endpoint, &auth, timeout)?;
+            let files = client.list(&endpoint.directory)?;
+            Ok::<_, ClientError>((client, files))
+        })
+        .await
+        .expect("SFTP client panicked")?;
+
+        let files = files
+            .into_iter()
+            .filter(|file| self.is_included(&file.name))
+            .collect();
+        Ok((client, files))
+    }
+
+    /// Downloads a file with the client, connecting again if the last download failed.
+    async fn download(
+        &self,
+        client: Option<Client>,
+        path: String,
+    ) -> (Option<Client>, Result<Vec<u8>, ClientError>) {
+        let endpoint = Arc::clone(&self.endpoint);
+        let auth = Arc::clone(&self.auth);
+        let timeout = self.timeout;
+        tokio::task::spawn_blocking(move || {
+            let mut client = match client {
+                Some(client) => client,
+                None => match Client::connect(&endpoint, &auth, timeout) {
+                    Ok(client) => client,
+                    Err(error) => return (None, Err(error)),
+                },
+            };
+            match client.download(&path) {
+                Ok(data) => (Some(client), Ok(data)),
+                Err(error) => {
+                    client.close();
+                    (None, Err(error))
+                }
+            }
+        })
+        .await
+        .expect("SFTP client panicked")
+    }
+
+    fn is_included(&self, name: &str) -> bool {
+        self.include.iter().any(|pattern| pattern.matches(name))
+            && !self.exclude.iter().any(|pattern| pattern.matches(name))
+    }
+
+    /// Sends the events of a file, returning whether they were delivered, or rejected by sinks.
+    /// The file is read again if they errored.
+    async fn send(&self, data: &[u8], path: &str, out: &mut SourceSender) -> Result<bool, ()> {
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let host = Bytes::from(self.endpoint.host.clone());
+        let file = Bytes::from(path.to_owned());
+        let events = util::decode_message(self.decoder.clone(), "sftp", data, None, &batch)
+            .map(|mut event| {
+                if let Some(log) = event.maybe_as_log_mut() {
+                    log.try_insert(log_schema().host_key(), host.clone());
+                    log.insert("file", file.clone());
+                }
+                event
+            })
+            .collect::<Vec<_>>();
+        drop(batch);
+
+        let count = events.len();
+        if count > 0 {
+            if let Err(error) = out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                return Err(());
+            }
+        }
+
+        Ok(match receiver {
+            None => true,
+            Some(receiver) => match receiver.await {
+                BatchStatus::Delivered => true,
+                BatchStatus::Errored => false,
+                // Sinks are responsible for emitting ComponentEventsDropped.
+                // Failed events cannot be retried, so the file isn't read again.
+                BatchStatus::Rejected => true,
+            },
+        })
+    }
+
+    async fn save(&self, processed: &ProcessedFiles) {
+        if let Err(error) = processed.save(&self.state_path).await {
+            emit!(SftpStateError {
+                error,
+                path: &self.state_path,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(endpoint: &str) -> SftpConfig {
+        toml::from_str(&format!(
+            r#"
+            endpoint = "{}"
+            username = "vector"
+            "#,
+            endpoint
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SftpConfig>();
+    }
+
+    #[test]
+    fn parses_endpoints() {
+        assert_eq!(
+            config("sftp://files.example.com/incoming")
+                .parse_endpoint()
+                .unwrap(),
+            Endpoint {
+                protocol: Protocol::Sftp,
+                host: "files.example.com".to_owned(),
+                port: 22,
+                directory: "/incoming".to_owned(),
+            }
+        );
+        assert_eq!(
+            config("ftps://files.example.com:2121")
+                .parse_endpoint()
+                .unwrap(),
+            Endpoint {
+                protocol: Protocol::Ftps,
+                host: "files.example.com".to_owned(),
+                port: 2121,
+                directory: "/".to_owned(),
+            }
+        );
+        assert!(config("http://files.example.com/")
+            .parse_endpoint()
+            .is_err());
+    }
+
+    #[test]
+    fn includes_files() {
+        let mut config = config("sftp://files.example.com/incoming");
+        config.include = vec!["*.log".to_owned(), "*.json".to_owned()];
+        config.exclude = vec!["*.tmp.*".to_owned()];
+        let source = SftpSource {
+            endpoint: Arc::new(config.parse_endpoint().unwrap()),
+            auth: Arc::new(Auth {
+                username: config.username.clone(),
+                password: None,
+                private_key_path: None,
+                private_key_passphrase: None,
+                host_key_fingerprint: None,
+            }),
+            include: parse_patterns(&config.include).unwrap(),
+            exclude: parse_patterns(&config.exclude).unwrap(),
+            interval: Duration::from_secs(config.interval_secs),
+            timeout: Duration::from_secs(config.timeout_secs),
+            state_path: PathBuf::from(STATE_FILENAME),
+            decoder: Default::default(),
+            acknowledgements: false,
+        };
+
+        assert!(source.is_included("app.log"));
+        assert!(source.is_included("app.json"));
+        assert!(!source.is_included("app.tmp.log"));
+        assert!(!source.is_included("app.csv"));
+    }
+
+    #[test]
+    fn parses_defaults() {
+        let config = config("sftp://files.example.com/incoming");
+        assert_eq!(config.include, vec!["*"]);
+        assert!(config.exclude.is_empty());
+        assert_eq!(config.interval_secs, 60);
+        assert_eq!(config.timeout_secs, 30);
+    }
+}
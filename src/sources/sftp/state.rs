@@ -0,0 +1,114 @@
+use std::{collections::BTreeMap, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use super::client::RemoteFile;
+
+/// The size and modification time of a file, which tell whether it changed.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub(super) struct FileVersion {
+    size: u64,
+    modified: Option<i64>,
+}
+
+impl From<&RemoteFile> for FileVersion {
+    fn from(file: &RemoteFile) -> Self {
+        Self {
+            size: file.size,
+            modified: file.modified,
+        }
+    }
+}
+
+/// The versions of the files which were read, by file name.
+#[derive(Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub(super) struct ProcessedFiles {
+    files: BTreeMap<String, FileVersion>,
+}
+
+impl ProcessedFiles {
+    pub(super) async fn load(path: &Path) -> io::Result<Self> {
+        match tokio::fs::read(path).await {
+            Ok(data) => serde_json::from_slice(&data)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Writes to a temporary file first, so that the file is replaced as a whole.
+    pub(super) async fn save(&self, path: &Path) -> io::Result<()> {
+        let temporary = path.with_extension("tmp");
+        tokio::fs::write(&temporary, serde_json::to_vec(self)?).await?;
+        tokio::fs::rename(&temporary, path).await
+    }
+
+    /// Whether this version of the file was read already.
+    pub(super) fn contains(&self, file: &RemoteFile) -> bool {
+        self.files.get(&file.name) == Some(&FileVersion::from(file))
+    }
+
+    pub(super) fn insert(&mut self, file: &RemoteFile) {
+        self.files.insert(file.name.clone(), file.into());
+    }
+
+    /// Forgets the files which were removed from the directory, so that a file added again with
+    /// the same name is read. Returns whether any file was forgotten.
+    pub(super) fn retain(&mut self, files: &[RemoteFile]) -> bool {
+        let count = self.files.len();
+        self.files
+            .retain(|name, _| files.iter().any(|file| &file.name == name));
+        self.files.len() != count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, size: u64) -> RemoteFile {
+        RemoteFile {
+            name: name.to_owned(),
+            size,
+            modified: Some(1_666_000_000),
+        }
+    }
+
+    #[test]
+    fn changed_files_are_not_contained() {
+        let mut processed = ProcessedFiles::default();
+        processed.insert(&file("a.log", 10));
+
+        assert!(processed.contains(&file("a.log", 10)));
+        assert!(!processed.contains(&file("a.log", 20)));
+        assert!(!processed.contains(&file("b.log", 10)));
+    }
+
+    #[test]
+    fn retains_listed_files() {
+        let mut processed = ProcessedFiles::default();
+        processed.insert(&file("a.log", 10));
+        processed.insert(&file("b.log", 10));
+
+        assert!(!processed.retain(&[file("a.log", 10), file("b.log", 10)]));
+        assert!(processed.retain(&[file("b.log", 10)]));
+        assert!(!processed.contains(&file("a.log", 10)));
+        assert!(processed.contains(&file("b.log", 10)));
+    }
+
+    #[tokio::test]
+    async fn saves_and_loads() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("processed.json");
+        assert_eq!(
+            ProcessedFiles::load(&path).await.unwrap(),
+            ProcessedFiles::default()
+        );
+
+        let mut processed = ProcessedFiles::default();
+        processed.insert(&file("a.log", 10));
+        processed.save(&path).await.unwrap();
+
+        assert_eq!(ProcessedFiles::load(&path).await.unwrap(), processed);
+    }
+}
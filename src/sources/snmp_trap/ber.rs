@@ -0,0 +1,390 @@
+//! Decoding of the BER encoded SNMP messages, which carry traps and informs.
+//! Only the subset of BER used by SNMP is supported, i.e. single byte tags and definite lengths.
+
+use bytes::Bytes;
+use snafu::Snafu;
+
+pub(super) const INTEGER: u8 = 0x02;
+pub(super) const OCTET_STRING: u8 = 0x04;
+pub(super) const NULL: u8 = 0x05;
+pub(super) const OBJECT_IDENTIFIER: u8 = 0x06;
+pub(super) const SEQUENCE: u8 = 0x30;
+pub(super) const IP_ADDRESS: u8 = 0x40;
+pub(super) const COUNTER32: u8 = 0x41;
+pub(super) const GAUGE32: u8 = 0x42;
+pub(super) const TIME_TICKS: u8 = 0x43;
+pub(super) const OPAQUE: u8 = 0x44;
+pub(super) const COUNTER64: u8 = 0x46;
+pub(super) const NO_SUCH_OBJECT: u8 = 0x80;
+pub(super) const NO_SUCH_INSTANCE: u8 = 0x81;
+pub(super) const END_OF_MIB_VIEW: u8 = 0x82;
+pub(super) const RESPONSE: u8 = 0xa2;
+pub(super) const INFORM_REQUEST: u8 = 0xa6;
+pub(super) const SNMPV2_TRAP: u8 = 0xa7;
+
+const VERSION_2C: i64 = 1;
+const VERSION_3: i64 = 3;
+
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub(super) enum DecodeError {
+    #[snafu(display("Malformed message: {}", reason))]
+    Malformed { reason: &'static str },
+    #[snafu(display("Unsupported SNMP version: {}", version))]
+    UnsupportedVersion { version: i64 },
+    #[snafu(display("Unsupported PDU type: {:#x}", tag))]
+    UnsupportedPdu { tag: u8 },
+    #[snafu(display("Unsupported security model: {}", model))]
+    UnsupportedSecurityModel { model: i64 },
+    #[snafu(display("Unknown community"))]
+    UnknownCommunity,
+    #[snafu(display("Unknown user: {}", user))]
+    UnknownUser { user: String },
+    #[snafu(display("Authentication failed: {}", reason))]
+    Authentication { reason: &'static str },
+    #[snafu(display("Decryption failed: {}", reason))]
+    Decryption { reason: String },
+}
+
+const fn malformed(reason: &'static str) -> DecodeError {
+    DecodeError::Malformed { reason }
+}
+
+/// Reads the tag-length-value elements of a buffer one by one.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Reader<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(super) const fn new(input: &'a [u8]) -> Self {
+        Self { input }
+    }
+
+    pub(super) const fn is_empty(&self) -> bool {
+        self.input.is_empty()
+    }
+
+    /// Reads the next element, returning its tag and contents.
+    pub(super) fn read_any(&mut self) -> Result<(u8, &'a [u8]), DecodeError> {
+        let (&tag, rest) = self
+            .input
+            .split_first()
+            .ok_or(malformed("unexpected end"))?;
+        let (&first, mut rest) = rest.split_first().ok_or(malformed("missing length"))?;
+        let length = if first & 0x80 == 0 {
+            first as usize
+        } else {
+            let count = (first & 0x7f) as usize;
+            if count == 0 || count > std::mem::size_of::<usize>() || rest.len() < count {
+                return Err(malformed("invalid length"));
+            }
+            let (bytes, after) = rest.split_at(count);
+            rest = after;
+            bytes
+                .iter()
+                .fold(0usize, |length, byte| (length << 8) | *byte as usize)
+        };
+        if rest.len() < length {
+            return Err(malformed("length exceeds message"));
+        }
+        let (contents, rest) = rest.split_at(length);
+        self.input = rest;
+        Ok((tag, contents))
+    }
+
+    /// Reads the next element, which must have the given tag.
+    pub(super) fn read(&mut self, expected: u8) -> Result<&'a [u8], DecodeError> {
+        match self.read_any()? {
+            (tag, contents) if tag == expected => Ok(contents),
+            _ => Err(malformed("unexpected element")),
+        }
+    }
+
+    pub(super) fn read_integer(&mut self) -> Result<i64, DecodeError> {
+        decode_integer(self.read(INTEGER)?)
+    }
+
+    pub(super) fn read_octet_string(&mut self) -> Result<&'a [u8], DecodeError> {
+        self.read(OCTET_STRING)
+    }
+
+    pub(super) fn read_sequence(&mut self) -> Result<Reader<'a>, DecodeError> {
+        self.read(SEQUENCE).map(Reader::new)
+    }
+}
+
+pub(super) fn decode_integer(bytes: &[u8]) -> Result<i64, DecodeError> {
+    if bytes.is_empty() || bytes.len() > 8 {
+        return Err(malformed("invalid integer"));
+    }
+    // sign extend from the first byte
+    let initial = if bytes[0] & 0x80 == 0 { 0 } else { -1 };
+    Ok(bytes
+        .iter()
+        .fold(initial, |value: i64, byte| (value << 8) | *byte as i64))
+}
+
+pub(super) fn decode_unsigned(bytes: &[u8]) -> Result<u64, DecodeError> {
+    // unsigned values may have a leading zero byte, so that they aren't negative
+    let bytes = match bytes {
+        [0, rest @ ..] if !rest.is_empty() => rest,
+        bytes => bytes,
+    };
+    if bytes.is_empty() || bytes.len() > 8 {
+        return Err(malformed("invalid unsigned integer"));
+    }
+    Ok(bytes
+        .iter()
+        .fold(0u64, |value, byte| (value << 8) | *byte as u64))
+}
+
+pub(super) fn decode_oid(bytes: &[u8]) -> Result<Vec<u32>, DecodeError> {
+    let mut subids = Vec::with_capacity(bytes.len() + 1);
+    let mut subid = 0u32;
+    for (index, byte) in bytes.iter().enumerate() {
+        subid = subid
+            .checked_mul(128)
+            .ok_or(malformed("object identifier component overflows"))?
+            | (byte & 0x7f) as u32;
+        if byte & 0x80 != 0 {
+            if index == bytes.len() - 1 {
+                return Err(malformed("truncated object identifier"));
+            }
+            continue;
+        }
+        if subids.is_empty() {
+            // the first component encodes the first two subidentifiers
+            let first = (subid / 40).min(2);
+            subids.push(first);
+            subids.push(subid - first * 40);
+        } else {
+            subids.push(subid);
+        }
+        subid = 0;
+    }
+    if subids.is_empty() {
+        return Err(malformed("empty object identifier"));
+    }
+    Ok(subids)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum VarBindValue {
+    Integer(i64),
+    OctetString(Bytes),
+    Null,
+    ObjectIdentifier(Vec<u32>),
+    IpAddress([u8; 4]),
+    Counter32(u64),
+    Gauge32(u64),
+    TimeTicks(u64),
+    Opaque(Bytes),
+    Counter64(u64),
+    NoSuchObject,
+    NoSuchInstance,
+    EndOfMibView,
+}
+
+impl VarBindValue {
+    fn decode(tag: u8, contents: &[u8]) -> Result<Self, DecodeError> {
+        Ok(match tag {
+            INTEGER => Self::Integer(decode_integer(contents)?),
+            OCTET_STRING => Self::OctetString(Bytes::copy_from_slice(contents)),
+            NULL => Self::Null,
+            OBJECT_IDENTIFIER => Self::ObjectIdentifier(decode_oid(contents)?),
+            IP_ADDRESS => Self::IpAddress(
+                contents
+                    .try_into()
+                    .map_err(|_| malformed("invalid IP address"))?,
+            ),
+            COUNTER32 => Self::Counter32(decode_unsigned(contents)?),
+            GAUGE32 => Self::Gauge32(decode_unsigned(contents)?),
+            TIME_TICKS => Self::TimeTicks(decode_unsigned(contents)?),
+            OPAQUE => Self::Opaque(Bytes::copy_from_slice(contents)),
+            COUNTER64 => Self::Counter64(decode_unsigned(contents)?),
+            NO_SUCH_OBJECT => Self::NoSuchObject,
+            NO_SUCH_INSTANCE => Self::NoSuchInstance,
+            END_OF_MIB_VIEW => Self::EndOfMibView,
+            _ => return Err(malformed("unknown value type")),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct VarBind {
+    pub(super) oid: Vec<u32>,
+    pub(super) value: VarBindValue,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum PduKind {
+    Trap,
+    Inform,
+}
+
+impl PduKind {
+    pub(super) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Trap => "trap",
+            Self::Inform => "inform",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct Pdu {
+    pub(super) kind: PduKind,
+    pub(super) request_id: i64,
+    pub(super) varbinds: Vec<VarBind>,
+}
+
+/// Decodes a trap or inform PDU, given the element's tag and contents.
+pub(super) fn decode_pdu(tag: u8, contents: &[u8]) -> Result<Pdu, DecodeError> {
+    let kind = match tag {
+        SNMPV2_TRAP => PduKind::Trap,
+        INFORM_REQUEST => PduKind::Inform,
+        tag => return Err(DecodeError::UnsupportedPdu { tag }),
+    };
+    let mut reader = Reader::new(contents);
+    let request_id = reader.read_integer()?;
+    // error status and index are always zero for traps and informs
+    reader.read_integer()?;
+    reader.read_integer()?;
+
+    let mut list = reader.read_sequence()?;
+    let mut varbinds = Vec::new();
+    while !list.is_empty() {
+        let mut varbind = list.read_sequence()?;
+        let oid = decode_oid(varbind.read(OBJECT_IDENTIFIER)?)?;
+        let (tag, contents) = varbind.read_any()?;
+        varbinds.push(VarBind {
+            oid,
+            value: VarBindValue::decode(tag, contents)?,
+        });
+    }
+
+    Ok(Pdu {
+        kind,
+        request_id,
+        varbinds,
+    })
+}
+
+/// Decodes a scoped PDU, i.e. the plaintext of an SNMPv3 message, ignoring any trailing padding
+/// of the encryption.
+pub(super) fn decode_scoped_pdu(input: &[u8]) -> Result<Pdu, DecodeError> {
+    let mut scoped = Reader::new(input).read_sequence()?;
+    // the context engine identifier and name
+    scoped.read_octet_string()?;
+    scoped.read_octet_string()?;
+    let (tag, contents) = scoped.read_any()?;
+    decode_pdu(tag, contents)
+}
+
+/// The user-based security model parameters of an SNMPv3 message.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct UsmParameters<'a> {
+    pub(super) engine_id: &'a [u8],
+    pub(super) engine_boots: i64,
+    pub(super) engine_time: i64,
+    pub(super) user_name: &'a [u8],
+    /// A slice of the message, so that it can be zeroed when verifying the authentication.
+    pub(super) authentication: &'a [u8],
+    pub(super) privacy: &'a [u8],
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(super) enum ScopedPduData<'a> {
+    Plaintext(&'a [u8]),
+    Encrypted(&'a [u8]),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(super) struct V3Message<'a> {
+    pub(super) flags: u8,
+    pub(super) security: UsmParameters<'a>,
+    pub(super) data: ScopedPduData<'a>,
+}
+
+impl V3Message<'_> {
+    pub(super) const fn authenticated(&self) -> bool {
+        self.flags & 0x01 != 0
+    }
+
+    pub(super) const fn encrypted(&self) -> bool {
+        self.flags & 0x02 != 0
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) enum Message<'a> {
+    V2c {
+        community: &'a [u8],
+        /// The offset of the PDU tag in the message, so that informs can be answered by only
+        /// changing the tag to a response.
+        pdu_offset: usize,
+        pdu: Pdu,
+    },
+    V3(V3Message<'a>),
+}
+
+/// The offset of a slice within the message it was read from.
+pub(super) fn offset_in(message: &[u8], part: &[u8]) -> usize {
+    part.as_ptr() as usize - message.as_ptr() as usize
+}
+
+pub(super) fn decode_message(input: &[u8]) -> Result<Message<'_>, DecodeError> {
+    let mut message = Reader::new(input).read_sequence()?;
+    match message.read_integer()? {
+        VERSION_2C => {
+            let community = message.read_octet_string()?;
+            let pdu_offset = offset_in(input, message.input);
+            let (tag, contents) = message.read_any()?;
+            Ok(Message::V2c {
+                community,
+                pdu_offset,
+                pdu: decode_pdu(tag, contents)?,
+            })
+        }
+        VERSION_3 => {
+            let mut global = message.read_sequence()?;
+            // the message identifier and maximum size
+            global.read_integer()?;
+            global.read_integer()?;
+            let flags = *global
+                .read_octet_string()?
+                .first()
+                .ok_or(malformed("empty message flags"))?;
+            match global.read_integer()? {
+                // the user-based security model
+                3 => {}
+                model => return Err(DecodeError::UnsupportedSecurityModel { model }),
+            }
+
+            let mut parameters = Reader::new(message.read_octet_string()?).read_sequence()?;
+            let security = UsmParameters {
+                engine_id: parameters.read_octet_string()?,
+                engine_boots: parameters.read_integer()?,
+                engine_time: parameters.read_integer()?,
+                user_name: parameters.read_octet_string()?,
+                authentication: parameters.read_octet_string()?,
+                privacy: parameters.read_octet_string()?,
+            };
+
+            // plaintext is decoded later, as a whole element
+            let scoped = message.input;
+            let data = match message.read_any()? {
+                (SEQUENCE, _) => ScopedPduData::Plaintext(scoped),
+                (OCTET_STRING, encrypted) => ScopedPduData::Encrypted(encrypted),
+                _ => return Err(malformed("invalid scoped PDU")),
+            };
+
+            Ok(Message::V3(V3Message {
+                flags,
+                security,
+                data,
+            }))
+        }
+        version => Err(DecodeError::UnsupportedVersion { version }),
+    }
+}
@@ -0,0 +1,390 @@
+//! Names of object identifiers, loaded from MIB modules.
+//! Only the object identifier assignments are read, e.g. `ifIndex OBJECT-TYPE ... ::= { ifEntry 1 }`,
+//! the rest of the SMI definitions are skipped.
+
+use std::{collections::HashMap, fs, path::Path};
+
+/// The definitions, which are needed to name the object identifiers of every trap, and the roots
+/// of the common MIB modules, so that only the modules of the traps need to be provided.
+const BUILTIN: &str = r#"
+SNMPv2-SMI DEFINITIONS ::= BEGIN
+org            OBJECT IDENTIFIER ::= { iso 3 }
+dod            OBJECT IDENTIFIER ::= { org 6 }
+internet       OBJECT IDENTIFIER ::= { dod 1 }
+directory      OBJECT IDENTIFIER ::= { internet 1 }
+mgmt           OBJECT IDENTIFIER ::= { internet 2 }
+mib-2          OBJECT IDENTIFIER ::= { mgmt 1 }
+transmission   OBJECT IDENTIFIER ::= { mib-2 10 }
+experimental   OBJECT IDENTIFIER ::= { internet 3 }
+private        OBJECT IDENTIFIER ::= { internet 4 }
+enterprises    OBJECT IDENTIFIER ::= { private 1 }
+security       OBJECT IDENTIFIER ::= { internet 5 }
+snmpV2         OBJECT IDENTIFIER ::= { internet 6 }
+snmpDomains    OBJECT IDENTIFIER ::= { snmpV2 1 }
+snmpProxys     OBJECT IDENTIFIER ::= { snmpV2 2 }
+snmpModules    OBJECT IDENTIFIER ::= { snmpV2 3 }
+END
+
+SNMPv2-MIB DEFINITIONS ::= BEGIN
+system                OBJECT IDENTIFIER ::= { mib-2 1 }
+sysDescr              OBJECT-TYPE ::= { system 1 }
+sysObjectID           OBJECT-TYPE ::= { system 2 }
+sysUpTime             OBJECT-TYPE ::= { system 3 }
+sysContact            OBJECT-TYPE ::= { system 4 }
+sysName               OBJECT-TYPE ::= { system 5 }
+sysLocation           OBJECT-TYPE ::= { system 6 }
+snmpMIB               MODULE-IDENTITY ::= { snmpModules 1 }
+snmpMIBObjects        OBJECT IDENTIFIER ::= { snmpMIB 1 }
+snmpTrap              OBJECT IDENTIFIER ::= { snmpMIBObjects 4 }
+snmpTrapOID           OBJECT-TYPE ::= { snmpTrap 1 }
+snmpTrapEnterprise    OBJECT-TYPE ::= { snmpTrap 3 }
+snmpTraps             OBJECT IDENTIFIER ::= { snmpMIBObjects 5 }
+coldStart             NOTIFICATION-TYPE ::= { snmpTraps 1 }
+warmStart             NOTIFICATION-TYPE ::= { snmpTraps 2 }
+authenticationFailure NOTIFICATION-TYPE ::= { snmpTraps 5 }
+END
+
+IF-MIB DEFINITIONS ::= BEGIN
+linkDown NOTIFICATION-TYPE ::= { snmpTraps 3 }
+linkUp   NOTIFICATION-TYPE ::= { snmpTraps 4 }
+END
+"#;
+
+/// The SMI macros, which assign an object identifier to a name.
+const MACROS: &[&str] = &[
+    "AGENT-CAPABILITIES",
+    "MODULE-COMPLIANCE",
+    "MODULE-IDENTITY",
+    "NOTIFICATION-GROUP",
+    "NOTIFICATION-TYPE",
+    "OBJECT-GROUP",
+    "OBJECT-IDENTITY",
+    "OBJECT-TYPE",
+];
+
+/// An object identifier assignment, relative to its parent.
+#[derive(Debug, PartialEq, Eq)]
+struct Assignment {
+    module: String,
+    name: String,
+    parent: String,
+    subids: Vec<u32>,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct Mib {
+    names: HashMap<Vec<u32>, String>,
+}
+
+impl Mib {
+    /// Loads the MIB modules of the given files, or of all files in the given directories.
+    pub(super) fn load(paths: &[impl AsRef<Path>]) -> std::io::Result<Self> {
+        let mut sources = vec![BUILTIN.to_owned()];
+        for path in paths {
+            let path = path.as_ref();
+            if path.is_dir() {
+                let mut entries = fs::read_dir(path)?
+                    .map(|entry| entry.map(|entry| entry.path()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                // sorted, so that conflicting definitions are resolved consistently
+                entries.sort();
+                for entry in entries.into_iter().filter(|entry| entry.is_file()) {
+                    sources.push(read_lossy(&entry)?);
+                }
+            } else {
+                sources.push(read_lossy(path)?);
+            }
+        }
+        Ok(Self::from_sources(&sources))
+    }
+
+    fn from_sources(sources: &[String]) -> Self {
+        let mut pending = sources
+            .iter()
+            .flat_map(|source| parse_assignments(source))
+            .collect::<Vec<_>>();
+
+        let mut oids = HashMap::from([
+            (String::from("ccitt"), vec![0]),
+            (String::from("iso"), vec![1]),
+            (String::from("joint-iso-ccitt"), vec![2]),
+        ]);
+        let mut names = HashMap::new();
+        // Assignments may refer to parents defined later, or in other modules, so they are
+        // resolved until none of the remaining ones can be.
+        loop {
+            let count = pending.len();
+            pending.retain(|assignment| {
+                let parent: &[u32] = match oids.get(&assignment.parent) {
+                    Some(parent) => parent,
+                    None if assignment.parent.is_empty() => &[],
+                    None => return true,
+                };
+                let oid = [parent, &assignment.subids].concat();
+                names.insert(
+                    oid.clone(),
+                    format!("{}::{}", assignment.module, assignment.name),
+                );
+                oids.insert(assignment.name.clone(), oid);
+                false
+            });
+            if pending.is_empty() || pending.len() == count {
+                break;
+            }
+        }
+        for assignment in pending {
+            debug!(
+                message = "Unable to resolve MIB object identifier.",
+                name = %assignment.name,
+                parent = %assignment.parent,
+            );
+        }
+
+        Self { names }
+    }
+
+    /// Names an object identifier after its longest defined prefix, followed by the remaining
+    /// subidentifiers, e.g. `IF-MIB::ifIndex.3`. Unknown identifiers are returned numerically.
+    pub(super) fn name(&self, oid: &[u32]) -> String {
+        (1..=oid.len())
+            .rev()
+            .find_map(|length| {
+                self.names.get(&oid[..length]).map(|name| {
+                    let mut name = name.clone();
+                    for subid in &oid[length..] {
+                        name.push('.');
+                        name.push_str(&subid.to_string());
+                    }
+                    name
+                })
+            })
+            .unwrap_or_else(|| format_oid(oid))
+    }
+}
+
+pub(super) fn format_oid(oid: &[u32]) -> String {
+    oid.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn read_lossy(path: &Path) -> std::io::Result<String> {
+    fs::read(path).map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Splits a MIB module into tokens, skipping comments and quoted strings, e.g. descriptions.
+fn tokenize(source: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    while let Some(c) = rest.chars().next() {
+        if let Some(comment) = rest.strip_prefix("--") {
+            // comments end with another `--` or at the end of the line
+            let end = match (comment.find("--"), comment.find('\n')) {
+                (Some(dashes), Some(newline)) if dashes < newline => dashes + 2,
+                (_, Some(newline)) => newline,
+                (Some(dashes), None) => dashes + 2,
+                (None, None) => comment.len(),
+            };
+            rest = &comment[end..];
+        } else if let Some(quoted) = rest.strip_prefix('"') {
+            rest = quoted.find('"').map_or("", |end| &quoted[end + 1..]);
+        } else if let Some(after) = rest.strip_prefix("::=") {
+            tokens.push("::=");
+            rest = after;
+        } else if c.is_ascii_alphanumeric() {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+                .unwrap_or(rest.len());
+            tokens.push(&rest[..end]);
+            rest = &rest[end..];
+        } else {
+            if matches!(c, '{' | '}' | '(' | ')') {
+                tokens.push(&rest[..1]);
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    tokens
+}
+
+fn parse_assignments(source: &str) -> Vec<Assignment> {
+    let tokens = tokenize(source);
+    let mut assignments = Vec::new();
+    let mut module = "";
+    let mut current = None;
+
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = tokens[index];
+        let next = tokens.get(index + 1).copied();
+        if next == Some("DEFINITIONS") {
+            module = token;
+        } else if token.starts_with(|c: char| c.is_ascii_lowercase())
+            && (next.map_or(false, |next| MACROS.contains(&next))
+                || (next == Some("OBJECT") && tokens.get(index + 2) == Some(&"IDENTIFIER")))
+        {
+            current = Some(token);
+        } else if token == "::=" && next == Some("{") {
+            if let Some(name) = current.take() {
+                let end = tokens[index..]
+                    .iter()
+                    .position(|token| *token == "}")
+                    .map_or(tokens.len(), |end| index + end);
+                if let Some(assignment) = parse_value(module, name, &tokens[index + 2..end]) {
+                    assignments.push(assignment);
+                }
+                index = end;
+            }
+        }
+        index += 1;
+    }
+
+    assignments
+}
+
+/// Parses an object identifier value, e.g. `{ ifEntry 1 }`, `{ iso org(3) dod(6) }` or `{ 1 3 6 }`.
+fn parse_value(module: &str, name: &str, components: &[&str]) -> Option<Assignment> {
+    let (first, rest) = components.split_first()?;
+    let (parent, mut subids) = match first.parse::<u32>() {
+        Ok(subid) => (String::new(), vec![subid]),
+        Err(_) => ((*first).to_owned(), Vec::new()),
+    };
+
+    let mut components = rest.iter();
+    while let Some(component) = components.next() {
+        match component.parse::<u32>() {
+            Ok(subid) => subids.push(subid),
+            // a named number, e.g. `org(3)`
+            Err(_) => match (components.next(), components.next(), components.next()) {
+                (Some(&"("), Some(number), Some(&")")) => subids.push(number.parse().ok()?),
+                _ => return None,
+            },
+        }
+    }
+
+    Some(Assignment {
+        module: module.to_owned(),
+        name: name.to_owned(),
+        parent,
+        subids,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IF_MIB: &str = r#"
+IF-MIB DEFINITIONS ::= BEGIN
+
+IMPORTS
+    MODULE-IDENTITY, OBJECT-TYPE, Counter32, Gauge32, Counter64,
+    Integer32, TimeTicks, mib-2, NOTIFICATION-TYPE  FROM SNMPv2-SMI;
+
+ifMIB MODULE-IDENTITY
+    LAST-UPDATED "200006140000Z"
+    DESCRIPTION
+            "The MIB module to describe generic objects for network
+            interface sub-layers. -- not a comment ::= { mib-2 99 }"
+    ::= { mib-2 31 }
+
+interfaces   OBJECT IDENTIFIER ::= { mib-2 2 } -- a comment ::= { mib-2 98 }
+
+ifTable OBJECT-TYPE
+    SYNTAX      SEQUENCE OF IfEntry
+    MAX-ACCESS  not-accessible
+    ::= { interfaces 2 }
+
+ifEntry OBJECT-TYPE
+    SYNTAX      IfEntry
+    INDEX   { ifIndex }
+    ::= { ifTable 1 }
+
+IfEntry ::=
+    SEQUENCE {
+        ifIndex                 InterfaceIndex,
+        ifSpecific              OBJECT IDENTIFIER
+    }
+
+ifIndex OBJECT-TYPE
+    SYNTAX      InterfaceIndex
+    ::= { ifEntry 1 }
+
+ifAdminStatus OBJECT-TYPE
+    SYNTAX  INTEGER {
+                up(1),
+                down(2)
+            }
+    ::= { ifEntry 7 }
+
+END
+"#;
+
+    #[test]
+    fn parses_assignments() {
+        let assignments = parse_assignments(IF_MIB);
+        let names = assignments
+            .iter()
+            .map(|assignment| assignment.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "ifMIB",
+                "interfaces",
+                "ifTable",
+                "ifEntry",
+                "ifIndex",
+                "ifAdminStatus"
+            ]
+        );
+        assert_eq!(
+            assignments[4],
+            Assignment {
+                module: "IF-MIB".to_owned(),
+                name: "ifIndex".to_owned(),
+                parent: "ifEntry".to_owned(),
+                subids: vec![1],
+            }
+        );
+    }
+
+    #[test]
+    fn parses_values() {
+        let assignment = parse_value("M", "internet", &tokenize("iso org(3) dod(6) 1")).unwrap();
+        assert_eq!(assignment.parent, "iso");
+        assert_eq!(assignment.subids, [3, 6, 1]);
+
+        let assignment = parse_value("M", "internet", &tokenize("1 3 6 1")).unwrap();
+        assert_eq!(assignment.parent, "");
+        assert_eq!(assignment.subids, [1, 3, 6, 1]);
+    }
+
+    #[test]
+    fn names_object_identifiers() {
+        let mib = Mib::from_sources(&[IF_MIB.to_owned(), BUILTIN.to_owned()]);
+
+        assert_eq!(
+            mib.name(&[1, 3, 6, 1, 2, 1, 2, 2, 1, 1, 3]),
+            "IF-MIB::ifIndex.3"
+        );
+        assert_eq!(
+            mib.name(&[1, 3, 6, 1, 2, 1, 2, 2, 1, 7, 3]),
+            "IF-MIB::ifAdminStatus.3"
+        );
+        assert_eq!(
+            mib.name(&[1, 3, 6, 1, 6, 3, 1, 1, 5, 3]),
+            "IF-MIB::linkDown"
+        );
+        assert_eq!(
+            mib.name(&[1, 3, 6, 1, 2, 1, 1, 3, 0]),
+            "SNMPv2-MIB::sysUpTime.0"
+        );
+        assert_eq!(
+            mib.name(&[1, 3, 6, 1, 4, 1, 9999, 1]),
+            "SNMPv2-SMI::enterprises.9999.1"
+        );
+        assert_eq!(mib.name(&[2, 999]), "2.999");
+    }
+}
@@ -0,0 +1,518 @@
+//! `SNMP trap` source.
+//! Receives SNMPv2c and SNMPv3 traps and informs over UDP, naming their object identifiers with
+//! the definitions of user provided MIB modules.
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    path::PathBuf,
+};
+
+use bytes::Bytes;
+use chrono::Utc;
+use listenfd::ListenFd;
+use vector_common::{internal_event::EventsReceived, sensitive_string::SensitiveString};
+use vector_config::{configurable_component, NamedComponent};
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use self::{
+    ber::{decode_message, DecodeError, Message, Pdu, VarBindValue, RESPONSE},
+    mib::{format_oid, Mib},
+    usm::UsmUser,
+};
+use super::util::net::{try_bind_udp_socket, SocketListenAddr};
+use crate::{
+    config::{log_schema, DataType, GenerateConfig, Output, Resource, SourceConfig, SourceContext},
+    event::{LogEvent, Value},
+    internal_events::{
+        SnmpTrapDecodeError, SocketBindError, SocketBytesReceived, SocketMode, SocketReceiveError,
+        StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    udp, SourceSender,
+};
+
+mod ber;
+mod mib;
+mod usm;
+
+/// The object identifier of `sysUpTime.0`, the first variable binding of every trap.
+const SYS_UP_TIME: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 3, 0];
+/// The object identifier of `snmpTrapOID.0`, the second variable binding of every trap.
+const SNMP_TRAP_OID: &[u32] = &[1, 3, 6, 1, 6, 3, 1, 1, 4, 1, 0];
+
+/// Configuration for the `snmp_trap` source.
+#[configurable_component(source("snmp_trap"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SnmpTrapConfig {
+    /// The address to listen for traps on.
+    #[serde(default = "default_address")]
+    address: SocketListenAddr,
+
+    /// The communities to accept SNMPv2c traps of.
+    ///
+    /// Traps of any community are accepted, if empty.
+    #[serde(default)]
+    communities: Vec<String>,
+
+    /// The users to accept SNMPv3 traps of.
+    ///
+    /// SNMPv3 traps are only accepted of configured users.
+    #[serde(default)]
+    users: Vec<SnmpUserConfig>,
+
+    /// MIB module files, or directories of MIB module files, to name object identifiers with.
+    ///
+    /// The roots of the common MIB modules, e.g. `SNMPv2-SMI::enterprises`, as well as the standard
+    /// traps are always known.
+    #[serde(default)]
+    mib_paths: Vec<PathBuf>,
+
+    /// The size, in bytes, of the receive buffer used for the socket.
+    ///
+    /// This should not typically needed to be changed.
+    receive_buffer_bytes: Option<usize>,
+}
+
+/// An SNMPv3 user of the user-based security model.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SnmpUserConfig {
+    /// The name of the user.
+    name: String,
+
+    #[configurable(derived)]
+    auth_protocol: Option<SnmpAuthProtocol>,
+
+    /// The authentication password.
+    auth_password: Option<SensitiveString>,
+
+    #[configurable(derived)]
+    privacy_protocol: Option<SnmpPrivacyProtocol>,
+
+    /// The privacy password.
+    ///
+    /// Privacy requires authentication.
+    privacy_password: Option<SensitiveString>,
+}
+
+/// The protocol to authenticate messages with.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SnmpAuthProtocol {
+    /// HMAC-MD5-96.
+    Md5,
+
+    /// HMAC-SHA-96.
+    Sha,
+}
+
+/// The protocol to encrypt messages with.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SnmpPrivacyProtocol {
+    /// CBC-DES.
+    Des,
+
+    /// CFB128-AES-128.
+    Aes,
+}
+
+fn default_address() -> SocketListenAddr {
+    SocketListenAddr::SocketAddr(SocketAddr::V4(SocketAddrV4::new(
+        Ipv4Addr::UNSPECIFIED,
+        162,
+    )))
+}
+
+impl GenerateConfig for SnmpTrapConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: default_address(),
+            communities: vec!["public".to_owned()],
+            users: Vec::new(),
+            mib_paths: Vec::new(),
+            receive_buffer_bytes: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for SnmpTrapConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let users = self
+            .users
+            .iter()
+            .map(|user| Ok((user.name.as_bytes().to_vec(), UsmUser::new(user)?)))
+            .collect::<crate::Result<_>>()?;
+        let receiver = TrapReceiver {
+            communities: self
+                .communities
+                .iter()
+                .map(|c| c.as_bytes().to_vec())
+                .collect(),
+            users,
+            mib: Mib::load(&self.mib_paths)?,
+        };
+
+        Ok(Box::pin(snmp_trap_source(
+            self.address,
+            self.receive_buffer_bytes,
+            receiver,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![self.address.as_udp_resource()]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+struct TrapReceiver {
+    communities: Vec<Vec<u8>>,
+    users: HashMap<Vec<u8>, UsmUser>,
+    mib: Mib,
+}
+
+impl TrapReceiver {
+    /// Decodes a message into an event, returning the response to send, if the message is an
+    /// inform.
+    fn receive(
+        &self,
+        input: &[u8],
+        peer: SocketAddr,
+    ) -> Result<(LogEvent, Option<Vec<u8>>), DecodeError> {
+        let mut fields = BTreeMap::new();
+        let (pdu, response) = match decode_message(input)? {
+            Message::V2c {
+                community,
+                pdu_offset,
+                pdu,
+            } => {
+                if !self.communities.is_empty()
+                    && !self.communities.iter().any(|known| known == community)
+                {
+                    return Err(DecodeError::UnknownCommunity);
+                }
+                fields.insert("version".to_owned(), Value::from("2c"));
+                fields.insert(
+                    "community".to_owned(),
+                    Value::from(Bytes::copy_from_slice(community)),
+                );
+
+                // The response to an inform is the same message, only with a different PDU type.
+                let response = matches!(pdu.kind, ber::PduKind::Inform).then(|| {
+                    let mut response = input.to_vec();
+                    response[pdu_offset] = RESPONSE;
+                    response
+                });
+                (pdu, response)
+            }
+            Message::V3(message) => {
+                let user_name = message.security.user_name;
+                let user = self
+                    .users
+                    .get(user_name)
+                    .ok_or_else(|| DecodeError::UnknownUser {
+                        user: String::from_utf8_lossy(user_name).into_owned(),
+                    })?;
+                let pdu = user.process(input, &message)?;
+                fields.insert("version".to_owned(), Value::from("3"));
+                fields.insert(
+                    "user".to_owned(),
+                    Value::from(Bytes::copy_from_slice(user_name)),
+                );
+                fields.insert(
+                    "engine_id".to_owned(),
+                    Value::from(hex::encode(message.security.engine_id)),
+                );
+                // Informs of SNMPv3 require the receiver to be the authoritative engine, which
+                // isn't supported, so that they aren't answered.
+                (pdu, None)
+            }
+        };
+
+        self.insert_pdu(&mut fields, pdu);
+        let mut log = LogEvent::from(fields);
+        log.try_insert(
+            log_schema().source_type_key(),
+            Bytes::from_static(SnmpTrapConfig::NAME.as_bytes()),
+        );
+        log.try_insert(log_schema().timestamp_key(), Utc::now());
+        log.try_insert(log_schema().host_key(), peer.ip().to_string());
+
+        Ok((log, response))
+    }
+
+    fn insert_pdu(&self, fields: &mut BTreeMap<String, Value>, pdu: Pdu) {
+        fields.insert("pdu_type".to_owned(), Value::from(pdu.kind.as_str()));
+        fields.insert("request_id".to_owned(), Value::from(pdu.request_id));
+
+        let mut varbinds = BTreeMap::new();
+        for varbind in pdu.varbinds {
+            match (varbind.oid.as_slice(), &varbind.value) {
+                (SYS_UP_TIME, VarBindValue::TimeTicks(ticks)) => {
+                    fields.insert("uptime".to_owned(), Value::from(saturating_i64(*ticks)));
+                }
+                (SNMP_TRAP_OID, VarBindValue::ObjectIdentifier(oid)) => {
+                    fields.insert("trap_oid".to_owned(), Value::from(format_oid(oid)));
+                    fields.insert("trap".to_owned(), Value::from(self.mib.name(oid)));
+                }
+                _ => {}
+            }
+            varbinds.insert(self.mib.name(&varbind.oid), self.value(varbind.value));
+        }
+        fields.insert("varbinds".to_owned(), Value::from(varbinds));
+    }
+
+    fn value(&self, value: VarBindValue) -> Value {
+        match value {
+            VarBindValue::Integer(value) => Value::from(value),
+            VarBindValue::OctetString(bytes) | VarBindValue::Opaque(bytes) => Value::from(bytes),
+            VarBindValue::ObjectIdentifier(oid) => Value::from(self.mib.name(&oid)),
+            VarBindValue::IpAddress(address) => Value::from(Ipv4Addr::from(address).to_string()),
+            VarBindValue::Counter32(value)
+            | VarBindValue::Gauge32(value)
+            | VarBindValue::TimeTicks(value)
+            | VarBindValue::Counter64(value) => Value::from(saturating_i64(value)),
+            VarBindValue::Null
+            | VarBindValue::NoSuchObject
+            | VarBindValue::NoSuchInstance
+            | VarBindValue::EndOfMibView => Value::Null,
+        }
+    }
+}
+
+/// Converts unsigned values to integers, only `Counter64` values can exceed them.
+fn saturating_i64(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+async fn snmp_trap_source(
+    address: SocketListenAddr,
+    receive_buffer_bytes: Option<usize>,
+    receiver: TrapReceiver,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let socket = try_bind_udp_socket(address, ListenFd::from_env())
+        .await
+        .map_err(|error| {
+            emit!(SocketBindError {
+                mode: SocketMode::Udp,
+                error,
+            })
+        })?;
+
+    if let Some(receive_buffer_bytes) = receive_buffer_bytes {
+        if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        }
+    }
+
+    info!(message = "Listening.", addr = %address, r#type = "udp");
+
+    // the maximum size of a UDP datagram
+    let mut buffer = vec![0; 65_535];
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            received = socket.recv_from(&mut buffer) => {
+                let (size, peer) = match received {
+                    Ok(received) => received,
+                    Err(error) => {
+                        emit!(SocketReceiveError {
+                            mode: SocketMode::Udp,
+                            error,
+                        });
+                        continue;
+                    }
+                };
+                emit!(SocketBytesReceived {
+                    mode: SocketMode::Udp,
+                    byte_size: size,
+                });
+
+                let (event, response) = match receiver.receive(&buffer[..size], peer) {
+                    Ok(received) => received,
+                    Err(error) => {
+                        emit!(SnmpTrapDecodeError { error, peer });
+                        continue;
+                    }
+                };
+                if let Some(response) = response {
+                    if let Err(error) = socket.send_to(&response, peer).await {
+                        warn!(message = "Failed to respond to inform.", %peer, %error);
+                    }
+                }
+
+                emit!(EventsReceived {
+                    count: 1,
+                    byte_size: event.size_of(),
+                });
+                if let Err(error) = out.send_event(event).await {
+                    emit!(StreamClosedError { error, count: 1 });
+                    return Err(());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tlv(tag: u8, contents: &[u8]) -> Vec<u8> {
+        let mut element = vec![tag];
+        if contents.len() < 0x80 {
+            element.push(contents.len() as u8);
+        } else {
+            element.extend([0x82, (contents.len() >> 8) as u8, contents.len() as u8]);
+        }
+        element.extend(contents);
+        element
+    }
+
+    fn varbind(oid: &[u8], value: Vec<u8>) -> Vec<u8> {
+        tlv(0x30, &[tlv(0x06, oid), value].concat())
+    }
+
+    /// A `linkDown` trap of interface 3, with a request identifier of 42.
+    fn pdu(tag: u8) -> Vec<u8> {
+        let varbinds = [
+            // sysUpTime.0 = 1234
+            varbind(&[0x2b, 6, 1, 2, 1, 1, 3, 0], tlv(0x43, &[0x04, 0xd2])),
+            // snmpTrapOID.0 = linkDown
+            varbind(
+                &[0x2b, 6, 1, 6, 3, 1, 1, 4, 1, 0],
+                tlv(0x06, &[0x2b, 6, 1, 6, 3, 1, 1, 5, 3]),
+            ),
+            // ifIndex.3 = 3
+            varbind(&[0x2b, 6, 1, 2, 1, 2, 2, 1, 1, 3], tlv(0x02, &[3])),
+            // ifDescr.3 = "eth0"
+            varbind(&[0x2b, 6, 1, 2, 1, 2, 2, 1, 2, 3], tlv(0x04, b"eth0")),
+        ]
+        .concat();
+        tlv(
+            tag,
+            &[
+                tlv(0x02, &[42]),
+                tlv(0x02, &[0]),
+                tlv(0x02, &[0]),
+                tlv(0x30, &varbinds),
+            ]
+            .concat(),
+        )
+    }
+
+    fn v2c_message(community: &[u8], pdu: Vec<u8>) -> Vec<u8> {
+        tlv(0x30, &[tlv(0x02, &[1]), tlv(0x04, community), pdu].concat())
+    }
+
+    fn receiver(communities: &[&str]) -> TrapReceiver {
+        TrapReceiver {
+            communities: communities.iter().map(|c| c.as_bytes().to_vec()).collect(),
+            users: HashMap::new(),
+            mib: Mib::load::<PathBuf>(&[]).unwrap(),
+        }
+    }
+
+    fn peer() -> SocketAddr {
+        "192.0.2.1:50000".parse().unwrap()
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SnmpTrapConfig>();
+    }
+
+    #[test]
+    fn receives_v2c_trap() {
+        let (log, response) = receiver(&["public"])
+            .receive(&v2c_message(b"public", pdu(0xa7)), peer())
+            .unwrap();
+
+        assert!(response.is_none());
+        assert_eq!(log["version"], "2c".into());
+        assert_eq!(log["community"], "public".into());
+        assert_eq!(log["pdu_type"], "trap".into());
+        assert_eq!(log["request_id"], 42.into());
+        assert_eq!(log["uptime"], 1234.into());
+        assert_eq!(log["trap_oid"], "1.3.6.1.6.3.1.1.5.3".into());
+        assert_eq!(log["trap"], "IF-MIB::linkDown".into());
+        assert_eq!(
+            log[r#"varbinds."SNMPv2-MIB::snmpTrapOID.0""#],
+            "IF-MIB::linkDown".into()
+        );
+        // without the IF-MIB module, only the root of the interfaces is known
+        assert_eq!(
+            log[r#"varbinds."SNMPv2-SMI::mib-2.2.2.1.2.3""#],
+            "eth0".into()
+        );
+        assert_eq!(log[log_schema().host_key()], "192.0.2.1".into());
+        assert_eq!(log[log_schema().source_type_key()], "snmp_trap".into());
+    }
+
+    #[test]
+    fn answers_v2c_informs() {
+        let message = v2c_message(b"public", pdu(0xa6));
+        let (log, response) = receiver(&[]).receive(&message, peer()).unwrap();
+
+        assert_eq!(log["pdu_type"], "inform".into());
+        let expected = v2c_message(b"public", pdu(0xa2));
+        assert_eq!(response, Some(expected));
+    }
+
+    #[test]
+    fn rejects_unknown_communities() {
+        let error = receiver(&["public"])
+            .receive(&v2c_message(b"private", pdu(0xa7)), peer())
+            .unwrap_err();
+        assert_eq!(error, DecodeError::UnknownCommunity);
+    }
+
+    #[test]
+    fn rejects_unsupported_messages() {
+        let version_1 = tlv(0x30, &[tlv(0x02, &[0]), tlv(0x04, b"public")].concat());
+        assert_eq!(
+            receiver(&[]).receive(&version_1, peer()).unwrap_err(),
+            DecodeError::UnsupportedVersion { version: 0 }
+        );
+        // a get request
+        assert_eq!(
+            receiver(&[])
+                .receive(&v2c_message(b"public", pdu(0xa0)), peer())
+                .unwrap_err(),
+            DecodeError::UnsupportedPdu { tag: 0xa0 }
+        );
+        let truncated = v2c_message(b"public", pdu(0xa7));
+        assert!(receiver(&[])
+            .receive(&truncated[..truncated.len() - 1], peer())
+            .is_err());
+    }
+
+    #[test]
+    fn decodes_object_identifiers() {
+        assert_eq!(
+            ber::decode_oid(&[0x2b, 6, 1, 4, 1, 0x8e, 0x1f, 1]).unwrap(),
+            [1, 3, 6, 1, 4, 1, 1823, 1]
+        );
+        assert!(ber::decode_oid(&[0x2b, 0x8e]).is_err());
+    }
+}
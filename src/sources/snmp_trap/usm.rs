@@ -0,0 +1,265 @@
+//! The user-based security model of SNMPv3 ([RFC 3414](https://www.rfc-editor.org/rfc/rfc3414)),
+//! with AES encryption from [RFC 3826](https://www.rfc-editor.org/rfc/rfc3826).
+//! Traps are sent by the authoritative engine, so that the receiver has nothing to discover and
+//! only needs to verify and decrypt messages with keys localized to the sender's engine.
+
+use openssl::{
+    hash::{hash, Hasher, MessageDigest},
+    memcmp,
+    pkey::PKey,
+    sign::Signer,
+    symm::{Cipher, Crypter, Mode},
+};
+
+use super::{
+    ber::{decode_scoped_pdu, offset_in, DecodeError, Pdu, ScopedPduData, V3Message},
+    SnmpAuthProtocol, SnmpPrivacyProtocol, SnmpUserConfig,
+};
+
+/// The length of the truncated HMAC of MD5 and SHA-1 authenticated messages.
+const AUTHENTICATION_LENGTH: usize = 12;
+
+/// An SNMPv3 user, with keys derived from the passwords.
+pub(super) struct UsmUser {
+    authentication: Option<(SnmpAuthProtocol, Vec<u8>)>,
+    privacy: Option<(SnmpPrivacyProtocol, Vec<u8>)>,
+}
+
+impl SnmpAuthProtocol {
+    fn digest(self) -> MessageDigest {
+        match self {
+            Self::Md5 => MessageDigest::md5(),
+            Self::Sha => MessageDigest::sha1(),
+        }
+    }
+}
+
+/// Derives a key from a password, by hashing a megabyte of the repeated password.
+fn password_to_key(
+    digest: MessageDigest,
+    password: &[u8],
+) -> Result<Vec<u8>, openssl::error::ErrorStack> {
+    let mut hasher = Hasher::new(digest)?;
+    let mut repeated = password.iter().cycle();
+    let mut chunk = [0; 64];
+    for _ in 0..(1024 * 1024 / chunk.len()) {
+        chunk
+            .iter_mut()
+            .zip(repeated.by_ref())
+            .for_each(|(byte, password)| *byte = *password);
+        hasher.update(&chunk)?;
+    }
+    Ok(hasher.finish()?.to_vec())
+}
+
+/// Localizes a key to an engine, so that a compromised engine doesn't reveal the keys of others.
+fn localize(digest: MessageDigest, key: &[u8], engine_id: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    hash(digest, &[key, engine_id, key].concat())
+        .map(|localized| localized.to_vec())
+        .map_err(|error| DecodeError::Decryption {
+            reason: error.to_string(),
+        })
+}
+
+impl UsmUser {
+    pub(super) fn new(config: &SnmpUserConfig) -> crate::Result<Self> {
+        let authentication = match (config.auth_protocol, &config.auth_password) {
+            (None, None) => None,
+            (Some(protocol), Some(password)) => Some((
+                protocol,
+                password_to_key(protocol.digest(), password.inner().as_bytes())?,
+            )),
+            _ => {
+                return Err(format!(
+                    "User {:?} needs both an authentication protocol and password",
+                    config.name
+                )
+                .into())
+            }
+        };
+        let privacy = match (
+            config.privacy_protocol,
+            &config.privacy_password,
+            &authentication,
+        ) {
+            (None, None, _) => None,
+            (Some(protocol), Some(password), Some((authentication, _))) => Some((
+                protocol,
+                password_to_key(authentication.digest(), password.inner().as_bytes())?,
+            )),
+            (_, _, None) => {
+                return Err(
+                    format!("User {:?} needs authentication to use privacy", config.name).into(),
+                )
+            }
+            _ => {
+                return Err(format!(
+                    "User {:?} needs both a privacy protocol and password",
+                    config.name
+                )
+                .into())
+            }
+        };
+
+        Ok(Self {
+            authentication,
+            privacy,
+        })
+    }
+
+    /// Verifies and decrypts a message of the user, returning its PDU.
+    pub(super) fn process(
+        &self,
+        input: &[u8],
+        message: &V3Message<'_>,
+    ) -> Result<Pdu, DecodeError> {
+        // The security level of the message must match the one of the user, otherwise messages
+        // could be downgraded.
+        if message.authenticated() != self.authentication.is_some()
+            || message.encrypted() != self.privacy.is_some()
+        {
+            return Err(DecodeError::Authentication {
+                reason: "security level doesn't match the one of the user",
+            });
+        }
+
+        if let Some((protocol, key)) = &self.authentication {
+            self.authenticate(*protocol, key, input, message)?;
+        }
+
+        match (message.data, &self.privacy) {
+            (ScopedPduData::Plaintext(plaintext), None) => decode_scoped_pdu(plaintext),
+            (ScopedPduData::Encrypted(encrypted), Some((protocol, key))) => {
+                let plaintext = self.decrypt(*protocol, key, encrypted, message)?;
+                decode_scoped_pdu(&plaintext)
+            }
+            _ => Err(DecodeError::Malformed {
+                reason: "scoped PDU doesn't match the message flags",
+            }),
+        }
+    }
+
+    fn authenticate(
+        &self,
+        protocol: SnmpAuthProtocol,
+        key: &[u8],
+        input: &[u8],
+        message: &V3Message<'_>,
+    ) -> Result<(), DecodeError> {
+        let received = message.security.authentication;
+        if received.len() != AUTHENTICATION_LENGTH {
+            return Err(DecodeError::Authentication {
+                reason: "invalid authentication parameters",
+            });
+        }
+
+        // The HMAC is calculated over the whole message, with zeroed authentication parameters.
+        let offset = offset_in(input, received);
+        let mut zeroed = input.to_vec();
+        zeroed[offset..offset + AUTHENTICATION_LENGTH].fill(0);
+
+        let digest = protocol.digest();
+        let expected = localize(digest, key, message.security.engine_id)
+            .and_then(|key| {
+                let key = PKey::hmac(&key)?;
+                let mut signer = Signer::new(digest, &key)?;
+                signer.update(&zeroed)?;
+                signer.sign_to_vec()
+            })
+            .map_err(|_| DecodeError::Authentication {
+                reason: "unable to calculate the HMAC",
+            })?;
+
+        if memcmp::eq(&expected[..AUTHENTICATION_LENGTH], received) {
+            Ok(())
+        } else {
+            Err(DecodeError::Authentication {
+                reason: "wrong digest",
+            })
+        }
+    }
+
+    fn decrypt(
+        &self,
+        protocol: SnmpPrivacyProtocol,
+        key: &[u8],
+        encrypted: &[u8],
+        message: &V3Message<'_>,
+    ) -> Result<Vec<u8>, DecodeError> {
+        let decryption_error = |reason: &str| DecodeError::Decryption {
+            reason: reason.to_owned(),
+        };
+        let salt = message.security.privacy;
+        if salt.len() != 8 {
+            return Err(decryption_error("invalid privacy parameters"));
+        }
+        let digest = self
+            .authentication
+            .as_ref()
+            .map(|(protocol, _)| protocol.digest())
+            .expect("privacy requires authentication");
+        let key = localize(digest, key, message.security.engine_id)?;
+
+        let (cipher, key, iv) = match protocol {
+            SnmpPrivacyProtocol::Des => {
+                if encrypted.len() % 8 != 0 {
+                    return Err(decryption_error("invalid length"));
+                }
+                // The initialization vector is the pre-IV, i.e. the second half of the key,
+                // XORed with the salt.
+                let iv = key[8..16]
+                    .iter()
+                    .zip(salt)
+                    .map(|(pre_iv, salt)| pre_iv ^ salt)
+                    .collect::<Vec<_>>();
+                (Cipher::des_cbc(), &key[..8], iv)
+            }
+            SnmpPrivacyProtocol::Aes => {
+                // The initialization vector is the engine boots and time, followed by the salt.
+                let mut iv = Vec::with_capacity(16);
+                iv.extend_from_slice(&(message.security.engine_boots as u32).to_be_bytes());
+                iv.extend_from_slice(&(message.security.engine_time as u32).to_be_bytes());
+                iv.extend_from_slice(salt);
+                (Cipher::aes_128_cfb128(), &key[..16], iv)
+            }
+        };
+
+        let mut crypter = Crypter::new(cipher, Mode::Decrypt, key, Some(&iv))
+            .map_err(|error| decryption_error(&error.to_string()))?;
+        crypter.pad(false);
+        let mut plaintext = vec![0; encrypted.len() + cipher.block_size()];
+        let mut length = crypter
+            .update(encrypted, &mut plaintext)
+            .map_err(|error| decryption_error(&error.to_string()))?;
+        length += crypter
+            .finalize(&mut plaintext[length..])
+            .map_err(|error| decryption_error(&error.to_string()))?;
+        plaintext.truncate(length);
+        Ok(plaintext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    /// The key localization examples of RFC 3414, appendix A.3.
+    #[test]
+    fn localizes_keys() {
+        let engine_id = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+
+        let key = password_to_key(MessageDigest::md5(), b"maplesyrup").unwrap();
+        assert_eq!(hex(&key), "9faf3283884e92834ebc9847d8edd963");
+        let key = localize(MessageDigest::md5(), &key, &engine_id).unwrap();
+        assert_eq!(hex(&key), "526f5eed9fcce26f8964c2930787d82b");
+
+        let key = password_to_key(MessageDigest::sha1(), b"maplesyrup").unwrap();
+        assert_eq!(hex(&key), "9fb5cc0381497b3793528939ff788d5d79145211");
+        let key = localize(MessageDigest::sha1(), &key, &engine_id).unwrap();
+        assert_eq!(hex(&key), "6695febc9288e36282235fc7151f128497b38f3f");
+    }
+}
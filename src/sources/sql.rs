@@ -0,0 +1,480 @@
+//! Polls a SQL database with a query, emitting each row as an event.
+//!
+//! PostgreSQL and MySQL are supported through the `Any` driver of `sqlx`, which is chosen by the
+//! scheme of the connection string.
+
+use std::{
+    collections::BTreeMap,
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use futures::StreamExt;
+use snafu::{ResultExt, Snafu};
+use sqlx::{
+    any::{AnyPoolOptions, AnyRow},
+    Any, AnyPool, Column, Decode, Row, Type,
+};
+use tokio::time;
+use tokio_stream::wrappers::IntervalStream;
+use vector_common::finalizer::OrderedFinalizer;
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Output, SourceConfig,
+        SourceContext,
+    },
+    event::{BatchNotifier, BatchStatus, LogEvent, Value},
+    internal_events::{
+        EventsReceived, SqlCheckpointError, SqlQueryError, SqlTrackingColumnError,
+        StreamClosedError,
+    },
+    serde::bool_or_struct,
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+const CHECKPOINT_FILENAME: &str = "checkpoint.txt";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid connection string: {}", source))]
+    InvalidConnectionString { source: sqlx::Error },
+    #[snafu(display("Could not read checkpoint from {:?}: {}", path, source))]
+    ReadCheckpoint { path: PathBuf, source: io::Error },
+    #[snafu(display("Invalid checkpoint {:?} for a {} tracking column", checkpoint, kind))]
+    InvalidCheckpoint {
+        checkpoint: String,
+        kind: &'static str,
+    },
+}
+
+/// Configuration for the `sql` source.
+#[configurable_component(source("sql"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SqlConfig {
+    /// The URL of the database to connect to.
+    ///
+    /// PostgreSQL is used with a `postgres://` URL, and MySQL with a `mysql://` URL.
+    pub connection_string: String,
+
+    /// The query to run at each interval.
+    ///
+    /// With a `tracking_column`, the watermark is bound as the only parameter of the query, written
+    /// `$1` for PostgreSQL or `?` for MySQL, so that only the rows after it are selected.
+    pub query: String,
+
+    /// The column whose greatest value is kept as the watermark, once its rows are read.
+    ///
+    /// If not set, all the rows of the query are read at each interval.
+    pub tracking_column: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub tracking_column_type: TrackingColumnType,
+
+    /// The interval between queries, in seconds.
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+
+    /// The directory used to persist the watermark.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    pub data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// The type of the tracking column.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum TrackingColumnType {
+    /// An incrementing integer column, starting after `0`.
+    #[derivative(Default)]
+    Integer,
+
+    /// A timestamp column, starting after the Unix epoch.
+    Timestamp,
+}
+
+impl TrackingColumnType {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Integer => "integer",
+            Self::Timestamp => "timestamp",
+        }
+    }
+
+    fn initial_watermark(self) -> Watermark {
+        match self {
+            Self::Integer => Watermark::Integer(0),
+            Self::Timestamp => Watermark::Timestamp(Utc.timestamp_nanos(0)),
+        }
+    }
+
+    fn parse_watermark(self, checkpoint: &str) -> Option<Watermark> {
+        match self {
+            Self::Integer => checkpoint.parse().ok().map(Watermark::Integer),
+            Self::Timestamp => DateTime::parse_from_rfc3339(checkpoint)
+                .ok()
+                .map(|timestamp| Watermark::Timestamp(timestamp.with_timezone(&Utc))),
+        }
+    }
+
+    /// The watermark of a row, if the value of its tracking column has this type.
+    fn watermark(self, value: &Value) -> Option<Watermark> {
+        match (self, value) {
+            (Self::Integer, Value::Integer(value)) => Some(Watermark::Integer(*value)),
+            (Self::Timestamp, Value::Timestamp(value)) => Some(Watermark::Timestamp(*value)),
+            _ => None,
+        }
+    }
+}
+
+/// The greatest value of the tracking column read so far.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Watermark {
+    Integer(i64),
+    Timestamp(DateTime<Utc>),
+}
+
+impl Watermark {
+    fn to_checkpoint(self) -> String {
+        match self {
+            Self::Integer(value) => value.to_string(),
+            Self::Timestamp(value) => value.to_rfc3339(),
+        }
+    }
+}
+
+const fn default_interval_secs() -> u64 {
+    15
+}
+
+impl GenerateConfig for SqlConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            connection_string: "postgres://vector@localhost/db".to_owned(),
+            query: "SELECT * FROM audit_log WHERE id > $1 ORDER BY id LIMIT 1000".to_owned(),
+            tracking_column: Some("id".to_owned()),
+            tracking_column_type: TrackingColumnType::Integer,
+            interval_secs: default_interval_secs(),
+            data_dir: None,
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for SqlConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        // Connections are opened once queried, so that the source starts while the database is
+        // unavailable.
+        let pool = AnyPoolOptions::new()
+            .max_connections(1)
+            .connect_lazy(&self.connection_string)
+            .context(InvalidConnectionStringSnafu)?;
+
+        let mut checkpoint_path = cx
+            .globals
+            // source are only global, name can be used for subdir
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        checkpoint_path.push(CHECKPOINT_FILENAME);
+
+        let watermark = match &self.tracking_column {
+            Some(_) => Some(read_checkpoint(&checkpoint_path, self.tracking_column_type).await?),
+            None => None,
+        };
+
+        Ok(Box::pin(
+            SqlSource {
+                query: self.query.clone(),
+                tracking_column: self.tracking_column.clone(),
+                tracking_column_type: self.tracking_column_type,
+                interval: Duration::from_secs(self.interval_secs),
+                checkpoint_path,
+                acknowledgements: cx.do_acknowledgements(&self.acknowledgements),
+            }
+            .run(pool, watermark, cx.out, cx.shutdown),
+        ))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+async fn read_checkpoint(path: &Path, kind: TrackingColumnType) -> Result<Watermark, BuildError> {
+    match tokio::fs::read_to_string(path).await {
+        Ok(checkpoint) if checkpoint.trim().is_empty() => Ok(kind.initial_watermark()),
+        Ok(checkpoint) => {
+            let checkpoint = checkpoint.trim();
+            kind.parse_watermark(checkpoint)
+                .ok_or_else(|| BuildError::InvalidCheckpoint {
+                    checkpoint: checkpoint.to_owned(),
+                    kind: kind.as_str(),
+                })
+        }
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(kind.initial_watermark()),
+        Err(source) => Err(BuildError::ReadCheckpoint {
+            path: path.to_path_buf(),
+            source,
+        }),
+    }
+}
+
+struct SqlSource {
+    query: String,
+    tracking_column: Option<String>,
+    tracking_column_type: TrackingColumnType,
+    interval: Duration,
+    checkpoint_path: PathBuf,
+    acknowledgements: bool,
+}
+
+impl SqlSource {
+    async fn run(
+        self,
+        pool: AnyPool,
+        mut watermark: Option<Watermark>,
+        mut out: SourceSender,
+        shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        let (finalizer, mut ack_stream) =
+            OrderedFinalizer::<Watermark>::maybe_new(self.acknowledgements, shutdown.clone());
+        let checkpoint_path = self.checkpoint_path.clone();
+        tokio::spawn(async move {
+            while let Some((status, watermark)) = ack_stream.next().await {
+                if status == BatchStatus::Delivered {
+                    write_checkpoint(&checkpoint_path, watermark).await;
+                }
+            }
+        });
+
+        let mut interval = IntervalStream::new(time::interval(self.interval)).take_until(shutdown);
+        while interval.next().await.is_some() {
+            let mut query = sqlx::query::<Any>(&self.query);
+            match watermark {
+                Some(Watermark::Integer(value)) => query = query.bind(value),
+                Some(Watermark::Timestamp(value)) => query = query.bind(value),
+                None => {}
+            }
+            let rows = match query.fetch_all(&pool).await {
+                Ok(rows) => rows,
+                Err(error) => {
+                    emit!(SqlQueryError { error });
+                    continue;
+                }
+            };
+            if rows.is_empty() {
+                continue;
+            }
+
+            let (notifier, batch_receiver) =
+                BatchNotifier::maybe_new_with_receiver(finalizer.is_some());
+            let mut next_watermark = watermark;
+            let mut events = Vec::with_capacity(rows.len());
+            for row in &rows {
+                let fields = row_to_fields(row);
+                if let Some(column) = &self.tracking_column {
+                    match fields
+                        .get(column)
+                        .and_then(|value| self.tracking_column_type.watermark(value))
+                    {
+                        Some(row_watermark) => {
+                            next_watermark = next_watermark.max(Some(row_watermark));
+                        }
+                        None => emit!(SqlTrackingColumnError {
+                            column,
+                            kind: self.tracking_column_type.as_str(),
+                        }),
+                    }
+                }
+                let mut log = LogEvent::from(fields);
+                log.try_insert(log_schema().source_type_key(), Bytes::from("sql"));
+                log.try_insert(log_schema().timestamp_key(), Utc::now());
+                events.push(log.with_batch_notifier_option(&notifier));
+            }
+            drop(notifier);
+
+            let count = events.len();
+            emit!(EventsReceived {
+                count,
+                byte_size: events.size_of(),
+            });
+            if let Err(error) = out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                return Err(());
+            }
+
+            if next_watermark != watermark {
+                watermark = next_watermark;
+                if let Some(watermark) = watermark {
+                    match (&finalizer, batch_receiver) {
+                        (Some(finalizer), Some(receiver)) => finalizer.add(watermark, receiver),
+                        _ => write_checkpoint(&self.checkpoint_path, watermark).await,
+                    }
+                }
+            }
+        }
+
+        pool.close().await;
+        Ok(())
+    }
+}
+
+/// The fields of a row, named like its columns even if their names have dots.
+fn row_to_fields(row: &AnyRow) -> BTreeMap<String, Value> {
+    row.columns()
+        .iter()
+        .map(|column| {
+            (
+                column.name().to_owned(),
+                decode_column(row, column.ordinal()),
+            )
+        })
+        .collect()
+}
+
+fn try_decode<'r, T>(row: &'r AnyRow, index: usize) -> Option<Option<T>>
+where
+    T: Decode<'r, Any> + Type<Any>,
+{
+    row.try_get::<Option<T>, _>(index).ok()
+}
+
+/// Decodes a column with the first type it's compatible with. The values of other types, such as
+/// numerics, are null.
+fn decode_column(row: &AnyRow, index: usize) -> Value {
+    if let Some(value) = try_decode::<i64>(row, index) {
+        value.into()
+    } else if let Some(value) = try_decode::<i32>(row, index) {
+        value.into()
+    } else if let Some(value) = try_decode::<i16>(row, index) {
+        value.into()
+    } else if let Some(value) = try_decode::<f64>(row, index) {
+        value.into()
+    } else if let Some(value) = try_decode::<f32>(row, index) {
+        value.map(f64::from).into()
+    } else if let Some(value) = try_decode::<bool>(row, index) {
+        value.into()
+    } else if let Some(value) = try_decode::<DateTime<Utc>>(row, index) {
+        value.into()
+    } else if let Some(value) = try_decode::<NaiveDateTime>(row, index) {
+        value
+            .map(|value| DateTime::<Utc>::from_utc(value, Utc))
+            .into()
+    } else if let Some(value) = try_decode::<NaiveDate>(row, index) {
+        value.map(|value| value.to_string()).into()
+    } else if let Some(value) = try_decode::<String>(row, index) {
+        value.into()
+    } else if let Some(value) = try_decode::<Vec<u8>>(row, index) {
+        value.map(Bytes::from).into()
+    } else {
+        Value::Null
+    }
+}
+
+/// Writes the watermark to a temporary file first, so that it's replaced as a whole.
+async fn write_checkpoint(path: &Path, watermark: Watermark) {
+    let temporary = path.with_extension("tmp");
+    let result = async {
+        tokio::fs::write(&temporary, watermark.to_checkpoint()).await?;
+        tokio::fs::rename(&temporary, path).await
+    }
+    .await;
+    if let Err(error) = result {
+        emit!(SqlCheckpointError {
+            error,
+            path: path.to_str().unwrap_or("unknown").to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SqlConfig>();
+    }
+
+    #[test]
+    fn parses_defaults() {
+        let config: SqlConfig = toml::from_str(
+            r#"
+            connection_string = "mysql://vector@localhost/db"
+            query = "SELECT * FROM audit_log WHERE id > ? ORDER BY id"
+            tracking_column = "id"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.tracking_column_type, TrackingColumnType::Integer);
+        assert_eq!(config.interval_secs, 15);
+    }
+
+    #[tokio::test]
+    async fn reads_checkpoints() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CHECKPOINT_FILENAME);
+
+        assert_eq!(
+            read_checkpoint(&path, TrackingColumnType::Integer)
+                .await
+                .unwrap(),
+            Watermark::Integer(0)
+        );
+
+        write_checkpoint(&path, Watermark::Integer(42)).await;
+        assert_eq!(
+            read_checkpoint(&path, TrackingColumnType::Integer)
+                .await
+                .unwrap(),
+            Watermark::Integer(42)
+        );
+        assert!(read_checkpoint(&path, TrackingColumnType::Timestamp)
+            .await
+            .is_err());
+
+        let timestamp = DateTime::parse_from_rfc3339("2022-10-14T06:18:04.123Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        write_checkpoint(&path, Watermark::Timestamp(timestamp)).await;
+        assert_eq!(
+            read_checkpoint(&path, TrackingColumnType::Timestamp)
+                .await
+                .unwrap(),
+            Watermark::Timestamp(timestamp)
+        );
+    }
+
+    #[test]
+    fn watermarks_of_tracking_column_type() {
+        assert_eq!(
+            TrackingColumnType::Integer.watermark(&Value::from(7)),
+            Some(Watermark::Integer(7))
+        );
+        assert_eq!(
+            TrackingColumnType::Timestamp.watermark(&Value::from(7)),
+            None
+        );
+        assert!(Some(Watermark::Integer(7)) > None);
+    }
+}
@@ -7,7 +7,7 @@ use codecs::{
 };
 use futures::{StreamExt, TryFutureExt};
 use listenfd::ListenFd;
-use smallvec::{smallvec, SmallVec};
+use smallvec::SmallVec;
 use tokio_util::udp::UdpFramed;
 use vector_config::configurable_component;
 use vector_core::ByteSizeOf;
@@ -32,9 +32,9 @@ pub mod parser;
 #[cfg(unix)]
 mod unix;
 
-use parser::parse;
+use parser::parse_packet;
 #[cfg(unix)]
-use unix::{statsd_unix, UnixConfig};
+use unix::{statsd_unix, statsd_unix_datagram, UnixConfig};
 use vector_core::config::LogNamespace;
 
 /// Configuration for the `statsd` source.
@@ -51,6 +51,10 @@ pub enum StatsdConfig {
     /// Listen on UDS. (Unix domain socket)
     #[cfg(unix)]
     Unix(#[configurable(derived)] UnixConfig),
+
+    /// Listen on UDS datagrams, as DogStatsD clients send by default. (Unix domain socket)
+    #[cfg(unix)]
+    UnixDatagram(#[configurable(derived)] UnixConfig),
 }
 
 /// UDP configuration for the `statsd` source.
@@ -160,11 +164,18 @@ impl SourceConfig for StatsdConfig {
             }
             #[cfg(unix)]
             StatsdConfig::Unix(config) => statsd_unix(config.clone(), cx.shutdown, cx.out),
+            #[cfg(unix)]
+            StatsdConfig::UnixDatagram(config) => {
+                statsd_unix_datagram(config.clone(), cx.shutdown, cx.out)
+            }
         }
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(config::DataType::Metric)]
+        // DogStatsD events are logs, everything else is a metric
+        vec![Output::default(
+            config::DataType::Metric | config::DataType::Log,
+        )]
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -172,7 +183,7 @@ impl SourceConfig for StatsdConfig {
             Self::Tcp(tcp) => vec![tcp.address.as_tcp_resource()],
             Self::Udp(udp) => vec![udp.address.as_udp_resource()],
             #[cfg(unix)]
-            Self::Unix(_) => vec![],
+            Self::Unix(_) | Self::UnixDatagram(_) => vec![],
         }
     }
 
@@ -219,18 +230,17 @@ impl decoding::format::Deserializer for StatsdDeserializer {
 
         match std::str::from_utf8(&bytes)
             .map_err(ParseError::InvalidUtf8)
-            .and_then(parse)
+            .and_then(parse_packet)
         {
-            Ok(metric) => {
-                let event = Event::Metric(metric);
+            Ok(events) => {
                 // The other modes already emit EventsReceived
                 if matches!(self.socket_mode, Some(SocketMode::Udp)) {
                     emit!(EventsReceived {
-                        count: 1,
-                        byte_size: event.size_of(),
+                        count: events.len(),
+                        byte_size: events.size_of(),
                     });
                 }
-                Ok(smallvec![event])
+                Ok(events.into())
             }
             Err(error) => Err(Box::new(error)),
         }
@@ -423,6 +433,29 @@ mod test {
         .await;
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_statsd_unix_datagram() {
+        assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async move {
+            let in_path = tempfile::tempdir()
+                .unwrap()
+                .into_path()
+                .join("unix_datagram_test");
+            let config = StatsdConfig::UnixDatagram(UnixConfig {
+                path: in_path.clone(),
+            });
+            let (sender, mut receiver) = mpsc::channel(200);
+            tokio::spawn(async move {
+                let socket = tokio::net::UnixDatagram::unbound().unwrap();
+                while let Some(bytes) = receiver.next().await {
+                    socket.send_to(bytes, &in_path).await.unwrap();
+                }
+            });
+            test_statsd(config, sender).await;
+        })
+        .await;
+    }
+
     async fn test_statsd(statsd_config: StatsdConfig, mut sender: mpsc::Sender<&'static [u8]>) {
         // Build our statsd source and then spawn it.  We use a big pipeline buffer because each
         // packet we send has a lot of metrics per packet.  We could technically count them all up
@@ -1,27 +1,53 @@
 use std::{
-    collections::BTreeMap,
     error, fmt,
     num::{ParseFloatError, ParseIntError},
     str::Utf8Error,
 };
 
+use chrono::{DateTime, TimeZone, Utc};
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-use crate::event::metric::{Metric, MetricKind, MetricValue, StatisticKind};
+use crate::{
+    config::log_schema,
+    event::{
+        metric::{Metric, MetricKind, MetricTags, MetricValue, StatisticKind},
+        Event, LogEvent, Value,
+    },
+};
 
 static WHITESPACE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
 static NONALPHANUM: Lazy<Regex> = Lazy::new(|| Regex::new(r"[^a-zA-Z_\-0-9\.]").unwrap());
 
+/// Parses a packet, which is either a metric, a DogStatsD event or a DogStatsD service check.
+///
+/// Events are mapped onto logs, and service checks onto absolute gauges of their status.
+pub fn parse_packet(packet: &str) -> Result<Vec<Event>, ParseError> {
+    if let Some(event) = packet.strip_prefix("_e{") {
+        parse_event(event).map(|log| vec![Event::Log(log)])
+    } else if let Some(service_check) = packet.strip_prefix("_sc|") {
+        parse_service_check(service_check).map(|metric| vec![Event::Metric(metric)])
+    } else {
+        parse_metrics(packet).map(|metrics| metrics.into_iter().map(Event::Metric).collect())
+    }
+}
+
+/// Parses a metric packet with a single value.
 pub fn parse(packet: &str) -> Result<Metric, ParseError> {
-    // https://docs.datadoghq.com/developers/dogstatsd/datagram_shell/#datagram-format
-    let key_and_body = packet.splitn(2, ':').collect::<Vec<_>>();
-    if key_and_body.len() != 2 {
-        return Err(ParseError::Malformed(
-            "should be key and body with ':' separator",
-        ));
+    let mut metrics = parse_metrics(packet)?;
+    if metrics.len() != 1 {
+        return Err(ParseError::Malformed("expected a single value"));
     }
-    let (key, body) = (key_and_body[0], key_and_body[1]);
+    Ok(metrics.remove(0))
+}
+
+/// Parses a metric packet, which may carry several values with the DogStatsD
+/// `<name>:<value>:<value>|<type>` format.
+pub fn parse_metrics(packet: &str) -> Result<Vec<Metric>, ParseError> {
+    // https://docs.datadoghq.com/developers/dogstatsd/datagram_shell/#datagram-format
+    let (key, body) = packet.split_once(':').ok_or(ParseError::Malformed(
+        "should be key and body with ':' separator",
+    ))?;
 
     let parts = body.split('|').collect::<Vec<_>>();
     if parts.len() < 2 {
@@ -32,31 +58,37 @@ pub fn parse(packet: &str) -> Result<Metric, ParseError> {
 
     let name = sanitize_key(key);
     let metric_type = parts[1];
+    let metadata = MetricMetadata::parse(&parts[2..])?;
 
-    // sampling part is optional and comes after metric type part
-    let sampling = parts.get(2).filter(|s| s.starts_with('@'));
-    let sample_rate = if let Some(s) = sampling {
-        1.0 / sanitize_sampling(parse_sampling(s)?)
+    // set members may contain ':', so they are never packed
+    let values = if metric_type == "s" {
+        vec![parts[0]]
     } else {
-        1.0
+        parts[0].split(':').collect()
     };
 
-    // tags are optional and could be found either after sampling of after metric type part
-    let tags = if sampling.is_none() {
-        parts.get(2)
-    } else {
-        parts.get(3)
-    };
-    let tags = tags.filter(|s| s.starts_with('#'));
-    let tags = if let Some(t) = tags {
-        Some(parse_tags(t)?)
-    } else {
-        None
-    };
+    values
+        .into_iter()
+        .map(|value| {
+            parse_value(&name, metric_type, value, metadata.sample_rate).map(|metric| {
+                metric
+                    .with_tags(metadata.tags.clone())
+                    .with_timestamp(metadata.timestamp)
+            })
+        })
+        .collect()
+}
 
+fn parse_value(
+    name: &str,
+    metric_type: &str,
+    value: &str,
+    sample_rate: f64,
+) -> Result<Metric, ParseError> {
+    let name = name.to_owned();
     let metric = match metric_type {
         "c" => {
-            let val: f64 = parts[0].parse()?;
+            let val: f64 = value.parse()?;
             Metric::new(
                 name,
                 MetricKind::Incremental,
@@ -64,55 +96,228 @@ pub fn parse(packet: &str) -> Result<Metric, ParseError> {
                     value: val * sample_rate,
                 },
             )
-            .with_tags(tags)
         }
         unit @ "h" | unit @ "ms" | unit @ "d" => {
-            let val: f64 = parts[0].parse()?;
+            let val: f64 = value.parse()?;
             Metric::new(
-                name, MetricKind::Incremental, MetricValue::Distribution {
+                name,
+                MetricKind::Incremental,
+                MetricValue::Distribution {
                     samples: vector_core::samples![convert_to_base_units(unit, val) => sample_rate as u32],
                     statistic: convert_to_statistic(unit),
                 },
-            ).with_tags(tags)
+            )
         }
         "g" => {
-            let value = if parts[0]
+            let gauge = if value
                 .chars()
                 .next()
                 .map(|c| c.is_ascii_digit())
                 .ok_or(ParseError::Malformed("empty first body component"))?
             {
-                parts[0].parse()?
+                value.parse()?
             } else {
-                parts[0][1..].parse()?
+                value[1..].parse()?
             };
 
-            match parse_direction(parts[0])? {
-                None => Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
-                    .with_tags(tags),
+            match parse_direction(value)? {
+                None => Metric::new(
+                    name,
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value: gauge },
+                ),
                 Some(sign) => Metric::new(
                     name,
                     MetricKind::Incremental,
                     MetricValue::Gauge {
-                        value: value * sign,
+                        value: gauge * sign,
                     },
-                )
-                .with_tags(tags),
+                ),
             }
         }
         "s" => Metric::new(
             name,
             MetricKind::Incremental,
             MetricValue::Set {
-                values: vec![parts[0].into()].into_iter().collect(),
+                values: vec![value.into()].into_iter().collect(),
             },
-        )
-        .with_tags(tags),
+        ),
         other => return Err(ParseError::UnknownMetricType(other.into())),
     };
     Ok(metric)
 }
 
+/// The optional components following the type of a metric, which may come in any order.
+struct MetricMetadata {
+    sample_rate: f64,
+    tags: Option<MetricTags>,
+    timestamp: Option<DateTime<Utc>>,
+}
+
+impl MetricMetadata {
+    fn parse(parts: &[&str]) -> Result<Self, ParseError> {
+        let mut sample_rate = 1.0;
+        let mut tags = None;
+        let mut container_id = None;
+        let mut timestamp = None;
+
+        for part in parts {
+            if part.starts_with('@') {
+                sample_rate = 1.0 / sanitize_sampling(parse_sampling(part)?);
+            } else if part.starts_with('#') {
+                tags = Some(parse_tags(part)?);
+            } else if let Some(id) = part.strip_prefix("c:") {
+                container_id = Some(id);
+            } else if let Some(seconds) = part.strip_prefix('T') {
+                timestamp = Some(parse_timestamp(seconds)?);
+            }
+        }
+
+        if let Some(id) = container_id {
+            tags.get_or_insert_with(MetricTags::new)
+                .insert("container_id".to_owned(), id.to_owned());
+        }
+
+        Ok(Self {
+            sample_rate,
+            tags,
+            timestamp,
+        })
+    }
+}
+
+/// Parses a DogStatsD event following its `_e{` prefix:
+/// `<title length>,<text length>}:<title>|<text>|d:<timestamp>|h:<hostname>|p:<priority>|t:<alert type>|#<tags>`.
+fn parse_event(input: &str) -> Result<LogEvent, ParseError> {
+    let (lengths, rest) = input.split_once("}:").ok_or(ParseError::Malformed(
+        "event should start with the lengths of its title and text",
+    ))?;
+    let (title_length, text_length) = lengths.split_once(',').ok_or(ParseError::Malformed(
+        "event lengths should be separated by ','",
+    ))?;
+    let (title_length, text_length): (usize, usize) = (title_length.parse()?, text_length.parse()?);
+
+    let title = rest.get(..title_length).ok_or(ParseError::Malformed(
+        "event title is shorter than its length",
+    ))?;
+    let rest = rest[title_length..]
+        .strip_prefix('|')
+        .ok_or(ParseError::Malformed(
+            "event title should be followed by its text",
+        ))?;
+    let text = rest.get(..text_length).ok_or(ParseError::Malformed(
+        "event text is shorter than its length",
+    ))?;
+    let metadata = match &rest[text_length..] {
+        "" => None,
+        metadata => Some(metadata.strip_prefix('|').ok_or(ParseError::Malformed(
+            "event text should be followed by pipe separated components",
+        ))?),
+    };
+
+    let mut log = LogEvent::default();
+    log.insert(log_schema().message_key(), unescape(text));
+    log.insert("title", title);
+    log.insert(log_schema().source_type_key(), "statsd");
+    log.insert("priority", "normal");
+    log.insert("alert_type", "info");
+
+    let mut timestamp = None;
+    for part in metadata
+        .into_iter()
+        .flat_map(|metadata| metadata.split('|'))
+    {
+        if let Some(seconds) = part.strip_prefix("d:") {
+            timestamp = Some(parse_timestamp(seconds)?);
+        } else if let Some(host) = part.strip_prefix("h:") {
+            log.insert(log_schema().host_key(), host);
+        } else if let Some(priority) = part.strip_prefix("p:") {
+            log.insert("priority", priority);
+        } else if let Some(alert_type) = part.strip_prefix("t:") {
+            log.insert("alert_type", alert_type);
+        } else if let Some(source_type_name) = part.strip_prefix("s:") {
+            log.insert("source_type_name", source_type_name);
+        } else if let Some(aggregation_key) = part.strip_prefix("k:") {
+            log.insert("aggregation_key", aggregation_key);
+        } else if part.starts_with('#') {
+            let tags = parse_tags(part)?
+                .into_iter()
+                .map(|(key, value)| (key, Value::from(value)))
+                .collect();
+            log.insert("tags", Value::Object(tags));
+        }
+    }
+    log.insert(
+        log_schema().timestamp_key(),
+        timestamp.unwrap_or_else(Utc::now),
+    );
+
+    Ok(log)
+}
+
+/// Parses a DogStatsD service check following its `_sc|` prefix:
+/// `<name>|<status>|d:<timestamp>|h:<hostname>|#<tags>|m:<message>`.
+///
+/// The status, from `0` for OK to `3` for unknown, becomes the value of a gauge, which is tagged
+/// with the hostname and the message.
+fn parse_service_check(input: &str) -> Result<Metric, ParseError> {
+    let mut parts = input.splitn(3, '|');
+    let name = parts
+        .next()
+        .filter(|name| !name.is_empty())
+        .ok_or(ParseError::Malformed("service check should have a name"))?;
+    let status: u8 = parts
+        .next()
+        .ok_or(ParseError::Malformed("service check should have a status"))?
+        .parse()?;
+    if status > 3 {
+        return Err(ParseError::Malformed(
+            "service check status should be between 0 and 3",
+        ));
+    }
+
+    let mut tags = MetricTags::new();
+    let mut timestamp = None;
+    let mut metadata = parts.next().unwrap_or_default();
+    while !metadata.is_empty() {
+        // the message comes last and may contain pipes
+        if let Some(message) = metadata.strip_prefix("m:") {
+            tags.insert("message".to_owned(), unescape(message));
+            break;
+        }
+        let (part, rest) = metadata.split_once('|').unwrap_or((metadata, ""));
+        if let Some(seconds) = part.strip_prefix("d:") {
+            timestamp = Some(parse_timestamp(seconds)?);
+        } else if let Some(host) = part.strip_prefix("h:") {
+            tags.insert("host".to_owned(), host.to_owned());
+        } else if part.starts_with('#') {
+            tags.extend(parse_tags(part)?);
+        }
+        metadata = rest;
+    }
+
+    Ok(Metric::new(
+        sanitize_key(name),
+        MetricKind::Absolute,
+        MetricValue::Gauge {
+            value: f64::from(status),
+        },
+    )
+    .with_tags((!tags.is_empty()).then_some(tags))
+    .with_timestamp(timestamp))
+}
+
+fn parse_timestamp(input: &str) -> Result<DateTime<Utc>, ParseError> {
+    let seconds: i64 = input.parse()?;
+    Utc.timestamp_opt(seconds, 0)
+        .single()
+        .ok_or(ParseError::Malformed("timestamp is out of range"))
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("\\n", "\n")
+}
+
 fn parse_sampling(input: &str) -> Result<f64, ParseError> {
     if !input.starts_with('@') || input.len() < 2 {
         return Err(ParseError::Malformed(
@@ -128,14 +333,14 @@ fn parse_sampling(input: &str) -> Result<f64, ParseError> {
     }
 }
 
-fn parse_tags(input: &str) -> Result<BTreeMap<String, String>, ParseError> {
+fn parse_tags(input: &str) -> Result<MetricTags, ParseError> {
     if !input.starts_with('#') || input.len() < 2 {
         return Err(ParseError::Malformed(
             "expected non empty '#'-prefixed tags component",
         ));
     }
 
-    let mut result = BTreeMap::new();
+    let mut result = MetricTags::new();
 
     let chunks = input[1..].split(',').collect::<Vec<_>>();
     for chunk in chunks {
@@ -228,8 +433,13 @@ impl From<ParseFloatError> for ParseError {
 mod test {
     use vector_common::assert_event_data_eq;
 
-    use super::{parse, sanitize_key, sanitize_sampling};
-    use crate::event::metric::{Metric, MetricKind, MetricValue, StatisticKind};
+    use chrono::{TimeZone, Utc};
+
+    use super::{parse, parse_metrics, parse_packet, sanitize_key, sanitize_sampling, ParseError};
+    use crate::{
+        config::log_schema,
+        event::metric::{Metric, MetricKind, MetricValue, StatisticKind},
+    };
 
     #[test]
     fn basic_counter() {
@@ -396,6 +606,100 @@ mod test {
         );
     }
 
+    #[test]
+    fn packed_values() {
+        let metrics = parse_metrics("glork:320:100|ms|@0.5").unwrap();
+        assert_eq!(metrics.len(), 2);
+        assert_event_data_eq!(
+            metrics[1].clone(),
+            Metric::new(
+                "glork",
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: vector_core::samples![0.1 => 2],
+                    statistic: StatisticKind::Histogram
+                },
+            ),
+        );
+        assert!(matches!(
+            parse("glork:320:100|ms"),
+            Err(ParseError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn metadata_in_any_order() {
+        assert_event_data_eq!(
+            parse(
+                "gaugor:333|g|T1656581400|c:83c0a99c0a54c0c187f461c7980e9b57|#region:us-west1|@0.5"
+            ),
+            Ok(Metric::new(
+                "gaugor",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 333.0 },
+            )
+            .with_tags(Some(
+                vec![
+                    (
+                        "container_id".to_owned(),
+                        "83c0a99c0a54c0c187f461c7980e9b57".to_owned()
+                    ),
+                    ("region".to_owned(), "us-west1".to_owned()),
+                ]
+                .into_iter()
+                .collect(),
+            ))
+            .with_timestamp(Some(Utc.timestamp(1656581400, 0)))),
+        );
+    }
+
+    #[test]
+    fn events() {
+        let events = parse_packet(
+            "_e{5,18}:Title|Text with a\\nbreak|d:1656581400|h:web-1|t:error|#env:prod",
+        )
+        .unwrap();
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "Text with a\nbreak".into());
+        assert_eq!(log["title"], "Title".into());
+        assert_eq!(log[log_schema().host_key()], "web-1".into());
+        assert_eq!(log["priority"], "normal".into());
+        assert_eq!(log["alert_type"], "error".into());
+        assert_eq!(log["tags.env"], "prod".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp(1656581400, 0).into()
+        );
+
+        assert!(parse_packet("_e{10,4}:Title|Text").is_err());
+    }
+
+    #[test]
+    fn service_checks() {
+        let events = parse_packet("_sc|app.health|2|h:web-1|#env:prod|m:Disk | full").unwrap();
+        assert_eq!(events.len(), 1);
+        assert_event_data_eq!(
+            events[0].as_metric().clone(),
+            Metric::new(
+                "app.health",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 2.0 },
+            )
+            .with_tags(Some(
+                vec![
+                    ("env".to_owned(), "prod".to_owned()),
+                    ("host".to_owned(), "web-1".to_owned()),
+                    ("message".to_owned(), "Disk | full".to_owned()),
+                ]
+                .into_iter()
+                .collect(),
+            )),
+        );
+
+        assert!(parse_packet("_sc|app.health|4").is_err());
+    }
+
     #[test]
     fn sanitizing_keys() {
         assert_eq!("foo-bar-baz", sanitize_key("foo/bar/baz"));
@@ -9,8 +9,12 @@ use vector_config::configurable_component;
 use super::StatsdDeserializer;
 use crate::{
     codecs::Decoder,
+    serde::default_max_length,
     shutdown::ShutdownSignal,
-    sources::{util::build_unix_stream_source, Source},
+    sources::{
+        util::{build_unix_datagram_source, build_unix_stream_source},
+        Source,
+    },
     SourceSender,
 };
 
@@ -43,3 +47,24 @@ pub fn statsd_unix(
         out,
     )
 }
+
+pub fn statsd_unix_datagram(
+    config: UnixConfig,
+    shutdown: ShutdownSignal,
+    out: SourceSender,
+) -> crate::Result<Source> {
+    let decoder = Decoder::new(
+        Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
+        Deserializer::Boxed(Box::new(StatsdDeserializer::unix())),
+    );
+
+    build_unix_datagram_source(
+        config.path,
+        None,
+        default_max_length(),
+        decoder,
+        |_events, _host| {},
+        shutdown,
+        out,
+    )
+}
@@ -52,6 +52,17 @@ pub struct SyslogConfig {
     ///
     /// [global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
     host_key: Option<String>,
+
+    /// The name of the log field to nest the elements of the RFC 5424 structured data under, by SD-ID.
+    ///
+    /// By default, each element is inserted at the root of the event, by SD-ID. The parameters repeated within an
+    /// element are kept as arrays of values, and the elements repeated with the same SD-ID are merged.
+    structured_data_key: Option<String>,
+
+    /// The name of the log field to keep the raw syslog frame in, as it was received.
+    ///
+    /// By default, the raw frame isn't kept.
+    raw_message_key: Option<String>,
 }
 
 /// Listener mode for the `syslog` source.
@@ -112,8 +123,17 @@ impl SyslogConfig {
             mode,
             host_key: None,
             max_length: crate::serde::default_max_length(),
+            structured_data_key: None,
+            raw_message_key: None,
         }
     }
+
+    fn deserializer(&self) -> SyslogDeserializer {
+        SyslogDeserializer::new(
+            self.structured_data_key.clone(),
+            self.raw_message_key.clone(),
+        )
+    }
 }
 
 impl GenerateConfig for SyslogConfig {
@@ -128,6 +148,8 @@ impl GenerateConfig for SyslogConfig {
             },
             host_key: None,
             max_length: crate::serde::default_max_length(),
+            structured_data_key: None,
+            raw_message_key: None,
         })
         .unwrap()
     }
@@ -152,6 +174,7 @@ impl SourceConfig for SyslogConfig {
                 let source = SyslogTcpSource {
                     max_length: self.max_length,
                     host_key,
+                    deserializer: self.deserializer(),
                 };
                 let shutdown_secs = 30;
                 let tls_config = tls.as_ref().map(|tls| tls.tls_config.clone());
@@ -177,6 +200,7 @@ impl SourceConfig for SyslogConfig {
                 address,
                 self.max_length,
                 host_key,
+                self.deserializer(),
                 receive_buffer_bytes,
                 cx.shutdown,
                 cx.out,
@@ -190,7 +214,7 @@ impl SourceConfig for SyslogConfig {
                     Framer::OctetCounting(OctetCountingDecoder::new_with_max_length(
                         self.max_length,
                     )),
-                    Deserializer::Syslog(SyslogDeserializer),
+                    Deserializer::Syslog(self.deserializer()),
                 );
 
                 build_unix_stream_source(
@@ -227,6 +251,7 @@ impl SourceConfig for SyslogConfig {
 struct SyslogTcpSource {
     max_length: usize,
     host_key: String,
+    deserializer: SyslogDeserializer,
 }
 
 impl TcpSource for SyslogTcpSource {
@@ -238,7 +263,7 @@ impl TcpSource for SyslogTcpSource {
     fn decoder(&self) -> Self::Decoder {
         Decoder::new(
             Framer::OctetCounting(OctetCountingDecoder::new_with_max_length(self.max_length)),
-            Deserializer::Syslog(SyslogDeserializer),
+            Deserializer::Syslog(self.deserializer.clone()),
         )
     }
 
@@ -255,6 +280,7 @@ pub fn udp(
     addr: SocketListenAddr,
     _max_length: usize,
     host_key: String,
+    deserializer: SyslogDeserializer,
     receive_buffer_bytes: Option<usize>,
     shutdown: ShutdownSignal,
     mut out: SourceSender,
@@ -284,7 +310,7 @@ pub fn udp(
             socket,
             Decoder::new(
                 Framer::Bytes(BytesDecoder::new()),
-                Deserializer::Syslog(SyslogDeserializer),
+                Deserializer::Syslog(deserializer),
             ),
         )
         .take_until(shutdown)
@@ -392,7 +418,7 @@ mod test {
         default_host: Option<Bytes>,
         bytes: Bytes,
     ) -> Option<Event> {
-        let parser = SyslogDeserializer;
+        let parser = SyslogDeserializer::default();
         let mut events = parser.parse(bytes, LogNamespace::Legacy).ok()?;
         handle_events(&mut events, host_key, default_host);
         Some(events.remove(0))
@@ -694,6 +720,34 @@ mod test {
         );
     }
 
+    #[test]
+    fn nests_structured_data_and_keeps_raw_frame() {
+        let config: SyslogConfig = toml::from_str(
+            r#"
+            mode = "udp"
+            address = "127.0.0.1:1235"
+            structured_data_key = "structured_data"
+            raw_message_key = "raw"
+            "#,
+        )
+        .unwrap();
+        let raw = r#"<13>1 2019-02-13T19:48:34+00:00 74794bfb6795 root 8449 - [meta sequenceId="1" sequenceId="2"] i am foobar"#;
+
+        let mut events = config
+            .deserializer()
+            .parse(Bytes::from(raw), LogNamespace::Legacy)
+            .unwrap();
+        handle_events(&mut events, "host", None);
+        let log = events[0].as_log();
+        assert_eq!(
+            log.get("structured_data.meta.sequenceId"),
+            Some(&Value::from(vec![Value::from("1"), Value::from("2")]))
+        );
+        assert_eq!(log.get("meta"), None);
+        assert_eq!(log.get("raw"), Some(&Value::from(raw)));
+        assert_eq!(log[log_schema().message_key()], "i am foobar".into());
+    }
+
     #[test]
     fn syslog_ng_default_network() {
         let msg = "i am foobar";
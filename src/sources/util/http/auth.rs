@@ -1,6 +1,8 @@
 use std::convert::TryFrom;
 
+use derivative::Derivative;
 use headers::{Authorization, HeaderMapExt};
+use openssl::{hash::MessageDigest, memcmp, pkey::PKey, sign::Signer};
 use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 use warp::http::HeaderMap;
@@ -11,71 +13,317 @@ use warp::http::HeaderMap;
 ))]
 use super::error::ErrorMessage;
 
-/// HTTP Basic authentication configuration.
+/// HTTP authentication configuration.
+///
+/// Basic authentication and bearer tokens are checked against the `Authorization` header, and a request is accepted
+/// if it matches any of them. When HMAC signatures are configured, the signature of every request is checked as well.
 #[configurable_component]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct HttpSourceAuthConfig {
     /// The username for basic authentication.
-    pub username: String,
+    pub username: Option<String>,
 
     /// The password for basic authentication.
-    pub password: SensitiveString,
+    pub password: Option<SensitiveString>,
+
+    /// A list of bearer tokens, any of which is accepted.
+    #[serde(default)]
+    pub tokens: Vec<SensitiveString>,
+
+    #[configurable(derived)]
+    pub hmac: Option<HttpSourceHmacConfig>,
+}
+
+/// Validation of the HMAC signature of the request bodies, as sent by many webhook providers.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct HttpSourceHmacConfig {
+    /// The secret shared with the sender, which the signatures are computed with.
+    pub secret: SensitiveString,
+
+    /// The name of the header carrying the signature.
+    #[serde(default = "default_hmac_header")]
+    pub header: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub algorithm: HmacAlgorithm,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub encoding: SignatureEncoding,
+
+    /// A prefix of the header value to remove before decoding the signature, such as `sha256=`.
+    pub prefix: Option<String>,
+}
+
+fn default_hmac_header() -> String {
+    "X-Hub-Signature-256".to_owned()
+}
+
+/// The hash function of the HMAC signatures.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HmacAlgorithm {
+    /// SHA-1.
+    Sha1,
+
+    /// SHA-256.
+    #[derivative(Default)]
+    Sha256,
+
+    /// SHA-512.
+    Sha512,
+}
+
+impl HmacAlgorithm {
+    fn digest(self) -> MessageDigest {
+        match self {
+            Self::Sha1 => MessageDigest::sha1(),
+            Self::Sha256 => MessageDigest::sha256(),
+            Self::Sha512 => MessageDigest::sha512(),
+        }
+    }
+}
+
+/// The encoding of the HMAC signatures in the header.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureEncoding {
+    /// Hexadecimal, in either case.
+    #[derivative(Default)]
+    Hex,
+
+    /// Standard Base64.
+    Base64,
 }
 
 impl TryFrom<Option<&HttpSourceAuthConfig>> for HttpSourceAuth {
     type Error = String;
 
     fn try_from(auth: Option<&HttpSourceAuthConfig>) -> Result<Self, Self::Error> {
-        match auth {
-            Some(auth) => {
+        let auth = match auth {
+            Some(auth) => auth,
+            None => return Ok(HttpSourceAuth::default()),
+        };
+
+        let mut tokens = Vec::new();
+        match (&auth.username, &auth.password) {
+            (Some(username), Some(password)) => {
                 let mut headers = HeaderMap::new();
-                headers.typed_insert(Authorization::basic(
-                    auth.username.as_str(),
-                    auth.password.inner(),
-                ));
+                headers.typed_insert(Authorization::basic(username.as_str(), password.inner()));
                 match headers.get("authorization") {
-                    Some(value) => {
-                        let token = value
+                    Some(value) => tokens.push(
+                        value
                             .to_str()
                             .map_err(|error| format!("Failed stringify HeaderValue: {:?}", error))?
-                            .to_owned();
-                        Ok(HttpSourceAuth { token: Some(token) })
-                    }
-                    None => Err("Authorization headers wasn't generated".to_owned()),
+                            .to_owned(),
+                    ),
+                    None => return Err("Authorization headers wasn't generated".to_owned()),
                 }
             }
-            None => Ok(HttpSourceAuth { token: None }),
+            (None, None) => {}
+            _ => {
+                return Err(
+                    "Both `username` and `password` must be set for basic authentication"
+                        .to_owned(),
+                )
+            }
+        }
+        tokens.extend(
+            auth.tokens
+                .iter()
+                .map(|token| format!("Bearer {}", token.inner())),
+        );
+
+        if tokens.is_empty() && auth.hmac.is_none() {
+            return Err("No authentication strategy is configured".to_owned());
         }
+
+        Ok(HttpSourceAuth {
+            tokens,
+            hmac: auth.hmac.clone(),
+        })
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct HttpSourceAuth {
+    /// The accepted values of the `Authorization` header.
     #[allow(unused)] // triggered by check-component-features
-    pub(self) token: Option<String>,
+    pub(self) tokens: Vec<String>,
+    #[allow(unused)] // triggered by check-component-features
+    pub(self) hmac: Option<HttpSourceHmacConfig>,
 }
 
 impl HttpSourceAuth {
     #[allow(unused)] // triggered by check-component-features
-    pub fn is_valid(&self, header: &Option<String>) -> Result<(), ErrorMessage> {
+    pub fn is_valid(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), ErrorMessage> {
         use warp::http::StatusCode;
 
-        match (&self.token, header) {
-            (Some(token1), Some(token2)) => {
-                if token1 == token2 {
-                    Ok(())
-                } else {
-                    Err(ErrorMessage::new(
-                        StatusCode::UNAUTHORIZED,
-                        "Invalid username/password".to_owned(),
-                    ))
+        let unauthorized =
+            |message: &str| ErrorMessage::new(StatusCode::UNAUTHORIZED, message.to_owned());
+
+        if !self.tokens.is_empty() {
+            match headers.get("authorization") {
+                Some(header) => {
+                    if !self
+                        .tokens
+                        .iter()
+                        .any(|token| constant_time_eq(token.as_bytes(), header.as_bytes()))
+                    {
+                        return Err(unauthorized("Invalid credentials"));
+                    }
                 }
+                None => return Err(unauthorized("No authorization header")),
             }
-            (Some(_), None) => Err(ErrorMessage::new(
-                StatusCode::UNAUTHORIZED,
-                "No authorization header".to_owned(),
-            )),
-            (None, _) => Ok(()),
+        }
+
+        if let Some(hmac) = &self.hmac {
+            let header = headers
+                .get(hmac.header.as_str())
+                .and_then(|header| header.to_str().ok())
+                .ok_or_else(|| unauthorized("No signature header"))?;
+            let signature =
+                decode_signature(hmac, header).ok_or_else(|| unauthorized("Invalid signature"))?;
+            let expected = sign(hmac, body).map_err(|error| {
+                ErrorMessage::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Failed computing signature: {}", error),
+                )
+            })?;
+            if !constant_time_eq(&signature, &expected) {
+                return Err(unauthorized("Invalid signature"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn decode_signature(hmac: &HttpSourceHmacConfig, header: &str) -> Option<Vec<u8>> {
+    let header = header.trim();
+    let signature = match &hmac.prefix {
+        Some(prefix) => header.strip_prefix(prefix.as_str())?,
+        None => header,
+    };
+    match hmac.encoding {
+        SignatureEncoding::Hex => hex::decode(signature).ok(),
+        SignatureEncoding::Base64 => base64::decode(signature).ok(),
+    }
+}
+
+fn sign(hmac: &HttpSourceHmacConfig, body: &[u8]) -> Result<Vec<u8>, openssl::error::ErrorStack> {
+    let key = PKey::hmac(hmac.secret.inner().as_bytes())?;
+    let mut signer = Signer::new(hmac.algorithm.digest(), &key)?;
+    signer.update(body)?;
+    signer.sign_to_vec()
+}
+
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len() && memcmp::eq(left, right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth(config: HttpSourceAuthConfig) -> HttpSourceAuth {
+        HttpSourceAuth::try_from(Some(&config)).unwrap()
+    }
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    fn hmac(encoding: SignatureEncoding, prefix: Option<&str>) -> HttpSourceHmacConfig {
+        HttpSourceHmacConfig {
+            secret: "It's a Secret to Everybody".to_owned().into(),
+            header: default_hmac_header(),
+            algorithm: HmacAlgorithm::Sha256,
+            encoding,
+            prefix: prefix.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn accepts_basic_auth_and_bearer_tokens() {
+        let auth = auth(HttpSourceAuthConfig {
+            username: Some("user".to_owned()),
+            password: Some("pass".to_owned().into()),
+            tokens: vec!["one".to_owned().into(), "two".to_owned().into()],
+            hmac: None,
+        });
+
+        for header in ["Basic dXNlcjpwYXNz", "Bearer one", "Bearer two"] {
+            assert!(auth
+                .is_valid(&headers(&[("authorization", header)]), b"")
+                .is_ok());
+        }
+        for header in ["Basic dXNlcjpvdGhlcg==", "Bearer three"] {
+            assert!(auth
+                .is_valid(&headers(&[("authorization", header)]), b"")
+                .is_err());
+        }
+        assert!(auth.is_valid(&HeaderMap::new(), b"").is_err());
+    }
+
+    #[test]
+    fn validates_hmac_signatures() {
+        // The example of the GitHub documentation on validating webhook deliveries.
+        let auth = auth(HttpSourceAuthConfig {
+            hmac: Some(hmac(SignatureEncoding::Hex, Some("sha256="))),
+            ..Default::default()
+        });
+        let signature = "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+        assert!(auth
+            .is_valid(
+                &headers(&[("x-hub-signature-256", signature)]),
+                b"Hello, World!"
+            )
+            .is_ok());
+        assert!(auth
+            .is_valid(
+                &headers(&[("x-hub-signature-256", signature)]),
+                b"Hello, Vector!"
+            )
+            .is_err());
+        assert!(auth.is_valid(&HeaderMap::new(), b"Hello, World!").is_err());
+
+        let auth = auth(HttpSourceAuthConfig {
+            hmac: Some(hmac(SignatureEncoding::Base64, None)),
+            ..Default::default()
+        });
+        assert!(auth
+            .is_valid(
+                &headers(&[(
+                    "x-hub-signature-256",
+                    "dXEH6g6yUJ/CESIczphLijdXC211hsIsRvQ3nIsEPhc="
+                )]),
+                b"Hello, World!"
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn rejects_incomplete_configs() {
+        for config in [
+            HttpSourceAuthConfig::default(),
+            HttpSourceAuthConfig {
+                username: Some("user".to_owned()),
+                ..Default::default()
+            },
+        ] {
+            assert!(HttpSourceAuth::try_from(Some(&config)).is_err());
         }
     }
 }
@@ -27,6 +27,9 @@ pub fn decode(header: &Option<String>, mut body: Bytes) -> Result<Bytes, ErrorMe
                         .map_err(|error| handle_decode_error(encoding, error))?;
                     decoded.into()
                 }
+                "zstd" => zstd::stream::decode_all(body.reader())
+                    .map_err(|error| handle_decode_error(encoding, error))?
+                    .into(),
                 "snappy" => SnappyDecoder::new()
                     .decompress_vec(&body)
                     .map_err(|error| handle_decode_error(encoding, error))?
@@ -54,3 +57,21 @@ fn handle_decode_error(encoding: &str, error: impl std::error::Error) -> ErrorMe
         format!("Failed decompressing payload with {} decoder.", encoding),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_zstd() {
+        let body = zstd::stream::encode_all(&b"hello world"[..], 0).unwrap();
+        let decoded = decode(&Some("zstd".to_owned()), body.into()).unwrap();
+        assert_eq!(decoded, Bytes::from_static(b"hello world"));
+    }
+
+    #[test]
+    fn rejects_invalid_zstd() {
+        let error = decode(&Some("zstd".to_owned()), Bytes::from_static(b"hello")).unwrap_err();
+        assert_eq!(error.code(), StatusCode::UNPROCESSABLE_ENTITY.as_u16());
+    }
+}
@@ -94,14 +94,12 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                 })
                 .untuple_one()
                 .and(warp::path::full())
-                .and(warp::header::optional::<String>("authorization"))
                 .and(warp::header::optional::<String>("content-encoding"))
                 .and(warp::header::headers_cloned())
                 .and(warp::body::bytes())
                 .and(warp::query::<HashMap<String, String>>())
                 .and_then(
                     move |path: FullPath,
-                          auth_header,
                           encoding_header,
                           headers: HeaderMap,
                           body: Bytes,
@@ -115,7 +113,7 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                         });
 
                         let events = auth
-                            .is_valid(&auth_header)
+                            .is_valid(&headers, &body)
                             .and_then(|()| decode(&encoding_header, body))
                             .and_then(|body| {
                                 self.build_events(body, headers, query_parameters, path.as_str())
@@ -57,6 +57,15 @@ pub(crate) trait HttpClientBuilder {
 
     /// Called before the HTTP request is made to build out the context.
     fn build(&self, url: &Uri) -> Self::Context;
+
+    /// Called at every interval to list the URLs to call along with their context.
+    ///
+    /// By default, the configured URLs are called.
+    fn targets(&self, urls: &[Uri]) -> Vec<(Uri, Self::Context)> {
+        urls.iter()
+            .map(|url| (url.clone(), self.build(url)))
+            .collect()
+    }
 }
 
 /// Methods that allow context-specific behavior during the scraping procedure.
@@ -112,18 +121,15 @@ pub(crate) async fn call<
         inputs.interval_secs,
     )))
     .take_until(inputs.shutdown)
-    .map(move |_| stream::iter(inputs.urls.clone()))
+    .map(move |_| stream::iter(context_builder.targets(&inputs.urls)))
     .flatten()
-    .map(move |url| {
+    .map(move |(url, mut context)| {
         // Building the HttpClient should not fail as it is just setting up the client with the
         // proxy and tls settings.
         let client = HttpClient::new(inputs.tls.clone(), &inputs.proxy)
             .expect("Building HTTP client failed");
         let endpoint = url.to_string();
 
-        let context_builder = context_builder.clone();
-        let mut context = context_builder.build(&url);
-
         let mut builder = match http_method {
             HttpMethod::Head => Request::head(&url),
             HttpMethod::Get => Request::get(&url),
@@ -3,7 +3,11 @@ mod body_decoding;
 mod encoding_config;
 #[cfg(all(unix, feature = "sources-dnstap"))]
 pub mod framestream;
-#[cfg(any(feature = "sources-vector", feature = "sources-opentelemetry"))]
+#[cfg(any(
+    feature = "sources-grpc_server",
+    feature = "sources-vector",
+    feature = "sources-opentelemetry"
+))]
 pub mod grpc;
 #[cfg(any(
     feature = "sources-utils-http-auth",
@@ -15,7 +19,13 @@ pub mod grpc;
 pub mod http;
 #[cfg(any(feature = "sources-http_client", feature = "sources-prometheus"))]
 pub mod http_client;
-#[cfg(any(feature = "sources-aws_sqs", feature = "sources-gcp_pubsub"))]
+#[cfg(any(
+    feature = "sources-aws_kinesis_streams",
+    feature = "sources-aws_sqs",
+    feature = "sources-azure_event_hubs",
+    feature = "sources-gcp_pubsub",
+    feature = "sources-sftp"
+))]
 mod message_decoding;
 pub mod multiline_config;
 #[cfg(any(feature = "sources-utils-net-tcp", feature = "sources-utils-net-udp"))]
@@ -25,7 +35,7 @@ pub mod net;
     any(feature = "sources-socket", feature = "sources-utils-net-unix",)
 ))]
 mod unix;
-#[cfg(all(unix, feature = "sources-socket"))]
+#[cfg(all(unix, any(feature = "sources-socket", feature = "sources-statsd")))]
 mod unix_datagram;
 #[cfg(all(unix, feature = "sources-utils-net-unix"))]
 mod unix_stream;
@@ -39,7 +49,7 @@ pub use multiline_config::MultilineConfig;
     any(feature = "sources-socket", feature = "sources-utils-net-unix",)
 ))]
 pub use unix::change_socket_permissions;
-#[cfg(all(unix, feature = "sources-socket",))]
+#[cfg(all(unix, any(feature = "sources-socket", feature = "sources-statsd")))]
 pub use unix_datagram::build_unix_datagram_source;
 #[cfg(all(unix, feature = "sources-utils-net-unix",))]
 pub use unix_stream::build_unix_stream_source;
@@ -60,5 +70,11 @@ pub use self::http::ErrorMessage;
 pub use self::http::HttpSource;
 #[cfg(feature = "sources-utils-http-auth")]
 pub use self::http::HttpSourceAuthConfig;
-#[cfg(any(feature = "sources-aws_sqs", feature = "sources-gcp_pubsub"))]
+#[cfg(any(
+    feature = "sources-aws_kinesis_streams",
+    feature = "sources-aws_sqs",
+    feature = "sources-azure_event_hubs",
+    feature = "sources-gcp_pubsub",
+    feature = "sources-sftp"
+))]
 pub use self::message_decoding::decode_message;
@@ -1,7 +1,7 @@
 use std::{convert::TryFrom, time::Duration};
 
 use regex::bytes::Regex;
-use snafu::{ResultExt, Snafu};
+use snafu::{OptionExt, ResultExt, Snafu};
 use vector_config::configurable_component;
 
 use crate::line_agg;
@@ -11,36 +11,110 @@ use crate::line_agg;
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[serde(deny_unknown_fields)]
 pub struct MultilineConfig {
+    #[configurable(derived)]
+    pub preset: Option<MultilinePreset>,
+
     /// Regular expression pattern that is used to match the start of a new message.
-    pub start_pattern: String,
+    ///
+    /// Required unless `preset` is set, in which case it overrides the pattern of the preset.
+    pub start_pattern: Option<String>,
 
     /// Regular expression pattern that is used to determine whether or not more lines should be read.
     ///
     /// This setting must be configured in conjunction with `mode`.
-    pub condition_pattern: String,
+    ///
+    /// Required unless `preset` is set, in which case it overrides the pattern of the preset.
+    pub condition_pattern: Option<String>,
 
     /// Aggregation mode.
     ///
     /// This setting must be configured in conjunction with `condition_pattern`.
-    pub mode: line_agg::Mode,
+    ///
+    /// Required unless `preset` is set, in which case it overrides the mode of the preset.
+    pub mode: Option<line_agg::Mode>,
 
     /// The maximum amount of time to wait for the next additional line, in milliseconds.
     ///
     /// Once this timeout is reached, the buffered message is guaranteed to be flushed, even if incomplete.
+    #[serde(default = "default_timeout_ms")]
     pub timeout_ms: u64,
 }
 
+const fn default_timeout_ms() -> u64 {
+    1000
+}
+
+/// Multi-line aggregation presets, which group the lines of the stack traces of common languages.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MultilinePreset {
+    /// Java exceptions, whose indented `at` lines and `Caused by:` lines follow the first line.
+    Java,
+
+    /// Python tracebacks, from `Traceback (most recent call last):` through the exception line.
+    Python,
+
+    /// Go panics, from the `panic:` line through the stacks of the goroutines.
+    Go,
+
+    /// Ruby exceptions, whose indented `from` lines follow the first line.
+    Ruby,
+}
+
+impl MultilinePreset {
+    /// The start pattern, condition pattern and mode of the preset.
+    const fn settings(self) -> (&'static str, &'static str, line_agg::Mode) {
+        match self {
+            Self::Java => (
+                r"^[^\s]",
+                r"^(\s+|Caused by:)",
+                line_agg::Mode::ContinueThrough,
+            ),
+            Self::Python => (
+                r"^Traceback \(most recent call last\):",
+                r"^\s",
+                line_agg::Mode::ContinuePast,
+            ),
+            Self::Go => (
+                r"^panic: ",
+                r"^(\s|$|\[signal |goroutine \d+ \[|created by |[\w./*()\[\]-]+\(.*\)$)",
+                line_agg::Mode::ContinueThrough,
+            ),
+            Self::Ruby => (r"^[^\s]", r"^\s+from\s", line_agg::Mode::ContinueThrough),
+        }
+    }
+}
+
 impl TryFrom<&MultilineConfig> for line_agg::Config {
     type Error = Error;
 
     fn try_from(config: &MultilineConfig) -> Result<Self, Self::Error> {
         let MultilineConfig {
+            preset,
             start_pattern,
             condition_pattern,
             mode,
             timeout_ms,
         } = config;
 
+        let preset = preset.map(MultilinePreset::settings);
+        let start_pattern = start_pattern
+            .as_deref()
+            .or_else(|| preset.map(|(start_pattern, _, _)| start_pattern))
+            .context(MissingMultilineOptionSnafu {
+                option: "start_pattern",
+            })?;
+        let condition_pattern = condition_pattern
+            .as_deref()
+            .or_else(|| preset.map(|(_, condition_pattern, _)| condition_pattern))
+            .context(MissingMultilineOptionSnafu {
+                option: "condition_pattern",
+            })?;
+        let mode = mode
+            .or_else(|| preset.map(|(_, _, mode)| mode))
+            .context(MissingMultilineOptionSnafu { option: "mode" })?;
+
         let start_pattern = Regex::new(start_pattern)
             .with_context(|_| InvalidMultilineStartPatternSnafu { start_pattern })?;
         let condition_pattern = Regex::new(condition_pattern)
@@ -50,7 +124,7 @@ impl TryFrom<&MultilineConfig> for line_agg::Config {
         Ok(Self {
             start_pattern,
             condition_pattern,
-            mode: *mode,
+            mode,
             timeout,
         })
     }
@@ -76,4 +150,138 @@ pub enum Error {
         condition_pattern: String,
         source: regex::Error,
     },
+    #[snafu(display("multiline option {:?} is required unless a preset is set", option))]
+    MissingMultilineOption { option: &'static str },
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use futures::StreamExt;
+
+    use super::*;
+
+    fn config(preset: MultilinePreset) -> line_agg::Config {
+        let config = MultilineConfig {
+            preset: Some(preset),
+            start_pattern: None,
+            condition_pattern: None,
+            mode: None,
+            timeout_ms: 10,
+        };
+        (&config).try_into().unwrap()
+    }
+
+    async fn aggregate(config: line_agg::Config, lines: &[&'static str]) -> Vec<String> {
+        let stream = futures::stream::iter(
+            lines
+                .iter()
+                .map(|line| ("test.log", Bytes::from_static(line.as_bytes()), ())),
+        );
+        line_agg::LineAgg::new(stream, line_agg::Logic::new(config))
+            .map(|(_, line, _)| String::from_utf8(line.to_vec()).unwrap())
+            .collect()
+            .await
+    }
+
+    #[test]
+    fn requires_patterns_without_preset() {
+        let config = MultilineConfig {
+            preset: None,
+            start_pattern: Some("^[^\\s]".to_owned()),
+            condition_pattern: None,
+            mode: Some(line_agg::Mode::ContinueThrough),
+            timeout_ms: 1000,
+        };
+        let error = line_agg::Config::try_from(&config).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::MissingMultilineOption {
+                option: "condition_pattern"
+            }
+        ));
+    }
+
+    #[test]
+    fn settings_override_presets() {
+        let config = MultilineConfig {
+            preset: Some(MultilinePreset::Java),
+            start_pattern: None,
+            condition_pattern: None,
+            mode: Some(line_agg::Mode::HaltBefore),
+            timeout_ms: 1000,
+        };
+        let config = line_agg::Config::try_from(&config).unwrap();
+        assert_eq!(config.mode, line_agg::Mode::HaltBefore);
+        assert_eq!(config.start_pattern.as_str(), r"^[^\s]");
+    }
+
+    #[tokio::test]
+    async fn java_preset() {
+        let lines = [
+            "Exception in thread \"main\" java.lang.IllegalStateException: boom",
+            "\tat com.example.App.run(App.java:12)",
+            "Caused by: java.io.IOException: closed",
+            "\tat com.example.App.read(App.java:30)",
+            "\t... 1 more",
+            "Started App in 1.2 seconds",
+        ];
+        assert_eq!(
+            aggregate(config(MultilinePreset::Java), &lines).await,
+            vec![lines[..5].join("\n"), lines[5].to_owned()]
+        );
+    }
+
+    #[tokio::test]
+    async fn python_preset() {
+        let lines = [
+            "Starting worker",
+            "Traceback (most recent call last):",
+            "  File \"worker.py\", line 3, in <module>",
+            "    run()",
+            "ValueError: invalid literal",
+            "Stopping worker",
+        ];
+        assert_eq!(
+            aggregate(config(MultilinePreset::Python), &lines).await,
+            vec![
+                lines[0].to_owned(),
+                lines[1..5].join("\n"),
+                lines[5].to_owned()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn go_preset() {
+        let lines = [
+            "panic: runtime error: invalid memory address or nil pointer dereference",
+            "[signal SIGSEGV: segmentation violation code=0x1 addr=0x0 pc=0x47e2f4]",
+            "",
+            "goroutine 1 [running]:",
+            "main.(*Server).handle(0x0, {0x4b2f40, 0xc000010250})",
+            "\t/app/server.go:42 +0x14",
+            "created by main.main",
+            "\t/app/main.go:10 +0x5a",
+            "exit status 2",
+        ];
+        assert_eq!(
+            aggregate(config(MultilinePreset::Go), &lines).await,
+            vec![lines[..8].join("\n"), lines[8].to_owned()]
+        );
+    }
+
+    #[tokio::test]
+    async fn ruby_preset() {
+        let lines = [
+            "app.rb:6:in `/': divided by 0 (ZeroDivisionError)",
+            "\tfrom app.rb:6:in `divide'",
+            "\tfrom app.rb:9:in `<main>'",
+            "Done",
+        ];
+        assert_eq!(
+            aggregate(config(MultilinePreset::Ruby), &lines).await,
+            vec![lines[..3].join("\n"), lines[3].to_owned()]
+        );
+    }
 }
@@ -0,0 +1,402 @@
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use tokio_tungstenite::tungstenite::{error::Error as WsError, protocol::Message};
+use tokio_util::codec::FramedRead;
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, InternalEventHandle as _, Protocol, Registered,
+};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    common::websocket::{is_closed, PingInterval, WebSocketConnector},
+    config::{log_schema, GenerateConfig, Output, SourceConfig, SourceContext},
+    event::Event,
+    http::Auth,
+    internal_events::{
+        ConnectionOpen, EventsReceived, OpenGauge, StreamClosedError, WsConnectionError,
+        WsConnectionShutdown,
+    },
+    serde::{default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+    SourceSender,
+};
+
+/// Configuration for the `websocket` source.
+#[configurable_component(source("websocket"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WebSocketConfig {
+    /// The WebSocket URI to connect to.
+    ///
+    /// This should include the protocol and host, but can also include the port, path, and any other valid part of a URI.
+    uri: String,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    auth: Option<Auth>,
+
+    /// A text message to send once connected, such as a request to subscribe to a stream.
+    ///
+    /// The message is sent again after reconnecting.
+    subscribe_message: Option<String>,
+
+    /// The interval, in seconds, between sending PINGs to the remote peer.
+    ping_interval: Option<u64>,
+
+    /// The timeout, in seconds, while waiting for a PONG response from the remote peer.
+    ///
+    /// If a response is not received in this time, the connection is reestablished.
+    ping_timeout: Option<u64>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    decoding: DeserializerConfig,
+}
+
+impl GenerateConfig for WebSocketConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            uri: "wss://stream.example.com/v1/feed".into(),
+            tls: None,
+            auth: None,
+            subscribe_message: None,
+            ping_interval: None,
+            ping_timeout: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for WebSocketConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let connector = WebSocketConnector::new(self.uri.clone(), tls, self.auth.clone())?;
+        let decoder = DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .build();
+
+        Ok(Box::pin(
+            WebSocketSource {
+                connector,
+                decoder,
+                subscribe_message: self.subscribe_message.clone(),
+                ping_interval: self.ping_interval.filter(|v| *v > 0),
+                ping_timeout: self.ping_timeout.filter(|v| *v > 0),
+            }
+            .run(cx.out, cx.shutdown),
+        ))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(self.decoding.output_type())]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// Why the messages of a connection stopped being handled.
+enum Stop {
+    /// The source is shut down.
+    Shutdown,
+    /// The connection was closed, or failed, and is reestablished.
+    Reconnect,
+}
+
+struct WebSocketSource {
+    connector: WebSocketConnector,
+    decoder: Decoder,
+    subscribe_message: Option<String>,
+    ping_interval: Option<u64>,
+    ping_timeout: Option<u64>,
+}
+
+impl WebSocketSource {
+    async fn run(self, mut out: SourceSender, mut shutdown: ShutdownSignal) -> Result<(), ()> {
+        let bytes_received = register!(BytesReceived::from(Protocol("websocket".into())));
+
+        loop {
+            let ws_stream = tokio::select! {
+                _ = &mut shutdown => break,
+                ws_stream = self.connector.connect_backoff() => ws_stream,
+            };
+            let (mut ws_sink, mut ws_stream) = ws_stream.split();
+            let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
+
+            if let Some(message) = &self.subscribe_message {
+                if let Err(error) = ws_sink.send(Message::text(message)).await {
+                    emit!(WsConnectionError { error });
+                    continue;
+                }
+            }
+
+            let stop = self
+                .handle_messages(
+                    &mut ws_sink,
+                    &mut ws_stream,
+                    &bytes_received,
+                    &mut out,
+                    &mut shutdown,
+                )
+                .await;
+            if let Stop::Shutdown = stop {
+                let _ = ws_sink.close().await;
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_messages<O, WS>(
+        &self,
+        ws_sink: &mut O,
+        ws_stream: &mut WS,
+        bytes_received: &Registered<BytesReceived>,
+        out: &mut SourceSender,
+        shutdown: &mut ShutdownSignal,
+    ) -> Stop
+    where
+        O: Sink<Message, Error = WsError> + Unpin,
+        WS: Stream<Item = Result<Message, WsError>> + Unpin,
+    {
+        const PING: &[u8] = b"PING";
+
+        let mut ping_interval = PingInterval::new(self.ping_interval);
+        let mut last_pong = Instant::now();
+
+        loop {
+            let result = tokio::select! {
+                _ = &mut *shutdown => return Stop::Shutdown,
+
+                _ = ping_interval.tick() => {
+                    match self.check_received_pong_time(last_pong) {
+                        Ok(()) => ws_sink.send(Message::Ping(PING.to_vec())).await,
+                        Err(error) => Err(error),
+                    }
+                },
+
+                msg = ws_stream.next() => {
+                    // Pongs are sent automatically by tungstenite during reading from the stream.
+                    match msg {
+                        Some(Ok(Message::Text(text))) => {
+                            if self.handle_frame(Bytes::from(text), bytes_received, out).await.is_err() {
+                                return Stop::Shutdown;
+                            }
+                            Ok(())
+                        }
+                        Some(Ok(Message::Binary(data))) => {
+                            if self.handle_frame(Bytes::from(data), bytes_received, out).await.is_err() {
+                                return Stop::Shutdown;
+                            }
+                            Ok(())
+                        }
+                        Some(Ok(Message::Pong(_))) => {
+                            last_pong = Instant::now();
+                            Ok(())
+                        }
+                        Some(Ok(Message::Close(_))) | None => Err(WsError::ConnectionClosed),
+                        Some(Ok(_)) => Ok(()),
+                        Some(Err(error)) => Err(error),
+                    }
+                },
+            };
+
+            if let Err(error) = result {
+                if is_closed(&error) {
+                    emit!(WsConnectionShutdown);
+                } else {
+                    emit!(WsConnectionError { error });
+                }
+                return Stop::Reconnect;
+            }
+        }
+    }
+
+    fn check_received_pong_time(&self, last_pong: Instant) -> Result<(), WsError> {
+        if let Some(ping_timeout) = self.ping_timeout {
+            if last_pong.elapsed() > Duration::from_secs(ping_timeout) {
+                return Err(WsError::Io(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "Pong not received in time",
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the events of a frame, failing only once the events can't be sent anymore.
+    async fn handle_frame(
+        &self,
+        data: Bytes,
+        bytes_received: &Registered<BytesReceived>,
+        out: &mut SourceSender,
+    ) -> Result<(), ()> {
+        bytes_received.emit(ByteSize(data.len()));
+        let mut stream = FramedRead::new(data.as_ref(), self.decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((events, _byte_size)) => {
+                    let count = events.len();
+                    emit!(EventsReceived {
+                        count,
+                        byte_size: events.size_of()
+                    });
+
+                    let now = Utc::now();
+                    let events = events.into_iter().map(|mut event| {
+                        if let Event::Log(ref mut log) = event {
+                            log.try_insert(
+                                log_schema().source_type_key(),
+                                Bytes::from("websocket"),
+                            );
+                            log.try_insert(log_schema().timestamp_key(), now);
+                        }
+                        event
+                    });
+
+                    out.send_batch(events).await.map_err(|error| {
+                        emit!(StreamClosedError { error, count });
+                    })?;
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs`, no further
+                    // handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::accept_async;
+
+    use super::*;
+    use crate::test_util::{
+        collect_n,
+        components::{assert_source_compliance, SOURCE_TAGS},
+        next_addr,
+    };
+
+    fn config(addr: SocketAddr, subscribe_message: Option<&str>) -> WebSocketConfig {
+        WebSocketConfig {
+            uri: format!("ws://{}", addr),
+            tls: None,
+            auth: None,
+            subscribe_message: subscribe_message.map(Into::into),
+            ping_interval: None,
+            ping_timeout: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+        }
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebSocketConfig>();
+    }
+
+    #[tokio::test]
+    async fn receives_frames_after_subscribing() {
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let addr = next_addr();
+            let listener = TcpListener::bind(addr).await.unwrap();
+
+            let (tx, rx) = SourceSender::new_test();
+            let source = config(addr, Some(r#"{"subscribe":"trades"}"#))
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(stream).await.unwrap();
+            let subscribe = ws.next().await.unwrap().unwrap();
+            assert_eq!(subscribe, Message::text(r#"{"subscribe":"trades"}"#));
+            ws.send(Message::text("first")).await.unwrap();
+            ws.send(Message::binary(b"second".to_vec())).await.unwrap();
+
+            let events = collect_n(rx, 2).await;
+            assert_eq!(
+                events[0].as_log()[log_schema().message_key()],
+                "first".into()
+            );
+            assert_eq!(
+                events[1].as_log()[log_schema().message_key()],
+                "second".into()
+            );
+            assert_eq!(
+                events[0].as_log()[log_schema().source_type_key()],
+                "websocket".into()
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn reconnects_after_close() {
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+
+        let (tx, rx) = SourceSender::new_test();
+        let source = config(addr, Some("subscribe"))
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        for message in ["before", "after"] {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(stream).await.unwrap();
+            // The subscribe message is sent again on every connection.
+            assert_eq!(
+                ws.next().await.unwrap().unwrap(),
+                Message::text("subscribe")
+            );
+            ws.send(Message::text(message)).await.unwrap();
+            ws.close(None).await.unwrap();
+        }
+
+        let events = collect_n(rx, 2).await;
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "before".into()
+        );
+        assert_eq!(
+            events[1].as_log()[log_schema().message_key()],
+            "after".into()
+        );
+    }
+}
@@ -0,0 +1,378 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use bytes::Bytes;
+use futures::StreamExt;
+use snafu::{ResultExt, Snafu};
+use tokio::sync::mpsc;
+use vector_common::{
+    byte_size_of::ByteSizeOf,
+    finalizer::OrderedFinalizer,
+    internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol},
+};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{log_schema, AcknowledgementsConfig, DataType, Output, SourceConfig, SourceContext},
+    event::{BatchNotifier, BatchStatus},
+    internal_events::{
+        EventsReceived, StreamClosedError, WindowsEventLogCheckpointError,
+        WindowsEventLogParseError, WindowsEventLogReadError,
+    },
+    serde::bool_or_struct,
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod parser;
+mod subscription;
+
+use subscription::{RawEvent, Subscription};
+
+const CHECKPOINT_FILENAME: &str = "bookmark.xml";
+/// How long to wait for events, before checking whether the source is shut down.
+const WAIT_MILLIS: u32 = 500;
+/// How long to wait after an error, before reading again.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one channel is required"))]
+    NoChannels,
+    #[snafu(display("Could not read bookmark from {:?}: {}", path, source))]
+    ReadBookmark { path: PathBuf, source: io::Error },
+    #[snafu(display("Could not subscribe to event log channels: {}", source))]
+    Subscribe { source: io::Error },
+}
+
+/// Configuration for the `windows_eventlog` source.
+#[configurable_component(source("windows_eventlog"))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct WindowsEventLogConfig {
+    /// The channels to read events from.
+    #[derivative(Default(value = "vec![\"Application\".to_owned(), \"System\".to_owned()]"))]
+    pub channels: Vec<String>,
+
+    /// The XPath query selecting the events to read from each channel.
+    ///
+    /// For example, `*[System[Level<=3]]` selects the critical, error and warning events.
+    #[serde(default = "default_query")]
+    #[derivative(Default(value = "default_query()"))]
+    pub query: String,
+
+    /// Whether to read the events already in the channels, when the source is first started.
+    ///
+    /// Later on, events are read after the bookmark of the last event read.
+    #[serde(default)]
+    pub read_existing_events: bool,
+
+    /// The maximum number of events to read at once.
+    #[serde(default = "default_batch_size")]
+    #[derivative(Default(value = "default_batch_size()"))]
+    pub batch_size: u32,
+
+    /// Whether to render the message of events, with the message resources of their provider.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    pub render_message: bool,
+
+    /// Whether to add the XML of events to the `xml` field.
+    #[serde(default)]
+    pub include_xml: bool,
+
+    /// The directory used to persist the bookmark of the last event read.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    pub data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_query() -> String {
+    "*".to_owned()
+}
+
+const fn default_batch_size() -> u32 {
+    100
+}
+
+impl_generate_config_from_default!(WindowsEventLogConfig);
+
+#[async_trait::async_trait]
+impl SourceConfig for WindowsEventLogConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.channels.is_empty() {
+            return Err(BuildError::NoChannels.into());
+        }
+
+        let mut checkpoint_path = cx
+            .globals
+            // source are only global, name can be used for subdir
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        checkpoint_path.push(CHECKPOINT_FILENAME);
+
+        let bookmark = match tokio::fs::read_to_string(&checkpoint_path).await {
+            Ok(bookmark) if !bookmark.trim().is_empty() => Some(bookmark),
+            Ok(_) => None,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+            Err(source) => {
+                return Err(BuildError::ReadBookmark {
+                    path: checkpoint_path,
+                    source,
+                }
+                .into())
+            }
+        };
+
+        let subscription = Subscription::new(
+            &build_query(&self.channels, &self.query),
+            bookmark.as_deref(),
+            self.read_existing_events,
+            self.render_message,
+        )
+        .context(SubscribeSnafu)?;
+
+        Ok(Box::pin(
+            EventLogSource {
+                batch_size: self.batch_size,
+                include_xml: self.include_xml,
+                checkpoint_path,
+                acknowledgements: cx.do_acknowledgements(&self.acknowledgements),
+            }
+            .run(subscription, cx.out, cx.shutdown),
+        ))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// Builds a structured query, selecting the events of the XPath query from every channel.
+fn build_query(channels: &[String], query: &str) -> String {
+    let selects = channels
+        .iter()
+        .map(|channel| {
+            format!(
+                "<Select Path=\"{}\">{}</Select>",
+                escape_xml(channel),
+                escape_xml(query)
+            )
+        })
+        .collect::<String>();
+    format!("<QueryList><Query Id=\"0\">{}</Query></QueryList>", selects)
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Events read together, along with the bookmark of the last one.
+struct Batch {
+    events: Vec<RawEvent>,
+    bookmark: String,
+}
+
+struct EventLogSource {
+    batch_size: u32,
+    include_xml: bool,
+    checkpoint_path: PathBuf,
+    acknowledgements: bool,
+}
+
+impl EventLogSource {
+    async fn run(
+        self,
+        subscription: Subscription,
+        mut out: SourceSender,
+        mut shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        // Reading blocks, so that events are read on a thread of their own.
+        let (sender, mut receiver) = mpsc::channel(1);
+        let stop = Arc::new(AtomicBool::new(false));
+        let reader = {
+            let stop = Arc::clone(&stop);
+            let batch_size = self.batch_size;
+            tokio::task::spawn_blocking(move || read_events(subscription, batch_size, sender, stop))
+        };
+
+        let (finalizer, mut ack_stream) =
+            OrderedFinalizer::<String>::maybe_new(self.acknowledgements, shutdown.clone());
+        let checkpoint_path = self.checkpoint_path.clone();
+        tokio::spawn(async move {
+            while let Some((status, bookmark)) = ack_stream.next().await {
+                if status == BatchStatus::Delivered {
+                    write_checkpoint(&checkpoint_path, &bookmark).await;
+                }
+            }
+        });
+
+        let bytes_received = register!(BytesReceived::from(Protocol::from("windows_eventlog")));
+        loop {
+            let batch = tokio::select! {
+                _ = &mut shutdown => break,
+                batch = receiver.recv() => match batch {
+                    Some(batch) => batch,
+                    None => break,
+                },
+            };
+
+            let (notifier, batch_receiver) =
+                BatchNotifier::maybe_new_with_receiver(finalizer.is_some());
+            let mut events = Vec::with_capacity(batch.events.len());
+            for event in batch.events {
+                bytes_received.emit(ByteSize(event.xml.len()));
+                match parser::parse_event(&event.xml, event.message, self.include_xml) {
+                    Ok(mut log) => {
+                        log.try_insert(
+                            log_schema().source_type_key(),
+                            Bytes::from("windows_eventlog"),
+                        );
+                        events.push(log.with_batch_notifier_option(&notifier));
+                    }
+                    Err(error) => emit!(WindowsEventLogParseError { error }),
+                }
+            }
+            drop(notifier);
+
+            let count = events.len();
+            emit!(EventsReceived {
+                count,
+                byte_size: events.size_of(),
+            });
+            if let Err(error) = out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                break;
+            }
+
+            match (&finalizer, batch_receiver) {
+                (Some(finalizer), Some(receiver)) => finalizer.add(batch.bookmark, receiver),
+                _ => write_checkpoint(&self.checkpoint_path, &batch.bookmark).await,
+            }
+        }
+
+        stop.store(true, Ordering::Relaxed);
+        drop(receiver);
+        let _ = reader.await;
+        Ok(())
+    }
+}
+
+fn read_events(
+    mut subscription: Subscription,
+    batch_size: u32,
+    sender: mpsc::Sender<Batch>,
+    stop: Arc<AtomicBool>,
+) {
+    while !stop.load(Ordering::Relaxed) {
+        let batch = subscription
+            .next_batch(batch_size, WAIT_MILLIS)
+            .and_then(|events| Ok((subscription.bookmark()?, events)));
+        match batch {
+            Ok((_, events)) if events.is_empty() => {}
+            Ok((bookmark, events)) => {
+                if sender.blocking_send(Batch { events, bookmark }).is_err() {
+                    break;
+                }
+            }
+            Err(error) => {
+                emit!(WindowsEventLogReadError { error });
+                std::thread::sleep(RETRY_DELAY);
+            }
+        }
+    }
+}
+
+/// Writes the bookmark to a temporary file first, so that it's replaced as a whole.
+async fn write_checkpoint(path: &Path, bookmark: &str) {
+    let temporary = path.with_extension("tmp");
+    let result = async {
+        tokio::fs::write(&temporary, bookmark).await?;
+        tokio::fs::rename(&temporary, path).await
+    }
+    .await;
+    if let Err(error) = result {
+        emit!(WindowsEventLogCheckpointError {
+            error,
+            path: path.to_str().unwrap_or("unknown").to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WindowsEventLogConfig>();
+    }
+
+    #[test]
+    fn builds_query_of_channels() {
+        let query = build_query(
+            &[
+                "System".to_owned(),
+                "Microsoft-Windows-Sysmon/Operational".to_owned(),
+            ],
+            "*[System[Level<=3]]",
+        );
+
+        assert_eq!(
+            query,
+            "<QueryList><Query Id=\"0\">\
+             <Select Path=\"System\">*[System[Level&lt;=3]]</Select>\
+             <Select Path=\"Microsoft-Windows-Sysmon/Operational\">*[System[Level&lt;=3]]</Select>\
+             </Query></QueryList>"
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_application_events() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let config = WindowsEventLogConfig {
+            channels: vec!["Application".to_owned()],
+            read_existing_events: true,
+            data_dir: Some(data_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        // Every Windows installation has events in the application channel.
+        let events = crate::test_util::collect_n(rx, 1).await;
+        let log = events[0].as_log();
+        assert_eq!(log["channel"], "Application".into());
+        assert_eq!(
+            log[log_schema().source_type_key()],
+            "windows_eventlog".into()
+        );
+        assert!(log.get("record_id").is_some());
+    }
+}
@@ -0,0 +1,262 @@
+//! Parsing of the XML rendering of events into structured fields.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use roxmltree::{Document, Node};
+
+use crate::event::{LogEvent, Value};
+
+/// The name of the provider of an event, to render its message with.
+pub fn provider_name(xml: &str) -> Option<String> {
+    let document = Document::parse(xml).ok()?;
+    let system = child(document.root_element(), "System")?;
+    child(system, "Provider")?.attribute("Name").map(Into::into)
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|child| child.is_element() && child.tag_name().name() == name)
+}
+
+fn text(node: Node<'_, '_>) -> String {
+    node.text().unwrap_or_default().trim().to_owned()
+}
+
+/// The names of the standard levels, whose values are the same for all providers.
+const fn level_name(level: i64) -> Option<&'static str> {
+    match level {
+        0 | 4 => Some("Information"),
+        1 => Some("Critical"),
+        2 => Some("Error"),
+        3 => Some("Warning"),
+        5 => Some("Verbose"),
+        _ => None,
+    }
+}
+
+/// Parses an event, adding the fields of its `System` section, its `EventData` or `UserData`, and
+/// its rendered message.
+pub fn parse_event(
+    xml: &str,
+    message: Option<String>,
+    include_xml: bool,
+) -> Result<LogEvent, roxmltree::Error> {
+    let document = Document::parse(xml)?;
+    let root = document.root_element();
+
+    let mut log = LogEvent::default();
+    if let Some(message) = message {
+        log.insert(
+            crate::config::log_schema().message_key(),
+            message.trim_end(),
+        );
+    }
+
+    if let Some(system) = child(root, "System") {
+        for node in system.children().filter(Node::is_element) {
+            match node.tag_name().name() {
+                "Provider" => {
+                    if let Some(name) = node.attribute("Name") {
+                        log.insert("provider_name", name);
+                    }
+                }
+                "EventID" => insert_integer(&mut log, "event_id", &text(node)),
+                "Version" => insert_integer(&mut log, "version", &text(node)),
+                "Level" => {
+                    let level = text(node).parse::<i64>().ok();
+                    match level.and_then(level_name) {
+                        Some(name) => log.insert("level", name),
+                        None => log.insert("level", text(node)),
+                    };
+                    if let Some(level) = level {
+                        log.insert("level_value", level);
+                    }
+                }
+                "Task" => insert_integer(&mut log, "task", &text(node)),
+                "Opcode" => insert_integer(&mut log, "opcode", &text(node)),
+                "Keywords" => {
+                    log.insert("keywords", text(node));
+                }
+                "TimeCreated" => {
+                    if let Some(timestamp) = node
+                        .attribute("SystemTime")
+                        .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+                    {
+                        log.insert(
+                            crate::config::log_schema().timestamp_key(),
+                            timestamp.with_timezone(&Utc),
+                        );
+                    }
+                }
+                "EventRecordID" => insert_integer(&mut log, "record_id", &text(node)),
+                "Correlation" => {
+                    if let Some(activity_id) = node.attribute("ActivityID") {
+                        log.insert("activity_id", activity_id);
+                    }
+                }
+                "Execution" => {
+                    if let Some(process_id) = node.attribute("ProcessID") {
+                        insert_integer(&mut log, "process_id", process_id);
+                    }
+                    if let Some(thread_id) = node.attribute("ThreadID") {
+                        insert_integer(&mut log, "thread_id", thread_id);
+                    }
+                }
+                "Channel" => {
+                    log.insert("channel", text(node));
+                }
+                "Computer" => {
+                    log.insert("computer", text(node));
+                }
+                "Security" => {
+                    if let Some(user_id) = node.attribute("UserID") {
+                        log.insert("user_id", user_id);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if let Some(event_data) = child(root, "EventData") {
+        log.insert("event_data", parse_event_data(event_data));
+    }
+    if let Some(user_data) = child(root, "UserData").and_then(|node| {
+        // User data holds a single element, defined by the provider.
+        node.children().find(Node::is_element)
+    }) {
+        log.insert("user_data", parse_element(user_data));
+    }
+
+    if include_xml {
+        log.insert("xml", xml);
+    }
+    Ok(log)
+}
+
+fn insert_integer(log: &mut LogEvent, key: &str, value: &str) {
+    match value.parse::<i64>() {
+        Ok(value) => log.insert(key, value),
+        Err(_) => log.insert(key, value),
+    };
+}
+
+/// Parses the `Data` elements, which are named by most providers. Unnamed ones are numbered like
+/// the insertion strings of their message.
+fn parse_event_data(node: Node<'_, '_>) -> Value {
+    let mut fields = BTreeMap::new();
+    let data = node
+        .children()
+        .filter(|child| child.is_element() && child.tag_name().name() == "Data");
+    for (index, data) in data.enumerate() {
+        let name = match data.attribute("Name") {
+            Some(name) => name.to_owned(),
+            None => format!("param{}", index + 1),
+        };
+        fields.insert(name, Value::from(text(data)));
+    }
+    Value::Object(fields)
+}
+
+fn parse_element(node: Node<'_, '_>) -> Value {
+    let children = node.children().filter(Node::is_element).collect::<Vec<_>>();
+    if children.is_empty() {
+        return Value::from(text(node));
+    }
+    Value::Object(
+        children
+            .into_iter()
+            .map(|child| (child.tag_name().name().to_owned(), parse_element(child)))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENT: &str = r#"<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event">
+  <System>
+    <Provider Name="Service Control Manager" Guid="{555908d1-a6d7-4695-8e1e-26931d2012f4}" EventSourceName="Service Control Manager"/>
+    <EventID Qualifiers="16384">7036</EventID>
+    <Version>0</Version>
+    <Level>4</Level>
+    <Task>0</Task>
+    <Opcode>0</Opcode>
+    <Keywords>0x8080000000000000</Keywords>
+    <TimeCreated SystemTime="2022-10-14T06:18:04.1234567Z"/>
+    <EventRecordID>12345</EventRecordID>
+    <Correlation/>
+    <Execution ProcessID="636" ThreadID="7080"/>
+    <Channel>System</Channel>
+    <Computer>WIN-HOST</Computer>
+    <Security/>
+  </System>
+  <EventData>
+    <Data Name="param1">Windows Update</Data>
+    <Data Name="param2">running</Data>
+  </EventData>
+</Event>"#;
+
+    #[test]
+    fn parses_system_fields() {
+        let log = parse_event(
+            EVENT,
+            Some("The service entered the running state.\r\n".into()),
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            log[crate::config::log_schema().message_key()],
+            "The service entered the running state.".into()
+        );
+        assert_eq!(log["provider_name"], "Service Control Manager".into());
+        assert_eq!(log["event_id"], 7036.into());
+        assert_eq!(log["level"], "Information".into());
+        assert_eq!(log["level_value"], 4.into());
+        assert_eq!(log["keywords"], "0x8080000000000000".into());
+        assert_eq!(log["record_id"], 12345.into());
+        assert_eq!(log["process_id"], 636.into());
+        assert_eq!(log["thread_id"], 7080.into());
+        assert_eq!(log["channel"], "System".into());
+        assert_eq!(log["computer"], "WIN-HOST".into());
+        assert_eq!(
+            log[crate::config::log_schema().timestamp_key()],
+            DateTime::parse_from_rfc3339("2022-10-14T06:18:04.1234567Z")
+                .unwrap()
+                .with_timezone(&Utc)
+                .into()
+        );
+        assert!(log.get("xml").is_none());
+    }
+
+    #[test]
+    fn parses_event_data() {
+        let log = parse_event(EVENT, None, true).unwrap();
+
+        assert_eq!(log["event_data.param1"], "Windows Update".into());
+        assert_eq!(log["event_data.param2"], "running".into());
+        assert_eq!(log["xml"], EVENT.into());
+        assert!(log.get(crate::config::log_schema().message_key()).is_none());
+    }
+
+    #[test]
+    fn numbers_unnamed_event_data() {
+        let xml = r#"<Event><System><Provider Name="Application Error"/></System><EventData><Data>first</Data><Data>second</Data></EventData></Event>"#;
+        let log = parse_event(xml, None, false).unwrap();
+
+        assert_eq!(log["event_data.param1"], "first".into());
+        assert_eq!(log["event_data.param2"], "second".into());
+        assert_eq!(provider_name(xml), Some("Application Error".to_owned()));
+    }
+
+    #[test]
+    fn parses_user_data() {
+        let xml = r#"<Event><System/><UserData><LogFileCleared><SubjectUserName>admin</SubjectUserName></LogFileCleared></UserData></Event>"#;
+        let log = parse_event(xml, None, false).unwrap();
+
+        assert_eq!(log["user_data.SubjectUserName"], "admin".into());
+    }
+}
@@ -0,0 +1,273 @@
+//! A pull subscription to event log channels, with the `EvtSubscribe` API.
+
+use std::{collections::HashMap, io, ptr};
+
+use windows_sys::Win32::{
+    Foundation::{
+        CloseHandle, GetLastError, ERROR_INSUFFICIENT_BUFFER, ERROR_NO_MORE_ITEMS, HANDLE,
+    },
+    System::{
+        EventLog::{
+            EvtClose, EvtCreateBookmark, EvtFormatMessage, EvtFormatMessageEvent, EvtNext,
+            EvtOpenPublisherMetadata, EvtRender, EvtRenderBookmark, EvtRenderEventXml,
+            EvtSubscribe, EvtSubscribeStartAfterBookmark, EvtSubscribeStartAtOldestRecord,
+            EvtSubscribeToFutureEvents, EvtUpdateBookmark,
+        },
+        Threading::{CreateEventW, ResetEvent, WaitForSingleObject},
+    },
+};
+
+/// An event read from a channel.
+pub struct RawEvent {
+    pub xml: String,
+    /// The message of the event, rendered with the message resources of its provider.
+    pub message: Option<String>,
+}
+
+/// Closes an event log handle once dropped.
+struct EvtHandle(isize);
+
+impl Drop for EvtHandle {
+    fn drop(&mut self) {
+        if self.0 != 0 {
+            unsafe { EvtClose(self.0) };
+        }
+    }
+}
+
+pub struct Subscription {
+    handle: EvtHandle,
+    signal: HANDLE,
+    bookmark: EvtHandle,
+    render_message: bool,
+    /// The publisher metadata of providers, to render messages with. Providers without message
+    /// resources are kept as well, so that they aren't opened again for every event.
+    publishers: HashMap<String, Option<EvtHandle>>,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        // The subscription is closed before the signal event it's using.
+        self.handle = EvtHandle(0);
+        unsafe { CloseHandle(self.signal) };
+    }
+}
+
+fn last_error() -> io::Error {
+    io::Error::from_raw_os_error(unsafe { GetLastError() } as i32)
+}
+
+fn to_wide(value: &str) -> Vec<u16> {
+    value.encode_utf16().chain(Some(0)).collect()
+}
+
+fn from_wide(buffer: &[u16]) -> String {
+    let len = buffer.iter().position(|c| *c == 0).unwrap_or(buffer.len());
+    String::from_utf16_lossy(&buffer[..len])
+}
+
+impl Subscription {
+    /// Subscribes to the events selected by a structured XML query, starting after the bookmark
+    /// if there is one.
+    pub fn new(
+        query: &str,
+        bookmark: Option<&str>,
+        read_existing_events: bool,
+        render_message: bool,
+    ) -> io::Result<Self> {
+        let (bookmark, flags) = match bookmark {
+            Some(xml) => (
+                EvtHandle(unsafe { EvtCreateBookmark(to_wide(xml).as_ptr()) }),
+                EvtSubscribeStartAfterBookmark,
+            ),
+            // Bookmarks are created empty, and updated with the events read.
+            None => (
+                EvtHandle(unsafe { EvtCreateBookmark(ptr::null()) }),
+                if read_existing_events {
+                    EvtSubscribeStartAtOldestRecord
+                } else {
+                    EvtSubscribeToFutureEvents
+                },
+            ),
+        };
+        if bookmark.0 == 0 {
+            return Err(last_error());
+        }
+
+        // Manual reset, so that the event stays signaled until all events were read.
+        let signal = unsafe { CreateEventW(ptr::null(), 1, 1, ptr::null()) };
+        if signal == 0 {
+            return Err(last_error());
+        }
+
+        let query = to_wide(query);
+        let handle = unsafe {
+            EvtSubscribe(
+                0,
+                signal,
+                ptr::null(),
+                query.as_ptr(),
+                if flags == EvtSubscribeStartAfterBookmark {
+                    bookmark.0
+                } else {
+                    0
+                },
+                ptr::null(),
+                None,
+                flags as u32,
+            )
+        };
+        if handle == 0 {
+            let error = last_error();
+            unsafe { CloseHandle(signal) };
+            return Err(error);
+        }
+
+        Ok(Self {
+            handle: EvtHandle(handle),
+            signal,
+            bookmark,
+            render_message,
+            publishers: HashMap::new(),
+        })
+    }
+
+    /// Reads the next events, waiting for up to the timeout if there are none.
+    pub fn next_batch(&mut self, max: u32, timeout_millis: u32) -> io::Result<Vec<RawEvent>> {
+        unsafe { WaitForSingleObject(self.signal, timeout_millis) };
+
+        let mut handles = vec![0isize; max as usize];
+        let mut returned = 0u32;
+        let ok = unsafe {
+            EvtNext(
+                self.handle.0,
+                max,
+                handles.as_mut_ptr(),
+                0,
+                0,
+                &mut returned,
+            )
+        };
+        if ok == 0 {
+            let error = unsafe { GetLastError() };
+            if error == ERROR_NO_MORE_ITEMS {
+                unsafe { ResetEvent(self.signal) };
+                return Ok(Vec::new());
+            }
+            return Err(io::Error::from_raw_os_error(error as i32));
+        }
+
+        let handles = handles
+            .into_iter()
+            .take(returned as usize)
+            .map(EvtHandle)
+            .collect::<Vec<_>>();
+        let mut events = Vec::with_capacity(handles.len());
+        for event in &handles {
+            let xml = render(event.0, EvtRenderEventXml as u32)?;
+            let message = if self.render_message {
+                self.format_message(event.0, &xml)
+            } else {
+                None
+            };
+            if unsafe { EvtUpdateBookmark(self.bookmark.0, event.0) } == 0 {
+                return Err(last_error());
+            }
+            events.push(RawEvent { xml, message });
+        }
+        Ok(events)
+    }
+
+    /// Renders the bookmark of the last event read, to start after it again.
+    pub fn bookmark(&self) -> io::Result<String> {
+        render(self.bookmark.0, EvtRenderBookmark as u32)
+    }
+
+    fn format_message(&mut self, event: isize, xml: &str) -> Option<String> {
+        let provider = super::parser::provider_name(xml)?;
+        let publisher = self
+            .publishers
+            .entry(provider)
+            .or_insert_with_key(|provider| {
+                let handle = unsafe {
+                    EvtOpenPublisherMetadata(0, to_wide(provider).as_ptr(), ptr::null(), 0, 0)
+                };
+                (handle != 0).then(|| EvtHandle(handle))
+            })
+            .as_ref()?;
+
+        let mut used = 0u32;
+        unsafe {
+            EvtFormatMessage(
+                publisher.0,
+                event,
+                0,
+                0,
+                ptr::null(),
+                EvtFormatMessageEvent as u32,
+                0,
+                ptr::null_mut(),
+                &mut used,
+            )
+        };
+        if unsafe { GetLastError() } != ERROR_INSUFFICIENT_BUFFER {
+            return None;
+        }
+
+        let mut buffer = vec![0u16; used as usize];
+        let ok = unsafe {
+            EvtFormatMessage(
+                publisher.0,
+                event,
+                0,
+                0,
+                ptr::null(),
+                EvtFormatMessageEvent as u32,
+                buffer.len() as u32,
+                buffer.as_mut_ptr(),
+                &mut used,
+            )
+        };
+        (ok != 0).then(|| from_wide(&buffer))
+    }
+}
+
+fn render(handle: isize, flags: u32) -> io::Result<String> {
+    let mut used = 0u32;
+    let mut properties = 0u32;
+    let ok = unsafe {
+        EvtRender(
+            0,
+            handle,
+            flags,
+            0,
+            ptr::null_mut(),
+            &mut used,
+            &mut properties,
+        )
+    };
+    if ok == 0 {
+        let error = unsafe { GetLastError() };
+        if error != ERROR_INSUFFICIENT_BUFFER {
+            return Err(io::Error::from_raw_os_error(error as i32));
+        }
+    }
+
+    // The size is in bytes, of UTF-16 characters.
+    let mut buffer = vec![0u16; (used as usize + 1) / 2];
+    let ok = unsafe {
+        EvtRender(
+            0,
+            handle,
+            flags,
+            (buffer.len() * 2) as u32,
+            buffer.as_mut_ptr().cast(),
+            &mut used,
+            &mut properties,
+        )
+    };
+    if ok == 0 {
+        return Err(last_error());
+    }
+    Ok(from_wide(&buffer))
+}
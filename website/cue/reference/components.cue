@@ -877,26 +877,96 @@ components: {
 			}
 
 			_http_basic_auth: {
-				common:      false
-				description: "Options for HTTP Basic Authentication."
-				required:    false
+				common: false
+				description: """
+					Options for authenticating the requests. Basic authentication and bearer tokens are checked
+					against the `Authorization` header, and a request is accepted if it matches any of them. When
+					`hmac` is set, the signature of every request body is checked as well.
+					"""
+				required: false
 				type: object: {
 					examples: []
 					options: {
 						username: {
-							description: "The basic authentication user name."
-							required:    true
+							description: "The basic authentication user name. Requires `password` to be set."
+							required:    false
 							type: string: {
+								default: null
 								examples: ["${HTTP_USERNAME}", "username"]
 							}
 						}
 						password: {
-							description: "The basic authentication password."
-							required:    true
+							description: "The basic authentication password. Requires `username` to be set."
+							required:    false
 							type: string: {
+								default: null
 								examples: ["${HTTP_PASSWORD}", "password"]
 							}
 						}
+						tokens: {
+							description: "A list of bearer tokens, any of which is accepted."
+							required:    false
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["${HTTP_TOKEN}"]
+								}
+							}
+						}
+						hmac: {
+							description: "Validation of the HMAC signature of the request bodies, as sent by many webhook providers."
+							required:    false
+							type: object: {
+								examples: []
+								options: {
+									secret: {
+										description: "The secret shared with the sender, which the signatures are computed with."
+										required:    true
+										type: string: {
+											examples: ["${WEBHOOK_SECRET}"]
+										}
+									}
+									header: {
+										description: "The name of the header carrying the signature."
+										required:    false
+										type: string: {
+											default: "X-Hub-Signature-256"
+										}
+									}
+									algorithm: {
+										description: "The hash function of the signatures."
+										required:    false
+										type: string: {
+											default: "sha256"
+											enum: {
+												sha1:   "SHA-1."
+												sha256: "SHA-256."
+												sha512: "SHA-512."
+											}
+										}
+									}
+									encoding: {
+										description: "The encoding of the signatures in the header."
+										required:    false
+										type: string: {
+											default: "hex"
+											enum: {
+												hex:    "Hexadecimal, in either case."
+												base64: "Standard Base64."
+											}
+										}
+									}
+									prefix: {
+										description: "A prefix of the header value to remove before decoding the signature."
+										required:    false
+										type: string: {
+											default: null
+											examples: ["sha256="]
+										}
+									}
+								}
+							}
+						}
 					}
 				}
 			}
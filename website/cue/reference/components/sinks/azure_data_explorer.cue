@@ -0,0 +1,158 @@
+package metadata
+
+components: sinks: azure_data_explorer: {
+	title: "Azure Data Explorer"
+
+	description: """
+		Ingests log events into an Azure Data Explorer (Kusto) table with queued ingestion,
+		uploading each batch to the storage of the cluster before it is ingested.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 30.0
+			}
+			compression: {
+				enabled: true
+				default: "gzip"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.azure_data_explorer
+
+				interface: {
+					socket: {
+						api: {
+							title: "Kusto queued ingestion"
+							url:   urls.azure_data_explorer_queued_ingestion
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		ingestion_endpoint: {
+			description: """
+				The ingestion endpoint of the cluster, which is its URI prefixed with `ingest-`.
+				Credentials are loaded from Azure Active Directory, with the environment variables,
+				a Managed Identity or the `az` CLI tool, in this order.
+				"""
+			required: true
+			type: string: {
+				examples: ["https://ingest-mycluster.westeurope.kusto.windows.net"]
+			}
+		}
+		database: {
+			description: "The database of the table."
+			required:    true
+			type: string: {
+				examples: ["logs"]
+			}
+		}
+		table: {
+			description: "The table to ingest the events into."
+			required:    true
+			type: string: {
+				examples: ["events"]
+			}
+		}
+		mapping_reference: {
+			description: """
+				The name of the JSON [ingestion mapping](\(urls.azure_data_explorer_ingestion_mappings))
+				of the table, mapping the fields of the events to its columns. If not set, the fields
+				are mapped to the columns of the same name.
+				"""
+			common:   true
+			required: false
+			type: string: {
+				default: null
+				examples: ["events_mapping"]
+			}
+		}
+		flush_immediately: {
+			description: """
+				Whether the blobs are ingested as soon as they are received, bypassing the ingestion
+				batching policy of the table. This lowers the latency of the ingestion, at the
+				expense of its efficiency, and should be used with caution.
+				"""
+			common:   false
+			required: false
+			type: bool: default: false
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		queued_ingestion: {
+			title: "Queued ingestion"
+			body: """
+				Each batch is encoded as JSON lines and uploaded as a blob to one of the temporary
+				storage containers of the cluster, then a message pointing to the blob is posted to
+				one of its ingestion queues. The containers and queues are fetched from the
+				ingestion endpoint, and refreshed every hour. The events are acknowledged once the
+				message is queued, and the cluster ingests the blobs according to the ingestion
+				batching policy of the table, so it can take a few minutes for them to be queryable.
+				"""
+		}
+		permissions: {
+			title: "Permissions"
+			body: """
+				The principal Vector runs as must be granted the `Ingestor` role on the database.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -83,7 +83,8 @@ components: sinks: clickhouse: {
 			required:    false
 			type: string: {
 				default: null
-				examples: ["mydatabase"]
+				examples: ["mydatabase", "{{ tenant }}"]
+				syntax: "template"
 			}
 		}
 		endpoint: {
@@ -97,12 +98,60 @@ components: sinks: clickhouse: {
 			description: "The table that data will be inserted into."
 			required:    true
 			type: string: {
-				examples: ["mytable"]
+				examples: ["mytable", "logs_{{ service }}"]
+				syntax: "template"
 			}
 		}
+		format: {
+			common:      false
+			description: "The format the events are inserted in."
+			required:    false
+			type: string: {
+				default: "json_each_row"
+				enum: {
+					json_each_row: "Events are encoded as JSON objects, which Clickhouse converts to the types of the columns."
+					row_binary:    "Events are encoded in the binary representation of the types of the `columns`. This is faster to insert than JSON, and the values are checked against the types before they are sent."
+				}
+			}
+		}
+		columns: {
+			common:        false
+			description:   "The columns of the table, which the events are encoded as with the `row_binary` format. The columns must be listed in the order of the table, unless they are a subset of it. Nullable columns are set to `NULL` when the field is missing, and other columns to the default value of their type."
+			relevant_when: "format = `row_binary`"
+			required:      false
+			type: array: {
+				default: []
+				items: type: object: options: {
+					name: {
+						description: "The name of the column."
+						required:    true
+						type: string: examples: ["message"]
+					}
+					type: {
+						description: "The Clickhouse type of the column. Integers, floats, `Bool`, `String`, `FixedString`, `UUID`, `Date`, `Date32`, `DateTime`, `DateTime64`, `IPv4`, `IPv6`, and `Nullable`, `LowCardinality`, `Array`, and `Map` of these are supported."
+						required:    true
+						type: string: examples: ["String", "Nullable(Int64)", "DateTime64(3)", "Map(String, String)"]
+					}
+					field: {
+						description: "The field of the events the values of the column are taken from. Defaults to the name of the column."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["kubernetes.pod_name"]
+						}
+					}
+				}
+			}
+		}
+		async_insert: {
+			common:      false
+			description: "Sets `async_insert`, letting Clickhouse buffer the inserted data before writing it to the table. `wait_for_async_insert` is set as well, so that events are only acknowledged once they are written."
+			required:    false
+			type: bool: default: false
+		}
 		skip_unknown_fields: {
 			common:      true
-			description: "Sets `input_format_skip_unknown_fields`, allowing Clickhouse to discard fields not present in the table schema."
+			description: "Sets `input_format_skip_unknown_fields`, allowing Clickhouse to discard fields not present in the table schema. Only used by the `json_each_row` format."
 			required:    false
 			type: bool: default: false
 		}
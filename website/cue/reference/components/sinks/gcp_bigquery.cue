@@ -0,0 +1,221 @@
+package metadata
+
+components: sinks: gcp_bigquery: {
+	title: "GCP BigQuery"
+
+	description: """
+		Writes log events as rows of a BigQuery table with the Storage Write API, making them
+		available for queries as soon as they are written.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["GCP"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				max_events:   10_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.gcp_bigquery
+
+				interface: {
+					socket: {
+						api: {
+							title: "BigQuery Storage Write API"
+							url:   urls.gcp_bigquery_write_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		api_key:          configuration._gcp_api_key
+		credentials_path: configuration._gcp_credentials_path
+		endpoint: {
+			common:      false
+			description: "The endpoint of the Storage Write API."
+			required:    false
+			type: string: {
+				default: "https://bigquerystorage.googleapis.com"
+				examples: ["http://localhost:9060"]
+			}
+		}
+		project: {
+			description: "The project of the table."
+			required:    true
+			type: string: {
+				examples: ["vector-123456"]
+			}
+		}
+		dataset: {
+			description: "The dataset of the table."
+			required:    true
+			type: string: {
+				examples: ["logs"]
+			}
+		}
+		table: {
+			description: "The table to write the events to."
+			required:    true
+			type: string: {
+				examples: ["events"]
+			}
+		}
+		schema: {
+			description: """
+				The columns of the table, and the fields of the events their values are taken from.
+				Events whose fields don't match the types of the columns are rejected, or written to
+				the `dead_letter_table`.
+				"""
+			required: true
+			type: array: items: type: object: options: {
+				name: {
+					description: "The name of the column."
+					required:    true
+					type: string: {
+						examples: ["message"]
+					}
+				}
+				type: {
+					description: "The BigQuery type of the column."
+					required:    true
+					type: string: enum: {
+						string:    "A `STRING` column. Numbers, booleans, and timestamps are written as their text."
+						bytes:     "A `BYTES` column."
+						int64:     "An `INT64` column."
+						float64:   "A `FLOAT64` column."
+						bool:      "A `BOOL` column."
+						timestamp: "A `TIMESTAMP` column. Timestamps may also be given as seconds since the Unix epoch, or as RFC 3339 strings."
+						date:      "A `DATE` column. Dates may also be given as timestamps, or as `YYYY-MM-DD` strings."
+						json:      "A `JSON` column. Values are written as JSON, so that objects and arrays may be stored."
+					}
+				}
+				mode: {
+					description: "The mode of the column."
+					common:      true
+					required:    false
+					type: string: {
+						default: "nullable"
+						enum: {
+							nullable: "The column may be `NULL`, which missing fields are written as."
+							required: "Events missing the field are rejected."
+							repeated: "The column is an array, written from array fields."
+						}
+					}
+				}
+				field: {
+					description: "The field of the events the values of the column are taken from. Defaults to the name of the column."
+					common:      true
+					required:    false
+					type: string: {
+						default: null
+						examples: ["metadata.host"]
+					}
+				}
+			}
+		}
+		dead_letter_table: {
+			description: """
+				A table of the same dataset to write the rejected events to. The table must have an
+				`event` column of type `JSON` or `STRING`, holding the event encoded as JSON, and an
+				`error` column of type `STRING`, holding the reason it was rejected. If not set, the
+				rejected events are dropped.
+				"""
+			common:   true
+			required: false
+			type: string: {
+				default: null
+				examples: ["rejected_events"]
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		write_api: {
+			title: "Storage Write API"
+			body: """
+				Each batch is appended to the default stream of the table, which commits the rows as
+				they are written, so they can be queried right away. The rows are encoded as protobuf
+				messages described by the `schema`.
+				"""
+		}
+		dead_letters: {
+			title: "Rejected events"
+			body: """
+				Events are checked against the types of the columns before they are sent, and
+				BigQuery rejects the rows which don't fit the table, such as strings too long for
+				their column. The rejected events are written to the `dead_letter_table` when it is
+				set, and the other rows of the batch are sent again, since BigQuery doesn't append
+				any of the rows of a batch with rejected ones. Without a dead-letter table, the
+				rejected events are dropped and their acknowledgements report them as rejected.
+				"""
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform: "gcp"
+			_service: "bigquery"
+
+			policies: [
+				{
+					_action: "tables.get"
+					required_for: ["healthcheck"]
+				},
+				{
+					_action: "tables.updateData"
+					required_for: ["operation"]
+				},
+			]
+		},
+	]
+
+	telemetry: metrics: {
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
@@ -61,6 +61,12 @@ components: sinks: kafka: {
 				examples: ["user_id"]
 			}
 		}
+		idempotence: {
+			common:      false
+			description: "Use an idempotent producer, which writes every message exactly once and in order, even when sending it has to be retried. Transactions always use an idempotent producer."
+			required:    false
+			type: bool: default: false
+		}
 		librdkafka_options: components._kafka.configuration.librdkafka_options
 		message_timeout_ms: {
 			common:      false
@@ -91,7 +97,54 @@ components: sinks: kafka: {
 						required:    false
 						type: string: {
 							default: null
-							examples: ["SCRAM-SHA-256", "SCRAM-SHA-512"]
+							examples: ["SCRAM-SHA-256", "SCRAM-SHA-512", "OAUTHBEARER"]
+						}
+					}
+					oauthbearer: {
+						common:      false
+						description: """
+							Options for SASL/OAUTHBEARER authentication with tokens from an OpenID Connect provider,
+							as used by Amazon MSK and Confluent Cloud. Tokens are requested with the client credentials
+							grant and refreshed before they expire. When set, `mechanism` defaults to `OAUTHBEARER`.
+							"""
+						required:    false
+						type: object: {
+							examples: []
+							options: {
+								client_id: {
+									description: "The client ID used to request tokens."
+									required:    true
+									type: string: examples: ["vector"]
+								}
+								client_secret: {
+									description: "The client secret used to request tokens."
+									required:    true
+									type: string: examples: ["${KAFKA_CLIENT_SECRET}"]
+								}
+								extensions: {
+									common:      false
+									description: "Extensions sent to the broker along with the token. Confluent Cloud, for example, requires the `logicalCluster` and `identityPoolId` extensions."
+									required:    false
+									type: object: {
+										examples: [{"logicalCluster": "lkc-123", "identityPoolId": "pool-abc"}]
+										options: {}
+									}
+								}
+								scope: {
+									common:      false
+									description: "The scope of the requested tokens."
+									required:    false
+									type: string: {
+										default: null
+										examples: ["kafka"]
+									}
+								}
+								token_endpoint_url: {
+									description: "The URL of the token endpoint of the OpenID Connect provider."
+									required:    true
+									type: string: examples: ["https://auth.example.com/oauth2/token"]
+								}
+							}
 						}
 					}
 					password: {
@@ -133,6 +186,69 @@ components: sinks: kafka: {
 				examples: ["headers"]
 			}
 		}
+		transactions: {
+			common:      false
+			description: """
+				Write events in transactions, for exactly-once delivery into Kafka. Events are
+				batched, and consumers reading with the `read_committed` isolation level see either a
+				whole batch or none of it. The events of a batch are only acknowledged once its
+				transaction is committed.
+				"""
+			required: false
+			type: object: {
+				examples: []
+				options: {
+					batch: {
+						common:      false
+						description: "The batches written in a transaction."
+						required:    false
+						type: object: options: {
+							max_bytes: {
+								common:      false
+								description: "The maximum size of a batch, in bytes of encoded messages."
+								required:    false
+								type: uint: {
+									default: null
+									unit:    "bytes"
+								}
+							}
+							max_events: {
+								common:      false
+								description: "The maximum number of events in a batch."
+								required:    false
+								type: uint: {
+									default: 1000
+									unit:    "events"
+								}
+							}
+							timeout_secs: {
+								common:      false
+								description: "The maximum age of a batch before it is written."
+								required:    false
+								type: float: {
+									default: 1.0
+									unit:    "seconds"
+								}
+							}
+						}
+					}
+					timeout_ms: {
+						common:      false
+						description: "The maximum time a transaction may stay open before the broker aborts it. `message_timeout_ms` is lowered to this value when it's higher."
+						required:    false
+						type: uint: {
+							default: 60000
+							unit:    "milliseconds"
+						}
+					}
+					transactional_id: {
+						description: "The transactional ID of the producer. It must be unique to this sink and stay the same across restarts, so that the transactions left open by a previous run are aborted."
+						required:    true
+						type: string: examples: ["vector-kafka-sink"]
+					}
+				}
+			}
+		}
 	}
 
 	input: {
@@ -0,0 +1,163 @@
+package metadata
+
+components: sinks: opentelemetry: {
+	title: "OpenTelemetry"
+
+	description: """
+		Exports logs and metrics with the OpenTelemetry protocol (OTLP), over gRPC or HTTP, to an
+		OpenTelemetry collector or any backend that receives OTLP directly.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			request: {
+				enabled: true
+				headers: true
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.opentelemetry
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			summary:      true
+			set:          true
+		}
+		traces: false
+	}
+
+	configuration: {
+		endpoint: {
+			description: """
+				The endpoint of the OTLP receiver. With the `http` protocol, logs and metrics are sent
+				to the `/v1/logs` and `/v1/metrics` paths of the endpoint.
+				"""
+			required: true
+			type: string: {
+				examples: ["http://localhost:4317", "https://otlp.example.com:4318"]
+			}
+		}
+		protocol: {
+			description: "The protocol to export with."
+			common:      true
+			required:    false
+			type: string: {
+				default: "grpc"
+				enum: {
+					grpc: "OTLP over gRPC."
+					http: "OTLP over HTTP, with binary protobuf payloads."
+				}
+			}
+		}
+		compression: {
+			description: "Compress the requests with gzip."
+			common:      true
+			required:    false
+			type: bool: default: false
+		}
+		resource_attributes: {
+			description: """
+				Resource attributes to set from the events, mapped to the log field or metric tag
+				they are taken from. The fields and tags are removed from the events.
+				"""
+			common:   true
+			required: false
+			type: object: {
+				examples: [{"service.name": "application", "host.name": "host"}]
+				options: {
+					"*": {
+						description: "The log field or metric tag to take the attribute from."
+						required:    true
+						type: string: {
+							examples: ["application"]
+						}
+					}
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		logs: {
+			title: "Logs"
+			body: """
+				Log events are encoded into log records the way the `opentelemetry` source decodes
+				them, so records pass through Vector unchanged. The `resources`, `scope` and
+				`attributes` fields set the resource, instrumentation scope and attributes of the
+				record, the message is its body, and the `timestamp`, `observed_timestamp`,
+				`trace_id`, `span_id`, `severity_text`, `severity_number`, `flags` and
+				`dropped_attributes_count` fields are set on the record. Any other field is sent as an
+				attribute of the record.
+				"""
+		}
+		metrics: {
+			title: "Metrics"
+			body: """
+				Counters are exported as monotonic sums, gauges as gauges, sets as gauges of their
+				number of values, distributions and aggregated histograms as histograms, and
+				aggregated summaries as summaries. Incremental metrics have the delta aggregation
+				temporality and absolute ones the cumulative one. The tags of the metrics are sent as
+				attributes of their data points. Sketches can't be represented in OTLP and are
+				dropped.
+				"""
+		}
+		healthcheck: {
+			title: "Health checks"
+			body: """
+				OTLP has no health check, so an empty logs export request is sent when Vector starts.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -27,19 +27,21 @@ components: sources: [Name=string]: {
 				required:    false
 				type: object: options: {
 					condition_pattern: {
-						description: "Condition regex pattern to look for. Exact behavior is configured via `mode`."
-						required:    true
+						description: "Condition regex pattern to look for. Exact behavior is configured via `mode`. Required unless `preset` is set, in which case it overrides the pattern of the preset."
+						required:    false
 						sort:        3
 						type: string: {
+							default: null
 							examples: ["^[\\s]+", "\\\\$", "^(INFO|ERROR) ", ";$"]
 							syntax: "regex"
 						}
 					}
 					mode: {
-						description: "Mode of operation, specifies how the `condition_pattern` is interpreted."
-						required:    true
+						description: "Mode of operation, specifies how the `condition_pattern` is interpreted. Required unless `preset` is set, in which case it overrides the mode of the preset."
+						required:    false
 						sort:        2
 						type: string: {
+							default: null
 							enum: {
 								continue_through: "All consecutive lines matching this pattern are included in the group. The first line (the line that matched the start pattern) does not need to match the `ContinueThrough` pattern. This is useful in cases such as a Java stack trace, where some indicator in the line (such as leading whitespace) indicates that it is an extension of the preceding line."
 								continue_past:    "All consecutive lines matching this pattern, plus one additional line, are included in the group. This is useful in cases where a log message ends with a continuation marker, such as a backslash, indicating that the following line is part of the same message."
@@ -48,20 +50,36 @@ components: sources: [Name=string]: {
 							}
 						}
 					}
+					preset: {
+						description: "Aggregates the lines of the stack traces of a common language, without configuring the patterns and the mode."
+						required:    false
+						sort:        0
+						type: string: {
+							default: null
+							enum: {
+								go:     "Go panics, from the `panic:` line through the stacks of the goroutines."
+								java:   "Java exceptions, whose indented `at` lines and `Caused by:` lines follow the first line."
+								python: "Python tracebacks, from `Traceback (most recent call last):` through the exception line."
+								ruby:   "Ruby exceptions, whose indented `from` lines follow the first line."
+							}
+						}
+					}
 					start_pattern: {
-						description: "Start regex pattern to look for as a beginning of the message."
-						required:    true
+						description: "Start regex pattern to look for as a beginning of the message. Required unless `preset` is set, in which case it overrides the pattern of the preset."
+						required:    false
 						sort:        1
 						type: string: {
+							default: null
 							examples: ["^[^\\s]", "\\\\$", "^(INFO|ERROR) ", "[^;]$"]
 							syntax: "regex"
 						}
 					}
 					timeout_ms: {
 						description: "The maximum time to wait for the continuation. Once this timeout is reached, the buffered message is guaranteed to be flushed, even if incomplete."
-						required:    true
+						required:    false
 						sort:        4
 						type: uint: {
+							default: 1_000
 							examples: [1_000, 600_000]
 							unit: "milliseconds"
 						}
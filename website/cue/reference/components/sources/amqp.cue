@@ -4,7 +4,7 @@ components: sources: amqp: {
 	title: "AMQP"
 
 	features: {
-		acknowledgements: false
+		acknowledgements: true
 		collect: {
 			checkpoint: enabled: false
 			from: {
@@ -42,6 +42,7 @@ components: sources: amqp: {
 	}
 
 	configuration: {
+		acknowledgements: configuration._source_acknowledgements
 		connection: {
 			description: "Connection options for the AMQP source."
 			required:    true
@@ -53,12 +54,47 @@ components: sources: amqp: {
 				}
 			}
 		}
-		group_id: {
-			description: "The consumer group name to be used to consume events from AMQP."
-			required:    true
+		queue: {
+			common:      true
+			description: "The name of the queue to consume."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "vector"
+				examples: ["vector", "logs"]
+				syntax: "literal"
+			}
+		}
+		consumer: {
+			common:      false
+			description: "The identifier for the consumer."
+			required:    false
 			warnings: []
 			type: string: {
-				examples: ["consumer-group-name"]
+				default: "vector"
+				examples: ["vector"]
+				syntax: "literal"
+			}
+		}
+		prefetch_count: {
+			common:      false
+			description: "The maximum number of unacknowledged messages delivered to the consumer at once. With acknowledgements enabled, messages are only acknowledged once they are processed, so that this bounds the number of messages in flight. Unlimited if not set."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: null
+				examples: [100]
+				unit: "messages"
+			}
+		}
+		headers_key: {
+			common:      false
+			description: "The log field name to add the headers of the AMQP message under, as an object."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "headers"
+				examples: ["headers"]
 				syntax: "literal"
 			}
 		}
@@ -127,6 +163,14 @@ components: sources: amqp: {
 					syntax: "literal"
 				}
 			}
+			headers: {
+				description: "The headers of the AMQP message, if it has any."
+				required:    false
+				type: object: {
+					examples: [{"x-retries": 1}]
+					options: {}
+				}
+			}
 		}
 	}
 
@@ -138,5 +182,16 @@ components: sources: amqp: {
 		processed_events_total:               components.sources.internal_metrics.output.metrics.processed_events_total
 	}
 
-	how_it_works: components._amqp.how_it_works
+	how_it_works: components._amqp.how_it_works & {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				Messages are acknowledged once their events are processed by the sinks, depending on
+				the `acknowledgements` setting, or once they are sent otherwise. Messages whose events
+				failed are requeued with a negative acknowledgement, while messages whose events were
+				rejected are rejected without requeueing, so that they are dead-lettered if the queue
+				has a dead letter exchange.
+				"""
+		}
+	}
 }
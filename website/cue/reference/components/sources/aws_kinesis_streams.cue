@@ -0,0 +1,225 @@
+package metadata
+
+components: sources: aws_kinesis_streams: components._aws & {
+	title: "AWS Kinesis Data Streams"
+
+	features: {
+		acknowledgements: true
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			checkpoint: enabled: true
+			proxy: enabled:      true
+			from: service:       services.aws_kinesis_data_streams
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: [
+			"""
+				The AWS Kinesis Data Streams source requires a DynamoDB table to store the leases and
+				checkpoints of the shards in, with a partition key named `lease_key` of the string
+				type.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		stream_name: {
+			description: "The name of the stream to read records from."
+			required:    true
+			type: string: {
+				examples: ["my-stream"]
+				syntax: "literal"
+			}
+		}
+		application_name: {
+			description: """
+				The name of the consuming application. Instances of Vector with the same application
+				name share the shards of the stream, and the checkpoints of the shards.
+				"""
+			required: true
+			type: string: {
+				examples: ["vector"]
+				syntax: "literal"
+			}
+		}
+		checkpoint_table: {
+			description: """
+				The name of the DynamoDB table to store leases and checkpoints of the shards in. It can
+				be shared by several applications and streams.
+				"""
+			required: true
+			type: string: {
+				examples: ["vector-checkpoints"]
+				syntax: "literal"
+			}
+		}
+		initial_position: {
+			common:      true
+			description: "Where to start reading shards without a checkpoint from."
+			required:    false
+			type: string: {
+				default: "latest"
+				enum: {
+					latest:       "Start with the records added after the shard is first read."
+					trim_horizon: "Start with the oldest records of the shard, which are still retained."
+				}
+				syntax: "literal"
+			}
+		}
+		batch_size: {
+			common:      false
+			description: "The maximum number of records to read from a shard at once. 1-10000"
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    "records"
+			}
+		}
+		poll_secs: {
+			common:      false
+			description: "How long to wait between polling a shard for records. Shards can be read from five times per second, by all consumers of the stream together."
+			required:    false
+			type: float: {
+				default: 1.0
+				unit:    "seconds"
+			}
+		}
+		shard_sync_secs: {
+			common:      false
+			description: "How often to list the shards of the stream. This discovers the shards created by resharding, and the shards whose leases expired."
+			required:    false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+		lease_duration_secs: {
+			common:      false
+			description: "How long a lease of a shard is valid without being renewed. Other instances of the application take over the shards of an instance, once their leases expire."
+			required:    false
+			type: uint: {
+				default: 30
+				unit:    "seconds"
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual Kinesis record"
+		fields: {
+			message: {
+				description: "The data of the Kinesis record."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			stream: {
+				description: "The name of the stream the record was read from."
+				required:    true
+				type: string: {
+					examples: ["my-stream"]
+				}
+			}
+			shard_id: {
+				description: "The ID of the shard the record was read from."
+				required:    true
+				type: string: {
+					examples: ["shardId-000000000000"]
+				}
+			}
+			partition_key: {
+				description: "The partition key of the record."
+				required:    true
+				type: string: {
+					examples: ["my-key"]
+				}
+			}
+			sequence_number: {
+				description: "The sequence number of the record within its shard."
+				required:    true
+				type: string: {
+					examples: ["49590338271490256608559692538361571095921575989136588898"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["aws_kinesis_streams"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The approximate time the record was added to the stream."
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+	}
+
+	how_it_works: {
+		leases: {
+			title: "Leases and checkpoints"
+			body: """
+				Each shard of the stream is read by a single instance of the application at a time,
+				which holds its lease in the DynamoDB table. Leases are renewed while the shard is read,
+				and expire otherwise, so that the shards of a stopped instance are taken over by the
+				others. The sequence number of the last record read from a shard is stored as its
+				checkpoint, once the events of the record are sent, or processed by the sinks,
+				depending on the `acknowledgements` setting. Shards are read from their checkpoint,
+				and from the `initial_position` otherwise.
+				"""
+		}
+		resharding: {
+			title: "Resharding"
+			body: """
+				After [resharding](\(urls.aws_kinesis_resharding)), the children of a shard are only
+				read once the shard was read to its end, so that the records of a partition key are
+				read in order. The children are read from their oldest records.
+				"""
+		}
+	}
+}
@@ -53,7 +53,8 @@ components: sources: aws_s3: components._aws & {
 			type: string: {
 				default: "sqs"
 				enum: {
-					sqs: "Consume S3 objects by polling for bucket notifications sent to an [AWS SQS queue](\(urls.aws_sqs))."
+					sqs:  "Consume S3 objects by polling for bucket notifications sent to an [AWS SQS queue](\(urls.aws_sqs))."
+					poll: "Consume S3 objects by listing the objects of a bucket on an interval, for buckets whose notifications can't be configured."
 				}
 			}
 		}
@@ -115,6 +116,61 @@ components: sources: aws_s3: components._aws & {
 		}
 	}
 
+		poll: {
+			common:      false
+			description: "Polling strategy options. Required if strategy=`poll`."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					bucket: {
+						description: "The name of the bucket to list objects of."
+						required:    true
+						type: string: {
+							examples: ["my-bucket"]
+						}
+					}
+					prefix: {
+						common:      true
+						description: "The prefix of the keys of the objects to read."
+						required:    false
+						type: string: {
+							default: ""
+							examples: ["AWSLogs/111111111111/elasticloadbalancing/"]
+						}
+					}
+					start_after: {
+						common:      true
+						description: "The key to start listing objects after, until objects are read. Objects with this key, or keys before it, aren't read."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["AWSLogs/111111111111/elasticloadbalancing/us-east-1/2022/10/01/"]
+						}
+					}
+					poll_secs: {
+						common:      true
+						description: "How long to wait between listings of the bucket."
+						required:    false
+						type: uint: {
+							default: 60
+							unit:    "seconds"
+						}
+					}
+					watermark_delay_secs: {
+						common:      false
+						description: "How long objects may be added after the objects with keys after theirs. See [polling](#polling)."
+						required:    false
+						type: uint: {
+							default: 3600
+							unit:    "seconds"
+						}
+					}
+				}
+			}
+		}
+	}
+
 	output: logs: object: {
 		description: "A line from an S3 object."
 		fields: {
@@ -160,6 +216,18 @@ components: sources: aws_s3: components._aws & {
 	}
 
 	how_it_works: {
+		polling: {
+			title: "Polling"
+			body: """
+				With `strategy = "poll"`, the objects of the bucket are listed after a watermark, in
+				the order of their keys. The watermark only moves past the objects read once they were
+				modified `watermark_delay_secs` ago, so that objects added later with keys before the
+				ones read, such as the logs of another load balancer, are read too. The objects read
+				after the watermark are kept in a ledger, so that they aren't read again. Both are
+				persisted in Vector's data directory. With acknowledgements enabled, an object is only
+				added to the ledger once its events are delivered.
+				"""
+		}
 		events: {
 			title: "Handling events from the `aws_s3` source"
 			body:  """
@@ -208,6 +276,10 @@ components: sources: aws_s3: components._aws & {
 				{
 					_action: "GetObject"
 				},
+				{
+					_action:       "ListBucket"
+					required_when: "[`strategy`](#strategy) is set to `poll`"
+				},
 			]
 		},
 		{
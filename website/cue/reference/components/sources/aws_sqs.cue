@@ -79,6 +79,21 @@ components: sources: aws_sqs: components._aws & {
 				unit:    "seconds"
 			}
 		}
+		extend_visibility_timeout: {
+			common:      false
+			description: "Whether to keep extending the visibility timeout of messages while their events are in flight. When enabled, the visibility timeout is extended by `visibility_timeout_secs` every half of it until the messages are deleted. When the delivery of their events fails, the messages are received again once the timeout expires. Requires the `sqs:ChangeMessageVisibility` permission on the queue."
+			required:    false
+			type: bool: default: false
+		}
+		max_visibility_extension_secs: {
+			common:      false
+			description: "The maximum time in seconds messages are kept unavailable for by extending their visibility timeout. SQS does not allow keeping messages unavailable for more than 12 hours after they are received."
+			required:    false
+			type: uint: {
+				default: 43200
+				unit:    "seconds"
+			}
+		}
 		delete_message: {
 			common:      true
 			description: "Whether to delete the message once Vector processes it. It can be useful to set this to `false` to debug or during initial Vector setup."
@@ -150,4 +165,26 @@ components: sources: aws_sqs: components._aws & {
 				"""
 		}
 	}
+
+	permissions: iam: [
+		{
+			platform:  "aws"
+			_service:  "sqs"
+			_docs_tag: "AWSSimpleQueueService"
+
+			policies: [
+				{
+					_action: "ReceiveMessage"
+				},
+				{
+					_action:       "DeleteMessage"
+					required_when: "[`delete_message`](#delete_message) is set to `true`"
+				},
+				{
+					_action:       "ChangeMessageVisibility"
+					required_when: "[`extend_visibility_timeout`](#extend_visibility_timeout) is set to `true`"
+				},
+			]
+		},
+	]
 }
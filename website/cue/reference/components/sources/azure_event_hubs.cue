@@ -0,0 +1,272 @@
+package metadata
+
+components: sources: azure_event_hubs: {
+	title: "Azure Event Hubs"
+
+	features: {
+		acknowledgements: true
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      false
+			}
+			checkpoint: enabled: true
+			from: {
+				service: services.azure_event_hubs
+
+				interface: socket: {
+					direction: "outgoing"
+					protocols: ["tcp"]
+					ssl: "required"
+				}
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: [
+			"""
+				The Azure Event Hubs source requires an Azure Blob Storage container to store the
+				ownership and checkpoints of the partitions in.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		connection_string: {
+			common: true
+			description: """
+				The [connection string](\(urls.azure_event_hubs_connection_string)) of the Event Hubs
+				namespace or event hub, with a shared access key. Either `namespace`, or this option,
+				must be specified.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["Endpoint=sb://example.servicebus.windows.net/;SharedAccessKeyName=listen;SharedAccessKey=${EVENT_HUBS_KEY};EntityPath=logs"]
+				syntax: "literal"
+			}
+		}
+		namespace: {
+			common: true
+			description: """
+				The fully qualified Event Hubs namespace. Credentials are loaded from Azure Active
+				Directory, with the environment variables, a Managed Identity or the `az` CLI tool, in
+				this order. Either `connection_string`, or this option, must be specified.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["example.servicebus.windows.net"]
+				syntax: "literal"
+			}
+		}
+		event_hub_name: {
+			common:      true
+			description: "The name of the event hub to read events from. Can be omitted if the connection string has an `EntityPath`."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["logs"]
+				syntax: "literal"
+			}
+		}
+		consumer_group: {
+			common: true
+			description: """
+				The consumer group to read events with. Instances of Vector with the same consumer
+				group and checkpoint store share the partitions of the event hub, and the checkpoints
+				of the partitions.
+				"""
+			required: false
+			type: string: {
+				default: "$Default"
+				syntax:  "literal"
+			}
+		}
+		checkpoint_store: {
+			description: "Storage of partition ownership and checkpoints, in an Azure Blob Storage container."
+			required:    true
+			type: object: options: {
+				connection_string: {
+					common:      true
+					description: "The Azure Blob Storage Account connection string. Either `storage_account`, or this option, must be specified."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["DefaultEndpointsProtocol=https;AccountName=mylogstorage;AccountKey=storageaccountkeybase64encoded;EndpointSuffix=core.windows.net"]
+						syntax: "literal"
+					}
+				}
+				storage_account: {
+					common:      true
+					description: "The Azure Blob Storage Account name, authenticated with Azure Active Directory. Either `connection_string`, or this option, must be specified."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["mylogstorage"]
+						syntax: "literal"
+					}
+				}
+				container_name: {
+					description: """
+						The name of the container to store blobs in. The blobs are named like the ones
+						of the Azure SDKs, so that processors built with them can share the container.
+						"""
+					required: true
+					type: string: {
+						examples: ["checkpoints"]
+						syntax: "literal"
+					}
+				}
+			}
+		}
+		start_position: {
+			common:      true
+			description: "Where to start reading partitions without a checkpoint from."
+			required:    false
+			type: string: {
+				default: "latest"
+				enum: {
+					latest:   "Start with the events enqueued after the partition is first read."
+					earliest: "Start with the oldest events of the partition, which are still retained."
+				}
+				syntax: "literal"
+			}
+		}
+		load_balancing_secs: {
+			common:      false
+			description: "How often to balance the ownership of partitions between instances. An instance claims at most one partition each time, so that the partitions are shared evenly once instances start."
+			required:    false
+			type: uint: {
+				default: 10
+				unit:    "seconds"
+			}
+		}
+		ownership_expiration_secs: {
+			common:      false
+			description: "How long the ownership of a partition is valid without being renewed. Other instances take over the partitions of an instance, once their ownership expires. Must be greater than `load_balancing_secs`."
+			required:    false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+	}
+
+	output: logs: event: {
+		description: "An individual Event Hubs event"
+		fields: {
+			message: {
+				description: "The body of the event."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			event_hub: {
+				description: "The name of the event hub the event was read from."
+				required:    true
+				type: string: {
+					examples: ["logs"]
+				}
+			}
+			partition_id: {
+				description: "The ID of the partition the event was read from."
+				required:    true
+				type: string: {
+					examples: ["0"]
+				}
+			}
+			offset: {
+				description: "The offset of the event within its partition."
+				required:    true
+				type: string: {
+					examples: ["4294967296"]
+				}
+			}
+			sequence_number: {
+				description: "The sequence number of the event within its partition."
+				required:    true
+				type: uint: {
+					examples: [42]
+					unit: null
+				}
+			}
+			partition_key: {
+				description: "The partition key of the event, if it was sent with one."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["my-key"]
+				}
+			}
+			properties: {
+				description: "The application properties of the event, if it has any."
+				required:    false
+				common:      false
+				type: object: {
+					examples: [{"content_type": "application/json"}]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["azure_event_hubs"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time the event was enqueued in the event hub."
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+	}
+
+	how_it_works: {
+		ownership: {
+			title: "Partition ownership and checkpoints"
+			body: """
+				Each partition of the event hub is read by a single instance of the consumer group at a
+				time, which claims its ownership in the checkpoint store. Ownership is renewed while the
+				partition is read, and expires otherwise, so that the partitions of a stopped instance
+				are taken over by the others. The offset of the last event read from a partition is
+				stored as its checkpoint, once the event is sent, or processed by the sinks, depending
+				on the `acknowledgements` setting. Partitions are read from their checkpoint, and from
+				the `start_position` otherwise.
+				"""
+		}
+	}
+}
@@ -103,6 +103,10 @@ components: sources: docker_logs: {
 				If absent, Vector will try to use `DOCKER_HOST` environment variable.
 				If `DOCKER_HOST` is also absent, Vector will use default Docker local socket
 				(`/var/run/docker.sock` on Unix platforms, `//./pipe/docker_engine` on Windows).
+				On Unix platforms, if the default Docker socket doesn't exist, Vector will use the
+				Docker compatible socket of Podman instead, trying the rootless socket
+				(`$XDG_RUNTIME_DIR/podman/podman.sock`) first and then the rootful one
+				(`/run/podman/podman.sock`).
 				"""
 			required: false
 			type: string: {
@@ -111,12 +115,59 @@ components: sources: docker_logs: {
 					"http://localhost:2375",
 					"https://localhost:2376",
 					"unix:///var/run/docker.sock",
+					"unix:///run/podman/podman.sock",
 					"npipe:////./pipe/docker_engine",
 					"/var/run/docker.sock",
 					"//./pipe/docker_engine",
 				]
 			}
 		}
+		runtime: {
+			common:      false
+			description: "The container runtime to collect logs from."
+			required:    false
+			type: string: {
+				default: "docker"
+				enum: {
+					docker: "Docker, or Podman, through the API served at `docker_host`."
+					cri: """
+						containerd, CRI-O or any other runtime implementing the Kubernetes Container Runtime
+						Interface, by reading the log files matched by [`cri_include`](#cri_include). The parts
+						of the lines which the runtime split up are joined when
+						[`auto_partial_merge`](#auto_partial_merge) is enabled.
+						"""
+				}
+			}
+		}
+		cri_include: {
+			common: false
+			description: """
+				A list of glob patterns of the CRI log files to read, when `runtime` is `cri`. The ID and
+				the name of the container are taken from the file name, which must end with the container
+				ID, like the files linked by the kubelet in `/var/log/containers`. Only the
+				`include_containers` and `exclude_containers` filters apply to these files.
+				"""
+			required: false
+			type: array: {
+				default: ["/var/log/containers/*.log"]
+				items: type: string: {
+					examples: ["/var/log/containers/*.log"]
+				}
+			}
+		}
+		data_dir: {
+			common: false
+			description: """
+				The directory used to persist the positions read in the CRI log files, when `runtime` is
+				`cri`. By default, the [global `data_dir` option](\(urls.vector_configuration)/global-options#data_dir)
+				is used. Make sure the running user has write permissions to this directory.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["/var/local/lib/vector/"]
+			}
+		}
 		tls: {
 			common: false
 			description: """
@@ -257,8 +308,8 @@ components: sources: docker_logs: {
 			description: "A Docker log event"
 			fields: {
 				container_created_at: {
-					description: "A UTC timestamp representing when the container was created."
-					required:    true
+					description: "A UTC timestamp representing when the container was created. Only set with the `docker` runtime."
+					required:    false
 					type: timestamp: {}
 				}
 				container_id: {
@@ -276,12 +327,19 @@ components: sources: docker_logs: {
 					}
 				}
 				image: {
-					description: "The image name that the container is based on."
-					required:    true
+					description: "The image name that the container is based on. Only set with the `docker` runtime."
+					required:    false
 					type: string: {
 						examples: ["ubuntu:latest", "busybox", "timberio/vector:latest-alpine"]
 					}
 				}
+				file: {
+					description: "The CRI log file that the log was read from. Only set with the `cri` runtime."
+					required:    false
+					type: string: {
+						examples: ["/var/log/containers/web-7d4b9c_default_nginx-4f5c8a1b2e3d4c5b6a7980f1e2d3c4b5a69788f9e0d1c2b3a4958677f8e9d0c1.log"]
+					}
+				}
 				message: {
 					description: "The raw log message."
 					required:    true
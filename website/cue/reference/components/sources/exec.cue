@@ -63,6 +63,28 @@ components: sources: exec: {
 			description: "The directory in which to run the command."
 			type: string: default: null
 		}
+		environment: {
+			common:      false
+			required:    false
+			description: "Custom environment variables to set or override when running the command. If a variable name already exists in the environment, its value is replaced."
+			type: object: {
+				examples: [{"LANG": "en_US.UTF-8", "TZ": "Etc/UTC"}]
+				options: {
+					"*": {
+						common:      false
+						description: "The value of the environment variable."
+						required:    false
+						type: string: {}
+					}
+				}
+			}
+		}
+		clear_environment: {
+			common:      false
+			required:    false
+			description: "Whether or not to clear the environment before setting the custom environment variables."
+			type: bool: default: false
+		}
 		include_stderr: {
 			common:      false
 			description: "Include the output of stderr when generating events."
@@ -87,7 +109,7 @@ components: sources: exec: {
 				options: {
 					exec_interval_secs: {
 						common:        true
-						description:   "The interval in seconds between scheduled command runs. The command will be killed if it takes longer than exec_interval_secs to run."
+						description:   "The interval in seconds between scheduled command runs. The command will be killed if it takes longer than exec_interval_secs to run, unless another `overlap_policy` is set."
 						relevant_when: "mode = `scheduled`"
 						required:      false
 						type: uint: {
@@ -95,6 +117,33 @@ components: sources: exec: {
 							unit:    "seconds"
 						}
 					}
+					cron: {
+						common:        true
+						description:   "A cron expression the command is run on, instead of every `exec_interval_secs`. The five standard fields are supported, as well as `@hourly`, `@daily`, `@weekly`, `@monthly`, and `@yearly`."
+						relevant_when: "mode = `scheduled`"
+						required:      false
+						type: string: {
+							default: null
+							examples: ["*/5 * * * *", "30 2 * * mon-fri", "@daily"]
+						}
+					}
+					timezone: configuration._timezone & {
+						relevant_when: "mode = `scheduled`"
+					}
+					overlap_policy: {
+						common:        false
+						description:   "What happens when a scheduled run is due while the previous one is still running."
+						relevant_when: "mode = `scheduled`"
+						required:      false
+						type: string: {
+							default: "replace"
+							enum: {
+								replace: "The command is killed when the next run is due."
+								skip:    "The next run is skipped while the command is still running."
+								allow:   "The runs are allowed to overlap."
+							}
+						}
+					}
 				}
 			}
 		}
@@ -122,6 +171,36 @@ components: sources: exec: {
 							unit:    "seconds"
 						}
 					}
+					max_respawn_interval_secs: {
+						common:        false
+						description:   "The maximum interval in seconds between restarting a command that keeps exiting. When set, the interval doubles on every consecutive exit, starting from `respawn_interval_secs`, up to this maximum."
+						relevant_when: "mode = `streaming`"
+						required:      false
+						type: uint: {
+							default: null
+							unit:    "seconds"
+						}
+					}
+					max_respawns: {
+						common:        false
+						description:   "The maximum number of consecutive restarts, after which a command that keeps exiting is no longer restarted."
+						relevant_when: "mode = `streaming`"
+						required:      false
+						type: uint: {
+							default: null
+							unit:    null
+						}
+					}
+					respawn_reset_secs: {
+						common:        false
+						description:   "The time in seconds a command must run for before it is considered healthy again. The exit of a healthy command resets the restart interval and the number of consecutive restarts."
+						relevant_when: "mode = `streaming`"
+						required:      false
+						type: uint: {
+							default: 60
+							unit:    "seconds"
+						}
+					}
 				}
 			}
 		}
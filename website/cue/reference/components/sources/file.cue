@@ -144,6 +144,24 @@ components: sources: file: {
 			required:    false
 			type: bool: default: false
 		}
+		ignore_rules: {
+			common:      false
+			description: """
+				Array of gitignore-style rules applied, in order, to the files matched by the
+				[`include` option](#include). Rules are matched against the path of a file relative to
+				the directory before the first wildcard of the `include` pattern which matched it. A rule
+				starting with `!` includes again the files excluded by the preceding rules, a rule ending
+				with `/` excludes everything within the matching directories, and a rule without any
+				other `/` matches the name of the file or of any of its parent directories.
+				"""
+			required:    false
+			type: array: {
+				default: null
+				items: type: string: {
+					examples: ["*.gz", "!important.log.gz", "archive/", "/debug/**/*.log"]
+				}
+			}
+		}
 		ignore_older_secs: {
 			common:      true
 			description: "Ignore files with a data modification date older than the specified number of seconds."
@@ -170,6 +188,16 @@ components: sources: file: {
 				examples: ["\r\n"]
 			}
 		}
+		max_depth: {
+			common:      false
+			description: "The maximum depth of the files read below the directory before the first wildcard of the `include` pattern which matched them. A depth of `1` only reads the files directly within that directory."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [1, 3]
+				unit: null
+			}
+		}
 		max_line_bytes: {
 			common:      false
 			description: "The maximum number of bytes a line can contain before being discarded. This protects against malformed lines or tailing incorrect files."
@@ -230,6 +258,19 @@ components: sources: file: {
 				}
 			}
 		}
+		symlinks: {
+			common:      false
+			description: "Which symbolic links are followed when looking for files. Symbolic links within the directory before the first wildcard of the `include` pattern are always followed."
+			required:    false
+			type: string: {
+				default: "follow"
+				enum: {
+					follow:     "Follow symbolic links to both files and directories."
+					files_only: "Follow symbolic links to files, but don't look for files within symbolic links to directories."
+					never:      "Don't follow any symbolic link."
+				}
+			}
+		}
 		ignore_checkpoints: {
 			common:      false
 			description: "This causes Vector to ignore existing checkpoints when determining where to start reading a file. Checkpoints are still written normally."
@@ -114,6 +114,36 @@ components: sources: gcp_pubsub: {
 				examples: [10.0]
 			}
 		}
+		max_ack_extension_secs: {
+			common:      false
+			description: "The maximum amount of time to extend the acknowledgement deadline of messages for, while their events are being delivered. With acknowledgements enabled, the deadline of messages is extended until their events are delivered, so that slow sinks don't cause messages to be retransmitted. Set to `0` to disable."
+			required:    false
+			type: uint: {
+				default: 3600
+				examples: [0, 600]
+				unit: "seconds"
+			}
+		}
+		max_outstanding_bytes: {
+			common:      false
+			description: "The maximum size of the messages to receive on a stream, which haven't been acknowledged yet. The server stops sending messages on a stream once this many bytes are outstanding. By default, there is no limit."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [104857600]
+				unit: "bytes"
+			}
+		}
+		max_outstanding_messages: {
+			common:      false
+			description: "The maximum number of messages to receive on a stream, which haven't been acknowledged yet. The server stops sending messages on a stream once this many messages are outstanding. By default, there is no limit."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [1000]
+				unit: null
+			}
+		}
 		max_concurrency: {
 			common:      false
 			description: "The maximum number of concurrent stream connections to open at once."
@@ -196,6 +226,14 @@ components: sources: gcp_pubsub: {
 					syntax: "literal"
 				}
 			}
+			ordering_key: {
+				description: "The ordering key of the message, if it was published with one."
+				required:    false
+				type: string: {
+					examples: ["customer-1234"]
+					syntax: "literal"
+				}
+			}
 			source_type: {
 				description: "The name of the source type."
 				required:    true
@@ -226,6 +264,15 @@ components: sources: gcp_pubsub: {
 				have the `acknowledgements` setting enabled.
 				"""
 		}
+		ordering: {
+			title: "Ordered delivery"
+			body: """
+				Subscriptions with message ordering enabled deliver the messages of an ordering key in order, and
+				on a single stream, which sends them downstream in the order they were received. If a message isn't
+				acknowledged in time, it is redelivered along with the following messages of its ordering key. The
+				ordering key is added to the events as the `ordering_key` field.
+				"""
+		}
 		auto_concurrency: {
 			title: "Automatic Concurrency Management"
 			body: """
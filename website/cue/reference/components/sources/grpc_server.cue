@@ -0,0 +1,158 @@
+package metadata
+
+components: sources: grpc_server: {
+	_port: 50051
+
+	title: "gRPC Server"
+
+	description: """
+		Receives log events through a gRPC service, whose request messages are decoded with
+		the descriptors of your own Protocol Buffers definitions.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator", "sidecar"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.grpc
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: enabled: false
+			keepalive: enabled:            true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		address: {
+			description: "The address to listen for connections on. It _must_ include a port."
+			required:    true
+			type: string: {
+				examples: ["0.0.0.0:\(_port)"]
+			}
+		}
+		descriptor_set_path: {
+			description: """
+				The path to the [file descriptor set](\(urls.protobuf_descriptor_set)), with the
+				service and the messages it's using. The file descriptor set can be generated
+				from the `.proto` files with `protoc --include_imports --descriptor_set_out=<path>`.
+				"""
+			required: true
+			type: string: {
+				examples: ["/etc/vector/ingest.desc"]
+			}
+		}
+		service: {
+			description: """
+				The fully qualified name of the service to expose. Each unary method of the
+				service is accepted, and responds with an empty response message once the events
+				are delivered.
+				"""
+			required: true
+			type: string: {
+				examples: ["acme.ingest.v1.IngestService"]
+			}
+		}
+		events_field: {
+			common: false
+			description: """
+				The name of a repeated message field of the requests, whose elements are each
+				decoded into an event. The other fields of the requests are added to each of the
+				events. By default, each request is decoded into a single event.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["entries"]
+			}
+		}
+		method_key: {
+			common:      false
+			description: "The key to add the name of the method that was called to."
+			required:    false
+			type: string: {
+				default: "method"
+				examples: ["grpc_method"]
+			}
+		}
+	}
+
+	output: logs: event: {
+		description: "A request message, or an element of its `events_field`."
+		fields: {
+			method: {
+				description: "The name of the method that was called."
+				required:    true
+				type: string: {
+					examples: ["Ingest"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["grpc_server"]
+				}
+			}
+			timestamp: fields._current_timestamp
+			"*": {
+				description: """
+					The fields of the message that are set. Enum values are converted to their
+					names, and fields set to their default value are omitted, as in proto3.
+					"""
+				required: true
+				type: "*": {}
+			}
+		}
+	}
+
+	how_it_works: {
+		decoding: {
+			title: "Decoding"
+			body: """
+				The service is looked up in the descriptor set as the source is started, so
+				that messages are decoded without generating code for them. Calls to unknown
+				methods, or to streaming methods, respond with the `UNIMPLEMENTED` status.
+				Requests that fail to decode respond with the `INVALID_ARGUMENT` status.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
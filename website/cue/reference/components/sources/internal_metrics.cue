@@ -793,6 +793,41 @@ components: sources: internal_metrics: {
 				path: _path
 			}
 		}
+		grok_no_match_total: {
+			description:       "The total number of values that didn't match any rule of a `parse_groks` rule set."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				rule_set: _rule_set
+			}
+		}
+		grok_rule_attempts_total: {
+			description:       "The total number of values a `parse_groks` rule was tried against."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				rule_set: _rule_set
+				rule:     _rule
+			}
+		}
+		grok_rule_average_match_seconds: {
+			description:       "The average time it takes to try a `parse_groks` rule against a value."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				rule_set: _rule_set
+				rule:     _rule
+			}
+		}
+		grok_rule_matches_total: {
+			description:       "The total number of values that matched a `parse_groks` rule."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				rule_set: _rule_set
+				rule:     _rule
+			}
+		}
 		http_bad_requests_total: {
 			description:       "The total number of HTTP `400 Bad Request` errors encountered."
 			type:              "counter"
@@ -1300,6 +1335,14 @@ components: sources: internal_metrics: {
 			description: "The HTTP status code of the request."
 			required:    false
 		}
+		_rule_set: {
+			description: "The ID of the compiled `parse_groks` rule set."
+			required:    true
+		}
+		_rule: {
+			description: "The index of the rule within the `parse_groks` rule set."
+			required:    true
+		}
 		_path: {
 			description: "The path that produced the error."
 			required:    true
@@ -66,14 +66,45 @@ components: sources: journald: {
 			required:    false
 			type: bool: default: false
 		}
+		start_from: {
+			common:      false
+			description: "Where to start reading the journal when no checkpoint was saved yet. Overrides `since_now` when set."
+			required:    false
+			type: object: options: {
+				position: {
+					description: "The position to start reading from."
+					required:    true
+					type: string: enum: {
+						head:   "Read from the oldest entry of the journal."
+						tail:   "Only read the entries appended after Vector starts reading the journal."
+						cursor: "Read the entries after the entry with the given `cursor`."
+					}
+				}
+				cursor: {
+					description:   "The cursor of the entry, such as one saved by `journalctl --cursor-file`."
+					required:      true
+					relevant_when: "position = `cursor`"
+					type: string: examples: ["s=d2d5ea2a7c2f4ac2ab5fd8f0a035cc38;i=1f4;b=5b1a7c4e19c24e0e9a1c38e0aaf5e0b4;m=2b8e2a;t=5ec5b82a1f3d0;x=2f1c0a7d3e8b6f5a"]
+				}
+			}
+		}
+		priority: {
+			common:      false
+			description: "Only include entries of this syslog level or more important, or of a range of levels such as `warning..err`. Levels are either names, such as `err`, or numbers from `0` to `7`."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["warning", "emerg..err", "3"]
+			}
+		}
 		exclude_units: {
 			common:      true
-			description: "The list of unit names to exclude from monitoring. Unit names lacking a `\".\"` will have `\".service\"` appended to make them a valid service unit name."
+			description: "The list of unit names to exclude from monitoring. Unit names lacking a `\".\"` will have `\".service\"` appended to make them a valid service unit name. Unit names containing `*`, `?` or `[` are globs."
 			required:    false
 			type: array: {
 				default: []
 				items: type: string: {
-					examples: ["badservice", "sysinit.target"]
+					examples: ["badservice", "sysinit.target", "debug-*"]
 				}
 			}
 		}
@@ -105,12 +136,12 @@ components: sources: journald: {
 		}
 		include_units: {
 			common:      true
-			description: "The list of unit names to monitor. If empty or not present, all units are accepted. Unit names lacking a `\".\"` will have `\".service\"` appended to make them a valid service unit name."
+			description: "The list of unit names to monitor. If empty or not present, all units are accepted. Unit names lacking a `\".\"` will have `\".service\"` appended to make them a valid service unit name. Unit names containing `*`, `?` or `[` are globs."
 			required:    false
 			type: array: {
 				default: []
 				items: type: string: {
-					examples: ["ntpd", "sysinit.target"]
+					examples: ["ntpd", "sysinit.target", "nginx-*"]
 				}
 			}
 		}
@@ -146,7 +146,54 @@ components: sources: kafka: {
 						required:    false
 						type: string: {
 							default: null
-							examples: ["SCRAM-SHA-256", "SCRAM-SHA-512"]
+							examples: ["SCRAM-SHA-256", "SCRAM-SHA-512", "OAUTHBEARER"]
+						}
+					}
+					oauthbearer: {
+						common:      false
+						description: """
+							Options for SASL/OAUTHBEARER authentication with tokens from an OpenID Connect provider,
+							as used by Amazon MSK and Confluent Cloud. Tokens are requested with the client credentials
+							grant and refreshed before they expire. When set, `mechanism` defaults to `OAUTHBEARER`.
+							"""
+						required:    false
+						type: object: {
+							examples: []
+							options: {
+								client_id: {
+									description: "The client ID used to request tokens."
+									required:    true
+									type: string: examples: ["vector"]
+								}
+								client_secret: {
+									description: "The client secret used to request tokens."
+									required:    true
+									type: string: examples: ["${KAFKA_CLIENT_SECRET}"]
+								}
+								extensions: {
+									common:      false
+									description: "Extensions sent to the broker along with the token. Confluent Cloud, for example, requires the `logicalCluster` and `identityPoolId` extensions."
+									required:    false
+									type: object: {
+										examples: [{"logicalCluster": "lkc-123", "identityPoolId": "pool-abc"}]
+										options: {}
+									}
+								}
+								scope: {
+									common:      false
+									description: "The scope of the requested tokens."
+									required:    false
+									type: string: {
+										default: null
+										examples: ["kafka"]
+									}
+								}
+								token_endpoint_url: {
+									description: "The URL of the token endpoint of the OpenID Connect provider."
+									required:    true
+									type: string: examples: ["https://auth.example.com/oauth2/token"]
+								}
+							}
 						}
 					}
 					password: {
@@ -181,6 +228,15 @@ components: sources: kafka: {
 			}
 		}
 		socket_timeout_ms: components._kafka.configuration.socket_timeout_ms
+		timestamp_type_key: {
+			common:      false
+			description: "The log field name to use for the timestamp type of the Kafka record, which is `create_time` when the timestamp was set by the producer and `log_append_time` when it was set by the broker. The timestamp type isn't added when unset."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["timestamp_type"]
+			}
+		}
 		topics: {
 			description: "The Kafka topics names to read events from. Regex is supported if the topic begins with `^`."
 			required:    true
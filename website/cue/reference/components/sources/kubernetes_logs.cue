@@ -268,6 +268,42 @@ components: sources: kubernetes_logs: {
 				examples: ["my_custom_label!=my_value", "my_custom_label!=my_value,my_other_custom_label=my_value"]
 			}
 		}
+		include_namespaces: {
+			common:      false
+			description: "A list of namespaces to read the logs of the `Pod`s from. If empty, the `Pod`s of all namespaces are read, unless excluded by `exclude_namespaces`."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: examples: ["production", "payments"]
+			}
+		}
+		exclude_namespaces: {
+			common:      false
+			description: "A list of namespaces to not read the logs of the `Pod`s from."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: examples: ["kube-system"]
+			}
+		}
+		include_pod_annotations: {
+			common:      false
+			description: "A list of the annotations of `Pod`s to add to each event. If not set, all the annotations are added, and if empty, none are."
+			required:    false
+			type: array: {
+				default: null
+				items: type: string: examples: ["app.kubernetes.io/version", "team"]
+			}
+		}
+		include_node_labels: {
+			common:      false
+			description: "A list of the labels of the `Node` to add to each event. If not set, all the labels are added, and if empty, none are."
+			required:    false
+			type: array: {
+				default: null
+				items: type: string: examples: ["topology.kubernetes.io/zone", "node.kubernetes.io/instance-type"]
+			}
+		}
 		max_read_bytes: {
 			category:    "Reading"
 			common:      false
@@ -547,6 +583,8 @@ components: sources: kubernetes_logs: {
 				* The `extra_label_selector` option specifies the label selector to
 				  filter `Pod`s with, to be used in addition to the [built-in
 				  `vector.dev/exclude` filter](#pod-exclusion).
+				* The `include_namespaces` and `exclude_namespaces` options specify
+				  the namespaces to read the logs of the `Pod`s from.
 				"""
 		}
 
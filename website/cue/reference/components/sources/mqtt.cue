@@ -0,0 +1,235 @@
+package metadata
+
+components: sources: mqtt: {
+	title: "MQTT"
+
+	features: {
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: false
+			tls: {
+				enabled:                true
+				can_verify_certificate: false
+				can_verify_hostname:    false
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			from: {
+				service: services.mqtt
+				interface: {
+					socket: {
+						direction: "outgoing"
+						port:      1883
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		host: {
+			common:      true
+			description: "The MQTT broker host to connect to."
+			required:    false
+			type: string: {
+				default: "localhost"
+				examples: ["mqtt.example.com"]
+				syntax: "literal"
+			}
+		}
+		port: {
+			common:      true
+			description: "The MQTT broker port to connect to. Defaults to `8883` with TLS enabled, and to `1883` otherwise."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [1883, 8883]
+				unit: null
+			}
+		}
+		protocol_version: {
+			common:      false
+			description: "The MQTT protocol version."
+			required:    false
+			type: string: {
+				default: "3.1.1"
+				enum: {
+					"3.1.1": "MQTT 3.1.1."
+					"5":     "MQTT 5."
+				}
+				syntax: "literal"
+			}
+		}
+		client_id: {
+			common:      false
+			description: "The client identifier of the connection."
+			required:    false
+			type: string: {
+				default: "vector"
+				examples: ["vector-edge-1"]
+				syntax: "literal"
+			}
+		}
+		topics: {
+			description: "The topic filters to subscribe to. Filters may contain the single level wildcard `+` and the multi level wildcard `#`."
+			required:    true
+			type: array: items: type: string: {
+				examples: ["sensors/+/temperature", "sensors/#"]
+				syntax: "literal"
+			}
+		}
+		qos: {
+			common:      false
+			description: "The quality of service to subscribe with."
+			required:    false
+			type: string: {
+				default: "at_least_once"
+				enum: {
+					at_most_once:  "Messages are delivered at most once, and may be lost."
+					at_least_once: "Messages are delivered at least once, and may be duplicated. With acknowledgements enabled, messages are only acknowledged to the broker once their events are delivered."
+				}
+				syntax: "literal"
+			}
+		}
+		clean_session: {
+			common:      false
+			description: "Whether to start a clean session, discarding the subscriptions and pending messages of earlier connections with the same client identifier."
+			required:    false
+			type: bool: default: true
+		}
+		keep_alive_secs: {
+			common:      false
+			description: "The interval in which the broker is pinged to keep the connection alive."
+			required:    false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+		auth: {
+			common:      false
+			description: "Username and password authentication."
+			required:    false
+			type: object: options: {
+				user: {
+					description: "The username."
+					required:    true
+					type: string: {
+						examples: ["vector"]
+						syntax: "literal"
+					}
+				}
+				password: {
+					description: "The password."
+					required:    true
+					type: string: {
+						examples: ["${MQTT_PASSWORD}"]
+						syntax: "literal"
+					}
+				}
+			}
+		}
+		topic_key: {
+			common:      false
+			description: "The log field name to use for the topic of the message."
+			required:    false
+			type: string: {
+				default: "topic"
+				syntax:  "literal"
+			}
+		}
+		retained_key: {
+			common:      false
+			description: "The log field name to use for the retained flag of the message."
+			required:    false
+			type: string: {
+				default: "retained"
+				syntax:  "literal"
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual MQTT message."
+		fields: {
+			message: {
+				description: "The payload of the MQTT message."
+				required:    true
+				type: string: {
+					examples: ["{\"temperature\": 21.5}"]
+				}
+			}
+			topic: {
+				description: "The topic the message was published to."
+				required:    true
+				type: string: {
+					examples: ["sensors/kitchen/temperature"]
+				}
+			}
+			retained: {
+				description: "Whether the message was retained by the broker, i.e. published before subscribing."
+				required:    true
+				type: bool: {}
+			}
+			timestamp: fields._current_timestamp
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["mqtt"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		rumqttc: {
+			title: "rumqttc"
+			body: """
+				The `mqtt` source uses [`rumqttc`](\(urls.rumqttc)) under the hood. The connection is re-established
+				automatically, and the topic filters are subscribed to again on every connection.
+				"""
+		}
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				With the `at_least_once` quality of service and acknowledgements enabled, messages are only acknowledged
+				to the broker once their events are delivered. As MQTT can't reject single messages, the broker only
+				redelivers unacknowledged messages after a reconnect, which requires `clean_session` to be disabled.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
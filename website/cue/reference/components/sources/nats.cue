@@ -4,7 +4,7 @@ components: sources: nats: {
 	title: "NATS"
 
 	features: {
-		acknowledgements: false
+		acknowledgements: true
 		collect: {
 			checkpoint: enabled: false
 			from: components._nats.features.collect.from
@@ -32,15 +32,61 @@ components: sources: nats: {
 	}
 
 	configuration: components._nats.configuration & {
+		acknowledgements: configuration._source_acknowledgements
 		queue: {
 			common:      false
-			description: "NATS Queue Group to join"
+			description: "NATS Queue Group to join. Not supported with `jetstream`."
 			required:    false
 			type: string: {
 				default: "vector"
 				examples: ["foo", "API Name Option Example"]
 			}
 		}
+		jetstream: {
+			common:      false
+			description: "Reads messages with a [JetStream](\(urls.nats_jetstream)) pull consumer, instead of a core NATS subscription."
+			required:    false
+			type: object: options: {
+				stream: {
+					description: "The name of the stream to read messages from."
+					required:    true
+					type: string: {
+						examples: ["logs"]
+					}
+				}
+				durable_name: {
+					description: "The name of the durable consumer to read messages with. Instances of Vector with the same consumer share the messages of the stream, and continue where the consumer left off after restarts."
+					required:    true
+					type: string: {
+						examples: ["vector"]
+					}
+				}
+				create_consumer: {
+					common:      false
+					description: "Whether to create the consumer, if it doesn't exist yet. The consumer only receives the messages of the `subject`, with acknowledgements of each message required."
+					required:    false
+					type: bool: default: true
+				}
+				batch_size: {
+					common:      false
+					description: "The maximum number of messages to pull at once."
+					required:    false
+					type: uint: {
+						default: 100
+						unit:    "messages"
+					}
+				}
+				ack_wait_secs: {
+					common:      false
+					description: "How long to wait for a message to be acknowledged before it's delivered again. Only used when creating the consumer."
+					required:    false
+					type: uint: {
+						default: 30
+						unit:    "seconds"
+					}
+				}
+			}
+		}
 	}
 
 	output: logs: record: {
@@ -74,5 +120,16 @@ components: sources: nats: {
 		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
 	}
 
-	how_it_works: components._nats.how_it_works
+	how_it_works: components._nats.how_it_works & {
+		jetstream: {
+			title: "JetStream"
+			body: """
+				With `jetstream`, messages are pulled from a durable consumer of a JetStream stream.
+				Each message is acknowledged once its events are processed by the sinks, depending on
+				the `acknowledgements` setting, or once they are sent otherwise. Messages whose
+				events failed are delivered again, while messages whose events were rejected are
+				terminated, so that they aren't delivered again.
+				"""
+		}
+	}
 }
@@ -40,7 +40,7 @@ components: sources: opentelemetry: {
 		requirements: []
 		warnings: [
 			"""
-				The `opentelemetry` source only supports log events at this time.
+				The `opentelemetry` source only supports log and trace events at this time.
 				""",
 		]
 		notices: []
@@ -131,6 +131,12 @@ components: sources: opentelemetry: {
 				Received log events will go to this output stream. Use `<component_id>.logs` as an input to downstream transforms and sinks.
 				"""
 		},
+		{
+			name: "traces"
+			description: """
+				Received spans will go to this output stream, as one trace event each. Use `<component_id>.traces` as an input to downstream transforms and sinks.
+				"""
+		},
 	]
 
 	output: {
@@ -177,6 +183,14 @@ components: sources: opentelemetry: {
 						]
 					}
 				}
+				scope: {
+					description: "The instrumentation scope the log record was emitted with, with its `name`, `version` and `attributes`."
+					required:    false
+					common:      false
+					type: object: {
+						examples: [{"name": "io.opentelemetry.contrib.mongodb", "version": "1.0.0"}]
+					}
+				}
 				message: {
 					description: "Contains the body of the log record."
 					required:    false
@@ -263,6 +277,128 @@ components: sources: opentelemetry: {
 				}
 			}
 		}
+		traces: {
+			description: "An individual span from a batch of spans received through an OTLP request"
+			fields: {
+				attributes: {
+					description: "Attributes that describe the operation of the span."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"http.method": "GET", "http.status_code": 200}]
+					}
+				}
+				resources: {
+					description: "Set of attributes that describe the resource."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"service.name": "donut_shop"}]
+					}
+				}
+				scope: {
+					description: "The instrumentation scope the span was created with, with its `name`, `version` and `attributes`."
+					required:    false
+					common:      false
+					type: object: {
+						examples: [{"name": "io.opentelemetry.contrib.mongodb", "version": "1.0.0"}]
+					}
+				}
+				trace_id: {
+					description: "The identifier of the trace the span belongs to, in hexadecimal."
+					required:    true
+					type: string: {
+						examples: ["66346462623365646437363566363230"]
+					}
+				}
+				span_id: {
+					description: "The identifier of the span, in hexadecimal."
+					required:    true
+					type: string: {
+						examples: ["43222c2d51a7abe3"]
+					}
+				}
+				parent_span_id: {
+					description: "The identifier of the parent span, in hexadecimal. It's empty for root spans."
+					required:    true
+					type: string: {
+						examples: ["b7ad6b7169203331", ""]
+					}
+				}
+				trace_state: {
+					description: "The trace state, as defined in W3C Trace Context."
+					required:    true
+					type: string: {
+						examples: ["rojo=00f067aa0ba902b7"]
+					}
+				}
+				name: {
+					description: "A description of the operation of the span."
+					required:    true
+					type: string: {
+						examples: ["GET /cart"]
+					}
+				}
+				kind: {
+					description: "The kind of span, from `0` (unspecified) to `5` (consumer)."
+					required:    true
+					type: uint: {
+						unit: null
+						examples: [2]
+					}
+				}
+				start_time_unix_nano: {
+					description: "The UTC Datetime when the span started."
+					required:    true
+					type: timestamp: {}
+				}
+				end_time_unix_nano: {
+					description: "The UTC Datetime when the span ended."
+					required:    true
+					type: timestamp: {}
+				}
+				events: {
+					description: "The time-stamped annotations of the span, with their `name`, `time_unix_nano` and `attributes`."
+					required:    false
+					common:      false
+					type: array: items: type: object: options: {}
+				}
+				links: {
+					description: "The references from the span to other spans, with their `trace_id`, `span_id`, `trace_state` and `attributes`."
+					required:    false
+					common:      false
+					type: array: items: type: object: options: {}
+				}
+				status: {
+					description: "The final status of the span, with its `message` and `code`."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"message": "", "code": 1}]
+					}
+				}
+				dropped_attributes_count: {
+					description: "Counts for attributes dropped due to collection limits."
+					required:    true
+					type: uint: unit: null
+				}
+				dropped_events_count: {
+					description: "Counts for events dropped due to collection limits."
+					required:    true
+					type: uint: unit: null
+				}
+				dropped_links_count: {
+					description: "Counts for links dropped due to collection limits."
+					required:    true
+					type: uint: unit: null
+				}
+				ingest_timestamp: {
+					description: "The UTC Datetime when the span was received by Vector."
+					required:    true
+					type: timestamp: {}
+				}
+			}
+		}
 	}
 
 	telemetry: metrics: {
@@ -54,10 +54,11 @@ components: sources: prometheus_scrape: {
 
 	configuration: {
 		endpoints: {
-			description: "Endpoints to scrape metrics from."
-			required:    true
+			description: "Endpoints to scrape metrics from. Either `endpoints` or `kubernetes_sd` must be set."
+			required:    false
 			warnings: ["You must explicitly add the path to your endpoints. Vector will _not_ automatically add `/metrics`."]
 			type: array: {
+				default: []
 				items: type: string: {
 					examples: ["http://localhost:9090/metrics"]
 				}
@@ -144,6 +145,149 @@ components: sources: prometheus_scrape: {
 				}
 			}
 		}
+		kubernetes_sd: {
+			common:      false
+			description: """
+				Discovers the targets to scrape from the Kubernetes API, like Prometheus' `kubernetes_sd_configs`.
+
+				Requires Vector to be built with the `sources-prometheus-kubernetes` feature, which the official
+				builds include.
+				"""
+			required:    false
+			type: object: options: {
+				role: {
+					description: "The kind of Kubernetes objects the targets are discovered from."
+					required:    false
+					type: string: {
+						default: "pod"
+						enum: {
+							pod:       "A target for every declared port of every container of the running pods."
+							endpoints: "A target for every port of every address of the endpoints of services."
+							service:   "A target for every port of the services, addressed by their DNS name."
+						}
+					}
+				}
+				namespaces: {
+					description: "The namespaces to discover targets in. By default, targets are discovered in all namespaces."
+					required:    false
+					type: array: {
+						default: []
+						items: type: string: examples: ["default", "monitoring"]
+					}
+				}
+				label_selector: {
+					description: "The label selector the discovered objects must match."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["app.kubernetes.io/part-of=shop"]
+					}
+				}
+				field_selector: {
+					description: "The field selector the discovered objects must match."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["status.phase=Running"]
+					}
+				}
+				annotations: {
+					description: """
+						Whether the `prometheus.io/*` annotations select and configure the targets. When enabled, only
+						the objects annotated with `prometheus.io/scrape: "true"` are scraped, and the
+						`prometheus.io/scheme`, `prometheus.io/path`, and `prometheus.io/port` annotations override the
+						scheme, path, and port of their targets. With the `endpoints` role, the annotations of the
+						service are used.
+						"""
+					required: false
+					type: bool: default: true
+				}
+				kube_config_file: {
+					description: """
+						Optional path to a kubeconfig file readable by Vector. If not set, Vector will try to connect to
+						Kubernetes using in-cluster configuration.
+						"""
+					required: false
+					type: string: {
+						default: null
+						examples: ["/path/to/.kube/config"]
+					}
+				}
+			}
+		}
+		relabel_configs: {
+			common: false
+			description: """
+				Relabeling rules applied in order to the targets before they are scraped, with the semantics of
+				Prometheus' `relabel_configs`. The rules apply to the discovered targets as well as to the configured
+				`endpoints`.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: object: options: {
+					source_labels: {
+						description: "The labels whose values are concatenated and matched against `regex`."
+						required:    false
+						type: array: {
+							default: []
+							items: type: string: examples: ["__meta_kubernetes_namespace"]
+						}
+					}
+					separator: {
+						description: "The separator placed between the concatenated values of the source labels."
+						required:    false
+						type: string: default: ";"
+					}
+					target_label: {
+						description: "The label the result is written to, for the `replace` and `hashmod` actions."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["namespace"]
+						}
+					}
+					regex: {
+						description: """
+							The regular expression, anchored at both ends, matched against the concatenated values, or
+							against the label names for the `labelmap`, `labeldrop`, and `labelkeep` actions.
+							"""
+						required: false
+						type: string: default: "(.*)"
+					}
+					modulus: {
+						description: "The modulus of the hash of the concatenated values, for the `hashmod` action."
+						required:    false
+						type: uint: {
+							default: null
+							examples: [4]
+							unit: null
+						}
+					}
+					replacement: {
+						description: "The value written for the `replace` and `labelmap` actions, in which `$1` refers to the first capture group."
+						required:    false
+						type: string: default: "$1"
+					}
+					action: {
+						description: "The action of the rule."
+						required:    false
+						type: string: {
+							default: "replace"
+							enum: {
+								replace:   "Write the replacement to the target label if the values match. The label is removed if the replacement is empty."
+								keep:      "Drop the target if the values don't match."
+								drop:      "Drop the target if the values match."
+								hashmod:   "Write the hash of the values modulo `modulus` to the target label."
+								labelmap:  "Copy the labels whose name matches to the label named after the replacement."
+								labeldrop: "Remove the labels whose name matches."
+								labelkeep: "Remove the labels whose name doesn't match."
+							}
+						}
+					}
+				}
+			}
+		}
 		auth: configuration._http_auth & {_args: {
 			password_example: "${PROMETHEUS_PASSWORD}"
 			username_example: "${PROMETHEUS_USERNAME}"
@@ -178,6 +322,23 @@ components: sources: prometheus_scrape: {
 		}
 	}
 
+	how_it_works: {
+		relabeling: {
+			title: "Targets and relabeling"
+			body: """
+				Every target is described by labels: `__scheme__`, `__address__`, and `__metrics_path__` make up its
+				URL, and the `__param_<name>` labels its query parameters. The discovered targets also have the
+				`__meta_kubernetes_*` labels of Prometheus' Kubernetes service discovery, such as
+				`__meta_kubernetes_namespace`, `__meta_kubernetes_pod_name`, or
+				`__meta_kubernetes_pod_label_<name>`.
+
+				The relabeling rules rewrite these labels, and may drop targets. The labels left which don't start
+				with `__` are added as tags to the scraped metrics, following `honor_labels` on conflicts. The
+				targets are discovered again at every scrape interval.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
 		http_error_response_total:            components.sources.internal_metrics.output.metrics.http_error_response_total
@@ -4,7 +4,7 @@ components: sources: redis: {
 	title: "Redis"
 
 	features: {
-		acknowledgements: false
+		acknowledgements: true
 		collect: {
 			checkpoint: enabled: false
 			tls: enabled:        false
@@ -79,13 +79,14 @@ components: sources: redis: {
 		}
 		data_type: {
 			common:      false
-			description: "The Redis data type (`list` or `channel`) to use."
+			description: "The Redis data type (`list`, `channel` or `stream`) to use."
 			required:    false
 			type: string: {
 				default: "list"
 				enum: {
 					list:    "Use the Redis `list` data type."
 					channel: "Use the Redis `channel` data type."
+					stream:  "Use the Redis `stream` data type, reading entries with a consumer group."
 				}
 				syntax: "literal"
 			}
@@ -114,6 +115,80 @@ components: sources: redis: {
 				}
 			}
 		}
+		stream: {
+			common:      false
+			description: "Options for the Redis `stream` data type."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					group: {
+						common:      true
+						description: "The consumer group to read entries with."
+						required:    false
+						type: string: {
+							default: "vector"
+							syntax:  "literal"
+						}
+					}
+					consumer: {
+						common:      true
+						description: "The name of the consumer within the consumer group. Every instance of Vector reading from the same group needs a distinct name, as the pending entries of a consumer are read again by the consumer of the same name after a restart. Defaults to the hostname."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["vector-1"]
+							syntax: "literal"
+						}
+					}
+					create_group: {
+						common:      false
+						description: "Whether to create the consumer group, and the stream, if they don't exist. Created groups start with the entries added after their creation."
+						required:    false
+						type: bool: default: true
+					}
+					claim_min_idle_secs: {
+						common:      false
+						description: "The minimum time for which entries of other consumers must have been pending to be claimed on start. This takes over the entries of consumers which are gone. By default, no entries are claimed. Requires Redis 6.2 or later."
+						required:    false
+						type: uint: {
+							default: null
+							examples: [3600]
+							unit: "seconds"
+						}
+					}
+					batch_size: {
+						common:      false
+						description: "The maximum number of entries to read at once."
+						required:    false
+						type: uint: {
+							default: 100
+							unit:    null
+						}
+					}
+					field: {
+						common:      false
+						description: "The field of the entries holding the message to decode. Entries without the field are skipped."
+						required:    false
+						type: string: {
+							default: "message"
+							syntax:  "literal"
+						}
+					}
+					id_key: {
+						common:      false
+						description: "The log field name to use for the ID of the stream entry. If not set, the ID is not added to the log event."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["stream_id"]
+							syntax: "literal"
+						}
+					}
+				}
+			}
+		}
 		redis_key: {
 			common:      false
 			description: "The log field name to use for the redis key. If set to an empty string or null, the key is not added to the log event."
@@ -161,6 +236,17 @@ components: sources: redis: {
 				API.
 				"""
 		}
+		streams: {
+			title: "Streams"
+			body:  """
+				With the `stream` data type, entries are read as a consumer of a consumer group. With acknowledgements
+				enabled, entries are acknowledged with `XACK` once their events are delivered, and consumers read their
+				pending entries, i.e. entries which were read but not acknowledged before a restart, before reading new
+				ones. With `claim_min_idle_secs`, the pending entries of other consumers are claimed as well, which takes
+				over the entries of consumers that are gone. Without acknowledgements, entries are read with `NOACK`,
+				and are lost if Vector stops before delivering them.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
@@ -0,0 +1,206 @@
+package metadata
+
+components: sources: sftp: {
+	title: "SFTP"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		codecs: {
+			enabled:         true
+			default_framing: "`newline_delimited` for codecs other than `native`, which defaults to `length_delimited`"
+		}
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: {
+					name:     "SFTP or FTP"
+					thing:    "a directory of an SFTP or FTP server"
+					url:      urls.sftp
+					versions: null
+				}
+
+				interface: socket: {
+					direction: "outgoing"
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		endpoint: {
+			description: """
+				The URL of the directory to poll. SFTP is used with an `sftp://` URL, FTP with
+				explicit TLS with an `ftps://` URL, and FTP without TLS with an `ftp://` URL.
+				"""
+			required: true
+			type: string: {
+				examples: ["sftp://files.example.com/incoming", "ftps://files.example.com:2121/logs"]
+			}
+		}
+		username: {
+			description: "The username to log in with."
+			required:    true
+			type: string: {
+				examples: ["vector"]
+			}
+		}
+		password: {
+			common:      true
+			description: "The password to log in with. With SFTP, it's not used if `private_key_path` is set."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["${SFTP_PASSWORD}"]
+			}
+		}
+		private_key_path: {
+			common:      true
+			description: "The path of the private key to log in with, for SFTP."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/etc/vector/id_ed25519"]
+			}
+		}
+		private_key_passphrase: {
+			common:      false
+			description: "The passphrase of the private key."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["${SFTP_KEY_PASSPHRASE}"]
+			}
+		}
+		host_key_fingerprint: {
+			common:      true
+			description: """
+				The SHA256 fingerprint the host key of the SFTP server is expected to have, formatted
+				like OpenSSH does. If not set, the host key isn't verified.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["SHA256:q6urq6urq6urq6urq6urq6urq6urq6urq6urq6urq6s"]
+			}
+		}
+		include: {
+			common:      true
+			description: "Glob patterns of the file names to read. The files of subdirectories aren't read."
+			required:    false
+			type: array: {
+				default: ["*"]
+				items: type: string: {
+					examples: ["*.log", "access-*.json"]
+				}
+			}
+		}
+		exclude: {
+			common:      false
+			description: "Glob patterns of the file names not to read, even if they are included."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["*.tmp", ".*"]
+				}
+			}
+		}
+		interval_secs: {
+			common:      true
+			description: "The interval between listings of the directory."
+			required:    false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+		timeout_secs: {
+			common:      false
+			description: "The timeout of connecting to the server, and of each request."
+			required:    false
+			type: uint: {
+				default: 30
+				unit:    "seconds"
+			}
+		}
+	}
+
+	output: logs: line: {
+		description: "An individual event from a file."
+		fields: {
+			message: {
+				description: "The decoded message of the event."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			file: {
+				description: "The path of the file the event was read from."
+				required:    true
+				type: string: {
+					examples: ["/incoming/access-2022-10-20.log"]
+				}
+			}
+			host: {
+				description: "The host of the server the file was read from."
+				required:    true
+				type: string: {
+					examples: ["files.example.com"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["sftp"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		processed_files: {
+			title: "Processed files"
+			body: """
+				The names, sizes and modification times of the files read are kept in Vector's data
+				directory, so that files aren't read again, also once restarted. A file is only read
+				once it has the same size and modification time in two listings, so that files that
+				are still uploaded aren't read. Files that change are read again as a whole. With
+				acknowledgements enabled, a file is only marked as read once its events are
+				delivered.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -0,0 +1,260 @@
+package metadata
+
+components: sources: snmp_trap: {
+	_port: 162
+
+	title: "SNMP Trap"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.snmp
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			keepalive: enabled:            false
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: {
+			common:      true
+			description: "The address to listen for traps on, or `systemd#N` to use the Nth socket passed by systemd socket activation. If an address is used it _must_ include a port."
+			required:    false
+			type: string: {
+				default: "0.0.0.0:\(_port)"
+				examples: ["0.0.0.0:\(_port)", "systemd#3"]
+			}
+		}
+		communities: {
+			common:      true
+			description: "The communities to accept SNMPv2c traps of. Traps of any community are accepted, if empty."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["public"]
+				}
+			}
+		}
+		users: {
+			common:      false
+			description: "The users to accept SNMPv3 traps of. SNMPv3 traps are only accepted of configured users."
+			required:    false
+			type: array: {
+				default: []
+				items: type: object: options: {
+					name: {
+						description: "The name of the user."
+						required:    true
+						type: string: {
+							examples: ["vector"]
+						}
+					}
+					auth_protocol: {
+						description: "The protocol to authenticate messages with."
+						required:    false
+						type: string: {
+							default: null
+							enum: {
+								md5: "HMAC-MD5-96."
+								sha: "HMAC-SHA-96."
+							}
+						}
+					}
+					auth_password: {
+						description: "The authentication password."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["${SNMP_AUTH_PASSWORD}"]
+						}
+					}
+					privacy_protocol: {
+						description: "The protocol to encrypt messages with. Privacy requires authentication."
+						required:    false
+						type: string: {
+							default: null
+							enum: {
+								des: "CBC-DES."
+								aes: "CFB128-AES-128."
+							}
+						}
+					}
+					privacy_password: {
+						description: "The privacy password."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["${SNMP_PRIVACY_PASSWORD}"]
+						}
+					}
+				}
+			}
+		}
+		mib_paths: {
+			common:      false
+			description: "MIB module files, or directories of MIB module files, to name object identifiers with. The roots of the common MIB modules, e.g. `SNMPv2-SMI::enterprises`, as well as the standard traps are always known."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["/usr/share/snmp/mibs"]
+				}
+			}
+		}
+	}
+
+	output: logs: trap: {
+		description: "An individual trap or inform."
+		fields: {
+			version: {
+				description: "The SNMP version of the message."
+				required:    true
+				type: string: {
+					enum: {
+						"2c": "SNMPv2c."
+						"3":  "SNMPv3."
+					}
+				}
+			}
+			community: {
+				description: "The community of an SNMPv2c message."
+				required:    false
+				type: string: {
+					examples: ["public"]
+				}
+			}
+			user: {
+				description: "The user of an SNMPv3 message."
+				required:    false
+				type: string: {
+					examples: ["vector"]
+				}
+			}
+			engine_id: {
+				description: "The hex encoded identifier of the engine, which sent an SNMPv3 message."
+				required:    false
+				type: string: {
+					examples: ["80001f888056b7d4e1c0a2d863"]
+				}
+			}
+			pdu_type: {
+				description: "The type of the PDU."
+				required:    true
+				type: string: {
+					enum: {
+						trap:   "An SNMPv2 trap."
+						inform: "An inform request."
+					}
+				}
+			}
+			request_id: {
+				description: "The request identifier of the PDU."
+				required:    true
+				type: uint: {
+					examples: [42]
+					unit: null
+				}
+			}
+			uptime: {
+				description: "The uptime of the sender, in hundredths of a second, i.e. the value of `sysUpTime.0`."
+				required:    false
+				type: uint: {
+					examples: [1234]
+					unit: null
+				}
+			}
+			trap_oid: {
+				description: "The object identifier of the trap, i.e. the value of `snmpTrapOID.0`."
+				required:    false
+				type: string: {
+					examples: ["1.3.6.1.6.3.1.1.5.3"]
+				}
+			}
+			trap: {
+				description: "The name of the trap, resolved with the MIB modules."
+				required:    false
+				type: string: {
+					examples: ["IF-MIB::linkDown"]
+				}
+			}
+			varbinds: {
+				description: "The variable bindings of the PDU, keyed by the names of their object identifiers. Unknown object identifiers are kept numerically."
+				required:    true
+				type: object: {
+					examples: [{"SNMPv2-MIB::sysUpTime.0": 1234, "IF-MIB::ifIndex.3": 3}]
+					options: {}
+				}
+			}
+			host: {
+				description: "The IP address of the sender."
+				required:    true
+				type: string: {
+					examples: ["192.0.2.1"]
+				}
+			}
+			timestamp: fields._current_timestamp
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["snmp_trap"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		informs: {
+			title: "Informs"
+			body: """
+				SNMPv2c informs are answered with a response as soon as they are received. SNMPv3 informs require the
+				receiver to be the authoritative engine, which isn't supported, so that they are received but not
+				answered.
+				"""
+		}
+		mibs: {
+			title: "MIB modules"
+			body: """
+				Only the object identifier assignments of the MIB modules are read, and object identifiers are named
+				after their longest known prefix, e.g. `IF-MIB::ifIndex.3`. Values aren't converted with the textual
+				conventions of the modules.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -68,15 +68,16 @@ components: sources: statsd: {
 			required:    true
 			type: string: {
 				enum: {
-					tcp:  "TCP Socket."
-					udp:  "UDP Socket."
-					unix: "Unix Domain Socket."
+					tcp:           "TCP Socket."
+					udp:           "UDP Socket."
+					unix:          "Unix Domain Socket."
+					unix_datagram: "Unix Domain Socket in datagram mode, as used by DogStatsD clients."
 				}
 			}
 		}
 		path: {
 			description:   "The unix socket path. *This should be an absolute path*."
-			relevant_when: "mode = `unix`"
+			relevant_when: "mode = `unix` or `unix_datagram`"
 			required:      true
 			type: string: {
 				examples: ["/path/to/socket"]
@@ -112,6 +113,82 @@ components: sources: statsd: {
 		set:          output._passthrough_set
 	}
 
+	output: logs: event: {
+		description: "A DogStatsD event."
+		fields: {
+			message: {
+				description: "The text of the event."
+				required:    true
+				type: string: {
+					examples: ["An error occurred while processing the request."]
+				}
+			}
+			title: {
+				description: "The title of the event."
+				required:    true
+				type: string: {
+					examples: ["Request failed"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time of the event, or the time it was received at if it has none."
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["statsd"]
+				}
+			}
+			host: {
+				description: "The hostname of the event."
+				required:    false
+				type: string: {
+					examples: ["web-1"]
+					default: null
+				}
+			}
+			priority: {
+				description: "The priority of the event."
+				required:    true
+				type: string: {
+					examples: ["normal", "low"]
+				}
+			}
+			alert_type: {
+				description: "The alert type of the event."
+				required:    true
+				type: string: {
+					examples: ["info", "error", "warning", "success"]
+				}
+			}
+			source_type_name: {
+				description: "The source type name of the event."
+				required:    false
+				type: string: {
+					examples: ["nagios"]
+					default: null
+				}
+			}
+			aggregation_key: {
+				description: "The key the event is aggregated with."
+				required:    false
+				type: string: {
+					examples: ["request-failures"]
+					default: null
+				}
+			}
+			tags: {
+				description: "The tags of the event."
+				required:    false
+				type: object: {
+					examples: [{"env": "prod"}]
+					options: {}
+				}
+			}
+		}
+	}
+
 	how_it_works: {
 		timestamps: {
 			title: "Timestamps"
@@ -122,6 +199,29 @@ components: sources: statsd: {
 				`null` timestamps are substituted with the current time by downstream sinks or
 				third-party services during sending/ingestion. See the
 				[metric data model](\(urls.vector_metric)) page for more info.
+
+				Metrics carrying the DogStatsD `T<unix timestamp>` component are assigned that
+				timestamp instead.
+				"""
+		}
+		dogstatsd: {
+			title: "DogStatsD extensions"
+			body:  """
+				The DogStatsD extensions of the protocol are supported:
+
+				- Tags (`|#key:value,other`), sample rates (`|@0.5`), container IDs (`|c:<id>`)
+				  and timestamps (`|T<unix timestamp>`) may follow the metric type in any order.
+				  Container IDs are added as the `container_id` tag.
+				- Several values may be packed in one metric, such as `latency:12:18:20|d`. Each
+				  value becomes its own metric.
+				- Distributions (`d`) are parsed into distributions with summary statistics.
+				- Events (`_e{...}`) are emitted as logs, with the fields described above.
+				- Service checks (`_sc|...`) are emitted as absolute gauges named after the
+				  check, whose value is the status, from `0` for OK to `3` for unknown. The
+				  hostname and the message are added as the `host` and `message` tags.
+
+				Sample rates scale the value of counters and the sample count of distributions,
+				and are accepted but have no effect on gauges and sets.
 				"""
 		}
 	}
@@ -69,6 +69,27 @@ components: sources: syslog: {
 				default: "host"
 			}
 		}
+		raw_message_key: {
+			common:      false
+			description: "The name of the log field to keep the raw syslog frame in, as it was received. By default, the raw frame isn't kept."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["raw_message"]
+			}
+		}
+		structured_data_key: {
+			common: false
+			description: """
+				The name of the log field to nest the elements of the RFC 5424 structured data under, by SD-ID.
+				By default, each element is inserted at the root of the event, by SD-ID.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["structured_data"]
+			}
+		}
 		max_length: {
 			common:      true
 			description: "The maximum buffer size of incoming messages. Messages larger than this are truncated."
@@ -192,7 +213,7 @@ components: sources: syslog: {
 			}
 			client_metadata: fields._client_metadata
 			"*": {
-				description: "In addition to the defined fields, any [Syslog 5424 structured fields](https://datatracker.ietf.org/doc/html/rfc5424#section-6.3) are parsed and inserted, namespaced under the name of each structured data section."
+				description: "In addition to the defined fields, any [Syslog 5424 structured fields](https://datatracker.ietf.org/doc/html/rfc5424#section-6.3) are parsed and inserted, namespaced under the name of each structured data section, or under [`structured_data_key`](#structured_data_key) if set. The parameters repeated within a section are kept as arrays of values, and the sections repeated with the same name are merged."
 				required:    true
 				type: string: {
 					examples: ["hello world"]
@@ -0,0 +1,145 @@
+package metadata
+
+components: sources: websocket: {
+	title: "WebSocket"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["daemon", "sidecar", "aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		codecs: {
+			enabled:         true
+			default_framing: "`bytes`"
+		}
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.websocket
+
+				interface: socket: {
+					direction: "outgoing"
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		auth: configuration._http_auth & {_args: {
+			password_example: "${WEBSOCKET_PASSWORD}"
+			username_example: "${WEBSOCKET_USERNAME}"
+		}}
+		uri: {
+			description: """
+				The WebSocket URI to connect to. This should include the protocol and host,
+				but can also include the port, path, and any other valid part of a URI.
+				"""
+			required: true
+			type: string: {
+				examples: ["wss://stream.example.com/v1/feed"]
+				syntax: "literal"
+			}
+		}
+		subscribe_message: {
+			common: true
+			description: """
+				A text message to send once connected, such as a request to subscribe to a
+				stream. The message is sent again after reconnecting.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: [#"{"type": "subscribe", "channels": ["trades"]}"#]
+			}
+		}
+		ping_interval: {
+			common:      true
+			description: "Send WebSocket pings each this number of seconds."
+			required:    false
+			type: uint: {
+				default: null
+				unit:    "seconds"
+			}
+		}
+		ping_timeout: {
+			common:        true
+			description:   "Try to reconnect to the WebSocket server if pong not received for this number of seconds."
+			relevant_when: "ping_interval is set"
+			required:      false
+			warnings: ["This parameter is not taken into account if ping_interval is not set"]
+			type: uint: {
+				default: null
+				unit:    "seconds"
+			}
+		}
+	}
+
+	output: logs: message: {
+		description: "An event decoded from a WebSocket message."
+		fields: {
+			message: {
+				description: "The raw text or binary message."
+				required:    true
+				type: string: {
+					examples: [#"{"price": 27000.5, "size": 0.01}"#]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["websocket"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		reconnects: {
+			title: "Reconnects"
+			body:  """
+				Each text or binary message is decoded into events. When the connection is
+				closed, fails, or a pong isn't received in time, Vector reconnects with an
+				exponential backoff, and sends the `subscribe_message` again. Messages sent
+				by the server while disconnected are not received.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		open_connections:                     components.sources.internal_metrics.output.metrics.open_connections
+		connection_established_total:         components.sources.internal_metrics.output.metrics.connection_established_total
+		connection_shutdown_total:            components.sources.internal_metrics.output.metrics.connection_shutdown_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -0,0 +1,314 @@
+package metadata
+
+components: sources: windows_eventlog: {
+	title: "Windows Event Log"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.windows_eventlog
+
+				interface: ffi: {}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      false
+			"aarch64-unknown-linux-musl":     false
+			"armv7-unknown-linux-gnueabihf":  false
+			"armv7-unknown-linux-musleabihf": false
+			"x86_64-apple-darwin":            false
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       false
+			"x86_64-unknown-linux-musl":      false
+		}
+
+		requirements: [
+			"""
+				Reading the `Security` channel requires Vector to run as a user with the
+				`Manage auditing and security log` right, such as a member of the
+				`Administrators` group.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		batch_size: {
+			common:      false
+			description: "The maximum number of events to read at once. A bookmark is set at the end of each batch."
+			required:    false
+			type: uint: {
+				default: 100
+				unit:    "events"
+			}
+		}
+		channels: {
+			common:      true
+			description: "The channels to read events from."
+			required:    true
+			type: array: items: type: string: {
+				examples: ["Application", "System", "Security", "Microsoft-Windows-Sysmon/Operational"]
+			}
+		}
+		include_xml: {
+			common:      false
+			description: "Whether to add the XML of events to the `xml` field."
+			required:    false
+			type: bool: default: false
+		}
+		query: {
+			common:      false
+			description: "The [XPath query](\(urls.windows_event_log_queries)) selecting the events to read from each channel."
+			required:    false
+			type: string: {
+				default: "*"
+				examples: ["*[System[Level<=3]]", "*[System[(EventID=4624 or EventID=4625)]]"]
+			}
+		}
+		read_existing_events: {
+			common:      true
+			description: "Whether to read the events already in the channels, when the source is first started. Later on, events are read after the bookmark of the last event read."
+			required:    false
+			type: bool: default: false
+		}
+		render_message: {
+			common:      false
+			description: "Whether to render the message of events, with the message resources of their provider."
+			required:    false
+			type: bool: default: true
+		}
+	}
+
+	output: logs: {
+		event: {
+			description: "A Windows event"
+			fields: {
+				activity_id: {
+					description: "The activity the event belongs to."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["{2d4e7b70-3a45-0003-a47b-4e2d453ad801}"]
+					}
+				}
+				channel: {
+					description: "The channel the event was read from."
+					required:    true
+					type: string: {
+						examples: ["System"]
+					}
+				}
+				computer: {
+					description: "The name of the computer the event was logged on."
+					required:    true
+					type: string: {
+						examples: ["WIN-HOST"]
+					}
+				}
+				event_data: {
+					description: "The data of the event, by name. Unnamed data is numbered like the insertion strings of the message, from `param1` on."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"param1": "Windows Update", "param2": "running"}]
+						options: {}
+					}
+				}
+				event_id: {
+					description: "The identifier of the event, defined by its provider."
+					required:    true
+					type: uint: {
+						examples: [7036]
+						unit: null
+					}
+				}
+				keywords: {
+					description: "The keywords of the event, as a hexadecimal mask."
+					required:    true
+					type: string: {
+						examples: ["0x8080000000000000"]
+					}
+				}
+				level: {
+					description: "The name of the level of the event."
+					required:    true
+					type: string: {
+						examples: ["Critical", "Error", "Warning", "Information", "Verbose"]
+					}
+				}
+				level_value: {
+					description: "The value of the level of the event."
+					required:    true
+					type: uint: {
+						examples: [4]
+						unit: null
+					}
+				}
+				message: {
+					description: "The message of the event, rendered with the message resources of its provider. It's missing if the provider has no message resources, or `render_message` is disabled."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["The Windows Update service entered the running state."]
+					}
+				}
+				opcode: {
+					description: "The opcode of the event."
+					required:    false
+					common:      false
+					type: uint: {
+						default: null
+						examples: [0]
+						unit: null
+					}
+				}
+				process_id: {
+					description: "The identifier of the process that logged the event."
+					required:    false
+					common:      false
+					type: uint: {
+						default: null
+						examples: [636]
+						unit: null
+					}
+				}
+				provider_name: {
+					description: "The name of the provider that logged the event."
+					required:    true
+					type: string: {
+						examples: ["Service Control Manager"]
+					}
+				}
+				record_id: {
+					description: "The number of the event in its channel."
+					required:    true
+					type: uint: {
+						examples: [12345]
+						unit: null
+					}
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: {
+						examples: ["windows_eventlog"]
+					}
+				}
+				task: {
+					description: "The task of the event, defined by its provider."
+					required:    false
+					common:      false
+					type: uint: {
+						default: null
+						examples: [0]
+						unit: null
+					}
+				}
+				thread_id: {
+					description: "The identifier of the thread that logged the event."
+					required:    false
+					common:      false
+					type: uint: {
+						default: null
+						examples: [7080]
+						unit: null
+					}
+				}
+				timestamp: {
+					description: "The time the event was logged."
+					required:    true
+					type: timestamp: {}
+				}
+				user_data: {
+					description: "The data of events defining their own XML structure, instead of named data."
+					required:    false
+					common:      false
+					type: object: {
+						examples: [{"SubjectUserName": "admin"}]
+						options: {}
+					}
+				}
+				user_id: {
+					description: "The security identifier of the user the event was logged for."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["S-1-5-18"]
+					}
+				}
+				version: {
+					description: "The version of the event definition."
+					required:    false
+					common:      false
+					type: uint: {
+						default: null
+						examples: [0]
+						unit: null
+					}
+				}
+				xml: {
+					description: "The XML of the event, if `include_xml` is enabled."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["<Event xmlns=\"http://schemas.microsoft.com/win/2004/08/events/event\">...</Event>"]
+					}
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		bookmarks: {
+			title: "Bookmarks"
+			body:  """
+				Vector keeps a bookmark of the last event read from the channels in its
+				data directory, and continues after it once restarted. With
+				acknowledgements enabled, the bookmark only moves past events once they
+				are delivered. The `read_existing_events` option only applies when there
+				is no bookmark yet.
+				"""
+		}
+		queries: {
+			title: "Queries"
+			body:  """
+				The `query` option is applied to each of the `channels`, which are
+				subscribed to as a single structured query. It uses the same
+				[XPath syntax](\(urls.windows_event_log_queries)) as the filters of the
+				Event Viewer, so that a filter can be copied from its XML tab.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
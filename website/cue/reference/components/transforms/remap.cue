@@ -226,6 +226,10 @@ components: transforms: "remap": {
 	]
 
 	telemetry: metrics: {
-		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
+		grok_no_match_total:             components.sources.internal_metrics.output.metrics.grok_no_match_total
+		grok_rule_attempts_total:        components.sources.internal_metrics.output.metrics.grok_rule_attempts_total
+		grok_rule_average_match_seconds: components.sources.internal_metrics.output.metrics.grok_rule_average_match_seconds
+		grok_rule_matches_total:         components.sources.internal_metrics.output.metrics.grok_rule_matches_total
+		processing_errors_total:         components.sources.internal_metrics.output.metrics.processing_errors_total
 	}
 }
@@ -63,7 +63,7 @@ remap: {
 		This function returns the rows that match the provided condition(s). _All_ fields need to
 		match for rows to be returned; if any fields don't match, no rows are returned.
 
-		There are currently two forms of search criteria:
+		There are currently five forms of search criteria:
 
 		1. **Exact match search**. The given field must match the value exactly. Case sensitivity
 		   can be specified using the `case_sensitive` argument. An exact match search can use an
@@ -77,6 +77,19 @@ remap: {
 		   match criteria. We recommend using date ranges as the _only_ criteria when the enrichment
 		   data set is very small.
 
+		3. **Prefix search**. The given field must start with the `prefix`, for example
+		   `{"host": {"prefix": "www."}}`.
+
+		4. **Wildcard search**. The given field must match the `wildcard` pattern, in which `*`
+		   matches any number of characters and `?` matches a single character, for example
+		   `{"host": {"wildcard": "*.example.com"}}`.
+
+		5. **CIDR search**. The given field must be a CIDR block, or a single IP address, containing
+		   the IP address `contains_ip`, for example `{"subnet": {"contains_ip": .client_ip}}`.
+
+		Prefix and wildcard searches respect the `case_sensitive` argument. Like date range searches,
+		they sequentially scan through the rows located via any exact match criteria.
+
 		For `geoip` enrichment tables this condition needs to be a VRL object with a single key-value pair
 		whose value needs to be a valid IP address. Example: `{"ip": .ip }`. If a return field is expected
 		and without a value, `null` will be used. This table can return following fields:
@@ -0,0 +1,48 @@
+package metadata
+
+remap: functions: decode_zstd: {
+	category:    "Codec"
+	description: """
+		Decompresses the `value` (a [Zstandard](\(urls.zstd)) compressed string) into its original string.
+		Decompression stops at `max_size`, so that maliciously crafted data can't exhaust memory.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The [Zstandard](\(urls.zstd)) data to decompress."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "max_size"
+			description: "The maximum size of the decompressed string in bytes."
+			required:    false
+			type: ["integer"]
+			default: 10485760
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't valid Zstandard compressed data.",
+		"The decompressed string exceeds `max_size`.",
+		"`max_size` isn't positive.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decompress Zstandard data"
+			source: """
+				decode_zstd!(encode_zstd("please decode me"))
+				"""
+			return: "please decode me"
+		},
+		{
+			title: "Limit the decompressed size"
+			source: """
+				decode_zstd(encode_zstd("please decode me"), max_size: 8) ?? "too large"
+				"""
+			return: "too large"
+		},
+	]
+}
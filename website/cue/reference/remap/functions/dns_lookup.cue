@@ -0,0 +1,49 @@
+package metadata
+
+remap: functions: dns_lookup: {
+	category:    "IP"
+	description: """
+		Resolves the host name `value` to its IP addresses, using the system resolver.
+
+		Results, including failed lookups, are cached by the process for `cache_ttl_secs`, so that
+		the same value isn't resolved again for every event. A lookup taking longer than `timeout_ms`
+		fails; timed out lookups aren't cached.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The host name to resolve."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "timeout_ms"
+			description: "The maximum time in milliseconds to wait for the resolver."
+			required:    false
+			default:     1000
+			type: ["integer"]
+		},
+		{
+			name:        "cache_ttl_secs"
+			description: "How long in seconds results are cached. `0` disables caching."
+			required:    false
+			default:     60
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` can't be resolved, or the lookup timed out",
+	]
+	return: types: ["array"]
+
+	examples: [
+		{
+			title: "Resolve a host name"
+			source: #"""
+				dns_lookup!("localhost", timeout_ms: 500)
+				"""#
+			return: ["127.0.0.1"]
+		},
+	]
+}
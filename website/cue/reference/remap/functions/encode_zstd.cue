@@ -0,0 +1,38 @@
+package metadata
+
+remap: functions: encode_zstd: {
+	category:    "Codec"
+	description: """
+		Compresses the `value` with [Zstandard](\(urls.zstd)).
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to compress."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "compression_level"
+			description: "The compression level, from `1` (fastest) to `22` (smallest). Negative levels trade compression for even more speed."
+			required:    false
+			type: ["integer"]
+			default: 3
+		},
+	]
+	internal_failure_reasons: [
+		"`compression_level` isn't a valid Zstandard compression level.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Compress and decompress data"
+			source: """
+				decode_zstd!(encode_zstd("please encode me", compression_level: 19))
+				"""
+			return: "please encode me"
+		},
+	]
+}
@@ -21,17 +21,50 @@ remap: functions: format_number: {
 		},
 		{
 			name:        "decimal_separator"
-			description: "The character to use between the whole and decimal parts of the number."
+			description: "The character to use between the whole and decimal parts of the number. Defaults to the one of the `locale`."
 			required:    false
 			type: ["string"]
 			default: "."
 		},
 		{
 			name:        "grouping_separator"
-			description: "The character to use between each thousands part of the number."
+			description: "The character to use between each thousands part of the number. Defaults to the one of the `locale`, or no grouping without a `locale`."
 			required:    false
 			type: ["string"]
-			default: ","
+		},
+		{
+			name:        "locale"
+			description: "The locale whose decimal and grouping separators are used, unless given explicitly."
+			required:    false
+			type: ["string"]
+			enum: {
+				de:    "German, e.g. `1.234,5`"
+				de_CH: "Swiss German, e.g. `1’234.5`"
+				en:    "English, e.g. `1,234.5`"
+				es:    "Spanish, e.g. `1.234,5`"
+				fr:    "French, e.g. `1 234,5` (with a narrow no-break space)"
+				it:    "Italian, e.g. `1.234,5`"
+				ja:    "Japanese, e.g. `1,234.5`"
+				nl:    "Dutch, e.g. `1.234,5`"
+				pl:    "Polish, e.g. `1 234,5` (with a no-break space)"
+				pt:    "Portuguese, e.g. `1.234,5`"
+				ru:    "Russian, e.g. `1 234,5` (with a no-break space)"
+				sv:    "Swedish, e.g. `1 234,5` (with a no-break space)"
+				zh:    "Chinese, e.g. `1,234.5`"
+			}
+		},
+		{
+			name:        "units"
+			description: """
+				Scales the `value`, a number of bytes, to the largest unit that keeps it at least 1 and appends the unit,
+				e.g. `1.50 MiB`. Scaled values are formatted with 2 decimal places, unless `scale` is given.
+				"""
+			required: false
+			type: ["string"]
+			enum: {
+				binary:  "Units of 1024 bytes: `KiB`, `MiB`, `GiB`, `TiB`, `PiB` and `EiB`"
+				decimal: "Units of 1000 bytes: `kB`, `MB`, `GB`, `TB`, `PB` and `EB`"
+			}
 		},
 	]
 	internal_failure_reasons: []
@@ -45,5 +78,19 @@ remap: functions: format_number: {
 				"""#
 			return: "1,234,567.890"
 		},
+		{
+			title: "Format a number for a locale"
+			source: #"""
+				format_number(1234567.89, 2, locale: "de")
+				"""#
+			return: "1.234.567,89"
+		},
+		{
+			title: "Format a number of bytes"
+			source: #"""
+				format_number(1572864, units: "binary")
+				"""#
+			return: "1.50 MiB"
+		},
 	]
 }
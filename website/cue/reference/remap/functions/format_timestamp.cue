@@ -19,6 +19,22 @@ remap: functions: format_timestamp: {
 			required:    true
 			type: ["string"]
 		},
+		{
+			name:        "locale"
+			description: "The locale of the month and weekday names. Defaults to English."
+			required:    false
+			type: ["string"]
+			enum: {
+				de: "German, e.g. `Mär`"
+				en: "English, e.g. `Mar`"
+				es: "Spanish, e.g. `mar`"
+				fr: "French, e.g. `mars`"
+				it: "Italian, e.g. `mar`"
+				nl: "Dutch, e.g. `mrt`"
+				pt: "Portuguese, e.g. `mar`"
+				sv: "Swedish, e.g. `mar`"
+			}
+		},
 	]
 	internal_failure_reasons: []
 	return: types: ["string"]
@@ -38,5 +54,12 @@ remap: functions: format_timestamp: {
 				"""#
 			return: "21-Oct-2020 16:00"
 		},
+		{
+			title: "Format a timestamp with localized names"
+			source: #"""
+				format_timestamp!(t'2020-10-21T16:00:00Z', format: "%A %d %B %Y", locale: "de")
+				"""#
+			return: "Mittwoch 21 Oktober 2020"
+		},
 	]
 }
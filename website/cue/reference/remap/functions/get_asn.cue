@@ -0,0 +1,48 @@
+package metadata
+
+remap: functions: get_asn: {
+	category:    "Enrichment"
+	description: """
+		Looks up an IP address in a [GeoIP enrichment table](\(urls.enrichment_tables_concept)) with
+		an ASN or ISP database, returning the autonomous system of the IP address.
+
+		\(remap._enrichment_table_explainer)
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The IP address to look up."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name: "table"
+			description: """
+				The GeoIP [enrichment table](\(urls.enrichment_tables_concept)) to search. Since the
+				table is used at boot time to create indices into the data, it must be statically
+				defined.
+				"""
+			required: false
+			type: ["string"]
+			default: "asn"
+		},
+	]
+	internal_failure_reasons: [
+		"The IP address isn't found in the table",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Look up an IP address"
+			source: #"""
+				get_asn!("2600:7000::1")
+				"""#
+			return: {
+				"autonomous_system_number":       6939
+				"autonomous_system_organization": "Hurricane Electric, Inc."
+			}
+		},
+	]
+}
@@ -0,0 +1,58 @@
+package metadata
+
+remap: functions: get_geoip: {
+	category:    "Enrichment"
+	description: """
+		Looks up an IP address in a [GeoIP enrichment table](\(urls.enrichment_tables_concept)),
+		returning the location of the IP address. This allows enriching events with GeoIP data
+		inside conditionals, for example only for IP addresses that aren't private.
+
+		\(remap._enrichment_table_explainer)
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The IP address to look up."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name: "table"
+			description: """
+				The GeoIP [enrichment table](\(urls.enrichment_tables_concept)) to search. Since the
+				table is used at boot time to create indices into the data, it must be statically
+				defined.
+				"""
+			required: false
+			type: ["string"]
+			default: "geoip"
+		},
+	]
+	internal_failure_reasons: [
+		"The IP address isn't found in the table",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Look up an IP address"
+			source: #"""
+				get_geoip!("2.125.160.216")
+				"""#
+			return: {
+				"city_name":      "Boxford"
+				"continent_code": "EU"
+				"country_code":   "GB"
+				"country_name":   "United Kingdom"
+				"latitude":       51.75
+				"longitude":      -1.25
+				"metro_code":     null
+				"postal_code":    "OX1"
+				"region_code":    "WBK"
+				"region_name":    "West Berkshire"
+				"timezone":       "Europe/London"
+			}
+		},
+	]
+}
@@ -0,0 +1,66 @@
+package metadata
+
+remap: functions: hmac: {
+	category:    "Cryptography"
+	description: """
+		Calculates an [HMAC](\(urls.hmac)) of the `value` with a secret `key`, e.g. to sign events or to
+		pseudonymize personal data with a keyed hash.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to calculate the HMAC for."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "key"
+			description: "The secret key."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "algorithm"
+			description: "The hash algorithm to use."
+			enum: {
+				sha1:   "[SHA-1](\(urls.sha1)) algorithm"
+				sha256: "[SHA-256](\(urls.sha2)) algorithm"
+				sha512: "[SHA-512](\(urls.sha2)) algorithm"
+			}
+			required: false
+			default:  "sha256"
+			type: ["string"]
+		},
+		{
+			name:        "encoding"
+			description: "How to encode the calculated HMAC."
+			enum: {
+				hex: "A lowercase hex string"
+				raw: "The raw bytes, e.g. to encode them with `encode_base64`"
+			}
+			required: false
+			default:  "hex"
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Calculate a HMAC"
+			source: #"""
+				hmac("foo", "secret")
+				"""#
+			return: "773ba44693c7553d6ee20f61ea5d2757a9a4f4a44d2841ae4e95b52e4cd62db4"
+		},
+		{
+			title: "Calculate a base64 encoded HMAC"
+			source: #"""
+				encode_base64(hmac("foo", "secret", algorithm: "sha256", encoding: "raw"))
+				"""#
+			return: "dzukRpPHVT1u4g9h6l0nV6mk9KRNKEGuTpW1LkzWLbQ="
+		},
+	]
+}
@@ -0,0 +1,80 @@
+package metadata
+
+remap: functions: parse_apache_error_log: {
+	category:    "Parse"
+	description: """
+		Parses Apache [error log](\(urls.apache_error)) lines, extracting the module, the severity, the process and
+		thread IDs, the client, the OS error, the error code (for example `AH00957`) and the message into separate
+		fields.
+		"""
+	notices: [
+		"""
+			Missing information in the log message may be indicated by `-`. These fields are omitted in the result.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "timestamp_format"
+			description: """
+				The [date/time format](\(urls.chrono_time_formats)) to use for encoding the timestamp. The time is parsed
+				in local time if the timestamp doesn't specify a timezone. By default, the default Apache format
+				`%a %b %d %H:%M:%S%.f %Y` is tried first, followed by `%d/%b/%Y:%T %z`.
+				"""
+			required: false
+			type: ["string"]
+		},
+	]
+
+	internal_failure_reasons: [
+		"`value` isn't an Apache error log line",
+		"`timestamp_format` isn't a valid format string",
+		"The timestamp in `value` fails to parse using the provided `timestamp_format`",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse an Apache error log line"
+			source: #"""
+				parse_apache_error_log!(
+				    s'[Wed Oct 11 14:32:52.123456 2000] [core:error] [pid 35708:tid 4328636416] [client 72.15.99.187:55820] File does not exist: /usr/local/apache2/htdocs/favicon.ico'
+				)
+				"""#
+			return: {
+				timestamp: "2000-10-11T14:32:52.123456Z"
+				module:    "core"
+				severity:  "error"
+				pid:       35708
+				thread:    "4328636416"
+				client:    "72.15.99.187"
+				port:      55820
+				message:   "File does not exist: /usr/local/apache2/htdocs/favicon.ico"
+			}
+		},
+		{
+			title: "Parse an Apache error log line with an error code"
+			source: #"""
+				parse_apache_error_log!(
+				    s'[Mon Mar 01 12:00:19 2021] [proxy:error] [pid 23964] (113)No route to host: AH00957: HTTP: attempt to connect to 10.1.0.244:9000 (hostname.domain.com) failed'
+				)
+				"""#
+			return: {
+				timestamp: "2021-03-01T12:00:19Z"
+				module:    "proxy"
+				severity:  "error"
+				pid:       23964
+				errno:     113
+				error:     "No route to host"
+				code:      "AH00957"
+				message:   "HTTP: attempt to connect to 10.1.0.244:9000 (hostname.domain.com) failed"
+			}
+		},
+	]
+}
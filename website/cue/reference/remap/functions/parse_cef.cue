@@ -18,6 +18,16 @@ remap: functions: parse_cef: {
 			required:    true
 			type: ["string"]
 		},
+		{
+			name:        "translate_custom_fields"
+			description: """
+				Toggles translation of custom field pairs to `key:value`, e.g. `cs1=alice cs1Label=user` becomes `user:alice`.
+				Custom fields without labels are kept as is.
+				"""
+			required: false
+			default:  false
+			type: ["boolean"]
+		},
 	]
 	internal_failure_reasons: [
 		"`value` isn't a properly formatted CEF string",
@@ -4,12 +4,16 @@ remap: functions: parse_duration: {
 	category: "Parse"
 	description: """
 		Parses the `value` into a human-readable duration format specified by `unit`.
+
+		The `value` can combine several values with units, e.g. `1h30m15s` or `1h 30m`, or be an
+		[ISO 8601 duration](\(urls.iso_8601_duration)), e.g. `PT5M` or `P1DT12H`. ISO 8601 durations with
+		years or months aren't supported, as their length depends on the date they start at.
 		"""
 
 	arguments: [
 		{
 			name:        "value"
-			description: "The string of the duration."
+			description: "The string of the duration, using the units listed for `unit`."
 			required:    true
 			type: ["string"]
 		},
@@ -29,6 +33,7 @@ remap: functions: parse_duration: {
 				m:  "Minutes (60 seconds in a minute)"
 				h:  "Hours (60 minutes in an hour)"
 				d:  "Days (24 hours in a day)"
+				w:  "Weeks (7 days in a week)"
 			}
 		},
 	]
@@ -45,5 +50,19 @@ remap: functions: parse_duration: {
 				"""#
 			return: 1.005
 		},
+		{
+			title: "Parse compound duration"
+			source: #"""
+				parse_duration!("1h30m15s", unit: "m")
+				"""#
+			return: 90.25
+		},
+		{
+			title: "Parse ISO 8601 duration"
+			source: #"""
+				parse_duration!("PT1M30S", unit: "s")
+				"""#
+			return: 90.0
+		},
 	]
 }
@@ -5,6 +5,12 @@ remap: functions: parse_groks: {
 	description: """
 		Parses the `value` using multiple [`grok`](\(urls.grok)) patterns. All patterns [listed here](\(urls.grok_patterns))
 		are supported.
+
+		Patterns also support the Datadog grok syntax, `%{MATCHER:EXTRACT:FILTER}`, with its matchers and filters,
+		so that rules written for Datadog log pipelines can be used as is.
+
+		How often each pattern is tried and matched, and how long it takes, is emitted as the `grok_rule_*` internal
+		metrics, to find patterns which never match or are slow.
 		"""
 	notices: [
 		"""
@@ -16,26 +22,84 @@ remap: functions: parse_groks: {
 	arguments: [
 		{
 			name:        "value"
-			description: "The string to parse."
+			description: "The string to parse. Bytes, which are not valid UTF-8, are preserved as is in the parsed values."
 			required:    true
 			type: ["string"]
 		},
 		{
 			name:        "patterns"
-			description: "The [Grok patterns](https://github.com/daschl/grok/tree/master/patterns), which are tried in order until the first match."
+			description: """
+				The [Grok patterns](https://github.com/daschl/grok/tree/master/patterns), which are tried in order until the first match.
+				A pattern given as an object can declare actions executed on the parsed fields after it matched:
+				`rename` (an object of old to new paths), `remove` (an array of paths) and `retain` (an array of the only paths to keep),
+				e.g. `{"pattern": "%{word:user} %{integer:tmp}", "remove": ["tmp"]}`.
+				"""
 			required:    true
 			type: ["array"]
 		},
 		{
 			name:        "aliases"
-			description: "The shared set of grok aliases that can be referenced in the patterns to simplify them."
+			description: """
+				The shared set of grok aliases that can be referenced in the patterns to simplify them.
+				An alias given as an object can carry filters applied wherever it is used with a destination,
+				before the filters of the destination, e.g. `{"_duration": {"pattern": "%{number}", "filters": "scale(1000000)"}}`.
+				"""
 			required:    false
 			default:     true
 			type: ["object"]
 		},
+		{
+			name:        "pattern_files"
+			description: """
+				Paths to files or directories with additional named patterns, in the classic grok pattern file syntax:
+				one `NAME definition` per line. These patterns are loaded once, when the program is compiled.
+				"""
+			required:    false
+			type: ["array"]
+		},
+		{
+			name:        "match_budget"
+			description: """
+				The maximum number of backtracking steps matching the `value` against each pattern may take.
+				Parsing fails once it's exceeded, instead of stalling on pathological input.
+				"""
+			required: false
+			type: ["integer"]
+		},
+		{
+			name:        "anchored"
+			description: """
+				Whether the patterns must match the whole `value` or may match a substring anywhere in it.
+				A pattern can override it when given as an object, e.g. `{"pattern": "%{word:user}", "anchored": false}`.
+				"""
+			required: false
+			default:  true
+			type: ["boolean"]
+		},
+		{
+			name:        "multiline"
+			description: """
+				Whether the dot matches new lines in all patterns, e.g. to parse a multiline stack trace with one pattern.
+				The `multilineData` pattern matches anything including new lines, e.g. `%{multilineData:error.stack}`.
+				"""
+			required: false
+			default:  false
+			type: ["boolean"]
+		},
+		{
+			name:        "keep_empty"
+			description: """
+				Whether to keep captures which matched an empty string as empty strings, instead of dropping them.
+				A single destination can opt in with the `keepEmpty` filter, e.g. `%{data:message:keepEmpty}`.
+				"""
+			required: false
+			default:  false
+			type: ["boolean"]
+		},
 	]
 	internal_failure_reasons: [
 		"`value` fails to parse using the provided `pattern`",
+		"matching the `value` exceeds the `match_budget`",
 	]
 	return: types: ["object"]
 
@@ -64,5 +128,28 @@ remap: functions: parse_groks: {
 				message:   "Hello world"
 			}
 		},
+		{
+			title: "Parse using Datadog matchers and filters"
+			source: #"""
+				parse_groks!(
+					"frank [13/Jul/2016:10:55:36] GET 200 202",
+					patterns: ["%{_user} \\[%{_date_access}\\] %{word:http.method} %{integer:http.status_code} %{_duration}"],
+					aliases: {
+						"_user": "%{notSpace:http.auth:nullIf(\"-\")}",
+						"_date_access": "%{notSpace:date_access}",
+						"_duration": "%{integer:duration:scale(1000000)}"
+					}
+				)
+				"""#
+			return: {
+				date_access: "13/Jul/2016:10:55:36"
+				duration:    202000000
+				http: {
+					auth:        "frank"
+					method:      "GET"
+					status_code: 200
+				}
+			}
+		},
 	]
 }
@@ -0,0 +1,69 @@
+package metadata
+
+remap: functions: parse_leef: {
+	category: "Parse"
+	description: """
+		Parses the `value` in LEEF(Log Event Extended Format) 1.0 or 2.0 format. Ignores everything up to the LEEF header.
+		LEEF 1.0 attributes are separated by tabs, LEEF 2.0 attributes by the delimiter declared in the header,
+		either a single character or a hex code such as `x09`, defaulting to a tab.
+		"""
+	notices: [
+		"""
+			All values are returned as strings. We recommend manually coercing values to desired types as you see fit.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to parse."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a properly formatted LEEF string",
+		"the LEEF version isn't 1.0 or 2.0",
+		"the LEEF 2.0 delimiter is invalid",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse a LEEF 1.0 message"
+			source: #"""
+				parse_leef!(
+					"LEEF:1.0|Microsoft|MSExchange|4.0 SP1|15345|src=10.50.1.1\tdst=2.10.20.20\tspt=1200"
+				)
+				"""#
+			return: {
+				"leefVersion":   "1.0"
+				"deviceVendor":  "Microsoft"
+				"deviceProduct": "MSExchange"
+				"deviceVersion": "4.0 SP1"
+				"eventId":       "15345"
+				"src":           "10.50.1.1"
+				"dst":           "2.10.20.20"
+				"spt":           "1200"
+			}
+		},
+		{
+			title: "Parse a LEEF 2.0 message with a custom delimiter"
+			source: #"""
+				parse_leef!(
+					"Jan 18 11:07:53 host LEEF:2.0|Lancope|StealthWatch|1.0|41|^|src=10.0.1.8^dst=10.0.0.5^sev=5"
+				)
+				"""#
+			return: {
+				"leefVersion":   "2.0"
+				"deviceVendor":  "Lancope"
+				"deviceProduct": "StealthWatch"
+				"deviceVersion": "1.0"
+				"eventId":       "41"
+				"src":           "10.0.1.8"
+				"dst":           "10.0.0.5"
+				"sev":           "5"
+			}
+		},
+	]
+}
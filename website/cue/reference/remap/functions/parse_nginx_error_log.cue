@@ -0,0 +1,64 @@
+package metadata
+
+remap: functions: parse_nginx_error_log: {
+	category:    "Parse"
+	description: """
+		Parses Nginx [error log](\(urls.nginx_error)) lines, extracting the severity, the process, thread and
+		connection IDs, the message and the request context (client, server, request, upstream, host and referer)
+		into separate fields.
+		"""
+	notices: [
+		"""
+			The fields of the request context are omitted in the result if they're missing in the log line.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "timestamp_format"
+			description: """
+				The [date/time format](\(urls.chrono_time_formats)) to use for encoding the timestamp. The time is parsed
+				in local time if the timestamp doesn't specify a timezone.
+				"""
+			required: false
+			default:  "%Y/%m/%d %H:%M:%S"
+			type: ["string"]
+		},
+	]
+
+	internal_failure_reasons: [
+		"`value` isn't an Nginx error log line",
+		"`timestamp_format` isn't a valid format string",
+		"The timestamp in `value` fails to parse using the provided `timestamp_format`",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse an Nginx error log line"
+			source: #"""
+				parse_nginx_error_log!(
+				    s'2021/04/01 13:02:31 [error] 31#31: *1 open() "/usr/share/nginx/html/not-found" failed (2: No such file or directory), client: 172.17.0.1, server: localhost, request: "POST /not-found HTTP/1.1", host: "localhost:8081"'
+				)
+				"""#
+			return: {
+				timestamp: "2021-04-01T13:02:31Z"
+				severity:  "error"
+				pid:       31
+				tid:       31
+				cid:       1
+				message:   "open() \"/usr/share/nginx/html/not-found\" failed (2: No such file or directory)"
+				client:    "172.17.0.1"
+				server:    "localhost"
+				request:   "POST /not-found HTTP/1.1"
+				host:      "localhost:8081"
+			}
+		},
+	]
+}
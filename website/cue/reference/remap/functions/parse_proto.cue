@@ -0,0 +1,52 @@
+package metadata
+
+remap: functions: parse_proto: {
+	category:    "Parse"
+	description: """
+		Parses the `value` as a [Protocol Buffers](\(urls.protobuf)) message of the given type, using a compiled
+		descriptor set. Enum values are returned by name, map fields as objects. Fields without a value, which in
+		proto3 includes fields set to their default value, are omitted.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The protobuf encoded message to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "desc_file"
+			description: """
+				The path to a `FileDescriptorSet` file, e.g. generated with
+				`protoc --include_imports --descriptor_set_out=events.desc events.proto`.
+				The file is read once, when the program is compiled.
+				"""
+			required: true
+			type: ["string"]
+		},
+		{
+			name:        "message_type"
+			description: "The fully qualified name of the message type, e.g. `package.Message`."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid protobuf message of the given type",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse a protobuf message"
+			source: #"""
+				parse_proto!(decode_base64!("Cgdzb21lb25lENIJ"), "events.desc", "test.Person")
+				"""#
+			return: {
+				name: "someone"
+				id:   1234
+			}
+		},
+	]
+}
@@ -19,7 +19,22 @@ remap: functions: parse_timestamp: {
 			required:    true
 			type: ["string"]
 		},
-
+		{
+			name:        "locale"
+			description: "The locale of the month and weekday names in the `value`. Abbreviations may be followed by a dot, e.g. `déc.`."
+			required:    false
+			type: ["string"]
+			enum: {
+				de: "German, e.g. `Mär`"
+				en: "English, e.g. `Mar`"
+				es: "Spanish, e.g. `mar`"
+				fr: "French, e.g. `mars`"
+				it: "Italian, e.g. `mar`"
+				nl: "Dutch, e.g. `mrt`"
+				pt: "Portuguese, e.g. `mar`"
+				sv: "Swedish, e.g. `mar`"
+			}
+		},
 	]
 	internal_failure_reasons: [
 		"`value` fails to parse using the provided `format`",
@@ -34,5 +49,12 @@ remap: functions: parse_timestamp: {
 				"""#
 			return: "2020-10-10T16:00:00Z"
 		},
+		{
+			title: "Parse timestamp with localized names"
+			source: #"""
+				parse_timestamp!("lundi 12 oct. 2020 16:00+00:00", format: "%A %d %b %Y %R %:z", locale: "fr")
+				"""#
+			return: "2020-10-12T16:00:00Z"
+		},
 	]
 }
@@ -6,6 +6,7 @@ remap: functions: redact: {
 		Redact sensitive data in `value` such as:
 
 		- [US social security card numbers](\(urls.us_social_security_number))
+		- email addresses, credit card numbers and IPv4 addresses
 		- and other forms of personally identifiable information via custom patterns
 		- (more to come!)
 
@@ -27,7 +28,7 @@ remap: functions: redact: {
 				For arrays and objects it will recurse into any nested arrays or objects. Any non-string elements will
 				be skipped.
 
-				Any redacted text will be replaced with `[REDACTED]`.
+				Any redacted text will be replaced according to the `redactor`, by default with `[REDACTED]`.
 				"""#
 			required: true
 			type: ["string", "object", "array"]
@@ -48,6 +49,10 @@ remap: functions: redact: {
 				- `pattern`: Redact text matching any regular expressions specified in the, required, `patterns`
 					key. This is the expanded form of just passing a regular expression as a filter.
 				- `us_social_security_number`: Redact US social security card numbers.
+				- `email_address`: Redact email addresses.
+				- `credit_card_number`: Redact credit card numbers of 13 to 19 digits, optionally grouped by spaces or
+					dashes, which pass the Luhn check.
+				- `ipv4_address`: Redact IPv4 addresses.
 
 				See examples for more details.
 
@@ -57,6 +62,26 @@ remap: functions: redact: {
 			required: true
 			type: ["array"]
 		},
+		{
+			name: "redactor"
+			description: #"""
+				How to redact the text matching the `filters`, either as a name or as an object with a `type` key
+				that corresponds to the name and additional keys for customizing the redactor:
+
+				- `full`: Replace the text with `[REDACTED]`.
+				- `sha256`: Replace the text with its hex encoded SHA-256 hash, so that the same text is always
+					replaced with the same pseudonym.
+				- `partial`: Replace all but the last characters of the text with `*`, e.g. to keep the last 4
+					digits of a card number. The number of characters kept is set with `keep_last` and defaults to
+					4. Text, which isn't longer than that, is replaced completely.
+				- `remove`: Remove the text.
+
+				This parameter must be a static expression.
+				"""#
+			required: false
+			default:  "full"
+			type: ["string", "object"]
+		},
 	]
 	internal_failure_reasons: []
 	return: types: ["string", "object", "array"]
@@ -79,5 +104,19 @@ remap: functions: redact: {
 				ssn:  "[REDACTED]"
 			}
 		},
+		{
+			title: "Replace email addresses with deterministic pseudonyms"
+			source: #"""
+				redact("sent by jane@example.com", filters: ["email_address"], redactor: "sha256")
+				"""#
+			return: "sent by 8c87b489ce35cf2e2f39f80e282cb2e804932a56a213983eeeb428407d43b52d"
+		},
+		{
+			title: "Keep the last digits of credit card numbers"
+			source: #"""
+				redact("card 4111 1111 1111 1111", filters: ["credit_card_number"], redactor: {"type": "partial", "keep_last": 4})
+				"""#
+			return: "card ***************1111"
+		},
 	]
 }
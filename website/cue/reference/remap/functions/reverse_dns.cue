@@ -0,0 +1,49 @@
+package metadata
+
+remap: functions: reverse_dns: {
+	category:    "IP"
+	description: """
+		Performs a reverse DNS lookup of the IP address `value`, using the system resolver.
+
+		Results, including failed lookups, are cached by the process for `cache_ttl_secs`, so that
+		the same value isn't resolved again for every event. A lookup taking longer than `timeout_ms`
+		fails; timed out lookups aren't cached.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The IP address to look up."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "timeout_ms"
+			description: "The maximum time in milliseconds to wait for the resolver."
+			required:    false
+			default:     1000
+			type: ["integer"]
+		},
+		{
+			name:        "cache_ttl_secs"
+			description: "How long in seconds results are cached. `0` disables caching."
+			required:    false
+			default:     60
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid IP address, has no host name, or the lookup timed out",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Look up the host name of an IP address"
+			source: #"""
+				reverse_dns!("8.8.8.8")
+				"""#
+			return: "dns.google"
+		},
+	]
+}
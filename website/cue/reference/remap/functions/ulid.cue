@@ -0,0 +1,25 @@
+package metadata
+
+remap: functions: ulid: {
+	category:    "Random"
+	description: """
+		Generates a time-sortable [ULID](\(urls.ulid)) string. The ULID starts with the
+		milliseconds since the Unix epoch, followed by random bits, encoded as 26 characters of
+		Crockford's base32, so that ULIDs generated in different milliseconds sort by the time
+		they were generated.
+		"""
+
+	arguments: []
+	internal_failure_reasons: []
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Create a ULID"
+			source: #"""
+				ulid()
+				"""#
+			return: "01GFA4SB4D6ZAVT8Q9MHG5Z0XW"
+		},
+	]
+}
@@ -0,0 +1,24 @@
+package metadata
+
+remap: functions: uuid_v7: {
+	category:    "Random"
+	description: """
+		Generates a time-sortable [UUIDv7](\(urls.uuidv7)) string. The UUID starts with the
+		milliseconds since the Unix epoch, followed by random bits, so that UUIDs generated in
+		different milliseconds sort by the time they were generated.
+		"""
+
+	arguments: []
+	internal_failure_reasons: []
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Create a UUIDv7"
+			source: #"""
+				uuid_v7()
+				"""#
+			return: "0183d44c-ad8d-7c5f-b0ef-ab4e2a171ba8"
+		},
+	]
+}
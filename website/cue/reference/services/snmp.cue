@@ -0,0 +1,10 @@
+package metadata
+
+services: snmp: {
+	name:     "SNMP"
+	thing:    "an \(name) agent"
+	url:      urls.snmp
+	versions: "v2c, v3"
+
+	description: "[SNMP](\(urls.snmp)) is a protocol to manage devices on IP networks, such as routers, switches and printers. Agents notify managers of events with traps and informs."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: windows_eventlog: {
+	name:     "Windows Event Log"
+	thing:    "the \(name)"
+	url:      urls.windows_event_log
+	versions: null
+
+	description: "The [Windows Event Log](\(urls.windows_event_log)) is the system of Windows that records the events of the operating system, its services and its applications, in channels such as `Application`, `Security` and `System`."
+}
@@ -63,6 +63,7 @@ urls: {
 	aws_kinesis_streams_api:                    "\(aws_docs)/kinesis/latest/APIReference/API_PutRecords.html"
 	aws_kinesis_streams_service_limits:         "\(aws_docs)/streams/latest/dev/service-sizes-and-limits.html"
 	aws_kinesis_split_shards:                   "\(aws_docs)/streams/latest/dev/kinesis-using-sdk-java-resharding-split.html"
+	aws_kinesis_resharding:                     "\(aws_docs)/streams/latest/dev/kinesis-using-sdk-java-after-resharding.html"
 	aws_prometheus:                             "\(aws_docs)/prometheus"
 	aws_regions:                                "\(aws_docs)/AmazonRDS/latest/UserGuide/Concepts.RegionsAndAvailabilityZones.html"
 	aws_s3:                                     "https://aws.amazon.com/s3/"
@@ -88,6 +89,11 @@ urls: {
 	axiom_cloud:                                "https://cloud.axiom.co"
 	azure_blob:                                 "https://azure.microsoft.com/en-us/services/storage/blobs/"
 	azure_blob_endpoints:                       "https://docs.microsoft.com/en-us/rest/api/storageservices/blob-service-rest-api"
+	azure_data_explorer:                        "https://azure.microsoft.com/en-us/products/data-explorer/"
+	azure_data_explorer_ingestion_mappings:     "https://learn.microsoft.com/en-us/azure/data-explorer/kusto/management/mappings"
+	azure_data_explorer_queued_ingestion:       "https://learn.microsoft.com/en-us/azure/data-explorer/kusto/api/netfx/about-kusto-ingest#queued-ingestion"
+	azure_event_hubs:                           "https://azure.microsoft.com/en-us/products/event-hubs/"
+	azure_event_hubs_connection_string:         "https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-get-connection-string"
 	azure_monitor:                              "https://azure.microsoft.com/en-us/services/monitor/"
 	azure_monitor_logs_endpoints:               "https://docs.microsoft.com/en-us/rest/api/monitor/"
 	base64:                                     "\(wikipedia)/wiki/Base64"
@@ -198,6 +204,8 @@ urls: {
 	gcp_authentication_api_key:                 "\(gcp)/docs/authentication/api-keys"
 	gcp_authentication_server_to_server:        "\(gcp)/docs/authentication/production"
 	gcp_authentication_service_account:         "\(gcp)/docs/authentication/production#obtaining_and_providing_service_account_credentials_manually"
+	gcp_bigquery:                               "\(gcp)/bigquery"
+	gcp_bigquery_write_api:                     "\(gcp)/bigquery/docs/write-api"
 	gcp_cloud_storage:                          "\(gcp)/storage"
 	gcp_chronicle:                              "https://chronicle.security"
 	gcp_folders:                                "\(gcp)/resource-manager/docs/creating-managing-folders"
@@ -230,11 +238,13 @@ urls: {
 	grok_debugger:                              "https://grokdebug.herokuapp.com/"
 	grok_patterns:                              "\(github)/daschl/grok/tree/master/patterns"
 	gzip:                                       "https://www.gzip.org/"
+	grpc:                                       "https://grpc.io/"
 	haproxy:                                    "https://www.haproxy.org/"
 	helm:                                       "https://helm.sh/"
 	heroku:                                     "https://www.heroku.com"
 	heroku_http_log_drain:                      "https://devcenter.heroku.com/articles/log-drains#https-drains"
 	heroku_start:                               "https://devcenter.heroku.com/start"
+	hmac:                                       "\(wikipedia)/wiki/HMAC"
 	homebrew:                                   "https://brew.sh/"
 	homebrew_services:                          "\(github)/Homebrew/homebrew-services"
 	honeycomb:                                  "https://honeycomb.io"
@@ -265,6 +275,7 @@ urls: {
 	ip_ntop:                                    "https://linux.die.net/man/3/inet_ntop"
 	ip_pton:                                    "https://linux.die.net/man/3/inet_pton"
 	iso_8601:                                   "\(wikipedia)/wiki/ISO_8601"
+	iso_8601_duration:                          "\(wikipedia)/wiki/ISO_8601#Durations"
 	iso3166_2:                                  "\(wikipedia)/wiki/ISO_3166-2"
 	issue_1694:                                 "\(vector_repo)/issues/1694"
 	journalctl:                                 "https://www.freedesktop.org/software/systemd/man/journalctl.html"
@@ -344,10 +355,13 @@ urls: {
 	mongodb:                                    "https://www.mongodb.com"
 	mongodb_command_server_status:              "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:       "https://docs.mongodb.com/manual/reference/connection-string/"
+	mysql:                                      "https://www.mysql.com/"
 	musl_builder_docker_image:                  "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
 	native_proto_schema:                        "\(vector_repo)/blob/master/lib/vector-core/proto/event.proto"
 	native_json_schema:                         "\(vector_repo)/blob/master/lib/codecs/tests/data/native_encoding/schema.cue"
+	mqtt:                                       "https://mqtt.org/"
 	nats:                                       "https://nats.io/"
+	nats_jetstream:                             "https://docs.nats.io/nats-concepts/jetstream"
 	nats_rs:                                    "\(github)/nats-io/nats.rs"
 	new_bug_report:                             "\(vector_repo)/issues/new?labels=type%3A+bug"
 	new_feature_request:                        "\(vector_repo)/issues/new?labels=type%3A+new+feature"
@@ -384,8 +398,11 @@ urls: {
 	percent_encoding_www_form_urlencoded:       "https://url.spec.whatwg.org/#application-x-www-form-urlencoded-percent-encode-set"
 	posix_acls:                                 "https://www.usenix.org/legacy/publications/library/proceedings/usenix03/tech/freenix03/full_papers/gruenbacher/gruenbacher_html/main.html"
 	postgresql:                                 "https://www.postgresql.org/"
+	postgresql_connection_uri:                  "https://www.postgresql.org/docs/current/libpq-connect.html#id-1.7.3.8.3.6"
+	postgresql_copy:                            "https://www.postgresql.org/docs/current/sql-copy.html"
 	postgresql_csvlog:                          "https://www.postgresql.org/docs/current/runtime-config-logging.html#RUNTIME-CONFIG-LOGGING-CSVLOG"
 	postgresql_matching:                        "https://www.postgresql.org/docs/current/functions-matching.html#FUNCTIONS-POSIX-REGEXP"
+	postgresql_upsert:                          "https://www.postgresql.org/docs/current/sql-insert.html#SQL-ON-CONFLICT"
 	procfs:                                     "https://en.wikipedia.org/wiki/Procfs#:~:text=The%20proc%20filesystem%20(procfs)%20is,in%20the%20kernel%20than%20traditional"
 	prometheus:                                 "https://prometheus.io/"
 	prometheus_client:                          "https://prometheus.io/docs/instrumenting/clientlibs/"
@@ -402,6 +419,7 @@ urls: {
 	prometheus_remote_write:                    "https://prometheus.io/docs/prometheus/latest/configuration/configuration/#remote_write"
 	prometheus_remote_write_protocol:           "https://docs.google.com/document/d/1LPhVRSFkGNSuU1fBd81ulhsCPR4hkSZyyBj1SZ8fWOM/edit#heading=h.n0d0vphea3fe"
 	protobuf:                                   "https://developers.google.com/protocol-buffers"
+	protobuf_descriptor_set:                    "https://developers.google.com/protocol-buffers/docs/techniques#self-description"
 	pulsar:                                     "https://pulsar.apache.org/"
 	pulsar_protocol:                            "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
 	raspbian:                                   "https://www.raspbian.org/"
@@ -425,6 +443,7 @@ urls: {
 	rhel:                                       "https://www.redhat.com/en/technologies/linux-platforms/enterprise-linux"
 	rpm:                                        "https://rpm.org/"
 	rust:                                       "https://www.rust-lang.org/"
+	rumqttc:                                    "\(github)/bytebeamio/rumqtt"
 	rust_date_time:                             "https://docs.rs/chrono/latest/chrono/struct.DateTime.html"
 	rust_grok_library:                          "\(github)/daschl/grok"
 	rust_k8s_openapi_crate:                     "https://docs.rs/k8s-openapi/"
@@ -445,12 +464,14 @@ urls: {
 	sematext_monitoring:                        "https://sematext.com/docs/monitoring/"
 	sematext_registration:                      "https://apps.sematext.com/ui/registration"
 	semver:                                     "https://semver.org/"
+	sftp:                                       "\(wikipedia)/wiki/SSH_File_Transfer_Protocol"
 	sha1:                                       "\(wikipedia)/wiki/SHA-1"
 	sha2:                                       "\(wikipedia)/wiki/SHA-2"
 	sha3:                                       "\(wikipedia)/wiki/SHA-3"
 	signal:                                     "\(wikipedia)/wiki/Signal_(IPC)"
 	snake_case:                                 "\(wikipedia)/wiki/Snake_case"
 	snappy:                                     "https://google.github.io/snappy/"
+	snmp:                                       "\(wikipedia)/wiki/Simple_Network_Management_Protocol"
 	socket:                                     "\(wikipedia)/wiki/Network_socket"
 	splunk:                                     "https://www.splunk.com"
 	splunk_hec:                                 "https://dev.splunk.com/enterprise/docs/dataapps/httpeventcollector/"
@@ -487,6 +508,7 @@ urls: {
 	tcp:                                        "\(wikipedia)/wiki/Transmission_Control_Protocol"
 	team:                                       "/community#team"
 	timber:                                     "https://timber.io"
+	timescaledb:                                "https://www.timescale.com/"
 	toml:                                       "\(github)/toml-lang/toml"
 	toml_array:                                 "\(github)/toml-lang/toml#array"
 	toml_table:                                 "\(github)/toml-lang/toml#table"
@@ -497,12 +519,14 @@ urls: {
 	uap:                                        "https://github.com/ua-parser/uap-core"
 	ubuntu:                                     "https://ubuntu.com/"
 	udp:                                        "\(wikipedia)/wiki/User_Datagram_Protocol"
+	ulid:                                       "\(github)/ulid/spec"
 	uds:                                        "\(wikipedia)/wiki/Unix_domain_socket"
 	unicode_replacement_character:              "\(wikipedia)/wiki/Specials_(Unicode_block)#Replacement_character"
 	unicode_whitespace:                         "\(wikipedia)/wiki/Unicode_character_property#Whitespace"
 	unix_timestamp:                             "\(wikipedia)/wiki/Unix_time"
 	utf8:                                       "\(wikipedia)/wiki/UTF-8"
 	uuidv4:                                     "\(wikipedia)/wiki/Universally_unique_identifier#Version_4_(random)"
+	uuidv7:                                     "https://datatracker.ietf.org/doc/html/draft-peabody-dispatch-new-uuid-format#section-5.2"
 	url:                                        "\(wikipedia)/wiki/URL"
 	us_social_security_number:                  "https://www.ssa.gov/history/ssn/geocard.html"
 	user_agent:                                 "https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/User-Agent"
@@ -632,6 +656,8 @@ urls: {
 	websocket:                                  "\(wikipedia)/wiki/WebSocket"
 	wikipedia:                                  "https://en.wikipedia.org"
 	windows:                                    "https://www.microsoft.com/en-us/windows"
+	windows_event_log:                          "https://learn.microsoft.com/en-us/windows/win32/wes/windows-event-log"
+	windows_event_log_queries:                  "https://learn.microsoft.com/en-us/windows/win32/wes/consuming-events"
 	windows_installer:                          "\(wikipedia)/wiki/Windows_Installer"
 	windows_service:                            "https://docs.microsoft.com/en-us/powershell/module/microsoft.powershell.management/new-service"
 	woothee:                                    "https://github.com/woothee/woothee"